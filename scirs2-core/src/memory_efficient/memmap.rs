@@ -16,7 +16,9 @@ use std::fs::{File, OpenOptions};
 use std::io::{Read, Write};
 use std::marker::PhantomData;
 use std::mem;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::ptr;
 use std::slice;
 use tempfile::NamedTempFile;
 
@@ -502,6 +504,162 @@ where
         Ok(array)
     }
 
+    /// Pointer to the first mapped byte, which need not be aligned for `A`
+    fn data_ptr(&self) -> Result<*const u8, CoreError> {
+        match (&self.mmap_view, &self.mmap_view_mut) {
+            (Some(view), _) => Ok(view.as_ptr()),
+            (_, Some(view)) => Ok(view.as_ptr()),
+            _ => Err(CoreError::ValidationError(
+                ErrorContext::new("Memory map is not initialized".to_string())
+                    .with_location(ErrorLocation::new(file!(), line!())),
+            )),
+        }
+    }
+
+    /// Mutable pointer to the first mapped byte, which need not be aligned for `A`
+    fn data_ptr_mut(&mut self) -> Result<*mut u8, CoreError> {
+        if self.mode == AccessMode::ReadOnly {
+            return Err(CoreError::ValidationError(
+                ErrorContext::new(
+                    "Cannot get mutable view of read-only memory-mapped array".to_string(),
+                )
+                .with_location(ErrorLocation::new(file!(), line!())),
+            ));
+        }
+
+        match &mut self.mmap_view_mut {
+            Some(view) => Ok(view.as_mut_ptr()),
+            None => Err(CoreError::ValidationError(
+                ErrorContext::new("Mutable memory map is not initialized".to_string())
+                    .with_location(ErrorLocation::new(file!(), line!())),
+            )),
+        }
+    }
+
+    /// Check that the mapped data can be borrowed as `&[A]`
+    fn check_aligned(&self, ptr: *const u8) -> Result<(), CoreError> {
+        if ptr.align_offset(mem::align_of::<A>()) != 0 {
+            return Err(CoreError::ValidationError(
+                ErrorContext::new(format!(
+                    "Memory-mapped data at byte offset {} is not aligned to {} bytes; \
+                     use read_elements or write_elements instead",
+                    self.offset,
+                    mem::align_of::<A>()
+                ))
+                .with_location(ErrorLocation::new(file!(), line!())),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that `len` elements starting at `start` lie within the array
+    fn check_range(&self, start: usize, len: usize) -> Result<(), CoreError> {
+        if start.checked_add(len).is_none_or(|end| end > self.size) {
+            return Err(CoreError::IndexError(
+                ErrorContext::new(format!(
+                    "Elements {}..{} out of bounds for memory-mapped array of {} elements",
+                    start,
+                    start.saturating_add(len),
+                    self.size
+                ))
+                .with_location(ErrorLocation::new(file!(), line!())),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Borrow the mapped data as a flat slice in row-major order without copying
+    ///
+    /// # Returns
+    ///
+    /// A slice over the memory-mapped elements
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data in the file is not aligned for `A`, which
+    /// happens when the header size or offset is not a multiple of its
+    /// alignment. [`read_elements`](Self::read_elements) works for any offset.
+    pub fn as_slice(&self) -> Result<&[A], CoreError> {
+        let ptr = self.data_ptr()?;
+        self.check_aligned(ptr)?;
+        Ok(unsafe { slice::from_raw_parts(ptr as *const A, self.size) })
+    }
+
+    /// Borrow the mapped data as a mutable flat slice in row-major order
+    ///
+    /// Unlike [`as_array_mut`](Self::as_array_mut), writes through the returned
+    /// slice go directly to the mapping and reach the file on [`flush`](Self::flush).
+    ///
+    /// # Returns
+    ///
+    /// A mutable slice over the memory-mapped elements
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the array is in read-only mode or the data in the
+    /// file is not aligned for `A`
+    pub fn as_slice_mut(&mut self) -> Result<&mut [A], CoreError> {
+        let ptr = self.data_ptr_mut()?;
+        self.check_aligned(ptr)?;
+        Ok(unsafe { slice::from_raw_parts_mut(ptr as *mut A, self.size) })
+    }
+
+    /// Copy a range of elements, in row-major order, out of the mapping
+    ///
+    /// Unlike [`as_slice`](Self::as_slice), this works whatever the alignment
+    /// of the data in the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - Flat indices of the elements to copy
+    ///
+    /// # Returns
+    ///
+    /// The elements in `range`
+    pub fn read_elements(&self, range: Range<usize>) -> Result<Vec<A>, CoreError> {
+        let len = range.end.saturating_sub(range.start);
+        self.check_range(range.start, len)?;
+        let ptr = self.data_ptr()?;
+        let mut elements = Vec::<A>::with_capacity(len);
+        // A byte copy into the aligned buffer has no alignment requirement on the source
+        unsafe {
+            ptr::copy_nonoverlapping(
+                ptr.add(range.start * mem::size_of::<A>()),
+                elements.as_mut_ptr() as *mut u8,
+                len * mem::size_of::<A>(),
+            );
+            elements.set_len(len);
+        }
+        Ok(elements)
+    }
+
+    /// Copy elements into the mapping, in row-major order, starting at flat index `start`
+    ///
+    /// Unlike [`as_slice_mut`](Self::as_slice_mut), this works whatever the
+    /// alignment of the data in the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Flat index of the first element to overwrite
+    /// * `elements` - Values to write
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the array is in read-only mode or the elements do
+    /// not fit
+    pub fn write_elements(&mut self, start: usize, elements: &[A]) -> Result<(), CoreError> {
+        self.check_range(start, elements.len())?;
+        let ptr = self.data_ptr_mut()?;
+        unsafe {
+            ptr::copy_nonoverlapping(
+                elements.as_ptr() as *const u8,
+                ptr.add(start * mem::size_of::<A>()),
+                mem::size_of_val(elements),
+            );
+        }
+        Ok(())
+    }
+
     /// Flush changes to disk if the array is writable
     ///
     /// # Returns
@@ -3,6 +3,8 @@
 //! This module re-exports all functionality from the main parallel.rs file
 //! and adds a work-stealing scheduler for more efficient thread utilization.
 
+use rayon::prelude::*;
+
 mod scheduler;
 
 pub use scheduler::{
@@ -14,3 +16,50 @@ pub use scheduler::{
 
 // Re-export the parallel_map function from the scheduler module
 pub use self::scheduler::parallel::par_map as parallel_map;
+
+/// Get the number of worker threads used by the parallel functions
+pub fn get_num_workers() -> usize {
+    rayon::current_num_threads()
+}
+
+/// Apply a fallible function to each element of a collection in parallel
+///
+/// Unlike [`parallel_map`], the function may borrow from its environment.
+///
+/// # Arguments
+///
+/// * `input` - Input collection
+/// * `f` - Function to apply to each element
+///
+/// # Returns
+///
+/// * Result containing the outputs in input order, or an error of one of the failed calls
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_core::parallel::try_parallel_map;
+///
+/// let divisor = 10;
+/// let input = vec![1, 2, 3, 4, 5];
+/// let result = try_parallel_map(&input, |&x| -> Result<i32, String> { Ok(divisor / x) });
+/// assert_eq!(result.unwrap(), vec![10, 5, 3, 2, 2]);
+///
+/// let result = try_parallel_map(&[1, 0], |&x| {
+///     if x == 0 {
+///         Err("Cannot process zero".to_string())
+///     } else {
+///         Ok(divisor / x)
+///     }
+/// });
+/// assert!(result.is_err());
+/// ```
+pub fn try_parallel_map<T, U, F, E>(input: &[T], f: F) -> Result<Vec<U>, E>
+where
+    T: Sync,
+    U: Send,
+    E: Send,
+    F: Fn(&T) -> Result<U, E> + Sync + Send,
+{
+    input.par_iter().map(f).collect()
+}
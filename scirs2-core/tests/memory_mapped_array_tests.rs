@@ -154,4 +154,25 @@ mod tests {
         // Invalid mode should return an error
         assert!("invalid".parse::<AccessMode>().is_err());
     }
+
+    #[test]
+    fn test_unaligned_memory_mapped_array() {
+        let data = Array1::<f64>::from_vec((0..10).map(|i| i as f64).collect());
+
+        // An odd byte offset leaves the mapped f64 values misaligned
+        let mut mmap = create_temp_mmap::<f64, _, _>(&data, AccessMode::ReadWrite, 3).unwrap();
+        assert!(mmap.as_slice().is_err());
+        assert!(mmap.as_slice_mut().is_err());
+
+        assert_eq!(mmap.read_elements(2..5).unwrap(), vec![2.0, 3.0, 4.0]);
+        mmap.write_elements(8, &[80.0, 90.0]).unwrap();
+        assert_eq!(mmap.read_elements(7..10).unwrap(), vec![7.0, 80.0, 90.0]);
+
+        assert!(mmap.read_elements(8..11).is_err());
+        assert!(mmap.write_elements(9, &[0.0, 0.0]).is_err());
+
+        // Aligned mappings can still be borrowed directly
+        let aligned = create_temp_mmap::<f64, _, _>(&data, AccessMode::ReadWrite, 0).unwrap();
+        assert_eq!(aligned.as_slice().unwrap()[3], 3.0);
+    }
 }
@@ -37,6 +37,10 @@ pub enum GraphError {
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
+    /// Malformed graph file or data that cannot be represented in a format
+    #[error("Format error: {0}")]
+    FormatError(String),
+
     /// Linear algebra error
     #[error("Linear algebra error: {0}")]
     LinAlgError(String),
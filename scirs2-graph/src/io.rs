@@ -1,11 +1,35 @@
 //! Input/output operations for graphs
 //!
 //! This module provides functions for reading and writing graph data
-//! in various formats.
+//! in various formats. The formats are implemented in submodules:
+//!
+//! - `edge_list`: Plain edge lists (`source target [weight]`)
+//! - `adjacency_list`: Adjacency lists (`source: target1 target2 ...`)
+//! - `matrix_market`: Matrix Market coordinate files
+//! - `graphml`: GraphML documents
+//! - `gml`: GML export
+//! - `pajek`: Pajek `.net` export
+//! - `dot`: Graphviz DOT export
+//!
+//! Node labels and edge weights are converted with their `FromStr` and
+//! `Display` implementations, so a graph written with `Graph<String, f64>`
+//! can be read back into `Graph<i32, f64>` as long as every label parses.
+
+mod adjacency_list;
+mod dot;
+mod edge_list;
+mod gml;
+mod graphml;
+mod matrix_market;
+mod pajek;
 
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::path::Path;
+use std::str::FromStr;
 
-use crate::base::{DiGraph, EdgeWeight, Graph, Node};
+use crate::base::{DiGraph, Edge, EdgeWeight, Graph, Node};
 use crate::error::{GraphError, Result};
 
 /// Supported file formats for graph I/O
@@ -19,9 +43,115 @@ pub enum GraphFormat {
     MatrixMarket,
     /// GraphML format (XML-based format for graphs)
     GraphML,
+    /// GML format (Graph Modelling Language), export only
+    Gml,
+    /// Pajek `.net` format, export only
+    Pajek,
+    /// Graphviz DOT format, export only
+    Dot,
+}
+
+/// Nodes and edges parsed from a file, before they are added to a graph
+struct GraphData<N, E> {
+    /// Nodes in file order, including isolated nodes
+    nodes: Vec<N>,
+    /// Edges as (source, target, weight)
+    edges: Vec<(N, N, E)>,
+    /// Whether the file declares its edges as undirected
+    undirected: bool,
+}
+
+impl<N, E> GraphData<N, E> {
+    fn new() -> Self {
+        GraphData {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            undirected: false,
+        }
+    }
+}
+
+/// Parse a node label
+fn parse_node<N: FromStr>(token: &str, line: usize) -> Result<N> {
+    token
+        .parse()
+        .map_err(|_| GraphError::FormatError(format!("line {}: invalid node '{}'", line, token)))
+}
+
+/// Parse an edge weight
+fn parse_weight<E: FromStr>(token: &str, line: usize) -> Result<E> {
+    token.parse().map_err(|_| {
+        GraphError::FormatError(format!("line {}: invalid edge weight '{}'", line, token))
+    })
+}
+
+/// Ensure a label can be written as a single whitespace-separated token
+fn check_token(label: &str, format: &str) -> Result<()> {
+    if label.is_empty() || label.chars().any(char::is_whitespace) {
+        return Err(GraphError::FormatError(format!(
+            "label '{}' cannot be written to {}: labels must be non-empty and contain no whitespace",
+            label, format
+        )));
+    }
+    Ok(())
+}
+
+/// Whether a value can be written without quotes in a numeric field
+fn is_numeric(token: &str) -> bool {
+    token.parse::<f64>().is_ok()
+}
+
+fn read_data<N, E>(path: &Path, format: GraphFormat, weighted: bool) -> Result<GraphData<N, E>>
+where
+    N: FromStr + Clone,
+    E: FromStr + Default,
+{
+    let input = std::fs::read_to_string(path)?;
+    match format {
+        GraphFormat::EdgeList => edge_list::read(&input, weighted),
+        GraphFormat::AdjacencyList => adjacency_list::read(&input, weighted),
+        GraphFormat::MatrixMarket => matrix_market::read(&input, weighted),
+        GraphFormat::GraphML => graphml::read(&input, weighted),
+        GraphFormat::Gml | GraphFormat::Pajek | GraphFormat::Dot => Err(GraphError::FormatError(
+            format!("{:?} is an export-only format", format),
+        )),
+    }
+}
+
+fn write_data<N, E>(
+    path: &Path,
+    format: GraphFormat,
+    nodes: &[&N],
+    edges: &[Edge<N, E>],
+    directed: bool,
+    weighted: bool,
+) -> Result<()>
+where
+    N: Node + Display,
+    E: EdgeWeight + Display,
+{
+    let mut out = BufWriter::new(File::create(path)?);
+    match format {
+        GraphFormat::EdgeList => edge_list::write(&mut out, nodes, edges, weighted)?,
+        GraphFormat::AdjacencyList => adjacency_list::write(&mut out, nodes, edges, weighted)?,
+        GraphFormat::MatrixMarket => {
+            matrix_market::write(&mut out, nodes, edges, directed, weighted)?
+        }
+        GraphFormat::GraphML => graphml::write(&mut out, nodes, edges, directed, weighted)?,
+        GraphFormat::Gml => gml::write(&mut out, nodes, edges, directed, weighted)?,
+        GraphFormat::Pajek => pajek::write(&mut out, nodes, edges, directed, weighted)?,
+        GraphFormat::Dot => dot::write(&mut out, nodes, edges, directed, weighted)?,
+    }
+    out.flush()?;
+    Ok(())
 }
 
-/// Reads a graph from a file - stubbed implementation
+/// Reads a graph from a file
+///
+/// Edge weights are parsed with `E::from_str` when `weighted` is set and
+/// default to `E::default()` otherwise. Setting `directed` declares that the
+/// file lists directed arcs: each pair of nodes is then joined by at most one
+/// edge, so opposite arcs `u -> v` and `v -> u` become a single edge.
 ///
 /// # Arguments
 /// * `path` - Path to the file
@@ -32,23 +162,47 @@ pub enum GraphFormat {
 /// # Returns
 /// * `Ok(Graph)` - The graph read from the file
 /// * `Err(GraphError)` - If there was an error reading the file
+///
+/// # Example
+/// ```no_run
+/// use scirs2_graph::io::{read_graph, GraphFormat};
+/// use scirs2_graph::Graph;
+///
+/// let graph: Graph<String, f64> =
+///     read_graph("network.txt", GraphFormat::EdgeList, true, false).unwrap();
+/// println!("{} nodes", graph.node_count());
+/// ```
 pub fn read_graph<N, E, P>(
-    _path: P,
-    _format: GraphFormat,
-    _weighted: bool,
-    _directed: bool,
+    path: P,
+    format: GraphFormat,
+    weighted: bool,
+    directed: bool,
 ) -> Result<Graph<N, E>>
 where
-    N: Node + std::fmt::Debug,
-    E: EdgeWeight + std::marker::Copy + std::fmt::Debug + std::default::Default,
+    N: Node + std::fmt::Debug + FromStr,
+    E: EdgeWeight + std::marker::Copy + std::fmt::Debug + std::default::Default + FromStr,
     P: AsRef<Path>,
 {
-    Err(GraphError::Other(
-        "Function not implemented yet".to_string(),
-    ))
+    let data = read_data::<N, E>(path.as_ref(), format, weighted)?;
+    let mut graph = Graph::new();
+    for node in data.nodes {
+        graph.add_node(node);
+    }
+    for (source, target, weight) in data.edges {
+        if directed && graph.has_edge(&source, &target) {
+            continue;
+        }
+        graph.add_edge(source, target, weight)?;
+    }
+    Ok(graph)
 }
 
-/// Reads a directed graph from a file - stubbed implementation
+/// Reads a directed graph from a file
+///
+/// Edge weights are parsed with `E::from_str` when `weighted` is set and
+/// default to `E::default()` otherwise. Edges of files that declare them
+/// undirected (symmetric Matrix Market files and GraphML documents with
+/// `edgedefault="undirected"`) are added in both directions.
 ///
 /// # Arguments
 /// * `path` - Path to the file
@@ -58,22 +212,31 @@ where
 /// # Returns
 /// * `Ok(DiGraph)` - The directed graph read from the file
 /// * `Err(GraphError)` - If there was an error reading the file
-pub fn read_digraph<N, E, P>(
-    _path: P,
-    _format: GraphFormat,
-    _weighted: bool,
-) -> Result<DiGraph<N, E>>
+pub fn read_digraph<N, E, P>(path: P, format: GraphFormat, weighted: bool) -> Result<DiGraph<N, E>>
 where
-    N: Node + std::fmt::Debug,
-    E: EdgeWeight + std::marker::Copy + std::fmt::Debug + std::default::Default,
+    N: Node + std::fmt::Debug + FromStr,
+    E: EdgeWeight + std::marker::Copy + std::fmt::Debug + std::default::Default + FromStr,
     P: AsRef<Path>,
 {
-    Err(GraphError::Other(
-        "Function not implemented yet".to_string(),
-    ))
+    let data = read_data::<N, E>(path.as_ref(), format, weighted)?;
+    let mut graph = DiGraph::new();
+    for node in data.nodes {
+        graph.add_node(node);
+    }
+    for (source, target, weight) in data.edges {
+        if data.undirected && source != target {
+            graph.add_edge(target.clone(), source.clone(), weight)?;
+        }
+        graph.add_edge(source, target, weight)?;
+    }
+    Ok(graph)
 }
 
-/// Writes a graph to a file - stubbed implementation
+/// Writes a graph to a file
+///
+/// Node labels and weights are written with their `Display` implementations.
+/// Matrix Market files identify nodes by position, so labels are not stored
+/// there; node `i` of `graph.nodes()` becomes row and column `i + 1`.
 ///
 /// # Arguments
 /// * `graph` - The graph to write
@@ -84,24 +247,43 @@ where
 /// # Returns
 /// * `Ok(())` - If the graph was written successfully
 /// * `Err(GraphError)` - If there was an error writing the file
+///
+/// # Example
+/// ```no_run
+/// use scirs2_graph::io::{write_graph, GraphFormat};
+/// use scirs2_graph::Graph;
+///
+/// let mut graph: Graph<&str, f64> = Graph::new();
+/// graph.add_edge("A", "B", 1.5).unwrap();
+/// write_graph(&graph, "network.graphml", GraphFormat::GraphML, true).unwrap();
+/// ```
 pub fn write_graph<N, E, Ix, P>(
-    _graph: &Graph<N, E, Ix>,
-    _path: P,
-    _format: GraphFormat,
-    _weighted: bool,
+    graph: &Graph<N, E, Ix>,
+    path: P,
+    format: GraphFormat,
+    weighted: bool,
 ) -> Result<()>
 where
-    N: Node + std::fmt::Debug,
-    E: EdgeWeight + std::marker::Copy + std::fmt::Debug + std::default::Default,
+    N: Node + std::fmt::Debug + Display,
+    E: EdgeWeight + std::marker::Copy + std::fmt::Debug + std::default::Default + Display,
     Ix: petgraph::graph::IndexType,
     P: AsRef<Path>,
 {
-    Err(GraphError::Other(
-        "Function not implemented yet".to_string(),
-    ))
+    write_data(
+        path.as_ref(),
+        format,
+        &graph.nodes(),
+        &graph.edges(),
+        false,
+        weighted,
+    )
 }
 
-/// Writes a directed graph to a file - stubbed implementation
+/// Writes a directed graph to a file
+///
+/// Node labels and weights are written with their `Display` implementations.
+/// Matrix Market files identify nodes by position, so labels are not stored
+/// there; node `i` of `graph.nodes()` becomes row and column `i + 1`.
 ///
 /// # Arguments
 /// * `graph` - The directed graph to write
@@ -113,28 +295,262 @@ where
 /// * `Ok(())` - If the graph was written successfully
 /// * `Err(GraphError)` - If there was an error writing the file
 pub fn write_digraph<N, E, Ix, P>(
-    _graph: &DiGraph<N, E, Ix>,
-    _path: P,
-    _format: GraphFormat,
-    _weighted: bool,
+    graph: &DiGraph<N, E, Ix>,
+    path: P,
+    format: GraphFormat,
+    weighted: bool,
 ) -> Result<()>
 where
-    N: Node + std::fmt::Debug,
-    E: EdgeWeight + std::marker::Copy + std::fmt::Debug + std::default::Default,
+    N: Node + std::fmt::Debug + Display,
+    E: EdgeWeight + std::marker::Copy + std::fmt::Debug + std::default::Default + Display,
     Ix: petgraph::graph::IndexType,
     P: AsRef<Path>,
 {
-    Err(GraphError::Other(
-        "Function not implemented yet".to_string(),
-    ))
+    write_data(
+        path.as_ref(),
+        format,
+        &graph.nodes(),
+        &graph.edges(),
+        true,
+        weighted,
+    )
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn sample_graph() -> Graph<String, f64> {
+        let mut graph = Graph::new();
+        graph
+            .add_edge("a".to_string(), "b".to_string(), 1.5)
+            .unwrap();
+        graph
+            .add_edge("b".to_string(), "c".to_string(), 2.0)
+            .unwrap();
+        graph
+            .add_edge("c".to_string(), "a".to_string(), -0.5)
+            .unwrap();
+        graph.add_node("isolated".to_string());
+        graph
+    }
+
+    fn sample_digraph() -> DiGraph<i32, f64> {
+        let mut graph = DiGraph::new();
+        graph.add_edge(0, 1, 1.0).unwrap();
+        graph.add_edge(1, 0, 3.0).unwrap();
+        graph.add_edge(1, 2, 2.5).unwrap();
+        graph.add_node(3);
+        graph
+    }
+
+    #[test]
+    fn test_graph_roundtrip() {
+        let dir = tempdir().unwrap();
+        let graph = sample_graph();
+
+        for format in [
+            GraphFormat::EdgeList,
+            GraphFormat::AdjacencyList,
+            GraphFormat::GraphML,
+        ] {
+            let path = dir.path().join(format!("{:?}", format));
+            write_graph(&graph, &path, format, true).unwrap();
+            let read: Graph<String, f64> = read_graph(&path, format, true, false).unwrap();
+
+            assert_eq!(read.node_count(), 4, "{:?}", format);
+            assert_eq!(read.edge_count(), 3, "{:?}", format);
+            assert!(read.has_node(&"isolated".to_string()));
+            assert_eq!(
+                read.edge_weight(&"b".to_string(), &"a".to_string())
+                    .unwrap(),
+                1.5
+            );
+            assert_eq!(
+                read.edge_weight(&"a".to_string(), &"c".to_string())
+                    .unwrap(),
+                -0.5
+            );
+        }
+    }
+
+    #[test]
+    fn test_digraph_roundtrip() {
+        let dir = tempdir().unwrap();
+        let graph = sample_digraph();
+
+        for format in [
+            GraphFormat::EdgeList,
+            GraphFormat::AdjacencyList,
+            GraphFormat::MatrixMarket,
+            GraphFormat::GraphML,
+        ] {
+            let path = dir.path().join(format!("{:?}", format));
+            write_digraph(&graph, &path, format, true).unwrap();
+            let read: DiGraph<i32, f64> = read_digraph(&path, format, true).unwrap();
+
+            assert_eq!(read.node_count(), 4, "{:?}", format);
+            assert_eq!(read.edge_count(), 3, "{:?}", format);
+            assert_eq!(read.edge_weight(&0, &1).unwrap(), 1.0);
+            assert_eq!(read.edge_weight(&1, &0).unwrap(), 3.0);
+            assert_eq!(read.edge_weight(&1, &2).unwrap(), 2.5);
+            assert!(!read.has_edge(&2, &1));
+        }
+    }
+
+    #[test]
+    fn test_unweighted_and_merged_arcs() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("edges.txt");
+        std::fs::write(&path, "# comment\n1 2\n2 1\n2 3\n\n4\n").unwrap();
+
+        let graph: Graph<u32, f64> = read_graph(&path, GraphFormat::EdgeList, false, true).unwrap();
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.edge_weight(&1, &2).unwrap(), 0.0);
+
+        let graph: Graph<u32, f64> =
+            read_graph(&path, GraphFormat::EdgeList, false, false).unwrap();
+        assert_eq!(graph.edge_count(), 3);
+
+        assert!(read_graph::<u32, f64, _>(&path, GraphFormat::EdgeList, true, false).is_err());
+    }
 
     #[test]
-    fn test_stub_implementation() {
-        // Just a placeholder to ensure the tests compile
-        // This test passes trivially
+    fn test_symmetric_matrix_market() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("graph.mtx");
+        std::fs::write(
+            &path,
+            "%%MatrixMarket matrix coordinate integer symmetric\n% comment\n3 3 2\n2 1 4\n3 2 5\n",
+        )
+        .unwrap();
+
+        let graph: Graph<usize, i64> =
+            read_graph(&path, GraphFormat::MatrixMarket, true, false).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.edge_weight(&0, &1).unwrap(), 4);
+
+        let digraph: DiGraph<usize, i64> =
+            read_digraph(&path, GraphFormat::MatrixMarket, true).unwrap();
+        assert_eq!(digraph.edge_count(), 4);
+        assert_eq!(digraph.edge_weight(&2, &1).unwrap(), 5);
+        assert_eq!(digraph.edge_weight(&1, &2).unwrap(), 5);
+
+        // Undirected graphs are written as symmetric matrices
+        write_graph(&graph, &path, GraphFormat::MatrixMarket, true).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("%%MatrixMarket matrix coordinate real symmetric"));
+        let read: Graph<usize, i64> =
+            read_graph(&path, GraphFormat::MatrixMarket, true, false).unwrap();
+        assert_eq!(read.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_skew_symmetric_matrix_market() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("graph.mtx");
+        std::fs::write(
+            &path,
+            "%%MatrixMarket matrix coordinate real skew-symmetric\n3 3 2\n2 1 1.5\n3 1 -2\n",
+        )
+        .unwrap();
+
+        let digraph: DiGraph<usize, f64> =
+            read_digraph(&path, GraphFormat::MatrixMarket, true).unwrap();
+        assert_eq!(digraph.edge_count(), 4);
+        assert_eq!(digraph.edge_weight(&1, &0).unwrap(), 1.5);
+        assert_eq!(digraph.edge_weight(&0, &1).unwrap(), -1.5);
+        assert_eq!(digraph.edge_weight(&2, &0).unwrap(), -2.0);
+        assert_eq!(digraph.edge_weight(&0, &2).unwrap(), 2.0);
+
+        // Pattern matrices cannot be skew-symmetric
+        std::fs::write(
+            &path,
+            "%%MatrixMarket matrix coordinate pattern skew-symmetric\n2 2 1\n2 1\n",
+        )
+        .unwrap();
+        assert!(read_digraph::<usize, f64, _>(&path, GraphFormat::MatrixMarket, false).is_err());
+    }
+
+    #[test]
+    fn test_graphml_from_other_tools() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("graph.graphml");
+        std::fs::write(
+            &path,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- written by another tool -->
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="node" attr.name="color" attr.type="string"/>
+  <key id="d1" for="edge" attr.name="weight" attr.type="double">
+    <default>1.0</default>
+  </key>
+  <graph id="G" edgedefault="undirected">
+    <node id="n&amp;0"><data key="d0">red</data></node>
+    <node id="n1"/>
+    <node id="n2"/>
+    <edge source="n&amp;0" target="n1"><data key="d1">2.5</data></edge>
+    <edge source="n1" target="n2"/>
+  </graph>
+</graphml>
+"#,
+        )
+        .unwrap();
+
+        let graph: Graph<String, f64> =
+            read_graph(&path, GraphFormat::GraphML, true, false).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(
+            graph
+                .edge_weight(&"n&0".to_string(), &"n1".to_string())
+                .unwrap(),
+            2.5
+        );
+        assert_eq!(
+            graph
+                .edge_weight(&"n1".to_string(), &"n2".to_string())
+                .unwrap(),
+            1.0
+        );
+
+        let digraph: DiGraph<String, f64> =
+            read_digraph(&path, GraphFormat::GraphML, true).unwrap();
+        assert_eq!(digraph.edge_count(), 4);
+    }
+
+    #[test]
+    fn test_export_formats() {
+        let dir = tempdir().unwrap();
+        let graph = sample_graph();
+        let digraph = sample_digraph();
+
+        let path = dir.path().join("graph.gml");
+        write_graph(&graph, &path, GraphFormat::Gml, true).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("directed 0"));
+        assert!(text.contains("label \"isolated\""));
+        assert!(text.contains("weight 1.5"));
+        assert!(read_graph::<String, f64, _>(&path, GraphFormat::Gml, true, false).is_err());
+
+        let path = dir.path().join("graph.net");
+        write_digraph(&digraph, &path, GraphFormat::Pajek, true).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("*Vertices 4\n1 \"0\"\n"));
+        assert!(text.contains("*Arcs\n1 2 1\n2 1 3\n2 3 2.5\n"));
+
+        let path = dir.path().join("graph.dot");
+        write_graph(&graph, &path, GraphFormat::Dot, false).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("graph {\n"));
+        assert!(text.contains("  \"a\" -- \"b\";\n"));
+        assert!(!text.contains("weight"));
+
+        write_digraph(&digraph, &path, GraphFormat::Dot, true).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("digraph {\n"));
+        assert!(text.contains("  \"1\" -> \"2\" [weight=2.5];\n"));
     }
 }
@@ -0,0 +1,94 @@
+//! Adjacency list format
+//!
+//! One line per node as `source: target1 target2 ...`. With weights, each
+//! target is written as `target:weight`. The colon after the source may be
+//! omitted, which also accepts the adjacency lists written by NetworkX. Blank
+//! lines and lines starting with `#` or `%` are ignored.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::Write;
+use std::str::FromStr;
+
+use super::{check_token, parse_node, parse_weight, GraphData};
+use crate::base::{Edge, EdgeWeight, Node};
+use crate::error::{GraphError, Result};
+
+/// Parse an adjacency list
+pub(super) fn read<N, E>(input: &str, weighted: bool) -> Result<GraphData<N, E>>
+where
+    N: FromStr + Clone,
+    E: FromStr + Default,
+{
+    let mut data = GraphData::new();
+    for (i, line) in input.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('%') {
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        let first = tokens.next().unwrap_or_default();
+        let source: N = parse_node(first.strip_suffix(':').unwrap_or(first), line_no)?;
+        data.nodes.push(source.clone());
+
+        for token in tokens {
+            let (target, weight) = if weighted {
+                let (target, weight) = token.rsplit_once(':').ok_or_else(|| {
+                    GraphError::FormatError(format!(
+                        "line {}: expected 'target:weight', found '{}'",
+                        line_no, token
+                    ))
+                })?;
+                (target, parse_weight(weight, line_no)?)
+            } else {
+                (token, E::default())
+            };
+            data.edges
+                .push((source.clone(), parse_node(target, line_no)?, weight));
+        }
+    }
+    Ok(data)
+}
+
+/// Write an adjacency list with one line per node
+///
+/// Every edge is listed once, under its source node.
+pub(super) fn write<W, N, E>(
+    out: &mut W,
+    nodes: &[&N],
+    edges: &[Edge<N, E>],
+    weighted: bool,
+) -> Result<()>
+where
+    W: Write,
+    N: Node + Display,
+    E: EdgeWeight + Display,
+{
+    let mut adjacency: HashMap<&N, Vec<&Edge<N, E>>> = HashMap::new();
+    for edge in edges {
+        adjacency.entry(&edge.source).or_default().push(edge);
+    }
+
+    for node in nodes {
+        let label = node.to_string();
+        check_token(&label, "an adjacency list")?;
+        if label.contains(':') {
+            return Err(GraphError::FormatError(format!(
+                "label '{}' cannot be written to an adjacency list: labels must not contain ':'",
+                label
+            )));
+        }
+        write!(out, "{}:", label)?;
+        for edge in adjacency.get(node).map(Vec::as_slice).unwrap_or_default() {
+            if weighted {
+                write!(out, " {}:{}", edge.target, edge.weight)?;
+            } else {
+                write!(out, " {}", edge.target)?;
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
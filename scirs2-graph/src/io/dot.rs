@@ -0,0 +1,63 @@
+//! Graphviz DOT export
+//!
+//! Every node is declared on its own line so that isolated nodes are kept,
+//! followed by one statement per edge. Weights are written as the `weight`
+//! edge attribute.
+
+use std::fmt::Display;
+use std::io::Write;
+
+use super::is_numeric;
+use crate::base::{Edge, EdgeWeight, Node};
+use crate::error::Result;
+
+/// Quote a DOT identifier
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Write a DOT graph
+pub(super) fn write<W, N, E>(
+    out: &mut W,
+    nodes: &[&N],
+    edges: &[Edge<N, E>],
+    directed: bool,
+    weighted: bool,
+) -> Result<()>
+where
+    W: Write,
+    N: Node + Display,
+    E: EdgeWeight + Display,
+{
+    let (keyword, connector) = if directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+
+    writeln!(out, "{} {{", keyword)?;
+    for node in nodes {
+        writeln!(out, "  {};", quote(&node.to_string()))?;
+    }
+    for edge in edges {
+        let source = quote(&edge.source.to_string());
+        let target = quote(&edge.target.to_string());
+        if weighted {
+            let weight = edge.weight.to_string();
+            let weight = if is_numeric(&weight) {
+                weight
+            } else {
+                quote(&weight)
+            };
+            writeln!(
+                out,
+                "  {} {} {} [weight={}];",
+                source, connector, target, weight
+            )?;
+        } else {
+            writeln!(out, "  {} {} {};", source, connector, target)?;
+        }
+    }
+    writeln!(out, "}}")?;
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! Edge list format
+//!
+//! One edge per line as `source target [weight]`, separated by whitespace.
+//! A line holding a single label declares an isolated node. Blank lines and
+//! lines starting with `#` or `%` are ignored.
+
+use std::fmt::Display;
+use std::io::Write;
+use std::str::FromStr;
+
+use super::{check_token, parse_node, parse_weight, GraphData};
+use crate::base::{Edge, EdgeWeight, Node};
+use crate::error::{GraphError, Result};
+
+/// Parse an edge list
+pub(super) fn read<N, E>(input: &str, weighted: bool) -> Result<GraphData<N, E>>
+where
+    N: FromStr,
+    E: FromStr + Default,
+{
+    let mut data = GraphData::new();
+    for (i, line) in input.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('%') {
+            continue;
+        }
+
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.len() {
+            1 => data.nodes.push(parse_node(tokens[0], line_no)?),
+            2 | 3 => {
+                let source = parse_node(tokens[0], line_no)?;
+                let target = parse_node(tokens[1], line_no)?;
+                let weight = match (weighted, tokens.get(2)) {
+                    (true, Some(token)) => parse_weight(token, line_no)?,
+                    (true, None) => {
+                        return Err(GraphError::FormatError(format!(
+                            "line {}: edge has no weight",
+                            line_no
+                        )))
+                    }
+                    (false, _) => E::default(),
+                };
+                data.edges.push((source, target, weight));
+            }
+            _ => {
+                return Err(GraphError::FormatError(format!(
+                    "line {}: expected 'source target [weight]'",
+                    line_no
+                )))
+            }
+        }
+    }
+    Ok(data)
+}
+
+/// Write an edge list, listing nodes without edges on their own line
+pub(super) fn write<W, N, E>(
+    out: &mut W,
+    nodes: &[&N],
+    edges: &[Edge<N, E>],
+    weighted: bool,
+) -> Result<()>
+where
+    W: Write,
+    N: Node + Display,
+    E: EdgeWeight + Display,
+{
+    let mut connected = std::collections::HashSet::new();
+    for edge in edges {
+        let (source, target) = (edge.source.to_string(), edge.target.to_string());
+        check_token(&source, "an edge list")?;
+        check_token(&target, "an edge list")?;
+        if weighted {
+            writeln!(out, "{} {} {}", source, target, edge.weight)?;
+        } else {
+            writeln!(out, "{} {}", source, target)?;
+        }
+        connected.insert(&edge.source);
+        connected.insert(&edge.target);
+    }
+    for node in nodes {
+        if !connected.contains(node) {
+            let label = node.to_string();
+            check_token(&label, "an edge list")?;
+            writeln!(out, "{}", label)?;
+        }
+    }
+    Ok(())
+}
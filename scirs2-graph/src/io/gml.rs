@@ -0,0 +1,70 @@
+//! GML export
+//!
+//! Writes the Graph Modelling Language format read by NetworkX, Gephi and
+//! Cytoscape. Nodes get consecutive integer ids and keep their label in the
+//! `label` attribute.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::Write;
+
+use super::is_numeric;
+use crate::base::{Edge, EdgeWeight, Node};
+use crate::error::Result;
+
+/// Quote a GML string, escaping characters that GML does not allow in strings
+fn quote(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("&quot;"),
+            '&' => out.push_str("&amp;"),
+            c if c.is_ascii() => out.push(c),
+            c => out.push_str(&format!("&#{};", c as u32)),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Write a GML document
+pub(super) fn write<W, N, E>(
+    out: &mut W,
+    nodes: &[&N],
+    edges: &[Edge<N, E>],
+    directed: bool,
+    weighted: bool,
+) -> Result<()>
+where
+    W: Write,
+    N: Node + Display,
+    E: EdgeWeight + Display,
+{
+    let ids: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+    writeln!(out, "graph [")?;
+    writeln!(out, "  directed {}", directed as u8)?;
+    for (id, node) in nodes.iter().enumerate() {
+        writeln!(out, "  node [")?;
+        writeln!(out, "    id {}", id)?;
+        writeln!(out, "    label {}", quote(&node.to_string()))?;
+        writeln!(out, "  ]")?;
+    }
+    for edge in edges {
+        writeln!(out, "  edge [")?;
+        writeln!(out, "    source {}", ids[&edge.source])?;
+        writeln!(out, "    target {}", ids[&edge.target])?;
+        if weighted {
+            let weight = edge.weight.to_string();
+            if is_numeric(&weight) {
+                writeln!(out, "    weight {}", weight)?;
+            } else {
+                writeln!(out, "    weight {}", quote(&weight))?;
+            }
+        }
+        writeln!(out, "  ]")?;
+    }
+    writeln!(out, "]")?;
+    Ok(())
+}
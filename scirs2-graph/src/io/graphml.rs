@@ -0,0 +1,475 @@
+//! GraphML format
+//!
+//! Nodes are identified by their `id` attribute and edge weights are stored
+//! in a `<data>` element for the edge key named `weight`. Other keys, ports,
+//! hyperedges and nested graphs are ignored when reading; the edges of nested
+//! graphs are added to the top-level graph.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::Write;
+use std::str::FromStr;
+
+use super::{parse_node, parse_weight, GraphData};
+use crate::base::{Edge, EdgeWeight, Node};
+use crate::error::{GraphError, Result};
+
+/// Piece of an XML document
+enum Token<'a> {
+    /// Start tag with its attributes; `empty` for self-closing tags
+    Start {
+        name: &'a str,
+        attributes: Vec<(&'a str, String)>,
+        empty: bool,
+    },
+    /// End tag
+    End(&'a str),
+    /// Character data with entities resolved
+    Text(String),
+}
+
+/// Minimal XML tokenizer covering the constructs used by GraphML files
+struct Tokenizer<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+fn xml_error<T>(tokenizer: &Tokenizer, message: &str) -> Result<T> {
+    Err(GraphError::FormatError(format!(
+        "line {}: {}",
+        tokenizer.line(),
+        message
+    )))
+}
+
+/// Strip the namespace prefix of a tag or attribute name
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Resolve XML character and entity references
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let resolved = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                let code = if let Some(hex) = entity.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    dec.parse().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32)
+            }
+        };
+        match resolved {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Escape text for use in XML content and attribute values
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(input: &'a str) -> Self {
+        Tokenizer { input, pos: 0 }
+    }
+
+    /// Line number of the current position, for error messages
+    fn line(&self) -> usize {
+        self.input[..self.pos].matches('\n').count() + 1
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Advance past `terminator`, returning the text before it
+    fn take_until(&mut self, terminator: &str) -> Result<&'a str> {
+        match self.rest().find(terminator) {
+            Some(end) => {
+                let text = &self.rest()[..end];
+                self.pos += end + terminator.len();
+                Ok(text)
+            }
+            None => xml_error(
+                self,
+                &format!("unterminated markup, expected '{}'", terminator),
+            ),
+        }
+    }
+
+    /// Read a tag or attribute name
+    fn name(&mut self) -> &'a str {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token<'a>>> {
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Ok(None);
+            }
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.pos += end;
+                return Ok(Some(Token::Text(unescape(&rest[..end]))));
+            }
+
+            if rest.starts_with("<!--") {
+                self.take_until("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                return Ok(Some(Token::Text(self.take_until("]]>")?.to_string())));
+            } else if rest.starts_with("<?") {
+                self.take_until("?>")?;
+            } else if rest.starts_with("<!") {
+                self.take_until(">")?;
+            } else if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.take_until(">")?.trim();
+                return Ok(Some(Token::End(local_name(name))));
+            } else {
+                self.pos += 1;
+                return self.start_tag().map(Some);
+            }
+        }
+    }
+
+    fn start_tag(&mut self) -> Result<Token<'a>> {
+        let name = local_name(self.name());
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(Token::Start {
+                    name,
+                    attributes,
+                    empty: true,
+                });
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                return Ok(Token::Start {
+                    name,
+                    attributes,
+                    empty: false,
+                });
+            }
+            if rest.is_empty() {
+                return xml_error(self, "unterminated start tag");
+            }
+
+            let attribute = local_name(self.name());
+            if attribute.is_empty() {
+                return xml_error(self, "invalid attribute");
+            }
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return xml_error(self, &format!("attribute '{}' has no value", attribute));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return xml_error(self, "attribute values must be quoted"),
+            };
+            self.pos += 1;
+            let value = self.take_until(if quote == '"' { "\"" } else { "'" })?;
+            attributes.push((attribute, unescape(value)));
+        }
+    }
+}
+
+fn attribute<'b>(attributes: &'b [(&str, String)], name: &str) -> Option<&'b str> {
+    attributes
+        .iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// What the text currently being read belongs to
+enum Capture {
+    None,
+    /// Default value of a key
+    Default(String),
+    /// Weight of the current edge
+    Weight,
+}
+
+/// Edge being read: source, target and weight text
+type PendingEdge = (String, String, Option<String>);
+
+/// Parse a GraphML document
+pub(super) fn read<N, E>(input: &str, weighted: bool) -> Result<GraphData<N, E>>
+where
+    N: FromStr,
+    E: FromStr + Default,
+{
+    let mut tokenizer = Tokenizer::new(input);
+    let mut data = GraphData::new();
+
+    // Key id -> (domain, attribute name) and key id -> default value
+    let mut keys: HashMap<String, (String, String)> = HashMap::new();
+    let mut defaults: HashMap<String, String> = HashMap::new();
+    let mut weight_key: Option<Option<String>> = None;
+
+    let mut current_key: Option<String> = None;
+    let mut edge: Option<PendingEdge> = None;
+    let mut capture = Capture::None;
+    let mut text = String::new();
+    let mut seen_graph = false;
+    let mut seen_root = false;
+
+    while let Some(token) = tokenizer.next_token()? {
+        let line = tokenizer.line();
+        match token {
+            Token::Start {
+                name,
+                attributes,
+                empty,
+            } => match name {
+                "graphml" => seen_root = true,
+                "key" => {
+                    let id = attribute(&attributes, "id").unwrap_or_default().to_string();
+                    let domain = attribute(&attributes, "for").unwrap_or("all").to_string();
+                    let attr_name = attribute(&attributes, "attr.name")
+                        .unwrap_or_default()
+                        .to_string();
+                    keys.insert(id.clone(), (domain, attr_name));
+                    if !empty {
+                        current_key = Some(id);
+                    }
+                }
+                "default" => {
+                    if let Some(id) = &current_key {
+                        capture = Capture::Default(id.clone());
+                        text.clear();
+                    }
+                }
+                "graph" if !seen_graph => {
+                    seen_graph = true;
+                    data.undirected = attribute(&attributes, "edgedefault") == Some("undirected");
+                }
+                "node" => {
+                    let id = attribute(&attributes, "id").ok_or_else(|| {
+                        GraphError::FormatError(format!("line {}: node without id", line))
+                    })?;
+                    data.nodes.push(parse_node(id, line)?);
+                }
+                "edge" => {
+                    let (Some(source), Some(target)) = (
+                        attribute(&attributes, "source"),
+                        attribute(&attributes, "target"),
+                    ) else {
+                        return Err(GraphError::FormatError(format!(
+                            "line {}: edge without source or target",
+                            line
+                        )));
+                    };
+                    let weight_key = weight_key.get_or_insert_with(|| find_weight_key(&keys));
+                    let pending = (source.to_string(), target.to_string(), None);
+                    if empty {
+                        finish_edge(pending, weight_key, &defaults, weighted, line, &mut data)?;
+                    } else {
+                        edge = Some(pending);
+                    }
+                }
+                "data" => {
+                    let key = attribute(&attributes, "key");
+                    let is_weight = edge.is_some()
+                        && key.is_some()
+                        && weight_key.as_ref().and_then(|k| k.as_deref()) == key;
+                    if is_weight && !empty {
+                        capture = Capture::Weight;
+                        text.clear();
+                    }
+                }
+                _ => {}
+            },
+            Token::Text(t) => {
+                if !matches!(capture, Capture::None) {
+                    text.push_str(&t);
+                }
+            }
+            Token::End(name) => match name {
+                "default" => {
+                    if let Capture::Default(id) = std::mem::replace(&mut capture, Capture::None) {
+                        defaults.insert(id, text.clone());
+                    }
+                }
+                "key" => current_key = None,
+                "data" => {
+                    if let (Capture::Weight, Some(pending)) = (&capture, edge.as_mut()) {
+                        pending.2 = Some(text.clone());
+                        capture = Capture::None;
+                    }
+                }
+                "edge" => {
+                    if let Some(pending) = edge.take() {
+                        let weight_key = weight_key.get_or_insert(None);
+                        finish_edge(pending, weight_key, &defaults, weighted, line, &mut data)?;
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    if !seen_root {
+        return Err(GraphError::FormatError(
+            "not a GraphML document: missing <graphml> element".to_string(),
+        ));
+    }
+    Ok(data)
+}
+
+/// Add a completely read edge to the graph data
+fn finish_edge<N, E>(
+    edge: PendingEdge,
+    weight_key: &Option<String>,
+    defaults: &HashMap<String, String>,
+    weighted: bool,
+    line: usize,
+    data: &mut GraphData<N, E>,
+) -> Result<()>
+where
+    N: FromStr,
+    E: FromStr + Default,
+{
+    let (source, target, weight_text) = edge;
+    let weight = if weighted {
+        let text = weight_text
+            .or_else(|| weight_key.as_ref().and_then(|k| defaults.get(k).cloned()))
+            .ok_or_else(|| {
+                GraphError::FormatError(format!(
+                    "line {}: edge {} - {} has no weight",
+                    line, source, target
+                ))
+            })?;
+        parse_weight(text.trim(), line)?
+    } else {
+        E::default()
+    };
+    data.edges.push((
+        parse_node(&source, line)?,
+        parse_node(&target, line)?,
+        weight,
+    ));
+    Ok(())
+}
+
+/// Find the key holding edge weights
+fn find_weight_key(keys: &HashMap<String, (String, String)>) -> Option<String> {
+    let for_edges = |domain: &str| domain == "edge" || domain == "all";
+    keys.iter()
+        .find(|(_, (domain, name))| for_edges(domain) && name == "weight")
+        .or_else(|| {
+            keys.iter()
+                .find(|(id, (domain, _))| for_edges(domain) && id.as_str() == "weight")
+        })
+        .map(|(id, _)| id.clone())
+}
+
+/// Write a GraphML document
+pub(super) fn write<W, N, E>(
+    out: &mut W,
+    nodes: &[&N],
+    edges: &[Edge<N, E>],
+    directed: bool,
+    weighted: bool,
+) -> Result<()>
+where
+    W: Write,
+    N: Node + Display,
+    E: EdgeWeight + Display,
+{
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd">"#
+    )?;
+    if weighted {
+        writeln!(
+            out,
+            r#"  <key id="weight" for="edge" attr.name="weight" attr.type="double"/>"#
+        )?;
+    }
+    writeln!(
+        out,
+        r#"  <graph id="G" edgedefault="{}">"#,
+        if directed { "directed" } else { "undirected" }
+    )?;
+    for node in nodes {
+        writeln!(out, r#"    <node id="{}"/>"#, escape(&node.to_string()))?;
+    }
+    for edge in edges {
+        let source = escape(&edge.source.to_string());
+        let target = escape(&edge.target.to_string());
+        if weighted {
+            writeln!(
+                out,
+                r#"    <edge source="{}" target="{}"><data key="weight">{}</data></edge>"#,
+                source,
+                target,
+                escape(&edge.weight.to_string())
+            )?;
+        } else {
+            writeln!(
+                out,
+                r#"    <edge source="{}" target="{}"/>"#,
+                source, target
+            )?;
+        }
+    }
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</graphml>")?;
+    Ok(())
+}
@@ -0,0 +1,215 @@
+//! Matrix Market coordinate format
+//!
+//! The graph is stored as its sparse adjacency matrix. Rows and columns are
+//! 1-based node positions; when reading, node `i` is labelled by parsing the
+//! 0-based index `i - 1`. Undirected graphs are written as symmetric matrices
+//! holding the lower triangle, and unweighted graphs use the `pattern` field.
+//! Skew-symmetric files are read as directed, with each mirrored entry
+//! carrying the negated value.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::Write;
+use std::str::FromStr;
+
+use super::{parse_node, parse_weight, GraphData};
+use crate::base::{Edge, EdgeWeight, Node};
+use crate::error::{GraphError, Result};
+
+/// Parse a Matrix Market coordinate file
+pub(super) fn read<N, E>(input: &str, weighted: bool) -> Result<GraphData<N, E>>
+where
+    N: FromStr,
+    E: FromStr + Default,
+{
+    let mut lines = input.lines().enumerate();
+
+    let (_, banner) = lines
+        .next()
+        .ok_or_else(|| GraphError::FormatError("empty Matrix Market file".to_string()))?;
+    let banner: Vec<String> = banner
+        .split_whitespace()
+        .map(|t| t.to_ascii_lowercase())
+        .collect();
+    if banner.len() != 5 || banner[0] != "%%matrixmarket" || banner[1] != "matrix" {
+        return Err(GraphError::FormatError(
+            "line 1: missing '%%MatrixMarket matrix' header".to_string(),
+        ));
+    }
+    if banner[2] != "coordinate" {
+        return Err(GraphError::FormatError(format!(
+            "line 1: unsupported Matrix Market format '{}', only 'coordinate' is supported",
+            banner[2]
+        )));
+    }
+    let pattern = match banner[3].as_str() {
+        "pattern" => true,
+        "real" | "integer" => false,
+        other => {
+            return Err(GraphError::FormatError(format!(
+                "line 1: unsupported Matrix Market field '{}'",
+                other
+            )))
+        }
+    };
+    if pattern && weighted {
+        return Err(GraphError::FormatError(
+            "line 1: pattern matrices have no edge weights".to_string(),
+        ));
+    }
+    let (symmetric, skew) = match banner[4].as_str() {
+        "general" => (false, false),
+        "symmetric" => (true, false),
+        "skew-symmetric" if !pattern => (false, true),
+        other => {
+            return Err(GraphError::FormatError(format!(
+                "line 1: unsupported Matrix Market symmetry '{}'",
+                other
+            )))
+        }
+    };
+
+    let mut data = GraphData::new();
+    data.undirected = symmetric;
+
+    // Skip comments up to the size line
+    let mut size = None;
+    for (i, line) in lines.by_ref() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        let numbers: Vec<usize> = line
+            .split_whitespace()
+            .map(|t| t.parse())
+            .collect::<std::result::Result<_, _>>()
+            .map_err(|_| GraphError::FormatError(format!("line {}: invalid size line", i + 1)))?;
+        if numbers.len() != 3 {
+            return Err(GraphError::FormatError(format!(
+                "line {}: expected 'rows columns entries'",
+                i + 1
+            )));
+        }
+        size = Some((numbers[0], numbers[1], numbers[2]));
+        break;
+    }
+    let (rows, cols, entries) =
+        size.ok_or_else(|| GraphError::FormatError("missing Matrix Market size line".to_string()))?;
+    let n = rows.max(cols);
+
+    for index in 0..n {
+        let label = index.to_string();
+        let node = label.parse().map_err(|_| {
+            GraphError::FormatError(format!("node index {} is not a valid node label", label))
+        })?;
+        data.nodes.push(node);
+    }
+
+    let mut found = 0;
+    for (i, line) in lines {
+        let line_no = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.len() < 2 {
+            return Err(GraphError::FormatError(format!(
+                "line {}: expected 'row column [value]'",
+                line_no
+            )));
+        }
+        let position = |token: &str, limit: usize| -> Result<usize> {
+            match token.parse::<usize>() {
+                Ok(p) if p >= 1 && p <= limit => Ok(p - 1),
+                _ => Err(GraphError::FormatError(format!(
+                    "line {}: index '{}' out of range",
+                    line_no, token
+                ))),
+            }
+        };
+        let row = position(tokens[0], rows)?;
+        let col = position(tokens[1], cols)?;
+        let weight = if weighted {
+            let token = tokens.get(2).ok_or_else(|| {
+                GraphError::FormatError(format!("line {}: entry has no value", line_no))
+            })?;
+            parse_weight(token, line_no)?
+        } else {
+            E::default()
+        };
+        data.edges.push((
+            parse_node(&row.to_string(), line_no)?,
+            parse_node(&col.to_string(), line_no)?,
+            weight,
+        ));
+        if skew && row != col {
+            let weight = if weighted {
+                parse_weight(&negate(tokens[2]), line_no)?
+            } else {
+                E::default()
+            };
+            data.edges.push((
+                parse_node(&col.to_string(), line_no)?,
+                parse_node(&row.to_string(), line_no)?,
+                weight,
+            ));
+        }
+        found += 1;
+    }
+
+    if found != entries {
+        return Err(GraphError::FormatError(format!(
+            "expected {} Matrix Market entries, found {}",
+            entries, found
+        )));
+    }
+    Ok(data)
+}
+
+/// Flip the sign of a numeric token, so that weight types need only `FromStr`
+fn negate(token: &str) -> String {
+    match token.strip_prefix('-') {
+        Some(magnitude) => magnitude.to_string(),
+        None => format!("-{}", token.strip_prefix('+').unwrap_or(token)),
+    }
+}
+
+/// Write the adjacency matrix in coordinate format
+pub(super) fn write<W, N, E>(
+    out: &mut W,
+    nodes: &[&N],
+    edges: &[Edge<N, E>],
+    directed: bool,
+    weighted: bool,
+) -> Result<()>
+where
+    W: Write,
+    N: Node,
+    E: EdgeWeight + Display,
+{
+    let positions: HashMap<&N, usize> =
+        nodes.iter().enumerate().map(|(i, n)| (*n, i + 1)).collect();
+
+    writeln!(
+        out,
+        "%%MatrixMarket matrix coordinate {} {}",
+        if weighted { "real" } else { "pattern" },
+        if directed { "general" } else { "symmetric" }
+    )?;
+    writeln!(out, "{} {} {}", nodes.len(), nodes.len(), edges.len())?;
+    for edge in edges {
+        let mut row = positions[&edge.source];
+        let mut col = positions[&edge.target];
+        // Symmetric files store the lower triangle
+        if !directed && row < col {
+            std::mem::swap(&mut row, &mut col);
+        }
+        if weighted {
+            writeln!(out, "{} {} {}", row, col, edge.weight)?;
+        } else {
+            writeln!(out, "{} {}", row, col)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! Pajek `.net` export
+//!
+//! Vertices are numbered from 1 in node order and listed with their label.
+//! Undirected graphs use an `*Edges` section and directed graphs `*Arcs`.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::Write;
+
+use super::is_numeric;
+use crate::base::{Edge, EdgeWeight, Node};
+use crate::error::{GraphError, Result};
+
+/// Write a Pajek network
+pub(super) fn write<W, N, E>(
+    out: &mut W,
+    nodes: &[&N],
+    edges: &[Edge<N, E>],
+    directed: bool,
+    weighted: bool,
+) -> Result<()>
+where
+    W: Write,
+    N: Node + Display,
+    E: EdgeWeight + Display,
+{
+    let ids: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i + 1)).collect();
+
+    writeln!(out, "*Vertices {}", nodes.len())?;
+    for (i, node) in nodes.iter().enumerate() {
+        // Pajek labels cannot escape quotes
+        let label = node.to_string().replace('"', "'");
+        writeln!(out, "{} \"{}\"", i + 1, label)?;
+    }
+    writeln!(out, "{}", if directed { "*Arcs" } else { "*Edges" })?;
+    for edge in edges {
+        let (source, target) = (ids[&edge.source], ids[&edge.target]);
+        if weighted {
+            let weight = edge.weight.to_string();
+            if !is_numeric(&weight) {
+                return Err(GraphError::FormatError(format!(
+                    "Pajek edge weights must be numeric, found '{}'",
+                    weight
+                )));
+            }
+            writeln!(out, "{} {} {}", source, target, weight)?;
+        } else {
+            writeln!(out, "{} {}", source, target)?;
+        }
+    }
+    Ok(())
+}
@@ -22,8 +22,7 @@ fn main() -> Result<()> {
     demonstrate_metadata_features()?;
 
     println!("Enhanced NetCDF example completed successfully!");
-    println!("Note: This is a structural demonstration - actual NetCDF file I/O");
-    println!("would require a complete NetCDF library implementation.");
+    println!("Note: variables that are never written are stored with their fill values.");
     Ok(())
 }
 
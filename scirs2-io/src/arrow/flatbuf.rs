@@ -0,0 +1,412 @@
+//! Minimal FlatBuffers reader and builder
+//!
+//! Arrow IPC metadata (schemas, record batch headers and file footers) is
+//! stored as FlatBuffers. This module reads tables, vectors, strings and
+//! unions with bounds checks on every access, and serializes a small object
+//! tree into a buffer with children laid out after their parents, so that
+//! every offset points forward as the format requires.
+
+use crate::error::{IoError, Result};
+
+fn invalid<T>() -> Result<T> {
+    Err(IoError::FormatError(
+        "Invalid Arrow IPC metadata".to_string(),
+    ))
+}
+
+fn read_bytes<const N: usize>(buf: &[u8], pos: usize) -> Result<[u8; N]> {
+    match buf.get(pos..pos.wrapping_add(N)) {
+        Some(bytes) => Ok(bytes.try_into().expect("slice length")),
+        None => invalid(),
+    }
+}
+
+fn read_u16(buf: &[u8], pos: usize) -> Result<u16> {
+    read_bytes(buf, pos).map(u16::from_le_bytes)
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> Result<u32> {
+    read_bytes(buf, pos).map(u32::from_le_bytes)
+}
+
+/// Follow an unsigned offset stored at `pos`
+fn follow(buf: &[u8], pos: usize) -> Result<usize> {
+    let target = pos + read_u32(buf, pos)? as usize;
+    if target >= buf.len() {
+        return invalid();
+    }
+    Ok(target)
+}
+
+/// A table inside a FlatBuffer
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Table<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    vtable: usize,
+    vtable_len: usize,
+}
+
+impl<'a> Table<'a> {
+    /// The root table of a buffer
+    pub fn root(buf: &'a [u8]) -> Result<Self> {
+        Self::at(buf, follow(buf, 0)?)
+    }
+
+    fn at(buf: &'a [u8], pos: usize) -> Result<Self> {
+        let soffset = i32::from_le_bytes(read_bytes(buf, pos)?) as i64;
+        let vtable = pos as i64 - soffset;
+        if vtable < 0 {
+            return invalid();
+        }
+        let vtable = vtable as usize;
+        let vtable_len = read_u16(buf, vtable)? as usize;
+        if vtable_len < 4 || vtable + vtable_len > buf.len() {
+            return invalid();
+        }
+        Ok(Self {
+            buf,
+            pos,
+            vtable,
+            vtable_len,
+        })
+    }
+
+    /// Absolute position of a field, or `None` if absent
+    fn field(&self, slot: usize) -> Result<Option<usize>> {
+        let entry = 4 + 2 * slot;
+        if entry + 2 > self.vtable_len {
+            return Ok(None);
+        }
+        match read_u16(self.buf, self.vtable + entry)? {
+            0 => Ok(None),
+            offset => Ok(Some(self.pos + offset as usize)),
+        }
+    }
+
+    pub fn u8(&self, slot: usize, default: u8) -> Result<u8> {
+        match self.field(slot)? {
+            Some(pos) => read_bytes::<1>(self.buf, pos).map(|b| b[0]),
+            None => Ok(default),
+        }
+    }
+
+    pub fn bool(&self, slot: usize, default: bool) -> Result<bool> {
+        Ok(self.u8(slot, default as u8)? != 0)
+    }
+
+    pub fn i16(&self, slot: usize, default: i16) -> Result<i16> {
+        match self.field(slot)? {
+            Some(pos) => read_bytes(self.buf, pos).map(i16::from_le_bytes),
+            None => Ok(default),
+        }
+    }
+
+    pub fn i32(&self, slot: usize, default: i32) -> Result<i32> {
+        match self.field(slot)? {
+            Some(pos) => read_bytes(self.buf, pos).map(i32::from_le_bytes),
+            None => Ok(default),
+        }
+    }
+
+    pub fn i64(&self, slot: usize, default: i64) -> Result<i64> {
+        match self.field(slot)? {
+            Some(pos) => read_bytes(self.buf, pos).map(i64::from_le_bytes),
+            None => Ok(default),
+        }
+    }
+
+    /// Child table referenced by an offset field
+    pub fn table(&self, slot: usize) -> Result<Option<Table<'a>>> {
+        match self.field(slot)? {
+            Some(pos) => Table::at(self.buf, follow(self.buf, pos)?).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn string(&self, slot: usize) -> Result<Option<&'a str>> {
+        let Some(vector) = self.vector(slot)? else {
+            return Ok(None);
+        };
+        let Some(bytes) = self.buf.get(vector.start..vector.start + vector.len) else {
+            return invalid();
+        };
+        std::str::from_utf8(bytes).map(Some).or_else(|_| invalid())
+    }
+
+    pub fn vector(&self, slot: usize) -> Result<Option<Vector<'a>>> {
+        let Some(pos) = self.field(slot)? else {
+            return Ok(None);
+        };
+        let pos = follow(self.buf, pos)?;
+        let len = read_u32(self.buf, pos)? as usize;
+        Ok(Some(Vector {
+            buf: self.buf,
+            start: pos + 4,
+            len,
+        }))
+    }
+}
+
+/// A vector inside a FlatBuffer
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Vector<'a> {
+    buf: &'a [u8],
+    start: usize,
+    len: usize,
+}
+
+impl<'a> Vector<'a> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Element `index` of a vector of tables
+    pub fn table(&self, index: usize) -> Result<Table<'a>> {
+        if index >= self.len {
+            return invalid();
+        }
+        let pos = self.start + 4 * index;
+        Table::at(self.buf, follow(self.buf, pos)?)
+    }
+
+    /// Bytes of element `index` of a vector of structs of `size` bytes
+    pub fn struct_bytes(&self, index: usize, size: usize) -> Result<&'a [u8]> {
+        if index >= self.len {
+            return invalid();
+        }
+        let pos = self.start + size * index;
+        match self.buf.get(pos..pos + size) {
+            Some(bytes) => Ok(bytes),
+            None => invalid(),
+        }
+    }
+}
+
+/// A field of a table being built
+#[derive(Debug, Clone)]
+pub(crate) enum Field {
+    /// Inline scalar, little-endian
+    Scalar(Vec<u8>),
+    /// Offset to a child object
+    Child(Object),
+}
+
+impl Field {
+    pub fn u8(v: u8) -> Self {
+        Field::Scalar(vec![v])
+    }
+
+    pub fn bool(v: bool) -> Self {
+        Field::Scalar(vec![v as u8])
+    }
+
+    pub fn i16(v: i16) -> Self {
+        Field::Scalar(v.to_le_bytes().to_vec())
+    }
+
+    pub fn i32(v: i32) -> Self {
+        Field::Scalar(v.to_le_bytes().to_vec())
+    }
+
+    pub fn i64(v: i64) -> Self {
+        Field::Scalar(v.to_le_bytes().to_vec())
+    }
+
+    pub fn string(s: &str) -> Self {
+        Field::Child(Object::String(s.to_string()))
+    }
+
+    pub fn table(fields: Vec<(usize, Field)>) -> Self {
+        Field::Child(Object::Table(fields))
+    }
+
+    pub fn tables(tables: Vec<Object>) -> Self {
+        Field::Child(Object::Tables(tables))
+    }
+
+    /// Vector of 8-byte-aligned structs given as their concatenated bytes
+    pub fn structs(data: Vec<u8>, count: usize) -> Self {
+        Field::Child(Object::Structs { data, count })
+    }
+}
+
+/// An object being built
+#[derive(Debug, Clone)]
+pub(crate) enum Object {
+    /// Table with (slot, field) pairs
+    Table(Vec<(usize, Field)>),
+    /// String
+    String(String),
+    /// Vector of tables
+    Tables(Vec<Object>),
+    /// Vector of structs
+    Structs { data: Vec<u8>, count: usize },
+}
+
+impl Object {
+    /// Serialize the object as the root of a new buffer
+    pub fn finish(&self) -> Vec<u8> {
+        let mut out = vec![0u8; 4];
+        let root = self.write(&mut out);
+        out[..4].copy_from_slice(&(root as u32).to_le_bytes());
+        while !out.len().is_multiple_of(8) {
+            out.push(0);
+        }
+        out
+    }
+
+    /// Append the object, returning the position its offsets point to
+    fn write(&self, out: &mut Vec<u8>) -> usize {
+        match self {
+            Object::String(s) => {
+                align(out, 4, 0);
+                let pos = out.len();
+                out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+                out.extend_from_slice(s.as_bytes());
+                out.push(0);
+                pos
+            }
+            Object::Structs { data, count } => {
+                // Elements start 8-aligned after the 4-byte length
+                align(out, 8, 4);
+                let pos = out.len();
+                out.extend_from_slice(&(*count as u32).to_le_bytes());
+                out.extend_from_slice(data);
+                pos
+            }
+            Object::Tables(tables) => {
+                align(out, 4, 0);
+                let pos = out.len();
+                out.extend_from_slice(&(tables.len() as u32).to_le_bytes());
+                let slots: Vec<usize> = (0..tables.len())
+                    .map(|_| {
+                        out.extend_from_slice(&[0; 4]);
+                        out.len() - 4
+                    })
+                    .collect();
+                for (table, slot) in tables.iter().zip(slots) {
+                    let target = table.write(out);
+                    patch(out, slot, target);
+                }
+                pos
+            }
+            Object::Table(fields) => write_table(fields, out),
+        }
+    }
+}
+
+/// Pad `out` so that `out.len() + offset` is a multiple of `alignment`
+fn align(out: &mut Vec<u8>, alignment: usize, offset: usize) {
+    while !(out.len() + offset).is_multiple_of(alignment) {
+        out.push(0);
+    }
+}
+
+fn patch(out: &mut [u8], slot: usize, target: usize) {
+    out[slot..slot + 4].copy_from_slice(&((target - slot) as u32).to_le_bytes());
+}
+
+fn write_table(fields: &[(usize, Field)], out: &mut Vec<u8>) -> usize {
+    // Lay out the fields after the soffset, largest first so they stay aligned
+    let size = |f: &Field| match f {
+        Field::Scalar(bytes) => bytes.len(),
+        Field::Child(_) => 4,
+    };
+    let mut order: Vec<&(usize, Field)> = fields.iter().collect();
+    order.sort_by_key(|(_, f)| std::cmp::Reverse(size(f)));
+    let mut layout = Vec::with_capacity(order.len());
+    let mut cursor = 4usize;
+    for (slot, field) in &order {
+        let n = size(field);
+        cursor = cursor.div_ceil(n) * n;
+        layout.push((*slot, cursor, field));
+        cursor += n;
+    }
+    let table_size = cursor.div_ceil(4) * 4;
+
+    let num_slots = fields.iter().map(|(s, _)| s + 1).max().unwrap_or(0);
+    let vtable_size = 4 + 2 * num_slots;
+    // The table itself starts 8-aligned, right after its vtable
+    align(out, 8, vtable_size);
+    let vtable_pos = out.len();
+    let mut vtable = vec![0u16; 2 + num_slots];
+    vtable[0] = vtable_size as u16;
+    vtable[1] = table_size as u16;
+    for (slot, offset, _) in &layout {
+        vtable[2 + slot] = *offset as u16;
+    }
+    for entry in vtable {
+        out.extend_from_slice(&entry.to_le_bytes());
+    }
+
+    let table_pos = out.len();
+    out.resize(table_pos + table_size, 0);
+    out[table_pos..table_pos + 4].copy_from_slice(&((table_pos - vtable_pos) as i32).to_le_bytes());
+    for (_, offset, field) in &layout {
+        if let Field::Scalar(bytes) = field {
+            out[table_pos + offset..table_pos + offset + bytes.len()].copy_from_slice(bytes);
+        }
+    }
+    for (_, offset, field) in &layout {
+        if let Field::Child(child) = field {
+            let target = child.write(out);
+            patch(out, table_pos + offset, target);
+        }
+    }
+    table_pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let child = Object::Table(vec![(0, Field::i32(-5)), (1, Field::string("child"))]);
+        let mut structs = Vec::new();
+        for i in 0..3i64 {
+            structs.extend_from_slice(&i.to_le_bytes());
+            structs.extend_from_slice(&(i * 10).to_le_bytes());
+        }
+        let root = Object::Table(vec![
+            (0, Field::i16(4)),
+            (1, Field::u8(3)),
+            (2, Field::Child(child.clone())),
+            (3, Field::i64(1 << 40)),
+            (5, Field::tables(vec![child.clone(), child])),
+            (6, Field::structs(structs, 3)),
+            (7, Field::bool(true)),
+        ]);
+        let buf = root.finish();
+        assert_eq!(buf.len() % 8, 0);
+
+        let table = Table::root(&buf).unwrap();
+        assert_eq!(table.i16(0, 0).unwrap(), 4);
+        assert_eq!(table.u8(1, 0).unwrap(), 3);
+        assert_eq!(table.i64(3, 0).unwrap(), 1 << 40);
+        assert_eq!(table.i32(4, 99).unwrap(), 99);
+        assert!(table.bool(7, false).unwrap());
+        assert!(table.table(4).unwrap().is_none());
+
+        let child = table.table(2).unwrap().unwrap();
+        assert_eq!(child.i32(0, 0).unwrap(), -5);
+        assert_eq!(child.string(1).unwrap(), Some("child"));
+
+        let tables = table.vector(5).unwrap().unwrap();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables.table(1).unwrap().string(1).unwrap(), Some("child"));
+        assert!(tables.table(2).is_err());
+
+        let structs = table.vector(6).unwrap().unwrap();
+        let second = structs.struct_bytes(1, 16).unwrap();
+        assert_eq!(i64::from_le_bytes(second[8..].try_into().unwrap()), 10);
+    }
+
+    #[test]
+    fn test_corrupt_buffer() {
+        let buf = Object::Table(vec![(0, Field::string("x"))]).finish();
+        assert!(Table::root(&buf[..6]).is_err());
+        assert!(Table::root(&[0xFF; 8]).is_err());
+    }
+}
@@ -0,0 +1,709 @@
+//! Arrow IPC messages, schemas and record batch bodies
+
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Write};
+
+use super::flatbuf::{self, Field as FbField, Object};
+use super::ArrowCompression;
+use crate::columnar::{Column, ColumnData, DataType, Field, Table};
+use crate::compression::{compress_data, decompress_data, CompressionAlgorithm};
+use crate::error::{IoError, Result};
+
+/// Magic bytes at the start and end of an Arrow file
+pub(super) const MAGIC: &[u8; 6] = b"ARROW1";
+
+const CONTINUATION: u32 = 0xFFFF_FFFF;
+const METADATA_V5: i16 = 4;
+
+// Message header union types
+pub(super) const HEADER_SCHEMA: u8 = 1;
+pub(super) const HEADER_DICTIONARY_BATCH: u8 = 2;
+pub(super) const HEADER_RECORD_BATCH: u8 = 3;
+
+// Type union ids
+const TYPE_INT: u8 = 2;
+const TYPE_FLOATING_POINT: u8 = 3;
+const TYPE_BINARY: u8 = 4;
+const TYPE_UTF8: u8 = 5;
+const TYPE_BOOL: u8 = 6;
+const TYPE_DATE: u8 = 8;
+const TYPE_TIME: u8 = 9;
+const TYPE_TIMESTAMP: u8 = 10;
+const TYPE_DURATION: u8 = 18;
+const TYPE_LARGE_BINARY: u8 = 19;
+const TYPE_LARGE_UTF8: u8 = 20;
+
+fn format_error<T>(msg: impl Into<String>) -> Result<T> {
+    Err(IoError::FormatError(msg.into()))
+}
+
+pub(super) fn io_error(e: std::io::Error) -> IoError {
+    IoError::FileError(e.to_string())
+}
+
+/// Dictionary encoding of a field
+#[derive(Debug, Clone, Copy)]
+pub(super) struct DictionaryInfo {
+    pub id: i64,
+    /// Integer type of the indices
+    pub index_type: DataType,
+}
+
+/// A field of an Arrow schema
+#[derive(Debug, Clone)]
+pub(super) struct ArrowField {
+    pub name: String,
+    /// Value type (the dictionary value type for dictionary-encoded fields)
+    pub data_type: DataType,
+    pub nullable: bool,
+    /// Whether variable-length values use 64-bit offsets
+    pub large_offsets: bool,
+    pub dictionary: Option<DictionaryInfo>,
+}
+
+impl ArrowField {
+    pub fn field(&self) -> Field {
+        Field::new(&self.name, self.data_type, self.nullable)
+    }
+}
+
+fn int_type(bit_width: i32, signed: bool) -> Result<DataType> {
+    Ok(match (bit_width, signed) {
+        (8, true) => DataType::Int8,
+        (16, true) => DataType::Int16,
+        (32, true) => DataType::Int32,
+        (64, true) => DataType::Int64,
+        (8, false) => DataType::UInt8,
+        (16, false) => DataType::UInt16,
+        (32, false) => DataType::UInt32,
+        (64, false) => DataType::UInt64,
+        _ => return format_error(format!("Invalid Arrow integer width {}", bit_width)),
+    })
+}
+
+/// Column type and offset width of an Arrow type
+fn parse_type(type_id: u8, table: Option<flatbuf::Table>) -> Result<(DataType, bool)> {
+    let int = |slot, default| match table {
+        Some(t) => t.i32(slot, default),
+        None => Ok(default),
+    };
+    let short = |slot, default| match table {
+        Some(t) => t.i16(slot, default),
+        None => Ok(default),
+    };
+    Ok(match type_id {
+        TYPE_INT => {
+            let signed = match table {
+                Some(t) => t.bool(1, false)?,
+                None => false,
+            };
+            (int_type(int(0, 0)?, signed)?, false)
+        }
+        TYPE_FLOATING_POINT => match short(0, 0)? {
+            1 => (DataType::Float32, false),
+            2 => (DataType::Float64, false),
+            _ => return format_error("Half-precision Arrow floats are not supported"),
+        },
+        TYPE_BINARY => (DataType::Binary, false),
+        TYPE_UTF8 => (DataType::Utf8, false),
+        TYPE_BOOL => (DataType::Boolean, false),
+        TYPE_LARGE_BINARY => (DataType::Binary, true),
+        TYPE_LARGE_UTF8 => (DataType::Utf8, true),
+        // Temporal types are read as their integer storage
+        TYPE_DATE if short(0, 1)? == 0 => (DataType::Int32, false),
+        TYPE_DATE | TYPE_TIMESTAMP | TYPE_DURATION => (DataType::Int64, false),
+        TYPE_TIME if int(1, 32)? == 32 => (DataType::Int32, false),
+        TYPE_TIME => (DataType::Int64, false),
+        other => return format_error(format!("Unsupported Arrow type id {}", other)),
+    })
+}
+
+/// Parse the fields of a Schema table
+pub(super) fn parse_schema(schema: flatbuf::Table) -> Result<Vec<ArrowField>> {
+    if schema.i16(0, 0)? != 0 {
+        return format_error("Big-endian Arrow data is not supported");
+    }
+    let Some(fields) = schema.vector(1)? else {
+        return Ok(Vec::new());
+    };
+    (0..fields.len())
+        .map(|i| {
+            let field = fields.table(i)?;
+            let name = field.string(0)?.unwrap_or_default().to_string();
+            if field.vector(5)?.is_some_and(|c| c.len() > 0) {
+                return format_error(format!("Nested Arrow field '{}' is not supported", name));
+            }
+            let (data_type, large_offsets) = parse_type(field.u8(2, 0)?, field.table(3)?)?;
+            let dictionary = match field.table(4)? {
+                Some(dict) => {
+                    let index_type = match dict.table(1)? {
+                        Some(int) => int_type(int.i32(0, 32)?, int.bool(1, true)?)?,
+                        None => DataType::Int32,
+                    };
+                    Some(DictionaryInfo {
+                        id: dict.i64(0, 0)?,
+                        index_type,
+                    })
+                }
+                None => None,
+            };
+            Ok(ArrowField {
+                name,
+                data_type,
+                nullable: field.bool(1, false)?,
+                large_offsets,
+                dictionary,
+            })
+        })
+        .collect()
+}
+
+fn type_object(data_type: DataType) -> (u8, Object) {
+    let int = |bits: i32, signed: bool| {
+        (
+            TYPE_INT,
+            Object::Table(vec![(0, FbField::i32(bits)), (1, FbField::bool(signed))]),
+        )
+    };
+    match data_type {
+        DataType::Boolean => (TYPE_BOOL, Object::Table(Vec::new())),
+        DataType::Int8 => int(8, true),
+        DataType::Int16 => int(16, true),
+        DataType::Int32 => int(32, true),
+        DataType::Int64 => int(64, true),
+        DataType::UInt8 => int(8, false),
+        DataType::UInt16 => int(16, false),
+        DataType::UInt32 => int(32, false),
+        DataType::UInt64 => int(64, false),
+        DataType::Float32 => (
+            TYPE_FLOATING_POINT,
+            Object::Table(vec![(0, FbField::i16(1))]),
+        ),
+        DataType::Float64 => (
+            TYPE_FLOATING_POINT,
+            Object::Table(vec![(0, FbField::i16(2))]),
+        ),
+        DataType::Utf8 => (TYPE_UTF8, Object::Table(Vec::new())),
+        DataType::Binary => (TYPE_BINARY, Object::Table(Vec::new())),
+    }
+}
+
+/// Build a Schema table; dictionary-encoded fields have Int32 indices
+pub(super) fn schema_object(fields: &[Field], dictionary_ids: &[Option<i64>]) -> Object {
+    let fields = fields
+        .iter()
+        .zip(dictionary_ids)
+        .map(|(field, dictionary)| {
+            let (type_id, type_table) = type_object(field.data_type);
+            let mut slots = vec![
+                (0, FbField::string(&field.name)),
+                (1, FbField::bool(field.nullable)),
+                (2, FbField::u8(type_id)),
+                (3, FbField::Child(type_table)),
+                (5, FbField::tables(Vec::new())),
+            ];
+            if let Some(id) = dictionary {
+                slots.push((
+                    4,
+                    FbField::table(vec![
+                        (0, FbField::i64(*id)),
+                        (
+                            1,
+                            FbField::table(vec![(0, FbField::i32(32)), (1, FbField::bool(true))]),
+                        ),
+                        (2, FbField::bool(false)),
+                    ]),
+                ));
+            }
+            Object::Table(slots)
+        })
+        .collect();
+    Object::Table(vec![(0, FbField::i16(0)), (1, FbField::tables(fields))])
+}
+
+/// Wrap a message header into a Message table
+pub(super) fn message_object(header_type: u8, header: Object, body_length: usize) -> Object {
+    Object::Table(vec![
+        (0, FbField::i16(METADATA_V5)),
+        (1, FbField::u8(header_type)),
+        (2, FbField::Child(header)),
+        (3, FbField::i64(body_length as i64)),
+    ])
+}
+
+/// Header type and header table of a Message
+pub(super) fn message_header(metadata: &[u8]) -> Result<(u8, flatbuf::Table<'_>, usize)> {
+    let message = flatbuf::Table::root(metadata)?;
+    let header_type = message.u8(1, 0)?;
+    let Some(header) = message.table(2)? else {
+        return format_error("Arrow message has no header");
+    };
+    let body_length = message.i64(3, 0)?;
+    if body_length < 0 {
+        return format_error("Negative Arrow message body length");
+    }
+    Ok((header_type, header, body_length as usize))
+}
+
+/// Read one encapsulated message, returning its metadata and body
+///
+/// Returns `None` at the end-of-stream marker or at the end of the input.
+pub(super) fn read_message<R: Read>(reader: &mut R) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+    let mut word = [0u8; 4];
+    match reader.read_exact(&mut word) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(io_error(e)),
+    }
+    // Files written before Arrow 0.15 omit the continuation marker
+    if u32::from_le_bytes(word) == CONTINUATION {
+        reader.read_exact(&mut word).map_err(io_error)?;
+    }
+    let len = i32::from_le_bytes(word);
+    if len == 0 {
+        return Ok(None);
+    }
+    if len < 0 {
+        return format_error("Negative Arrow metadata length");
+    }
+    let mut metadata = vec![0u8; len as usize];
+    reader.read_exact(&mut metadata).map_err(io_error)?;
+    let (_, _, body_length) = message_header(&metadata)?;
+    let mut body = Vec::new();
+    reader
+        .by_ref()
+        .take(body_length as u64)
+        .read_to_end(&mut body)
+        .map_err(io_error)?;
+    if body.len() != body_length {
+        return format_error("Truncated Arrow message body");
+    }
+    Ok(Some((metadata, body)))
+}
+
+/// Write one encapsulated message, returning the metadata and body lengths
+pub(super) fn write_message<W: Write>(
+    writer: &mut W,
+    message: &Object,
+    body: &[u8],
+) -> Result<(usize, usize)> {
+    let metadata = message.finish();
+    writer
+        .write_all(&CONTINUATION.to_le_bytes())
+        .and_then(|_| writer.write_all(&(metadata.len() as i32).to_le_bytes()))
+        .and_then(|_| writer.write_all(&metadata))
+        .and_then(|_| writer.write_all(body))
+        .map_err(io_error)?;
+    Ok((metadata.len() + 8, body.len()))
+}
+
+/// Write the end-of-stream marker
+pub(super) fn write_eos<W: Write>(writer: &mut W) -> Result<()> {
+    writer
+        .write_all(&CONTINUATION.to_le_bytes())
+        .and_then(|_| writer.write_all(&0i32.to_le_bytes()))
+        .map_err(io_error)
+}
+
+fn bitmap(bytes: &[u8], len: usize) -> Result<Vec<bool>> {
+    if bytes.len() * 8 < len {
+        return format_error("Arrow bitmap is too short");
+    }
+    Ok((0..len).map(|i| bytes[i / 8] >> (i % 8) & 1 == 1).collect())
+}
+
+fn pack_bitmap(bits: impl Iterator<Item = bool>) -> Vec<u8> {
+    let mut out = Vec::new();
+    for (i, bit) in bits.enumerate() {
+        if i % 8 == 0 {
+            out.push(0);
+        }
+        if bit {
+            *out.last_mut().expect("pushed above") |= 1 << (i % 8);
+        }
+    }
+    out
+}
+
+fn fixed_values<const N: usize, T>(
+    bytes: &[u8],
+    len: usize,
+    f: fn([u8; N]) -> T,
+) -> Result<Vec<T>> {
+    match bytes.get(..len * N) {
+        Some(bytes) => Ok(bytes
+            .chunks_exact(N)
+            .map(|c| f(c.try_into().expect("chunk size")))
+            .collect()),
+        None => format_error("Arrow value buffer is too short"),
+    }
+}
+
+/// Values of a fixed-width or boolean array
+fn decode_fixed(data_type: DataType, bytes: &[u8], len: usize) -> Result<ColumnData> {
+    Ok(match data_type {
+        DataType::Boolean => ColumnData::Boolean(bitmap(bytes, len)?),
+        DataType::Int8 => ColumnData::Int8(fixed_values(bytes, len, i8::from_le_bytes)?),
+        DataType::Int16 => ColumnData::Int16(fixed_values(bytes, len, i16::from_le_bytes)?),
+        DataType::Int32 => ColumnData::Int32(fixed_values(bytes, len, i32::from_le_bytes)?),
+        DataType::Int64 => ColumnData::Int64(fixed_values(bytes, len, i64::from_le_bytes)?),
+        DataType::UInt8 => ColumnData::UInt8(fixed_values(bytes, len, u8::from_le_bytes)?),
+        DataType::UInt16 => ColumnData::UInt16(fixed_values(bytes, len, u16::from_le_bytes)?),
+        DataType::UInt32 => ColumnData::UInt32(fixed_values(bytes, len, u32::from_le_bytes)?),
+        DataType::UInt64 => ColumnData::UInt64(fixed_values(bytes, len, u64::from_le_bytes)?),
+        DataType::Float32 => ColumnData::Float32(fixed_values(bytes, len, f32::from_le_bytes)?),
+        DataType::Float64 => ColumnData::Float64(fixed_values(bytes, len, f64::from_le_bytes)?),
+        DataType::Utf8 | DataType::Binary => unreachable!("variable-width type"),
+    })
+}
+
+/// Values of a variable-width array
+fn decode_variable(
+    data_type: DataType,
+    offsets: &[u8],
+    data: &[u8],
+    len: usize,
+    large: bool,
+) -> Result<ColumnData> {
+    let offsets: Vec<usize> = if large {
+        fixed_values(offsets, len + 1, i64::from_le_bytes)?
+            .into_iter()
+            .map(|o| o.max(0) as usize)
+            .collect()
+    } else {
+        fixed_values(offsets, len + 1, i32::from_le_bytes)?
+            .into_iter()
+            .map(|o| o.max(0) as usize)
+            .collect()
+    };
+    let mut values = Vec::with_capacity(len);
+    for w in offsets.windows(2) {
+        match data.get(w[0]..w[1]) {
+            Some(bytes) => values.push(bytes.to_vec()),
+            None => return format_error("Arrow offsets exceed the data buffer"),
+        }
+    }
+    Ok(match data_type {
+        DataType::Utf8 => ColumnData::Utf8(
+            values
+                .into_iter()
+                .map(|b| String::from_utf8_lossy(&b).into_owned())
+                .collect(),
+        ),
+        _ => ColumnData::Binary(values),
+    })
+}
+
+/// Dictionary indices as positions, `None` for null entries
+fn decode_indices(
+    index_type: DataType,
+    bytes: &[u8],
+    validity: Option<&[bool]>,
+    len: usize,
+) -> Result<Vec<Option<usize>>> {
+    let raw: Vec<i64> = match decode_fixed(index_type, bytes, len)? {
+        ColumnData::Int8(v) => v.into_iter().map(i64::from).collect(),
+        ColumnData::Int16(v) => v.into_iter().map(i64::from).collect(),
+        ColumnData::Int32(v) => v.into_iter().map(i64::from).collect(),
+        ColumnData::Int64(v) => v,
+        ColumnData::UInt8(v) => v.into_iter().map(i64::from).collect(),
+        ColumnData::UInt16(v) => v.into_iter().map(i64::from).collect(),
+        ColumnData::UInt32(v) => v.into_iter().map(i64::from).collect(),
+        ColumnData::UInt64(v) => v.into_iter().map(|x| x as i64).collect(),
+        _ => return format_error("Arrow dictionary indices must be integers"),
+    };
+    raw.into_iter()
+        .enumerate()
+        .map(|(i, index)| {
+            if validity.is_some_and(|v| !v[i]) {
+                Ok(None)
+            } else if index < 0 {
+                format_error("Negative Arrow dictionary index")
+            } else {
+                Ok(Some(index as usize))
+            }
+        })
+        .collect()
+}
+
+/// Buffer (de)compression settings of a record batch
+fn batch_compression(batch: &flatbuf::Table) -> Result<Option<CompressionAlgorithm>> {
+    match batch.table(3)? {
+        None => Ok(None),
+        Some(compression) => match compression.u8(0, 0)? {
+            0 => Ok(Some(CompressionAlgorithm::Lz4)),
+            1 => Ok(Some(CompressionAlgorithm::Zstd)),
+            other => Err(IoError::UnsupportedCompressionAlgorithm(format!(
+                "Arrow codec {}",
+                other
+            ))),
+        },
+    }
+}
+
+/// Decode the columns of a record batch
+///
+/// Only the fields listed in `projection` are decoded; they are returned in
+/// projection order.
+pub(super) fn decode_batch(
+    batch: flatbuf::Table,
+    body: &[u8],
+    fields: &[ArrowField],
+    dictionaries: &HashMap<i64, Column>,
+    projection: &[usize],
+) -> Result<Table> {
+    let num_rows = batch.i64(0, 0)?.max(0) as usize;
+    let compression = batch_compression(&batch)?;
+    let Some(nodes) = batch.vector(1)? else {
+        return format_error("Arrow record batch has no field nodes");
+    };
+    let Some(buffers) = batch.vector(2)? else {
+        return format_error("Arrow record batch has no buffers");
+    };
+
+    let mut buffer_index = 0;
+    let mut next_buffer = || -> Result<Vec<u8>> {
+        let desc = buffers.struct_bytes(buffer_index, 16)?;
+        buffer_index += 1;
+        let offset = i64::from_le_bytes(desc[..8].try_into().expect("8 bytes"));
+        let length = i64::from_le_bytes(desc[8..].try_into().expect("8 bytes"));
+        if offset < 0 || length < 0 {
+            return format_error("Negative Arrow buffer offset or length");
+        }
+        let Some(bytes) = body.get(offset as usize..(offset + length) as usize) else {
+            return format_error("Arrow buffer lies outside the message body");
+        };
+        match compression {
+            Some(algorithm) if bytes.len() >= 8 => {
+                let uncompressed = i64::from_le_bytes(bytes[..8].try_into().expect("8 bytes"));
+                if uncompressed == -1 {
+                    Ok(bytes[8..].to_vec())
+                } else {
+                    decompress_data(&bytes[8..], algorithm)
+                }
+            }
+            _ => Ok(bytes.to_vec()),
+        }
+    };
+
+    let mut columns: Vec<Option<Column>> = vec![None; fields.len()];
+    for (i, field) in fields.iter().enumerate() {
+        let node = nodes.struct_bytes(i, 16)?;
+        let len = i64::from_le_bytes(node[..8].try_into().expect("8 bytes")).max(0) as usize;
+        let null_count = i64::from_le_bytes(node[8..].try_into().expect("8 bytes"));
+        let validity_bytes = next_buffer()?;
+        let variable = field.dictionary.is_none()
+            && matches!(field.data_type, DataType::Utf8 | DataType::Binary);
+        let (first, second) = if variable {
+            (next_buffer()?, Some(next_buffer()?))
+        } else {
+            (next_buffer()?, None)
+        };
+        if !projection.contains(&i) {
+            continue;
+        }
+
+        let validity = if null_count > 0 && !validity_bytes.is_empty() {
+            Some(bitmap(&validity_bytes, len)?)
+        } else {
+            None
+        };
+        let data = match (&field.dictionary, second) {
+            (Some(dict), _) => {
+                let Some(values) = dictionaries.get(&dict.id) else {
+                    return format_error(format!(
+                        "Arrow dictionary {} used before it was defined",
+                        dict.id
+                    ));
+                };
+                let indices = decode_indices(dict.index_type, &first, validity.as_deref(), len)?;
+                values.data.take(&indices)?
+            }
+            (None, Some(data)) => {
+                decode_variable(field.data_type, &first, &data, len, field.large_offsets)?
+            }
+            (None, None) => decode_fixed(field.data_type, &first, len)?,
+        };
+        if data.len() != num_rows {
+            return format_error(format!(
+                "Arrow field '{}' has {} values in a batch of {} rows",
+                field.name,
+                data.len(),
+                num_rows
+            ));
+        }
+        columns[i] = Some(match validity {
+            Some(validity) => Column::with_validity(&field.name, data, validity)?,
+            None if field.nullable => {
+                Column::with_validity(&field.name, data, vec![true; num_rows])?
+            }
+            None => Column::new(&field.name, data),
+        });
+    }
+
+    let columns = projection
+        .iter()
+        .map(|&i| columns[i].take().expect("decoded above"))
+        .collect();
+    Table::new(columns)
+}
+
+/// Decode a dictionary batch, returning its id, values and delta flag
+pub(super) fn decode_dictionary(
+    header: flatbuf::Table,
+    body: &[u8],
+    fields: &[ArrowField],
+) -> Result<(i64, Column, bool)> {
+    let id = header.i64(0, 0)?;
+    let Some(field) = fields
+        .iter()
+        .find(|f| f.dictionary.is_some_and(|d| d.id == id))
+    else {
+        return format_error(format!("Arrow dictionary {} is not used by the schema", id));
+    };
+    let Some(batch) = header.table(1)? else {
+        return format_error("Arrow dictionary batch has no data");
+    };
+    let value_field = ArrowField {
+        dictionary: None,
+        ..field.clone()
+    };
+    let values = decode_batch(batch, body, &[value_field], &HashMap::new(), &[0])?;
+    let column = values.into_columns().pop().expect("one projected column");
+    Ok((id, column, header.bool(2, false)?))
+}
+
+/// Record batch body being assembled
+#[derive(Default)]
+pub(super) struct BodyBuilder {
+    body: Vec<u8>,
+    nodes: Vec<u8>,
+    buffers: Vec<u8>,
+    num_buffers: usize,
+    num_nodes: usize,
+    compression: Option<CompressionAlgorithm>,
+}
+
+impl BodyBuilder {
+    pub fn new(compression: ArrowCompression) -> Self {
+        Self {
+            compression: match compression {
+                ArrowCompression::None => None,
+                ArrowCompression::Lz4 => Some(CompressionAlgorithm::Lz4),
+                ArrowCompression::Zstd => Some(CompressionAlgorithm::Zstd),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn node(&mut self, len: usize, null_count: usize) {
+        self.nodes.extend_from_slice(&(len as i64).to_le_bytes());
+        self.nodes
+            .extend_from_slice(&(null_count as i64).to_le_bytes());
+        self.num_nodes += 1;
+    }
+
+    fn buffer(&mut self, data: &[u8]) -> Result<()> {
+        let offset = self.body.len();
+        match self.compression {
+            Some(algorithm) if !data.is_empty() => {
+                let compressed = compress_data(data, algorithm, None)?;
+                // Store incompressible buffers raw, marked by a length of -1
+                if compressed.len() < data.len() {
+                    self.body
+                        .extend_from_slice(&(data.len() as i64).to_le_bytes());
+                    self.body.extend_from_slice(&compressed);
+                } else {
+                    self.body.extend_from_slice(&(-1i64).to_le_bytes());
+                    self.body.extend_from_slice(data);
+                }
+            }
+            _ => self.body.extend_from_slice(data),
+        }
+        let length = self.body.len() - offset;
+        while !self.body.len().is_multiple_of(8) {
+            self.body.push(0);
+        }
+        self.buffers
+            .extend_from_slice(&(offset as i64).to_le_bytes());
+        self.buffers
+            .extend_from_slice(&(length as i64).to_le_bytes());
+        self.num_buffers += 1;
+        Ok(())
+    }
+
+    /// Append a column, or its dictionary indices if given
+    pub fn column(&mut self, column: &Column, indices: Option<&[i32]>) -> Result<()> {
+        let null_count = column.null_count();
+        self.node(column.len(), null_count);
+        match &column.validity {
+            Some(validity) if null_count > 0 => {
+                self.buffer(&pack_bitmap(validity.iter().copied()))?
+            }
+            _ => self.buffer(&[])?,
+        }
+        if let Some(indices) = indices {
+            let bytes: Vec<u8> = indices.iter().flat_map(|i| i.to_le_bytes()).collect();
+            return self.buffer(&bytes);
+        }
+
+        macro_rules! fixed {
+            ($v:expr) => {{
+                let bytes: Vec<u8> = $v.iter().flat_map(|x| x.to_le_bytes()).collect();
+                self.buffer(&bytes)
+            }};
+        }
+        match &column.data {
+            ColumnData::Boolean(v) => self.buffer(&pack_bitmap(v.iter().copied())),
+            ColumnData::Int8(v) => fixed!(v),
+            ColumnData::Int16(v) => fixed!(v),
+            ColumnData::Int32(v) => fixed!(v),
+            ColumnData::Int64(v) => fixed!(v),
+            ColumnData::UInt8(v) => fixed!(v),
+            ColumnData::UInt16(v) => fixed!(v),
+            ColumnData::UInt32(v) => fixed!(v),
+            ColumnData::UInt64(v) => fixed!(v),
+            ColumnData::Float32(v) => fixed!(v),
+            ColumnData::Float64(v) => fixed!(v),
+            ColumnData::Utf8(v) => self.variable(&column.name, v.iter().map(String::as_bytes)),
+            ColumnData::Binary(v) => self.variable(&column.name, v.iter().map(Vec::as_slice)),
+        }
+    }
+
+    fn variable<'a>(&mut self, name: &str, values: impl Iterator<Item = &'a [u8]>) -> Result<()> {
+        let mut offsets = vec![0u8; 4];
+        let mut data = Vec::new();
+        for value in values {
+            data.extend_from_slice(value);
+            let Ok(end) = i32::try_from(data.len()) else {
+                return Err(IoError::ValidationError(format!(
+                    "Column '{}' holds more than 2 GiB of data in one batch",
+                    name
+                )));
+            };
+            offsets.extend_from_slice(&end.to_le_bytes());
+        }
+        self.buffer(&offsets)?;
+        self.buffer(&data)
+    }
+
+    /// Finish the RecordBatch table and body
+    pub fn finish(self, num_rows: usize) -> (Object, Vec<u8>) {
+        let mut slots = vec![
+            (0, FbField::i64(num_rows as i64)),
+            (1, FbField::structs(self.nodes, self.num_nodes)),
+            (2, FbField::structs(self.buffers, self.num_buffers)),
+        ];
+        if let Some(algorithm) = self.compression {
+            let codec = match algorithm {
+                CompressionAlgorithm::Zstd => 1,
+                _ => 0,
+            };
+            slots.push((
+                3,
+                FbField::table(vec![(0, FbField::u8(codec)), (1, FbField::u8(0))]),
+            ));
+        }
+        (Object::Table(slots), self.body)
+    }
+}
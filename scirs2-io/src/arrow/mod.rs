@@ -0,0 +1,348 @@
+//! Apache Arrow IPC format
+//!
+//! This module reads and writes the Arrow inter-process communication
+//! format, both the random-access file format (`.arrow`, also known as
+//! Feather v2) and the streaming format. Record batches are read into the
+//! column-oriented [`Table`] from the [`columnar`](crate::columnar) module.
+//!
+//! Supported features:
+//!
+//! - Boolean, integer, floating-point, string and binary columns, including
+//!   64-bit offset variants; temporal columns are read as their integer storage
+//! - Validity bitmaps (nulls)
+//! - Dictionary-encoded columns, including delta dictionaries
+//! - LZ4 frame and Zstd buffer compression via the
+//!   [`compression`](crate::compression) module
+//! - Column projection and batch-by-batch reading
+//!
+//! Nested types (lists, structs, maps, unions) are not supported.
+//!
+//! # Example
+//!
+//! ```
+//! use ndarray::array;
+//! use scirs2_io::arrow::{read_arrow_file, write_arrow_file};
+//! use scirs2_io::columnar::{Column, Table};
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! let path = dir.path().join("data.arrow");
+//!
+//! let table = Table::new(vec![Column::from_array("x", &array![1.0f32, 2.0, 3.0])]).unwrap();
+//! write_arrow_file(&path, &table, None).unwrap();
+//!
+//! let read = read_arrow_file(&path).unwrap();
+//! assert_eq!(read.column("x").unwrap().to_array::<f32>().unwrap(), array![1.0, 2.0, 3.0]);
+//! ```
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::columnar::Table;
+use crate::error::{IoError, Result};
+
+mod flatbuf;
+mod format;
+mod reader;
+mod writer;
+
+pub use reader::{ArrowFileReader, ArrowStreamReader};
+pub use writer::ArrowWriter;
+
+/// Record batch buffer compression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrowCompression {
+    /// No compression
+    #[default]
+    None,
+    /// LZ4 frame compression
+    Lz4,
+    /// Zstandard compression
+    Zstd,
+}
+
+/// Options for writing Arrow IPC data
+#[derive(Debug, Clone, Default)]
+pub struct ArrowWriteOptions {
+    /// Buffer compression
+    pub compression: ArrowCompression,
+    /// Dictionary-encode string columns
+    pub dictionary: bool,
+}
+
+/// Read a whole Arrow IPC file into a table
+///
+/// # Arguments
+///
+/// * `path` - Path to the Arrow file
+///
+/// # Returns
+///
+/// A table holding the rows of all record batches
+pub fn read_arrow_file<P: AsRef<Path>>(path: P) -> Result<Table> {
+    ArrowFileReader::open(path)?.read_all(None)
+}
+
+/// Read selected columns of an Arrow IPC file into a table
+///
+/// # Arguments
+///
+/// * `path` - Path to the Arrow file
+/// * `columns` - Names of the columns to read, in the order they should appear
+///
+/// # Returns
+///
+/// A table holding the requested columns
+pub fn read_arrow_file_columns<P: AsRef<Path>>(path: P, columns: &[&str]) -> Result<Table> {
+    ArrowFileReader::open(path)?.read_all(Some(columns))
+}
+
+/// Read a file in the Arrow IPC stream format into a table
+///
+/// # Arguments
+///
+/// * `path` - Path to the stream file
+///
+/// # Returns
+///
+/// A table holding the rows of all record batches
+pub fn read_arrow_stream<P: AsRef<Path>>(path: P) -> Result<Table> {
+    let file = File::open(path.as_ref())
+        .map_err(|e| IoError::FileError(format!("{}: {}", path.as_ref().display(), e)))?;
+    ArrowStreamReader::new(std::io::BufReader::new(file), None)?.read_all()
+}
+
+/// Write a table to an Arrow IPC file as a single record batch
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to create
+/// * `table` - The table to write
+/// * `options` - Write options (defaults to uncompressed, no dictionaries)
+pub fn write_arrow_file<P: AsRef<Path>>(
+    path: P,
+    table: &Table,
+    options: Option<ArrowWriteOptions>,
+) -> Result<()> {
+    let mut writer = ArrowWriter::new_file(create(path.as_ref())?, &table.schema(), options)?;
+    writer.write_table(table)?;
+    writer.finish().map(|_| ())
+}
+
+/// Write a table in the Arrow IPC stream format as a single record batch
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to create
+/// * `table` - The table to write
+/// * `options` - Write options (defaults to uncompressed, no dictionaries)
+pub fn write_arrow_stream<P: AsRef<Path>>(
+    path: P,
+    table: &Table,
+    options: Option<ArrowWriteOptions>,
+) -> Result<()> {
+    let mut writer = ArrowWriter::new_stream(create(path.as_ref())?, &table.schema(), options)?;
+    writer.write_table(table)?;
+    writer.finish().map(|_| ())
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>> {
+    File::create(path)
+        .map(BufWriter::new)
+        .map_err(|e| IoError::FileError(format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::columnar::{Column, ColumnData};
+    use ndarray::array;
+    use tempfile::tempdir;
+
+    fn sample_table(offset: usize, n: usize) -> Table {
+        let rows = offset..offset + n;
+        Table::new(vec![
+            Column::new(
+                "id",
+                ColumnData::Int64(rows.clone().map(|i| i as i64).collect()),
+            ),
+            Column::with_validity(
+                "value",
+                ColumnData::Float64(rows.clone().map(|i| i as f64 / 4.0).collect()),
+                rows.clone().map(|i| i % 5 != 0).collect(),
+            )
+            .unwrap(),
+            Column::with_validity(
+                "label",
+                ColumnData::Utf8(rows.clone().map(|i| format!("L{}", i % 3)).collect()),
+                rows.clone().map(|i| i % 4 != 1).collect(),
+            )
+            .unwrap(),
+            Column::new(
+                "flag",
+                ColumnData::Boolean(rows.clone().map(|i| i % 2 == 0).collect()),
+            ),
+            Column::new(
+                "u16",
+                ColumnData::UInt16(rows.clone().map(|i| i as u16).collect()),
+            ),
+            Column::new(
+                "raw",
+                ColumnData::Binary(rows.map(|i| vec![1u8; i % 4]).collect()),
+            ),
+        ])
+        .unwrap()
+    }
+
+    /// Compare tables, ignoring the placeholder values of null entries
+    fn assert_tables_equal(a: &Table, b: &Table) {
+        assert_eq!(a.schema(), b.schema());
+        for (ca, cb) in a.columns().iter().zip(b.columns()) {
+            assert_eq!(ca.validity, cb.validity, "validity of {}", ca.name);
+            let valid: Vec<Option<usize>> =
+                (0..ca.len()).map(|i| ca.is_valid(i).then_some(i)).collect();
+            assert_eq!(
+                ca.data.take(&valid).unwrap(),
+                cb.data.take(&valid).unwrap(),
+                "column {}",
+                ca.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_file_roundtrip() {
+        let dir = tempdir().unwrap();
+        let table = sample_table(0, 500);
+        for compression in [
+            ArrowCompression::None,
+            ArrowCompression::Lz4,
+            ArrowCompression::Zstd,
+        ] {
+            for dictionary in [false, true] {
+                let path = dir
+                    .path()
+                    .join(format!("{:?}_{}.arrow", compression, dictionary));
+                let options = ArrowWriteOptions {
+                    compression,
+                    dictionary,
+                };
+                write_arrow_file(&path, &table, Some(options)).unwrap();
+                assert_tables_equal(&table, &read_arrow_file(&path).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.arrows");
+        let table = sample_table(0, 100);
+        write_arrow_stream(&path, &table, None).unwrap();
+        assert_tables_equal(&table, &read_arrow_stream(&path).unwrap());
+        // A stream is not a file
+        assert!(read_arrow_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_batches_projection_and_delta_dictionaries() {
+        let dir = tempdir().unwrap();
+        let first = sample_table(0, 10);
+        let second = sample_table(10, 7);
+        let options = ArrowWriteOptions {
+            compression: ArrowCompression::Zstd,
+            dictionary: true,
+        };
+
+        // Second batch introduces new labels, written as a delta dictionary
+        let relabel = |table: &Table, prefix: &str| {
+            let columns = table
+                .columns()
+                .iter()
+                .map(|c| match (&c.data, c.name.as_str()) {
+                    (ColumnData::Utf8(v), "label") => Column {
+                        data: ColumnData::Utf8(
+                            v.iter().map(|s| format!("{}{}", prefix, s)).collect(),
+                        ),
+                        ..c.clone()
+                    },
+                    _ => c.clone(),
+                })
+                .collect();
+            Table::new(columns).unwrap()
+        };
+        let second = relabel(&second, "new-");
+
+        let path = dir.path().join("batches.arrow");
+        let file = std::fs::File::create(&path).unwrap();
+        let mut writer =
+            ArrowWriter::new_file(file, &first.schema(), Some(options.clone())).unwrap();
+        writer.write_table(&first).unwrap();
+        writer.write_table(&second).unwrap();
+        assert!(writer.write_table(&first.select(&["id"]).unwrap()).is_err());
+        writer.finish().unwrap();
+
+        let mut expected = first.clone();
+        expected.append(&second).unwrap();
+
+        let mut reader = ArrowFileReader::open(&path).unwrap();
+        assert_eq!(reader.num_batches(), 2);
+        assert_eq!(reader.schema(), first.schema());
+        let batch = reader.read_batch(1, Some(&["label", "id"])).unwrap();
+        assert_eq!(batch.column_names(), vec!["label", "id"]);
+        assert_tables_equal(&second.select(&["label", "id"]).unwrap(), &batch);
+        assert!(reader.read_batch(2, None).is_err());
+        assert!(reader.read_batch(0, Some(&["missing"])).is_err());
+        assert_tables_equal(&expected, &read_arrow_file(&path).unwrap());
+
+        let projected = read_arrow_file_columns(&path, &["u16"]).unwrap();
+        assert_eq!(projected.num_rows(), 17);
+
+        // Same batches in the stream format, read incrementally
+        let mut stream = Vec::new();
+        let mut writer =
+            ArrowWriter::new_stream(&mut stream, &first.schema(), Some(options)).unwrap();
+        writer.write_table(&first).unwrap();
+        writer.write_table(&second).unwrap();
+        writer.finish().unwrap();
+
+        let reader = ArrowStreamReader::new(stream.as_slice(), Some(&["id", "label"])).unwrap();
+        let batches: Vec<Table> = reader.map(|b| b.unwrap()).collect();
+        assert_eq!(batches.len(), 2);
+        assert_tables_equal(
+            &expected
+                .select(&["id", "label"])
+                .unwrap()
+                .slice(10, 7)
+                .unwrap(),
+            &batches[1],
+        );
+    }
+
+    #[test]
+    fn test_arrays_and_record_array() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("arrays.arrow");
+        let table = Table::new(vec![
+            Column::from_array("a", &array![1i32, 2, 3]),
+            Column::from_array("b", &array![0.5, 1.5, 2.5]),
+        ])
+        .unwrap();
+        write_arrow_file(&path, &table, None).unwrap();
+        let read = read_arrow_file(&path).unwrap();
+        assert_eq!(
+            read.to_array2_f64().unwrap(),
+            array![[1.0, 0.5], [2.0, 1.5], [3.0, 2.5]]
+        );
+        assert_eq!(read.to_record_array().unwrap().num_records(), 3);
+    }
+
+    #[test]
+    fn test_rejects_invalid_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bad.arrow");
+        std::fs::write(&path, b"ARROW1\0\0 not really arrow data ARROW1").unwrap();
+        assert!(read_arrow_file(&path).is_err());
+        assert!(ArrowStreamReader::new(&b"\xff\xff\xff\xff\x08\0\0\0abcdefgh"[..], None).is_err());
+    }
+}
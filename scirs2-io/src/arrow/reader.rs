@@ -0,0 +1,295 @@
+//! Arrow IPC file and stream readers
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use super::flatbuf;
+use super::format::{
+    decode_batch, decode_dictionary, io_error, message_header, parse_schema, read_message,
+    ArrowField, HEADER_DICTIONARY_BATCH, HEADER_RECORD_BATCH, HEADER_SCHEMA, MAGIC,
+};
+use crate::columnar::{Column, Field, Table};
+use crate::error::{IoError, Result};
+
+fn format_error<T>(msg: impl Into<String>) -> Result<T> {
+    Err(IoError::FormatError(msg.into()))
+}
+
+/// Resolve column names to field indices
+fn projection(fields: &[ArrowField], columns: Option<&[&str]>) -> Result<Vec<usize>> {
+    match columns {
+        None => Ok((0..fields.len()).collect()),
+        Some(names) => names
+            .iter()
+            .map(|name| {
+                fields.iter().position(|f| f.name == *name).ok_or_else(|| {
+                    IoError::ValidationError(format!("Arrow schema has no column '{}'", name))
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Add a dictionary batch to the known dictionaries
+fn apply_dictionary(
+    dictionaries: &mut HashMap<i64, Column>,
+    header: flatbuf::Table,
+    body: &[u8],
+    fields: &[ArrowField],
+) -> Result<()> {
+    let (id, values, is_delta) = decode_dictionary(header, body, fields)?;
+    match dictionaries.get_mut(&id) {
+        Some(existing) if is_delta => existing.data.extend_from(&values.data),
+        _ => {
+            dictionaries.insert(id, values);
+            Ok(())
+        }
+    }
+}
+
+fn empty_table(fields: &[ArrowField], projection: &[usize]) -> Table {
+    let schema: Vec<Field> = projection.iter().map(|&i| fields[i].field()).collect();
+    Table::empty(&schema)
+}
+
+/// Location of a message in an Arrow file
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    offset: u64,
+}
+
+/// Random-access reader for Arrow IPC files
+pub struct ArrowFileReader {
+    file: BufReader<File>,
+    fields: Vec<ArrowField>,
+    dictionaries: HashMap<i64, Column>,
+    batches: Vec<Block>,
+}
+
+impl ArrowFileReader {
+    /// Open an Arrow file and read its schema and dictionaries
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path.as_ref())
+            .map_err(|e| IoError::FileError(format!("{}: {}", path.as_ref().display(), e)))?;
+        let len = file.metadata().map_err(io_error)?.len();
+        let mut file = BufReader::new(file);
+        if len < 18 {
+            return format_error("File is too small to be an Arrow file");
+        }
+
+        let mut head = [0u8; 6];
+        file.read_exact(&mut head).map_err(io_error)?;
+        let mut tail = [0u8; 10];
+        file.seek(SeekFrom::Start(len - 10)).map_err(io_error)?;
+        file.read_exact(&mut tail).map_err(io_error)?;
+        if &head != MAGIC || &tail[4..] != MAGIC {
+            return format_error("Not an Arrow file (missing ARROW1 magic)");
+        }
+        let footer_len = i32::from_le_bytes(tail[..4].try_into().expect("4 bytes"));
+        if footer_len < 0 || footer_len as u64 + 18 > len {
+            return format_error("Arrow footer length exceeds file size");
+        }
+        let mut footer = vec![0u8; footer_len as usize];
+        file.seek(SeekFrom::Start(len - 10 - footer_len as u64))
+            .map_err(io_error)?;
+        file.read_exact(&mut footer).map_err(io_error)?;
+
+        let footer_table = flatbuf::Table::root(&footer)?;
+        let Some(schema) = footer_table.table(1)? else {
+            return format_error("Arrow footer has no schema");
+        };
+        let fields = parse_schema(schema)?;
+        let blocks = |slot| -> Result<Vec<Block>> {
+            let Some(vector) = footer_table.vector(slot)? else {
+                return Ok(Vec::new());
+            };
+            (0..vector.len())
+                .map(|i| {
+                    let bytes = vector.struct_bytes(i, 24)?;
+                    let offset = i64::from_le_bytes(bytes[..8].try_into().expect("8 bytes"));
+                    if offset < 0 || offset as u64 >= len {
+                        return format_error("Arrow block lies outside the file");
+                    }
+                    Ok(Block {
+                        offset: offset as u64,
+                    })
+                })
+                .collect()
+        };
+        let dictionary_blocks = blocks(2)?;
+        let batches = blocks(3)?;
+
+        let mut reader = Self {
+            file,
+            fields,
+            dictionaries: HashMap::new(),
+            batches,
+        };
+        for block in dictionary_blocks {
+            let (metadata, body) = reader.read_block(block)?;
+            let (header_type, header, _) = message_header(&metadata)?;
+            if header_type != HEADER_DICTIONARY_BATCH {
+                return format_error("Arrow dictionary block holds another message type");
+            }
+            apply_dictionary(&mut reader.dictionaries, header, &body, &reader.fields)?;
+        }
+        Ok(reader)
+    }
+
+    /// Schema of the columns in the file
+    pub fn schema(&self) -> Vec<Field> {
+        self.fields.iter().map(ArrowField::field).collect()
+    }
+
+    /// Number of record batches
+    pub fn num_batches(&self) -> usize {
+        self.batches.len()
+    }
+
+    /// Read one record batch
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the record batch
+    /// * `columns` - Names of the columns to read, or `None` for all columns
+    pub fn read_batch(&mut self, index: usize, columns: Option<&[&str]>) -> Result<Table> {
+        let projection = projection(&self.fields, columns)?;
+        self.read_projected(index, &projection)
+    }
+
+    /// Read all record batches into a single table
+    pub(super) fn read_all(&mut self, columns: Option<&[&str]>) -> Result<Table> {
+        let projection = projection(&self.fields, columns)?;
+        let mut table = empty_table(&self.fields, &projection);
+        for index in 0..self.batches.len() {
+            let batch = self.read_projected(index, &projection)?;
+            table.append(&batch)?;
+        }
+        Ok(table)
+    }
+
+    fn read_projected(&mut self, index: usize, projection: &[usize]) -> Result<Table> {
+        let Some(&block) = self.batches.get(index) else {
+            return Err(IoError::ValidationError(format!(
+                "Record batch {} out of range (file has {})",
+                index,
+                self.batches.len()
+            )));
+        };
+        let (metadata, body) = self.read_block(block)?;
+        let (header_type, header, _) = message_header(&metadata)?;
+        if header_type != HEADER_RECORD_BATCH {
+            return format_error("Arrow record batch block holds another message type");
+        }
+        decode_batch(header, &body, &self.fields, &self.dictionaries, projection)
+    }
+
+    fn read_block(&mut self, block: Block) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.file
+            .seek(SeekFrom::Start(block.offset))
+            .map_err(io_error)?;
+        read_message(&mut self.file)?
+            .ok_or_else(|| IoError::FormatError("Empty Arrow file block".to_string()))
+    }
+}
+
+/// Sequential reader for the Arrow IPC stream format
+///
+/// Record batches are decoded one at a time as the stream is consumed; the
+/// reader is also an iterator over the batches.
+pub struct ArrowStreamReader<R: Read> {
+    reader: R,
+    fields: Vec<ArrowField>,
+    dictionaries: HashMap<i64, Column>,
+    projection: Vec<usize>,
+    finished: bool,
+}
+
+impl<R: Read> ArrowStreamReader<R> {
+    /// Start reading a stream, consuming its schema message
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the stream
+    /// * `columns` - Names of the columns to decode, or `None` for all columns
+    pub fn new(mut reader: R, columns: Option<&[&str]>) -> Result<Self> {
+        let Some((metadata, _)) = read_message(&mut reader)? else {
+            return format_error("Arrow stream is empty");
+        };
+        let (header_type, header, _) = message_header(&metadata)?;
+        if header_type != HEADER_SCHEMA {
+            return format_error("Arrow stream does not start with a schema");
+        }
+        let fields = parse_schema(header)?;
+        let projection = projection(&fields, columns)?;
+        Ok(Self {
+            reader,
+            fields,
+            dictionaries: HashMap::new(),
+            projection,
+            finished: false,
+        })
+    }
+
+    /// Schema of the projected columns
+    pub fn schema(&self) -> Vec<Field> {
+        self.projection
+            .iter()
+            .map(|&i| self.fields[i].field())
+            .collect()
+    }
+
+    /// Read the next record batch, or `None` at the end of the stream
+    pub fn next_batch(&mut self) -> Result<Option<Table>> {
+        while !self.finished {
+            let Some((metadata, body)) = read_message(&mut self.reader)? else {
+                self.finished = true;
+                break;
+            };
+            let (header_type, header, _) = message_header(&metadata)?;
+            match header_type {
+                HEADER_DICTIONARY_BATCH => {
+                    apply_dictionary(&mut self.dictionaries, header, &body, &self.fields)?
+                }
+                HEADER_RECORD_BATCH => {
+                    return decode_batch(
+                        header,
+                        &body,
+                        &self.fields,
+                        &self.dictionaries,
+                        &self.projection,
+                    )
+                    .map(Some)
+                }
+                _ => return format_error("Unexpected message in Arrow stream"),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Read the remaining record batches into a single table
+    pub fn read_all(&mut self) -> Result<Table> {
+        let mut table = empty_table(&self.fields, &self.projection);
+        while let Some(batch) = self.next_batch()? {
+            table.append(&batch)?;
+        }
+        Ok(table)
+    }
+}
+
+impl<R: Read> Iterator for ArrowStreamReader<R> {
+    type Item = Result<Table>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_batch() {
+            Ok(Some(batch)) => Some(Ok(batch)),
+            Ok(None) => None,
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Arrow IPC file and stream writer
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use super::flatbuf::{Field as FbField, Object};
+use super::format::{
+    io_error, message_object, schema_object, write_eos, write_message, BodyBuilder,
+    HEADER_DICTIONARY_BATCH, HEADER_RECORD_BATCH, HEADER_SCHEMA, MAGIC,
+};
+use super::ArrowWriteOptions;
+use crate::columnar::{Column, ColumnData, DataType, Field, Table};
+use crate::error::{IoError, Result};
+
+/// Dictionary built up for a string column across batches
+#[derive(Default)]
+struct Dictionary {
+    lookup: HashMap<String, i32>,
+    values: Vec<String>,
+    /// Number of values already written, `None` before the first batch
+    written: Option<usize>,
+}
+
+impl Dictionary {
+    /// Indices of the column's values, adding new values to the dictionary
+    fn encode(&mut self, column: &Column) -> Result<Vec<i32>> {
+        let ColumnData::Utf8(values) = &column.data else {
+            unreachable!("only string columns are dictionary-encoded");
+        };
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                if !column.is_valid(i) {
+                    return Ok(0);
+                }
+                if let Some(&index) = self.lookup.get(value) {
+                    return Ok(index);
+                }
+                let index = i32::try_from(self.values.len()).map_err(|_| {
+                    IoError::ValidationError(format!(
+                        "Dictionary of column '{}' is too large",
+                        column.name
+                    ))
+                })?;
+                self.lookup.insert(value.clone(), index);
+                self.values.push(value.clone());
+                Ok(index)
+            })
+            .collect()
+    }
+}
+
+/// Position of a message in an Arrow file
+struct Block {
+    offset: u64,
+    metadata_length: usize,
+    body_length: usize,
+}
+
+impl Block {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(24);
+        bytes.extend_from_slice(&(self.offset as i64).to_le_bytes());
+        bytes.extend_from_slice(&(self.metadata_length as i32).to_le_bytes());
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(&(self.body_length as i64).to_le_bytes());
+        bytes
+    }
+}
+
+/// Incremental writer for the Arrow IPC file and stream formats
+///
+/// Every call to [`write_table`](ArrowWriter::write_table) appends one record
+/// batch. [`finish`](ArrowWriter::finish) writes the end-of-stream marker and,
+/// for files, the footer.
+pub struct ArrowWriter<W: Write> {
+    out: W,
+    schema: Vec<Field>,
+    options: ArrowWriteOptions,
+    schema_object: Object,
+    dictionaries: Vec<Option<Dictionary>>,
+    file_format: bool,
+    offset: u64,
+    dictionary_blocks: Vec<Block>,
+    batch_blocks: Vec<Block>,
+}
+
+impl<W: Write> ArrowWriter<W> {
+    /// Start writing an Arrow file (random-access format)
+    pub fn new_file(out: W, schema: &[Field], options: Option<ArrowWriteOptions>) -> Result<Self> {
+        Self::new(out, schema, options, true)
+    }
+
+    /// Start writing an Arrow stream
+    pub fn new_stream(
+        out: W,
+        schema: &[Field],
+        options: Option<ArrowWriteOptions>,
+    ) -> Result<Self> {
+        Self::new(out, schema, options, false)
+    }
+
+    fn new(
+        mut out: W,
+        schema: &[Field],
+        options: Option<ArrowWriteOptions>,
+        file_format: bool,
+    ) -> Result<Self> {
+        let options = options.unwrap_or_default();
+        let dictionaries: Vec<Option<Dictionary>> = schema
+            .iter()
+            .map(|f| {
+                (options.dictionary && f.data_type == DataType::Utf8).then(Dictionary::default)
+            })
+            .collect();
+        let ids: Vec<Option<i64>> = dictionaries
+            .iter()
+            .enumerate()
+            .map(|(i, d)| d.as_ref().map(|_| i as i64))
+            .collect();
+        let schema_object = schema_object(schema, &ids);
+
+        let mut offset = 0;
+        if file_format {
+            out.write_all(MAGIC)
+                .and_then(|_| out.write_all(&[0, 0]))
+                .map_err(io_error)?;
+            offset = 8;
+        }
+        let (metadata_length, _) = write_message(
+            &mut out,
+            &message_object(HEADER_SCHEMA, schema_object.clone(), 0),
+            &[],
+        )?;
+        offset += metadata_length as u64;
+
+        Ok(Self {
+            out,
+            schema: schema.to_vec(),
+            options,
+            schema_object,
+            dictionaries,
+            file_format,
+            offset,
+            dictionary_blocks: Vec::new(),
+            batch_blocks: Vec::new(),
+        })
+    }
+
+    /// Append a table as one record batch
+    ///
+    /// The table's columns must match the writer's schema by name and type.
+    pub fn write_table(&mut self, table: &Table) -> Result<()> {
+        if table.num_columns() != self.schema.len() {
+            return Err(IoError::ValidationError(format!(
+                "Table has {} columns, Arrow schema has {}",
+                table.num_columns(),
+                self.schema.len()
+            )));
+        }
+        for (column, field) in table.columns().iter().zip(&self.schema) {
+            if column.name != field.name || column.data.data_type() != field.data_type {
+                return Err(IoError::ValidationError(format!(
+                    "Column '{}' ({:?}) does not match schema field '{}' ({:?})",
+                    column.name,
+                    column.data.data_type(),
+                    field.name,
+                    field.data_type
+                )));
+            }
+        }
+
+        let mut indices = Vec::with_capacity(table.num_columns());
+        for (i, column) in table.columns().iter().enumerate() {
+            let Some(dictionary) = self.dictionaries[i].as_mut() else {
+                indices.push(None);
+                continue;
+            };
+            indices.push(Some(dictionary.encode(column)?));
+            // New dictionary entries go out before the batch that uses them
+            let written = dictionary.written.unwrap_or(0);
+            if dictionary.written.is_none() || dictionary.values.len() > written {
+                let is_delta = dictionary.written.is_some();
+                let new_values = dictionary.values[written..].to_vec();
+                dictionary.written = Some(dictionary.values.len());
+                self.write_dictionary(i as i64, new_values, is_delta)?;
+            }
+        }
+
+        let mut body = BodyBuilder::new(self.options.compression);
+        for (column, indices) in table.columns().iter().zip(&indices) {
+            body.column(column, indices.as_deref())?;
+        }
+        let (batch, body) = body.finish(table.num_rows());
+        let message = message_object(HEADER_RECORD_BATCH, batch, body.len());
+        let block = self.write(&message, &body)?;
+        self.batch_blocks.push(block);
+        Ok(())
+    }
+
+    /// Finish the stream or file and return the underlying writer
+    pub fn finish(mut self) -> Result<W> {
+        write_eos(&mut self.out)?;
+        if self.file_format {
+            let blocks = |blocks: &[Block]| {
+                FbField::structs(
+                    blocks.iter().flat_map(Block::to_bytes).collect(),
+                    blocks.len(),
+                )
+            };
+            let footer = Object::Table(vec![
+                (0, FbField::i16(4)),
+                (1, FbField::Child(self.schema_object.clone())),
+                (2, blocks(&self.dictionary_blocks)),
+                (3, blocks(&self.batch_blocks)),
+            ])
+            .finish();
+            self.out
+                .write_all(&footer)
+                .and_then(|_| self.out.write_all(&(footer.len() as i32).to_le_bytes()))
+                .and_then(|_| self.out.write_all(MAGIC))
+                .map_err(io_error)?;
+        }
+        self.out.flush().map_err(io_error)?;
+        Ok(self.out)
+    }
+
+    fn write_dictionary(&mut self, id: i64, values: Vec<String>, is_delta: bool) -> Result<()> {
+        let column = Column::new("values", ColumnData::Utf8(values));
+        let mut body = BodyBuilder::new(self.options.compression);
+        body.column(&column, None)?;
+        let (batch, body) = body.finish(column.len());
+        let header = Object::Table(vec![
+            (0, FbField::i64(id)),
+            (1, FbField::Child(batch)),
+            (2, FbField::bool(is_delta)),
+        ]);
+        let message = message_object(HEADER_DICTIONARY_BATCH, header, body.len());
+        let block = self.write(&message, &body)?;
+        self.dictionary_blocks.push(block);
+        Ok(())
+    }
+
+    fn write(&mut self, message: &Object, body: &[u8]) -> Result<Block> {
+        let (metadata_length, body_length) = write_message(&mut self.out, message, body)?;
+        let block = Block {
+            offset: self.offset,
+            metadata_length,
+            body_length,
+        };
+        self.offset += (metadata_length + body_length) as u64;
+        Ok(block)
+    }
+}
@@ -0,0 +1,811 @@
+//! In-memory columnar tables
+//!
+//! This module provides the column-oriented table shared by the [`parquet`]
+//! and [`arrow`] modules. A [`Table`] is a list of equally long, typed
+//! [`Column`]s, each with an optional validity mask marking null entries.
+//! Columns convert to and from `ndarray` arrays, and whole tables convert to
+//! and from `scirs2_core::array::RecordArray`.
+//!
+//! [`parquet`]: crate::parquet
+//! [`arrow`]: crate::arrow
+//!
+//! # Example
+//!
+//! ```
+//! use ndarray::array;
+//! use scirs2_io::columnar::{Column, ColumnData, Table};
+//!
+//! let table = Table::new(vec![
+//!     Column::from_array("x", &array![1.0, 2.0, 3.0]),
+//!     Column::new("label", ColumnData::Utf8(vec!["a".into(), "b".into(), "c".into()])),
+//! ])
+//! .unwrap();
+//!
+//! let x = table.column("x").unwrap().to_array::<f64>().unwrap();
+//! assert_eq!(x, array![1.0, 2.0, 3.0]);
+//! ```
+
+use std::collections::HashSet;
+
+use ndarray::{Array1, Array2, ArrayBase, Data, Ix1};
+use scirs2_core::array::{FieldValue, Record, RecordArray};
+
+use crate::error::{IoError, Result};
+
+/// Logical type of a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataType {
+    /// Boolean values
+    Boolean,
+    /// 8-bit signed integers
+    Int8,
+    /// 16-bit signed integers
+    Int16,
+    /// 32-bit signed integers
+    Int32,
+    /// 64-bit signed integers
+    Int64,
+    /// 8-bit unsigned integers
+    UInt8,
+    /// 16-bit unsigned integers
+    UInt16,
+    /// 32-bit unsigned integers
+    UInt32,
+    /// 64-bit unsigned integers
+    UInt64,
+    /// Single-precision floating point
+    Float32,
+    /// Double-precision floating point
+    Float64,
+    /// UTF-8 strings
+    Utf8,
+    /// Arbitrary byte strings
+    Binary,
+}
+
+/// Name, type and nullability of a column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// Column name
+    pub name: String,
+    /// Column type
+    pub data_type: DataType,
+    /// Whether the column may contain nulls
+    pub nullable: bool,
+}
+
+impl Field {
+    /// Create a new field
+    pub fn new(name: &str, data_type: DataType, nullable: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            data_type,
+            nullable,
+        }
+    }
+}
+
+/// Values of a column
+///
+/// Entries marked null by the column's validity mask hold an unspecified
+/// placeholder value (zero, `false` or an empty string).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    /// Boolean values
+    Boolean(Vec<bool>),
+    /// 8-bit signed integers
+    Int8(Vec<i8>),
+    /// 16-bit signed integers
+    Int16(Vec<i16>),
+    /// 32-bit signed integers
+    Int32(Vec<i32>),
+    /// 64-bit signed integers
+    Int64(Vec<i64>),
+    /// 8-bit unsigned integers
+    UInt8(Vec<u8>),
+    /// 16-bit unsigned integers
+    UInt16(Vec<u16>),
+    /// 32-bit unsigned integers
+    UInt32(Vec<u32>),
+    /// 64-bit unsigned integers
+    UInt64(Vec<u64>),
+    /// Single-precision floating point
+    Float32(Vec<f32>),
+    /// Double-precision floating point
+    Float64(Vec<f64>),
+    /// UTF-8 strings
+    Utf8(Vec<String>),
+    /// Arbitrary byte strings
+    Binary(Vec<Vec<u8>>),
+}
+
+/// Apply an expression to the vector inside any `ColumnData` variant
+macro_rules! with_values {
+    ($data:expr, $v:ident => $body:expr) => {
+        match $data {
+            ColumnData::Boolean($v) => $body,
+            ColumnData::Int8($v) => $body,
+            ColumnData::Int16($v) => $body,
+            ColumnData::Int32($v) => $body,
+            ColumnData::Int64($v) => $body,
+            ColumnData::UInt8($v) => $body,
+            ColumnData::UInt16($v) => $body,
+            ColumnData::UInt32($v) => $body,
+            ColumnData::UInt64($v) => $body,
+            ColumnData::Float32($v) => $body,
+            ColumnData::Float64($v) => $body,
+            ColumnData::Utf8($v) => $body,
+            ColumnData::Binary($v) => $body,
+        }
+    };
+}
+
+impl ColumnData {
+    /// Create an empty column of the given type
+    pub fn empty(data_type: DataType) -> Self {
+        match data_type {
+            DataType::Boolean => ColumnData::Boolean(Vec::new()),
+            DataType::Int8 => ColumnData::Int8(Vec::new()),
+            DataType::Int16 => ColumnData::Int16(Vec::new()),
+            DataType::Int32 => ColumnData::Int32(Vec::new()),
+            DataType::Int64 => ColumnData::Int64(Vec::new()),
+            DataType::UInt8 => ColumnData::UInt8(Vec::new()),
+            DataType::UInt16 => ColumnData::UInt16(Vec::new()),
+            DataType::UInt32 => ColumnData::UInt32(Vec::new()),
+            DataType::UInt64 => ColumnData::UInt64(Vec::new()),
+            DataType::Float32 => ColumnData::Float32(Vec::new()),
+            DataType::Float64 => ColumnData::Float64(Vec::new()),
+            DataType::Utf8 => ColumnData::Utf8(Vec::new()),
+            DataType::Binary => ColumnData::Binary(Vec::new()),
+        }
+    }
+
+    /// Type of the values
+    pub fn data_type(&self) -> DataType {
+        match self {
+            ColumnData::Boolean(_) => DataType::Boolean,
+            ColumnData::Int8(_) => DataType::Int8,
+            ColumnData::Int16(_) => DataType::Int16,
+            ColumnData::Int32(_) => DataType::Int32,
+            ColumnData::Int64(_) => DataType::Int64,
+            ColumnData::UInt8(_) => DataType::UInt8,
+            ColumnData::UInt16(_) => DataType::UInt16,
+            ColumnData::UInt32(_) => DataType::UInt32,
+            ColumnData::UInt64(_) => DataType::UInt64,
+            ColumnData::Float32(_) => DataType::Float32,
+            ColumnData::Float64(_) => DataType::Float64,
+            ColumnData::Utf8(_) => DataType::Utf8,
+            ColumnData::Binary(_) => DataType::Binary,
+        }
+    }
+
+    /// Number of values
+    pub fn len(&self) -> usize {
+        with_values!(self, v => v.len())
+    }
+
+    /// Whether there are no values
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copy of the values in `offset..offset + len`
+    fn slice(&self, offset: usize, len: usize) -> ColumnData {
+        let range = offset..offset + len;
+        match self {
+            ColumnData::Boolean(v) => ColumnData::Boolean(v[range].to_vec()),
+            ColumnData::Int8(v) => ColumnData::Int8(v[range].to_vec()),
+            ColumnData::Int16(v) => ColumnData::Int16(v[range].to_vec()),
+            ColumnData::Int32(v) => ColumnData::Int32(v[range].to_vec()),
+            ColumnData::Int64(v) => ColumnData::Int64(v[range].to_vec()),
+            ColumnData::UInt8(v) => ColumnData::UInt8(v[range].to_vec()),
+            ColumnData::UInt16(v) => ColumnData::UInt16(v[range].to_vec()),
+            ColumnData::UInt32(v) => ColumnData::UInt32(v[range].to_vec()),
+            ColumnData::UInt64(v) => ColumnData::UInt64(v[range].to_vec()),
+            ColumnData::Float32(v) => ColumnData::Float32(v[range].to_vec()),
+            ColumnData::Float64(v) => ColumnData::Float64(v[range].to_vec()),
+            ColumnData::Utf8(v) => ColumnData::Utf8(v[range].to_vec()),
+            ColumnData::Binary(v) => ColumnData::Binary(v[range].to_vec()),
+        }
+    }
+
+    /// Append the values of another column of the same type
+    pub(crate) fn extend_from(&mut self, other: &ColumnData) -> Result<()> {
+        match (self, other) {
+            (ColumnData::Boolean(a), ColumnData::Boolean(b)) => a.extend_from_slice(b),
+            (ColumnData::Int8(a), ColumnData::Int8(b)) => a.extend_from_slice(b),
+            (ColumnData::Int16(a), ColumnData::Int16(b)) => a.extend_from_slice(b),
+            (ColumnData::Int32(a), ColumnData::Int32(b)) => a.extend_from_slice(b),
+            (ColumnData::Int64(a), ColumnData::Int64(b)) => a.extend_from_slice(b),
+            (ColumnData::UInt8(a), ColumnData::UInt8(b)) => a.extend_from_slice(b),
+            (ColumnData::UInt16(a), ColumnData::UInt16(b)) => a.extend_from_slice(b),
+            (ColumnData::UInt32(a), ColumnData::UInt32(b)) => a.extend_from_slice(b),
+            (ColumnData::UInt64(a), ColumnData::UInt64(b)) => a.extend_from_slice(b),
+            (ColumnData::Float32(a), ColumnData::Float32(b)) => a.extend_from_slice(b),
+            (ColumnData::Float64(a), ColumnData::Float64(b)) => a.extend_from_slice(b),
+            (ColumnData::Utf8(a), ColumnData::Utf8(b)) => a.extend_from_slice(b),
+            (ColumnData::Binary(a), ColumnData::Binary(b)) => a.extend_from_slice(b),
+            (a, b) => {
+                return Err(IoError::ValidationError(format!(
+                    "Cannot append a {:?} column to a {:?} column",
+                    b.data_type(),
+                    a.data_type()
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    /// Gather values by index, using a placeholder where the index is `None`
+    pub(crate) fn take(&self, indices: &[Option<usize>]) -> Result<ColumnData> {
+        fn gather<T: Clone + Default>(values: &[T], indices: &[Option<usize>]) -> Result<Vec<T>> {
+            indices
+                .iter()
+                .map(|index| match index {
+                    None => Ok(T::default()),
+                    Some(i) => values.get(*i).cloned().ok_or_else(|| {
+                        IoError::FormatError(format!(
+                            "Index {} out of range for {} values",
+                            i,
+                            values.len()
+                        ))
+                    }),
+                })
+                .collect()
+        }
+        Ok(match self {
+            ColumnData::Boolean(v) => ColumnData::Boolean(gather(v, indices)?),
+            ColumnData::Int8(v) => ColumnData::Int8(gather(v, indices)?),
+            ColumnData::Int16(v) => ColumnData::Int16(gather(v, indices)?),
+            ColumnData::Int32(v) => ColumnData::Int32(gather(v, indices)?),
+            ColumnData::Int64(v) => ColumnData::Int64(gather(v, indices)?),
+            ColumnData::UInt8(v) => ColumnData::UInt8(gather(v, indices)?),
+            ColumnData::UInt16(v) => ColumnData::UInt16(gather(v, indices)?),
+            ColumnData::UInt32(v) => ColumnData::UInt32(gather(v, indices)?),
+            ColumnData::UInt64(v) => ColumnData::UInt64(gather(v, indices)?),
+            ColumnData::Float32(v) => ColumnData::Float32(gather(v, indices)?),
+            ColumnData::Float64(v) => ColumnData::Float64(gather(v, indices)?),
+            ColumnData::Utf8(v) => ColumnData::Utf8(gather(v, indices)?),
+            ColumnData::Binary(v) => ColumnData::Binary(gather(v, indices)?),
+        })
+    }
+
+    /// Value at `index` as `f64`, if the column is numeric or boolean
+    fn value_as_f64(&self, index: usize) -> Option<f64> {
+        Some(match self {
+            ColumnData::Boolean(v) => v[index] as u8 as f64,
+            ColumnData::Int8(v) => v[index] as f64,
+            ColumnData::Int16(v) => v[index] as f64,
+            ColumnData::Int32(v) => v[index] as f64,
+            ColumnData::Int64(v) => v[index] as f64,
+            ColumnData::UInt8(v) => v[index] as f64,
+            ColumnData::UInt16(v) => v[index] as f64,
+            ColumnData::UInt32(v) => v[index] as f64,
+            ColumnData::UInt64(v) => v[index] as f64,
+            ColumnData::Float32(v) => v[index] as f64,
+            ColumnData::Float64(v) => v[index],
+            ColumnData::Utf8(_) | ColumnData::Binary(_) => return None,
+        })
+    }
+
+    /// Value at `index` as a record field value
+    fn field_value(&self, index: usize) -> Result<FieldValue> {
+        Ok(match self {
+            ColumnData::Boolean(v) => FieldValue::Bool(v[index]),
+            ColumnData::Int8(v) => FieldValue::Int8(v[index]),
+            ColumnData::Int16(v) => FieldValue::Int16(v[index]),
+            ColumnData::Int32(v) => FieldValue::Int32(v[index]),
+            ColumnData::Int64(v) => FieldValue::Int64(v[index]),
+            ColumnData::UInt8(v) => FieldValue::UInt8(v[index]),
+            ColumnData::UInt16(v) => FieldValue::UInt16(v[index]),
+            ColumnData::UInt32(v) => FieldValue::UInt32(v[index]),
+            ColumnData::UInt64(v) => FieldValue::UInt64(v[index]),
+            ColumnData::Float32(v) => FieldValue::Float32(v[index]),
+            ColumnData::Float64(v) => FieldValue::Float64(v[index]),
+            ColumnData::Utf8(v) => FieldValue::String(v[index].clone()),
+            ColumnData::Binary(_) => {
+                return Err(IoError::ValidationError(
+                    "Binary columns cannot be stored in a RecordArray".to_string(),
+                ))
+            }
+        })
+    }
+}
+
+/// Element types that can be stored in a [`Column`]
+pub trait ColumnElement: Clone {
+    /// Column type holding this element type
+    const DATA_TYPE: DataType;
+
+    /// Wrap values into column data
+    fn into_column_data(values: Vec<Self>) -> ColumnData;
+
+    /// Borrow the values of column data of this element type
+    fn values(data: &ColumnData) -> Option<&[Self]>;
+}
+
+macro_rules! impl_column_element {
+    ($($t:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl ColumnElement for $t {
+                const DATA_TYPE: DataType = DataType::$variant;
+
+                fn into_column_data(values: Vec<Self>) -> ColumnData {
+                    ColumnData::$variant(values)
+                }
+
+                fn values(data: &ColumnData) -> Option<&[Self]> {
+                    match data {
+                        ColumnData::$variant(v) => Some(v),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_column_element!(
+    bool => Boolean,
+    i8 => Int8,
+    i16 => Int16,
+    i32 => Int32,
+    i64 => Int64,
+    u8 => UInt8,
+    u16 => UInt16,
+    u32 => UInt32,
+    u64 => UInt64,
+    f32 => Float32,
+    f64 => Float64,
+    String => Utf8,
+    Vec<u8> => Binary,
+);
+
+/// A named column with an optional validity mask
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    /// Column name
+    pub name: String,
+    /// Column values
+    pub data: ColumnData,
+    /// Validity mask (`false` marks a null entry); `None` means no nulls
+    pub validity: Option<Vec<bool>>,
+}
+
+impl Column {
+    /// Create a column without nulls
+    pub fn new(name: &str, data: ColumnData) -> Self {
+        Self {
+            name: name.to_string(),
+            data,
+            validity: None,
+        }
+    }
+
+    /// Create a column with a validity mask (`false` marks a null entry)
+    pub fn with_validity(name: &str, data: ColumnData, validity: Vec<bool>) -> Result<Self> {
+        if validity.len() != data.len() {
+            return Err(IoError::ValidationError(format!(
+                "Validity mask of column '{}' has {} entries, expected {}",
+                name,
+                validity.len(),
+                data.len()
+            )));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            data,
+            validity: Some(validity),
+        })
+    }
+
+    /// Create a column from a one-dimensional array
+    pub fn from_array<T, S>(name: &str, array: &ArrayBase<S, Ix1>) -> Self
+    where
+        T: ColumnElement,
+        S: Data<Elem = T>,
+    {
+        Self::new(name, T::into_column_data(array.iter().cloned().collect()))
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the column has no entries
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Name, type and nullability of the column
+    pub fn field(&self) -> Field {
+        Field::new(&self.name, self.data.data_type(), self.validity.is_some())
+    }
+
+    /// Whether entry `index` is not null
+    pub fn is_valid(&self, index: usize) -> bool {
+        self.validity.as_ref().is_none_or(|v| v[index])
+    }
+
+    /// Number of null entries
+    pub fn null_count(&self) -> usize {
+        self.validity
+            .as_ref()
+            .map_or(0, |v| v.iter().filter(|&&valid| !valid).count())
+    }
+
+    /// Copy the values into an array of their exact element type
+    ///
+    /// Fails if the column holds another type or contains nulls.
+    pub fn to_array<T: ColumnElement>(&self) -> Result<Array1<T>> {
+        let values = T::values(&self.data).ok_or_else(|| {
+            IoError::ValidationError(format!(
+                "Column '{}' has type {:?}, not {:?}",
+                self.name,
+                self.data.data_type(),
+                T::DATA_TYPE
+            ))
+        })?;
+        if self.null_count() > 0 {
+            return Err(IoError::ValidationError(format!(
+                "Column '{}' contains {} nulls",
+                self.name,
+                self.null_count()
+            )));
+        }
+        Ok(Array1::from(values.to_vec()))
+    }
+
+    /// Convert a numeric or boolean column to `f64`, mapping nulls to NaN
+    pub fn to_f64_array(&self) -> Result<Array1<f64>> {
+        (0..self.len())
+            .map(|i| {
+                if !self.is_valid(i) {
+                    return Ok(f64::NAN);
+                }
+                self.data.value_as_f64(i).ok_or_else(|| {
+                    IoError::ValidationError(format!(
+                        "Column '{}' of type {:?} is not numeric",
+                        self.name,
+                        self.data.data_type()
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Copy of the entries in `offset..offset + len`
+    fn slice(&self, offset: usize, len: usize) -> Column {
+        Column {
+            name: self.name.clone(),
+            data: self.data.slice(offset, len),
+            validity: self
+                .validity
+                .as_ref()
+                .map(|v| v[offset..offset + len].to_vec()),
+        }
+    }
+}
+
+/// A table of equally long named columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    columns: Vec<Column>,
+    num_rows: usize,
+}
+
+impl Table {
+    /// Create a table, checking that the columns have unique names and equal lengths
+    pub fn new(columns: Vec<Column>) -> Result<Self> {
+        let num_rows = columns.first().map_or(0, Column::len);
+        let mut names = HashSet::new();
+        for column in &columns {
+            if column.len() != num_rows {
+                return Err(IoError::ValidationError(format!(
+                    "Column '{}' has {} rows, expected {}",
+                    column.name,
+                    column.len(),
+                    num_rows
+                )));
+            }
+            if let Some(validity) = &column.validity {
+                if validity.len() != num_rows {
+                    return Err(IoError::ValidationError(format!(
+                        "Validity mask of column '{}' has {} entries, expected {}",
+                        column.name,
+                        validity.len(),
+                        num_rows
+                    )));
+                }
+            }
+            if !names.insert(column.name.as_str()) {
+                return Err(IoError::ValidationError(format!(
+                    "Duplicate column name '{}'",
+                    column.name
+                )));
+            }
+        }
+        Ok(Self { columns, num_rows })
+    }
+
+    /// Create a table without rows with the given columns
+    pub fn empty(schema: &[Field]) -> Self {
+        let columns = schema
+            .iter()
+            .map(|field| Column {
+                name: field.name.clone(),
+                data: ColumnData::empty(field.data_type),
+                validity: field.nullable.then(Vec::new),
+            })
+            .collect();
+        Self {
+            columns,
+            num_rows: 0,
+        }
+    }
+
+    /// Number of rows
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Number of columns
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// All columns
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Column with the given name
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    /// Names of the columns
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    /// Fields describing the columns
+    pub fn schema(&self) -> Vec<Field> {
+        self.columns.iter().map(Column::field).collect()
+    }
+
+    /// Take the columns out of the table
+    pub fn into_columns(self) -> Vec<Column> {
+        self.columns
+    }
+
+    /// New table holding only the named columns, in the given order
+    pub fn select(&self, names: &[&str]) -> Result<Table> {
+        let columns = names
+            .iter()
+            .map(|name| {
+                self.column(name)
+                    .cloned()
+                    .ok_or_else(|| IoError::ValidationError(format!("No column named '{}'", name)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Table::new(columns)
+    }
+
+    /// New table holding the rows in `offset..offset + len`
+    pub fn slice(&self, offset: usize, len: usize) -> Result<Table> {
+        if offset + len > self.num_rows {
+            return Err(IoError::ValidationError(format!(
+                "Rows {}..{} are out of range for a table with {} rows",
+                offset,
+                offset + len,
+                self.num_rows
+            )));
+        }
+        Ok(Table {
+            columns: self.columns.iter().map(|c| c.slice(offset, len)).collect(),
+            num_rows: len,
+        })
+    }
+
+    /// Append the rows of a table with the same column names and types
+    pub fn append(&mut self, other: &Table) -> Result<()> {
+        if self.column_names() != other.column_names() {
+            return Err(IoError::ValidationError(
+                "Cannot append a table with different columns".to_string(),
+            ));
+        }
+        for (column, addition) in self.columns.iter_mut().zip(&other.columns) {
+            column.data.extend_from(&addition.data)?;
+            match (&mut column.validity, &addition.validity) {
+                (Some(a), Some(b)) => a.extend_from_slice(b),
+                (Some(a), None) => a.resize(a.len() + addition.len(), true),
+                (None, Some(b)) => {
+                    let mut validity = vec![true; self.num_rows];
+                    validity.extend_from_slice(b);
+                    column.validity = Some(validity);
+                }
+                (None, None) => {}
+            }
+        }
+        self.num_rows += other.num_rows;
+        Ok(())
+    }
+
+    /// Stack all columns into a `rows x columns` array of `f64`
+    ///
+    /// Every column must be numeric or boolean; nulls become NaN.
+    pub fn to_array2_f64(&self) -> Result<Array2<f64>> {
+        let mut array = Array2::zeros((self.num_rows, self.columns.len()));
+        for (j, column) in self.columns.iter().enumerate() {
+            array.column_mut(j).assign(&column.to_f64_array()?);
+        }
+        Ok(array)
+    }
+
+    /// Convert the table into a `RecordArray` with one record per row
+    ///
+    /// Nulls in floating-point columns become NaN; other columns must not
+    /// contain nulls, as record fields cannot represent them.
+    pub fn to_record_array(&self) -> Result<RecordArray> {
+        let mut records = Vec::with_capacity(self.num_rows);
+        for row in 0..self.num_rows {
+            let mut record = Record::new();
+            for column in &self.columns {
+                let value = if column.is_valid(row) {
+                    column.data.field_value(row)?
+                } else {
+                    match column.data {
+                        ColumnData::Float32(_) => FieldValue::Float32(f32::NAN),
+                        ColumnData::Float64(_) => FieldValue::Float64(f64::NAN),
+                        _ => {
+                            return Err(IoError::ValidationError(format!(
+                                "Column '{}' contains nulls, which RecordArray cannot represent",
+                                column.name
+                            )))
+                        }
+                    }
+                };
+                record.add_field(&column.name, value);
+            }
+            records.push(record);
+        }
+        RecordArray::new(records).map_err(|e| IoError::ValidationError(e.to_string()))
+    }
+
+    /// Build a table from a `RecordArray`
+    ///
+    /// Column types are taken from the first record; every record must use
+    /// the same type for a field.
+    pub fn from_record_array(records: &RecordArray) -> Result<Table> {
+        let mut columns = Vec::with_capacity(records.field_names.len());
+        for name in &records.field_names {
+            let values = records
+                .get_field_values(name)
+                .map_err(|e| IoError::ValidationError(e.to_string()))?;
+            columns.push(Column::new(name, field_values_to_data(name, values)?));
+        }
+        Table::new(columns)
+    }
+}
+
+/// Collect record field values of a single type into column data
+fn field_values_to_data(name: &str, values: Vec<FieldValue>) -> Result<ColumnData> {
+    macro_rules! collect {
+        ($variant:ident, $data:ident) => {{
+            let mut out = Vec::with_capacity(values.len());
+            for value in values {
+                match value {
+                    FieldValue::$variant(v) => out.push(v),
+                    other => {
+                        return Err(IoError::ValidationError(format!(
+                            "Field '{}' mixes value types ({} is not {})",
+                            name,
+                            other,
+                            stringify!($variant)
+                        )))
+                    }
+                }
+            }
+            ColumnData::$data(out)
+        }};
+    }
+
+    Ok(match values.first() {
+        None | Some(FieldValue::Float64(_)) => collect!(Float64, Float64),
+        Some(FieldValue::Bool(_)) => collect!(Bool, Boolean),
+        Some(FieldValue::Int8(_)) => collect!(Int8, Int8),
+        Some(FieldValue::Int16(_)) => collect!(Int16, Int16),
+        Some(FieldValue::Int32(_)) => collect!(Int32, Int32),
+        Some(FieldValue::Int64(_)) => collect!(Int64, Int64),
+        Some(FieldValue::UInt8(_)) => collect!(UInt8, UInt8),
+        Some(FieldValue::UInt16(_)) => collect!(UInt16, UInt16),
+        Some(FieldValue::UInt32(_)) => collect!(UInt32, UInt32),
+        Some(FieldValue::UInt64(_)) => collect!(UInt64, UInt64),
+        Some(FieldValue::Float32(_)) => collect!(Float32, Float32),
+        Some(FieldValue::String(_)) => collect!(String, Utf8),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    fn sample() -> Table {
+        Table::new(vec![
+            Column::from_array("id", &array![1i64, 2, 3]),
+            Column::with_validity(
+                "value",
+                ColumnData::Float64(vec![0.5, 0.0, 2.5]),
+                vec![true, false, true],
+            )
+            .unwrap(),
+            Column::new(
+                "name",
+                ColumnData::Utf8(vec!["a".into(), "b".into(), "c".into()]),
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_table_construction() {
+        let table = sample();
+        assert_eq!(table.num_rows(), 3);
+        assert_eq!(table.column_names(), vec!["id", "value", "name"]);
+        assert_eq!(table.column("value").unwrap().null_count(), 1);
+
+        let short = Column::from_array("short", &array![1.0]);
+        assert!(Table::new(vec![short, Column::from_array("x", &array![1.0, 2.0])]).is_err());
+        assert!(Table::new(vec![
+            Column::from_array("x", &array![1.0]),
+            Column::from_array("x", &array![2.0]),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_array_conversions() {
+        let table = sample();
+        assert_eq!(
+            table.column("id").unwrap().to_array::<i64>().unwrap(),
+            array![1, 2, 3]
+        );
+        assert!(table.column("id").unwrap().to_array::<f64>().is_err());
+        assert!(table.column("value").unwrap().to_array::<f64>().is_err());
+
+        let values = table.column("value").unwrap().to_f64_array().unwrap();
+        assert!(values[1].is_nan());
+        assert!(table.to_array2_f64().is_err());
+
+        let numeric = table.select(&["value", "id"]).unwrap();
+        let array = numeric.to_array2_f64().unwrap();
+        assert_eq!(array.shape(), &[3, 2]);
+        assert_eq!(array[[2, 1]], 3.0);
+    }
+
+    #[test]
+    fn test_slice_and_append() {
+        let table = sample();
+        let mut head = table.slice(0, 1).unwrap();
+        head.append(&table.slice(1, 2).unwrap()).unwrap();
+        assert_eq!(head, table);
+        assert!(table.slice(2, 2).is_err());
+    }
+
+    #[test]
+    fn test_record_array_roundtrip() {
+        let table = sample();
+        let records = table.to_record_array().unwrap();
+        assert_eq!(records.num_records(), 3);
+
+        let back = Table::from_record_array(&records).unwrap();
+        assert_eq!(back.column("id"), table.column("id"));
+        assert_eq!(back.column("name"), table.column("name"));
+        let values = back.column("value").unwrap().to_array::<f64>().unwrap();
+        assert!(values[1].is_nan());
+        assert_eq!(values[2], 2.5);
+    }
+}
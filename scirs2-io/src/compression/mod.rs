@@ -6,7 +6,7 @@
 //!
 //! ## Features
 //!
-//! - Multiple compression algorithms (GZIP, ZSTD, LZ4, BZIP2)
+//! - Multiple compression algorithms (GZIP, ZSTD, LZ4, BZIP2, Snappy)
 //! - Configurable compression levels
 //! - Memory-efficient compression of large datasets
 //! - Metadata preservation during compression
@@ -51,6 +51,7 @@ use zstd::{decode_all, encode_all};
 
 // Re-export ndarray submodule
 pub mod ndarray;
+mod snappy;
 
 use crate::error::{IoError, Result};
 
@@ -65,6 +66,8 @@ pub enum CompressionAlgorithm {
     Lz4,
     /// BZIP2 compression (high compression ratio, slower speed)
     Bzip2,
+    /// Snappy raw block compression (very fast, used by Parquet)
+    Snappy,
 }
 
 impl CompressionAlgorithm {
@@ -75,6 +78,7 @@ impl CompressionAlgorithm {
             CompressionAlgorithm::Zstd => "zst",
             CompressionAlgorithm::Lz4 => "lz4",
             CompressionAlgorithm::Bzip2 => "bz2",
+            CompressionAlgorithm::Snappy => "snappy",
         }
     }
 
@@ -85,6 +89,7 @@ impl CompressionAlgorithm {
             "zst" | "zstd" => Some(CompressionAlgorithm::Zstd),
             "lz4" => Some(CompressionAlgorithm::Lz4),
             "bz2" | "bzip2" => Some(CompressionAlgorithm::Bzip2),
+            "snappy" => Some(CompressionAlgorithm::Snappy),
             _ => None,
         }
     }
@@ -110,6 +115,8 @@ fn normalize_compression_level(level: Option<u32>, algorithm: CompressionAlgorit
         }
         CompressionAlgorithm::Lz4 => Ok(level),
         CompressionAlgorithm::Bzip2 => Ok(level),
+        // Snappy has no compression levels
+        CompressionAlgorithm::Snappy => Ok(level),
     }
 }
 
@@ -165,6 +172,7 @@ pub fn compress_data(
                 .map_err(|e| IoError::CompressionError(e.to_string()))?;
             Ok(compressed)
         }
+        CompressionAlgorithm::Snappy => snappy::compress(data),
     }
 }
 
@@ -208,6 +216,7 @@ pub fn decompress_data(data: &[u8], algorithm: CompressionAlgorithm) -> Result<V
                 .map_err(|e| IoError::DecompressionError(e.to_string()))?;
             Ok(decompressed)
         }
+        CompressionAlgorithm::Snappy => snappy::decompress(data),
     }
 }
 
@@ -424,5 +433,13 @@ pub fn algorithm_info(algorithm: CompressionAlgorithm) -> CompressionInfo {
             decompression_speed: 4,
             file_extension: "bz2".to_string(),
         },
+        CompressionAlgorithm::Snappy => CompressionInfo {
+            name: "Snappy".to_string(),
+            description: "Very fast compression algorithm with modest compression ratio, used in columnar formats".to_string(),
+            typical_compression_ratio: 1.7,
+            compression_speed: 9,
+            decompression_speed: 9,
+            file_extension: "snappy".to_string(),
+        },
     }
 }
@@ -143,6 +143,7 @@ where
         "Zstd" => CompressionAlgorithm::Zstd,
         "Lz4" => CompressionAlgorithm::Lz4,
         "Bzip2" => CompressionAlgorithm::Bzip2,
+        "Snappy" => CompressionAlgorithm::Snappy,
         _ => {
             return Err(IoError::DecompressionError(format!(
                 "Unknown compression algorithm: {}",
@@ -318,6 +319,7 @@ where
         "Zstd" => CompressionAlgorithm::Zstd,
         "Lz4" => CompressionAlgorithm::Lz4,
         "Bzip2" => CompressionAlgorithm::Bzip2,
+        "Snappy" => CompressionAlgorithm::Snappy,
         _ => {
             return Err(IoError::DecompressionError(format!(
                 "Unknown compression algorithm: {}",
@@ -0,0 +1,85 @@
+//! Snappy raw block format
+//!
+//! The unframed Snappy format used inside Parquet pages: a varint holding the
+//! uncompressed length followed by literal and copy elements. Encoding and
+//! decoding are delegated to the `snap` crate.
+
+use snap::raw::{decompress_len, Decoder, Encoder};
+
+use crate::error::{IoError, Result};
+
+/// Largest number of output bytes a single input byte can expand to
+const MAX_EXPANSION: usize = 64;
+
+/// Compress data into a Snappy raw block
+pub(crate) fn compress(input: &[u8]) -> Result<Vec<u8>> {
+    Encoder::new()
+        .compress_vec(input)
+        .map_err(|e| IoError::CompressionError(format!("Snappy: {}", e)))
+}
+
+/// Decompress a Snappy raw block
+pub(crate) fn decompress(input: &[u8]) -> Result<Vec<u8>> {
+    let error = |msg: String| IoError::DecompressionError(format!("Snappy: {}", msg));
+    // Check the declared length before the decoder allocates it
+    let expected = decompress_len(input).map_err(|e| error(e.to_string()))?;
+    if expected > input.len().saturating_mul(MAX_EXPANSION) {
+        return Err(error(format!(
+            "declared length {} is too large for {} bytes of input",
+            expected,
+            input.len()
+        )));
+    }
+    Decoder::new()
+        .decompress_vec(input)
+        .map_err(|e| error(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut inputs: Vec<Vec<u8>> = vec![
+            Vec::new(),
+            b"a".to_vec(),
+            b"abcabcabcabcabcabcabcabcabcabcabcabc".to_vec(),
+            vec![7u8; 100_000],
+        ];
+        // Pseudo-random bytes mixed with repeated text
+        let mut state = 12345u32;
+        let mut mixed = Vec::new();
+        for i in 0..50_000 {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            mixed.push((state >> 16) as u8);
+            if i % 100 == 0 {
+                mixed.extend_from_slice(b"scientific data ");
+            }
+        }
+        inputs.push(mixed);
+
+        for input in inputs {
+            let compressed = compress(&input).unwrap();
+            assert_eq!(decompress(&compressed).unwrap(), input);
+        }
+        assert!(compress(&[7u8; 100_000]).unwrap().len() < 5_000);
+    }
+
+    #[test]
+    fn test_decompress_reference_block() {
+        // Literal "abcdefghi" followed by a two-byte-offset copy of "ab"
+        let block = [
+            0x0b, 0x20, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', 0x06, 0x09, 0x00,
+        ];
+        assert_eq!(decompress(&block).unwrap(), b"abcdefghiab".to_vec());
+    }
+
+    #[test]
+    fn test_corrupt_input() {
+        assert!(decompress(&[0x05, 0x01, 0x00]).is_err());
+        assert!(decompress(&[0x0a, 0x04, b'a']).is_err());
+        // A preamble claiming 2^35 bytes must not be allocated
+        assert!(decompress(&[0xff, 0xff, 0xff, 0xff, 0x7f]).is_err());
+    }
+}
@@ -9,6 +9,7 @@
 //! - Handling of missing values and type conversions
 //! - Memory-efficient processing of large files
 //! - Column-based I/O operations
+//! - Streaming, chunked reading with type inference and incremental writing
 
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use ndarray::{Array1, Array2};
@@ -19,6 +20,10 @@ use std::path::Path;
 
 use crate::error::{IoError, Result};
 
+mod streaming;
+
+pub use streaming::{CsvChunk, CsvStreamOptions, CsvStreamReader, CsvStreamWriter, TypedColumn};
+
 /// CSV reader configuration
 #[derive(Debug, Clone)]
 pub struct CsvReaderConfig {
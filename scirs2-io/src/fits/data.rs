@@ -0,0 +1,346 @@
+//! Big-endian FITS data arrays
+
+use ndarray::{ArrayD, IxDyn};
+use num_complex::{Complex32, Complex64};
+
+use crate::error::{IoError, Result};
+
+/// Array read from or written to a FITS file
+///
+/// Image HDUs use the numeric variants; table columns may also be logical,
+/// string or variable-length. Unsigned 16/32/64-bit and signed 8-bit values
+/// are stored with the standard `BZERO`/`TZERO` offsets.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FitsArray {
+    /// Unsigned bytes (`BITPIX = 8`, `B`)
+    UInt8(ArrayD<u8>),
+    /// Signed bytes (`B` with zero point -128)
+    Int8(ArrayD<i8>),
+    /// 16-bit integers (`BITPIX = 16`, `I`)
+    Int16(ArrayD<i16>),
+    /// Unsigned 16-bit integers (`I` with zero point 32768)
+    UInt16(ArrayD<u16>),
+    /// 32-bit integers (`BITPIX = 32`, `J`)
+    Int32(ArrayD<i32>),
+    /// Unsigned 32-bit integers (`J` with zero point 2^31)
+    UInt32(ArrayD<u32>),
+    /// 64-bit integers (`BITPIX = 64`, `K`)
+    Int64(ArrayD<i64>),
+    /// Unsigned 64-bit integers (`K` with zero point 2^63)
+    UInt64(ArrayD<u64>),
+    /// Single-precision floats (`BITPIX = -32`, `E`)
+    Float32(ArrayD<f32>),
+    /// Double-precision floats (`BITPIX = -64`, `D`), also the result of scaling
+    Float64(ArrayD<f64>),
+    /// Single-precision complex values (`C`)
+    Complex64(ArrayD<Complex32>),
+    /// Double-precision complex values (`M`)
+    Complex128(ArrayD<Complex64>),
+    /// Logical values (`L`) and bits (`X`)
+    Logical(ArrayD<bool>),
+    /// Character strings (`A`), trailing blanks removed
+    String(ArrayD<String>),
+    /// Variable-length arrays (`P`, `Q`), one 1D array per row
+    VariableLength(Vec<FitsArray>),
+}
+
+/// Apply an expression to the array inside any fixed-size [`FitsArray`] variant
+macro_rules! with_fits_array {
+    ($value:expr, $array:ident => $body:expr, $varlen:ident => $other:expr) => {
+        match $value {
+            FitsArray::UInt8($array) => $body,
+            FitsArray::Int8($array) => $body,
+            FitsArray::Int16($array) => $body,
+            FitsArray::UInt16($array) => $body,
+            FitsArray::Int32($array) => $body,
+            FitsArray::UInt32($array) => $body,
+            FitsArray::Int64($array) => $body,
+            FitsArray::UInt64($array) => $body,
+            FitsArray::Float32($array) => $body,
+            FitsArray::Float64($array) => $body,
+            FitsArray::Complex64($array) => $body,
+            FitsArray::Complex128($array) => $body,
+            FitsArray::Logical($array) => $body,
+            FitsArray::String($array) => $body,
+            FitsArray::VariableLength($varlen) => $other,
+        }
+    };
+}
+
+impl FitsArray {
+    /// Shape of the array; the number of rows for variable-length arrays
+    pub fn shape(&self) -> Vec<usize> {
+        with_fits_array!(self, a => a.shape().to_vec(), rows => vec![rows.len()])
+    }
+
+    /// Convert numeric or logical values to `f64`
+    pub fn to_f64(&self) -> Result<ArrayD<f64>> {
+        Ok(match self {
+            FitsArray::UInt8(a) => a.mapv(f64::from),
+            FitsArray::Int8(a) => a.mapv(f64::from),
+            FitsArray::Int16(a) => a.mapv(f64::from),
+            FitsArray::UInt16(a) => a.mapv(f64::from),
+            FitsArray::Int32(a) => a.mapv(f64::from),
+            FitsArray::UInt32(a) => a.mapv(f64::from),
+            FitsArray::Int64(a) => a.mapv(|x| x as f64),
+            FitsArray::UInt64(a) => a.mapv(|x| x as f64),
+            FitsArray::Float32(a) => a.mapv(f64::from),
+            FitsArray::Float64(a) => a.clone(),
+            FitsArray::Logical(a) => a.mapv(|x| if x { 1.0 } else { 0.0 }),
+            _ => {
+                return Err(IoError::FormatError(
+                    "Complex, string and variable-length arrays cannot be converted to f64"
+                        .to_string(),
+                ))
+            }
+        })
+    }
+
+    /// Reshape a 1D array decoded from a FITS data stream
+    pub(crate) fn reshape(self, shape: &[usize]) -> Result<Self> {
+        fn reshape<T>(array: ArrayD<T>, shape: &[usize]) -> Result<ArrayD<T>> {
+            array
+                .into_shape_with_order(IxDyn(shape))
+                .map_err(|e| IoError::FormatError(format!("Invalid FITS array shape: {}", e)))
+        }
+        Ok(match self {
+            FitsArray::UInt8(a) => FitsArray::UInt8(reshape(a, shape)?),
+            FitsArray::Int8(a) => FitsArray::Int8(reshape(a, shape)?),
+            FitsArray::Int16(a) => FitsArray::Int16(reshape(a, shape)?),
+            FitsArray::UInt16(a) => FitsArray::UInt16(reshape(a, shape)?),
+            FitsArray::Int32(a) => FitsArray::Int32(reshape(a, shape)?),
+            FitsArray::UInt32(a) => FitsArray::UInt32(reshape(a, shape)?),
+            FitsArray::Int64(a) => FitsArray::Int64(reshape(a, shape)?),
+            FitsArray::UInt64(a) => FitsArray::UInt64(reshape(a, shape)?),
+            FitsArray::Float32(a) => FitsArray::Float32(reshape(a, shape)?),
+            FitsArray::Float64(a) => FitsArray::Float64(reshape(a, shape)?),
+            FitsArray::Complex64(a) => FitsArray::Complex64(reshape(a, shape)?),
+            FitsArray::Complex128(a) => FitsArray::Complex128(reshape(a, shape)?),
+            FitsArray::Logical(a) => FitsArray::Logical(reshape(a, shape)?),
+            FitsArray::String(a) => FitsArray::String(reshape(a, shape)?),
+            FitsArray::VariableLength(_) => {
+                return Err(IoError::FormatError(
+                    "Variable-length arrays cannot be reshaped".to_string(),
+                ))
+            }
+        })
+    }
+}
+
+macro_rules! fits_array_from {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl From<ArrayD<$ty>> for FitsArray {
+                fn from(array: ArrayD<$ty>) -> Self {
+                    FitsArray::$variant(array)
+                }
+            }
+        )*
+    };
+}
+
+fits_array_from!(
+    u8 => UInt8, i8 => Int8, i16 => Int16, u16 => UInt16, i32 => Int32, u32 => UInt32,
+    i64 => Int64, u64 => UInt64, f32 => Float32, f64 => Float64, Complex32 => Complex64,
+    Complex64 => Complex128, bool => Logical, String => String
+);
+
+/// Linear scaling and null value of stored integers
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Scaling {
+    pub scale: f64,
+    pub zero: f64,
+    pub null: Option<i64>,
+}
+
+impl Scaling {
+    fn is_identity(&self) -> bool {
+        self.scale == 1.0 && self.zero == 0.0
+    }
+
+    fn is_offset(&self, zero: f64) -> bool {
+        self.scale == 1.0 && self.zero == zero
+    }
+
+    fn apply(&self, raw: i64, value: f64) -> f64 {
+        if self.null == Some(raw) {
+            f64::NAN
+        } else {
+            value * self.scale + self.zero
+        }
+    }
+}
+
+/// Size in bytes of one element of a binary table or image data type
+pub(crate) fn element_size(code: char) -> Option<usize> {
+    match code {
+        'L' | 'B' | 'A' => Some(1),
+        'I' => Some(2),
+        'J' | 'E' => Some(4),
+        'K' | 'D' | 'C' => Some(8),
+        'M' => Some(16),
+        _ => None,
+    }
+}
+
+/// Binary table data type of an image `BITPIX`
+pub(crate) fn bitpix_code(bitpix: i64) -> Result<char> {
+    match bitpix {
+        8 => Ok('B'),
+        16 => Ok('I'),
+        32 => Ok('J'),
+        64 => Ok('K'),
+        -32 => Ok('E'),
+        -64 => Ok('D'),
+        _ => Err(IoError::FormatError(format!("Invalid BITPIX {}", bitpix))),
+    }
+}
+
+/// Image `BITPIX` of a binary table data type
+pub(crate) fn code_bitpix(code: char) -> Option<i64> {
+    match code {
+        'B' => Some(8),
+        'I' => Some(16),
+        'J' => Some(32),
+        'K' => Some(64),
+        'E' => Some(-32),
+        'D' => Some(-64),
+        _ => None,
+    }
+}
+
+fn values<const N: usize, T>(bytes: &[u8], convert: impl Fn([u8; N]) -> T) -> Vec<T> {
+    bytes
+        .chunks_exact(N)
+        .map(|chunk| convert(chunk.try_into().unwrap()))
+        .collect()
+}
+
+fn array<T>(values: Vec<T>) -> ArrayD<T> {
+    ArrayD::from_shape_vec(IxDyn(&[values.len()]), values).unwrap()
+}
+
+/// Decode big-endian values into a 1D array, applying scaling
+///
+/// Integers with the standard unsigned offsets become unsigned arrays,
+/// other scaled values become `Float64` with nulls as NaN.
+pub(crate) fn decode(code: char, bytes: &[u8], scaling: &Scaling) -> Result<FitsArray> {
+    macro_rules! integers {
+        ($ty:ty, $n:expr, $raw:ident, $offset:expr, $unsigned:ident, $flip:expr) => {{
+            let raw: Vec<$ty> = values::<$n, _>(bytes, <$ty>::from_be_bytes);
+            if scaling.is_offset($offset) {
+                FitsArray::$unsigned(array(raw.into_iter().map($flip).collect()))
+            } else if scaling.is_identity() {
+                FitsArray::$raw(array(raw))
+            } else {
+                FitsArray::Float64(array(
+                    raw.into_iter()
+                        .map(|x| scaling.apply(x as i64, x as f64))
+                        .collect(),
+                ))
+            }
+        }};
+    }
+    macro_rules! floats {
+        ($ty:ty, $n:expr, $variant:ident) => {{
+            let raw: Vec<$ty> = values::<$n, _>(bytes, <$ty>::from_be_bytes);
+            if scaling.is_identity() {
+                FitsArray::$variant(array(raw))
+            } else {
+                FitsArray::Float64(array(
+                    raw.into_iter()
+                        .map(|x| f64::from(x) * scaling.scale + scaling.zero)
+                        .collect(),
+                ))
+            }
+        }};
+    }
+
+    Ok(match code {
+        'B' => integers!(u8, 1, UInt8, -128.0, Int8, |x: u8| (x ^ 0x80) as i8),
+        'I' => integers!(i16, 2, Int16, 32768.0, UInt16, |x: i16| (x as u16) ^ 0x8000),
+        'J' => integers!(i32, 4, Int32, 2147483648.0, UInt32, |x: i32| (x as u32)
+            ^ 0x8000_0000),
+        'K' => integers!(i64, 8, Int64, 9223372036854775808.0, UInt64, |x: i64| (x
+            as u64)
+            ^ (1 << 63)),
+        'E' => floats!(f32, 4, Float32),
+        'D' => floats!(f64, 8, Float64),
+        'C' => FitsArray::Complex64(array(values::<8, _>(bytes, |b| {
+            Complex32::new(
+                f32::from_be_bytes(b[..4].try_into().unwrap()),
+                f32::from_be_bytes(b[4..].try_into().unwrap()),
+            )
+        }))),
+        'M' => FitsArray::Complex128(array(values::<16, _>(bytes, |b| {
+            Complex64::new(
+                f64::from_be_bytes(b[..8].try_into().unwrap()),
+                f64::from_be_bytes(b[8..].try_into().unwrap()),
+            )
+        }))),
+        'L' => FitsArray::Logical(array(bytes.iter().map(|&b| b == b'T').collect())),
+        _ => {
+            return Err(IoError::FormatError(format!(
+                "Unsupported FITS data type '{}'",
+                code
+            )))
+        }
+    })
+}
+
+/// Encode a numeric or logical array as big-endian values in C order
+///
+/// Returns the binary table data type, the zero point to record for offset
+/// types, and the encoded bytes.
+pub(crate) fn encode(data: &FitsArray) -> Result<(char, Option<f64>, Vec<u8>)> {
+    fn bytes<T: Copy, const N: usize>(
+        array: &ArrayD<T>,
+        convert: impl Fn(T) -> [u8; N],
+    ) -> Vec<u8> {
+        array.iter().flat_map(|&x| convert(x)).collect()
+    }
+    Ok(match data {
+        FitsArray::UInt8(a) => ('B', None, a.iter().copied().collect()),
+        FitsArray::Int8(a) => (
+            'B',
+            Some(-128.0),
+            a.iter().map(|&x| x as u8 ^ 0x80).collect(),
+        ),
+        FitsArray::Int16(a) => ('I', None, bytes(a, i16::to_be_bytes)),
+        FitsArray::UInt16(a) => ('I', Some(32768.0), bytes(a, |x| (x ^ 0x8000).to_be_bytes())),
+        FitsArray::Int32(a) => ('J', None, bytes(a, i32::to_be_bytes)),
+        FitsArray::UInt32(a) => (
+            'J',
+            Some(2147483648.0),
+            bytes(a, |x| (x ^ 0x8000_0000).to_be_bytes()),
+        ),
+        FitsArray::Int64(a) => ('K', None, bytes(a, i64::to_be_bytes)),
+        FitsArray::UInt64(a) => (
+            'K',
+            Some(9223372036854775808.0),
+            bytes(a, |x| (x ^ (1 << 63)).to_be_bytes()),
+        ),
+        FitsArray::Float32(a) => ('E', None, bytes(a, f32::to_be_bytes)),
+        FitsArray::Float64(a) => ('D', None, bytes(a, f64::to_be_bytes)),
+        FitsArray::Complex64(a) => ('C', None, {
+            a.iter()
+                .flat_map(|c| c.re.to_be_bytes().into_iter().chain(c.im.to_be_bytes()))
+                .collect()
+        }),
+        FitsArray::Complex128(a) => ('M', None, {
+            a.iter()
+                .flat_map(|c| c.re.to_be_bytes().into_iter().chain(c.im.to_be_bytes()))
+                .collect()
+        }),
+        FitsArray::Logical(a) => (
+            'L',
+            None,
+            a.iter().map(|&x| if x { b'T' } else { b'F' }).collect(),
+        ),
+        FitsArray::String(_) | FitsArray::VariableLength(_) => {
+            return Err(IoError::ValidationError(
+                "String and variable-length arrays have no numeric encoding".to_string(),
+            ))
+        }
+    })
+}
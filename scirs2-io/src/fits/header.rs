@@ -0,0 +1,704 @@
+//! FITS header cards and keywords
+
+use std::io::Read;
+
+use num_complex::Complex64;
+
+use crate::error::{IoError, Result};
+
+/// Size of a FITS block in bytes
+pub(crate) const BLOCK_SIZE: usize = 2880;
+const CARD_SIZE: usize = 80;
+
+/// Keywords that describe the data layout and are written by the writer itself
+const STRUCTURAL_KEYWORDS: &[&str] = &[
+    "SIMPLE", "XTENSION", "BITPIX", "EXTEND", "PCOUNT", "GCOUNT", "TFIELDS", "BSCALE", "BZERO",
+    "THEAP", "BLANK", "END",
+];
+const STRUCTURAL_PREFIXES: &[&str] = &[
+    "NAXIS", "TFORM", "TTYPE", "TUNIT", "TDIM", "TZERO", "TSCAL", "TNULL", "TBCOL",
+];
+
+/// Value of a header keyword
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeaderValue {
+    /// Logical value (`T` or `F`)
+    Logical(bool),
+    /// Integer value
+    Integer(i64),
+    /// Floating-point value
+    Float(f64),
+    /// Complex value, written as `(re, im)`
+    Complex(Complex64),
+    /// Character string
+    String(String),
+    /// Keyword present without a value
+    Undefined,
+}
+
+impl HeaderValue {
+    /// The value as a boolean, if it is logical
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            HeaderValue::Logical(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// The value as an integer, if it is integral
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            HeaderValue::Integer(i) => Some(*i),
+            HeaderValue::Float(f) if f.fract() == 0.0 && f.abs() < 9.2e18 => Some(*f as i64),
+            _ => None,
+        }
+    }
+
+    /// The value as a floating-point number, if it is numeric
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            HeaderValue::Integer(i) => Some(*i as f64),
+            HeaderValue::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// The value as a string slice, if it is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            HeaderValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for HeaderValue {
+    fn from(value: bool) -> Self {
+        HeaderValue::Logical(value)
+    }
+}
+
+impl From<i64> for HeaderValue {
+    fn from(value: i64) -> Self {
+        HeaderValue::Integer(value)
+    }
+}
+
+impl From<i32> for HeaderValue {
+    fn from(value: i32) -> Self {
+        HeaderValue::Integer(value.into())
+    }
+}
+
+impl From<usize> for HeaderValue {
+    fn from(value: usize) -> Self {
+        HeaderValue::Integer(value as i64)
+    }
+}
+
+impl From<f64> for HeaderValue {
+    fn from(value: f64) -> Self {
+        HeaderValue::Float(value)
+    }
+}
+
+impl From<Complex64> for HeaderValue {
+    fn from(value: Complex64) -> Self {
+        HeaderValue::Complex(value)
+    }
+}
+
+impl From<&str> for HeaderValue {
+    fn from(value: &str) -> Self {
+        HeaderValue::String(value.to_string())
+    }
+}
+
+impl From<String> for HeaderValue {
+    fn from(value: String) -> Self {
+        HeaderValue::String(value)
+    }
+}
+
+/// One keyword record of a header
+///
+/// Commentary cards (`COMMENT`, `HISTORY` and blank keywords) have no value;
+/// their text is stored as the comment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderCard {
+    /// Keyword, upper case; `HIERARCH` keywords are stored without the prefix
+    pub keyword: String,
+    /// Value, `None` for commentary cards
+    pub value: Option<HeaderValue>,
+    /// Comment or commentary text
+    pub comment: Option<String>,
+}
+
+/// Header of a FITS HDU: an ordered list of keyword records
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FitsHeader {
+    cards: Vec<HeaderCard>,
+}
+
+impl FitsHeader {
+    /// Create an empty header
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All cards in order
+    pub fn cards(&self) -> &[HeaderCard] {
+        &self.cards
+    }
+
+    /// Value of a keyword (case-insensitive)
+    pub fn get(&self, keyword: &str) -> Option<&HeaderValue> {
+        self.card(keyword).and_then(|card| card.value.as_ref())
+    }
+
+    /// Comment of a keyword (case-insensitive)
+    pub fn comment(&self, keyword: &str) -> Option<&str> {
+        self.card(keyword).and_then(|card| card.comment.as_deref())
+    }
+
+    /// Logical value of a keyword
+    pub fn get_bool(&self, keyword: &str) -> Option<bool> {
+        self.get(keyword).and_then(HeaderValue::as_bool)
+    }
+
+    /// Integer value of a keyword
+    pub fn get_i64(&self, keyword: &str) -> Option<i64> {
+        self.get(keyword).and_then(HeaderValue::as_i64)
+    }
+
+    /// Numeric value of a keyword
+    pub fn get_f64(&self, keyword: &str) -> Option<f64> {
+        self.get(keyword).and_then(HeaderValue::as_f64)
+    }
+
+    /// String value of a keyword
+    pub fn get_str(&self, keyword: &str) -> Option<&str> {
+        self.get(keyword).and_then(HeaderValue::as_str)
+    }
+
+    /// Set a keyword, replacing an existing value or appending a new card
+    pub fn set<V: Into<HeaderValue>>(&mut self, keyword: &str, value: V, comment: Option<&str>) {
+        let keyword = keyword.to_ascii_uppercase();
+        let card = HeaderCard {
+            keyword,
+            value: Some(value.into()),
+            comment: comment.map(str::to_string),
+        };
+        match self
+            .cards
+            .iter_mut()
+            .find(|c| c.value.is_some() && c.keyword == card.keyword)
+        {
+            Some(existing) => *existing = card,
+            None => self.cards.push(card),
+        }
+    }
+
+    /// Remove a keyword, returning its value
+    pub fn remove(&mut self, keyword: &str) -> Option<HeaderValue> {
+        let keyword = keyword.to_ascii_uppercase();
+        let index = self
+            .cards
+            .iter()
+            .position(|c| c.value.is_some() && c.keyword == keyword)?;
+        self.cards.remove(index).value
+    }
+
+    /// Append a `COMMENT` card
+    pub fn add_comment(&mut self, text: &str) {
+        self.push_commentary("COMMENT", text);
+    }
+
+    /// Append a `HISTORY` card
+    pub fn add_history(&mut self, text: &str) {
+        self.push_commentary("HISTORY", text);
+    }
+
+    /// Texts of all `COMMENT` cards
+    pub fn comments(&self) -> Vec<&str> {
+        self.commentary("COMMENT")
+    }
+
+    /// Texts of all `HISTORY` cards
+    pub fn history(&self) -> Vec<&str> {
+        self.commentary("HISTORY")
+    }
+
+    fn card(&self, keyword: &str) -> Option<&HeaderCard> {
+        self.cards
+            .iter()
+            .find(|c| c.value.is_some() && c.keyword.eq_ignore_ascii_case(keyword))
+    }
+
+    fn push_commentary(&mut self, keyword: &str, text: &str) {
+        self.cards.push(HeaderCard {
+            keyword: keyword.to_string(),
+            value: None,
+            comment: Some(text.to_string()),
+        });
+    }
+
+    fn commentary(&self, keyword: &str) -> Vec<&str> {
+        self.cards
+            .iter()
+            .filter(|c| c.value.is_none() && c.keyword == keyword)
+            .filter_map(|c| c.comment.as_deref())
+            .collect()
+    }
+
+    /// Whether a keyword is managed by the writer
+    pub(crate) fn is_structural(keyword: &str) -> bool {
+        STRUCTURAL_KEYWORDS.contains(&keyword)
+            || STRUCTURAL_PREFIXES.iter().any(|prefix| {
+                keyword
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.bytes().all(|b| b.is_ascii_digit()))
+            })
+    }
+
+    /// Append the non-structural cards of another header
+    pub(crate) fn extend_user_cards(&mut self, other: &FitsHeader) {
+        for card in &other.cards {
+            if card.value.is_none() || !Self::is_structural(&card.keyword) {
+                match &card.value {
+                    Some(value) => self.set(&card.keyword, value.clone(), card.comment.as_deref()),
+                    None => self.cards.push(card.clone()),
+                }
+            }
+        }
+    }
+
+    /// Read a header from its 2880-byte blocks
+    ///
+    /// Returns `None` at the end of the stream and the number of bytes read.
+    pub(crate) fn read<R: Read>(reader: &mut R) -> Result<Option<(Self, u64)>> {
+        let mut header = FitsHeader::new();
+        let mut block = vec![0u8; BLOCK_SIZE];
+        let mut size = 0u64;
+        loop {
+            // Allow trailing bytes that do not form a complete block
+            let mut filled = 0;
+            while filled < BLOCK_SIZE {
+                match reader.read(&mut block[filled..]) {
+                    Ok(0) => break,
+                    Ok(n) => filled += n,
+                    Err(e) => return Err(IoError::FileError(e.to_string())),
+                }
+            }
+            // Zero padding after the last HDU is tolerated
+            if size == 0 && block[..filled].iter().all(|&b| b == 0) {
+                return Ok(None);
+            }
+            if filled < BLOCK_SIZE {
+                return Err(IoError::FormatError(
+                    "Truncated FITS header: END card not found".to_string(),
+                ));
+            }
+            size += BLOCK_SIZE as u64;
+            for card in block.chunks_exact(CARD_SIZE) {
+                if !card.iter().all(|&b| (0x20..=0x7e).contains(&b)) {
+                    return Err(IoError::FormatError(
+                        "FITS header contains non-ASCII characters".to_string(),
+                    ));
+                }
+                let card = std::str::from_utf8(card).unwrap();
+                if card.starts_with("END") && card[3..].trim().is_empty() {
+                    return Ok(Some((header, size)));
+                }
+                header.parse_card(card)?;
+            }
+        }
+    }
+
+    fn parse_card(&mut self, card: &str) -> Result<()> {
+        let keyword = card[..8].trim_end();
+        let (keyword, value_text) = if keyword == "HIERARCH" {
+            match card[8..].split_once('=') {
+                Some((name, value)) => (name.trim().to_string(), Some(value)),
+                None => (keyword.to_string(), None),
+            }
+        } else if &card[8..10] == "= " {
+            (keyword.to_string(), Some(&card[10..]))
+        } else {
+            (keyword.to_string(), None)
+        };
+
+        let Some(value_text) = value_text else {
+            if keyword == "CONTINUE" && self.continue_string(&card[8..])? {
+                return Ok(());
+            }
+            if keyword.is_empty() && card[8..].trim().is_empty() {
+                return Ok(());
+            }
+            self.cards.push(HeaderCard {
+                keyword,
+                value: None,
+                comment: Some(card[8..].trim_end().to_string()),
+            });
+            return Ok(());
+        };
+
+        let (value, comment) = parse_value(value_text).map_err(|message| {
+            IoError::FormatError(format!(
+                "Invalid value of keyword '{}': {}",
+                keyword, message
+            ))
+        })?;
+        self.cards.push(HeaderCard {
+            keyword,
+            value: Some(value),
+            comment,
+        });
+        Ok(())
+    }
+
+    /// Append the text of a `CONTINUE` card to a long string ending in `&`
+    ///
+    /// Returns `false` if the card does not continue the previous string.
+    fn continue_string(&mut self, text: &str) -> Result<bool> {
+        let Some(HeaderCard {
+            value: Some(HeaderValue::String(previous)),
+            comment,
+            ..
+        }) = self.cards.last_mut()
+        else {
+            return Ok(false);
+        };
+        if !previous.ends_with('&') || !text.trim_start().starts_with('\'') {
+            return Ok(false);
+        }
+        let (value, more) = parse_value(text).map_err(|message| {
+            IoError::FormatError(format!("Invalid CONTINUE card: {}", message))
+        })?;
+        let HeaderValue::String(value) = value else {
+            return Ok(false);
+        };
+        previous.pop();
+        previous.push_str(&value);
+        if more.is_some() {
+            *comment = more;
+        }
+        Ok(true)
+    }
+
+    /// Encode the header as 2880-byte blocks, including the `END` card
+    pub(crate) fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        for card in &self.cards {
+            format_card(card, &mut out)?;
+        }
+        out.extend_from_slice(format!("{:<80}", "END").as_bytes());
+        out.resize(out.len().div_ceil(BLOCK_SIZE) * BLOCK_SIZE, b' ');
+        Ok(out)
+    }
+}
+
+/// Parse the value field of a card into the value and an optional comment
+fn parse_value(text: &str) -> std::result::Result<(HeaderValue, Option<String>), String> {
+    let trimmed = text.trim_start();
+    let (value, rest) = if let Some(quoted) = trimmed.strip_prefix('\'') {
+        let mut value = String::new();
+        let mut chars = quoted.char_indices().peekable();
+        let end = loop {
+            match chars.next() {
+                Some((i, '\'')) => {
+                    if matches!(chars.peek(), Some((_, '\''))) {
+                        chars.next();
+                        value.push('\'');
+                    } else {
+                        break i + 1;
+                    }
+                }
+                Some((_, c)) => value.push(c),
+                None => return Err("unterminated string".to_string()),
+            }
+        };
+        // Trailing blanks are not significant, leading blanks are
+        let value = value.trim_end().to_string();
+        (HeaderValue::String(value), &quoted[end..])
+    } else {
+        let (field, rest) = match trimmed.find('/') {
+            Some(i) => (&trimmed[..i], &trimmed[i..]),
+            None => (trimmed, ""),
+        };
+        (parse_scalar(field.trim())?, rest)
+    };
+
+    let rest = rest.trim();
+    let comment = match rest.strip_prefix('/') {
+        Some(comment) => Some(comment.trim().to_string()),
+        None if rest.is_empty() => None,
+        None => return Err(format!("unexpected text '{}'", rest)),
+    };
+    Ok((value, comment))
+}
+
+fn parse_scalar(field: &str) -> std::result::Result<HeaderValue, String> {
+    let float = |s: &str| s.trim().replace(['D', 'd'], "E").parse::<f64>().ok();
+    if field.is_empty() {
+        Ok(HeaderValue::Undefined)
+    } else if field == "T" {
+        Ok(HeaderValue::Logical(true))
+    } else if field == "F" {
+        Ok(HeaderValue::Logical(false))
+    } else if let Some(inner) = field.strip_prefix('(').and_then(|f| f.strip_suffix(')')) {
+        let (re, im) = inner.split_once(',').ok_or("invalid complex value")?;
+        match (float(re), float(im)) {
+            (Some(re), Some(im)) => Ok(HeaderValue::Complex(Complex64::new(re, im))),
+            _ => Err(format!("invalid complex value '{}'", field)),
+        }
+    } else if let Ok(i) = field.trim_start_matches('+').parse::<i64>() {
+        Ok(HeaderValue::Integer(i))
+    } else if let Some(f) = float(field) {
+        Ok(HeaderValue::Float(f))
+    } else {
+        Err(format!("cannot parse '{}'", field))
+    }
+}
+
+/// Format a floating-point value so that it always contains a decimal point
+fn format_float(value: f64) -> Result<String> {
+    if !value.is_finite() {
+        return Err(IoError::ValidationError(format!(
+            "FITS headers cannot hold the value {}",
+            value
+        )));
+    }
+    let text = if value == value.trunc() && value.abs() < 1e15 {
+        format!("{:.1}", value)
+    } else if (1e-4..1e15).contains(&value.abs()) {
+        format!("{}", value)
+    } else {
+        let text = format!("{:E}", value);
+        match text.split_once('E') {
+            Some((mantissa, exponent)) if !mantissa.contains('.') => {
+                format!("{}.0E{}", mantissa, exponent)
+            }
+            _ => text,
+        }
+    };
+    Ok(text)
+}
+
+/// Append one 80-character card
+fn push_card(out: &mut Vec<u8>, text: &str) {
+    out.extend_from_slice(format!("{:<80}", text).as_bytes());
+}
+
+fn format_card(card: &HeaderCard, out: &mut Vec<u8>) -> Result<()> {
+    if let Some(c) = card
+        .keyword
+        .chars()
+        .chain(card.comment.iter().flat_map(|c| c.chars()))
+        .find(|c| !(' '..='~').contains(c))
+    {
+        return Err(IoError::ValidationError(format!(
+            "FITS header text must be printable ASCII, found {:?}",
+            c
+        )));
+    }
+
+    let Some(value) = &card.value else {
+        let text = card.comment.as_deref().unwrap_or("");
+        let chars: Vec<char> = text.chars().collect();
+        for segment in chars.chunks(72) {
+            let text: String = segment.iter().collect();
+            push_card(out, &format!("{:<8}{}", card.keyword, text));
+        }
+        if chars.is_empty() {
+            push_card(out, &card.keyword);
+        }
+        return Ok(());
+    };
+
+    let hierarch = card.keyword.len() > 8
+        || !card
+            .keyword
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'-' || b == b'_');
+    let prefix = if hierarch {
+        format!("HIERARCH {} = ", card.keyword)
+    } else {
+        format!("{:<8}= ", card.keyword)
+    };
+
+    let quote = |s: &str| s.replace('\'', "''");
+    let value_text = match value {
+        HeaderValue::String(s) => {
+            if let Some(c) = s.chars().find(|c| !(' '..='~').contains(c)) {
+                return Err(IoError::ValidationError(format!(
+                    "FITS header text must be printable ASCII, found {:?}",
+                    c
+                )));
+            }
+            let quoted = quote(s);
+            if prefix.len() + quoted.len() + 2 > CARD_SIZE {
+                return format_long_string(&prefix, s, card.comment.as_deref(), out);
+            }
+            format!("'{:<8}'", quoted)
+        }
+        HeaderValue::Logical(b) => format!("{:>20}", if *b { "T" } else { "F" }),
+        HeaderValue::Integer(i) => format!("{:>20}", i),
+        HeaderValue::Float(f) => format!("{:>20}", format_float(*f)?),
+        HeaderValue::Complex(c) => {
+            format!("({}, {})", format_float(c.re)?, format_float(c.im)?)
+        }
+        HeaderValue::Undefined => " ".repeat(20),
+    };
+
+    let mut text = prefix + &value_text;
+    if text.len() > CARD_SIZE {
+        return Err(IoError::ValidationError(format!(
+            "Keyword '{}' does not fit in a header card",
+            card.keyword
+        )));
+    }
+    if let Some(comment) = &card.comment {
+        text.push_str(" / ");
+        text.push_str(comment);
+        text.truncate(CARD_SIZE);
+    }
+    push_card(out, &text);
+    Ok(())
+}
+
+/// Write a string that does not fit in one card using `CONTINUE` cards
+fn format_long_string(
+    prefix: &str,
+    value: &str,
+    comment: Option<&str>,
+    out: &mut Vec<u8>,
+) -> Result<()> {
+    let mut segments = Vec::new();
+    let mut segment = String::new();
+    let mut room = CARD_SIZE - prefix.len() - 3;
+    for c in value.chars() {
+        let width = if c == '\'' { 2 } else { 1 };
+        if segment.len() + width > room {
+            segments.push(std::mem::take(&mut segment));
+            room = CARD_SIZE - 10 - 3;
+        }
+        if c == '\'' {
+            segment.push_str("''");
+        } else {
+            segment.push(c);
+        }
+    }
+    segments.push(segment);
+
+    let last = segments.len() - 1;
+    for (i, segment) in segments.iter().enumerate() {
+        let lead = if i == 0 {
+            prefix.to_string()
+        } else {
+            "CONTINUE  ".to_string()
+        };
+        let mut text = if i < last {
+            format!("{}'{}&'", lead, segment)
+        } else {
+            format!("{}'{}'", lead, segment)
+        };
+        if i == last {
+            if let Some(comment) = comment {
+                text.push_str(" / ");
+                text.push_str(comment);
+                text.truncate(CARD_SIZE);
+            }
+        }
+        push_card(out, &text);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(header: &FitsHeader) -> FitsHeader {
+        let bytes = header.to_bytes().unwrap();
+        assert_eq!(bytes.len() % BLOCK_SIZE, 0);
+        let (read, size) = FitsHeader::read(&mut bytes.as_slice()).unwrap().unwrap();
+        assert_eq!(size as usize, bytes.len());
+        read
+    }
+
+    #[test]
+    fn test_parse_cards() {
+        let cards = [
+            "SIMPLE  =                    T / conforms to FITS standard",
+            "BITPIX  =                  -32",
+            "EXPTIME =              1.5D+02 / exposure [s]",
+            "OBJECT  = 'M31 ''core'''       / target",
+            "CPLX    = (1.0, -2.5)",
+            "EMPTY   =",
+            "HIERARCH ESO DET GAIN = 2.5 / electrons per ADU",
+            "LONGSTR = 'abc&'",
+            "CONTINUE  'def' / continued",
+            "COMMENT   some commentary",
+            "HISTORY processed",
+            "END",
+        ];
+        let mut block: Vec<u8> = cards
+            .iter()
+            .flat_map(|c| format!("{:<80}", c).into_bytes())
+            .collect();
+        block.resize(BLOCK_SIZE, b' ');
+        let (header, _) = FitsHeader::read(&mut block.as_slice()).unwrap().unwrap();
+
+        assert_eq!(header.get_bool("simple"), Some(true));
+        assert_eq!(header.comment("SIMPLE"), Some("conforms to FITS standard"));
+        assert_eq!(header.get_i64("BITPIX"), Some(-32));
+        assert_eq!(header.get_f64("EXPTIME"), Some(150.0));
+        assert_eq!(header.get_str("OBJECT"), Some("M31 'core'"));
+        assert_eq!(
+            header.get("CPLX"),
+            Some(&HeaderValue::Complex(Complex64::new(1.0, -2.5)))
+        );
+        assert_eq!(header.get("EMPTY"), Some(&HeaderValue::Undefined));
+        assert_eq!(header.get_f64("ESO DET GAIN"), Some(2.5));
+        assert_eq!(header.get_str("LONGSTR"), Some("abcdef"));
+        assert_eq!(header.comment("LONGSTR"), Some("continued"));
+        assert_eq!(header.comments(), vec!["  some commentary"]);
+        assert_eq!(header.history(), vec!["processed"]);
+    }
+
+    #[test]
+    fn test_write_and_read_back() {
+        let mut header = FitsHeader::new();
+        header.set("INT", -42i64, Some("an integer"));
+        header.set("FLOAT", 6.02214076e23, None);
+        header.set("SMALL", 1e-300, None);
+        header.set("WHOLE", 3.0, None);
+        header.set("FLAG", false, None);
+        header.set("NAME", "it's", None);
+        header.set("Z", Complex64::new(0.5, 2.0), None);
+        header.set("hierarchical.key", 7i64, None);
+        let long = "a long value with 'quotes' ".repeat(6);
+        header.set("LONG", long.trim_end(), Some("long comment"));
+        header.add_history("created by a test");
+        header.set("INT", 43i64, Some("replaced"));
+        assert_eq!(header.remove("FLAG"), Some(HeaderValue::Logical(false)));
+
+        let read = roundtrip(&header);
+        assert_eq!(read, header);
+        assert_eq!(read.get_i64("INT"), Some(43));
+        assert_eq!(read.get_str("LONG"), Some(long.trim_end()));
+
+        header.set("BAD", f64::NAN, None);
+        assert!(header.to_bytes().is_err());
+    }
+
+    #[test]
+    fn test_structural_keywords() {
+        assert!(FitsHeader::is_structural("NAXIS"));
+        assert!(FitsHeader::is_structural("NAXIS2"));
+        assert!(FitsHeader::is_structural("TFORM12"));
+        assert!(!FitsHeader::is_structural("TFORMAT"));
+        assert!(!FitsHeader::is_structural("OBJECT"));
+    }
+}
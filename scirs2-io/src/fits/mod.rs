@@ -0,0 +1,673 @@
+//! FITS (Flexible Image Transport System) file format module
+//!
+//! This module reads and writes FITS files as used in astronomy:
+//!
+//! - Primary HDUs and `IMAGE` extensions with any `BITPIX` (8, 16, 32, 64, -32, -64)
+//! - `BSCALE`/`BZERO` scaling and `BLANK` values, including the unsigned integer conventions
+//! - ASCII `TABLE` and binary `BINTABLE` extensions read into column arrays
+//! - Typed header keywords with `CONTINUE` long strings and `HIERARCH` keywords
+//! - Writing images and binary tables to new files
+//!
+//! ## Examples
+//!
+//! ```
+//! use ndarray::{Array2, ArrayD};
+//! use scirs2_io::fits::{FitsArray, FitsFile, FitsHeader, FitsWriter};
+//! use tempfile::tempdir;
+//!
+//! let dir = tempdir().unwrap();
+//! let path = dir.path().join("image.fits");
+//!
+//! let image: ArrayD<f32> = Array2::from_shape_fn((3, 4), |(i, j)| (i * 4 + j) as f32).into_dyn();
+//! let mut header = FitsHeader::new();
+//! header.set("OBJECT", "M31", Some("target name"));
+//!
+//! let mut writer = FitsWriter::create(&path).unwrap();
+//! writer.write_image(&FitsArray::from(image.clone()), Some(&header)).unwrap();
+//! writer.finish().unwrap();
+//!
+//! let fits = FitsFile::open(&path).unwrap();
+//! let metadata = fits.hdus()[0].image_metadata().unwrap();
+//! assert_eq!(metadata.shape, vec![3, 4]);
+//! assert_eq!(metadata.object.as_deref(), Some("M31"));
+//! assert_eq!(fits.read_image(0).unwrap(), FitsArray::Float32(image));
+//! ```
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use ndarray::ArrayD;
+
+use crate::error::{IoError, Result};
+
+mod data;
+mod header;
+mod table;
+
+pub use data::FitsArray;
+pub use header::{FitsHeader, HeaderCard, HeaderValue};
+pub use table::{FitsColumn, FitsTable};
+
+use data::{bitpix_code, code_bitpix, decode, encode, Scaling};
+use header::BLOCK_SIZE;
+
+/// Kind of header-data unit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HduType {
+    /// Primary HDU at the start of the file
+    Primary,
+    /// `IMAGE` extension
+    Image,
+    /// ASCII `TABLE` extension
+    AsciiTable,
+    /// `BINTABLE` extension
+    BinaryTable,
+    /// Any other extension type
+    Other(String),
+}
+
+/// Header-data unit of a FITS file
+#[derive(Debug, Clone)]
+pub struct Hdu {
+    /// Kind of HDU
+    pub hdu_type: HduType,
+    /// Header keywords
+    pub header: FitsHeader,
+    data_offset: u64,
+    data_size: u64,
+}
+
+impl Hdu {
+    /// Extension name (`EXTNAME`)
+    pub fn name(&self) -> Option<&str> {
+        self.header.get_str("EXTNAME")
+    }
+
+    /// Image metadata for primary and `IMAGE` HDUs
+    pub fn image_metadata(&self) -> Option<FitsImageMetadata> {
+        if !matches!(self.hdu_type, HduType::Primary | HduType::Image) {
+            return None;
+        }
+        let header = &self.header;
+        let string = |keyword: &str| header.get_str(keyword).map(|s| s.trim().to_string());
+        Some(FitsImageMetadata {
+            bitpix: header.get_i64("BITPIX")?,
+            shape: image_shape(header).ok()?,
+            bscale: header.get_f64("BSCALE").unwrap_or(1.0),
+            bzero: header.get_f64("BZERO").unwrap_or(0.0),
+            blank: header.get_i64("BLANK"),
+            unit: string("BUNIT"),
+            object: string("OBJECT"),
+            telescope: string("TELESCOP"),
+            instrument: string("INSTRUME"),
+            observer: string("OBSERVER"),
+            date_obs: string("DATE-OBS"),
+            exposure: header
+                .get_f64("EXPTIME")
+                .or_else(|| header.get_f64("EXPOSURE")),
+            extname: string("EXTNAME"),
+        })
+    }
+}
+
+/// Typed metadata of a FITS image
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitsImageMetadata {
+    /// Stored data type (`BITPIX`)
+    pub bitpix: i64,
+    /// Array shape in ndarray order, i.e. the reversed `NAXISn` values
+    pub shape: Vec<usize>,
+    /// Scale factor (`BSCALE`)
+    pub bscale: f64,
+    /// Zero point (`BZERO`)
+    pub bzero: f64,
+    /// Undefined integer value (`BLANK`)
+    pub blank: Option<i64>,
+    /// Physical unit (`BUNIT`)
+    pub unit: Option<String>,
+    /// Observed object (`OBJECT`)
+    pub object: Option<String>,
+    /// Telescope (`TELESCOP`)
+    pub telescope: Option<String>,
+    /// Instrument (`INSTRUME`)
+    pub instrument: Option<String>,
+    /// Observer (`OBSERVER`)
+    pub observer: Option<String>,
+    /// Observation date (`DATE-OBS`)
+    pub date_obs: Option<String>,
+    /// Exposure time in seconds (`EXPTIME` or `EXPOSURE`)
+    pub exposure: Option<f64>,
+    /// Extension name (`EXTNAME`)
+    pub extname: Option<String>,
+}
+
+/// Image shape in ndarray order from the `NAXISn` keywords
+fn image_shape(header: &FitsHeader) -> Result<Vec<usize>> {
+    let naxis = header
+        .get_i64("NAXIS")
+        .ok_or_else(|| IoError::FormatError("Missing FITS keyword NAXIS".to_string()))?;
+    (1..=naxis)
+        .rev()
+        .map(|i| {
+            header
+                .get_i64(&format!("NAXIS{}", i))
+                .and_then(|n| usize::try_from(n).ok())
+                .ok_or_else(|| IoError::FormatError(format!("Invalid FITS keyword NAXIS{}", i)))
+        })
+        .collect()
+}
+
+/// Size of the data of an HDU in bytes, without padding
+fn data_size(header: &FitsHeader) -> Result<u64> {
+    let bitpix = header
+        .get_i64("BITPIX")
+        .ok_or_else(|| IoError::FormatError("Missing FITS keyword BITPIX".to_string()))?;
+    bitpix_code(bitpix)?;
+    let naxis = header.get_i64("NAXIS").unwrap_or(0);
+    if naxis == 0 {
+        return Ok(0);
+    }
+    // Random groups have NAXIS1 = 0, which does not count towards the size
+    let random_groups =
+        header.get_i64("NAXIS1") == Some(0) && header.get_bool("GROUPS") == Some(true);
+    let mut elements = 1u64;
+    for i in 1..=naxis {
+        if i == 1 && random_groups {
+            continue;
+        }
+        let n = header
+            .get_i64(&format!("NAXIS{}", i))
+            .and_then(|n| u64::try_from(n).ok())
+            .ok_or_else(|| IoError::FormatError(format!("Invalid FITS keyword NAXIS{}", i)))?;
+        elements *= n;
+    }
+    let pcount = header.get_i64("PCOUNT").unwrap_or(0).max(0) as u64;
+    let gcount = header.get_i64("GCOUNT").unwrap_or(1).max(0) as u64;
+    Ok(bitpix.unsigned_abs() / 8 * gcount * (pcount + elements))
+}
+
+fn padded(size: u64) -> u64 {
+    size.div_ceil(BLOCK_SIZE as u64) * BLOCK_SIZE as u64
+}
+
+/// FITS file opened for reading
+///
+/// Opening a file reads all headers; data is read on demand.
+#[derive(Debug, Clone)]
+pub struct FitsFile {
+    path: PathBuf,
+    hdus: Vec<Hdu>,
+}
+
+impl FitsFile {
+    /// Open a FITS file and read the headers of all HDUs
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the FITS file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<FitsFile>` - The opened file or an error
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path).map_err(|e| IoError::FileError(e.to_string()))?;
+        let file_size = file
+            .metadata()
+            .map_err(|e| IoError::FileError(e.to_string()))?
+            .len();
+        let mut reader = BufReader::new(file);
+
+        let mut hdus = Vec::new();
+        let mut offset = 0u64;
+        while offset < file_size {
+            let Some((header, header_size)) = FitsHeader::read(&mut reader)? else {
+                break;
+            };
+            let hdu_type = if hdus.is_empty() {
+                if header.get_bool("SIMPLE").is_none() {
+                    return Err(IoError::FormatError(
+                        "Not a FITS file: missing SIMPLE keyword".to_string(),
+                    ));
+                }
+                HduType::Primary
+            } else {
+                match header.get_str("XTENSION").map(str::trim) {
+                    Some("IMAGE") => HduType::Image,
+                    Some("TABLE") => HduType::AsciiTable,
+                    Some("BINTABLE") | Some("A3DTABLE") => HduType::BinaryTable,
+                    Some(other) => HduType::Other(other.to_string()),
+                    None => {
+                        return Err(IoError::FormatError(
+                            "FITS extension without XTENSION keyword".to_string(),
+                        ))
+                    }
+                }
+            };
+            let size = data_size(&header)?;
+            let data_offset = offset + header_size;
+            offset = data_offset + padded(size);
+            if data_offset + size > file_size {
+                return Err(IoError::FormatError(format!(
+                    "Truncated FITS file: HDU {} needs {} data bytes",
+                    hdus.len(),
+                    size
+                )));
+            }
+            reader
+                .seek(SeekFrom::Start(offset.min(file_size)))
+                .map_err(|e| IoError::FileError(e.to_string()))?;
+            hdus.push(Hdu {
+                hdu_type,
+                header,
+                data_offset,
+                data_size: size,
+            });
+        }
+        if hdus.is_empty() {
+            return Err(IoError::FormatError("Empty FITS file".to_string()));
+        }
+        Ok(Self { path, hdus })
+    }
+
+    /// All HDUs in file order
+    pub fn hdus(&self) -> &[Hdu] {
+        &self.hdus
+    }
+
+    /// Number of HDUs
+    pub fn num_hdus(&self) -> usize {
+        self.hdus.len()
+    }
+
+    /// Index of the first HDU with the given `EXTNAME`, ignoring case
+    pub fn find(&self, extname: &str) -> Option<usize> {
+        self.hdus.iter().position(|hdu| {
+            hdu.name()
+                .is_some_and(|name| name.trim().eq_ignore_ascii_case(extname))
+        })
+    }
+
+    fn hdu(&self, index: usize) -> Result<&Hdu> {
+        self.hdus.get(index).ok_or_else(|| {
+            IoError::ValidationError(format!(
+                "HDU index {} out of range ({} HDUs)",
+                index,
+                self.hdus.len()
+            ))
+        })
+    }
+
+    fn read_data(&self, hdu: &Hdu) -> Result<Vec<u8>> {
+        let mut file = File::open(&self.path).map_err(|e| IoError::FileError(e.to_string()))?;
+        file.seek(SeekFrom::Start(hdu.data_offset))
+            .map_err(|e| IoError::FileError(e.to_string()))?;
+        let mut data = vec![0u8; hdu.data_size as usize];
+        file.read_exact(&mut data)
+            .map_err(|e| IoError::FileError(e.to_string()))?;
+        Ok(data)
+    }
+
+    /// Read the data of a primary or `IMAGE` HDU
+    ///
+    /// Integer data with a `BSCALE`/`BZERO` other than the unsigned offsets
+    /// is returned as `Float64` with `BLANK` values replaced by NaN.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the HDU
+    ///
+    /// # Returns
+    ///
+    /// * `Result<FitsArray>` - The image in ndarray axis order or an error
+    pub fn read_image(&self, index: usize) -> Result<FitsArray> {
+        let hdu = self.hdu(index)?;
+        if !matches!(hdu.hdu_type, HduType::Primary | HduType::Image) {
+            return Err(IoError::FormatError(format!(
+                "HDU {} is not an image ({:?})",
+                index, hdu.hdu_type
+            )));
+        }
+        if hdu.header.get_bool("GROUPS") == Some(true) {
+            return Err(IoError::FormatError(
+                "Random groups data is not supported".to_string(),
+            ));
+        }
+        let header = &hdu.header;
+        let code = bitpix_code(header.get_i64("BITPIX").unwrap_or(8))?;
+        let shape = image_shape(header)?;
+        let scaling = Scaling {
+            scale: header.get_f64("BSCALE").unwrap_or(1.0),
+            zero: header.get_f64("BZERO").unwrap_or(0.0),
+            null: header.get_i64("BLANK"),
+        };
+        let data = self.read_data(hdu)?;
+        if shape.is_empty() {
+            return decode(code, &data, &scaling);
+        }
+        decode(code, &data, &scaling)?.reshape(&shape)
+    }
+
+    /// Read the data of an ASCII or binary table HDU
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the HDU
+    ///
+    /// # Returns
+    ///
+    /// * `Result<FitsTable>` - The table columns or an error
+    pub fn read_table(&self, index: usize) -> Result<FitsTable> {
+        let hdu = self.hdu(index)?;
+        match hdu.hdu_type {
+            HduType::BinaryTable => table::read_binary_table(&hdu.header, &self.read_data(hdu)?),
+            HduType::AsciiTable => table::read_ascii_table(&hdu.header, &self.read_data(hdu)?),
+            _ => Err(IoError::FormatError(format!(
+                "HDU {} is not a table ({:?})",
+                index, hdu.hdu_type
+            ))),
+        }
+    }
+}
+
+/// Writer producing a FITS file one HDU at a time
+///
+/// The first image becomes the primary HDU; if a table is written first, an
+/// empty primary HDU is inserted before it.
+pub struct FitsWriter<W: Write> {
+    writer: W,
+    num_hdus: usize,
+}
+
+impl FitsWriter<BufWriter<File>> {
+    /// Create a FITS file for writing
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file to create
+    ///
+    /// # Returns
+    ///
+    /// * `Result<FitsWriter<BufWriter<File>>>` - The writer or an error
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::create(path).map_err(|e| IoError::FileError(e.to_string()))?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write> FitsWriter<W> {
+    /// Create a writer on top of any output stream
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            num_hdus: 0,
+        }
+    }
+
+    fn write_hdu(&mut self, header: &FitsHeader, data: &[u8]) -> Result<()> {
+        let mut bytes = header.to_bytes()?;
+        bytes.extend_from_slice(data);
+        bytes.resize(padded(bytes.len() as u64) as usize, 0);
+        self.writer
+            .write_all(&bytes)
+            .map_err(|e| IoError::FileError(e.to_string()))?;
+        self.num_hdus += 1;
+        Ok(())
+    }
+
+    /// Header of a new image HDU, primary or extension depending on position
+    fn image_header(&self, bitpix: i64, shape: &[usize]) -> FitsHeader {
+        let mut header = FitsHeader::new();
+        if self.num_hdus == 0 {
+            header.set("SIMPLE", true, Some("conforms to FITS standard"));
+        } else {
+            header.set("XTENSION", "IMAGE", Some("image extension"));
+        }
+        header.set("BITPIX", bitpix, Some("array data type"));
+        header.set("NAXIS", shape.len(), Some("number of array dimensions"));
+        for (i, &n) in shape.iter().rev().enumerate() {
+            header.set(&format!("NAXIS{}", i + 1), n, None);
+        }
+        if self.num_hdus == 0 {
+            header.set("EXTEND", true, None);
+        } else {
+            header.set("PCOUNT", 0i64, None);
+            header.set("GCOUNT", 1i64, None);
+        }
+        header
+    }
+
+    /// Write an image HDU
+    ///
+    /// Unsigned and signed byte arrays are written with the standard `BZERO`
+    /// offsets. Complex, logical and string arrays cannot be stored as images.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - Image data in ndarray axis order
+    /// * `header` - Optional additional keywords; structural keywords are ignored
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - Success or an error
+    pub fn write_image(&mut self, image: &FitsArray, header: Option<&FitsHeader>) -> Result<()> {
+        let (code, zero, data) = encode(image)?;
+        let bitpix = code_bitpix(code).ok_or_else(|| {
+            IoError::ValidationError(format!(
+                "Arrays of type '{}' cannot be written as FITS images",
+                code
+            ))
+        })?;
+        let mut hdu_header = self.image_header(bitpix, &image.shape());
+        if let Some(zero) = zero {
+            hdu_header.set("BSCALE", 1.0, None);
+            hdu_header.set("BZERO", zero, None);
+        }
+        if let Some(header) = header {
+            hdu_header.extend_user_cards(header);
+        }
+        self.write_hdu(&hdu_header, &data)
+    }
+
+    /// Write a binary table extension
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - Table to write
+    /// * `header` - Optional additional keywords such as `EXTNAME`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - Success or an error
+    pub fn write_table(&mut self, table: &FitsTable, header: Option<&FitsHeader>) -> Result<()> {
+        if self.num_hdus == 0 {
+            let primary = self.image_header(8, &[]);
+            self.write_hdu(&primary, &[])?;
+        }
+        let (hdu_header, data) = table::binary_table_hdu(table, header)?;
+        self.write_hdu(&hdu_header, &data)
+    }
+
+    /// Flush the output and return the underlying writer
+    ///
+    /// A file without any HDU gets an empty primary HDU.
+    pub fn finish(mut self) -> Result<W> {
+        if self.num_hdus == 0 {
+            let primary = self.image_header(8, &[]);
+            self.write_hdu(&primary, &[])?;
+        }
+        self.writer
+            .flush()
+            .map_err(|e| IoError::FileError(e.to_string()))?;
+        Ok(self.writer)
+    }
+}
+
+/// Read the primary image of a FITS file as `f64` values
+///
+/// # Arguments
+///
+/// * `path` - Path to the FITS file
+///
+/// # Returns
+///
+/// * `Result<ArrayD<f64>>` - The scaled image or an error
+pub fn read_fits_image<P: AsRef<Path>>(path: P) -> Result<ArrayD<f64>> {
+    FitsFile::open(path)?.read_image(0)?.to_f64()
+}
+
+/// Read the first table extension of a FITS file
+///
+/// # Arguments
+///
+/// * `path` - Path to the FITS file
+///
+/// # Returns
+///
+/// * `Result<FitsTable>` - The table or an error
+pub fn read_fits_table<P: AsRef<Path>>(path: P) -> Result<FitsTable> {
+    let fits = FitsFile::open(path)?;
+    let index = fits
+        .hdus()
+        .iter()
+        .position(|hdu| matches!(hdu.hdu_type, HduType::AsciiTable | HduType::BinaryTable))
+        .ok_or_else(|| IoError::FormatError("FITS file contains no table".to_string()))?;
+    fits.read_table(index)
+}
+
+/// Write an array as the primary image of a new FITS file
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to create
+/// * `image` - Image data in ndarray axis order
+///
+/// # Returns
+///
+/// * `Result<()>` - Success or an error
+pub fn write_fits_image<P, A>(path: P, image: A) -> Result<()>
+where
+    P: AsRef<Path>,
+    A: Into<FitsArray>,
+{
+    let mut writer = FitsWriter::create(path)?;
+    writer.write_image(&image.into(), None)?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr1, Array3};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_image_types_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("types.fits");
+        let images = vec![
+            FitsArray::from(arr1(&[0u8, 7, 255]).into_dyn()),
+            FitsArray::from(arr1(&[-128i8, 0, 127]).into_dyn()),
+            FitsArray::from(arr1(&[-3i16, 0, 30000]).into_dyn()),
+            FitsArray::from(arr1(&[0u16, 32768, 65535]).into_dyn()),
+            FitsArray::from(arr1(&[i32::MIN, 0, i32::MAX]).into_dyn()),
+            FitsArray::from(arr1(&[0u32, 1 << 31, u32::MAX]).into_dyn()),
+            FitsArray::from(arr1(&[i64::MIN, 0, i64::MAX]).into_dyn()),
+            FitsArray::from(arr1(&[0u64, 1 << 63, u64::MAX]).into_dyn()),
+            FitsArray::from(arr1(&[1.5f32, -2.0, f32::NAN]).into_dyn()),
+            FitsArray::from(
+                Array3::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 100 + j * 10 + k) as f64)
+                    .into_dyn(),
+            ),
+        ];
+
+        let mut writer = FitsWriter::create(&path).unwrap();
+        for image in &images {
+            writer.write_image(image, None).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let fits = FitsFile::open(&path).unwrap();
+        assert_eq!(fits.num_hdus(), images.len());
+        assert_eq!(fits.hdus()[0].hdu_type, HduType::Primary);
+        assert_eq!(fits.hdus()[1].hdu_type, HduType::Image);
+        for (i, image) in images.iter().enumerate() {
+            let read = fits.read_image(i).unwrap();
+            if let (FitsArray::Float32(a), FitsArray::Float32(b)) = (&read, image) {
+                assert_eq!(a[0], b[0]);
+                assert!(a[2].is_nan());
+            } else {
+                assert_eq!(&read, image);
+            }
+        }
+        assert_eq!(
+            fits.hdus()[9].image_metadata().unwrap().shape,
+            vec![2, 3, 4]
+        );
+        assert_eq!(fits.hdus()[3].image_metadata().unwrap().bzero, 32768.0);
+    }
+
+    #[test]
+    fn test_scaled_image_with_blank() {
+        let mut header = FitsHeader::new();
+        header.set("SIMPLE", true, None);
+        header.set("BITPIX", 16i64, None);
+        header.set("NAXIS", 2i64, None);
+        header.set("NAXIS1", 3i64, None);
+        header.set("NAXIS2", 2i64, None);
+        header.set("BSCALE", 0.5, None);
+        header.set("BZERO", 10.0, None);
+        header.set("BLANK", -1i64, None);
+        header.set("BUNIT", "K", None);
+        header.set("EXPTIME", 30.0, None);
+        let mut bytes = header.to_bytes().unwrap();
+        for value in [0i16, 2, 4, -1, 8, 10] {
+            bytes.extend_from_slice(&value.to_be_bytes());
+        }
+        bytes.resize(padded(bytes.len() as u64) as usize, 0);
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("scaled.fits");
+        std::fs::write(&path, bytes).unwrap();
+
+        let image = read_fits_image(&path).unwrap();
+        assert_eq!(image.shape(), &[2, 3]);
+        assert_eq!(image[[0, 0]], 10.0);
+        assert_eq!(image[[0, 2]], 12.0);
+        assert!(image[[1, 0]].is_nan());
+        assert_eq!(image[[1, 2]], 15.0);
+
+        let metadata = FitsFile::open(&path).unwrap().hdus()[0]
+            .image_metadata()
+            .unwrap();
+        assert_eq!(metadata.unit.as_deref(), Some("K"));
+        assert_eq!(metadata.exposure, Some(30.0));
+        assert_eq!(metadata.blank, Some(-1));
+    }
+
+    #[test]
+    fn test_table_file_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("table.fits");
+        let table = FitsTable::new(vec![
+            FitsColumn::new("RA", arr1(&[10.5f64, 20.25]).into_dyn()).with_unit("deg"),
+            FitsColumn::new("MAG", arr1(&[12.0f32, 13.5]).into_dyn()),
+        ])
+        .unwrap();
+        let mut header = FitsHeader::new();
+        header.set("EXTNAME", "CATALOG", None);
+
+        let mut writer = FitsWriter::create(&path).unwrap();
+        writer.write_table(&table, Some(&header)).unwrap();
+        writer.finish().unwrap();
+
+        let fits = FitsFile::open(&path).unwrap();
+        assert_eq!(fits.num_hdus(), 2);
+        assert_eq!(fits.find("catalog"), Some(1));
+        assert!(fits.read_table(0).is_err());
+        assert_eq!(fits.read_table(1).unwrap(), table);
+        assert_eq!(read_fits_table(&path).unwrap(), table);
+    }
+}
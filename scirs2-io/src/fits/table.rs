@@ -0,0 +1,708 @@
+//! FITS ASCII and binary table extensions
+
+use ndarray::{ArrayD, IxDyn};
+
+use super::data::{decode, element_size, encode, FitsArray, Scaling};
+use super::header::FitsHeader;
+use crate::error::{IoError, Result};
+
+/// Column of a FITS table
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitsColumn {
+    /// Column name (`TTYPEn`)
+    pub name: String,
+    /// Physical unit (`TUNITn`)
+    pub unit: Option<String>,
+    /// Column values; the first axis indexes rows
+    pub data: FitsArray,
+}
+
+impl FitsColumn {
+    /// Create a column without a unit
+    pub fn new<A: Into<FitsArray>>(name: &str, data: A) -> Self {
+        Self {
+            name: name.to_string(),
+            unit: None,
+            data: data.into(),
+        }
+    }
+
+    /// Set the physical unit of the column
+    pub fn with_unit(mut self, unit: &str) -> Self {
+        self.unit = Some(unit.to_string());
+        self
+    }
+}
+
+/// Table read from or written to a FITS table extension
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitsTable {
+    columns: Vec<FitsColumn>,
+    num_rows: usize,
+}
+
+impl FitsTable {
+    /// Create a table, checking that all columns have the same number of rows
+    pub fn new(columns: Vec<FitsColumn>) -> Result<Self> {
+        let num_rows = columns.first().map_or(0, |c| column_rows(&c.data));
+        for column in &columns {
+            if column.data.shape().is_empty() {
+                return Err(IoError::ValidationError(format!(
+                    "Column '{}' must have at least one dimension",
+                    column.name
+                )));
+            }
+            if column_rows(&column.data) != num_rows {
+                return Err(IoError::ValidationError(format!(
+                    "Column '{}' has {} rows, expected {}",
+                    column.name,
+                    column_rows(&column.data),
+                    num_rows
+                )));
+            }
+        }
+        Ok(Self { columns, num_rows })
+    }
+
+    /// Number of rows
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Number of columns
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// All columns in file order
+    pub fn columns(&self) -> &[FitsColumn] {
+        &self.columns
+    }
+
+    /// Find a column by name, ignoring case as FITS readers conventionally do
+    pub fn column(&self, name: &str) -> Option<&FitsColumn> {
+        self.columns
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Names of all columns
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(|c| c.name.as_str()).collect()
+    }
+}
+
+fn column_rows(data: &FitsArray) -> usize {
+    data.shape().first().copied().unwrap_or(0)
+}
+
+fn required(header: &FitsHeader, keyword: &str) -> Result<i64> {
+    header
+        .get_i64(keyword)
+        .ok_or_else(|| IoError::FormatError(format!("Missing FITS keyword {}", keyword)))
+}
+
+fn required_usize(header: &FitsHeader, keyword: &str) -> Result<usize> {
+    usize::try_from(required(header, keyword)?)
+        .map_err(|_| IoError::FormatError(format!("Negative value for FITS keyword {}", keyword)))
+}
+
+fn column_name(header: &FitsHeader, index: usize) -> String {
+    header
+        .get_str(&format!("TTYPE{}", index))
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| format!("COL{}", index))
+}
+
+fn column_unit(header: &FitsHeader, index: usize) -> Option<String> {
+    header
+        .get_str(&format!("TUNIT{}", index))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn column_scaling(header: &FitsHeader, index: usize) -> Scaling {
+    Scaling {
+        scale: header.get_f64(&format!("TSCAL{}", index)).unwrap_or(1.0),
+        zero: header.get_f64(&format!("TZERO{}", index)).unwrap_or(0.0),
+        null: header.get_i64(&format!("TNULL{}", index)),
+    }
+}
+
+/// Parsed binary table `TFORMn` value
+struct BinaryForm {
+    repeat: usize,
+    code: char,
+    /// Element type of a variable-length array descriptor
+    element: Option<char>,
+}
+
+impl BinaryForm {
+    fn parse(tform: &str) -> Result<Self> {
+        let tform = tform.trim();
+        let digits = tform.bytes().take_while(|b| b.is_ascii_digit()).count();
+        let repeat = if digits == 0 {
+            1
+        } else {
+            tform[..digits]
+                .parse()
+                .map_err(|_| IoError::FormatError(format!("Invalid TFORM '{}'", tform)))?
+        };
+        let mut rest = tform[digits..].chars();
+        let code = rest
+            .next()
+            .ok_or_else(|| IoError::FormatError(format!("Invalid TFORM '{}'", tform)))?;
+        let element = match code {
+            'P' | 'Q' => Some(
+                rest.next()
+                    .ok_or_else(|| IoError::FormatError(format!("Invalid TFORM '{}'", tform)))?,
+            ),
+            _ => None,
+        };
+        Ok(Self {
+            repeat,
+            code,
+            element,
+        })
+    }
+
+    /// Width in bytes of the field within a row
+    fn width(&self) -> Result<usize> {
+        match self.code {
+            'X' => Ok(self.repeat.div_ceil(8)),
+            'P' => Ok(self.repeat.min(1) * 8),
+            'Q' => Ok(self.repeat.min(1) * 16),
+            code => element_size(code)
+                .map(|size| size * self.repeat)
+                .ok_or_else(|| {
+                    IoError::FormatError(format!("Unsupported binary table type '{}'", code))
+                }),
+        }
+    }
+}
+
+/// Parse a `TDIMn` value such as `(3,4)` in FITS axis order
+fn parse_tdim(tdim: &str) -> Result<Vec<usize>> {
+    tdim.trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(|s| {
+            s.trim()
+                .parse()
+                .map_err(|_| IoError::FormatError(format!("Invalid TDIM '{}'", tdim)))
+        })
+        .collect()
+}
+
+fn trim_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end])
+        .trim_end()
+        .to_string()
+}
+
+/// Read the data of a `BINTABLE` extension, including its heap
+pub(crate) fn read_binary_table(header: &FitsHeader, data: &[u8]) -> Result<FitsTable> {
+    let row_width = required_usize(header, "NAXIS1")?;
+    let num_rows = required_usize(header, "NAXIS2")?;
+    let num_fields = required_usize(header, "TFIELDS")?;
+    let table_size = row_width * num_rows;
+    if data.len() < table_size {
+        return Err(IoError::FormatError(
+            "Truncated FITS binary table".to_string(),
+        ));
+    }
+    let heap_start = match header.get_i64("THEAP") {
+        Some(theap) => usize::try_from(theap)
+            .map_err(|_| IoError::FormatError("Negative THEAP".to_string()))?,
+        None => table_size,
+    };
+
+    let mut columns = Vec::with_capacity(num_fields);
+    let mut offset = 0;
+    for index in 1..=num_fields {
+        let tform = header
+            .get_str(&format!("TFORM{}", index))
+            .ok_or_else(|| IoError::FormatError(format!("Missing FITS keyword TFORM{}", index)))?;
+        let form = BinaryForm::parse(tform)?;
+        let width = form.width()?;
+        if offset + width > row_width {
+            return Err(IoError::FormatError(format!(
+                "Column {} extends beyond the table row",
+                index
+            )));
+        }
+        let field = |row: usize| &data[row * row_width + offset..row * row_width + offset + width];
+        let scaling = column_scaling(header, index);
+        let tdim = header
+            .get_str(&format!("TDIM{}", index))
+            .map(parse_tdim)
+            .transpose()?;
+
+        let column_data = match form.code {
+            'A' => {
+                // TDIM gives the string length first, then the array axes
+                let (length, axes) = match &tdim {
+                    Some(dims) if dims.len() > 1 && dims[0] > 0 => (dims[0], &dims[1..]),
+                    _ => (form.repeat.max(1), &[][..]),
+                };
+                let strings: Vec<String> = (0..num_rows)
+                    .flat_map(|row| {
+                        field(row)
+                            .chunks(length)
+                            .map(trim_string)
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+                let mut shape = vec![num_rows];
+                shape.extend(axes.iter().rev());
+                FitsArray::String(ArrayD::from_shape_vec(IxDyn(&shape), strings).map_err(|e| {
+                    IoError::FormatError(format!("Invalid string column shape: {}", e))
+                })?)
+            }
+            'X' => {
+                let bits: Vec<bool> = (0..num_rows)
+                    .flat_map(|row| {
+                        let bytes = field(row);
+                        (0..form.repeat).map(move |bit| bytes[bit / 8] & (0x80 >> (bit % 8)) != 0)
+                    })
+                    .collect();
+                let shape = row_shape(num_rows, form.repeat, None);
+                FitsArray::Logical(ArrayD::from_shape_vec(IxDyn(&shape), bits).map_err(|e| {
+                    IoError::FormatError(format!("Invalid bit column shape: {}", e))
+                })?)
+            }
+            'P' | 'Q' => {
+                let element = form.element.unwrap_or('B');
+                let mut rows = Vec::with_capacity(num_rows);
+                for row in 0..num_rows {
+                    let bytes = field(row);
+                    let (count, heap_offset) = if form.code == 'P' {
+                        (
+                            u32::from_be_bytes(bytes[..4].try_into().unwrap()) as usize,
+                            u32::from_be_bytes(bytes[4..8].try_into().unwrap()) as usize,
+                        )
+                    } else {
+                        (
+                            u64::from_be_bytes(bytes[..8].try_into().unwrap()) as usize,
+                            u64::from_be_bytes(bytes[8..16].try_into().unwrap()) as usize,
+                        )
+                    };
+                    rows.push(read_heap_array(
+                        data,
+                        heap_start + heap_offset,
+                        count,
+                        element,
+                        &scaling,
+                    )?);
+                }
+                FitsArray::VariableLength(rows)
+            }
+            code => {
+                let bytes: Vec<u8> = (0..num_rows).flat_map(|row| field(row).to_vec()).collect();
+                let shape = row_shape(num_rows, form.repeat, tdim.as_deref());
+                decode(code, &bytes, &scaling)?.reshape(&shape)?
+            }
+        };
+        columns.push(FitsColumn {
+            name: column_name(header, index),
+            unit: column_unit(header, index),
+            data: column_data,
+        });
+        offset += width;
+    }
+    FitsTable::new(columns)
+}
+
+/// Shape of a column with `repeat` elements per row, using `TDIMn` if given
+fn row_shape(num_rows: usize, repeat: usize, tdim: Option<&[usize]>) -> Vec<usize> {
+    let mut shape = vec![num_rows];
+    match tdim {
+        Some(dims) if dims.iter().product::<usize>() == repeat => shape.extend(dims.iter().rev()),
+        _ if repeat != 1 => shape.push(repeat),
+        _ => {}
+    }
+    shape
+}
+
+fn read_heap_array(
+    data: &[u8],
+    start: usize,
+    count: usize,
+    element: char,
+    scaling: &Scaling,
+) -> Result<FitsArray> {
+    let size = match element {
+        'X' => count.div_ceil(8),
+        code => {
+            element_size(code).ok_or_else(|| {
+                IoError::FormatError(format!("Unsupported variable-length type '{}'", code))
+            })? * count
+        }
+    };
+    let bytes = data.get(start..start + size).ok_or_else(|| {
+        IoError::FormatError("Variable-length array outside the heap".to_string())
+    })?;
+    match element {
+        'A' => Ok(FitsArray::String(ArrayD::from_elem(
+            IxDyn(&[1]),
+            trim_string(bytes),
+        ))),
+        'X' => Ok(FitsArray::Logical(
+            ArrayD::from_shape_vec(
+                IxDyn(&[count]),
+                (0..count)
+                    .map(|bit| bytes[bit / 8] & (0x80 >> (bit % 8)) != 0)
+                    .collect(),
+            )
+            .unwrap(),
+        )),
+        code => decode(code, bytes, scaling),
+    }
+}
+
+/// Parsed ASCII table `TFORMn` value
+struct AsciiForm {
+    code: char,
+    width: usize,
+    decimals: usize,
+}
+
+impl AsciiForm {
+    fn parse(tform: &str) -> Result<Self> {
+        let tform = tform.trim();
+        let invalid = || IoError::FormatError(format!("Invalid ASCII table TFORM '{}'", tform));
+        let mut chars = tform.chars();
+        let code = chars.next().ok_or_else(invalid)?;
+        let rest = chars.as_str();
+        let (width, decimals) = match rest.split_once('.') {
+            Some((w, d)) => (w, d.parse().map_err(|_| invalid())?),
+            None => (rest, 0),
+        };
+        Ok(Self {
+            code,
+            width: width.parse().map_err(|_| invalid())?,
+            decimals,
+        })
+    }
+}
+
+/// Parse a real number from an ASCII table field
+fn parse_ascii_real(text: &str, decimals: usize) -> Option<f64> {
+    let text = text.trim().replace(['D', 'd'], "E");
+    let (mantissa, exponent) = match text.find(['E', 'e']) {
+        Some(pos) => (&text[..pos], Some(&text[pos + 1..])),
+        None => (text.as_str(), None),
+    };
+    let mut value: f64 = mantissa.parse().ok()?;
+    // Without an explicit point the last `decimals` digits are fractional
+    if !mantissa.contains('.') && decimals > 0 {
+        value /= 10f64.powi(decimals as i32);
+    }
+    if let Some(exponent) = exponent {
+        value *= 10f64.powi(exponent.trim().parse().ok()?);
+    }
+    Some(value)
+}
+
+/// Read the data of an ASCII `TABLE` extension
+pub(crate) fn read_ascii_table(header: &FitsHeader, data: &[u8]) -> Result<FitsTable> {
+    let row_width = required_usize(header, "NAXIS1")?;
+    let num_rows = required_usize(header, "NAXIS2")?;
+    let num_fields = required_usize(header, "TFIELDS")?;
+    if data.len() < row_width * num_rows {
+        return Err(IoError::FormatError(
+            "Truncated FITS ASCII table".to_string(),
+        ));
+    }
+
+    let mut columns = Vec::with_capacity(num_fields);
+    for index in 1..=num_fields {
+        let tform = header
+            .get_str(&format!("TFORM{}", index))
+            .ok_or_else(|| IoError::FormatError(format!("Missing FITS keyword TFORM{}", index)))?;
+        let form = AsciiForm::parse(tform)?;
+        let start = required_usize(header, &format!("TBCOL{}", index))?
+            .checked_sub(1)
+            .ok_or_else(|| IoError::FormatError(format!("Invalid TBCOL{}", index)))?;
+        if start + form.width > row_width {
+            return Err(IoError::FormatError(format!(
+                "Column {} extends beyond the table row",
+                index
+            )));
+        }
+        let fields: Vec<String> = (0..num_rows)
+            .map(|row| {
+                let offset = row * row_width + start;
+                String::from_utf8_lossy(&data[offset..offset + form.width]).into_owned()
+            })
+            .collect();
+        let scale = header.get_f64(&format!("TSCAL{}", index)).unwrap_or(1.0);
+        let zero = header.get_f64(&format!("TZERO{}", index)).unwrap_or(0.0);
+        let null = header
+            .get_str(&format!("TNULL{}", index))
+            .map(|s| s.trim().to_string());
+        let is_null = |field: &str| {
+            let field = field.trim();
+            field.is_empty() || null.as_deref() == Some(field)
+        };
+        let invalid = |field: &str| {
+            IoError::FormatError(format!(
+                "Invalid value '{}' in ASCII table column {}",
+                field.trim(),
+                index
+            ))
+        };
+
+        let column_data = match form.code {
+            'A' => FitsArray::String(
+                ArrayD::from_shape_vec(
+                    IxDyn(&[num_rows]),
+                    fields.iter().map(|f| f.trim_end().to_string()).collect(),
+                )
+                .unwrap(),
+            ),
+            'I' if scale == 1.0 && zero == 0.0 && !fields.iter().any(|f| is_null(f)) => {
+                let values = fields
+                    .iter()
+                    .map(|f| f.trim().parse::<i64>().map_err(|_| invalid(f)))
+                    .collect::<Result<Vec<_>>>()?;
+                FitsArray::Int64(ArrayD::from_shape_vec(IxDyn(&[num_rows]), values).unwrap())
+            }
+            'I' | 'F' | 'E' | 'D' => {
+                let values = fields
+                    .iter()
+                    .map(|f| {
+                        if is_null(f) {
+                            Ok(f64::NAN)
+                        } else {
+                            parse_ascii_real(f, form.decimals)
+                                .map(|v| v * scale + zero)
+                                .ok_or_else(|| invalid(f))
+                        }
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                FitsArray::Float64(ArrayD::from_shape_vec(IxDyn(&[num_rows]), values).unwrap())
+            }
+            code => {
+                return Err(IoError::FormatError(format!(
+                    "Unsupported ASCII table type '{}'",
+                    code
+                )))
+            }
+        };
+        columns.push(FitsColumn {
+            name: column_name(header, index),
+            unit: column_unit(header, index),
+            data: column_data,
+        });
+    }
+    FitsTable::new(columns)
+}
+
+/// Build the header and data of a `BINTABLE` extension
+///
+/// Non-structural cards of `user_header` are appended after the table keywords.
+pub(crate) fn binary_table_hdu(
+    table: &FitsTable,
+    user_header: Option<&FitsHeader>,
+) -> Result<(FitsHeader, Vec<u8>)> {
+    let num_rows = table.num_rows();
+    let mut header = FitsHeader::new();
+    header.set("XTENSION", "BINTABLE", Some("binary table extension"));
+    header.set("BITPIX", 8i64, None);
+    header.set("NAXIS", 2i64, None);
+    header.set("NAXIS1", 0i64, Some("width of table in bytes"));
+    header.set("NAXIS2", num_rows, Some("number of rows"));
+    header.set("PCOUNT", 0i64, None);
+    header.set("GCOUNT", 1i64, None);
+    header.set("TFIELDS", table.num_columns(), Some("number of columns"));
+
+    // Encoded values of each column, `width` bytes per row
+    let mut encoded = Vec::with_capacity(table.num_columns());
+    for (i, column) in table.columns().iter().enumerate() {
+        let index = i + 1;
+        let shape = column.data.shape();
+        let axes = &shape[1..];
+        let count: usize = axes.iter().product();
+        header.set(&format!("TTYPE{}", index), column.name.as_str(), None);
+
+        let (tform, tdim, bytes) = match &column.data {
+            FitsArray::String(strings) => {
+                let length = strings.iter().map(|s| s.len()).max().unwrap_or(0).max(1);
+                let mut bytes = Vec::with_capacity(strings.len() * length);
+                for s in strings.iter() {
+                    bytes.extend_from_slice(s.as_bytes());
+                    bytes.resize(bytes.len() + length - s.len(), b' ');
+                }
+                let tdim = (!axes.is_empty()).then(|| {
+                    std::iter::once(length)
+                        .chain(axes.iter().rev().copied())
+                        .map(|d| d.to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                });
+                (format!("{}A", length * count), tdim, bytes)
+            }
+            FitsArray::VariableLength(_) => {
+                return Err(IoError::ValidationError(format!(
+                    "Writing variable-length column '{}' is not supported",
+                    column.name
+                )))
+            }
+            data => {
+                let (code, zero, bytes) = encode(data)?;
+                if let Some(zero) = zero {
+                    header.set(&format!("TZERO{}", index), zero, None);
+                }
+                let tdim = (axes.len() > 1).then(|| {
+                    axes.iter()
+                        .rev()
+                        .map(|d| d.to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                });
+                (format!("{}{}", count, code), tdim, bytes)
+            }
+        };
+        header.set(&format!("TFORM{}", index), tform, None);
+        if let Some(unit) = &column.unit {
+            header.set(&format!("TUNIT{}", index), unit.as_str(), None);
+        }
+        if let Some(tdim) = tdim {
+            header.set(&format!("TDIM{}", index), format!("({})", tdim), None);
+        }
+        let width = bytes.len().checked_div(num_rows).unwrap_or(0);
+        encoded.push((width, bytes));
+    }
+
+    let row_width: usize = encoded.iter().map(|(width, _)| width).sum();
+    header.set("NAXIS1", row_width, Some("width of table in bytes"));
+    let mut data = Vec::with_capacity(row_width * num_rows);
+    for row in 0..num_rows {
+        for (width, bytes) in &encoded {
+            data.extend_from_slice(&bytes[row * width..(row + 1) * width]);
+        }
+    }
+
+    if let Some(user_header) = user_header {
+        header.extend_user_cards(user_header);
+    }
+    Ok((header, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr1, arr2};
+
+    #[test]
+    fn test_binary_table_roundtrip() {
+        let table = FitsTable::new(vec![
+            FitsColumn::new("ID", arr1(&[1i32, 2, 3]).into_dyn()),
+            FitsColumn::new(
+                "FLUX",
+                arr2(&[[1.0f64, 2.0], [3.0, 4.0], [5.0, 6.0]]).into_dyn(),
+            )
+            .with_unit("Jy"),
+            FitsColumn::new(
+                "NAME",
+                arr1(&["a".to_string(), "bcd".to_string(), String::new()]).into_dyn(),
+            ),
+            FitsColumn::new("FLAG", arr1(&[true, false, true]).into_dyn()),
+            FitsColumn::new("COUNT", arr1(&[0u16, 40000, 65535]).into_dyn()),
+        ])
+        .unwrap();
+
+        let (header, data) = binary_table_hdu(&table, None).unwrap();
+        assert_eq!(header.get_i64("NAXIS1"), Some(4 + 16 + 3 + 1 + 2));
+        assert_eq!(header.get_str("TFORM2"), Some("2D"));
+        assert_eq!(header.get_str("TFORM3"), Some("3A"));
+
+        let read = read_binary_table(&header, &data).unwrap();
+        assert_eq!(read, table);
+        assert_eq!(read.column("flux").unwrap().unit.as_deref(), Some("Jy"));
+    }
+
+    #[test]
+    fn test_binary_table_bits_and_heap() {
+        let mut header = FitsHeader::new();
+        header.set("NAXIS1", 10i64, None);
+        header.set("NAXIS2", 2i64, None);
+        header.set("TFIELDS", 2i64, None);
+        header.set("TFORM1", "10X", None);
+        header.set("TFORM2", "1PJ(2)", None);
+        header.set("TTYPE2", "VAR", None);
+
+        let mut data = Vec::new();
+        // Row 1: bits 1010000011, one heap value at offset 0
+        data.extend_from_slice(&[0b1010_0000, 0b1100_0000]);
+        data.extend_from_slice(&1u32.to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes());
+        // Row 2: no bits set, two heap values at offset 4
+        data.extend_from_slice(&[0, 0]);
+        data.extend_from_slice(&2u32.to_be_bytes());
+        data.extend_from_slice(&4u32.to_be_bytes());
+        for value in [7i32, 8, 9] {
+            data.extend_from_slice(&value.to_be_bytes());
+        }
+
+        let table = read_binary_table(&header, &data).unwrap();
+        match &table.columns()[0].data {
+            FitsArray::Logical(bits) => {
+                assert_eq!(bits.shape(), &[2, 10]);
+                assert!(bits[[0, 0]] && !bits[[0, 1]] && bits[[0, 2]] && bits[[0, 9]]);
+                assert!(!bits[[1, 0]]);
+            }
+            other => panic!("unexpected column {:?}", other),
+        }
+        assert_eq!(table.column_names(), vec!["COL1", "VAR"]);
+        match &table.column("VAR").unwrap().data {
+            FitsArray::VariableLength(rows) => {
+                assert_eq!(rows[0], FitsArray::Int32(arr1(&[7]).into_dyn()));
+                assert_eq!(rows[1], FitsArray::Int32(arr1(&[8, 9]).into_dyn()));
+            }
+            other => panic!("unexpected column {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ascii_table() {
+        let mut header = FitsHeader::new();
+        header.set("NAXIS1", 20i64, None);
+        header.set("NAXIS2", 3i64, None);
+        header.set("TFIELDS", 3i64, None);
+        header.set("TTYPE1", "NAME", None);
+        header.set("TFORM1", "A6", None);
+        header.set("TBCOL1", 1i64, None);
+        header.set("TTYPE2", "N", None);
+        header.set("TFORM2", "I4", None);
+        header.set("TBCOL2", 7i64, None);
+        header.set("TTYPE3", "X", None);
+        header.set("TFORM3", "E10.2", None);
+        header.set("TBCOL3", 11i64, None);
+        let data = [
+            "alpha    1  1.5D+01 ",
+            "beta    -2      1234",
+            "gamma  999          ",
+        ]
+        .concat();
+
+        let table = read_ascii_table(&header, data.as_bytes()).unwrap();
+        assert_eq!(
+            table.column("NAME").unwrap().data,
+            FitsArray::String(
+                arr1(&["alpha".to_string(), "beta".to_string(), "gamma".to_string()]).into_dyn()
+            )
+        );
+        assert_eq!(
+            table.column("N").unwrap().data,
+            FitsArray::Int64(arr1(&[1, -2, 999]).into_dyn())
+        );
+        let x = table.column("X").unwrap().data.to_f64().unwrap();
+        assert_eq!(x[0], 15.0);
+        assert!((x[1] - 12.34).abs() < 1e-12);
+        assert!(x[2].is_nan());
+    }
+}
@@ -11,12 +11,23 @@
 //! - Multiple datatypes (integers, floats, strings, compound types)
 //! - Chunking and compression support
 //! - Integration with ndarray for efficient array operations
+//!
+//! Files are read and written by a pure-Rust implementation of the HDF5 on-disk
+//! format, so no HDF5 C library is required. Written files use the layout of the
+//! reference library's default settings (version 0 superblock, symbol-table
+//! groups) and open in h5py and the HDF5 command line tools; files produced by
+//! h5py, including those using the newer 1.8/1.10 object formats, read back into
+//! the same `Group`/`Dataset` structures.
 
 use crate::error::{IoError, Result};
 use ndarray::{ArrayBase, ArrayD, IxDyn};
 use std::collections::HashMap;
 use std::path::Path;
 
+mod format;
+pub(crate) mod reader;
+mod writer;
+
 /// HDF5 data type enumeration
 #[derive(Debug, Clone, PartialEq)]
 pub enum HDF5DataType {
@@ -54,6 +65,16 @@ pub enum HDF5DataType {
         /// Enumeration values (name, value) pairs
         values: Vec<(String, i64)>,
     },
+    /// Opaque type (uninterpreted bytes)
+    Opaque {
+        /// Size of one element in bytes
+        size: usize,
+    },
+    /// Reference type (object or region references, stored as raw bytes)
+    Reference {
+        /// Size of one element in bytes
+        size: usize,
+    },
 }
 
 /// String encoding types
@@ -94,12 +115,10 @@ pub struct DatasetOptions {
 /// HDF5 file handle
 pub struct HDF5File {
     /// File path
-    #[allow(dead_code)]
     path: String,
     /// Root group
     root: Group,
     /// File access mode
-    #[allow(dead_code)]
     mode: FileMode,
 }
 
@@ -220,13 +239,31 @@ impl HDF5File {
         })
     }
 
-    /// Open an existing HDF5 file
+    /// Open an HDF5 file
+    ///
+    /// `ReadOnly` and `ReadWrite` load the whole group hierarchy of an existing
+    /// file. `Create` fails if the file already exists, while `Truncate` starts
+    /// from an empty root group; in both cases nothing is written until
+    /// [`HDF5File::write`] is called.
     pub fn open<P: AsRef<Path>>(path: P, mode: FileMode) -> Result<Self> {
-        // TODO: Implement actual HDF5 file reading
-        // This is a placeholder implementation
+        let path = path.as_ref();
+        let root = match mode {
+            FileMode::ReadOnly | FileMode::ReadWrite => reader::read_file(path)?,
+            FileMode::Create => {
+                if path.exists() {
+                    return Err(IoError::FileError(format!(
+                        "File '{}' already exists",
+                        path.display()
+                    )));
+                }
+                Group::new("/".to_string())
+            }
+            FileMode::Truncate => Group::new("/".to_string()),
+        };
+
         Ok(Self {
-            path: path.as_ref().to_string_lossy().to_string(),
-            root: Group::new("/".to_string()),
+            path: path.to_string_lossy().to_string(),
+            root,
             mode,
         })
     }
@@ -328,17 +365,24 @@ impl HDF5File {
     }
 
     /// Write the file to disk
+    ///
+    /// The complete group hierarchy is serialized, replacing any existing file
+    /// at the same path.
     pub fn write(&self) -> Result<()> {
-        // TODO: Implement actual HDF5 file writing
-        // This is a placeholder implementation
-        println!("Writing HDF5 file to: {}", self.path);
-        Ok(())
+        if self.mode == FileMode::ReadOnly {
+            return Err(IoError::FileError(format!(
+                "HDF5 file '{}' was opened read-only",
+                self.path
+            )));
+        }
+        writer::write_file(Path::new(&self.path), &self.root)
     }
 
     /// Close the file
+    ///
+    /// The file contents are held in memory, so closing only releases the
+    /// handle; call [`HDF5File::write`] first to persist changes.
     pub fn close(self) -> Result<()> {
-        // TODO: Implement actual file closing
-        // This is a placeholder implementation
         Ok(())
     }
 }
@@ -491,4 +535,294 @@ mod tests {
         assert_eq!(file.mode, FileMode::Create);
         assert_eq!(file.root.name, "/");
     }
+
+    fn dataset(name: &str, dtype: HDF5DataType, shape: Vec<usize>, data: DataArray) -> Dataset {
+        Dataset {
+            name: name.to_string(),
+            dtype,
+            shape,
+            data,
+            attributes: HashMap::new(),
+            options: DatasetOptions::default(),
+        }
+    }
+
+    #[test]
+    fn test_write_read_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("roundtrip.h5");
+
+        let mut datasets = HashMap::new();
+        let temperature = ndarray::array![[20.5, 21.0, 21.5], [22.0, 22.5, 23.0]].into_dyn();
+        datasets.insert("data/temperature".to_string(), temperature.clone());
+        datasets.insert(
+            "time".to_string(),
+            ndarray::array![0.0, 0.5, 1.0].into_dyn(),
+        );
+        write_hdf5(&path, datasets).unwrap();
+
+        let root = read_hdf5(&path).unwrap();
+        assert_eq!(root.name, "/");
+        assert!(root.datasets.contains_key("time"));
+        let data_group = root.get_group("data").unwrap();
+        assert_eq!(data_group.name, "data");
+        assert_eq!(data_group.datasets["temperature"].shape, vec![2, 3]);
+
+        let file = HDF5File::open(&path, FileMode::ReadOnly).unwrap();
+        assert_eq!(file.read_dataset("data/temperature").unwrap(), temperature);
+        assert!(file.write().is_err());
+    }
+
+    #[test]
+    fn test_types_and_attributes_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("types.h5");
+
+        create_hdf5_with_structure(&path, |file| {
+            let root = file.root_mut();
+            root.set_attribute("version", AttributeValue::Integer(-3));
+            root.set_attribute("scale", AttributeValue::Float(0.25));
+            root.set_attribute("title", AttributeValue::String("Résumé".to_string()));
+            root.set_attribute("ids", AttributeValue::IntegerArray(vec![1, 2, 3]));
+            root.set_attribute("weights", AttributeValue::FloatArray(vec![0.5, 1.5]));
+            root.set_attribute(
+                "labels",
+                AttributeValue::StringArray(vec!["a".to_string(), "longer".to_string()]),
+            );
+
+            let group = root.create_group("typed");
+            let mut counts = dataset(
+                "counts",
+                HDF5DataType::Integer {
+                    size: 2,
+                    signed: false,
+                },
+                vec![4],
+                DataArray::Integer(vec![0, 1, 65535, 42]),
+            );
+            counts
+                .attributes
+                .insert("unit".to_string(), AttributeValue::String("m".to_string()));
+            group.datasets.insert("counts".to_string(), counts);
+            group.datasets.insert(
+                "single".to_string(),
+                dataset(
+                    "single",
+                    HDF5DataType::Float { size: 4 },
+                    vec![2, 2],
+                    DataArray::Float(vec![1.5, -2.25, 3.0, 1e10]),
+                ),
+            );
+            group.datasets.insert(
+                "names".to_string(),
+                dataset(
+                    "names",
+                    HDF5DataType::String {
+                        encoding: StringEncoding::UTF8,
+                    },
+                    vec![3],
+                    DataArray::String(vec!["x".to_string(), "".to_string(), "zeta".to_string()]),
+                ),
+            );
+            group.datasets.insert(
+                "blob".to_string(),
+                dataset(
+                    "blob",
+                    HDF5DataType::Opaque { size: 1 },
+                    vec![5],
+                    DataArray::Binary(vec![0, 255, 7, 8, 9]),
+                ),
+            );
+            group.datasets.insert(
+                "scalar".to_string(),
+                dataset(
+                    "scalar",
+                    HDF5DataType::Integer {
+                        size: 8,
+                        signed: true,
+                    },
+                    vec![],
+                    DataArray::Integer(vec![i64::MIN]),
+                ),
+            );
+            group.create_group("empty");
+            Ok(())
+        })
+        .unwrap();
+
+        let root = read_hdf5(&path).unwrap();
+        assert!(matches!(
+            root.attributes["version"],
+            AttributeValue::Integer(-3)
+        ));
+        assert!(matches!(root.attributes["scale"], AttributeValue::Float(v) if v == 0.25));
+        assert!(matches!(&root.attributes["title"], AttributeValue::String(s) if s == "Résumé"));
+        assert!(
+            matches!(&root.attributes["ids"], AttributeValue::IntegerArray(v) if v == &[1, 2, 3])
+        );
+        assert!(
+            matches!(&root.attributes["weights"], AttributeValue::FloatArray(v) if v == &[0.5, 1.5])
+        );
+        assert!(
+            matches!(&root.attributes["labels"], AttributeValue::StringArray(v) if v == &["a", "longer"])
+        );
+
+        let typed = root.get_group("typed").unwrap();
+        assert!(typed.get_group("empty").unwrap().datasets.is_empty());
+
+        let counts = &typed.datasets["counts"];
+        assert_eq!(
+            counts.dtype,
+            HDF5DataType::Integer {
+                size: 2,
+                signed: false
+            }
+        );
+        assert!(matches!(&counts.data, DataArray::Integer(v) if v == &[0, 1, 65535, 42]));
+        assert!(matches!(&counts.attributes["unit"], AttributeValue::String(s) if s == "m"));
+
+        let single = &typed.datasets["single"];
+        assert_eq!(single.dtype, HDF5DataType::Float { size: 4 });
+        assert!(matches!(&single.data, DataArray::Float(v) if v == &[1.5, -2.25, 3.0, 1e10]));
+
+        assert!(
+            matches!(&typed.datasets["names"].data, DataArray::String(v) if v == &["x", "", "zeta"])
+        );
+        assert!(
+            matches!(&typed.datasets["blob"].data, DataArray::Binary(v) if v == &[0, 255, 7, 8, 9])
+        );
+
+        let scalar = &typed.datasets["scalar"];
+        assert!(scalar.shape.is_empty());
+        assert!(matches!(&scalar.data, DataArray::Integer(v) if v == &[i64::MIN]));
+    }
+
+    #[test]
+    fn test_chunked_compressed_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chunked.h5");
+
+        let values: Vec<f64> = (0..35).map(|i| i as f64 * 0.5).collect();
+        let mut file = HDF5File::create(&path).unwrap();
+        let options = DatasetOptions {
+            chunk_size: Some(vec![2, 3]),
+            compression: CompressionOptions {
+                gzip: Some(6),
+                shuffle: true,
+                ..Default::default()
+            },
+            fill_value: Some(-1.0),
+            fletcher32: true,
+        };
+        let array = ArrayD::from_shape_vec(IxDyn(&[5, 7]), values.clone()).unwrap();
+        file.create_dataset_from_array("compressed", &array, Some(options))
+            .unwrap();
+
+        // Enough chunks to need a multi-level chunk index
+        let long: Vec<f64> = (0..1000).map(|i| (i as f64).sin()).collect();
+        let long_array = ArrayD::from_shape_vec(IxDyn(&[1000]), long).unwrap();
+        let long_options = DatasetOptions {
+            chunk_size: Some(vec![7]),
+            ..Default::default()
+        };
+        file.create_dataset_from_array("long", &long_array, Some(long_options))
+            .unwrap();
+        file.write().unwrap();
+
+        let file = HDF5File::open(&path, FileMode::ReadOnly).unwrap();
+        assert_eq!(file.read_dataset("compressed").unwrap(), array);
+        assert_eq!(file.read_dataset("long").unwrap(), long_array);
+
+        let options = &file.root().datasets["compressed"].options;
+        assert_eq!(options.chunk_size, Some(vec![2, 3]));
+        assert_eq!(options.compression.gzip, Some(6));
+        assert!(options.compression.shuffle);
+        assert!(options.fletcher32);
+        assert_eq!(options.fill_value, Some(-1.0));
+    }
+
+    #[test]
+    fn test_dataspace_larger_than_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("truncated.h5");
+
+        let array = ArrayD::from_shape_vec(IxDyn(&[3, 5]), vec![1.0; 15]).unwrap();
+        let mut file = HDF5File::create(&path).unwrap();
+        file.create_dataset_from_array("contiguous", &array, None)
+            .unwrap();
+        file.write().unwrap();
+        let contiguous = std::fs::read(&path).unwrap();
+
+        let mut file = HDF5File::create(&path).unwrap();
+        let options = DatasetOptions {
+            chunk_size: Some(vec![2, 2]),
+            ..Default::default()
+        };
+        file.create_dataset_from_array("chunked", &array, Some(options))
+            .unwrap();
+        file.write().unwrap();
+        let chunked = std::fs::read(&path).unwrap();
+
+        // Rewrite the first dimension of the 3x5 dataspace message
+        let mut space = vec![1u8, 2, 0, 0, 0, 0, 0, 0];
+        space.extend_from_slice(&3u64.to_le_bytes());
+        space.extend_from_slice(&5u64.to_le_bytes());
+        let with_rows = |bytes: &[u8], rows: u64| {
+            let pos = bytes.windows(space.len()).position(|w| w == space).unwrap();
+            let mut bytes = bytes.to_vec();
+            bytes[pos + 8..pos + 16].copy_from_slice(&rows.to_le_bytes());
+            bytes
+        };
+
+        for (bytes, rows) in [
+            (&contiguous, 300),
+            (&contiguous, 1 << 33),
+            (&contiguous, u64::MAX / 4),
+            (&chunked, 1 << 33),
+        ] {
+            std::fs::write(&path, with_rows(bytes, rows)).unwrap();
+            assert!(read_hdf5(&path).is_err(), "{} rows", rows);
+        }
+    }
+
+    #[test]
+    fn test_large_group_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("large_group.h5");
+
+        // More members than fit in one symbol table B-tree node
+        let mut datasets = HashMap::new();
+        for i in 0..300 {
+            datasets.insert(
+                format!("g/d{:03}", i),
+                ndarray::arr1(&[i as f64]).into_dyn(),
+            );
+        }
+        write_hdf5(&path, datasets).unwrap();
+
+        let root = read_hdf5(&path).unwrap();
+        let group = root.get_group("g").unwrap();
+        assert_eq!(group.datasets.len(), 300);
+        assert!(matches!(&group.datasets["d123"].data, DataArray::Float(v) if v == &[123.0]));
+    }
+
+    #[test]
+    fn test_open_modes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("modes.h5");
+        write_hdf5(&path, HashMap::new()).unwrap();
+
+        assert!(HDF5File::open(&path, FileMode::Create).is_err());
+        let mut file = HDF5File::open(&path, FileMode::ReadWrite).unwrap();
+        file.root_mut()
+            .set_attribute("added", AttributeValue::Integer(1));
+        file.write().unwrap();
+        assert!(read_hdf5(&path).unwrap().attributes.contains_key("added"));
+
+        let file = HDF5File::open(&path, FileMode::Truncate).unwrap();
+        assert!(file.root().attributes.is_empty());
+
+        std::fs::write(&path, b"not an hdf5 file").unwrap();
+        assert!(read_hdf5(&path).is_err());
+    }
 }
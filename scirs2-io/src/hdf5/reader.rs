@@ -0,0 +1,2164 @@
+//! HDF5 file reader
+//!
+//! Parses the subset of the HDF5 format produced by the reference library and
+//! h5py: superblock versions 0-3, version 1 and 2 object headers (with
+//! continuation blocks), symbol-table groups as well as compact and dense
+//! link/attribute storage (fractal heaps indexed by version 2 B-trees), and
+//! compact, contiguous and chunked dataset layouts. Chunks may be indexed by a
+//! version 1 B-tree or, for files written with the 1.10 format, by the single
+//! chunk, implicit and fixed array indexes. The deflate, shuffle, Fletcher32
+//! and LZF filters are supported; variable-length strings are resolved through
+//! the global heap.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::Path;
+
+use flate2::read::ZlibDecoder;
+
+use super::format::*;
+use super::{
+    AttributeValue, CompressionOptions, DataArray, Dataset, DatasetOptions, Group, HDF5DataType,
+    StringEncoding,
+};
+use crate::error::{IoError, Result};
+
+/// Maximum nesting of shared datatypes and fractal heap indirect blocks
+const MAX_INDIRECTION: usize = 64;
+
+/// Largest part of a dataset, in bytes, that may be materialised from fill
+/// values rather than from data stored in the file
+const MAX_UNSTORED_BYTES: usize = 1 << 28;
+
+/// Largest ratio of decoded to stored chunk size any supported filter produces
+const MAX_FILTER_EXPANSION: usize = 1032;
+
+/// Read an HDF5 file and return its root group
+pub(crate) fn read_file(path: &Path) -> Result<Group> {
+    read_file_with_paths(path).map(|(root, _)| root)
+}
+
+/// Read an HDF5 file and return its root group together with the absolute
+/// path of every object, keyed by object header address
+///
+/// The addresses are the values stored in object references, which lets
+/// callers resolve reference datasets against the returned tree.
+pub(crate) fn read_file_with_paths(path: &Path) -> Result<(Group, HashMap<u64, String>)> {
+    let data = std::fs::read(path)
+        .map_err(|e| IoError::FileError(format!("Failed to read HDF5 file: {}", e)))?;
+    let reader = FileReader::new(data)?;
+    let mut ancestors = Vec::new();
+    let messages = reader.object_header(reader.root_address)?;
+    let root = reader.read_group("/", &messages, reader.root_address, &mut ancestors)?;
+    Ok((root, reader.object_paths.into_inner()))
+}
+
+fn format_error<T>(msg: impl Into<String>) -> Result<T> {
+    Err(IoError::FormatError(msg.into()))
+}
+
+/// Bounds-checked little-endian reader over a byte slice
+struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.buf.len().saturating_sub(self.pos)
+    }
+
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        if n > self.remaining() {
+            return format_error("Unexpected end of HDF5 structure");
+        }
+        let out = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(out)
+    }
+
+    fn skip(&mut self, n: usize) -> Result<()> {
+        self.bytes(n).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(self.uint(2)? as u16)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(self.uint(4)? as u32)
+    }
+
+    /// Read an unsigned little-endian integer of `n` (at most 8) bytes
+    fn uint(&mut self, n: usize) -> Result<u64> {
+        Ok(le_uint(self.bytes(n)?))
+    }
+
+    /// Read a null-terminated string
+    fn cstr(&mut self) -> Result<String> {
+        let rest = &self.buf[self.pos.min(self.buf.len())..];
+        let len = rest
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| IoError::FormatError("Unterminated string".to_string()))?;
+        let s = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.pos += len + 1;
+        Ok(s)
+    }
+}
+
+fn le_uint(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .take(8)
+        .enumerate()
+        .fold(0u64, |acc, (i, &b)| acc | (b as u64) << (8 * i))
+}
+
+/// Number of bytes needed to encode `value` (the library's `H5VM_limit_enc_size`)
+fn limit_enc_size(value: u64) -> usize {
+    (log2_floor(value) / 8) as usize + 1
+}
+
+fn log2_floor(value: u64) -> u32 {
+    63 - value.max(1).leading_zeros()
+}
+
+/// A raw object header message
+struct Message {
+    msg_type: u16,
+    flags: u8,
+    data: Vec<u8>,
+}
+
+/// Datatype as decoded from a datatype message
+#[derive(Debug, Clone)]
+enum Dtype {
+    Integer {
+        size: usize,
+        signed: bool,
+        big_endian: bool,
+    },
+    Float {
+        size: usize,
+        big_endian: bool,
+    },
+    String {
+        size: usize,
+        encoding: StringEncoding,
+        space_padded: bool,
+    },
+    VarString {
+        size: usize,
+        encoding: StringEncoding,
+    },
+    Opaque {
+        size: usize,
+    },
+    Enum {
+        base: Box<Dtype>,
+        values: Vec<(String, i64)>,
+    },
+    Compound {
+        size: usize,
+        fields: Vec<(String, Dtype)>,
+    },
+    Array {
+        base: Box<Dtype>,
+        shape: Vec<usize>,
+    },
+    Reference {
+        size: usize,
+    },
+    Other {
+        size: usize,
+    },
+}
+
+impl Dtype {
+    fn size(&self) -> usize {
+        match self {
+            Dtype::Integer { size, .. }
+            | Dtype::Float { size, .. }
+            | Dtype::String { size, .. }
+            | Dtype::VarString { size, .. }
+            | Dtype::Opaque { size }
+            | Dtype::Compound { size, .. }
+            | Dtype::Reference { size }
+            | Dtype::Other { size } => *size,
+            Dtype::Enum { base, .. } => base.size(),
+            Dtype::Array { base, shape } => base.size() * shape.iter().product::<usize>(),
+        }
+    }
+
+    fn public(&self) -> HDF5DataType {
+        match self {
+            Dtype::Integer { size, signed, .. } => HDF5DataType::Integer {
+                size: *size,
+                signed: *signed,
+            },
+            Dtype::Float { size, .. } => HDF5DataType::Float { size: *size },
+            Dtype::String { encoding, .. } | Dtype::VarString { encoding, .. } => {
+                HDF5DataType::String {
+                    encoding: encoding.clone(),
+                }
+            }
+            Dtype::Enum { values, .. } => HDF5DataType::Enum {
+                values: values.clone(),
+            },
+            Dtype::Compound { fields, .. } => HDF5DataType::Compound {
+                fields: fields
+                    .iter()
+                    .map(|(n, t)| (n.clone(), t.public()))
+                    .collect(),
+            },
+            Dtype::Array { base, shape } => HDF5DataType::Array {
+                base_type: Box::new(base.public()),
+                shape: shape.clone(),
+            },
+            Dtype::Reference { size } => HDF5DataType::Reference { size: *size },
+            Dtype::Opaque { size } | Dtype::Other { size } => HDF5DataType::Opaque { size: *size },
+        }
+    }
+}
+
+/// Decoded dataspace message
+struct Dataspace {
+    dims: Vec<usize>,
+    max_dims: Option<Vec<u64>>,
+    is_null: bool,
+}
+
+impl Dataspace {
+    fn count(&self) -> Result<usize> {
+        if self.is_null {
+            return Ok(0);
+        }
+        self.dims
+            .iter()
+            .try_fold(1usize, |acc, &d| acc.checked_mul(d))
+            .ok_or_else(|| IoError::FormatError("Dataspace is too large".to_string()))
+    }
+}
+
+/// A filter of a dataset's filter pipeline
+struct FilterInfo {
+    id: u16,
+    client_data: Vec<u32>,
+}
+
+/// Storage layout of a dataset
+enum Layout {
+    Compact(Vec<u8>),
+    Contiguous {
+        address: u64,
+    },
+    BTreeV1 {
+        address: u64,
+        chunk: Vec<usize>,
+    },
+    SingleChunk {
+        address: u64,
+        chunk: Vec<usize>,
+        filtered: Option<(u64, u32)>,
+    },
+    Implicit {
+        address: u64,
+        chunk: Vec<usize>,
+    },
+    FixedArray {
+        address: u64,
+        chunk: Vec<usize>,
+    },
+}
+
+impl Layout {
+    fn chunk(&self) -> Option<&[usize]> {
+        match self {
+            Layout::BTreeV1 { chunk, .. }
+            | Layout::SingleChunk { chunk, .. }
+            | Layout::Implicit { chunk, .. }
+            | Layout::FixedArray { chunk, .. } => Some(chunk),
+            _ => None,
+        }
+    }
+}
+
+/// A chunk located through one of the chunk indexes
+struct ChunkRecord {
+    origin: Vec<usize>,
+    address: u64,
+    size: usize,
+    filter_mask: u32,
+}
+
+/// Fractal heap header fields needed to locate managed objects
+struct FractalHeap {
+    table_width: usize,
+    start_block_size: u64,
+    max_direct_block_size: u64,
+    root_address: u64,
+    root_rows: usize,
+    offset_bytes: usize,
+    length_bytes: usize,
+    filtered: bool,
+}
+
+struct FileReader {
+    data: Vec<u8>,
+    base: u64,
+    offset_size: usize,
+    length_size: usize,
+    root_address: u64,
+    global_heaps: RefCell<HashMap<u64, HashMap<u16, Vec<u8>>>>,
+    object_paths: RefCell<HashMap<u64, String>>,
+}
+
+impl FileReader {
+    fn new(data: Vec<u8>) -> Result<Self> {
+        // The superblock may follow a user block of 512, 1024, 2048, ... bytes
+        let mut sb_pos = 0usize;
+        loop {
+            if sb_pos + 8 > data.len() {
+                return format_error("Not an HDF5 file: superblock signature not found");
+            }
+            if data[sb_pos..sb_pos + 8] == SIGNATURE {
+                break;
+            }
+            sb_pos = if sb_pos == 0 { 512 } else { sb_pos * 2 };
+        }
+
+        let mut c = Cursor::new(&data[sb_pos..]);
+        c.skip(8)?;
+        let version = c.u8()?;
+        let (offset_size, length_size, base, root_address) = match version {
+            0 | 1 => {
+                c.skip(4)?; // free-space, root entry, reserved, shared header versions
+                let offset_size = c.u8()? as usize;
+                let length_size = c.u8()? as usize;
+                c.skip(1)?;
+                c.skip(4)?; // group leaf and internal K
+                c.skip(4)?; // file consistency flags
+                if version == 1 {
+                    c.skip(4)?; // indexed storage K and reserved
+                }
+                let base = c.uint(offset_size)?;
+                c.skip(3 * offset_size)?; // free-space, end of file, driver info
+                                          // Root group symbol table entry
+                c.skip(offset_size)?;
+                let root = c.uint(offset_size)?;
+                (offset_size, length_size, base, root)
+            }
+            2 | 3 => {
+                let offset_size = c.u8()? as usize;
+                let length_size = c.u8()? as usize;
+                c.skip(1)?; // file consistency flags
+                let base = c.uint(offset_size)?;
+                c.skip(2 * offset_size)?; // superblock extension, end of file
+                let root = c.uint(offset_size)?;
+                (offset_size, length_size, base, root)
+            }
+            v => return format_error(format!("Unsupported HDF5 superblock version {}", v)),
+        };
+
+        if !matches!(offset_size, 2 | 4 | 8) || !matches!(length_size, 2 | 4 | 8) {
+            return format_error(format!(
+                "Unsupported HDF5 offset/length sizes {}/{}",
+                offset_size, length_size
+            ));
+        }
+
+        Ok(Self {
+            data,
+            // A base address of 0 in a file with a user block refers to the superblock
+            base: if base == 0 { sb_pos as u64 } else { base },
+            offset_size,
+            length_size,
+            root_address,
+            global_heaps: RefCell::new(HashMap::new()),
+            object_paths: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Cursor positioned at a file address
+    fn at(&self, address: u64) -> Result<Cursor<'_>> {
+        if address == UNDEF_ADDR {
+            return format_error("Attempt to read from an undefined address");
+        }
+        let pos = address
+            .checked_add(self.base)
+            .filter(|&p| p < self.data.len() as u64)
+            .ok_or_else(|| {
+                IoError::FormatError(format!("Address {:#x} is beyond the end of file", address))
+            })?;
+        Ok(Cursor::new(&self.data[pos as usize..]))
+    }
+
+    /// Number of file bytes from `address` to the end of the file
+    fn bytes_after(&self, address: u64) -> usize {
+        address
+            .checked_add(self.base)
+            .and_then(|pos| (self.data.len() as u64).checked_sub(pos))
+            .map_or(0, |n| n as usize)
+    }
+
+    fn read_bytes(&self, address: u64, len: usize) -> Result<&[u8]> {
+        if len == 0 {
+            return Ok(&[]);
+        }
+        self.at(address)?.bytes(len)
+    }
+
+    fn offset(&self, c: &mut Cursor) -> Result<u64> {
+        let value = c.uint(self.offset_size)?;
+        let undef = if self.offset_size == 8 {
+            u64::MAX
+        } else {
+            (1u64 << (8 * self.offset_size)) - 1
+        };
+        Ok(if value == undef { UNDEF_ADDR } else { value })
+    }
+
+    fn length(&self, c: &mut Cursor) -> Result<u64> {
+        c.uint(self.length_size)
+    }
+
+    // ------------------------------------------------------------------
+    // Object headers
+    // ------------------------------------------------------------------
+
+    fn object_header(&self, address: u64) -> Result<Vec<Message>> {
+        let mut messages = Vec::new();
+        let mut c = self.at(address)?;
+        let mut blocks: Vec<(u64, u64)> = Vec::new();
+        let mut seen = HashSet::new();
+
+        if c.buf.starts_with(b"OHDR") {
+            c.skip(4)?;
+            let version = c.u8()?;
+            if version != 2 {
+                return format_error(format!("Unsupported object header version {}", version));
+            }
+            let flags = c.u8()?;
+            if flags & 0x20 != 0 {
+                c.skip(16)?; // access, modification, change and birth times
+            }
+            if flags & 0x10 != 0 {
+                c.skip(4)?; // attribute storage phase change values
+            }
+            let chunk_size = c.uint(1 << (flags & 0x03))? as usize;
+            let body = c.bytes(chunk_size)?;
+            self.parse_v2_messages(body, flags, &mut messages, &mut blocks)?;
+
+            while let Some((addr, len)) = blocks.pop() {
+                if !seen.insert(addr) {
+                    continue;
+                }
+                let block = self.read_bytes(addr, len as usize)?;
+                if !block.starts_with(b"OCHK") || block.len() < 8 {
+                    return format_error("Invalid object header continuation block");
+                }
+                self.parse_v2_messages(
+                    &block[4..block.len() - 4],
+                    flags,
+                    &mut messages,
+                    &mut blocks,
+                )?;
+            }
+        } else {
+            let version = c.u8()?;
+            if version != 1 {
+                return format_error(format!(
+                    "Unsupported object header version {} at {:#x}",
+                    version, address
+                ));
+            }
+            c.skip(1)?;
+            let n_messages = c.u16()? as usize;
+            c.skip(4)?; // reference count
+            let size = c.u32()? as u64;
+            blocks.push((address + 16, size));
+
+            let mut queue = std::collections::VecDeque::from(blocks);
+            while let Some((addr, len)) = queue.pop_front() {
+                if !seen.insert(addr) {
+                    continue;
+                }
+                let block = self.read_bytes(addr, len as usize)?;
+                let mut more = Vec::new();
+                self.parse_v1_messages(block, &mut messages, &mut more)?;
+                queue.extend(more);
+                if messages.len() > n_messages.max(1) * 4 + 1024 {
+                    return format_error("Corrupt object header message count");
+                }
+            }
+        }
+
+        Ok(messages)
+    }
+
+    fn parse_v1_messages(
+        &self,
+        block: &[u8],
+        messages: &mut Vec<Message>,
+        continuations: &mut Vec<(u64, u64)>,
+    ) -> Result<()> {
+        let mut c = Cursor::new(block);
+        while c.remaining() >= 8 {
+            let msg_type = c.u16()?;
+            let size = c.u16()? as usize;
+            let flags = c.u8()?;
+            c.skip(3)?;
+            let data = c.bytes(size)?;
+            self.push_message(msg_type, flags, data, messages, continuations)?;
+        }
+        Ok(())
+    }
+
+    fn parse_v2_messages(
+        &self,
+        block: &[u8],
+        header_flags: u8,
+        messages: &mut Vec<Message>,
+        continuations: &mut Vec<(u64, u64)>,
+    ) -> Result<()> {
+        let header_size = if header_flags & 0x04 != 0 { 6 } else { 4 };
+        let mut c = Cursor::new(block);
+        while c.remaining() >= header_size {
+            let msg_type = c.u8()? as u16;
+            let size = c.u16()? as usize;
+            let flags = c.u8()?;
+            if header_flags & 0x04 != 0 {
+                c.skip(2)?; // creation order
+            }
+            let data = c.bytes(size)?;
+            self.push_message(msg_type, flags, data, messages, continuations)?;
+        }
+        Ok(())
+    }
+
+    fn push_message(
+        &self,
+        msg_type: u16,
+        flags: u8,
+        data: &[u8],
+        messages: &mut Vec<Message>,
+        continuations: &mut Vec<(u64, u64)>,
+    ) -> Result<()> {
+        match msg_type {
+            MSG_NIL => {}
+            MSG_CONTINUATION => {
+                let mut c = Cursor::new(data);
+                let addr = self.offset(&mut c)?;
+                let len = self.length(&mut c)?;
+                continuations.push((addr, len));
+            }
+            _ => messages.push(Message {
+                msg_type,
+                flags,
+                data: data.to_vec(),
+            }),
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Groups
+    // ------------------------------------------------------------------
+
+    fn read_group(
+        &self,
+        path: &str,
+        messages: &[Message],
+        address: u64,
+        ancestors: &mut Vec<u64>,
+    ) -> Result<Group> {
+        let name = match path.rsplit('/').next() {
+            Some(last) if !last.is_empty() => last,
+            _ => path,
+        };
+        let mut group = Group::new(name.to_string());
+        group.attributes = self.read_attributes(messages)?;
+        self.object_paths
+            .borrow_mut()
+            .entry(address)
+            .or_insert_with(|| path.to_string());
+
+        ancestors.push(address);
+        for (link_name, target) in self.read_links(messages)? {
+            // Hard links may point back up the hierarchy
+            if ancestors.contains(&target) {
+                continue;
+            }
+            let child_path = format!("{}/{}", path.trim_end_matches('/'), link_name);
+            self.object_paths
+                .borrow_mut()
+                .entry(target)
+                .or_insert_with(|| child_path.clone());
+            let child = self.object_header(target)?;
+            if child.iter().any(|m| m.msg_type == MSG_LAYOUT) {
+                let dataset = self.read_dataset(&link_name, &child)?;
+                group.datasets.insert(link_name, dataset);
+            } else if child.iter().any(|m| {
+                matches!(
+                    m.msg_type,
+                    MSG_SYMBOL_TABLE | MSG_LINK_INFO | MSG_LINK | MSG_GROUP_INFO
+                )
+            }) {
+                let sub = self.read_group(&child_path, &child, target, ancestors)?;
+                group.groups.insert(link_name, sub);
+            }
+            // Anything else (e.g. committed datatypes) has no counterpart here
+        }
+        ancestors.pop();
+
+        Ok(group)
+    }
+
+    /// Collect the hard links of a group as (name, object header address) pairs
+    fn read_links(&self, messages: &[Message]) -> Result<Vec<(String, u64)>> {
+        let mut links = Vec::new();
+        for msg in messages {
+            match msg.msg_type {
+                MSG_SYMBOL_TABLE => {
+                    let mut c = Cursor::new(&msg.data);
+                    let btree = self.offset(&mut c)?;
+                    let heap = self.offset(&mut c)?;
+                    let heap_data = self.local_heap_data(heap)?;
+                    let mut visited = HashSet::new();
+                    self.walk_group_btree(btree, heap_data, &mut links, &mut visited)?;
+                }
+                MSG_LINK => {
+                    if let Some(link) = self.parse_link(&msg.data)? {
+                        links.push(link);
+                    }
+                }
+                MSG_LINK_INFO => {
+                    let mut c = Cursor::new(&msg.data);
+                    c.skip(1)?;
+                    let flags = c.u8()?;
+                    if flags & 0x01 != 0 {
+                        c.skip(8)?;
+                    }
+                    let heap = self.offset(&mut c)?;
+                    let name_index = self.offset(&mut c)?;
+                    if heap != UNDEF_ADDR && name_index != UNDEF_ADDR {
+                        let heap = self.fractal_heap(heap)?;
+                        for record in self.btree2_records(name_index)? {
+                            // Link name records: 4-byte hash followed by the heap ID
+                            if record.len() <= 4 {
+                                continue;
+                            }
+                            let object = self.heap_object(&heap, &record[4..])?;
+                            if let Some(link) = self.parse_link(&object)? {
+                                links.push(link);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(links)
+    }
+
+    fn parse_link(&self, data: &[u8]) -> Result<Option<(String, u64)>> {
+        let mut c = Cursor::new(data);
+        let version = c.u8()?;
+        if version != 1 {
+            return format_error(format!("Unsupported link message version {}", version));
+        }
+        let flags = c.u8()?;
+        let link_type = if flags & 0x08 != 0 { c.u8()? } else { 0 };
+        if flags & 0x04 != 0 {
+            c.skip(8)?;
+        }
+        if flags & 0x10 != 0 {
+            c.skip(1)?;
+        }
+        let name_len = c.uint(1 << (flags & 0x03))? as usize;
+        let name = String::from_utf8_lossy(c.bytes(name_len)?).into_owned();
+        if link_type != 0 {
+            // Soft and external links cannot be represented in the group tree
+            return Ok(None);
+        }
+        let address = self.offset(&mut c)?;
+        Ok(Some((name, address)))
+    }
+
+    fn local_heap_data(&self, address: u64) -> Result<u64> {
+        let mut c = self.at(address)?;
+        if c.bytes(4)? != b"HEAP" {
+            return format_error("Invalid local heap signature");
+        }
+        c.skip(4)?;
+        self.length(&mut c)?;
+        self.length(&mut c)?;
+        self.offset(&mut c)
+    }
+
+    fn walk_group_btree(
+        &self,
+        address: u64,
+        heap_data: u64,
+        links: &mut Vec<(String, u64)>,
+        visited: &mut HashSet<u64>,
+    ) -> Result<()> {
+        if address == UNDEF_ADDR || !visited.insert(address) {
+            return Ok(());
+        }
+        let mut c = self.at(address)?;
+        if c.bytes(4)? != b"TREE" {
+            return format_error("Invalid group B-tree node signature");
+        }
+        let node_type = c.u8()?;
+        if node_type != 0 {
+            return format_error("Group B-tree has unexpected node type");
+        }
+        let level = c.u8()?;
+        let entries = c.u16()? as usize;
+        c.skip(2 * self.offset_size)?;
+
+        for _ in 0..entries {
+            self.length(&mut c)?; // key
+            let child = self.offset(&mut c)?;
+            if level > 0 {
+                self.walk_group_btree(child, heap_data, links, visited)?;
+            } else {
+                self.read_symbol_node(child, heap_data, links)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_symbol_node(
+        &self,
+        address: u64,
+        heap_data: u64,
+        links: &mut Vec<(String, u64)>,
+    ) -> Result<()> {
+        let mut c = self.at(address)?;
+        if c.bytes(4)? != b"SNOD" {
+            return format_error("Invalid symbol table node signature");
+        }
+        c.skip(2)?;
+        let n_symbols = c.u16()? as usize;
+        for _ in 0..n_symbols {
+            let name_offset = self.length(&mut c)?;
+            let header = self.offset(&mut c)?;
+            c.skip(8 + 16)?; // cache type, reserved, scratch pad
+            let name = self.at(heap_data + name_offset)?.cstr()?;
+            links.push((name, header));
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Fractal heaps and version 2 B-trees (dense link/attribute storage)
+    // ------------------------------------------------------------------
+
+    fn fractal_heap(&self, address: u64) -> Result<FractalHeap> {
+        let mut c = self.at(address)?;
+        if c.bytes(4)? != b"FRHP" {
+            return format_error("Invalid fractal heap signature");
+        }
+        c.skip(1)?;
+        c.skip(2)?; // heap ID length
+        let io_filter_len = c.u16()?;
+        c.skip(1)?; // flags
+        let max_managed_size = c.u32()? as u64;
+        self.length(&mut c)?; // next huge object ID
+        self.offset(&mut c)?; // huge object B-tree
+        self.length(&mut c)?; // free space
+        self.offset(&mut c)?; // free space manager
+        for _ in 0..8 {
+            // managed space, allocated space, iterator offset, managed object
+            // count and huge/tiny object statistics
+            self.length(&mut c)?;
+        }
+        let table_width = c.u16()? as usize;
+        let start_block_size = self.length(&mut c)?;
+        let max_direct_block_size = self.length(&mut c)?;
+        let max_heap_bits = c.u16()? as usize;
+        c.skip(2)?; // starting number of rows
+        let root_address = self.offset(&mut c)?;
+        let root_rows = c.u16()? as usize;
+
+        if table_width == 0 || start_block_size == 0 || max_direct_block_size == 0 {
+            return format_error("Invalid fractal heap doubling table");
+        }
+
+        let length_bytes = (log2_floor(max_direct_block_size) as usize)
+            .div_ceil(8)
+            .min(limit_enc_size(max_managed_size));
+        Ok(FractalHeap {
+            table_width,
+            start_block_size,
+            max_direct_block_size,
+            root_address,
+            root_rows,
+            offset_bytes: max_heap_bits.div_ceil(8),
+            length_bytes,
+            filtered: io_filter_len > 0,
+        })
+    }
+
+    fn heap_object(&self, heap: &FractalHeap, id: &[u8]) -> Result<Vec<u8>> {
+        let mut c = Cursor::new(id);
+        let flags = c.u8()?;
+        match (flags >> 4) & 0x03 {
+            0 => {
+                if heap.filtered {
+                    return format_error("Filtered fractal heaps are not supported");
+                }
+                let offset = c.uint(heap.offset_bytes)?;
+                let length = c.uint(heap.length_bytes)? as usize;
+                let address = self.locate_managed_object(heap, offset)?;
+                Ok(self.read_bytes(address, length)?.to_vec())
+            }
+            2 => {
+                let length = (flags & 0x0f) as usize + 1;
+                Ok(c.bytes(length)?.to_vec())
+            }
+            _ => format_error("Huge fractal heap objects are not supported"),
+        }
+    }
+
+    /// Translate a managed object's heap offset into a file address
+    fn locate_managed_object(&self, heap: &FractalHeap, offset: u64) -> Result<u64> {
+        if heap.root_rows == 0 {
+            // The root block is a single direct block
+            return Ok(heap.root_address + offset);
+        }
+
+        let width = heap.table_width as u64;
+        let max_direct_rows = (log2_floor(heap.max_direct_block_size)
+            - log2_floor(heap.start_block_size)) as usize
+            + 2;
+        let mut block_address = heap.root_address;
+        let mut rows = heap.root_rows;
+        let mut block_offset = 0u64;
+
+        for _ in 0..MAX_INDIRECTION {
+            let mut c = self.at(block_address)?;
+            if c.bytes(4)? != b"FHIB" {
+                return format_error("Invalid fractal heap indirect block signature");
+            }
+            c.skip(1 + self.offset_size + heap.offset_bytes)?;
+            let entries = c.bytes(c.remaining())?;
+            let direct_rows = rows.min(max_direct_rows);
+
+            let relative = offset
+                .checked_sub(block_offset)
+                .ok_or_else(|| IoError::FormatError("Invalid heap offset".to_string()))?;
+            let mut row_start = 0u64;
+            let mut found = None;
+            for row in 0..rows {
+                let block_size = if row == 0 {
+                    heap.start_block_size
+                } else {
+                    heap.start_block_size << (row - 1)
+                };
+                if relative < row_start + width * block_size {
+                    let col = (relative - row_start) / block_size;
+                    found = Some((row, col, block_size, row_start + col * block_size));
+                    break;
+                }
+                row_start += width * block_size;
+            }
+            let (row, col, block_size, start) = found
+                .ok_or_else(|| IoError::FormatError("Heap offset outside of heap".to_string()))?;
+
+            let mut ec = Cursor::new(entries);
+            if row < max_direct_rows {
+                ec.skip((row * heap.table_width + col as usize) * self.offset_size)?;
+                let child = self.offset(&mut ec)?;
+                return Ok(child + (relative - start));
+            }
+
+            let index = (row - max_direct_rows) * heap.table_width + col as usize;
+            ec.skip((direct_rows * heap.table_width + index) * self.offset_size)?;
+            block_address = self.offset(&mut ec)?;
+            rows =
+                (log2_floor(block_size) - log2_floor(heap.start_block_size * width)) as usize + 1;
+            block_offset += start;
+        }
+        format_error("Fractal heap is nested too deeply")
+    }
+
+    /// Return every record stored in a version 2 B-tree
+    fn btree2_records(&self, address: u64) -> Result<Vec<Vec<u8>>> {
+        let mut c = self.at(address)?;
+        if c.bytes(4)? != b"BTHD" {
+            return format_error("Invalid version 2 B-tree header signature");
+        }
+        c.skip(2)?; // version, type
+        let node_size = c.u32()? as usize;
+        let record_size = c.u16()? as usize;
+        let depth = c.u16()? as usize;
+        c.skip(2)?; // split and merge percentages
+        let root = self.offset(&mut c)?;
+        let root_records = c.u16()? as usize;
+
+        if record_size == 0 || node_size <= 10 {
+            return format_error("Invalid version 2 B-tree parameters");
+        }
+
+        // Sizes of the record counts stored with child pointers
+        let leaf_max = ((node_size - 10) / record_size) as u64;
+        let max_nrec_size = limit_enc_size(leaf_max);
+        let mut cum_max = vec![leaf_max];
+        let mut cum_size = vec![0usize];
+        for d in 1..=depth {
+            let pointer = self.offset_size + max_nrec_size + cum_size[d - 1];
+            let max = node_size.saturating_sub(10 + pointer) / (record_size + pointer);
+            let total = (max as u64 + 1)
+                .saturating_mul(cum_max[d - 1])
+                .saturating_add(max as u64);
+            cum_max.push(total);
+            cum_size.push(limit_enc_size(total));
+        }
+
+        let mut records = Vec::new();
+        if root != UNDEF_ADDR {
+            self.walk_btree2(
+                root,
+                root_records,
+                depth,
+                record_size,
+                max_nrec_size,
+                &cum_size,
+                &mut records,
+            )?;
+        }
+        Ok(records)
+    }
+
+    fn walk_btree2(
+        &self,
+        address: u64,
+        n_records: usize,
+        depth: usize,
+        record_size: usize,
+        max_nrec_size: usize,
+        cum_size: &[usize],
+        records: &mut Vec<Vec<u8>>,
+    ) -> Result<()> {
+        let mut c = self.at(address)?;
+        let signature = c.bytes(4)?;
+        c.skip(2)?;
+        if depth == 0 {
+            if signature != b"BTLF" {
+                return format_error("Invalid version 2 B-tree leaf signature");
+            }
+            for _ in 0..n_records {
+                records.push(c.bytes(record_size)?.to_vec());
+            }
+            return Ok(());
+        }
+
+        if signature != b"BTIN" {
+            return format_error("Invalid version 2 B-tree internal node signature");
+        }
+        for _ in 0..n_records {
+            records.push(c.bytes(record_size)?.to_vec());
+        }
+        for _ in 0..=n_records {
+            let child = self.offset(&mut c)?;
+            let child_records = c.uint(max_nrec_size)? as usize;
+            if depth > 1 {
+                c.skip(cum_size[depth - 1])?;
+            }
+            self.walk_btree2(
+                child,
+                child_records,
+                depth - 1,
+                record_size,
+                max_nrec_size,
+                cum_size,
+                records,
+            )?;
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Attributes
+    // ------------------------------------------------------------------
+
+    fn read_attributes(&self, messages: &[Message]) -> Result<HashMap<String, AttributeValue>> {
+        let mut attributes = HashMap::new();
+        for msg in messages {
+            match msg.msg_type {
+                MSG_ATTRIBUTE => {
+                    if let Some((name, value)) = self.parse_attribute(&msg.data)? {
+                        attributes.insert(name, value);
+                    }
+                }
+                MSG_ATTRIBUTE_INFO => {
+                    let mut c = Cursor::new(&msg.data);
+                    c.skip(1)?;
+                    let flags = c.u8()?;
+                    if flags & 0x01 != 0 {
+                        c.skip(2)?;
+                    }
+                    let heap = self.offset(&mut c)?;
+                    let name_index = self.offset(&mut c)?;
+                    if heap != UNDEF_ADDR && name_index != UNDEF_ADDR {
+                        let heap = self.fractal_heap(heap)?;
+                        for record in self.btree2_records(name_index)? {
+                            // Attribute name records: 8-byte heap ID, message flags, ...
+                            if record.len() < 9 || record[8] & 0x02 != 0 {
+                                continue;
+                            }
+                            let object = self.heap_object(&heap, &record[..8])?;
+                            if let Some((name, value)) = self.parse_attribute(&object)? {
+                                attributes.insert(name, value);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(attributes)
+    }
+
+    fn parse_attribute(&self, data: &[u8]) -> Result<Option<(String, AttributeValue)>> {
+        let mut c = Cursor::new(data);
+        let version = c.u8()?;
+        let flags = c.u8()?;
+        let name_size = c.u16()? as usize;
+        let datatype_size = c.u16()? as usize;
+        let dataspace_size = c.u16()? as usize;
+        if version == 3 {
+            c.skip(1)?; // name character set
+        }
+        let padded = |n: usize| if version == 1 { n.div_ceil(8) * 8 } else { n };
+
+        let name_bytes = c.bytes(padded(name_size))?;
+        let name_len = name_bytes[..name_size.min(name_bytes.len())]
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(name_size);
+        let name = String::from_utf8_lossy(&name_bytes[..name_len]).into_owned();
+
+        let datatype_bytes = c.bytes(padded(datatype_size))?;
+        let dataspace_bytes = c.bytes(padded(dataspace_size))?;
+
+        let dtype = if flags & 0x01 != 0 {
+            self.shared_datatype(datatype_bytes, 0)?
+        } else {
+            self.decode_datatype(&mut Cursor::new(datatype_bytes))?
+        };
+        if flags & 0x02 != 0 {
+            // Shared dataspaces only occur with shared object header messages
+            return Ok(None);
+        }
+        let space = decode_dataspace(&mut Cursor::new(dataspace_bytes), self.length_size)?;
+        let count = space.count()?;
+        if space.is_null {
+            return Ok(None);
+        }
+        let raw = c.bytes(count * dtype.size())?;
+        let data = self.convert(&dtype, raw, count)?;
+        let scalar = space.dims.is_empty();
+
+        let value = match data {
+            DataArray::Integer(v) if scalar && v.len() == 1 => AttributeValue::Integer(v[0]),
+            DataArray::Integer(v) => AttributeValue::IntegerArray(v),
+            DataArray::Float(v) if scalar && v.len() == 1 => AttributeValue::Float(v[0]),
+            DataArray::Float(v) => AttributeValue::FloatArray(v),
+            DataArray::String(mut v) if scalar && v.len() == 1 => {
+                AttributeValue::String(v.remove(0))
+            }
+            DataArray::String(v) => AttributeValue::StringArray(v),
+            DataArray::Binary(_) => return Ok(None),
+        };
+        Ok(Some((name, value)))
+    }
+
+    // ------------------------------------------------------------------
+    // Datatypes
+    // ------------------------------------------------------------------
+
+    /// Resolve a shared (committed) datatype message
+    fn shared_datatype(&self, data: &[u8], depth: usize) -> Result<Dtype> {
+        if depth > MAX_INDIRECTION {
+            return format_error("Shared datatype chain is too long");
+        }
+        let mut c = Cursor::new(data);
+        let version = c.u8()?;
+        let kind = c.u8()?;
+        let address =
+            match version {
+                1 => {
+                    c.skip(6)?;
+                    self.offset(&mut c)?
+                }
+                2 => self.offset(&mut c)?,
+                3 if kind == 2 => self.offset(&mut c)?,
+                _ => return format_error(
+                    "Datatypes stored in the shared object header message heap are not supported",
+                ),
+            };
+        let messages = self.object_header(address)?;
+        let msg = messages
+            .iter()
+            .find(|m| m.msg_type == MSG_DATATYPE)
+            .ok_or_else(|| IoError::FormatError("Committed datatype not found".to_string()))?;
+        if msg.flags & 0x02 != 0 {
+            return self.shared_datatype(&msg.data, depth + 1);
+        }
+        self.decode_datatype(&mut Cursor::new(&msg.data))
+    }
+
+    fn decode_datatype(&self, c: &mut Cursor) -> Result<Dtype> {
+        let class_version = c.u8()?;
+        let class = class_version & 0x0f;
+        let version = class_version >> 4;
+        let bits = c.bytes(3)?;
+        let (b0, b1) = (bits[0], bits[1]);
+        let size = c.u32()? as usize;
+
+        let dtype = match class {
+            CLASS_FIXED_POINT | CLASS_BITFIELD => {
+                c.skip(4)?;
+                Dtype::Integer {
+                    size,
+                    signed: class == CLASS_FIXED_POINT && b0 & 0x08 != 0,
+                    big_endian: b0 & 0x01 != 0,
+                }
+            }
+            CLASS_FLOATING_POINT => {
+                c.skip(12)?;
+                if b0 & 0x40 != 0 {
+                    // VAX byte order
+                    Dtype::Other { size }
+                } else {
+                    Dtype::Float {
+                        size,
+                        big_endian: b0 & 0x01 != 0,
+                    }
+                }
+            }
+            CLASS_TIME => {
+                c.skip(2)?;
+                Dtype::Other { size }
+            }
+            CLASS_STRING => Dtype::String {
+                size,
+                encoding: if b0 >> 4 == 1 {
+                    StringEncoding::UTF8
+                } else {
+                    StringEncoding::ASCII
+                },
+                space_padded: b0 & 0x0f == 2,
+            },
+            CLASS_OPAQUE => {
+                c.skip(b0 as usize)?;
+                Dtype::Opaque { size }
+            }
+            CLASS_COMPOUND => {
+                let n_members = (b0 as usize) | ((b1 as usize) << 8);
+                let mut fields = Vec::with_capacity(n_members);
+                for _ in 0..n_members {
+                    let start = c.pos;
+                    let name = c.cstr()?;
+                    if version < 3 {
+                        let consumed = c.pos - start;
+                        c.skip(consumed.div_ceil(8) * 8 - consumed)?;
+                    }
+                    let member = match version {
+                        1 => {
+                            c.skip(4)?; // byte offset
+                            let rank = c.u8()? as usize;
+                            c.skip(3 + 4 + 4)?;
+                            let dims = c.bytes(16)?;
+                            let shape: Vec<usize> = (0..rank.min(4))
+                                .map(|i| le_uint(&dims[4 * i..4 * i + 4]) as usize)
+                                .collect();
+                            let base = self.decode_datatype(c)?;
+                            if shape.is_empty() {
+                                base
+                            } else {
+                                Dtype::Array {
+                                    base: Box::new(base),
+                                    shape,
+                                }
+                            }
+                        }
+                        2 => {
+                            c.skip(4)?;
+                            self.decode_datatype(c)?
+                        }
+                        _ => {
+                            c.skip(limit_enc_size(size.max(1) as u64).min(4))?;
+                            self.decode_datatype(c)?
+                        }
+                    };
+                    fields.push((name, member));
+                }
+                Dtype::Compound { size, fields }
+            }
+            CLASS_REFERENCE => Dtype::Reference { size },
+            CLASS_ENUM => {
+                let n_members = (b0 as usize) | ((b1 as usize) << 8);
+                let base = self.decode_datatype(c)?;
+                let mut names = Vec::with_capacity(n_members);
+                for _ in 0..n_members {
+                    let start = c.pos;
+                    names.push(c.cstr()?);
+                    if version < 3 {
+                        let consumed = c.pos - start;
+                        c.skip(consumed.div_ceil(8) * 8 - consumed)?;
+                    }
+                }
+                let mut values = Vec::with_capacity(n_members);
+                for name in names {
+                    let raw = c.bytes(base.size())?;
+                    let value = match &base {
+                        Dtype::Integer {
+                            size,
+                            signed,
+                            big_endian,
+                        } => decode_int(raw, *size, *signed, *big_endian),
+                        _ => 0,
+                    };
+                    values.push((name, value));
+                }
+                Dtype::Enum {
+                    base: Box::new(base),
+                    values,
+                }
+            }
+            CLASS_VARIABLE_LENGTH => {
+                let base = self.decode_datatype(c)?;
+                if b0 & 0x0f == 1 {
+                    Dtype::VarString {
+                        size,
+                        encoding: if b1 & 0x0f == 1 {
+                            StringEncoding::UTF8
+                        } else {
+                            StringEncoding::ASCII
+                        },
+                    }
+                } else {
+                    drop(base);
+                    Dtype::Other { size }
+                }
+            }
+            CLASS_ARRAY => {
+                let rank = c.u8()? as usize;
+                if version < 3 {
+                    c.skip(3)?;
+                }
+                let mut shape = Vec::with_capacity(rank);
+                for _ in 0..rank {
+                    shape.push(c.u32()? as usize);
+                }
+                if version < 3 {
+                    c.skip(4 * rank)?;
+                }
+                let base = self.decode_datatype(c)?;
+                Dtype::Array {
+                    base: Box::new(base),
+                    shape,
+                }
+            }
+            other => return format_error(format!("Unknown HDF5 datatype class {}", other)),
+        };
+        Ok(dtype)
+    }
+
+    /// Convert raw element bytes into a `DataArray`
+    fn convert(&self, dtype: &Dtype, raw: &[u8], count: usize) -> Result<DataArray> {
+        let size = dtype.size();
+        if raw.len() < count * size {
+            return format_error("Not enough data for the dataspace");
+        }
+        let elements = || raw.chunks_exact(size.max(1)).take(count);
+
+        Ok(match dtype {
+            Dtype::Integer {
+                size,
+                signed,
+                big_endian,
+            } if *size <= 8 => DataArray::Integer(
+                elements()
+                    .map(|e| decode_int(e, *size, *signed, *big_endian))
+                    .collect(),
+            ),
+            Dtype::Enum { base, .. } => return self.convert(base, raw, count),
+            Dtype::Float {
+                size: 2 | 4 | 8,
+                big_endian,
+            } => DataArray::Float(elements().map(|e| decode_float(e, *big_endian)).collect()),
+            Dtype::String { space_padded, .. } => DataArray::String(
+                elements()
+                    .map(|e| {
+                        let end = e.iter().position(|&b| b == 0).unwrap_or(e.len());
+                        let s = String::from_utf8_lossy(&e[..end]);
+                        if *space_padded {
+                            s.trim_end_matches(' ').to_string()
+                        } else {
+                            s.into_owned()
+                        }
+                    })
+                    .collect(),
+            ),
+            Dtype::VarString { .. } => {
+                let mut strings = Vec::with_capacity(count);
+                for e in elements() {
+                    let mut ec = Cursor::new(e);
+                    let len = ec.u32()? as usize;
+                    let collection = self.offset(&mut ec)?;
+                    let index = ec.u32()?;
+                    if len == 0 || collection == UNDEF_ADDR || collection == 0 {
+                        strings.push(String::new());
+                        continue;
+                    }
+                    let object = self.global_heap_object(collection, index as u16)?;
+                    let end = object.len().min(len);
+                    let bytes = &object[..end];
+                    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+                    strings.push(String::from_utf8_lossy(&bytes[..end]).into_owned());
+                }
+                DataArray::String(strings)
+            }
+            _ => DataArray::Binary(raw[..count * size].to_vec()),
+        })
+    }
+
+    fn global_heap_object(&self, collection: u64, index: u16) -> Result<Vec<u8>> {
+        if let Some(object) = self
+            .global_heaps
+            .borrow()
+            .get(&collection)
+            .and_then(|heap| heap.get(&index))
+        {
+            return Ok(object.clone());
+        }
+
+        let mut c = self.at(collection)?;
+        if c.bytes(4)? != b"GCOL" {
+            return format_error("Invalid global heap collection signature");
+        }
+        c.skip(4)?;
+        let size = self.length(&mut c)? as usize;
+        let mut objects = HashMap::new();
+        let header_len = 8 + self.length_size;
+        while c.pos + header_len <= size && c.remaining() >= header_len {
+            let object_index = c.u16()?;
+            c.skip(6)?;
+            let object_size = self.length(&mut c)? as usize;
+            if object_index == 0 {
+                break;
+            }
+            let data = c.bytes(object_size)?;
+            objects.insert(object_index, data.to_vec());
+            c.skip((object_size.div_ceil(8) * 8 - object_size).min(c.remaining()))?;
+        }
+
+        let object = objects.get(&index).cloned().ok_or_else(|| {
+            IoError::FormatError(format!("Global heap object {} not found", index))
+        })?;
+        self.global_heaps.borrow_mut().insert(collection, objects);
+        Ok(object)
+    }
+
+    // ------------------------------------------------------------------
+    // Datasets
+    // ------------------------------------------------------------------
+
+    fn read_dataset(&self, name: &str, messages: &[Message]) -> Result<Dataset> {
+        let find = |t: u16| messages.iter().find(|m| m.msg_type == t);
+
+        let space_msg = find(MSG_DATASPACE)
+            .ok_or_else(|| IoError::FormatError(format!("Dataset '{}' has no dataspace", name)))?;
+        let space = decode_dataspace(&mut Cursor::new(&space_msg.data), self.length_size)?;
+
+        let type_msg = find(MSG_DATATYPE)
+            .ok_or_else(|| IoError::FormatError(format!("Dataset '{}' has no datatype", name)))?;
+        let dtype = if type_msg.flags & 0x02 != 0 {
+            self.shared_datatype(&type_msg.data, 0)?
+        } else {
+            self.decode_datatype(&mut Cursor::new(&type_msg.data))?
+        };
+
+        let layout_msg = find(MSG_LAYOUT)
+            .ok_or_else(|| IoError::FormatError(format!("Dataset '{}' has no layout", name)))?;
+        let layout = self.decode_layout(&layout_msg.data)?;
+
+        let filters = match find(MSG_FILTER_PIPELINE) {
+            Some(msg) => decode_filter_pipeline(&msg.data)?,
+            None => Vec::new(),
+        };
+
+        let fill = messages
+            .iter()
+            .find_map(|m| match m.msg_type {
+                MSG_FILL_VALUE => decode_fill_value(&m.data).transpose(),
+                0x0004 => decode_old_fill_value(&m.data).transpose(),
+                _ => None,
+            })
+            .transpose()?
+            .filter(|bytes| bytes.len() == dtype.size());
+
+        let count = space.count()?;
+        let element_size = dtype.size();
+        let total = count
+            .checked_mul(element_size)
+            .ok_or_else(|| IoError::FormatError("Dataset is too large".to_string()))?;
+
+        let raw = if count == 0 {
+            Vec::new()
+        } else {
+            self.read_raw(
+                &layout,
+                &space,
+                element_size,
+                total,
+                &filters,
+                fill.as_deref(),
+            )
+            .map_err(|e| match e {
+                IoError::FormatError(msg) => {
+                    IoError::FormatError(format!("Dataset '{}': {}", name, msg))
+                }
+                other => other,
+            })?
+        };
+        let data = self.convert(&dtype, &raw, count)?;
+
+        let mut options = DatasetOptions {
+            chunk_size: layout.chunk().map(|c| c.to_vec()),
+            compression: CompressionOptions::default(),
+            fill_value: fill.as_deref().and_then(|bytes| match &dtype {
+                Dtype::Integer {
+                    size,
+                    signed,
+                    big_endian,
+                } if *size <= 8 => Some(decode_int(bytes, *size, *signed, *big_endian) as f64),
+                Dtype::Float {
+                    size: 2 | 4 | 8,
+                    big_endian,
+                } => Some(decode_float(bytes, *big_endian)),
+                _ => None,
+            }),
+            fletcher32: false,
+        };
+        for filter in &filters {
+            match filter.id {
+                FILTER_DEFLATE => {
+                    options.compression.gzip =
+                        Some(filter.client_data.first().copied().unwrap_or(6) as u8)
+                }
+                FILTER_SHUFFLE => options.compression.shuffle = true,
+                FILTER_FLETCHER32 => options.fletcher32 = true,
+                FILTER_SZIP => {
+                    let cd = &filter.client_data;
+                    options.compression.szip = Some((
+                        cd.first().copied().unwrap_or(0),
+                        cd.get(1).copied().unwrap_or(0),
+                    ));
+                }
+                FILTER_LZF => options.compression.lzf = true,
+                _ => {}
+            }
+        }
+
+        Ok(Dataset {
+            name: name.to_string(),
+            dtype: dtype.public(),
+            shape: if space.is_null {
+                vec![0]
+            } else {
+                space.dims.clone()
+            },
+            data,
+            attributes: self.read_attributes(messages)?,
+            options,
+        })
+    }
+
+    fn decode_layout(&self, data: &[u8]) -> Result<Layout> {
+        let mut c = Cursor::new(data);
+        let version = c.u8()?;
+        match version {
+            1 | 2 => {
+                let ndims = c.u8()? as usize;
+                let class = c.u8()?;
+                c.skip(5)?;
+                let address = if class != 0 {
+                    self.offset(&mut c)?
+                } else {
+                    UNDEF_ADDR
+                };
+                let mut dims = Vec::with_capacity(ndims);
+                for _ in 0..ndims {
+                    dims.push(c.u32()? as usize);
+                }
+                match class {
+                    0 => {
+                        let size = c.u32()? as usize;
+                        Ok(Layout::Compact(c.bytes(size)?.to_vec()))
+                    }
+                    1 => Ok(Layout::Contiguous { address }),
+                    2 => {
+                        dims.pop();
+                        Ok(Layout::BTreeV1 {
+                            address,
+                            chunk: dims,
+                        })
+                    }
+                    _ => format_error(format!("Unknown layout class {}", class)),
+                }
+            }
+            3 | 4 => {
+                let class = c.u8()?;
+                match class {
+                    0 => {
+                        let size = c.u16()? as usize;
+                        Ok(Layout::Compact(c.bytes(size)?.to_vec()))
+                    }
+                    1 => Ok(Layout::Contiguous {
+                        address: self.offset(&mut c)?,
+                    }),
+                    2 if version == 3 => {
+                        let ndims = c.u8()? as usize;
+                        let address = self.offset(&mut c)?;
+                        let mut chunk = Vec::with_capacity(ndims);
+                        for _ in 0..ndims {
+                            chunk.push(c.u32()? as usize);
+                        }
+                        chunk.pop();
+                        Ok(Layout::BTreeV1 { address, chunk })
+                    }
+                    2 => {
+                        let flags = c.u8()?;
+                        let ndims = c.u8()? as usize;
+                        let dim_bytes = c.u8()? as usize;
+                        let mut chunk = Vec::with_capacity(ndims);
+                        for _ in 0..ndims {
+                            chunk.push(c.uint(dim_bytes)? as usize);
+                        }
+                        chunk.pop();
+                        let index_type = c.u8()?;
+                        match index_type {
+                            1 => {
+                                let filtered = if flags & 0x02 != 0 {
+                                    let size = self.length(&mut c)?;
+                                    let mask = c.u32()?;
+                                    Some((size, mask))
+                                } else {
+                                    None
+                                };
+                                Ok(Layout::SingleChunk {
+                                    address: self.offset(&mut c)?,
+                                    chunk,
+                                    filtered,
+                                })
+                            }
+                            2 => Ok(Layout::Implicit {
+                                address: self.offset(&mut c)?,
+                                chunk,
+                            }),
+                            3 => {
+                                c.skip(1)?; // page bits
+                                Ok(Layout::FixedArray {
+                                    address: self.offset(&mut c)?,
+                                    chunk,
+                                })
+                            }
+                            4 => format_error("Extensible array chunk indexes are not supported"),
+                            5 => format_error("Version 2 B-tree chunk indexes are not supported"),
+                            t => format_error(format!("Unknown chunk index type {}", t)),
+                        }
+                    }
+                    3 => format_error("Virtual datasets are not supported"),
+                    _ => format_error(format!("Unknown layout class {}", class)),
+                }
+            }
+            v => format_error(format!("Unsupported layout message version {}", v)),
+        }
+    }
+
+    fn read_raw(
+        &self,
+        layout: &Layout,
+        space: &Dataspace,
+        element_size: usize,
+        total: usize,
+        filters: &[FilterInfo],
+        fill: Option<&[u8]>,
+    ) -> Result<Vec<u8>> {
+        let chunked = match layout {
+            Layout::Compact(_) | Layout::Contiguous { .. } => None,
+            Layout::BTreeV1 { chunk, .. }
+            | Layout::SingleChunk { chunk, .. }
+            | Layout::Implicit { chunk, .. }
+            | Layout::FixedArray { chunk, .. } => {
+                if chunk.len() != space.dims.len() || chunk.contains(&0) {
+                    return format_error("Chunk dimensions do not match the dataspace");
+                }
+                let chunk_bytes = chunk
+                    .iter()
+                    .try_fold(element_size, |acc, &c| acc.checked_mul(c))
+                    .ok_or_else(|| IoError::FormatError("Chunk is too large".to_string()))?;
+                let records = self.chunk_records(layout, space, chunk, chunk_bytes)?;
+                Some((chunk, chunk_bytes, records))
+            }
+        };
+
+        // Check the extent against what the file can supply before allocating
+        let stored = match (layout, &chunked) {
+            (_, Some((_, chunk_bytes, records))) => {
+                let expansion = if filters.is_empty() {
+                    1
+                } else {
+                    MAX_FILTER_EXPANSION
+                };
+                for record in records.iter().filter(|r| r.address != UNDEF_ADDR) {
+                    if record.size > self.bytes_after(record.address)
+                        || *chunk_bytes > record.size.saturating_mul(expansion)
+                    {
+                        return format_error(format!(
+                            "Chunk at {:#x} cannot hold {} bytes",
+                            record.address, chunk_bytes
+                        ));
+                    }
+                }
+                records.len().saturating_mul(*chunk_bytes)
+            }
+            (Layout::Compact(data), _) => data.len(),
+            (Layout::Contiguous { address }, _) if *address != UNDEF_ADDR => {
+                if total > self.bytes_after(*address) {
+                    return format_error("Contiguous data extends past the end of the file");
+                }
+                total
+            }
+            _ => 0,
+        };
+        if total > stored.saturating_add(MAX_UNSTORED_BYTES) {
+            return format_error(format!(
+                "{} bytes of data but only {} are stored in the file",
+                total, stored
+            ));
+        }
+
+        let mut out = match fill {
+            Some(bytes) if bytes.iter().any(|&b| b != 0) => {
+                bytes.repeat(total / element_size.max(1))
+            }
+            _ => vec![0u8; total],
+        };
+
+        match layout {
+            Layout::Compact(data) => {
+                let n = data.len().min(total);
+                out[..n].copy_from_slice(&data[..n]);
+            }
+            Layout::Contiguous { address } if *address != UNDEF_ADDR => {
+                out.copy_from_slice(self.read_bytes(*address, total)?);
+            }
+            _ => {}
+        }
+        let Some((chunk, chunk_bytes, records)) = chunked else {
+            return Ok(out);
+        };
+
+        for record in records {
+            if record.address == UNDEF_ADDR {
+                continue;
+            }
+            let stored = self.read_bytes(record.address, record.size)?;
+            let decoded = apply_inverse_filters(
+                stored.to_vec(),
+                filters,
+                record.filter_mask,
+                element_size,
+                chunk_bytes,
+            )?;
+            if decoded.len() < chunk_bytes {
+                return format_error("Decoded chunk is smaller than expected");
+            }
+            place_chunk(
+                &mut out,
+                &space.dims,
+                chunk,
+                &record.origin,
+                &decoded,
+                element_size,
+            );
+        }
+        Ok(out)
+    }
+
+    fn chunk_records(
+        &self,
+        layout: &Layout,
+        space: &Dataspace,
+        chunk: &[usize],
+        chunk_bytes: usize,
+    ) -> Result<Vec<ChunkRecord>> {
+        // Linear chunk indexes enumerate the chunk grid of the maximum dimensions
+        let grid_dims: Vec<usize> = match &space.max_dims {
+            Some(max) => max
+                .iter()
+                .zip(&space.dims)
+                .map(|(&m, &d)| {
+                    if m == u64::MAX {
+                        d
+                    } else {
+                        (m as usize).max(d)
+                    }
+                })
+                .collect(),
+            None => space.dims.clone(),
+        };
+        let grid: Vec<usize> = grid_dims
+            .iter()
+            .zip(chunk)
+            .map(|(&d, &c)| d.div_ceil(c))
+            .collect();
+        let origin_of = |mut index: usize| -> Vec<usize> {
+            let mut origin = vec![0usize; chunk.len()];
+            for d in (0..chunk.len()).rev() {
+                origin[d] = (index % grid[d].max(1)) * chunk[d];
+                index /= grid[d].max(1);
+            }
+            origin
+        };
+
+        let mut records = Vec::new();
+        match layout {
+            Layout::BTreeV1 { address, .. } => {
+                let mut visited = HashSet::new();
+                self.walk_chunk_btree(*address, chunk.len(), &mut records, &mut visited)?;
+            }
+            Layout::SingleChunk {
+                address, filtered, ..
+            } => {
+                let (size, filter_mask) = match filtered {
+                    Some((size, mask)) => (*size as usize, *mask),
+                    None => (chunk_bytes, 0),
+                };
+                records.push(ChunkRecord {
+                    origin: vec![0; chunk.len()],
+                    address: *address,
+                    size,
+                    filter_mask,
+                });
+            }
+            Layout::Implicit { address, .. } if *address != UNDEF_ADDR => {
+                // Implicitly indexed chunks are stored back to back
+                let n_chunks = grid
+                    .iter()
+                    .try_fold(1usize, |acc, &g| acc.checked_mul(g))
+                    .filter(|&n| n.saturating_mul(chunk_bytes) <= self.bytes_after(*address))
+                    .ok_or_else(|| {
+                        IoError::FormatError(
+                            "Implicitly indexed chunks extend past the end of the file".to_string(),
+                        )
+                    })?;
+                for i in 0..n_chunks {
+                    records.push(ChunkRecord {
+                        origin: origin_of(i),
+                        address: address + (i * chunk_bytes) as u64,
+                        size: chunk_bytes,
+                        filter_mask: 0,
+                    });
+                }
+            }
+            Layout::FixedArray { address, .. } if *address != UNDEF_ADDR => {
+                for (i, (chunk_address, size, mask)) in self
+                    .fixed_array_entries(*address, chunk_bytes)?
+                    .into_iter()
+                    .enumerate()
+                {
+                    records.push(ChunkRecord {
+                        origin: origin_of(i),
+                        address: chunk_address,
+                        size,
+                        filter_mask: mask,
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        // Chunks lying completely outside the current extent are ignored
+        records.retain(|r| r.origin.iter().zip(&space.dims).all(|(&o, &d)| o < d));
+        Ok(records)
+    }
+
+    fn walk_chunk_btree(
+        &self,
+        address: u64,
+        rank: usize,
+        records: &mut Vec<ChunkRecord>,
+        visited: &mut HashSet<u64>,
+    ) -> Result<()> {
+        if address == UNDEF_ADDR || !visited.insert(address) {
+            return Ok(());
+        }
+        let mut c = self.at(address)?;
+        if c.bytes(4)? != b"TREE" {
+            return format_error("Invalid chunk B-tree node signature");
+        }
+        if c.u8()? != 1 {
+            return format_error("Chunk B-tree has unexpected node type");
+        }
+        let level = c.u8()?;
+        let entries = c.u16()? as usize;
+        c.skip(2 * self.offset_size)?;
+
+        for _ in 0..entries {
+            let size = c.u32()? as usize;
+            let filter_mask = c.u32()?;
+            let mut origin = Vec::with_capacity(rank);
+            for _ in 0..rank {
+                origin.push(c.uint(8)? as usize);
+            }
+            c.skip(8)?; // element offset, always zero
+            let child = self.offset(&mut c)?;
+            if level > 0 {
+                self.walk_chunk_btree(child, rank, records, visited)?;
+            } else {
+                records.push(ChunkRecord {
+                    origin,
+                    address: child,
+                    size,
+                    filter_mask,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Entries (address, stored size, filter mask) of a fixed array chunk index
+    fn fixed_array_entries(
+        &self,
+        address: u64,
+        chunk_bytes: usize,
+    ) -> Result<Vec<(u64, usize, u32)>> {
+        let mut c = self.at(address)?;
+        if c.bytes(4)? != b"FAHD" {
+            return format_error("Invalid fixed array header signature");
+        }
+        c.skip(1)?;
+        let client = c.u8()?;
+        let entry_size = c.u8()? as usize;
+        let page_bits = c.u8()? as u32;
+        let n_entries = self.length(&mut c)? as usize;
+        let block = self.offset(&mut c)?;
+        if block == UNDEF_ADDR {
+            return Ok(Vec::new());
+        }
+
+        let mut c = self.at(block)?;
+        if c.bytes(4)? != b"FADB" {
+            return format_error("Invalid fixed array data block signature");
+        }
+        c.skip(2)?;
+        self.offset(&mut c)?;
+
+        let mut entries = Vec::with_capacity(n_entries.min(c.remaining() / entry_size.max(1)));
+        let parse_entry = |c: &mut Cursor| -> Result<(u64, usize, u32)> {
+            let start = c.pos;
+            let address = self.offset(c)?;
+            let (size, mask) = if client == 1 {
+                let size_bytes = entry_size.saturating_sub(self.offset_size + 4);
+                (c.uint(size_bytes)? as usize, c.u32()?)
+            } else {
+                (chunk_bytes, 0)
+            };
+            c.pos = start + entry_size;
+            Ok((address, size, mask))
+        };
+
+        let page_size = 1usize << page_bits.min(31);
+        if n_entries > page_size {
+            let n_pages = n_entries.div_ceil(page_size);
+            let bitmap = c.bytes(n_pages.div_ceil(8))?.to_vec();
+            c.skip(4)?; // checksum
+            for page in 0..n_pages {
+                let in_page = page_size.min(n_entries - page * page_size);
+                if bitmap[page / 8] & (0x80 >> (page % 8)) == 0 {
+                    // Uninitialised pages are allocated but hold no chunks
+                    entries.extend(std::iter::repeat_n((UNDEF_ADDR, 0, 0), in_page));
+                    c.skip((in_page * entry_size + 4).min(c.remaining()))?;
+                    continue;
+                }
+                for _ in 0..in_page {
+                    entries.push(parse_entry(&mut c)?);
+                }
+                c.skip(4)?;
+            }
+        } else {
+            for _ in 0..n_entries {
+                entries.push(parse_entry(&mut c)?);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+fn decode_int(bytes: &[u8], size: usize, signed: bool, big_endian: bool) -> i64 {
+    let size = size.min(8);
+    let mut value = 0u64;
+    for i in 0..size {
+        let b = if big_endian {
+            bytes[size - 1 - i]
+        } else {
+            bytes[i]
+        };
+        value |= (b as u64) << (8 * i);
+    }
+    if signed && size < 8 && value & (1 << (8 * size - 1)) != 0 {
+        value |= !0u64 << (8 * size);
+    }
+    value as i64
+}
+
+fn decode_float(bytes: &[u8], big_endian: bool) -> f64 {
+    let mut buf = [0u8; 8];
+    let n = bytes.len().min(8);
+    buf[..n].copy_from_slice(&bytes[..n]);
+    if big_endian {
+        buf[..n].reverse();
+    }
+    match n {
+        2 => half_to_f64(u16::from_le_bytes([buf[0], buf[1]])),
+        4 => f32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+        _ => f64::from_le_bytes(buf),
+    }
+}
+
+fn half_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f64;
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        e => (1.0 + mantissa / 1024.0) * 2f64.powi(e - 15),
+    }
+}
+
+fn decode_dataspace(c: &mut Cursor, length_size: usize) -> Result<Dataspace> {
+    let version = c.u8()?;
+    let rank = c.u8()? as usize;
+    let flags = c.u8()?;
+    let is_null = match version {
+        1 => {
+            c.skip(5)?;
+            false
+        }
+        2 => c.u8()? == 2,
+        v => return format_error(format!("Unsupported dataspace version {}", v)),
+    };
+    let mut dims = Vec::with_capacity(rank);
+    for _ in 0..rank {
+        dims.push(c.uint(length_size)? as usize);
+    }
+    let max_dims = if flags & 0x01 != 0 {
+        let mut max = Vec::with_capacity(rank);
+        for _ in 0..rank {
+            let m = c.uint(length_size)?;
+            let all_ones = if length_size == 8 {
+                u64::MAX
+            } else {
+                (1u64 << (8 * length_size)) - 1
+            };
+            max.push(if m == all_ones { u64::MAX } else { m });
+        }
+        Some(max)
+    } else {
+        None
+    };
+    Ok(Dataspace {
+        dims,
+        max_dims,
+        is_null,
+    })
+}
+
+fn decode_filter_pipeline(data: &[u8]) -> Result<Vec<FilterInfo>> {
+    let mut c = Cursor::new(data);
+    let version = c.u8()?;
+    let n_filters = c.u8()? as usize;
+    if version == 1 {
+        c.skip(6)?;
+    }
+    let mut filters = Vec::with_capacity(n_filters);
+    for _ in 0..n_filters {
+        let id = c.u16()?;
+        let name_len = if version == 1 || id >= 256 {
+            c.u16()? as usize
+        } else {
+            0
+        };
+        c.skip(2)?; // flags
+        let n_values = c.u16()? as usize;
+        if version == 1 {
+            c.skip(name_len.div_ceil(8) * 8)?;
+        } else {
+            c.skip(name_len)?;
+        }
+        let mut client_data = Vec::with_capacity(n_values);
+        for _ in 0..n_values {
+            client_data.push(c.u32()?);
+        }
+        if version == 1 && n_values % 2 == 1 {
+            c.skip(4)?;
+        }
+        filters.push(FilterInfo { id, client_data });
+    }
+    Ok(filters)
+}
+
+/// Fill value bytes from a fill value message, if one is defined
+fn decode_fill_value(data: &[u8]) -> Result<Option<Vec<u8>>> {
+    let mut c = Cursor::new(data);
+    let version = c.u8()?;
+    let defined = match version {
+        1 | 2 => {
+            c.skip(2)?;
+            let defined = c.u8()? != 0;
+            if version == 2 && !defined {
+                return Ok(None);
+            }
+            defined
+        }
+        3 => c.u8()? & 0x20 != 0,
+        v => return format_error(format!("Unsupported fill value message version {}", v)),
+    };
+    if !defined || c.remaining() < 4 {
+        return Ok(None);
+    }
+    let size = c.u32()? as usize;
+    Ok(Some(c.bytes(size)?.to_vec()))
+}
+
+fn decode_old_fill_value(data: &[u8]) -> Result<Option<Vec<u8>>> {
+    let mut c = Cursor::new(data);
+    let size = c.u32()? as usize;
+    if size == 0 {
+        return Ok(None);
+    }
+    Ok(Some(c.bytes(size)?.to_vec()))
+}
+
+/// Undo the filter pipeline for one chunk
+fn apply_inverse_filters(
+    mut data: Vec<u8>,
+    filters: &[FilterInfo],
+    filter_mask: u32,
+    element_size: usize,
+    chunk_bytes: usize,
+) -> Result<Vec<u8>> {
+    for (i, filter) in filters.iter().enumerate().rev() {
+        if i < 32 && filter_mask & (1 << i) != 0 {
+            continue;
+        }
+        data = match filter.id {
+            FILTER_DEFLATE => {
+                let mut out = Vec::with_capacity(chunk_bytes);
+                ZlibDecoder::new(&data[..])
+                    .read_to_end(&mut out)
+                    .map_err(|e| IoError::DecompressionError(e.to_string()))?;
+                out
+            }
+            FILTER_SHUFFLE => {
+                let size = filter
+                    .client_data
+                    .first()
+                    .map(|&s| s as usize)
+                    .unwrap_or(element_size);
+                unshuffle(&data, size)
+            }
+            FILTER_FLETCHER32 => {
+                if data.len() < 4 {
+                    return Err(IoError::ChecksumError(
+                        "Fletcher32 chunk is truncated".to_string(),
+                    ));
+                }
+                let (payload, stored) = data.split_at(data.len() - 4);
+                let stored = u32::from_le_bytes([stored[0], stored[1], stored[2], stored[3]]);
+                if fletcher32(payload) != stored && fletcher32(payload).swap_bytes() != stored {
+                    return Err(IoError::ChecksumError(
+                        "Fletcher32 checksum mismatch in HDF5 chunk".to_string(),
+                    ));
+                }
+                payload.to_vec()
+            }
+            FILTER_LZF => lzf_decompress(&data, chunk_bytes).ok_or_else(|| {
+                IoError::DecompressionError("Corrupt LZF compressed chunk".to_string())
+            })?,
+            FILTER_SZIP => {
+                return Err(IoError::UnsupportedCompressionAlgorithm(
+                    "szip (HDF5 filter 4)".to_string(),
+                ))
+            }
+            id => {
+                return Err(IoError::UnsupportedCompressionAlgorithm(format!(
+                    "HDF5 filter {}",
+                    id
+                )))
+            }
+        };
+    }
+    Ok(data)
+}
+
+/// Copy a decoded chunk into its place in the row-major output buffer
+fn place_chunk(
+    out: &mut [u8],
+    shape: &[usize],
+    chunk: &[usize],
+    origin: &[usize],
+    data: &[u8],
+    element_size: usize,
+) {
+    let rank = shape.len();
+    let chunk_elements: usize = chunk.iter().product();
+    let row_len = chunk[rank - 1].min(shape[rank - 1] - origin[rank - 1]);
+    let n_rows = chunk_elements / chunk[rank - 1];
+    let mut index = vec![0usize; rank - 1];
+    for row in 0..n_rows {
+        let inside = index
+            .iter()
+            .enumerate()
+            .all(|(d, &i)| origin[d] + i < shape[d]);
+        if inside {
+            let mut dst = 0usize;
+            for d in 0..rank {
+                let i = if d + 1 == rank { 0 } else { index[d] };
+                dst = dst * shape[d] + origin[d] + i;
+            }
+            let dst = dst * element_size;
+            let src = row * chunk[rank - 1] * element_size;
+            out[dst..dst + row_len * element_size]
+                .copy_from_slice(&data[src..src + row_len * element_size]);
+        }
+        for d in (0..rank - 1).rev() {
+            index[d] += 1;
+            if index[d] < chunk[d] {
+                break;
+            }
+            index[d] = 0;
+        }
+    }
+}
@@ -0,0 +1,915 @@
+//! HDF5 file writer
+//!
+//! Files are written with the structures the reference library emits under its
+//! default ("earliest") format settings: a version 0 superblock, version 1
+//! object headers and symbol-table groups backed by a local heap and a
+//! version 1 B-tree. Datasets use contiguous or chunked storage, the latter
+//! indexed by a version 1 B-tree and optionally passed through the shuffle,
+//! deflate and Fletcher32 filters. Every HDF5 release since 1.6 (and therefore
+//! h5py) can read the result.
+
+use std::io::Write;
+use std::path::Path;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use super::format::*;
+use super::{
+    AttributeValue, DataArray, Dataset, DatasetOptions, Group, HDF5DataType, StringEncoding,
+};
+use crate::error::{IoError, Result};
+
+/// Group leaf node K (symbol table nodes hold at most 2K entries)
+const GROUP_LEAF_K: usize = 4;
+/// Group internal node K (group B-tree nodes hold at most 2K children)
+const GROUP_INTERNAL_K: usize = 16;
+/// Size of a symbol table entry with 8-byte offsets and lengths
+const SYMBOL_ENTRY_SIZE: usize = 40;
+/// Size of the version 0 superblock including the root symbol table entry
+const SUPERBLOCK_SIZE: usize = 96;
+/// Value terminating the free list of a local heap
+const HEAP_FREE_NULL: u64 = 1;
+
+/// Element type of a dataset or attribute as it will be stored on disk
+#[derive(Debug, Clone, PartialEq)]
+enum ElementType {
+    Integer {
+        size: usize,
+        signed: bool,
+    },
+    Float {
+        size: usize,
+    },
+    String {
+        size: usize,
+        encoding: StringEncoding,
+    },
+    Opaque {
+        size: usize,
+    },
+}
+
+impl ElementType {
+    fn size(&self) -> usize {
+        match self {
+            ElementType::Integer { size, .. }
+            | ElementType::Float { size }
+            | ElementType::String { size, .. }
+            | ElementType::Opaque { size } => *size,
+        }
+    }
+
+    /// Encode the datatype message (version 1)
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(24);
+        match self {
+            ElementType::Integer { size, signed } => {
+                out.push(0x10 | CLASS_FIXED_POINT);
+                out.extend_from_slice(&[if *signed { 0x08 } else { 0x00 }, 0, 0]);
+                put_u32(&mut out, *size as u32);
+                put_u16(&mut out, 0);
+                put_u16(&mut out, (*size * 8) as u16);
+            }
+            ElementType::Float { size } => {
+                let (exp_loc, exp_size, mant_size, bias) = if *size == 4 {
+                    (23u8, 8u8, 23u8, 127u32)
+                } else {
+                    (52, 11, 52, 1023)
+                };
+                out.push(0x10 | CLASS_FLOATING_POINT);
+                // Implied leading mantissa bit, sign bit is the most significant bit
+                out.extend_from_slice(&[0x20, (*size * 8 - 1) as u8, 0]);
+                put_u32(&mut out, *size as u32);
+                put_u16(&mut out, 0);
+                put_u16(&mut out, (*size * 8) as u16);
+                out.extend_from_slice(&[exp_loc, exp_size, 0, mant_size]);
+                put_u32(&mut out, bias);
+            }
+            ElementType::String { size, encoding } => {
+                let charset = match encoding {
+                    StringEncoding::ASCII => 0u8,
+                    StringEncoding::UTF8 => 1u8,
+                };
+                out.push(0x10 | CLASS_STRING);
+                // Null padded, like NumPy fixed-width byte strings
+                out.extend_from_slice(&[0x01 | (charset << 4), 0, 0]);
+                put_u32(&mut out, *size as u32);
+            }
+            ElementType::Opaque { size } => {
+                let tag = pad8(b"scirs2\0");
+                out.push(0x10 | CLASS_OPAQUE);
+                out.extend_from_slice(&[tag.len() as u8, 0, 0]);
+                put_u32(&mut out, *size as u32);
+                out.extend_from_slice(&tag);
+            }
+        }
+        out
+    }
+}
+
+/// A child entry of a version 1 B-tree together with its bounding keys
+struct BTreeChild {
+    address: u64,
+    left_key: Vec<u8>,
+    right_key: Vec<u8>,
+}
+
+/// Location of a written object as recorded in a symbol table entry
+struct SymbolEntry {
+    header: u64,
+    /// B-tree and local heap addresses for groups
+    stab: Option<(u64, u64)>,
+}
+
+/// Filters applied to every chunk of a dataset, in pipeline order
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    Shuffle,
+    Deflate(u32),
+    Fletcher32,
+}
+
+/// Serialize a group hierarchy into an HDF5 file at `path`
+pub(crate) fn write_file(path: &Path, root: &Group) -> Result<()> {
+    let mut writer = Writer {
+        buf: vec![0u8; SUPERBLOCK_SIZE],
+    };
+    let root_entry = writer.write_group(root)?;
+    writer.align();
+    writer.write_superblock(&root_entry);
+
+    let mut file = std::fs::File::create(path)
+        .map_err(|e| IoError::FileError(format!("Failed to create HDF5 file: {}", e)))?;
+    file.write_all(&writer.buf)
+        .map_err(|e| IoError::FileError(format!("Failed to write HDF5 file: {}", e)))?;
+    Ok(())
+}
+
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    /// Pad the file to an 8-byte boundary
+    fn align(&mut self) {
+        let padded = self.buf.len().div_ceil(8) * 8;
+        self.buf.resize(padded, 0);
+    }
+
+    /// Append a block at the next aligned address and return that address
+    fn allocate(&mut self, bytes: &[u8]) -> u64 {
+        self.align();
+        let address = self.buf.len() as u64;
+        self.buf.extend_from_slice(bytes);
+        address
+    }
+
+    fn write_superblock(&mut self, root: &SymbolEntry) {
+        let mut sb = Vec::with_capacity(SUPERBLOCK_SIZE);
+        sb.extend_from_slice(&SIGNATURE);
+        // Superblock, free-space, root group symbol table entry and shared
+        // header message format versions, then sizes of offsets and lengths
+        sb.extend_from_slice(&[0, 0, 0, 0, 0, 8, 8, 0]);
+        put_u16(&mut sb, GROUP_LEAF_K as u16);
+        put_u16(&mut sb, GROUP_INTERNAL_K as u16);
+        put_u32(&mut sb, 0); // file consistency flags
+        put_u64(&mut sb, 0); // base address
+        put_u64(&mut sb, UNDEF_ADDR); // free-space info
+        put_u64(&mut sb, self.buf.len() as u64); // end of file address
+        put_u64(&mut sb, UNDEF_ADDR); // driver information block
+        encode_symbol_entry(&mut sb, 0, root);
+        self.buf[..SUPERBLOCK_SIZE].copy_from_slice(&sb);
+    }
+
+    fn write_group(&mut self, group: &Group) -> Result<SymbolEntry> {
+        let mut members: Vec<(&str, SymbolEntry)> = Vec::new();
+
+        let mut group_names: Vec<&String> = group.groups.keys().collect();
+        group_names.sort();
+        for name in group_names {
+            validate_link_name(name)?;
+            let entry = self.write_group(&group.groups[name])?;
+            members.push((name, entry));
+        }
+
+        let mut dataset_names: Vec<&String> = group.datasets.keys().collect();
+        dataset_names.sort();
+        for name in dataset_names {
+            validate_link_name(name)?;
+            if group.groups.contains_key(name) {
+                return Err(IoError::FormatError(format!(
+                    "'{}' is used for both a group and a dataset in group '{}'",
+                    name, group.name
+                )));
+            }
+            let header = self.write_dataset(&group.datasets[name])?;
+            members.push((name, SymbolEntry { header, stab: None }));
+        }
+
+        // Symbol table nodes are searched with a binary search on the names
+        members.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+
+        // Local heap holding the link names; offset 0 is the empty string
+        let mut heap_data = vec![0u8; 8];
+        let mut name_offsets = Vec::with_capacity(members.len());
+        for (name, _) in &members {
+            name_offsets.push(heap_data.len() as u64);
+            let mut bytes = name.as_bytes().to_vec();
+            bytes.push(0);
+            heap_data.extend_from_slice(&pad8(&bytes));
+        }
+        let heap_data_addr = self.allocate(&heap_data);
+        let mut heap = Vec::with_capacity(32);
+        heap.extend_from_slice(b"HEAP");
+        heap.extend_from_slice(&[0, 0, 0, 0]);
+        put_u64(&mut heap, heap_data.len() as u64);
+        put_u64(&mut heap, HEAP_FREE_NULL);
+        put_u64(&mut heap, heap_data_addr);
+        let heap_addr = self.allocate(&heap);
+
+        // Symbol table nodes
+        let per_node = 2 * GROUP_LEAF_K;
+        let node_size = 8 + per_node * SYMBOL_ENTRY_SIZE;
+        let mut children = Vec::new();
+        let mut previous_key = 0u64;
+        for (start, node_members) in members
+            .chunks(per_node)
+            .enumerate()
+            .map(|(i, c)| (i * per_node, c))
+        {
+            let mut node = Vec::with_capacity(node_size);
+            node.extend_from_slice(b"SNOD");
+            node.extend_from_slice(&[1, 0]);
+            put_u16(&mut node, node_members.len() as u16);
+            for (k, (_, entry)) in node_members.iter().enumerate() {
+                encode_symbol_entry(&mut node, name_offsets[start + k], entry);
+            }
+            node.resize(node_size, 0);
+            let address = self.allocate(&node);
+            let last_key = name_offsets[start + node_members.len() - 1];
+            children.push(BTreeChild {
+                address,
+                left_key: previous_key.to_le_bytes().to_vec(),
+                right_key: last_key.to_le_bytes().to_vec(),
+            });
+            previous_key = last_key;
+        }
+
+        let btree_addr = if children.is_empty() {
+            self.write_btree_node(
+                0,
+                0,
+                &[],
+                &[0u64.to_le_bytes().to_vec()],
+                2 * GROUP_INTERNAL_K,
+                8,
+            )
+        } else {
+            self.write_btree(0, children, 2 * GROUP_INTERNAL_K, 8)
+        };
+
+        let mut stab = Vec::with_capacity(16);
+        put_u64(&mut stab, btree_addr);
+        put_u64(&mut stab, heap_addr);
+        let mut messages = vec![(MSG_SYMBOL_TABLE, 0u8, stab)];
+        messages.extend(encode_attributes(&group.attributes)?);
+
+        let header = self.write_object_header(&messages)?;
+        Ok(SymbolEntry {
+            header,
+            stab: Some((btree_addr, heap_addr)),
+        })
+    }
+
+    fn write_dataset(&mut self, dataset: &Dataset) -> Result<u64> {
+        let element = element_type(dataset);
+        let element_size = element.size();
+        let count: usize = dataset.shape.iter().product();
+        let raw = encode_data(&dataset.data, &element, count)
+            .map_err(|e| IoError::FormatError(format!("Dataset '{}': {}", dataset.name, e)))?;
+
+        let options = &dataset.options;
+        let filters = build_filters(options, element_size)?;
+
+        let layout = if options.chunk_size.is_some() || !filters.is_empty() {
+            if dataset.shape.is_empty() {
+                return Err(IoError::FormatError(format!(
+                    "Dataset '{}': chunked storage requires at least one dimension",
+                    dataset.name
+                )));
+            }
+            let chunk: Vec<usize> = match &options.chunk_size {
+                Some(chunk) => chunk.clone(),
+                None => dataset.shape.iter().map(|&d| d.max(1)).collect(),
+            };
+            if chunk.len() != dataset.shape.len() || chunk.contains(&0) {
+                return Err(IoError::FormatError(format!(
+                    "Dataset '{}': chunk size {:?} does not match shape {:?}",
+                    dataset.name, chunk, dataset.shape
+                )));
+            }
+            let btree_addr =
+                self.write_chunks(&raw, &dataset.shape, &chunk, element_size, &filters)?;
+
+            let mut msg = vec![3u8, 2, (chunk.len() + 1) as u8];
+            put_u64(&mut msg, btree_addr);
+            for &c in &chunk {
+                put_u32(&mut msg, c as u32);
+            }
+            put_u32(&mut msg, element_size as u32);
+            msg
+        } else {
+            let address = if raw.is_empty() {
+                UNDEF_ADDR
+            } else {
+                self.allocate(&raw)
+            };
+            let mut msg = vec![3u8, 1];
+            put_u64(&mut msg, address);
+            put_u64(&mut msg, raw.len() as u64);
+            msg
+        };
+
+        // Fill value message (version 2)
+        let fill = options
+            .fill_value
+            .and_then(|value| encode_fill_value(value, &element));
+        let alloc_time = if layout[1] == 2 { 3u8 } else { 1u8 };
+        let mut fill_msg = vec![2u8, alloc_time, 2, u8::from(fill.is_some())];
+        if let Some(bytes) = fill {
+            put_u32(&mut fill_msg, bytes.len() as u32);
+            fill_msg.extend_from_slice(&bytes);
+        }
+
+        let mut messages = vec![
+            (MSG_DATASPACE, 0u8, encode_dataspace(&dataset.shape)),
+            (MSG_DATATYPE, 1u8, element.encode()),
+            (MSG_FILL_VALUE, 1u8, fill_msg),
+            (MSG_LAYOUT, 0u8, layout),
+        ];
+        if !filters.is_empty() {
+            messages.push((
+                MSG_FILTER_PIPELINE,
+                0u8,
+                encode_filter_pipeline(&filters, element_size),
+            ));
+        }
+        messages.extend(encode_attributes(&dataset.attributes)?);
+
+        self.write_object_header(&messages)
+    }
+
+    /// Write all chunks of a dataset and the B-tree indexing them
+    fn write_chunks(
+        &mut self,
+        raw: &[u8],
+        shape: &[usize],
+        chunk: &[usize],
+        element_size: usize,
+        filters: &[Filter],
+    ) -> Result<u64> {
+        let rank = shape.len();
+        if shape.contains(&0) {
+            return Ok(UNDEF_ADDR);
+        }
+
+        let grid: Vec<usize> = shape
+            .iter()
+            .zip(chunk)
+            .map(|(&d, &c)| d.div_ceil(c))
+            .collect();
+        let n_chunks: usize = grid.iter().product();
+
+        let mut entries = Vec::with_capacity(n_chunks);
+        let mut grid_index = vec![0usize; rank];
+        for _ in 0..n_chunks {
+            let origin: Vec<usize> = grid_index.iter().zip(chunk).map(|(&g, &c)| g * c).collect();
+            let data = extract_chunk(raw, shape, chunk, &origin, element_size);
+            let (stored, mask) = apply_filters(data, filters, element_size)?;
+            let address = self.allocate(&stored);
+            entries.push((origin, stored.len(), mask, address));
+
+            // Advance in row-major order
+            for d in (0..rank).rev() {
+                grid_index[d] += 1;
+                if grid_index[d] < grid[d] {
+                    break;
+                }
+                grid_index[d] = 0;
+            }
+        }
+
+        let key_size = 8 + 8 * (rank + 1);
+        let end_origin: Vec<usize> = entries
+            .last()
+            .map(|(origin, ..)| origin.iter().zip(chunk).map(|(&o, &c)| o + c).collect())
+            .unwrap_or_default();
+        let mut children = Vec::with_capacity(entries.len());
+        for (i, (origin, size, mask, address)) in entries.iter().enumerate() {
+            let right_key = match entries.get(i + 1) {
+                Some((next, next_size, next_mask, _)) => chunk_key(*next_size, *next_mask, next),
+                None => chunk_key(0, 0, &end_origin),
+            };
+            children.push(BTreeChild {
+                address: *address,
+                left_key: chunk_key(*size, *mask, origin),
+                right_key,
+            });
+        }
+        Ok(self.write_btree(1, children, 2 * CHUNK_BTREE_K, key_size))
+    }
+
+    /// Write a (possibly multi-level) version 1 B-tree over `children`
+    fn write_btree(
+        &mut self,
+        node_type: u8,
+        mut children: Vec<BTreeChild>,
+        max_entries: usize,
+        key_size: usize,
+    ) -> u64 {
+        let mut level = 0u8;
+        loop {
+            let node_size = btree_node_size(max_entries, key_size);
+            let n_nodes = children.len().div_ceil(max_entries);
+            self.align();
+            let base = self.buf.len() as u64;
+
+            let mut parents = Vec::with_capacity(n_nodes);
+            for (i, group) in children.chunks(max_entries).enumerate() {
+                let left = if i == 0 {
+                    UNDEF_ADDR
+                } else {
+                    base + ((i - 1) * node_size) as u64
+                };
+                let right = if i + 1 == n_nodes {
+                    UNDEF_ADDR
+                } else {
+                    base + ((i + 1) * node_size) as u64
+                };
+
+                let addresses: Vec<u64> = group.iter().map(|c| c.address).collect();
+                let mut keys: Vec<Vec<u8>> = group.iter().map(|c| c.left_key.clone()).collect();
+                keys.push(group[group.len() - 1].right_key.clone());
+
+                let address = self.write_btree_node_with_siblings(
+                    node_type,
+                    level,
+                    &addresses,
+                    &keys,
+                    max_entries,
+                    key_size,
+                    (left, right),
+                );
+                debug_assert_eq!(address, base + (i * node_size) as u64);
+                parents.push(BTreeChild {
+                    address,
+                    left_key: group[0].left_key.clone(),
+                    right_key: group[group.len() - 1].right_key.clone(),
+                });
+            }
+
+            if parents.len() == 1 {
+                return parents[0].address;
+            }
+            children = parents;
+            level += 1;
+        }
+    }
+
+    fn write_btree_node(
+        &mut self,
+        node_type: u8,
+        level: u8,
+        children: &[u64],
+        keys: &[Vec<u8>],
+        max_entries: usize,
+        key_size: usize,
+    ) -> u64 {
+        self.write_btree_node_with_siblings(
+            node_type,
+            level,
+            children,
+            keys,
+            max_entries,
+            key_size,
+            (UNDEF_ADDR, UNDEF_ADDR),
+        )
+    }
+
+    fn write_btree_node_with_siblings(
+        &mut self,
+        node_type: u8,
+        level: u8,
+        children: &[u64],
+        keys: &[Vec<u8>],
+        max_entries: usize,
+        key_size: usize,
+        siblings: (u64, u64),
+    ) -> u64 {
+        // Nodes are always read back at their full size, so pad them out
+        let node_size = btree_node_size(max_entries, key_size);
+        let mut node = Vec::with_capacity(node_size);
+        node.extend_from_slice(b"TREE");
+        node.push(node_type);
+        node.push(level);
+        put_u16(&mut node, children.len() as u16);
+        put_u64(&mut node, siblings.0);
+        put_u64(&mut node, siblings.1);
+        for (key, child) in keys.iter().zip(children) {
+            node.extend_from_slice(key);
+            put_u64(&mut node, *child);
+        }
+        node.extend_from_slice(&keys[children.len()]);
+        node.resize(node_size, 0);
+        self.allocate(&node)
+    }
+
+    /// Write a version 1 object header holding `messages`
+    fn write_object_header(&mut self, messages: &[(u16, u8, Vec<u8>)]) -> Result<u64> {
+        let mut body = Vec::new();
+        for (msg_type, flags, data) in messages {
+            let padded = pad8(data);
+            if padded.len() > u16::MAX as usize {
+                return Err(IoError::FormatError(format!(
+                    "Object header message of {} bytes exceeds the 64 KiB limit",
+                    padded.len()
+                )));
+            }
+            put_u16(&mut body, *msg_type);
+            put_u16(&mut body, padded.len() as u16);
+            body.extend_from_slice(&[*flags, 0, 0, 0]);
+            body.extend_from_slice(&padded);
+        }
+
+        let mut header = Vec::with_capacity(16 + body.len());
+        header.extend_from_slice(&[1, 0]);
+        put_u16(&mut header, messages.len() as u16);
+        put_u32(&mut header, 1); // object reference count
+        put_u32(&mut header, body.len() as u32);
+        put_u32(&mut header, 0); // alignment padding
+        header.extend_from_slice(&body);
+        Ok(self.allocate(&header))
+    }
+}
+
+/// Size on disk of a version 1 B-tree node
+fn btree_node_size(max_entries: usize, key_size: usize) -> usize {
+    8 + 16 + max_entries * 8 + (max_entries + 1) * key_size
+}
+
+/// Encode a key of the chunk index B-tree
+fn chunk_key(size: usize, filter_mask: u32, origin: &[usize]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(8 + 8 * (origin.len() + 1));
+    put_u32(&mut key, size as u32);
+    put_u32(&mut key, filter_mask);
+    for &o in origin {
+        put_u64(&mut key, o as u64);
+    }
+    put_u64(&mut key, 0);
+    key
+}
+
+fn encode_symbol_entry(out: &mut Vec<u8>, name_offset: u64, entry: &SymbolEntry) {
+    put_u64(out, name_offset);
+    put_u64(out, entry.header);
+    match entry.stab {
+        Some((btree, heap)) => {
+            put_u32(out, 1);
+            put_u32(out, 0);
+            put_u64(out, btree);
+            put_u64(out, heap);
+        }
+        None => {
+            put_u32(out, 0);
+            put_u32(out, 0);
+            out.extend_from_slice(&[0u8; 16]);
+        }
+    }
+}
+
+fn validate_link_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.contains('/') || name == "." {
+        return Err(IoError::FormatError(format!(
+            "Invalid HDF5 object name '{}'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Pick the on-disk element type of a dataset from its data and declared dtype
+fn element_type(dataset: &Dataset) -> ElementType {
+    match (&dataset.data, &dataset.dtype) {
+        (DataArray::Float(_), HDF5DataType::Float { size: 4 }) => ElementType::Float { size: 4 },
+        (DataArray::Float(_), _) => ElementType::Float { size: 8 },
+        (DataArray::Integer(_), HDF5DataType::Integer { size, signed })
+            if matches!(size, 1 | 2 | 4 | 8) =>
+        {
+            ElementType::Integer {
+                size: *size,
+                signed: *signed,
+            }
+        }
+        (DataArray::Integer(_), _) => ElementType::Integer {
+            size: 8,
+            signed: true,
+        },
+        (DataArray::String(values), dtype) => ElementType::String {
+            size: values.iter().map(|s| s.len()).max().unwrap_or(0).max(1),
+            encoding: match dtype {
+                HDF5DataType::String { encoding } => encoding.clone(),
+                _ => StringEncoding::UTF8,
+            },
+        },
+        (DataArray::Binary(_), HDF5DataType::Integer { size: 1, signed }) => ElementType::Integer {
+            size: 1,
+            signed: *signed,
+        },
+        (DataArray::Binary(_), HDF5DataType::Opaque { size }) if *size > 0 => {
+            ElementType::Opaque { size: *size }
+        }
+        (DataArray::Binary(_), _) => ElementType::Opaque { size: 1 },
+    }
+}
+
+/// Encode the elements of `data` in little-endian order
+fn encode_data(data: &DataArray, element: &ElementType, count: usize) -> Result<Vec<u8>> {
+    let size = element.size();
+    let n_values = match data {
+        DataArray::Integer(v) => v.len(),
+        DataArray::Float(v) => v.len(),
+        DataArray::String(v) => v.len(),
+        DataArray::Binary(v) => v.len() / size,
+    };
+    if n_values != count || matches!(data, DataArray::Binary(v) if v.len() % size != 0) {
+        return Err(IoError::FormatError(format!(
+            "data holds {} elements but the shape requires {}",
+            n_values, count
+        )));
+    }
+
+    let mut out = Vec::with_capacity(count * size);
+    match data {
+        DataArray::Integer(values) => {
+            for v in values {
+                out.extend_from_slice(&v.to_le_bytes()[..size]);
+            }
+        }
+        DataArray::Float(values) => {
+            for &v in values {
+                if size == 4 {
+                    out.extend_from_slice(&(v as f32).to_le_bytes());
+                } else {
+                    out.extend_from_slice(&v.to_le_bytes());
+                }
+            }
+        }
+        DataArray::String(values) => {
+            for v in values {
+                let start = out.len();
+                out.extend_from_slice(v.as_bytes());
+                out.resize(start + size, 0);
+            }
+        }
+        DataArray::Binary(values) => out.extend_from_slice(values),
+    }
+    Ok(out)
+}
+
+fn encode_fill_value(value: f64, element: &ElementType) -> Option<Vec<u8>> {
+    match element {
+        ElementType::Float { size: 4 } => Some((value as f32).to_le_bytes().to_vec()),
+        ElementType::Float { .. } => Some(value.to_le_bytes().to_vec()),
+        ElementType::Integer { size, .. } => Some((value as i64).to_le_bytes()[..*size].to_vec()),
+        _ => None,
+    }
+}
+
+/// Encode a version 1 dataspace message (rank 0 is a scalar)
+fn encode_dataspace(shape: &[usize]) -> Vec<u8> {
+    let mut out = vec![1u8, shape.len() as u8, 0, 0, 0, 0, 0, 0];
+    for &d in shape {
+        put_u64(&mut out, d as u64);
+    }
+    out
+}
+
+fn build_filters(options: &DatasetOptions, element_size: usize) -> Result<Vec<Filter>> {
+    let compression = &options.compression;
+    if compression.szip.is_some() {
+        return Err(IoError::UnsupportedCompressionAlgorithm(
+            "szip (HDF5 filter 4)".to_string(),
+        ));
+    }
+    if compression.lzf {
+        return Err(IoError::UnsupportedCompressionAlgorithm(
+            "lzf (HDF5 filter 32000) is only supported for reading".to_string(),
+        ));
+    }
+
+    let mut filters = Vec::new();
+    if compression.shuffle && element_size > 1 {
+        filters.push(Filter::Shuffle);
+    }
+    if let Some(level) = compression.gzip {
+        if level > 9 {
+            return Err(IoError::CompressionError(format!(
+                "gzip level must be between 0 and 9, got {}",
+                level
+            )));
+        }
+        filters.push(Filter::Deflate(level as u32));
+    }
+    if options.fletcher32 {
+        filters.push(Filter::Fletcher32);
+    }
+    Ok(filters)
+}
+
+/// Encode a version 1 filter pipeline message
+fn encode_filter_pipeline(filters: &[Filter], element_size: usize) -> Vec<u8> {
+    let mut out = vec![1u8, filters.len() as u8, 0, 0, 0, 0, 0, 0];
+    for filter in filters {
+        let (id, flags, client_data) = match filter {
+            Filter::Shuffle => (FILTER_SHUFFLE, 1u16, vec![element_size as u32]),
+            Filter::Deflate(level) => (FILTER_DEFLATE, 1u16, vec![*level]),
+            Filter::Fletcher32 => (FILTER_FLETCHER32, 0u16, vec![]),
+        };
+        put_u16(&mut out, id);
+        put_u16(&mut out, 0); // no name
+        put_u16(&mut out, flags);
+        put_u16(&mut out, client_data.len() as u16);
+        for value in &client_data {
+            put_u32(&mut out, *value);
+        }
+        if client_data.len() % 2 == 1 {
+            put_u32(&mut out, 0);
+        }
+    }
+    out
+}
+
+/// Run a chunk through the filter pipeline, returning the stored bytes and filter mask
+fn apply_filters(
+    mut data: Vec<u8>,
+    filters: &[Filter],
+    element_size: usize,
+) -> Result<(Vec<u8>, u32)> {
+    for filter in filters {
+        data = match filter {
+            Filter::Shuffle => shuffle(&data, element_size),
+            Filter::Deflate(level) => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::new(*level));
+                encoder
+                    .write_all(&data)
+                    .and_then(|_| encoder.finish())
+                    .map_err(|e| IoError::CompressionError(e.to_string()))?
+            }
+            Filter::Fletcher32 => {
+                let checksum = fletcher32(&data);
+                data.extend_from_slice(&checksum.to_le_bytes());
+                data
+            }
+        };
+    }
+    Ok((data, 0))
+}
+
+/// Copy the elements of one chunk out of a row-major buffer, padding past the edges
+fn extract_chunk(
+    raw: &[u8],
+    shape: &[usize],
+    chunk: &[usize],
+    origin: &[usize],
+    element_size: usize,
+) -> Vec<u8> {
+    let rank = shape.len();
+    let chunk_elements: usize = chunk.iter().product();
+    let mut out = vec![0u8; chunk_elements * element_size];
+
+    // Copy contiguous runs along the fastest-varying dimension
+    let row_len = chunk[rank - 1].min(shape[rank - 1] - origin[rank - 1]);
+    let n_rows = chunk_elements / chunk[rank - 1];
+    let mut index = vec![0usize; rank.saturating_sub(1)];
+    for row in 0..n_rows {
+        let inside = index
+            .iter()
+            .enumerate()
+            .all(|(d, &i)| origin[d] + i < shape[d]);
+        if inside {
+            let mut src = 0usize;
+            for d in 0..rank {
+                let i = if d + 1 == rank { 0 } else { index[d] };
+                src = src * shape[d] + origin[d] + i;
+            }
+            let src = src * element_size;
+            let dst = row * chunk[rank - 1] * element_size;
+            out[dst..dst + row_len * element_size]
+                .copy_from_slice(&raw[src..src + row_len * element_size]);
+        }
+        for d in (0..rank.saturating_sub(1)).rev() {
+            index[d] += 1;
+            if index[d] < chunk[d] {
+                break;
+            }
+            index[d] = 0;
+        }
+    }
+    out
+}
+
+/// Encode the attribute messages (version 1) of an object, sorted by name
+fn encode_attributes(
+    attributes: &std::collections::HashMap<String, AttributeValue>,
+) -> Result<Vec<(u16, u8, Vec<u8>)>> {
+    let mut names: Vec<&String> = attributes.keys().collect();
+    names.sort();
+
+    let mut messages = Vec::with_capacity(names.len());
+    for name in names {
+        let value = &attributes[name];
+        let (element, shape, data): (ElementType, Vec<usize>, Vec<u8>) = match value {
+            AttributeValue::Integer(v) => (
+                ElementType::Integer {
+                    size: 8,
+                    signed: true,
+                },
+                vec![],
+                v.to_le_bytes().to_vec(),
+            ),
+            AttributeValue::Float(v) => (
+                ElementType::Float { size: 8 },
+                vec![],
+                v.to_le_bytes().to_vec(),
+            ),
+            AttributeValue::String(v) => (
+                ElementType::String {
+                    size: v.len().max(1),
+                    encoding: StringEncoding::UTF8,
+                },
+                vec![],
+                {
+                    let mut bytes = v.as_bytes().to_vec();
+                    bytes.resize(v.len().max(1), 0);
+                    bytes
+                },
+            ),
+            AttributeValue::IntegerArray(v) => (
+                ElementType::Integer {
+                    size: 8,
+                    signed: true,
+                },
+                vec![v.len()],
+                v.iter().flat_map(|x| x.to_le_bytes()).collect(),
+            ),
+            AttributeValue::FloatArray(v) => (
+                ElementType::Float { size: 8 },
+                vec![v.len()],
+                v.iter().flat_map(|x| x.to_le_bytes()).collect(),
+            ),
+            AttributeValue::StringArray(v) => {
+                let element = ElementType::String {
+                    size: v.iter().map(|s| s.len()).max().unwrap_or(0).max(1),
+                    encoding: StringEncoding::UTF8,
+                };
+                let data = encode_data(&DataArray::String(v.clone()), &element, v.len())?;
+                (element, vec![v.len()], data)
+            }
+        };
+
+        let datatype = element.encode();
+        let dataspace = encode_dataspace(&shape);
+        let mut name_bytes = name.as_bytes().to_vec();
+        name_bytes.push(0);
+
+        let mut msg = vec![1u8, 0];
+        put_u16(&mut msg, name_bytes.len() as u16);
+        put_u16(&mut msg, datatype.len() as u16);
+        put_u16(&mut msg, dataspace.len() as u16);
+        msg.extend_from_slice(&pad8(&name_bytes));
+        msg.extend_from_slice(&pad8(&datatype));
+        msg.extend_from_slice(&pad8(&dataspace));
+        msg.extend_from_slice(&data);
+        messages.push((MSG_ATTRIBUTE, 0u8, msg));
+    }
+    Ok(messages)
+}
+
+fn pad8(bytes: &[u8]) -> Vec<u8> {
+    let mut out = bytes.to_vec();
+    out.resize(bytes.len().div_ceil(8) * 8, 0);
+    out
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
@@ -9,6 +9,7 @@
 //! - Conversion between different image formats
 //! - Basic image properties and information
 //! - Image sequence handling and animations (GIF, sequence of images)
+//! - Scientific multi-page TIFF, BigTIFF and OME-TIFF with native sample types (see [`tiff`])
 
 use chrono::{DateTime, Utc};
 use image::AnimationDecoder;
@@ -19,6 +20,8 @@ use std::path::Path;
 
 use crate::error::{IoError, Result};
 
+pub mod tiff;
+
 /// Image color mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColorMode {
@@ -0,0 +1,297 @@
+//! TIFF compression schemes and predictors
+
+use std::io::{Read, Write};
+
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use weezl::BitOrder;
+
+use super::ifd::ByteOrder;
+use crate::error::{IoError, Result};
+
+/// Compression of TIFF image data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TiffCompression {
+    /// No compression
+    #[default]
+    None,
+    /// Lempel-Ziv-Welch compression
+    Lzw,
+    /// Deflate (zlib) compression
+    Deflate,
+    /// PackBits run-length encoding
+    PackBits,
+}
+
+impl TiffCompression {
+    /// Compression from the value of the `Compression` tag
+    pub(crate) fn from_code(code: u64) -> Result<Self> {
+        match code {
+            1 => Ok(TiffCompression::None),
+            5 => Ok(TiffCompression::Lzw),
+            8 | 32946 => Ok(TiffCompression::Deflate),
+            32773 => Ok(TiffCompression::PackBits),
+            _ => Err(IoError::UnsupportedCompressionAlgorithm(format!(
+                "TIFF compression scheme {}",
+                code
+            ))),
+        }
+    }
+
+    /// Upper bound on the ratio of decoded to encoded size
+    pub(crate) fn max_expansion(self) -> u64 {
+        match self {
+            TiffCompression::None => 1,
+            // A two-byte run decodes to at most 128 bytes
+            TiffCompression::PackBits => 64,
+            TiffCompression::Deflate => 1032,
+            // A code of at least 9 bits decodes to at most 4096 bytes
+            TiffCompression::Lzw => 4096,
+        }
+    }
+
+    /// Value of the `Compression` tag
+    pub(crate) fn code(self) -> u16 {
+        match self {
+            TiffCompression::None => 1,
+            TiffCompression::Lzw => 5,
+            TiffCompression::Deflate => 8,
+            TiffCompression::PackBits => 32773,
+        }
+    }
+}
+
+/// Decompress one strip or tile
+pub(crate) fn decompress(
+    compression: TiffCompression,
+    data: &[u8],
+    expected: usize,
+) -> Result<Vec<u8>> {
+    let mut out = match compression {
+        TiffCompression::None => data.to_vec(),
+        TiffCompression::Lzw => {
+            let mut out = Vec::with_capacity(expected);
+            // Some writers omit the end-of-information code, so do not require it
+            let result = weezl::decode::Decoder::with_tiff_size_switch(BitOrder::Msb, 8)
+                .into_vec(&mut out)
+                .decode(data);
+            result
+                .status
+                .map_err(|e| IoError::DecompressionError(format!("LZW: {}", e)))?;
+            out
+        }
+        TiffCompression::Deflate => {
+            let mut out = Vec::with_capacity(expected);
+            ZlibDecoder::new(data)
+                .read_to_end(&mut out)
+                .map_err(|e| IoError::DecompressionError(format!("Deflate: {}", e)))?;
+            out
+        }
+        TiffCompression::PackBits => unpack_bits(data, expected),
+    };
+    if out.len() < expected {
+        return Err(IoError::DecompressionError(format!(
+            "TIFF chunk decoded to {} bytes, expected {}",
+            out.len(),
+            expected
+        )));
+    }
+    out.truncate(expected);
+    Ok(out)
+}
+
+/// Compress one strip or tile
+pub(crate) fn compress(compression: TiffCompression, data: &[u8]) -> Result<Vec<u8>> {
+    match compression {
+        TiffCompression::None => Ok(data.to_vec()),
+        TiffCompression::Lzw => weezl::encode::Encoder::with_tiff_size_switch(BitOrder::Msb, 8)
+            .encode(data)
+            .map_err(|e| IoError::CompressionError(format!("LZW: {}", e))),
+        TiffCompression::Deflate => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder
+                .write_all(data)
+                .and_then(|_| encoder.finish())
+                .map_err(|e| IoError::CompressionError(format!("Deflate: {}", e)))
+        }
+        TiffCompression::PackBits => Ok(pack_bits(data)),
+    }
+}
+
+fn unpack_bits(data: &[u8], expected: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(expected);
+    let mut i = 0;
+    while i < data.len() && out.len() < expected {
+        let n = data[i] as i8;
+        i += 1;
+        if n >= 0 {
+            let end = (i + n as usize + 1).min(data.len());
+            out.extend_from_slice(&data[i..end]);
+            i = end;
+        } else if n != -128 {
+            if let Some(&value) = data.get(i) {
+                out.extend(std::iter::repeat_n(value, (1 - n as isize) as usize));
+            }
+            i += 1;
+        }
+    }
+    out
+}
+
+fn pack_bits(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + data.len() / 128 + 1);
+    let mut i = 0;
+    while i < data.len() {
+        let run = data[i..]
+            .iter()
+            .take(128)
+            .take_while(|&&b| b == data[i])
+            .count();
+        if run >= 2 {
+            out.push((1 - run as isize) as u8);
+            out.push(data[i]);
+            i += run;
+        } else {
+            // Literal bytes up to the next run of at least three
+            let start = i;
+            while i < data.len()
+                && i - start < 128
+                && !(i + 2 < data.len() && data[i] == data[i + 1] && data[i] == data[i + 2])
+            {
+                i += 1;
+            }
+            out.push((i - start - 1) as u8);
+            out.extend_from_slice(&data[start..i]);
+        }
+    }
+    out
+}
+
+/// Undo horizontal differencing (predictor 2) in place
+///
+/// `row_samples` is the number of samples per row and `stride` the number of
+/// interleaved samples per pixel.
+pub(crate) fn undo_horizontal_predictor(
+    data: &mut [u8],
+    order: ByteOrder,
+    width: usize,
+    row_samples: usize,
+    stride: usize,
+) {
+    let mask = if width == 8 {
+        u64::MAX
+    } else {
+        (1u64 << (width * 8)) - 1
+    };
+    for row in data.chunks_exact_mut(row_samples * width) {
+        for i in stride..row_samples {
+            let previous = order.uint(&row[(i - stride) * width..], width);
+            let current = order.uint(&row[i * width..], width);
+            order.put_uint(
+                &mut row[i * width..],
+                width,
+                current.wrapping_add(previous) & mask,
+            );
+        }
+    }
+}
+
+/// Undo floating-point differencing (predictor 3) in place
+///
+/// The decoded values are stored back in the file byte order.
+pub(crate) fn undo_float_predictor(
+    data: &mut [u8],
+    order: ByteOrder,
+    width: usize,
+    row_samples: usize,
+    stride: usize,
+) {
+    let row_bytes = row_samples * width;
+    let mut shuffled = vec![0u8; row_bytes];
+    for row in data.chunks_exact_mut(row_bytes) {
+        for i in stride..row_bytes {
+            row[i] = row[i].wrapping_add(row[i - stride]);
+        }
+        shuffled.copy_from_slice(row);
+        // Byte plane k holds the k-th most significant byte of every value
+        for i in 0..row_samples {
+            for k in 0..width {
+                let byte = shuffled[k * row_samples + i];
+                match order {
+                    ByteOrder::Big => row[i * width + k] = byte,
+                    ByteOrder::Little => row[i * width + width - 1 - k] = byte,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_roundtrip() {
+        let mut data = vec![0u8; 300];
+        data.extend((0..=255u8).cycle().take(700));
+        data.extend_from_slice(&[7, 7, 1, 2, 3, 3, 3, 9]);
+        for compression in [
+            TiffCompression::None,
+            TiffCompression::Lzw,
+            TiffCompression::Deflate,
+            TiffCompression::PackBits,
+        ] {
+            let packed = compress(compression, &data).unwrap();
+            assert_eq!(decompress(compression, &packed, data.len()).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_packbits_reference() {
+        // Example from the TIFF 6.0 specification
+        let packed = [
+            0xFE, 0xAA, 0x02, 0x80, 0x00, 0x2A, 0xFD, 0xAA, 0x03, 0x80, 0x00, 0x2A, 0x22, 0xF7,
+            0xAA,
+        ];
+        let expected = [
+            0xAA, 0xAA, 0xAA, 0x80, 0x00, 0x2A, 0xAA, 0xAA, 0xAA, 0xAA, 0x80, 0x00, 0x2A, 0x22,
+            0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA,
+        ];
+        assert_eq!(unpack_bits(&packed, expected.len()), expected);
+    }
+
+    #[test]
+    fn test_predictors() {
+        // Two RGB pixels of 16-bit samples, differenced per channel
+        let mut data: Vec<u8> = [100u16, 200, 300, 5, 65535, 10]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect();
+        undo_horizontal_predictor(&mut data, ByteOrder::Little, 2, 6, 3);
+        let values: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        assert_eq!(values, vec![100, 200, 300, 105, 199, 310]);
+
+        // Shuffle and difference two f32 values as a writer would
+        let values = [1.5f32, -2.25];
+        let planes: Vec<u8> = (0..4)
+            .flat_map(|k| values.iter().map(move |v| v.to_be_bytes()[k]))
+            .collect();
+        let mut data: Vec<u8> = planes
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| {
+                if i == 0 {
+                    b
+                } else {
+                    b.wrapping_sub(planes[i - 1])
+                }
+            })
+            .collect();
+        undo_float_predictor(&mut data, ByteOrder::Little, 4, 2, 1);
+        assert_eq!(f32::from_le_bytes(data[..4].try_into().unwrap()), 1.5);
+        assert_eq!(f32::from_le_bytes(data[4..].try_into().unwrap()), -2.25);
+    }
+}
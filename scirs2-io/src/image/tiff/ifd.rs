@@ -0,0 +1,396 @@
+//! TIFF and BigTIFF image file directories
+
+use std::collections::BTreeMap;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use crate::error::{IoError, Result};
+
+/// Tags used by the reader and writer
+pub(crate) mod tag {
+    pub const NEW_SUBFILE_TYPE: u16 = 254;
+    pub const IMAGE_WIDTH: u16 = 256;
+    pub const IMAGE_LENGTH: u16 = 257;
+    pub const BITS_PER_SAMPLE: u16 = 258;
+    pub const COMPRESSION: u16 = 259;
+    pub const PHOTOMETRIC: u16 = 262;
+    pub const IMAGE_DESCRIPTION: u16 = 270;
+    pub const STRIP_OFFSETS: u16 = 273;
+    pub const SAMPLES_PER_PIXEL: u16 = 277;
+    pub const ROWS_PER_STRIP: u16 = 278;
+    pub const STRIP_BYTE_COUNTS: u16 = 279;
+    pub const X_RESOLUTION: u16 = 282;
+    pub const Y_RESOLUTION: u16 = 283;
+    pub const PLANAR_CONFIGURATION: u16 = 284;
+    pub const RESOLUTION_UNIT: u16 = 296;
+    pub const SOFTWARE: u16 = 305;
+    pub const PREDICTOR: u16 = 317;
+    pub const TILE_WIDTH: u16 = 322;
+    pub const TILE_LENGTH: u16 = 323;
+    pub const TILE_OFFSETS: u16 = 324;
+    pub const TILE_BYTE_COUNTS: u16 = 325;
+    pub const SUB_IFDS: u16 = 330;
+    pub const EXTRA_SAMPLES: u16 = 338;
+    pub const SAMPLE_FORMAT: u16 = 339;
+}
+
+/// Byte order of a TIFF file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ByteOrder {
+    Little,
+    Big,
+}
+
+impl ByteOrder {
+    pub fn u16(self, bytes: &[u8]) -> u16 {
+        let bytes = bytes[..2].try_into().unwrap();
+        match self {
+            ByteOrder::Little => u16::from_le_bytes(bytes),
+            ByteOrder::Big => u16::from_be_bytes(bytes),
+        }
+    }
+
+    pub fn u32(self, bytes: &[u8]) -> u32 {
+        let bytes = bytes[..4].try_into().unwrap();
+        match self {
+            ByteOrder::Little => u32::from_le_bytes(bytes),
+            ByteOrder::Big => u32::from_be_bytes(bytes),
+        }
+    }
+
+    pub fn u64(self, bytes: &[u8]) -> u64 {
+        let bytes = bytes[..8].try_into().unwrap();
+        match self {
+            ByteOrder::Little => u64::from_le_bytes(bytes),
+            ByteOrder::Big => u64::from_be_bytes(bytes),
+        }
+    }
+
+    /// Read an unsigned integer of `width` bytes
+    pub fn uint(self, bytes: &[u8], width: usize) -> u64 {
+        match width {
+            1 => bytes[0] as u64,
+            2 => self.u16(bytes) as u64,
+            4 => self.u32(bytes) as u64,
+            _ => self.u64(bytes),
+        }
+    }
+
+    /// Store the low `width` bytes of an unsigned integer
+    pub fn put_uint(self, bytes: &mut [u8], width: usize, value: u64) {
+        let le = value.to_le_bytes();
+        for i in 0..width {
+            bytes[i] = match self {
+                ByteOrder::Little => le[i],
+                ByteOrder::Big => le[width - 1 - i],
+            };
+        }
+    }
+}
+
+/// Value of a directory entry
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TagValue {
+    Unsigned(Vec<u64>),
+    Signed(Vec<i64>),
+    Float(Vec<f64>),
+    Ascii(String),
+    Bytes(Vec<u8>),
+}
+
+impl TagValue {
+    pub fn first_u64(&self) -> Option<u64> {
+        match self {
+            TagValue::Unsigned(v) => v.first().copied(),
+            TagValue::Signed(v) => v.first().and_then(|&x| u64::try_from(x).ok()),
+            _ => None,
+        }
+    }
+
+    pub fn u64_vec(&self) -> Option<Vec<u64>> {
+        match self {
+            TagValue::Unsigned(v) => Some(v.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn first_f64(&self) -> Option<f64> {
+        match self {
+            TagValue::Float(v) => v.first().copied(),
+            TagValue::Unsigned(v) => v.first().map(|&x| x as f64),
+            TagValue::Signed(v) => v.first().map(|&x| x as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            TagValue::Ascii(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed image file directory
+pub(crate) type Ifd = BTreeMap<u16, TagValue>;
+
+/// Size in bytes of one value of a TIFF field type
+fn type_size(field_type: u16) -> Option<usize> {
+    match field_type {
+        1 | 2 | 6 | 7 => Some(1),
+        3 | 8 => Some(2),
+        4 | 9 | 11 | 13 => Some(4),
+        5 | 10 | 12 | 16 | 17 | 18 => Some(8),
+        _ => None,
+    }
+}
+
+fn io_error(e: std::io::Error) -> IoError {
+    IoError::FileError(e.to_string())
+}
+
+/// Reader for the header and directories of a TIFF or BigTIFF file
+pub(crate) struct IfdReader<R: Read + Seek> {
+    reader: R,
+    pub order: ByteOrder,
+    pub big: bool,
+    /// Length of the file in bytes
+    pub len: u64,
+}
+
+impl<R: Read + Seek> IfdReader<R> {
+    /// Read the file header, returning the reader and the first IFD offset
+    pub fn new(mut reader: R) -> Result<(Self, u64)> {
+        let mut header = [0u8; 16];
+        reader
+            .read_exact(&mut header[..8])
+            .map_err(|_| IoError::FormatError("File is too short to be a TIFF file".to_string()))?;
+        let order = match &header[..2] {
+            b"II" => ByteOrder::Little,
+            b"MM" => ByteOrder::Big,
+            _ => return Err(IoError::FormatError("Not a TIFF file".to_string())),
+        };
+        let (big, first) = match order.u16(&header[2..]) {
+            42 => (false, order.u32(&header[4..]) as u64),
+            43 => {
+                reader.read_exact(&mut header[8..]).map_err(io_error)?;
+                if order.u16(&header[4..]) != 8 {
+                    return Err(IoError::FormatError(
+                        "Unsupported BigTIFF offset size".to_string(),
+                    ));
+                }
+                (true, order.u64(&header[8..]))
+            }
+            version => {
+                return Err(IoError::FormatError(format!(
+                    "Unsupported TIFF version {}",
+                    version
+                )))
+            }
+        };
+        let len = reader.seek(SeekFrom::End(0)).map_err(io_error)?;
+        Ok((
+            Self {
+                reader,
+                order,
+                big,
+                len,
+            },
+            first,
+        ))
+    }
+
+    fn read_at(&mut self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        // Check before allocating, since lengths come from the file
+        if offset
+            .checked_add(len as u64)
+            .is_none_or(|end| end > self.len)
+        {
+            return Err(IoError::FormatError(format!(
+                "Truncated TIFF file at offset {}",
+                offset
+            )));
+        }
+        self.reader
+            .seek(SeekFrom::Start(offset))
+            .map_err(io_error)?;
+        let mut buffer = vec![0u8; len];
+        self.reader.read_exact(&mut buffer).map_err(|_| {
+            IoError::FormatError(format!("Truncated TIFF file at offset {}", offset))
+        })?;
+        Ok(buffer)
+    }
+
+    /// Read the directory at `offset`, returning it and the next offset
+    pub fn read_ifd(&mut self, offset: u64) -> Result<(Ifd, u64)> {
+        let order = self.order;
+        let (count_size, entry_size, offset_size) = if self.big { (8, 20, 8) } else { (2, 12, 4) };
+        let count = order.uint(&self.read_at(offset, count_size)?, count_size) as usize;
+        let entries_size = count
+            .checked_mul(entry_size)
+            .and_then(|size| size.checked_add(offset_size))
+            .ok_or_else(|| IoError::FormatError("TIFF directory is too large".to_string()))?;
+        let entries = self.read_at(offset + count_size as u64, entries_size)?;
+
+        let mut ifd = Ifd::new();
+        for entry in entries[..count * entry_size].chunks_exact(entry_size) {
+            let code = order.u16(entry);
+            let field_type = order.u16(&entry[2..]);
+            let Some(size) = type_size(field_type) else {
+                continue;
+            };
+            let n = order.uint(&entry[4..], offset_size) as usize;
+            let total = n
+                .checked_mul(size)
+                .ok_or_else(|| IoError::FormatError(format!("TIFF tag {} is too large", code)))?;
+            let data = if total <= offset_size {
+                entry[4 + offset_size..4 + offset_size + total].to_vec()
+            } else {
+                let at = order.uint(&entry[4 + offset_size..], offset_size);
+                self.read_at(at, total)?
+            };
+            ifd.insert(code, decode_value(order, field_type, n, &data));
+        }
+        let next = order.uint(&entries[count * entry_size..], offset_size);
+        Ok((ifd, next))
+    }
+
+    /// Read `len` bytes of image data
+    pub fn read_data(&mut self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        self.read_at(offset, len)
+    }
+}
+
+fn decode_value(order: ByteOrder, field_type: u16, n: usize, data: &[u8]) -> TagValue {
+    let values = |size: usize| data.chunks_exact(size).take(n);
+    match field_type {
+        2 => {
+            let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+            TagValue::Ascii(String::from_utf8_lossy(&data[..end]).into_owned())
+        }
+        1 => TagValue::Unsigned(data.iter().map(|&b| b as u64).collect()),
+        7 => TagValue::Bytes(data.to_vec()),
+        6 => TagValue::Signed(data.iter().map(|&b| b as i8 as i64).collect()),
+        3 => TagValue::Unsigned(values(2).map(|b| order.u16(b) as u64).collect()),
+        8 => TagValue::Signed(values(2).map(|b| order.u16(b) as i16 as i64).collect()),
+        4 | 13 => TagValue::Unsigned(values(4).map(|b| order.u32(b) as u64).collect()),
+        9 => TagValue::Signed(values(4).map(|b| order.u32(b) as i32 as i64).collect()),
+        16 | 18 => TagValue::Unsigned(values(8).map(|b| order.u64(b)).collect()),
+        17 => TagValue::Signed(values(8).map(|b| order.u64(b) as i64).collect()),
+        5 => TagValue::Float(
+            values(8)
+                .map(|b| order.u32(b) as f64 / order.u32(&b[4..]) as f64)
+                .collect(),
+        ),
+        10 => TagValue::Float(
+            values(8)
+                .map(|b| order.u32(b) as i32 as f64 / order.u32(&b[4..]) as i32 as f64)
+                .collect(),
+        ),
+        11 => TagValue::Float(
+            values(4)
+                .map(|b| f32::from_bits(order.u32(b)) as f64)
+                .collect(),
+        ),
+        _ => TagValue::Float(values(8).map(|b| f64::from_bits(order.u64(b))).collect()),
+    }
+}
+
+/// Directory entry to be written
+pub(crate) enum Entry {
+    Short(Vec<u16>),
+    /// `LONG` in classic TIFF, `LONG8` in BigTIFF
+    Offsets(Vec<u64>),
+    Long(Vec<u32>),
+    Rational(u32, u32),
+    Ascii(String),
+}
+
+impl Entry {
+    /// Field type, value count and little-endian value bytes
+    fn encode(&self, big: bool) -> (u16, usize, Vec<u8>) {
+        match self {
+            Entry::Short(v) => (3, v.len(), v.iter().flat_map(|x| x.to_le_bytes()).collect()),
+            Entry::Long(v) => (4, v.len(), v.iter().flat_map(|x| x.to_le_bytes()).collect()),
+            Entry::Offsets(v) if big => (
+                16,
+                v.len(),
+                v.iter().flat_map(|x| x.to_le_bytes()).collect(),
+            ),
+            Entry::Offsets(v) => (
+                4,
+                v.len(),
+                v.iter().flat_map(|&x| (x as u32).to_le_bytes()).collect(),
+            ),
+            Entry::Rational(n, d) => (5, 1, [n.to_le_bytes(), d.to_le_bytes()].concat()),
+            Entry::Ascii(s) => {
+                let mut bytes = s.as_bytes().to_vec();
+                bytes.push(0);
+                (2, bytes.len(), bytes)
+            }
+        }
+    }
+}
+
+/// Write a little-endian directory at the current (word aligned) position
+///
+/// Returns the offset of the directory and the position of its next-IFD field.
+pub(crate) fn write_ifd<W: Write + Seek>(
+    writer: &mut W,
+    big: bool,
+    entries: &BTreeMap<u16, Entry>,
+) -> Result<(u64, u64)> {
+    let mut position = writer.stream_position().map_err(io_error)?;
+    if position % 2 == 1 {
+        writer.write_all(&[0]).map_err(io_error)?;
+        position += 1;
+    }
+    let (count_size, entry_size, offset_size) = if big { (8, 20, 8) } else { (2, 12, 4) };
+    let ifd_size = count_size + entries.len() * entry_size + offset_size;
+
+    let mut table = Vec::with_capacity(ifd_size);
+    let mut extra = Vec::new();
+    if big {
+        table.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+    } else {
+        table.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    }
+    for (&code, entry) in entries {
+        let (field_type, count, mut bytes) = entry.encode(big);
+        table.extend_from_slice(&code.to_le_bytes());
+        table.extend_from_slice(&field_type.to_le_bytes());
+        if big {
+            table.extend_from_slice(&(count as u64).to_le_bytes());
+        } else {
+            table.extend_from_slice(&(count as u32).to_le_bytes());
+        }
+        if bytes.len() <= offset_size {
+            bytes.resize(offset_size, 0);
+            table.extend_from_slice(&bytes);
+        } else {
+            let at = position + ifd_size as u64 + extra.len() as u64;
+            if big {
+                table.extend_from_slice(&at.to_le_bytes());
+            } else {
+                table.extend_from_slice(&offset32(at)?.to_le_bytes());
+            }
+            extra.extend_from_slice(&bytes);
+            if extra.len() % 2 == 1 {
+                extra.push(0);
+            }
+        }
+    }
+    let next_field = position + table.len() as u64;
+    table.resize(ifd_size, 0);
+    writer.write_all(&table).map_err(io_error)?;
+    writer.write_all(&extra).map_err(io_error)?;
+    Ok((position, next_field))
+}
+
+/// Check that an offset fits into a classic TIFF file
+pub(crate) fn offset32(offset: u64) -> Result<u32> {
+    u32::try_from(offset).map_err(|_| {
+        IoError::ValidationError(
+            "File exceeds 4 GiB; enable BigTIFF to write larger files".to_string(),
+        )
+    })
+}
@@ -0,0 +1,1292 @@
+//! Scientific TIFF, BigTIFF and OME-TIFF support
+//!
+//! Unlike [`load_image`](super::load_image), which converts everything to
+//! 8-bit RGB, this module keeps the stored sample type and reads multi-page
+//! files into n-dimensional arrays, as needed for microscopy stacks:
+//!
+//! - Classic TIFF and BigTIFF, little and big endian
+//! - Striped and tiled pages, chunky and planar sample layouts
+//! - 8/16/32-bit integer and 32/64-bit float samples
+//! - LZW, Deflate and PackBits compression with horizontal and floating-point predictors
+//! - Lazy per-page access and reduced-resolution levels stored as SubIFDs
+//! - OME-XML metadata (dimension order, physical pixel sizes, channel names)
+//!   and ImageJ hyperstack descriptions
+//!
+//! ## Examples
+//!
+//! ```
+//! use ndarray::{Array, IxDyn};
+//! use scirs2_io::image::tiff::{read_tiff, write_tiff, OmeMetadata, TiffArray, TiffCompression, TiffWriteOptions};
+//! use tempfile::tempdir;
+//!
+//! let dir = tempdir().unwrap();
+//! let path = dir.path().join("stack.ome.tif");
+//!
+//! // Two channels of three focal planes, in TCZYX order
+//! let stack = Array::from_shape_fn(IxDyn(&[1, 2, 3, 32, 48]), |i| (i[1] * 1000 + i[2] * 100 + i[3]) as u16);
+//! let options = TiffWriteOptions {
+//!     compression: TiffCompression::Lzw,
+//!     ome: Some(OmeMetadata {
+//!         physical_size_x: Some(0.5),
+//!         physical_size_y: Some(0.5),
+//!         channel_names: vec!["DAPI".to_string(), "GFP".to_string()],
+//!         ..Default::default()
+//!     }),
+//!     ..Default::default()
+//! };
+//! write_tiff(&path, &TiffArray::from(stack.clone()), &options).unwrap();
+//!
+//! let image = read_tiff(&path).unwrap();
+//! assert_eq!(image.axes, "TCZYX");
+//! assert_eq!(image.data, TiffArray::UInt16(stack));
+//! let ome = image.ome.unwrap();
+//! assert_eq!(ome.physical_size_x, Some(0.5));
+//! assert_eq!(ome.channel_names[1], "GFP");
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use ndarray::{ArrayD, Axis, IxDyn};
+
+use crate::error::{IoError, Result};
+
+mod codec;
+mod ifd;
+mod ome;
+
+pub use codec::TiffCompression;
+pub use ome::OmeMetadata;
+
+use ifd::{offset32, tag, write_ifd, ByteOrder, Entry, Ifd, IfdReader};
+
+/// Maximum nesting depth of SubIFDs
+const MAX_SUB_IFD_DEPTH: usize = 8;
+
+/// Pixel data of a TIFF page or stack
+#[derive(Debug, Clone, PartialEq)]
+pub enum TiffArray {
+    /// Unsigned 8-bit samples
+    UInt8(ArrayD<u8>),
+    /// Signed 8-bit samples
+    Int8(ArrayD<i8>),
+    /// Unsigned 16-bit samples
+    UInt16(ArrayD<u16>),
+    /// Signed 16-bit samples
+    Int16(ArrayD<i16>),
+    /// Unsigned 32-bit samples
+    UInt32(ArrayD<u32>),
+    /// Signed 32-bit samples
+    Int32(ArrayD<i32>),
+    /// 32-bit float samples
+    Float32(ArrayD<f32>),
+    /// 64-bit float samples
+    Float64(ArrayD<f64>),
+}
+
+/// Apply an expression to the array inside any [`TiffArray`] variant
+macro_rules! with_tiff_array {
+    ($value:expr, $array:ident => $body:expr) => {
+        match $value {
+            TiffArray::UInt8($array) => $body,
+            TiffArray::Int8($array) => $body,
+            TiffArray::UInt16($array) => $body,
+            TiffArray::Int16($array) => $body,
+            TiffArray::UInt32($array) => $body,
+            TiffArray::Int32($array) => $body,
+            TiffArray::Float32($array) => $body,
+            TiffArray::Float64($array) => $body,
+        }
+    };
+}
+
+/// Rebuild the same [`TiffArray`] variant from an expression on its array
+macro_rules! map_tiff_array {
+    ($value:expr, $array:ident => $body:expr) => {
+        match $value {
+            TiffArray::UInt8($array) => TiffArray::UInt8($body),
+            TiffArray::Int8($array) => TiffArray::Int8($body),
+            TiffArray::UInt16($array) => TiffArray::UInt16($body),
+            TiffArray::Int16($array) => TiffArray::Int16($body),
+            TiffArray::UInt32($array) => TiffArray::UInt32($body),
+            TiffArray::Int32($array) => TiffArray::Int32($body),
+            TiffArray::Float32($array) => TiffArray::Float32($body),
+            TiffArray::Float64($array) => TiffArray::Float64($body),
+        }
+    };
+}
+
+impl TiffArray {
+    /// Shape of the array
+    pub fn shape(&self) -> &[usize] {
+        with_tiff_array!(self, a => a.shape())
+    }
+
+    /// Convert the samples to `f64`
+    pub fn to_f64(&self) -> ArrayD<f64> {
+        match self {
+            TiffArray::UInt8(a) => a.mapv(f64::from),
+            TiffArray::Int8(a) => a.mapv(f64::from),
+            TiffArray::UInt16(a) => a.mapv(f64::from),
+            TiffArray::Int16(a) => a.mapv(f64::from),
+            TiffArray::UInt32(a) => a.mapv(f64::from),
+            TiffArray::Int32(a) => a.mapv(f64::from),
+            TiffArray::Float32(a) => a.mapv(f64::from),
+            TiffArray::Float64(a) => a.clone(),
+        }
+    }
+
+    /// Bits per sample and `SampleFormat` tag value
+    fn sample_type(&self) -> (u16, TiffSampleFormat) {
+        match self {
+            TiffArray::UInt8(_) => (8, TiffSampleFormat::UnsignedInt),
+            TiffArray::Int8(_) => (8, TiffSampleFormat::SignedInt),
+            TiffArray::UInt16(_) => (16, TiffSampleFormat::UnsignedInt),
+            TiffArray::Int16(_) => (16, TiffSampleFormat::SignedInt),
+            TiffArray::UInt32(_) => (32, TiffSampleFormat::UnsignedInt),
+            TiffArray::Int32(_) => (32, TiffSampleFormat::SignedInt),
+            TiffArray::Float32(_) => (32, TiffSampleFormat::Float),
+            TiffArray::Float64(_) => (64, TiffSampleFormat::Float),
+        }
+    }
+
+    /// OME pixel type name
+    fn ome_pixel_type(&self) -> &'static str {
+        match self {
+            TiffArray::UInt8(_) => "uint8",
+            TiffArray::Int8(_) => "int8",
+            TiffArray::UInt16(_) => "uint16",
+            TiffArray::Int16(_) => "int16",
+            TiffArray::UInt32(_) => "uint32",
+            TiffArray::Int32(_) => "int32",
+            TiffArray::Float32(_) => "float",
+            TiffArray::Float64(_) => "double",
+        }
+    }
+
+    /// Samples as little-endian bytes in C order
+    fn to_le_bytes(&self) -> Vec<u8> {
+        with_tiff_array!(self, a => a.iter().flat_map(|x| x.to_le_bytes()).collect())
+    }
+
+    /// Decode samples stored in the given byte order
+    fn from_bytes(
+        bytes: &[u8],
+        order: ByteOrder,
+        bits: u16,
+        format: TiffSampleFormat,
+        shape: &[usize],
+    ) -> Result<Self> {
+        macro_rules! decode {
+            ($ty:ty, $variant:ident) => {{
+                const N: usize = std::mem::size_of::<$ty>();
+                let values: Vec<$ty> = bytes
+                    .chunks_exact(N)
+                    .map(|b| {
+                        let b: [u8; N] = b.try_into().unwrap();
+                        match order {
+                            ByteOrder::Little => <$ty>::from_le_bytes(b),
+                            ByteOrder::Big => <$ty>::from_be_bytes(b),
+                        }
+                    })
+                    .collect();
+                TiffArray::$variant(
+                    ArrayD::from_shape_vec(IxDyn(shape), values).map_err(|e| {
+                        IoError::FormatError(format!("Invalid TIFF page shape: {}", e))
+                    })?,
+                )
+            }};
+        }
+        Ok(match (format, bits) {
+            (TiffSampleFormat::UnsignedInt, 8) => decode!(u8, UInt8),
+            (TiffSampleFormat::SignedInt, 8) => decode!(i8, Int8),
+            (TiffSampleFormat::UnsignedInt, 16) => decode!(u16, UInt16),
+            (TiffSampleFormat::SignedInt, 16) => decode!(i16, Int16),
+            (TiffSampleFormat::UnsignedInt, 32) => decode!(u32, UInt32),
+            (TiffSampleFormat::SignedInt, 32) => decode!(i32, Int32),
+            (TiffSampleFormat::Float, 32) => decode!(f32, Float32),
+            (TiffSampleFormat::Float, 64) => decode!(f64, Float64),
+            _ => {
+                return Err(IoError::FormatError(format!(
+                    "Unsupported TIFF sample type: {:?} with {} bits",
+                    format, bits
+                )))
+            }
+        })
+    }
+
+    /// Stack arrays of equal type and shape along a new leading axis
+    fn stack(pages: Vec<TiffArray>, shape: &[usize]) -> Result<Self> {
+        macro_rules! stack {
+            ($variant:ident) => {{
+                let mut values = Vec::new();
+                for page in pages {
+                    match page {
+                        TiffArray::$variant(a) => values.extend(a.iter().copied()),
+                        _ => {
+                            return Err(IoError::FormatError(
+                                "TIFF pages have different sample types".to_string(),
+                            ))
+                        }
+                    }
+                }
+                TiffArray::$variant(ArrayD::from_shape_vec(IxDyn(shape), values).map_err(|_| {
+                    IoError::FormatError("TIFF pages have different shapes".to_string())
+                })?)
+            }};
+        }
+        Ok(match pages.first() {
+            Some(TiffArray::UInt8(_)) => stack!(UInt8),
+            Some(TiffArray::Int8(_)) => stack!(Int8),
+            Some(TiffArray::UInt16(_)) => stack!(UInt16),
+            Some(TiffArray::Int16(_)) => stack!(Int16),
+            Some(TiffArray::UInt32(_)) => stack!(UInt32),
+            Some(TiffArray::Int32(_)) => stack!(Int32),
+            Some(TiffArray::Float32(_)) => stack!(Float32),
+            Some(TiffArray::Float64(_)) => stack!(Float64),
+            None => return Err(IoError::FormatError("TIFF file has no pages".to_string())),
+        })
+    }
+
+    /// Split the leading axes into 2D pages of the trailing two axes
+    fn pages(&self) -> Result<Vec<TiffArray>> {
+        let shape = self.shape();
+        if shape.len() < 2 {
+            return Err(IoError::ValidationError(
+                "TIFF images need at least two dimensions".to_string(),
+            ));
+        }
+        let (height, width) = (shape[shape.len() - 2], shape[shape.len() - 1]);
+        let count = shape[..shape.len() - 2].iter().product::<usize>();
+        let flat = map_tiff_array!(self, a => a
+            .as_standard_layout()
+            .into_owned()
+            .into_shape_with_order(IxDyn(&[count, height, width]))
+            .unwrap());
+        Ok((0..count)
+            .map(|i| map_tiff_array!(&flat, a => a.index_axis(Axis(0), i).to_owned()))
+            .collect())
+    }
+}
+
+macro_rules! tiff_array_from {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl From<ArrayD<$ty>> for TiffArray {
+                fn from(array: ArrayD<$ty>) -> Self {
+                    TiffArray::$variant(array)
+                }
+            }
+        )*
+    };
+}
+
+tiff_array_from!(
+    u8 => UInt8, i8 => Int8, u16 => UInt16, i16 => Int16, u32 => UInt32, i32 => Int32,
+    f32 => Float32, f64 => Float64
+);
+
+/// Interpretation of the samples (`SampleFormat` tag)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TiffSampleFormat {
+    /// Unsigned integers
+    UnsignedInt,
+    /// Two's complement signed integers
+    SignedInt,
+    /// IEEE floating point
+    Float,
+}
+
+impl TiffSampleFormat {
+    fn code(self) -> u16 {
+        match self {
+            TiffSampleFormat::UnsignedInt => 1,
+            TiffSampleFormat::SignedInt => 2,
+            TiffSampleFormat::Float => 3,
+        }
+    }
+}
+
+/// Layout and metadata of one TIFF page (image file directory)
+#[derive(Debug, Clone)]
+pub struct TiffPage {
+    /// Image width in pixels
+    pub width: usize,
+    /// Image height in pixels
+    pub height: usize,
+    /// Number of samples per pixel
+    pub samples_per_pixel: usize,
+    /// Bits per sample
+    pub bits_per_sample: u16,
+    /// Interpretation of the samples
+    pub sample_format: TiffSampleFormat,
+    /// Compression of the pixel data
+    pub compression: TiffCompression,
+    /// Tile width and height, `None` for striped pages
+    pub tile_size: Option<(usize, usize)>,
+    /// Rows per strip of striped pages
+    pub rows_per_strip: usize,
+    /// Whether samples are stored in separate planes
+    pub planar: bool,
+    /// Image description, e.g. OME-XML or an ImageJ header
+    pub description: Option<String>,
+    /// Horizontal resolution in pixels per resolution unit
+    pub x_resolution: Option<f64>,
+    /// Vertical resolution in pixels per resolution unit
+    pub y_resolution: Option<f64>,
+    /// Whether the page is a reduced-resolution copy of another page
+    pub reduced_resolution: bool,
+    /// Reduced-resolution levels stored in SubIFDs, largest first
+    pub sub_resolutions: Vec<TiffPage>,
+    predictor: u64,
+    offsets: Vec<u64>,
+    byte_counts: Vec<u64>,
+}
+
+impl TiffPage {
+    /// Shape of the page array: `[height, width]` or `[height, width, samples]`
+    pub fn shape(&self) -> Vec<usize> {
+        if self.samples_per_pixel == 1 {
+            vec![self.height, self.width]
+        } else {
+            vec![self.height, self.width, self.samples_per_pixel]
+        }
+    }
+
+    /// Parse a directory; `visited` holds the offsets of the directories read so far
+    fn from_ifd<R: std::io::Read + Seek>(
+        ifd: &Ifd,
+        reader: &mut IfdReader<R>,
+        visited: &mut HashSet<u64>,
+        depth: usize,
+    ) -> Result<Self> {
+        let unsigned = |code: u16| ifd.get(&code).and_then(|v| v.first_u64());
+        let required = |code: u16, name: &str| match unsigned(code) {
+            Some(0) => Err(IoError::FormatError(format!(
+                "TIFF page with zero {}",
+                name
+            ))),
+            Some(v) => Ok(v as usize),
+            None => Err(IoError::FormatError(format!("TIFF page without {}", name))),
+        };
+        let width = required(tag::IMAGE_WIDTH, "ImageWidth")?;
+        let height = required(tag::IMAGE_LENGTH, "ImageLength")?;
+        let samples_per_pixel = match unsigned(tag::SAMPLES_PER_PIXEL) {
+            Some(_) => required(tag::SAMPLES_PER_PIXEL, "SamplesPerPixel")?,
+            None => 1,
+        };
+        let bits = ifd
+            .get(&tag::BITS_PER_SAMPLE)
+            .and_then(|v| v.u64_vec())
+            .unwrap_or_else(|| vec![1]);
+        if bits.iter().any(|&b| b != bits[0]) {
+            return Err(IoError::FormatError(
+                "TIFF pages with different bits per sample are not supported".to_string(),
+            ));
+        }
+        let sample_format = match unsigned(tag::SAMPLE_FORMAT).unwrap_or(1) {
+            1 => TiffSampleFormat::UnsignedInt,
+            2 => TiffSampleFormat::SignedInt,
+            3 => TiffSampleFormat::Float,
+            other => {
+                return Err(IoError::FormatError(format!(
+                    "Unsupported TIFF sample format {}",
+                    other
+                )))
+            }
+        };
+        let (tile_size, offset_tag, count_tag) = match unsigned(tag::TILE_WIDTH) {
+            Some(_) => (
+                Some((
+                    required(tag::TILE_WIDTH, "TileWidth")?,
+                    required(tag::TILE_LENGTH, "TileLength")?,
+                )),
+                tag::TILE_OFFSETS,
+                tag::TILE_BYTE_COUNTS,
+            ),
+            None => (None, tag::STRIP_OFFSETS, tag::STRIP_BYTE_COUNTS),
+        };
+        let offsets = ifd
+            .get(&offset_tag)
+            .and_then(|v| v.u64_vec())
+            .ok_or_else(|| IoError::FormatError("TIFF page without data offsets".to_string()))?;
+        let byte_counts = ifd
+            .get(&count_tag)
+            .and_then(|v| v.u64_vec())
+            .ok_or_else(|| IoError::FormatError("TIFF page without byte counts".to_string()))?;
+
+        let mut sub_resolutions = Vec::new();
+        if let Some(sub_ifds) = ifd.get(&tag::SUB_IFDS).and_then(|v| v.u64_vec()) {
+            if depth >= MAX_SUB_IFD_DEPTH {
+                return Err(IoError::FormatError(
+                    "TIFF SubIFDs are nested too deeply".to_string(),
+                ));
+            }
+            for offset in sub_ifds {
+                if !visited.insert(offset) {
+                    return Err(IoError::FormatError(
+                        "TIFF SubIFDs contain a loop".to_string(),
+                    ));
+                }
+                let (sub_ifd, _) = reader.read_ifd(offset)?;
+                sub_resolutions.push(TiffPage::from_ifd(&sub_ifd, reader, visited, depth + 1)?);
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            samples_per_pixel,
+            bits_per_sample: bits[0] as u16,
+            sample_format,
+            compression: TiffCompression::from_code(unsigned(tag::COMPRESSION).unwrap_or(1))?,
+            tile_size,
+            rows_per_strip: unsigned(tag::ROWS_PER_STRIP)
+                .map_or(height, |r| (r as usize).min(height))
+                .max(1),
+            planar: unsigned(tag::PLANAR_CONFIGURATION) == Some(2),
+            description: ifd
+                .get(&tag::IMAGE_DESCRIPTION)
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            x_resolution: ifd.get(&tag::X_RESOLUTION).and_then(|v| v.first_f64()),
+            y_resolution: ifd.get(&tag::Y_RESOLUTION).and_then(|v| v.first_f64()),
+            reduced_resolution: unsigned(tag::NEW_SUBFILE_TYPE).unwrap_or(0) & 1 == 1,
+            sub_resolutions,
+            predictor: unsigned(tag::PREDICTOR).unwrap_or(1),
+            offsets,
+            byte_counts,
+        })
+    }
+
+    /// Decode the pixel data of the page
+    fn read<R: std::io::Read + Seek>(&self, reader: &mut IfdReader<R>) -> Result<TiffArray> {
+        let order = reader.order;
+        let bytes_per_sample = self.bits_per_sample as usize / 8;
+        if !matches!(self.bits_per_sample, 8 | 16 | 32 | 64) {
+            return Err(IoError::FormatError(format!(
+                "Unsupported TIFF bits per sample: {}",
+                self.bits_per_sample
+            )));
+        }
+        let (width, height, samples) = (self.width, self.height, self.samples_per_pixel);
+        let (chunk_width, chunk_height) = self.tile_size.unwrap_or((width, self.rows_per_strip));
+        let across = width.div_ceil(chunk_width);
+        let down = height.div_ceil(chunk_height);
+        let planes = if self.planar { samples } else { 1 };
+        let chunk_samples = if self.planar { 1 } else { samples };
+        let pixel_bytes = samples * bytes_per_sample;
+        let size = height
+            .checked_mul(width)
+            .and_then(|n| n.checked_mul(pixel_bytes))
+            .ok_or_else(|| IoError::FormatError("TIFF page is too large".to_string()))?;
+        let chunks = across * down * planes;
+        if self.offsets.len() < chunks || self.byte_counts.len() < self.offsets.len() {
+            return Err(IoError::FormatError(
+                "TIFF page has too few strips or tiles".to_string(),
+            ));
+        }
+        // Dimensions come from the header, so check that the data can hold
+        // them before allocating
+        let expansion = self.compression.max_expansion();
+        let available = self.byte_counts[..chunks]
+            .iter()
+            .map(|&count| count.min(reader.len).saturating_mul(expansion))
+            .fold(0u64, u64::saturating_add);
+        if size as u64 > available {
+            return Err(IoError::FormatError(format!(
+                "TIFF page of {} bytes is larger than its data allows",
+                size
+            )));
+        }
+
+        let mut buffer = vec![0u8; size];
+        for k in 0..across * down * planes {
+            let plane = k / (across * down);
+            let (cx, cy) = (k % (across * down) % across, k % (across * down) / across);
+            // The last strip may be shorter; tiles always have their full size
+            let rows = if self.tile_size.is_some() {
+                chunk_height
+            } else {
+                chunk_height.min(height - cy * chunk_height)
+            };
+            let row_samples = chunk_width * chunk_samples;
+            let raw = reader.read_data(self.offsets[k], self.byte_counts[k] as usize)?;
+            let expected = rows
+                .checked_mul(row_samples)
+                .and_then(|n| n.checked_mul(bytes_per_sample))
+                .filter(|&n| n as u64 <= (raw.len() as u64).saturating_mul(expansion))
+                .ok_or_else(|| {
+                    IoError::FormatError(format!(
+                        "TIFF strip or tile {} is larger than its data allows",
+                        k
+                    ))
+                })?;
+            let mut chunk = codec::decompress(self.compression, &raw, expected)?;
+            match self.predictor {
+                1 => {}
+                2 => codec::undo_horizontal_predictor(
+                    &mut chunk,
+                    order,
+                    bytes_per_sample,
+                    row_samples,
+                    chunk_samples,
+                ),
+                3 => codec::undo_float_predictor(
+                    &mut chunk,
+                    order,
+                    bytes_per_sample,
+                    row_samples,
+                    chunk_samples,
+                ),
+                other => {
+                    return Err(IoError::FormatError(format!(
+                        "Unsupported TIFF predictor {}",
+                        other
+                    )))
+                }
+            }
+
+            let x0 = cx * chunk_width;
+            let columns = chunk_width.min(width - x0);
+            for r in 0..rows {
+                let y = cy * chunk_height + r;
+                if y >= height {
+                    break;
+                }
+                let src_row = &chunk[r * row_samples * bytes_per_sample..];
+                let dst_row = (y * width + x0) * pixel_bytes;
+                if self.planar {
+                    for c in 0..columns {
+                        let dst = dst_row + c * pixel_bytes + plane * bytes_per_sample;
+                        buffer[dst..dst + bytes_per_sample].copy_from_slice(
+                            &src_row[c * bytes_per_sample..(c + 1) * bytes_per_sample],
+                        );
+                    }
+                } else {
+                    let len = columns * pixel_bytes;
+                    buffer[dst_row..dst_row + len].copy_from_slice(&src_row[..len]);
+                }
+            }
+        }
+        TiffArray::from_bytes(
+            &buffer,
+            order,
+            self.bits_per_sample,
+            self.sample_format,
+            &self.shape(),
+        )
+    }
+}
+
+/// Image stack read from a TIFF file
+#[derive(Debug, Clone, PartialEq)]
+pub struct TiffImage {
+    /// Pixel data
+    pub data: TiffArray,
+    /// Axis labels of `data`, e.g. `YX`, `IYX` (page index), `TZCYX` or `YXS` (samples)
+    pub axes: String,
+    /// OME-XML metadata if the file is an OME-TIFF
+    pub ome: Option<OmeMetadata>,
+}
+
+/// TIFF file opened for reading
+///
+/// Opening a file reads all directories; pixel data is read per page on demand.
+#[derive(Debug, Clone)]
+pub struct TiffFile {
+    path: PathBuf,
+    big: bool,
+    pages: Vec<TiffPage>,
+    ome: Option<OmeMetadata>,
+}
+
+impl TiffFile {
+    /// Open a TIFF or BigTIFF file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the TIFF file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TiffFile>` - The opened file or an error
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let (mut reader, mut offset) = Self::reader(&path)?;
+        let mut pages = Vec::new();
+        let mut visited = HashSet::new();
+        while offset != 0 {
+            if !visited.insert(offset) {
+                return Err(IoError::FormatError(
+                    "TIFF directory chain contains a loop".to_string(),
+                ));
+            }
+            let (ifd, next) = reader.read_ifd(offset)?;
+            pages.push(TiffPage::from_ifd(&ifd, &mut reader, &mut visited, 0)?);
+            offset = next;
+        }
+        if pages.is_empty() {
+            return Err(IoError::FormatError("TIFF file has no pages".to_string()));
+        }
+        // A malformed OME-XML block is ignored rather than making the pixels unreadable
+        let ome = pages[0]
+            .description
+            .as_deref()
+            .filter(|d| d.contains("<OME"))
+            .and_then(|d| OmeMetadata::parse(d).ok());
+        Ok(Self {
+            path,
+            big: reader.big,
+            pages,
+            ome,
+        })
+    }
+
+    fn reader(path: &Path) -> Result<(IfdReader<BufReader<File>>, u64)> {
+        let file = File::open(path).map_err(|e| IoError::FileError(e.to_string()))?;
+        IfdReader::new(BufReader::new(file))
+    }
+
+    /// All pages in file order
+    pub fn pages(&self) -> &[TiffPage] {
+        &self.pages
+    }
+
+    /// Number of pages
+    pub fn num_pages(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Whether the file is a BigTIFF
+    pub fn is_bigtiff(&self) -> bool {
+        self.big
+    }
+
+    /// OME-XML metadata of an OME-TIFF file
+    pub fn ome_metadata(&self) -> Option<&OmeMetadata> {
+        self.ome.as_ref()
+    }
+
+    fn page(&self, index: usize) -> Result<&TiffPage> {
+        self.pages.get(index).ok_or_else(|| {
+            IoError::ValidationError(format!(
+                "Page {} out of range ({} pages)",
+                index,
+                self.pages.len()
+            ))
+        })
+    }
+
+    /// Read the pixels of one page
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the page
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TiffArray>` - Array of shape `[height, width]` or `[height, width, samples]`
+    pub fn read_page(&self, index: usize) -> Result<TiffArray> {
+        let page = self.page(index)?;
+        page.read(&mut Self::reader(&self.path)?.0)
+    }
+
+    /// Read a reduced-resolution level of a page
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the page
+    /// * `level` - Resolution level, 0 for the full-resolution page
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TiffArray>` - The pixels of the level or an error
+    pub fn read_level(&self, index: usize, level: usize) -> Result<TiffArray> {
+        let page = self.page(index)?;
+        let page = match level {
+            0 => page,
+            _ => page.sub_resolutions.get(level - 1).ok_or_else(|| {
+                IoError::ValidationError(format!(
+                    "Resolution level {} out of range ({} levels)",
+                    level,
+                    page.sub_resolutions.len() + 1
+                ))
+            })?,
+        };
+        page.read(&mut Self::reader(&self.path)?.0)
+    }
+
+    /// Read all full-resolution pages into one array
+    ///
+    /// Pages are stacked along leading axes given by the OME-XML dimension
+    /// order or an ImageJ hyperstack description, or along a single page axis
+    /// `I` otherwise.
+    pub fn read_all(&self) -> Result<TiffImage> {
+        let indices: Vec<usize> = (0..self.pages.len())
+            .filter(|&i| !self.pages[i].reduced_resolution)
+            .collect();
+        let mut reader = Self::reader(&self.path)?.0;
+        let arrays = indices
+            .iter()
+            .map(|&i| self.pages[i].read(&mut reader))
+            .collect::<Result<Vec<_>>>()?;
+        let page_shape = arrays[0].shape().to_vec();
+        let page_axes = if page_shape.len() == 3 { "YXS" } else { "YX" };
+
+        let (leading_axes, leading_shape) = match self.leading_axes(arrays.len()) {
+            Some(leading) => leading,
+            None if arrays.len() == 1 => (String::new(), Vec::new()),
+            None => ("I".to_string(), vec![arrays.len()]),
+        };
+        let mut shape = leading_shape;
+        shape.extend_from_slice(&page_shape);
+        let data = if shape.len() == page_shape.len() {
+            arrays.into_iter().next().unwrap()
+        } else {
+            TiffArray::stack(arrays, &shape)?
+        };
+        Ok(TiffImage {
+            data,
+            axes: leading_axes + page_axes,
+            ome: self.ome.clone(),
+        })
+    }
+
+    /// Axes and sizes that index the pages, from OME-XML or ImageJ metadata
+    fn leading_axes(&self, num_pages: usize) -> Option<(String, Vec<usize>)> {
+        if let Some(ome) = &self.ome {
+            if ome.num_planes() == num_pages {
+                let axes: String = ome.axes().chars().take(3).collect();
+                let sizes = axes.chars().map(|a| ome.size(a)).collect();
+                return Some((axes, sizes));
+            }
+        }
+        let description = self.pages[0].description.as_deref()?;
+        if !description.starts_with("ImageJ=") {
+            return None;
+        }
+        let value = |key: &str| {
+            description
+                .lines()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .unwrap_or(1)
+        };
+        // ImageJ stores channels fastest, then slices, then frames
+        let sizes = vec![value("frames"), value("slices"), value("channels")];
+        if sizes.iter().product::<usize>() != num_pages || num_pages == 1 {
+            return None;
+        }
+        Some(("TZC".to_string(), sizes))
+    }
+}
+
+/// Options for writing TIFF files
+#[derive(Debug, Clone, Default)]
+pub struct TiffWriteOptions {
+    /// Compression of the pixel data
+    pub compression: TiffCompression,
+    /// Write a BigTIFF file, required for files larger than 4 GiB
+    pub bigtiff: bool,
+    /// Tile width and height (multiples of 16); strips are written if `None`
+    pub tile_size: Option<(usize, usize)>,
+    /// Rows per strip; by default strips hold about 64 KiB
+    pub rows_per_strip: Option<usize>,
+    /// OME-XML metadata written to the first page by [`write_tiff`]
+    pub ome: Option<OmeMetadata>,
+}
+
+/// Writer producing a multi-page TIFF file
+pub struct TiffWriter<W: Write + Seek> {
+    writer: W,
+    options: TiffWriteOptions,
+    /// Position of the field that must point to the next directory
+    next_ifd_field: u64,
+}
+
+impl TiffWriter<BufWriter<File>> {
+    /// Create a TIFF file for writing
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file to create
+    /// * `options` - Compression, layout and BigTIFF options
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TiffWriter<BufWriter<File>>>` - The writer or an error
+    pub fn create<P: AsRef<Path>>(path: P, options: TiffWriteOptions) -> Result<Self> {
+        let file = File::create(path).map_err(|e| IoError::FileError(e.to_string()))?;
+        Self::new(BufWriter::new(file), options)
+    }
+}
+
+impl<W: Write + Seek> TiffWriter<W> {
+    /// Create a writer on a seekable stream and write the file header
+    pub fn new(mut writer: W, options: TiffWriteOptions) -> Result<Self> {
+        if let Some((w, h)) = options.tile_size {
+            if w == 0 || h == 0 || w % 16 != 0 || h % 16 != 0 {
+                return Err(IoError::ValidationError(
+                    "TIFF tile sizes must be positive multiples of 16".to_string(),
+                ));
+            }
+        }
+        let header: &[u8] = if options.bigtiff {
+            &[b'I', b'I', 43, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+        } else {
+            &[b'I', b'I', 42, 0, 0, 0, 0, 0]
+        };
+        writer
+            .write_all(header)
+            .map_err(|e| IoError::FileError(e.to_string()))?;
+        let next_ifd_field = if options.bigtiff { 8 } else { 4 };
+        Ok(Self {
+            writer,
+            options,
+            next_ifd_field,
+        })
+    }
+
+    /// Append a page
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - Array of shape `[height, width]` or `[height, width, samples]`
+    /// * `description` - Optional `ImageDescription`, e.g. OME-XML
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - Success or an error
+    pub fn write_page(&mut self, page: &TiffArray, description: Option<&str>) -> Result<()> {
+        let shape = page.shape();
+        let (height, width, samples) = match *shape {
+            [h, w] => (h, w, 1),
+            [h, w, s] => (h, w, s),
+            _ => {
+                return Err(IoError::ValidationError(format!(
+                    "TIFF pages must have 2 or 3 dimensions, got {}",
+                    shape.len()
+                )))
+            }
+        };
+        if width == 0 || height == 0 || samples == 0 {
+            return Err(IoError::ValidationError(
+                "TIFF pages must not be empty".to_string(),
+            ));
+        }
+        let (bits, sample_format) = page.sample_type();
+        let pixel_bytes = samples * bits as usize / 8;
+        let data = page.to_le_bytes();
+        let big = self.options.bigtiff;
+        let io = |e: std::io::Error| IoError::FileError(e.to_string());
+
+        // Uncompressed chunks in file order
+        let mut chunks = Vec::new();
+        let (chunk_width, chunk_height) = match self.options.tile_size {
+            Some((tile_width, tile_height)) => {
+                for ty in 0..height.div_ceil(tile_height) {
+                    for tx in 0..width.div_ceil(tile_width) {
+                        let mut tile = vec![0u8; tile_width * tile_height * pixel_bytes];
+                        let x0 = tx * tile_width;
+                        let columns = tile_width.min(width - x0);
+                        for r in 0..tile_height.min(height - ty * tile_height) {
+                            let src = ((ty * tile_height + r) * width + x0) * pixel_bytes;
+                            let dst = r * tile_width * pixel_bytes;
+                            tile[dst..dst + columns * pixel_bytes]
+                                .copy_from_slice(&data[src..src + columns * pixel_bytes]);
+                        }
+                        chunks.push(tile);
+                    }
+                }
+                (tile_width, tile_height)
+            }
+            None => {
+                let row_bytes = width * pixel_bytes;
+                let rows = self
+                    .options
+                    .rows_per_strip
+                    .unwrap_or((65536 / row_bytes).max(1))
+                    .clamp(1, height);
+                chunks.extend(data.chunks(rows * row_bytes).map(<[u8]>::to_vec));
+                (width, rows)
+            }
+        };
+
+        let mut offsets = Vec::with_capacity(chunks.len());
+        let mut byte_counts = Vec::with_capacity(chunks.len());
+        for chunk in &chunks {
+            let compressed = codec::compress(self.options.compression, chunk)?;
+            let mut position = self.writer.stream_position().map_err(io)?;
+            if position % 2 == 1 {
+                self.writer.write_all(&[0]).map_err(io)?;
+                position += 1;
+            }
+            self.writer.write_all(&compressed).map_err(io)?;
+            if !big {
+                offset32(position + compressed.len() as u64)?;
+            }
+            offsets.push(position);
+            byte_counts.push(compressed.len() as u64);
+        }
+
+        let mut entries = BTreeMap::new();
+        entries.insert(tag::NEW_SUBFILE_TYPE, Entry::Long(vec![0]));
+        entries.insert(tag::IMAGE_WIDTH, Entry::Long(vec![width as u32]));
+        entries.insert(tag::IMAGE_LENGTH, Entry::Long(vec![height as u32]));
+        entries.insert(tag::BITS_PER_SAMPLE, Entry::Short(vec![bits; samples]));
+        entries.insert(
+            tag::COMPRESSION,
+            Entry::Short(vec![self.options.compression.code()]),
+        );
+        let rgb = samples == 3 || samples == 4;
+        entries.insert(
+            tag::PHOTOMETRIC,
+            Entry::Short(vec![if rgb { 2 } else { 1 }]),
+        );
+        if let Some(description) = description {
+            entries.insert(
+                tag::IMAGE_DESCRIPTION,
+                Entry::Ascii(description.to_string()),
+            );
+        }
+        entries.insert(tag::SAMPLES_PER_PIXEL, Entry::Short(vec![samples as u16]));
+        entries.insert(tag::X_RESOLUTION, Entry::Rational(1, 1));
+        entries.insert(tag::Y_RESOLUTION, Entry::Rational(1, 1));
+        entries.insert(tag::PLANAR_CONFIGURATION, Entry::Short(vec![1]));
+        entries.insert(tag::RESOLUTION_UNIT, Entry::Short(vec![1]));
+        entries.insert(tag::SOFTWARE, Entry::Ascii("scirs2-io".to_string()));
+        if self.options.tile_size.is_some() {
+            entries.insert(tag::TILE_WIDTH, Entry::Long(vec![chunk_width as u32]));
+            entries.insert(tag::TILE_LENGTH, Entry::Long(vec![chunk_height as u32]));
+            entries.insert(tag::TILE_OFFSETS, Entry::Offsets(offsets));
+            entries.insert(tag::TILE_BYTE_COUNTS, Entry::Offsets(byte_counts));
+        } else {
+            entries.insert(tag::ROWS_PER_STRIP, Entry::Long(vec![chunk_height as u32]));
+            entries.insert(tag::STRIP_OFFSETS, Entry::Offsets(offsets));
+            entries.insert(tag::STRIP_BYTE_COUNTS, Entry::Offsets(byte_counts));
+        }
+        let extra_samples = if rgb { samples - 3 } else { samples - 1 };
+        if extra_samples > 0 {
+            // Alpha for RGBA, unspecified for other multi-sample pages
+            let kind = if rgb { 2 } else { 0 };
+            entries.insert(tag::EXTRA_SAMPLES, Entry::Short(vec![kind; extra_samples]));
+        }
+        entries.insert(
+            tag::SAMPLE_FORMAT,
+            Entry::Short(vec![sample_format.code(); samples]),
+        );
+
+        let (ifd_offset, next_field) = write_ifd(&mut self.writer, big, &entries)?;
+        let end = self.writer.stream_position().map_err(io)?;
+        self.writer
+            .seek(SeekFrom::Start(self.next_ifd_field))
+            .map_err(io)?;
+        if big {
+            self.writer
+                .write_all(&ifd_offset.to_le_bytes())
+                .map_err(io)?;
+        } else {
+            offset32(end)?;
+            self.writer
+                .write_all(&(ifd_offset as u32).to_le_bytes())
+                .map_err(io)?;
+        }
+        self.writer.seek(SeekFrom::Start(end)).map_err(io)?;
+        self.next_ifd_field = next_field;
+        Ok(())
+    }
+
+    /// Flush the output and return the underlying writer
+    pub fn finish(mut self) -> Result<W> {
+        self.writer
+            .flush()
+            .map_err(|e| IoError::FileError(e.to_string()))?;
+        Ok(self.writer)
+    }
+}
+
+/// Read all pages of a TIFF file into one array
+///
+/// # Arguments
+///
+/// * `path` - Path to the TIFF file
+///
+/// # Returns
+///
+/// * `Result<TiffImage>` - The stacked pixels with axis labels and OME metadata
+pub fn read_tiff<P: AsRef<Path>>(path: P) -> Result<TiffImage> {
+    TiffFile::open(path)?.read_all()
+}
+
+/// Write an array of rank 2 to 5 as a multi-page TIFF file
+///
+/// The last two axes are the image rows and columns; all leading axes are
+/// written as pages in C order. With `options.ome` set, the array axes are
+/// the reversed OME dimension order (e.g. `TCZYX`), with missing leading axes
+/// of size one, and the sizes and pixel type of the OME-XML are taken from
+/// the array.
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to create
+/// * `data` - Pixel data
+/// * `options` - Compression, layout and metadata options
+///
+/// # Returns
+///
+/// * `Result<()>` - Success or an error
+pub fn write_tiff<P: AsRef<Path>>(
+    path: P,
+    data: &TiffArray,
+    options: &TiffWriteOptions,
+) -> Result<()> {
+    let shape = data.shape();
+    if !(2..=5).contains(&shape.len()) {
+        return Err(IoError::ValidationError(format!(
+            "TIFF stacks must have 2 to 5 dimensions, got {}",
+            shape.len()
+        )));
+    }
+    let description = match &options.ome {
+        Some(ome) => {
+            let mut ome = ome.clone();
+            let mut full_shape = vec![1; 5 - shape.len()];
+            full_shape.extend_from_slice(shape);
+            for (axis, &size) in ome.axes().chars().zip(&full_shape) {
+                match axis {
+                    'X' => ome.size_x = size,
+                    'Y' => ome.size_y = size,
+                    'Z' => ome.size_z = size,
+                    'C' => ome.size_c = size,
+                    _ => ome.size_t = size,
+                }
+            }
+            ome.pixel_type = Some(data.ome_pixel_type().to_string());
+            Some(ome.to_xml())
+        }
+        None => None,
+    };
+    let mut writer = TiffWriter::create(path, options.clone())?;
+    for (i, page) in data.pages()?.iter().enumerate() {
+        let page_description = if i == 0 { description.as_deref() } else { None };
+        writer.write_page(page, page_description)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array;
+    use tempfile::tempdir;
+
+    fn ramp<T>(shape: &[usize], f: impl Fn(usize) -> T) -> ArrayD<T> {
+        let mut i = 0;
+        Array::from_shape_simple_fn(IxDyn(shape), || {
+            i += 1;
+            f(i)
+        })
+    }
+
+    #[test]
+    fn test_layouts_and_types_roundtrip() {
+        let dir = tempdir().unwrap();
+        let arrays = vec![
+            TiffArray::from(ramp(&[37, 53], |i| i as u8)),
+            TiffArray::from(ramp(&[37, 53], |i| i as i16 - 1000)),
+            TiffArray::from(ramp(&[37, 53, 3], |i| i as u16 * 7)),
+            TiffArray::from(ramp(&[20, 21], |i| i as i32 * -3)),
+            TiffArray::from(ramp(&[20, 21], |i| i as f32 * 0.25)),
+            TiffArray::from(ramp(&[20, 21, 2], |i| i as f64 / 3.0)),
+        ];
+        for (n, (compression, bigtiff, tile_size)) in [
+            (TiffCompression::None, false, None),
+            (TiffCompression::Lzw, false, Some((16, 32))),
+            (TiffCompression::Deflate, true, None),
+            (TiffCompression::PackBits, true, Some((32, 16))),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let path = dir.path().join(format!("pages{}.tif", n));
+            let options = TiffWriteOptions {
+                compression,
+                bigtiff,
+                tile_size,
+                rows_per_strip: Some(5),
+                ..Default::default()
+            };
+            let mut writer = TiffWriter::create(&path, options).unwrap();
+            for array in &arrays {
+                writer.write_page(array, Some("page")).unwrap();
+            }
+            writer.finish().unwrap();
+
+            let tiff = TiffFile::open(&path).unwrap();
+            assert_eq!(tiff.is_bigtiff(), bigtiff);
+            assert_eq!(tiff.num_pages(), arrays.len());
+            assert_eq!(tiff.pages()[0].compression, compression);
+            assert_eq!(tiff.pages()[2].tile_size, tile_size);
+            for (i, array) in arrays.iter().enumerate().rev() {
+                assert_eq!(&tiff.read_page(i).unwrap(), array);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stack_axes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stack.tif");
+        let stack = ramp(&[4, 10, 12], |i| i as u16);
+        write_tiff(&path, &TiffArray::from(stack.clone()), &Default::default()).unwrap();
+        let image = read_tiff(&path).unwrap();
+        assert_eq!(image.axes, "IYX");
+        assert_eq!(image.data, TiffArray::UInt16(stack));
+        assert!(image.ome.is_none());
+
+        let path = dir.path().join("imagej.tif");
+        let mut writer = TiffWriter::create(&path, Default::default()).unwrap();
+        let description = "ImageJ=1.53t\nimages=6\nchannels=2\nslices=3\nhyperstack=true\n";
+        for i in 0..6 {
+            let page = TiffArray::from(ramp(&[4, 5], |_| i as f32));
+            writer
+                .write_page(&page, (i == 0).then_some(description))
+                .unwrap();
+        }
+        writer.finish().unwrap();
+        let image = read_tiff(&path).unwrap();
+        assert_eq!(image.axes, "TZCYX");
+        assert_eq!(image.data.shape(), &[1, 3, 2, 4, 5]);
+        assert_eq!(image.data.to_f64()[[0, 2, 1, 0, 0]], 5.0);
+    }
+
+    /// Big-endian classic TIFF directory with single-valued entries
+    /// `(tag, field type, value)` and no next directory
+    fn big_endian_ifd(values: &[(u16, u16, u32)]) -> Vec<u8> {
+        let mut ifd = (values.len() as u16).to_be_bytes().to_vec();
+        for &(code, field_type, value) in values {
+            ifd.extend_from_slice(&code.to_be_bytes());
+            ifd.extend_from_slice(&field_type.to_be_bytes());
+            ifd.extend_from_slice(&1u32.to_be_bytes());
+            if field_type == 3 {
+                ifd.extend_from_slice(&(value as u16).to_be_bytes());
+                ifd.extend_from_slice(&[0, 0]);
+            } else {
+                ifd.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+        ifd.extend_from_slice(&0u32.to_be_bytes());
+        ifd
+    }
+
+    #[test]
+    fn test_big_endian_predictor_and_sub_resolutions() {
+        // Hand-built big-endian file: one 4x2 16-bit page, horizontally
+        // differenced, with a 2x1 reduced-resolution SubIFD
+        let mut file = b"MM\x00\x2a\x00\x00\x00\x08".to_vec();
+        let entries = big_endian_ifd;
+        let main_ifd_size = 2 + 10 * 12 + 4;
+        let sub_ifd = 8 + main_ifd_size as u32;
+        let sub_ifd_size = 2 + 7 * 12 + 4;
+        let data = sub_ifd + sub_ifd_size as u32;
+        file.extend(entries(&[
+            (tag::IMAGE_WIDTH, 3, 4),
+            (tag::IMAGE_LENGTH, 3, 2),
+            (tag::BITS_PER_SAMPLE, 3, 16),
+            (tag::COMPRESSION, 3, 1),
+            (tag::STRIP_OFFSETS, 4, data),
+            (tag::ROWS_PER_STRIP, 3, 2),
+            (tag::STRIP_BYTE_COUNTS, 4, 16),
+            (tag::PREDICTOR, 3, 2),
+            (tag::SUB_IFDS, 4, sub_ifd),
+            (tag::SAMPLE_FORMAT, 3, 1),
+        ]));
+        file.extend(entries(&[
+            (tag::NEW_SUBFILE_TYPE, 4, 1),
+            (tag::IMAGE_WIDTH, 3, 2),
+            (tag::IMAGE_LENGTH, 3, 1),
+            (tag::BITS_PER_SAMPLE, 3, 16),
+            (tag::STRIP_OFFSETS, 4, data + 16),
+            (tag::ROWS_PER_STRIP, 3, 1),
+            (tag::STRIP_BYTE_COUNTS, 4, 4),
+        ]));
+        for value in [10u16, 1, 1, 1, 500, 65535, 2, 3, 7, 9] {
+            file.extend_from_slice(&value.to_be_bytes());
+        }
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("be.tif");
+        std::fs::write(&path, file).unwrap();
+
+        let tiff = TiffFile::open(&path).unwrap();
+        assert_eq!(tiff.pages()[0].sub_resolutions.len(), 1);
+        assert_eq!(
+            tiff.read_page(0).unwrap(),
+            TiffArray::UInt16(
+                ArrayD::from_shape_vec(IxDyn(&[2, 4]), vec![10, 11, 12, 13, 500, 499, 501, 504])
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            tiff.read_level(0, 1).unwrap(),
+            TiffArray::UInt16(ArrayD::from_shape_vec(IxDyn(&[1, 2]), vec![7, 9]).unwrap())
+        );
+        assert!(tiff.read_level(0, 2).is_err());
+    }
+
+    #[test]
+    fn test_malformed_headers() {
+        let dir = tempdir().unwrap();
+        // One directory at offset 8, whose own bytes serve as pixel data
+        let read = |name: &str, values: &[(u16, u16, u32)]| {
+            let mut file = b"MM\x00\x2a\x00\x00\x00\x08".to_vec();
+            file.extend(big_endian_ifd(values));
+            let path = dir.path().join(name);
+            std::fs::write(&path, file).unwrap();
+            TiffFile::open(&path).and_then(|tiff| tiff.read_page(0))
+        };
+        let page = |width: u32, height: u32, bits: u32| {
+            vec![
+                (tag::IMAGE_WIDTH, 4, width),
+                (tag::IMAGE_LENGTH, 4, height),
+                (tag::BITS_PER_SAMPLE, 3, bits),
+                (tag::STRIP_OFFSETS, 4, 8),
+                (tag::STRIP_BYTE_COUNTS, 4, 16),
+            ]
+        };
+        assert!(read("valid.tif", &page(4, 4, 8)).is_ok());
+
+        // Zero dimensions
+        assert!(read("zero_width.tif", &page(0, 4, 8)).is_err());
+        assert!(read("zero_height.tif", &page(4, 0, 8)).is_err());
+        let tiles = |tile_width: u32, tile_length: u32| {
+            vec![
+                (tag::IMAGE_WIDTH, 4, 4),
+                (tag::IMAGE_LENGTH, 4, 4),
+                (tag::BITS_PER_SAMPLE, 3, 8),
+                (tag::TILE_WIDTH, 4, tile_width),
+                (tag::TILE_LENGTH, 4, tile_length),
+                (tag::TILE_OFFSETS, 4, 8),
+                (tag::TILE_BYTE_COUNTS, 4, 16),
+            ]
+        };
+        assert!(read("zero_tile_width.tif", &tiles(0, 4)).is_err());
+        assert!(read("zero_tile_length.tif", &tiles(4, 0)).is_err());
+
+        // Sizes that overflow or exceed what the data can hold
+        assert!(read("overflow.tif", &page(u32::MAX, u32::MAX, 64)).is_err());
+        assert!(read("huge.tif", &page(1 << 16, 1 << 16, 8)).is_err());
+        assert!(read("huge_tile.tif", &tiles(1 << 30, 1 << 30)).is_err());
+        let mut counts = page(4, 4, 8);
+        counts[4] = (tag::STRIP_BYTE_COUNTS, 4, u32::MAX);
+        assert!(read("long_strip.tif", &counts).is_err());
+
+        // A SubIFD that points to its own directory
+        let mut looped = page(4, 4, 8);
+        looped.push((tag::SUB_IFDS, 4, 8));
+        assert!(read("subifd_loop.tif", &looped).is_err());
+    }
+}
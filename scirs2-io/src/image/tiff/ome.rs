@@ -0,0 +1,269 @@
+//! OME-XML metadata of OME-TIFF files
+
+use regex::Regex;
+
+use crate::error::{IoError, Result};
+
+/// Image metadata from the OME-XML block of an OME-TIFF file
+///
+/// Only the first `Image` element is interpreted. Array axes follow the
+/// reversed `dimension_order`, e.g. `TCZYX` for the default `XYZCT`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OmeMetadata {
+    /// Image name
+    pub name: Option<String>,
+    /// Order of the dimensions from fastest to slowest varying, e.g. `XYZCT`
+    pub dimension_order: String,
+    /// Image width in pixels
+    pub size_x: usize,
+    /// Image height in pixels
+    pub size_y: usize,
+    /// Number of focal planes
+    pub size_z: usize,
+    /// Number of channels
+    pub size_c: usize,
+    /// Number of time points
+    pub size_t: usize,
+    /// OME pixel type, e.g. `uint16` or `float`
+    pub pixel_type: Option<String>,
+    /// Physical pixel width
+    pub physical_size_x: Option<f64>,
+    /// Physical pixel height
+    pub physical_size_y: Option<f64>,
+    /// Physical distance between focal planes
+    pub physical_size_z: Option<f64>,
+    /// Unit of the physical sizes (micrometers if not given)
+    pub physical_size_unit: Option<String>,
+    /// Time between time points in seconds
+    pub time_increment: Option<f64>,
+    /// Channel names, empty strings for unnamed channels
+    pub channel_names: Vec<String>,
+}
+
+impl Default for OmeMetadata {
+    fn default() -> Self {
+        Self {
+            name: None,
+            dimension_order: "XYZCT".to_string(),
+            size_x: 1,
+            size_y: 1,
+            size_z: 1,
+            size_c: 1,
+            size_t: 1,
+            pixel_type: None,
+            physical_size_x: None,
+            physical_size_y: None,
+            physical_size_z: None,
+            physical_size_unit: None,
+            time_increment: None,
+            channel_names: Vec::new(),
+        }
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Attributes of the first element with the given name in `xml`
+///
+/// Returns the attributes and the byte offset just past the start tag.
+fn element(xml: &str, name: &str) -> Option<(Vec<(String, String)>, usize)> {
+    let tag = Regex::new(&format!(r"<(?:\w+:)?{}[\s/>]", name)).unwrap();
+    let start = tag.find(xml)?.start();
+    let end = start + xml[start..].find('>')? + 1;
+    let attribute = Regex::new(r#"([\w:]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    let attributes = attribute
+        .captures_iter(&xml[start..end])
+        .map(|c| {
+            let value = c.get(2).or_else(|| c.get(3)).unwrap().as_str();
+            (c[1].to_string(), unescape(value))
+        })
+        .collect();
+    Some((attributes, end))
+}
+
+fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+impl OmeMetadata {
+    /// Parse an OME-XML document
+    pub fn parse(xml: &str) -> Result<Self> {
+        let invalid = |what: &str| IoError::FormatError(format!("Invalid OME-XML: {}", what));
+        let (image, image_end) = element(xml, "Image").ok_or_else(|| invalid("no Image"))?;
+        let (pixels, pixels_end) =
+            element(&xml[image_end..], "Pixels").ok_or_else(|| invalid("no Pixels"))?;
+        let size = |name: &str| -> Result<usize> {
+            attribute(&pixels, name)
+                .map_or(Ok(1), |v| v.trim().parse())
+                .map_err(|_| invalid(name))
+        };
+        let float = |name: &str| attribute(&pixels, name).and_then(|v| v.trim().parse().ok());
+
+        let mut channel_names = Vec::new();
+        let mut rest = &xml[image_end + pixels_end..];
+        let pixels_close = rest.find("Pixels>").unwrap_or(rest.len());
+        rest = &rest[..pixels_close];
+        while let Some((channel, end)) = element(rest, "Channel") {
+            channel_names.push(attribute(&channel, "Name").unwrap_or("").to_string());
+            rest = &rest[end..];
+        }
+
+        let dimension_order = attribute(&pixels, "DimensionOrder")
+            .unwrap_or("XYZCT")
+            .to_ascii_uppercase();
+        let mut sorted: Vec<char> = dimension_order.chars().collect();
+        sorted.sort_unstable();
+        if sorted != ['C', 'T', 'X', 'Y', 'Z'] || !dimension_order.starts_with("XY") {
+            return Err(invalid("DimensionOrder"));
+        }
+        Ok(Self {
+            name: attribute(&image, "Name").map(str::to_string),
+            dimension_order,
+            size_x: size("SizeX")?,
+            size_y: size("SizeY")?,
+            size_z: size("SizeZ")?,
+            size_c: size("SizeC")?,
+            size_t: size("SizeT")?,
+            pixel_type: attribute(&pixels, "Type").map(str::to_string),
+            physical_size_x: float("PhysicalSizeX"),
+            physical_size_y: float("PhysicalSizeY"),
+            physical_size_z: float("PhysicalSizeZ"),
+            physical_size_unit: attribute(&pixels, "PhysicalSizeXUnit").map(str::to_string),
+            time_increment: float("TimeIncrement"),
+            channel_names,
+        })
+    }
+
+    /// Array axes in ndarray order, the reversed dimension order
+    pub fn axes(&self) -> String {
+        self.dimension_order.chars().rev().collect()
+    }
+
+    /// Size of the axis with the given letter
+    pub fn size(&self, axis: char) -> usize {
+        match axis {
+            'X' => self.size_x,
+            'Y' => self.size_y,
+            'Z' => self.size_z,
+            'C' => self.size_c,
+            'T' => self.size_t,
+            _ => 1,
+        }
+    }
+
+    /// Number of image planes, one per TIFF page
+    pub fn num_planes(&self) -> usize {
+        self.size_z * self.size_c * self.size_t
+    }
+
+    /// Serialize as an OME-XML document
+    pub fn to_xml(&self) -> String {
+        let mut pixels = format!(
+            r#"<Pixels ID="Pixels:0" DimensionOrder="{}" Type="{}" SizeX="{}" SizeY="{}" SizeZ="{}" SizeC="{}" SizeT="{}""#,
+            escape(&self.dimension_order),
+            escape(self.pixel_type.as_deref().unwrap_or("uint8")),
+            self.size_x,
+            self.size_y,
+            self.size_z,
+            self.size_c,
+            self.size_t
+        );
+        for (name, value) in [
+            ("PhysicalSizeX", self.physical_size_x),
+            ("PhysicalSizeY", self.physical_size_y),
+            ("PhysicalSizeZ", self.physical_size_z),
+        ] {
+            if let Some(value) = value {
+                pixels.push_str(&format!(r#" {}="{}""#, name, value));
+                if let Some(unit) = &self.physical_size_unit {
+                    pixels.push_str(&format!(r#" {}Unit="{}""#, name, escape(unit)));
+                }
+            }
+        }
+        if let Some(increment) = self.time_increment {
+            pixels.push_str(&format!(r#" TimeIncrement="{}""#, increment));
+        }
+        pixels.push('>');
+        for c in 0..self.size_c {
+            pixels.push_str(&format!(r#"<Channel ID="Channel:0:{}""#, c));
+            if let Some(name) = self.channel_names.get(c).filter(|n| !n.is_empty()) {
+                pixels.push_str(&format!(r#" Name="{}""#, escape(name)));
+            }
+            pixels.push_str(r#" SamplesPerPixel="1"/>"#);
+        }
+        pixels.push_str(&format!(
+            r#"<TiffData PlaneCount="{}"/></Pixels>"#,
+            self.num_planes()
+        ));
+
+        let name = self
+            .name
+            .as_ref()
+            .map(|n| format!(r#" Name="{}""#, escape(n)))
+            .unwrap_or_default();
+        format!(
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<OME xmlns="http://www.openmicroscopy.org/Schemas/OME/2016-06" "#,
+                r#"xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" "#,
+                r#"xsi:schemaLocation="http://www.openmicroscopy.org/Schemas/OME/2016-06 "#,
+                r#"http://www.openmicroscopy.org/Schemas/OME/2016-06/ome.xsd">"#,
+                r#"<Image ID="Image:0"{}>{}</Image></OME>"#
+            ),
+            name, pixels
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ome_xml() {
+        let xml = r#"<?xml version="1.0"?>
+<OME xmlns="http://www.openmicroscopy.org/Schemas/OME/2016-06">
+  <Instrument ID="Instrument:0"/>
+  <Image ID="Image:0" Name="cells &amp; nuclei">
+    <Pixels ID="Pixels:0" DimensionOrder="XYCZT" Type="uint16" SizeX="512" SizeY="256"
+            SizeZ="5" SizeC="2" SizeT="1" PhysicalSizeX="0.65" PhysicalSizeXUnit="µm"
+            PhysicalSizeY="0.65" PhysicalSizeZ="2.0">
+      <Channel ID="Channel:0:0" Name="DAPI" SamplesPerPixel="1"/>
+      <Channel ID="Channel:0:1" SamplesPerPixel="1"></Channel>
+      <TiffData/>
+    </Pixels>
+  </Image>
+  <Image ID="Image:1"><Pixels DimensionOrder="XYZCT" SizeX="1" SizeY="1"><Channel Name="other"/></Pixels></Image>
+</OME>"#;
+        let ome = OmeMetadata::parse(xml).unwrap();
+        assert_eq!(ome.name.as_deref(), Some("cells & nuclei"));
+        assert_eq!(ome.axes(), "TZCYX");
+        assert_eq!(
+            (ome.size_x, ome.size_y, ome.size_z, ome.size_c),
+            (512, 256, 5, 2)
+        );
+        assert_eq!(ome.physical_size_x, Some(0.65));
+        assert_eq!(ome.physical_size_z, Some(2.0));
+        assert_eq!(ome.physical_size_unit.as_deref(), Some("µm"));
+        assert_eq!(ome.channel_names, vec!["DAPI".to_string(), String::new()]);
+        assert_eq!(ome.num_planes(), 10);
+
+        assert_eq!(OmeMetadata::parse(&ome.to_xml()).unwrap(), ome);
+    }
+}
@@ -14,12 +14,16 @@
 //! ## Modules
 //!
 //! - `arff`: Support for ARFF (Attribute-Relation File Format) files
+//! - `arrow`: Support for Apache Arrow IPC files and streams
+//! - `columnar`: In-memory columnar tables shared by the Parquet and Arrow modules
 //! - `compression`: Utilities for data compression and decompression
 //! - `csv`: Support for CSV (Comma-Separated Values) files
+//! - `fits`: Support for FITS astronomical image and table files
 //! - `image`: Support for image file formats (PNG, JPEG, BMP, TIFF)
 //! - `matlab`: Support for MATLAB (.mat) files
 //! - `matrix_market`: Support for Matrix Market sparse and dense matrix files
 //! - `netcdf`: Support for NetCDF scientific data files
+//! - `parquet`: Support for Apache Parquet files
 //! - `serialize`: Utilities for data serialization and deserialization
 //! - `validation`: Utilities for data validation and integrity checking
 //! - `wavfile`: Support for WAV audio files
@@ -32,10 +36,25 @@
 #![allow(clippy::type_complexity)] // Complex type is necessary for format validators
 
 pub mod arff;
+/// Apache Arrow IPC format module
+///
+/// Provides functionality for reading and writing Arrow IPC data:
+/// - File (random access) and stream formats
+/// - Column projection and batch-by-batch reading
+/// - Dictionary-encoded columns and validity bitmaps
+/// - LZ4 and Zstd buffer compression
+pub mod arrow;
+/// Columnar table module
+///
+/// Provides the in-memory table used by the Parquet and Arrow modules:
+/// - Typed columns with optional validity masks
+/// - Conversion of columns to and from ndarray arrays
+/// - Conversion of tables to and from `scirs2_core::array::RecordArray`
+pub mod columnar;
 /// Data compression module
 ///
 /// Provides utilities for compressing and decompressing scientific data:
-/// - Lossless compression algorithms (GZIP, ZSTD, LZ4, BZIP2)
+/// - Lossless compression algorithms (GZIP, ZSTD, LZ4, BZIP2, Snappy)
 /// - Array compression with metadata preservation
 /// - Chunked compression for large datasets
 /// - Compression level configuration
@@ -49,8 +68,17 @@ pub mod compression;
 /// - Memory-efficient processing of large files using chunked reading
 /// - Support for specialized data types (date, time, complex numbers)
 /// - Column-based operations with flexible configuration
+/// - Streaming, chunked reading with type inference and incremental writing
 pub mod csv;
 pub mod error;
+/// FITS file format module
+///
+/// Provides functionality for reading and writing FITS (Flexible Image Transport System) files:
+/// - Primary HDUs and image extensions with all BITPIX types and BSCALE/BZERO scaling
+/// - ASCII and binary table extensions read into column arrays
+/// - Typed header keywords and image metadata
+/// - Writing images and binary tables
+pub mod fits;
 /// HDF5 file format module
 ///
 /// Provides functionality for reading and writing HDF5 (Hierarchical Data Format) files:
@@ -86,6 +114,14 @@ pub mod matrix_market;
 /// - Conversion between NetCDF and ndarray data structures
 /// - Memory-efficient access to large datasets
 pub mod netcdf;
+/// Apache Parquet file format module
+///
+/// Provides functionality for reading and writing Parquet files:
+/// - Reading whole files, selected columns, or one row group at a time
+/// - PLAIN, dictionary and RLE encodings
+/// - Snappy, Gzip and Zstd page compression
+/// - Incremental writing with configurable row groups
+pub mod parquet;
 /// Data serialization utilities
 ///
 /// Provides functionality for serializing and deserializing scientific data:
@@ -93,6 +129,7 @@ pub mod netcdf;
 /// - Array serialization with metadata
 /// - Structured data serialization
 /// - Sparse matrix serialization
+/// - NumPy `.npy` and `.npz` files
 pub mod serialize;
 /// Data validation and integrity checking module
 ///
@@ -1,13 +1,27 @@
 //! MATLAB file format (.mat) handling module
 //!
 //! This module provides functionality for reading and writing MATLAB .mat files.
-//! Currently supporting MATLAB v5 format (Level 5 MAT-File).
-
-use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
-use ndarray::{Array, ArrayD, IxDyn};
+//! MATLAB v5 files (Level 5 MAT-File, also used by v6 and v7) are read and
+//! written, including zlib-compressed (`miCOMPRESSED`) data elements. MATLAB
+//! v7.3 files, which are HDF5 files with a MATLAB header, can be read through
+//! the same [`read_mat`] entry point.
+//!
+//! Arrays keep their MATLAB shape: a 3x2 MATLAB matrix is read as an array of
+//! shape `[3, 2]`. The conversion between MATLAB's column-major storage and
+//! ndarray is handled internally. MATLAB has no one-dimensional arrays, so
+//! arrays with fewer than two dimensions are written as row vectors.
+
+mod v73;
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use ndarray::{Array, ArrayD, IxDyn, ShapeBuilder};
+use num_traits::NumCast;
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufWriter, Read, Write};
 use std::path::Path;
 
 use crate::error::{IoError, Result};
@@ -43,122 +57,134 @@ pub enum MatType {
     Cell(Vec<MatType>),
     /// Structure
     Struct(HashMap<String, MatType>),
+    /// Sparse matrix
+    Sparse(SparseMatrix),
+}
+
+/// Sparse matrix in MATLAB's compressed sparse column layout
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix {
+    /// Number of rows
+    pub rows: usize,
+    /// Number of columns
+    pub cols: usize,
+    /// Row index of each stored value
+    pub row_indices: Vec<usize>,
+    /// Start of each column in `row_indices` and `values` (length `cols + 1`)
+    pub col_ptrs: Vec<usize>,
+    /// Stored values
+    pub values: Vec<f64>,
+    /// Whether the matrix is a logical sparse matrix
+    pub logical: bool,
 }
 
 /// MATLAB header information
 #[derive(Debug, Clone)]
 struct MatHeader {
     /// Version of the MAT file
-    _version: u16,
-    /// Endianness indicator
-    endian_indicator: u16,
+    version: u16,
+    /// Whether the file was written big-endian
+    big_endian: bool,
 }
 
+/// Size of the MAT file header in bytes
+const HEADER_SIZE: usize = 128;
+
 // MATLAB data type identifiers (miTYPE values)
-const MI_INT8: i32 = 1;
-const _MI_UINT8: i32 = 2;
-const _MI_INT16: i32 = 3;
-const _MI_UINT16: i32 = 4;
-const MI_INT32: i32 = 5;
-const MI_UINT32: i32 = 6;
-const _MI_SINGLE: i32 = 7;
-const _MI_DOUBLE: i32 = 9;
-const _MI_INT64: i32 = 12;
-const _MI_UINT64: i32 = 13;
-const MI_MATRIX: i32 = 14;
-const _MI_COMPRESSED: i32 = 15;
-const MI_UTF8: i32 = 16;
-const _MI_UTF16: i32 = 17;
-const _MI_UTF32: i32 = 18;
+const MI_INT8: u32 = 1;
+const MI_UINT8: u32 = 2;
+const MI_INT16: u32 = 3;
+const MI_UINT16: u32 = 4;
+const MI_INT32: u32 = 5;
+const MI_UINT32: u32 = 6;
+const MI_SINGLE: u32 = 7;
+const MI_DOUBLE: u32 = 9;
+const MI_INT64: u32 = 12;
+const MI_UINT64: u32 = 13;
+const MI_MATRIX: u32 = 14;
+const MI_COMPRESSED: u32 = 15;
+const MI_UTF8: u32 = 16;
+const MI_UTF16: u32 = 17;
+const MI_UTF32: u32 = 18;
 
 // MATLAB array type values (mxCLASS values)
-const _MX_CELL_CLASS: i32 = 1;
-const _MX_STRUCT_CLASS: i32 = 2;
-const _MX_OBJECT_CLASS: i32 = 3;
-const MX_CHAR_CLASS: i32 = 4;
-const _MX_SPARSE_CLASS: i32 = 5;
-const MX_DOUBLE_CLASS: i32 = 6;
-const MX_SINGLE_CLASS: i32 = 7;
-const MX_INT8_CLASS: i32 = 8;
-const MX_UINT8_CLASS: i32 = 9;
-const MX_INT16_CLASS: i32 = 10;
-const MX_UINT16_CLASS: i32 = 11;
-const MX_INT32_CLASS: i32 = 12;
-const MX_UINT32_CLASS: i32 = 13;
-const MX_INT64_CLASS: i32 = 14;
-const MX_UINT64_CLASS: i32 = 15;
+const MX_CELL_CLASS: u32 = 1;
+const MX_STRUCT_CLASS: u32 = 2;
+const MX_OBJECT_CLASS: u32 = 3;
+const MX_CHAR_CLASS: u32 = 4;
+const MX_SPARSE_CLASS: u32 = 5;
+const MX_DOUBLE_CLASS: u32 = 6;
+const MX_SINGLE_CLASS: u32 = 7;
+const MX_INT8_CLASS: u32 = 8;
+const MX_UINT8_CLASS: u32 = 9;
+const MX_INT16_CLASS: u32 = 10;
+const MX_UINT16_CLASS: u32 = 11;
+const MX_INT32_CLASS: u32 = 12;
+const MX_UINT32_CLASS: u32 = 13;
+const MX_INT64_CLASS: u32 = 14;
+const MX_UINT64_CLASS: u32 = 15;
+
+/// Longest struct field name accepted by MATLAB
+const MAX_FIELD_NAME: usize = 63;
 
 /// Matrix flags for MATLAB data
 #[derive(Debug, Clone)]
 struct MatrixFlags {
     /// Class type (double, single, etc.)
-    class_type: i32,
+    class_type: u32,
     /// Whether the matrix is complex
     is_complex: bool,
     /// Whether the matrix is a global variable
-    _is_global: bool,
+    is_global: bool,
     /// Whether the matrix is logical
-    _is_logical: bool,
+    is_logical: bool,
 }
 
 impl MatrixFlags {
+    /// Flags of a real, non-global array of the given class
+    fn new(class_type: u32) -> Self {
+        MatrixFlags {
+            class_type,
+            is_complex: false,
+            is_global: false,
+            is_logical: false,
+        }
+    }
+
     /// Parse matrix flags from a u32
     fn from_u32(flags: u32) -> Self {
-        let class_type = (flags & 0xFF) as i32;
-        let is_complex = (flags & 0x800) != 0;
-        let _is_global = (flags & 0x400) != 0;
-        let _is_logical = (flags & 0x200) != 0;
-
         MatrixFlags {
-            class_type,
-            is_complex,
-            _is_global,
-            _is_logical,
+            class_type: flags & 0xFF,
+            is_complex: (flags & 0x800) != 0,
+            is_global: (flags & 0x400) != 0,
+            is_logical: (flags & 0x200) != 0,
         }
     }
 
     /// Convert to a u32 for writing
-    fn _to_u32(&self) -> u32 {
-        let mut flags = self.class_type as u32;
+    fn to_u32(&self) -> u32 {
+        let mut flags = self.class_type;
         if self.is_complex {
             flags |= 0x800;
         }
-        if self._is_global {
+        if self.is_global {
             flags |= 0x400;
         }
-        if self._is_logical {
+        if self.is_logical {
             flags |= 0x200;
         }
         flags
     }
 }
 
-/// Data element for MATLAB file
-#[derive(Debug, Clone)]
-struct _DataElement {
-    /// Data type
-    data_type: i32,
-    /// Data (as bytes)
-    data: Vec<u8>,
-}
-
-/// Matrix array for MATLAB file
-#[derive(Debug, Clone)]
-struct _MatrixArray {
-    /// Matrix flags
-    flags: MatrixFlags,
-    /// Array dimensions
-    dims: Vec<i32>,
-    /// Array name
-    name: String,
-    /// Real data
-    real_data: Vec<u8>,
-    /// Imaginary data (if complex)
-    imag_data: Option<Vec<u8>>,
-}
-
 /// Reads a MATLAB .mat file
 ///
+/// Both v5 (including compressed v7) files and HDF5-based v7.3 files are
+/// supported. Variables of classes that cannot be represented by [`MatType`]
+/// (such as function handles) are skipped. Struct arrays with more than one
+/// element are returned as a [`MatType::Cell`] of [`MatType::Struct`] values.
+/// The imaginary part of complex arrays is dropped.
+///
 /// # Arguments
 ///
 /// * `path` - Path to the .mat file
@@ -179,370 +205,496 @@ struct _MatrixArray {
 /// }
 /// ```
 pub fn read_mat<P: AsRef<Path>>(path: P) -> Result<HashMap<String, MatType>> {
-    let file = File::open(path).map_err(|e| IoError::FileError(e.to_string()))?;
-    let mut reader = BufReader::new(file);
-
-    // Read the MAT file header (116 bytes)
-    let mut header_bytes = [0u8; 116];
-    reader
-        .read_exact(&mut header_bytes)
-        .map_err(|e| IoError::FileError(format!("Failed to read MAT header: {}", e)))?;
-
-    // Check magic string "MATLAB"
-    let magic = std::str::from_utf8(&header_bytes[0..6])
-        .map_err(|_| IoError::FormatError("Invalid MAT file header".to_string()))?;
-
-    if magic != "MATLAB" {
-        return Err(IoError::FormatError("Not a valid MATLAB file".to_string()));
+    let mut file = File::open(path.as_ref()).map_err(|e| IoError::FileError(e.to_string()))?;
+    let mut data = Vec::new();
+    file.read_to_end(&mut data)
+        .map_err(|e| IoError::FileError(format!("Failed to read MAT file: {}", e)))?;
+
+    let header = parse_header(&data)?;
+    if header.version == 0x0200 {
+        return v73::read_mat_v73(path.as_ref());
     }
 
-    // Parse version and endianness
-    let subsystem_data_offset = &header_bytes[108..116];
-    let version = LittleEndian::read_u16(&subsystem_data_offset[0..2]);
-    let endian_indicator = LittleEndian::read_u16(&subsystem_data_offset[2..4]);
-
-    let header = MatHeader {
-        _version: version,
-        endian_indicator,
-    };
-
-    // Check endianness indicator
-    if header.endian_indicator != 0x4D49 && header.endian_indicator != 0x494D {
-        return Err(IoError::FormatError(
-            "Invalid endianness indicator".to_string(),
-        ));
-    }
-
-    // Read data elements
+    let mut reader = ElementReader::new(&data[HEADER_SIZE..], header.big_endian);
     let mut variables = HashMap::<String, MatType>::new();
-
-    // Read data elements until EOF
-    while let Ok(element_type) = read_i32(&mut reader) {
-        // Check if we've reached EOF
-        if element_type == 0 {
-            break;
-        }
-
-        // Read element size
-        let element_size = read_i32(&mut reader)?;
-
-        // Handle different data types
+    while let Some((element_type, element)) = reader.next_element()? {
         match element_type {
             MI_MATRIX => {
-                // Read matrix data
-                let mut matrix_data = vec![0u8; element_size as usize];
-                reader.read_exact(&mut matrix_data).map_err(|e| {
-                    IoError::FileError(format!("Failed to read matrix data: {}", e))
-                })?;
-
-                // Parse matrix data
-                if let Ok((name, mat_type)) = parse_matrix_data(&matrix_data) {
-                    variables.insert(name, mat_type);
+                if let Some((name, value)) = parse_matrix(element, header.big_endian)? {
+                    variables.insert(name, value);
                 }
             }
-            _ => {
-                // Skip unknown element types
-                reader
-                    .by_ref()
-                    .take(element_size as u64)
-                    .read_to_end(&mut vec![])
-                    .map_err(|e| IoError::FileError(format!("Failed to skip element: {}", e)))?;
+            MI_COMPRESSED => {
+                let mut inflated = Vec::new();
+                ZlibDecoder::new(element)
+                    .read_to_end(&mut inflated)
+                    .map_err(|e| {
+                        IoError::DecompressionError(format!(
+                            "Failed to inflate MAT data element: {}",
+                            e
+                        ))
+                    })?;
+                let mut inner = ElementReader::new(&inflated, header.big_endian);
+                while let Some((inner_type, inner_element)) = inner.next_element()? {
+                    if inner_type == MI_MATRIX {
+                        if let Some((name, value)) = parse_matrix(inner_element, header.big_endian)?
+                        {
+                            variables.insert(name, value);
+                        }
+                    }
+                }
             }
+            // Skip unknown element types
+            _ => {}
         }
     }
 
     Ok(variables)
 }
 
-/// Parse matrix data from byte array
-fn parse_matrix_data(data: &[u8]) -> Result<(String, MatType)> {
-    let mut cursor = 0;
-
-    // Read array flags
-    let array_flags_type = LittleEndian::read_i32(&data[cursor..cursor + 4]);
-    cursor += 4;
-
-    let array_flags_size = LittleEndian::read_i32(&data[cursor..cursor + 4]);
-    cursor += 4;
+/// Parse the 128-byte MAT file header
+fn parse_header(data: &[u8]) -> Result<MatHeader> {
+    if data.len() < HEADER_SIZE {
+        return Err(IoError::FormatError(
+            "File is too short to be a MAT file".to_string(),
+        ));
+    }
 
-    if array_flags_type != MI_UINT32 || array_flags_size != 8 {
-        return Err(IoError::FormatError("Invalid array flags".to_string()));
+    // Check magic string "MATLAB"
+    if &data[0..6] != b"MATLAB" {
+        return Err(IoError::FormatError("Not a valid MATLAB file".to_string()));
     }
 
-    let flags = MatrixFlags::from_u32(LittleEndian::read_u32(&data[cursor..cursor + 4]));
-    cursor += 8; // Skip flags (4 bytes) and reserved (4 bytes)
+    // The endian indicator reads "IM" when the file was written little-endian
+    let big_endian = match &data[126..128] {
+        b"IM" => false,
+        b"MI" => true,
+        _ => {
+            return Err(IoError::FormatError(
+                "Invalid endianness indicator".to_string(),
+            ))
+        }
+    };
+    let version = if big_endian {
+        BigEndian::read_u16(&data[124..126])
+    } else {
+        LittleEndian::read_u16(&data[124..126])
+    };
 
-    // Read dimensions
-    let dimensions_type = LittleEndian::read_i32(&data[cursor..cursor + 4]);
-    cursor += 4;
+    Ok(MatHeader {
+        version,
+        big_endian,
+    })
+}
 
-    let dimensions_size = LittleEndian::read_i32(&data[cursor..cursor + 4]);
-    cursor += 4;
+/// Sequential reader over the data elements of a MAT file
+struct ElementReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
 
-    if dimensions_type != MI_INT32 {
-        return Err(IoError::FormatError("Invalid dimensions type".to_string()));
+impl<'a> ElementReader<'a> {
+    fn new(buf: &'a [u8], big_endian: bool) -> Self {
+        Self {
+            buf,
+            pos: 0,
+            big_endian,
+        }
     }
 
-    let num_dims = dimensions_size / 4;
-    let mut dims = Vec::with_capacity(num_dims as usize);
-    for i in 0..num_dims {
-        dims.push(LittleEndian::read_i32(
-            &data[cursor + (i * 4) as usize..cursor + ((i + 1) * 4) as usize],
-        ));
+    /// Number of unread bytes
+    fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
     }
-    cursor += dimensions_size as usize;
 
-    // Pad to 8-byte boundary
-    if cursor % 8 != 0 {
-        cursor += 8 - (cursor % 8);
+    fn u32_at(&self, pos: usize) -> u32 {
+        let bytes = &self.buf[pos..pos + 4];
+        if self.big_endian {
+            BigEndian::read_u32(bytes)
+        } else {
+            LittleEndian::read_u32(bytes)
+        }
     }
 
-    // Read array name
-    let name_type = LittleEndian::read_i32(&data[cursor..cursor + 4]);
-    cursor += 4;
+    /// Read the next data element, returning its type and payload
+    fn next_element(&mut self) -> Result<Option<(u32, &'a [u8])>> {
+        if self.buf.len() - self.pos < 8 {
+            return Ok(None);
+        }
+        let tag = self.u32_at(self.pos);
+
+        // Small data element format: type and size share the first four bytes
+        let (small_size, small_type) = (tag >> 16, tag & 0xFFFF);
+        if small_size != 0 {
+            if small_size > 4 {
+                return Err(IoError::FormatError(
+                    "Invalid small data element size".to_string(),
+                ));
+            }
+            let start = self.pos + 4;
+            self.pos += 8;
+            return Ok(Some((
+                small_type,
+                &self.buf[start..start + small_size as usize],
+            )));
+        }
 
-    let name_size = LittleEndian::read_i32(&data[cursor..cursor + 4]);
-    cursor += 4;
+        let element_type = tag;
+        let size = self.u32_at(self.pos + 4) as usize;
+        let start = self.pos + 8;
+        if size > self.buf.len() - start {
+            return Err(IoError::FormatError(
+                "Data element extends beyond the end of the file".to_string(),
+            ));
+        }
+        // Elements are padded to 8 bytes, except compressed ones
+        let padded = if element_type == MI_COMPRESSED {
+            size
+        } else {
+            (size + 7) & !7
+        };
+        self.pos = (start + padded).min(self.buf.len());
+        Ok(Some((element_type, &self.buf[start..start + size])))
+    }
 
-    if name_type != MI_INT8 && name_type != MI_UTF8 {
-        return Err(IoError::FormatError("Invalid name type".to_string()));
+    /// Read the next data element, failing if there is none
+    fn expect_element(&mut self, what: &str) -> Result<(u32, &'a [u8])> {
+        self.next_element()?
+            .ok_or_else(|| IoError::FormatError(format!("Missing {} in MAT array", what)))
     }
+}
 
-    let name = std::str::from_utf8(&data[cursor..cursor + name_size as usize])
-        .map_err(|_| IoError::FormatError("Invalid name encoding".to_string()))?
-        .to_string();
+/// Decode a numeric data element into values of type `T`
+fn decode_values<T: NumCast>(element_type: u32, data: &[u8], big_endian: bool) -> Result<Vec<T>> {
+    fn collect<T: NumCast, V: num_traits::ToPrimitive + Copy>(
+        data: &[u8],
+        size: usize,
+        read: impl Fn(&[u8]) -> V,
+    ) -> Result<Vec<T>> {
+        data.chunks_exact(size)
+            .map(|chunk| {
+                T::from(read(chunk)).ok_or_else(|| {
+                    IoError::FormatError("MAT value out of range for its class".to_string())
+                })
+            })
+            .collect()
+    }
 
-    cursor += name_size as usize;
+    macro_rules! decode {
+        ($size:expr, $method:ident) => {
+            if big_endian {
+                collect(data, $size, BigEndian::$method)
+            } else {
+                collect(data, $size, LittleEndian::$method)
+            }
+        };
+    }
 
-    // Pad to 8-byte boundary
-    if cursor % 8 != 0 {
-        cursor += 8 - (cursor % 8);
+    match element_type {
+        MI_INT8 => collect(data, 1, |b| b[0] as i8),
+        MI_UINT8 | MI_UTF8 => collect(data, 1, |b| b[0]),
+        MI_INT16 => decode!(2, read_i16),
+        MI_UINT16 | MI_UTF16 => decode!(2, read_u16),
+        MI_INT32 => decode!(4, read_i32),
+        MI_UINT32 | MI_UTF32 => decode!(4, read_u32),
+        MI_SINGLE => decode!(4, read_f32),
+        MI_DOUBLE => decode!(8, read_f64),
+        MI_INT64 => decode!(8, read_i64),
+        MI_UINT64 => decode!(8, read_u64),
+        other => Err(IoError::FormatError(format!(
+            "Unsupported MAT data element type {}",
+            other
+        ))),
     }
+}
 
-    // Read data
-    let data_type = LittleEndian::read_i32(&data[cursor..cursor + 4]);
-    cursor += 4;
+/// Build an array from values stored in MATLAB's column-major order
+fn column_major_array<T>(dims: &[usize], values: Vec<T>) -> Result<ArrayD<T>> {
+    Array::from_shape_vec(IxDyn(dims).f(), values)
+        .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))
+}
 
-    let data_size = LittleEndian::read_i32(&data[cursor..cursor + 4]);
-    cursor += 4;
+/// Parse the payload of an miMATRIX element
+///
+/// Returns `None` for arrays of classes that have no `MatType` counterpart.
+fn parse_matrix(data: &[u8], big_endian: bool) -> Result<Option<(String, MatType)>> {
+    // Empty cell entries may be stored as miMATRIX elements without content
+    if data.is_empty() {
+        return Ok(Some((
+            String::new(),
+            MatType::Double(ArrayD::zeros(IxDyn(&[0, 0]))),
+        )));
+    }
 
-    let real_data = &data[cursor..cursor + data_size as usize];
-    cursor += data_size as usize;
+    let mut reader = ElementReader::new(data, big_endian);
 
-    // Pad to 8-byte boundary
-    if cursor % 8 != 0 {
-        cursor += 8 - (cursor % 8);
+    // Read array flags
+    let (flags_type, flags_data) = reader.expect_element("array flags")?;
+    if flags_type != MI_UINT32 || flags_data.len() != 8 {
+        return Err(IoError::FormatError("Invalid array flags".to_string()));
     }
+    let flags = MatrixFlags::from_u32(reader_u32(flags_data, big_endian));
 
-    // Read imaginary part if complex
-    let _imag_data = if flags.is_complex {
-        let imag_type = LittleEndian::read_i32(&data[cursor..cursor + 4]);
-        cursor += 4;
+    // Read dimensions
+    let (dims_type, dims_data) = reader.expect_element("dimensions")?;
+    if dims_type != MI_INT32 {
+        return Err(IoError::FormatError("Invalid dimensions type".to_string()));
+    }
+    let dims: Vec<usize> = decode_values::<i32>(dims_type, dims_data, big_endian)?
+        .into_iter()
+        .map(|d| d.max(0) as usize)
+        .collect();
+    let numel = dims
+        .iter()
+        .try_fold(1usize, |acc, &d| acc.checked_mul(d))
+        .ok_or_else(|| IoError::FormatError("MAT array has too many elements".to_string()))?;
 
-        let imag_size = LittleEndian::read_i32(&data[cursor..cursor + 4]);
-        cursor += 4;
+    // Read array name
+    let (name_type, name_data) = reader.expect_element("array name")?;
+    if name_type != MI_INT8 && name_type != MI_UTF8 {
+        return Err(IoError::FormatError("Invalid name type".to_string()));
+    }
+    let name = std::str::from_utf8(name_data)
+        .map_err(|_| IoError::FormatError("Invalid name encoding".to_string()))?
+        .to_string();
 
-        if imag_type != data_type {
-            return Err(IoError::FormatError(
-                "Mismatched imaginary type".to_string(),
-            ));
+    let value = match flags.class_type {
+        MX_CELL_CLASS => {
+            // Every cell is a nested element of at least eight bytes
+            if numel > reader.remaining() / 8 {
+                return Err(IoError::FormatError(format!(
+                    "Cell array '{}' has more elements than its data can hold",
+                    name
+                )));
+            }
+            let mut cells = Vec::with_capacity(numel);
+            for _ in 0..numel {
+                cells.push(parse_nested(&mut reader, big_endian)?);
+            }
+            MatType::Cell(cells)
         }
-
-        Some(&data[cursor..cursor + imag_size as usize])
-    } else {
-        None
-    };
-
-    // Convert to appropriate MatType based on class type
-    let mat_type = match flags.class_type {
-        MX_DOUBLE_CLASS => {
-            let data_vec = bytes_to_f64_vec(real_data);
-            let ndarray = Array::from_shape_vec(IxDyn(&convert_dims(&dims)), data_vec)
-                .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))?;
-            MatType::Double(ndarray)
-        }
-        MX_SINGLE_CLASS => {
-            let data_vec = bytes_to_f32_vec(real_data);
-            let ndarray = Array::from_shape_vec(IxDyn(&convert_dims(&dims)), data_vec)
-                .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))?;
-            MatType::Single(ndarray)
-        }
-        MX_INT8_CLASS => {
-            let data_vec = real_data.to_vec();
-            let ndarray = Array::from_shape_vec(
-                IxDyn(&convert_dims(&dims)),
-                data_vec.into_iter().map(|b| b as i8).collect(),
-            )
-            .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))?;
-            MatType::Int8(ndarray)
-        }
-        MX_UINT8_CLASS => {
-            let data_vec = real_data.to_vec();
-            let ndarray = Array::from_shape_vec(IxDyn(&convert_dims(&dims)), data_vec)
-                .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))?;
-            MatType::UInt8(ndarray)
-        }
-        MX_INT16_CLASS => {
-            let data_vec = bytes_to_i16_vec(real_data);
-            let ndarray = Array::from_shape_vec(IxDyn(&convert_dims(&dims)), data_vec)
-                .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))?;
-            MatType::Int16(ndarray)
-        }
-        MX_UINT16_CLASS => {
-            let data_vec = bytes_to_u16_vec(real_data);
-            let ndarray = Array::from_shape_vec(IxDyn(&convert_dims(&dims)), data_vec)
-                .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))?;
-            MatType::UInt16(ndarray)
-        }
-        MX_INT32_CLASS => {
-            let data_vec = bytes_to_i32_vec(real_data);
-            let ndarray = Array::from_shape_vec(IxDyn(&convert_dims(&dims)), data_vec)
-                .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))?;
-            MatType::Int32(ndarray)
-        }
-        MX_UINT32_CLASS => {
-            let data_vec = bytes_to_u32_vec(real_data);
-            let ndarray = Array::from_shape_vec(IxDyn(&convert_dims(&dims)), data_vec)
-                .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))?;
-            MatType::UInt32(ndarray)
-        }
-        MX_INT64_CLASS => {
-            let data_vec = bytes_to_i64_vec(real_data);
-            let ndarray = Array::from_shape_vec(IxDyn(&convert_dims(&dims)), data_vec)
-                .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))?;
-            MatType::Int64(ndarray)
-        }
-        MX_UINT64_CLASS => {
-            let data_vec = bytes_to_u64_vec(real_data);
-            let ndarray = Array::from_shape_vec(IxDyn(&convert_dims(&dims)), data_vec)
-                .map_err(|e| IoError::FormatError(format!("Failed to create array: {}", e)))?;
-            MatType::UInt64(ndarray)
+        MX_STRUCT_CLASS | MX_OBJECT_CLASS => {
+            if flags.class_type == MX_OBJECT_CLASS {
+                // Objects carry their class name before the fields
+                reader.expect_element("class name")?;
+            }
+            let (len_type, len_data) = reader.expect_element("field name length")?;
+            let field_len = decode_values::<usize>(len_type, len_data, big_endian)?
+                .first()
+                .copied()
+                .unwrap_or(0);
+            let (_, names_data) = reader.expect_element("field names")?;
+            let field_names: Vec<String> = if field_len == 0 {
+                Vec::new()
+            } else {
+                names_data
+                    .chunks(field_len)
+                    .map(|chunk| {
+                        let end = chunk.iter().position(|&b| b == 0).unwrap_or(chunk.len());
+                        String::from_utf8_lossy(&chunk[..end]).into_owned()
+                    })
+                    .collect()
+            };
+
+            // Every field of every element is a nested element of at least
+            // eight bytes; arrays of fieldless structs are bounded by the header
+            let limit = match field_names.len() {
+                0 => data.len(),
+                n => reader.remaining() / 8 / n,
+            };
+            if numel > limit {
+                return Err(IoError::FormatError(format!(
+                    "Struct array '{}' has more elements than its data can hold",
+                    name
+                )));
+            }
+            let mut elements = Vec::with_capacity(numel);
+            for _ in 0..numel {
+                let mut fields = HashMap::new();
+                for field in &field_names {
+                    fields.insert(field.clone(), parse_nested(&mut reader, big_endian)?);
+                }
+                elements.push(MatType::Struct(fields));
+            }
+            if numel == 1 {
+                elements.pop().expect("one struct element")
+            } else {
+                MatType::Cell(elements)
+            }
+        }
+        MX_SPARSE_CLASS => {
+            let (ir_type, ir_data) = reader.expect_element("sparse row indices")?;
+            let (jc_type, jc_data) = reader.expect_element("sparse column pointers")?;
+            let (pr_type, pr_data) = reader.expect_element("sparse values")?;
+            let rows = dims.first().copied().unwrap_or(0);
+            let cols = dims.get(1).copied().unwrap_or(0);
+            let col_ptrs: Vec<usize> = decode_values(jc_type, jc_data, big_endian)?;
+            if col_ptrs.len() != cols + 1 {
+                return Err(IoError::FormatError(
+                    "Sparse column pointers do not match the number of columns".to_string(),
+                ));
+            }
+            let nnz = col_ptrs[cols];
+            let mut row_indices: Vec<usize> = decode_values(ir_type, ir_data, big_endian)?;
+            let mut values: Vec<f64> = decode_values(pr_type, pr_data, big_endian)?;
+            if row_indices.len() < nnz || values.len() < nnz {
+                return Err(IoError::FormatError(
+                    "Sparse matrix has fewer stored values than its column pointers require"
+                        .to_string(),
+                ));
+            }
+            // nzmax may exceed the number of stored values
+            row_indices.truncate(nnz);
+            values.truncate(nnz);
+            MatType::Sparse(SparseMatrix {
+                rows,
+                cols,
+                row_indices,
+                col_ptrs,
+                values,
+                logical: flags.is_logical,
+            })
         }
         MX_CHAR_CLASS => {
-            // Convert to string
-            let chars: Vec<u16> = bytes_to_u16_vec(real_data);
-            let utf16_chars: Vec<u16> = chars.into_iter().collect();
-            let string = String::from_utf16_lossy(&utf16_chars);
-            MatType::Char(string)
+            let (char_type, char_data) = reader.expect_element("character data")?;
+            let units: Vec<u32> = if char_type == MI_UTF8 {
+                String::from_utf8_lossy(char_data)
+                    .chars()
+                    .map(|c| c as u32)
+                    .collect()
+            } else {
+                decode_values(char_type, char_data, big_endian)?
+            };
+            MatType::Char(char_array_to_string(&dims, &units))
         }
-        _ => {
-            // Unsupported class type
-            return Err(IoError::FormatError(format!(
-                "Unsupported class type: {}",
-                flags.class_type
-            )));
+        MX_DOUBLE_CLASS | MX_SINGLE_CLASS | MX_INT8_CLASS | MX_UINT8_CLASS | MX_INT16_CLASS
+        | MX_UINT16_CLASS | MX_INT32_CLASS | MX_UINT32_CLASS | MX_INT64_CLASS | MX_UINT64_CLASS => {
+            let (data_type, real_data) = reader.expect_element("real part")?;
+            if flags.is_logical {
+                let values: Vec<u8> = decode_values(data_type, real_data, big_endian)?;
+                MatType::Logical(column_major_array(
+                    &dims,
+                    values.into_iter().map(|v| v != 0).collect(),
+                )?)
+            } else {
+                numeric_array(flags.class_type, &dims, data_type, real_data, big_endian)?
+            }
         }
+        // Function handles, opaque objects and other classes are not supported
+        _ => return Ok(None),
     };
 
-    Ok((name, mat_type))
+    Ok(Some((name, value)))
 }
 
-/// Convert MATLAB dimensions to ndarray dimensions
-fn convert_dims(dims: &[i32]) -> Vec<usize> {
-    // MATLAB stores dimensions in column-major order
-    // For compatibility with ndarray (row-major), we reverse the dimensions
-    dims.iter().rev().map(|&d| d as usize).collect()
-}
-
-/// Read an i32 from the reader
-fn read_i32<R: Read>(reader: &mut R) -> Result<i32> {
-    let mut buffer = [0u8; 4];
-    match reader.read_exact(&mut buffer) {
-        Ok(_) => Ok(LittleEndian::read_i32(&buffer)),
-        Err(_) => Ok(0), // EOF
-    }
-}
-
-/// Convert bytes to f64 vector
-fn bytes_to_f64_vec(bytes: &[u8]) -> Vec<f64> {
-    let mut result = Vec::with_capacity(bytes.len() / 8);
-    for i in (0..bytes.len()).step_by(8) {
-        if i + 8 <= bytes.len() {
-            result.push(LittleEndian::read_f64(&bytes[i..i + 8]));
-        }
-    }
-    result
-}
-
-/// Convert bytes to f32 vector
-fn bytes_to_f32_vec(bytes: &[u8]) -> Vec<f32> {
-    let mut result = Vec::with_capacity(bytes.len() / 4);
-    for i in (0..bytes.len()).step_by(4) {
-        if i + 4 <= bytes.len() {
-            result.push(LittleEndian::read_f32(&bytes[i..i + 4]));
-        }
-    }
-    result
-}
-
-/// Convert bytes to i16 vector
-fn bytes_to_i16_vec(bytes: &[u8]) -> Vec<i16> {
-    let mut result = Vec::with_capacity(bytes.len() / 2);
-    for i in (0..bytes.len()).step_by(2) {
-        if i + 2 <= bytes.len() {
-            result.push(LittleEndian::read_i16(&bytes[i..i + 2]));
-        }
-    }
-    result
-}
-
-/// Convert bytes to u16 vector
-fn bytes_to_u16_vec(bytes: &[u8]) -> Vec<u16> {
-    let mut result = Vec::with_capacity(bytes.len() / 2);
-    for i in (0..bytes.len()).step_by(2) {
-        if i + 2 <= bytes.len() {
-            result.push(LittleEndian::read_u16(&bytes[i..i + 2]));
-        }
-    }
-    result
-}
-
-/// Convert bytes to i32 vector
-fn bytes_to_i32_vec(bytes: &[u8]) -> Vec<i32> {
-    let mut result = Vec::with_capacity(bytes.len() / 4);
-    for i in (0..bytes.len()).step_by(4) {
-        if i + 4 <= bytes.len() {
-            result.push(LittleEndian::read_i32(&bytes[i..i + 4]));
-        }
+/// Parse a nested (cell or field) miMATRIX element
+fn parse_nested(reader: &mut ElementReader, big_endian: bool) -> Result<MatType> {
+    let (element_type, data) = reader.expect_element("nested array")?;
+    if element_type != MI_MATRIX {
+        return Err(IoError::FormatError(
+            "Expected a matrix element inside a cell or struct".to_string(),
+        ));
     }
-    result
+    // Unsupported nested classes become empty arrays to keep positions intact
+    Ok(parse_matrix(data, big_endian)?
+        .map(|(_, value)| value)
+        .unwrap_or_else(|| MatType::Double(ArrayD::zeros(IxDyn(&[0, 0])))))
 }
 
-/// Convert bytes to u32 vector
-fn bytes_to_u32_vec(bytes: &[u8]) -> Vec<u32> {
-    let mut result = Vec::with_capacity(bytes.len() / 4);
-    for i in (0..bytes.len()).step_by(4) {
-        if i + 4 <= bytes.len() {
-            result.push(LittleEndian::read_u32(&bytes[i..i + 4]));
-        }
+/// Read the first u32 of a buffer
+fn reader_u32(data: &[u8], big_endian: bool) -> u32 {
+    if big_endian {
+        BigEndian::read_u32(data)
+    } else {
+        LittleEndian::read_u32(data)
     }
-    result
 }
 
-/// Convert bytes to i64 vector
-fn bytes_to_i64_vec(bytes: &[u8]) -> Vec<i64> {
-    let mut result = Vec::with_capacity(bytes.len() / 8);
-    for i in (0..bytes.len()).step_by(8) {
-        if i + 8 <= bytes.len() {
-            result.push(LittleEndian::read_i64(&bytes[i..i + 8]));
+/// Build the `MatType` of a numeric class from its stored data
+fn numeric_array(
+    class_type: u32,
+    dims: &[usize],
+    data_type: u32,
+    data: &[u8],
+    big_endian: bool,
+) -> Result<MatType> {
+    Ok(match class_type {
+        MX_DOUBLE_CLASS => MatType::Double(column_major_array(
+            dims,
+            decode_values(data_type, data, big_endian)?,
+        )?),
+        MX_SINGLE_CLASS => MatType::Single(column_major_array(
+            dims,
+            decode_values(data_type, data, big_endian)?,
+        )?),
+        MX_INT8_CLASS => MatType::Int8(column_major_array(
+            dims,
+            decode_values(data_type, data, big_endian)?,
+        )?),
+        MX_UINT8_CLASS => MatType::UInt8(column_major_array(
+            dims,
+            decode_values(data_type, data, big_endian)?,
+        )?),
+        MX_INT16_CLASS => MatType::Int16(column_major_array(
+            dims,
+            decode_values(data_type, data, big_endian)?,
+        )?),
+        MX_UINT16_CLASS => MatType::UInt16(column_major_array(
+            dims,
+            decode_values(data_type, data, big_endian)?,
+        )?),
+        MX_INT32_CLASS => MatType::Int32(column_major_array(
+            dims,
+            decode_values(data_type, data, big_endian)?,
+        )?),
+        MX_UINT32_CLASS => MatType::UInt32(column_major_array(
+            dims,
+            decode_values(data_type, data, big_endian)?,
+        )?),
+        MX_INT64_CLASS => MatType::Int64(column_major_array(
+            dims,
+            decode_values(data_type, data, big_endian)?,
+        )?),
+        MX_UINT64_CLASS => MatType::UInt64(column_major_array(
+            dims,
+            decode_values(data_type, data, big_endian)?,
+        )?),
+        other => {
+            return Err(IoError::FormatError(format!(
+                "Unsupported class type: {}",
+                other
+            )))
         }
-    }
-    result
+    })
 }
 
-/// Convert bytes to u64 vector
-fn bytes_to_u64_vec(bytes: &[u8]) -> Vec<u64> {
-    let mut result = Vec::with_capacity(bytes.len() / 8);
-    for i in (0..bytes.len()).step_by(8) {
-        if i + 8 <= bytes.len() {
-            result.push(LittleEndian::read_u64(&bytes[i..i + 8]));
-        }
+/// Convert a column-major MATLAB character array into a string
+///
+/// Multi-row character arrays are joined with newlines.
+fn char_array_to_string(dims: &[usize], units: &[u32]) -> String {
+    let rows = dims.first().copied().unwrap_or(0);
+    let cols = units.len().checked_div(rows).unwrap_or(0);
+    let mut lines = Vec::with_capacity(rows);
+    for r in 0..rows {
+        let row: Vec<u32> = (0..cols).map(|c| units[c * rows + r]).collect();
+        // Values that are not valid scalar values are UTF-16 surrogate pairs
+        let utf16: Vec<u16> = row.iter().map(|&u| u as u16).collect();
+        let line = if row.iter().all(|&u| u <= 0xFFFF) {
+            String::from_utf16_lossy(&utf16)
+        } else {
+            row.iter().filter_map(|&u| char::from_u32(u)).collect()
+        };
+        lines.push(line);
     }
-    result
+    lines.join("\n")
 }
 
 /// Writes data to a MATLAB .mat file
 ///
+/// The file is written in the MATLAB v5 format without compression. Variables
+/// are written in name order.
+///
 /// # Arguments
 ///
 /// * `path` - Path where the .mat file should be written
@@ -562,46 +714,752 @@ fn bytes_to_u64_vec(bytes: &[u8]) -> Vec<u64> {
 ///
 /// write_mat(Path::new("output.mat"), &vars).unwrap();
 /// ```
-pub fn write_mat<P: AsRef<Path>>(path: P, _vars: &HashMap<String, MatType>) -> Result<()> {
-    let file = File::create(path).map_err(|e| IoError::FileError(e.to_string()))?;
-    let mut writer = BufWriter::new(file);
+pub fn write_mat<P: AsRef<Path>>(path: P, vars: &HashMap<String, MatType>) -> Result<()> {
+    write_mat_impl(path.as_ref(), vars, false)
+}
+
+/// Writes data to a MATLAB .mat file with zlib compression
+///
+/// Each variable is stored as a compressed (`miCOMPRESSED`) data element, as
+/// MATLAB does for v7 files by default.
+///
+/// # Arguments
+///
+/// * `path` - Path where the .mat file should be written
+/// * `vars` - A HashMap mapping variable names to their values
+pub fn write_mat_compressed<P: AsRef<Path>>(
+    path: P,
+    vars: &HashMap<String, MatType>,
+) -> Result<()> {
+    write_mat_impl(path.as_ref(), vars, true)
+}
 
-    // Write MAT file header
+fn write_mat_impl(path: &Path, vars: &HashMap<String, MatType>, compress: bool) -> Result<()> {
+    let mut names: Vec<&String> = vars.keys().collect();
+    names.sort();
+
+    let mut body = Vec::new();
+    for name in names {
+        validate_name(name, "variable")?;
+        let mut element = Vec::new();
+        write_matrix(&mut element, name, &vars[name])?;
+        if compress {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder
+                .write_all(&element)
+                .map_err(|e| IoError::CompressionError(e.to_string()))?;
+            let compressed = encoder
+                .finish()
+                .map_err(|e| IoError::CompressionError(e.to_string()))?;
+            put_u32(&mut body, MI_COMPRESSED);
+            put_u32(&mut body, element_size(compressed.len())?);
+            body.extend_from_slice(&compressed);
+        } else {
+            body.extend_from_slice(&element);
+        }
+    }
 
-    // Write "MATLAB" magic string
+    let file = File::create(path).map_err(|e| IoError::FileError(e.to_string()))?;
+    let mut writer = BufWriter::new(file);
     writer
-        .write_all(b"MATLAB ")
-        .map_err(|e| IoError::FileError(format!("Failed to write MAT header: {}", e)))?;
+        .write_all(&header_bytes())
+        .and_then(|_| writer.write_all(&body))
+        .and_then(|_| writer.flush())
+        .map_err(|e| IoError::FileError(format!("Failed to write MAT file: {}", e)))
+}
 
-    // Write version platform info (padding to 116 bytes total)
-    let version = format!(
-        "7.0 MAT-file, Platform: SCIRS2, Created by: scirs2-io {}",
+/// Build the 128-byte v5 header
+fn header_bytes() -> [u8; HEADER_SIZE] {
+    let mut header = [b' '; HEADER_SIZE];
+    let text = format!(
+        "MATLAB 5.0 MAT-file, Platform: {}, Created on: {}, by scirs2-io {}",
+        std::env::consts::OS,
+        chrono::Local::now().format("%a %b %e %H:%M:%S %Y"),
         env!("CARGO_PKG_VERSION")
     );
-    let version_bytes = version.as_bytes();
-    let version_len = version_bytes.len().min(110); // Ensure it fits in the header
+    let text_len = text.len().min(116);
+    header[..text_len].copy_from_slice(&text.as_bytes()[..text_len]);
+    // Subsystem data offset: none
+    header[116..124].fill(0);
+    // Version 0x0100 and the "IM" endian indicator, little-endian
+    header[124..126].copy_from_slice(&0x0100u16.to_le_bytes());
+    header[126..128].copy_from_slice(b"IM");
+    header
+}
 
-    writer
-        .write_all(&version_bytes[0..version_len])
-        .map_err(|e| IoError::FileError(format!("Failed to write version info: {}", e)))?;
+fn validate_name(name: &str, what: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_FIELD_NAME || !name.is_ascii() {
+        return Err(IoError::ValidationError(format!(
+            "Invalid MATLAB {} name '{}': names must be 1 to {} ASCII characters",
+            what, name, MAX_FIELD_NAME
+        )));
+    }
+    Ok(())
+}
 
-    // Pad to 116 bytes
-    let padding = vec![0u8; 116 - 6 - version_len];
-    writer
-        .write_all(&padding)
-        .map_err(|e| IoError::FileError(format!("Failed to write header padding: {}", e)))?;
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
 
-    // Write version (0x0100 for MAT 5) and endianness indicator (0x4D49 for little endian)
-    writer
-        .write_u16::<LittleEndian>(0x0100)
-        .map_err(|e| IoError::FileError(format!("Failed to write version: {}", e)))?;
-    writer
-        .write_u16::<LittleEndian>(0x4D49)
-        .map_err(|e| IoError::FileError(format!("Failed to write endianness: {}", e)))?;
-
-    // TODO: Implement full writing of variables
-    // For now, return a placeholder message
-    Err(IoError::Other(
-        "MATLAB file writing not fully implemented yet".to_string(),
-    ))
+fn element_size(len: usize) -> Result<u32> {
+    u32::try_from(len).map_err(|_| {
+        IoError::SerializationError(
+            "MAT v5 data elements are limited to 4 GiB; the array is too large".to_string(),
+        )
+    })
+}
+
+/// Append a data element, using the small element format for up to 4 bytes
+fn write_element(out: &mut Vec<u8>, element_type: u32, data: &[u8]) -> Result<()> {
+    if data.len() <= 4 && !data.is_empty() {
+        put_u32(out, ((data.len() as u32) << 16) | element_type);
+        out.extend_from_slice(data);
+        out.resize(out.len() + 4 - data.len(), 0);
+    } else {
+        put_u32(out, element_type);
+        put_u32(out, element_size(data.len())?);
+        out.extend_from_slice(data);
+        out.resize((out.len() + 7) & !7, 0);
+    }
+    Ok(())
+}
+
+/// Append an miMATRIX element with the given flags, dimensions and name,
+/// with `contents` producing the class-specific sub-elements
+fn write_array(
+    out: &mut Vec<u8>,
+    flags: &MatrixFlags,
+    nzmax: u32,
+    dims: &[usize],
+    name: &str,
+    contents: impl FnOnce(&mut Vec<u8>) -> Result<()>,
+) -> Result<()> {
+    let mut body = Vec::new();
+
+    let mut flag_data = Vec::with_capacity(8);
+    put_u32(&mut flag_data, flags.to_u32());
+    put_u32(&mut flag_data, nzmax);
+    write_element(&mut body, MI_UINT32, &flag_data)?;
+
+    let mut dim_data = Vec::with_capacity(dims.len() * 4);
+    for &d in dims {
+        let d = i32::try_from(d).map_err(|_| {
+            IoError::SerializationError(format!("Dimension {} is too large for a MAT file", d))
+        })?;
+        dim_data.extend_from_slice(&d.to_le_bytes());
+    }
+    write_element(&mut body, MI_INT32, &dim_data)?;
+    write_element(&mut body, MI_INT8, name.as_bytes())?;
+
+    contents(&mut body)?;
+
+    put_u32(out, MI_MATRIX);
+    put_u32(out, element_size(body.len())?);
+    out.extend_from_slice(&body);
+    Ok(())
+}
+
+/// MATLAB dimensions of an ndarray shape (at least two dimensions)
+fn mat_dims(shape: &[usize]) -> Vec<usize> {
+    match shape.len() {
+        0 => vec![1, 1],
+        1 => vec![1, shape[0]],
+        _ => shape.to_vec(),
+    }
+}
+
+/// Encode the elements of an array in MATLAB's column-major order
+fn column_major_bytes<T: Copy, const N: usize>(
+    array: &ArrayD<T>,
+    to_bytes: impl Fn(T) -> [u8; N],
+) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(array.len() * N);
+    // Iterating the transposed view visits the first axis fastest
+    for &value in array.t().iter() {
+        bytes.extend_from_slice(&to_bytes(value));
+    }
+    bytes
+}
+
+/// Append a numeric miMATRIX element
+fn write_numeric(
+    out: &mut Vec<u8>,
+    name: &str,
+    class_type: u32,
+    shape: &[usize],
+    data_type: u32,
+    data: &[u8],
+) -> Result<()> {
+    write_array(
+        out,
+        &MatrixFlags::new(class_type),
+        0,
+        &mat_dims(shape),
+        name,
+        |body| write_element(body, data_type, data),
+    )
+}
+
+/// Append the miMATRIX element of a variable
+fn write_matrix(out: &mut Vec<u8>, name: &str, value: &MatType) -> Result<()> {
+    match value {
+        MatType::Double(a) => {
+            let data = column_major_bytes(a, f64::to_le_bytes);
+            write_numeric(out, name, MX_DOUBLE_CLASS, a.shape(), MI_DOUBLE, &data)
+        }
+        MatType::Single(a) => {
+            let data = column_major_bytes(a, f32::to_le_bytes);
+            write_numeric(out, name, MX_SINGLE_CLASS, a.shape(), MI_SINGLE, &data)
+        }
+        MatType::Int8(a) => {
+            let data = column_major_bytes(a, i8::to_le_bytes);
+            write_numeric(out, name, MX_INT8_CLASS, a.shape(), MI_INT8, &data)
+        }
+        MatType::Int16(a) => {
+            let data = column_major_bytes(a, i16::to_le_bytes);
+            write_numeric(out, name, MX_INT16_CLASS, a.shape(), MI_INT16, &data)
+        }
+        MatType::Int32(a) => {
+            let data = column_major_bytes(a, i32::to_le_bytes);
+            write_numeric(out, name, MX_INT32_CLASS, a.shape(), MI_INT32, &data)
+        }
+        MatType::Int64(a) => {
+            let data = column_major_bytes(a, i64::to_le_bytes);
+            write_numeric(out, name, MX_INT64_CLASS, a.shape(), MI_INT64, &data)
+        }
+        MatType::UInt8(a) => {
+            let data = column_major_bytes(a, u8::to_le_bytes);
+            write_numeric(out, name, MX_UINT8_CLASS, a.shape(), MI_UINT8, &data)
+        }
+        MatType::UInt16(a) => {
+            let data = column_major_bytes(a, u16::to_le_bytes);
+            write_numeric(out, name, MX_UINT16_CLASS, a.shape(), MI_UINT16, &data)
+        }
+        MatType::UInt32(a) => {
+            let data = column_major_bytes(a, u32::to_le_bytes);
+            write_numeric(out, name, MX_UINT32_CLASS, a.shape(), MI_UINT32, &data)
+        }
+        MatType::UInt64(a) => {
+            let data = column_major_bytes(a, u64::to_le_bytes);
+            write_numeric(out, name, MX_UINT64_CLASS, a.shape(), MI_UINT64, &data)
+        }
+        MatType::Logical(a) => {
+            let data = column_major_bytes(a, |b| [b as u8]);
+            let mut flags = MatrixFlags::new(MX_UINT8_CLASS);
+            flags.is_logical = true;
+            write_array(out, &flags, 0, &mat_dims(a.shape()), name, |body| {
+                write_element(body, MI_UINT8, &data)
+            })
+        }
+        MatType::Char(s) => {
+            let units: Vec<u16> = s.encode_utf16().collect();
+            let dims = if units.is_empty() {
+                vec![0, 0]
+            } else {
+                vec![1, units.len()]
+            };
+            let data: Vec<u8> = units.iter().flat_map(|u| u.to_le_bytes()).collect();
+            write_array(
+                out,
+                &MatrixFlags::new(MX_CHAR_CLASS),
+                0,
+                &dims,
+                name,
+                |body| write_element(body, MI_UINT16, &data),
+            )
+        }
+        MatType::Cell(cells) => write_array(
+            out,
+            &MatrixFlags::new(MX_CELL_CLASS),
+            0,
+            &[1, cells.len()],
+            name,
+            |body| {
+                for cell in cells {
+                    write_matrix(body, "", cell)?;
+                }
+                Ok(())
+            },
+        ),
+        MatType::Struct(fields) => {
+            let mut field_names: Vec<&String> = fields.keys().collect();
+            field_names.sort();
+            for field in &field_names {
+                validate_name(field, "field")?;
+            }
+            // Each name is stored NUL-terminated in a fixed-width slot
+            let slot = field_names.iter().map(|f| f.len() + 1).max().unwrap_or(1);
+            write_array(
+                out,
+                &MatrixFlags::new(MX_STRUCT_CLASS),
+                0,
+                &[1, 1],
+                name,
+                |body| {
+                    write_element(body, MI_INT32, &(slot as i32).to_le_bytes())?;
+                    let mut names = vec![0u8; slot * field_names.len()];
+                    for (i, field) in field_names.iter().enumerate() {
+                        names[i * slot..i * slot + field.len()].copy_from_slice(field.as_bytes());
+                    }
+                    write_element(body, MI_INT8, &names)?;
+                    for field in &field_names {
+                        write_matrix(body, "", &fields[*field])?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+        MatType::Sparse(sparse) => write_sparse(out, name, sparse),
+    }
+}
+
+/// Append the miMATRIX element of a sparse matrix
+fn write_sparse(out: &mut Vec<u8>, name: &str, sparse: &SparseMatrix) -> Result<()> {
+    let nnz = sparse.values.len();
+    let valid = sparse.col_ptrs.len() == sparse.cols + 1
+        && sparse.col_ptrs.first() == Some(&0)
+        && sparse.col_ptrs.last() == Some(&nnz)
+        && sparse.col_ptrs.windows(2).all(|w| w[0] <= w[1])
+        && sparse.row_indices.len() == nnz
+        && sparse.row_indices.iter().all(|&r| r < sparse.rows);
+    if !valid {
+        return Err(IoError::ValidationError(format!(
+            "Sparse matrix '{}' is not in valid compressed sparse column form",
+            name
+        )));
+    }
+
+    let to_i32 = |values: &[usize]| -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(values.len() * 4);
+        for &v in values {
+            let v = i32::try_from(v).map_err(|_| {
+                IoError::SerializationError("Sparse matrix is too large for a MAT file".to_string())
+            })?;
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        Ok(bytes)
+    };
+    let ir = to_i32(&sparse.row_indices)?;
+    let jc = to_i32(&sparse.col_ptrs)?;
+
+    let mut flags = MatrixFlags::new(MX_SPARSE_CLASS);
+    flags.is_logical = sparse.logical;
+    let nzmax = element_size(nnz.max(1))?;
+    write_array(
+        out,
+        &flags,
+        nzmax,
+        &[sparse.rows, sparse.cols],
+        name,
+        |body| {
+            write_element(body, MI_INT32, &ir)?;
+            write_element(body, MI_INT32, &jc)?;
+            if sparse.logical {
+                let data: Vec<u8> = sparse.values.iter().map(|&v| (v != 0.0) as u8).collect();
+                write_element(body, MI_UINT8, &data)
+            } else {
+                let data: Vec<u8> = sparse.values.iter().flat_map(|v| v.to_le_bytes()).collect();
+                write_element(body, MI_DOUBLE, &data)
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hdf5::{
+        create_hdf5_with_structure, AttributeValue, DataArray, Dataset, DatasetOptions,
+        HDF5DataType, StringEncoding,
+    };
+    use ndarray::array;
+
+    fn sample_vars() -> HashMap<String, MatType> {
+        let mut vars = HashMap::new();
+        vars.insert(
+            "matrix".to_string(),
+            MatType::Double(array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]].into_dyn()),
+        );
+        vars.insert(
+            "s".to_string(),
+            MatType::Single(array![1.5f32, -2.5].into_dyn()),
+        );
+        vars.insert(
+            "i8".to_string(),
+            MatType::Int8(array![[-128i8], [127]].into_dyn()),
+        );
+        vars.insert("u8".to_string(), MatType::UInt8(array![7u8].into_dyn()));
+        vars.insert(
+            "i16".to_string(),
+            MatType::Int16(array![-300i16, 300].into_dyn()),
+        );
+        vars.insert(
+            "u16".to_string(),
+            MatType::UInt16(array![65535u16].into_dyn()),
+        );
+        vars.insert(
+            "i32".to_string(),
+            MatType::Int32(array![i32::MIN, 0, i32::MAX].into_dyn()),
+        );
+        vars.insert("u32".to_string(), MatType::UInt32(array![1u32].into_dyn()));
+        vars.insert(
+            "i64".to_string(),
+            MatType::Int64(array![i64::MIN, i64::MAX].into_dyn()),
+        );
+        vars.insert(
+            "u64".to_string(),
+            MatType::UInt64(array![u64::MAX].into_dyn()),
+        );
+        vars.insert(
+            "flags".to_string(),
+            MatType::Logical(array![[true, false], [false, true]].into_dyn()),
+        );
+        vars.insert("text".to_string(), MatType::Char("héllo".to_string()));
+        vars.insert(
+            "cell".to_string(),
+            MatType::Cell(vec![
+                MatType::Double(array![42.0].into_dyn()),
+                MatType::Char("abc".to_string()),
+            ]),
+        );
+        let mut fields = HashMap::new();
+        fields.insert(
+            "alpha".to_string(),
+            MatType::Double(array![1.0, 2.0].into_dyn()),
+        );
+        fields.insert(
+            "a_much_longer_field_name".to_string(),
+            MatType::Char("x".to_string()),
+        );
+        vars.insert("st".to_string(), MatType::Struct(fields));
+        vars.insert(
+            "sp".to_string(),
+            MatType::Sparse(SparseMatrix {
+                rows: 3,
+                cols: 2,
+                row_indices: vec![0, 2, 1],
+                col_ptrs: vec![0, 2, 3],
+                values: vec![1.0, -2.0, 3.5],
+                logical: false,
+            }),
+        );
+        vars
+    }
+
+    fn assert_sample(read: &HashMap<String, MatType>) {
+        assert_eq!(read.len(), 15);
+        match &read["matrix"] {
+            MatType::Double(a) => {
+                assert_eq!(a, &array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]].into_dyn())
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match &read["s"] {
+            // One-dimensional arrays come back as row vectors
+            MatType::Single(a) => assert_eq!(a, &array![[1.5f32, -2.5]].into_dyn()),
+            other => panic!("unexpected {:?}", other),
+        }
+        match &read["i8"] {
+            MatType::Int8(a) => assert_eq!(a, &array![[-128i8], [127]].into_dyn()),
+            other => panic!("unexpected {:?}", other),
+        }
+        match &read["i64"] {
+            MatType::Int64(a) => assert_eq!(a, &array![[i64::MIN, i64::MAX]].into_dyn()),
+            other => panic!("unexpected {:?}", other),
+        }
+        match &read["u64"] {
+            MatType::UInt64(a) => assert_eq!(a, &array![[u64::MAX]].into_dyn()),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(&read["u8"], MatType::UInt8(a) if a[[0, 0]] == 7));
+        assert!(matches!(&read["i16"], MatType::Int16(a) if a[[0, 0]] == -300));
+        assert!(matches!(&read["u16"], MatType::UInt16(a) if a[[0, 0]] == 65535));
+        assert!(matches!(&read["i32"], MatType::Int32(a) if a[[0, 2]] == i32::MAX));
+        assert!(matches!(&read["u32"], MatType::UInt32(a) if a[[0, 0]] == 1));
+        match &read["flags"] {
+            MatType::Logical(a) => {
+                assert_eq!(a, &array![[true, false], [false, true]].into_dyn())
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(&read["text"], MatType::Char(s) if s == "héllo"));
+        match &read["cell"] {
+            MatType::Cell(cells) => {
+                assert_eq!(cells.len(), 2);
+                assert!(matches!(&cells[0], MatType::Double(a) if a[[0, 0]] == 42.0));
+                assert!(matches!(&cells[1], MatType::Char(s) if s == "abc"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match &read["st"] {
+            MatType::Struct(fields) => {
+                assert_eq!(fields.len(), 2);
+                assert!(matches!(&fields["alpha"], MatType::Double(a) if a.len() == 2));
+                assert!(
+                    matches!(&fields["a_much_longer_field_name"], MatType::Char(s) if s == "x")
+                );
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match &read["sp"] {
+            MatType::Sparse(sp) => {
+                assert_eq!((sp.rows, sp.cols), (3, 2));
+                assert_eq!(sp.row_indices, vec![0, 2, 1]);
+                assert_eq!(sp.col_ptrs, vec![0, 2, 3]);
+                assert_eq!(sp.values, vec![1.0, -2.0, 3.5]);
+                assert!(!sp.logical);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_write_read_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vars.mat");
+        write_mat(&path, &sample_vars()).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(bytes.starts_with(b"MATLAB 5.0 MAT-file"));
+        assert_eq!(&bytes[124..128], &[0x00, 0x01, b'I', b'M']);
+
+        assert_sample(&read_mat(&path).unwrap());
+    }
+
+    #[test]
+    fn test_huge_cell_and_struct_dimensions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("huge.mat");
+
+        let mut fields = HashMap::new();
+        fields.insert("a".to_string(), MatType::Double(array![1.0].into_dyn()));
+        let cell = MatType::Cell(vec![
+            MatType::Double(array![1.0].into_dyn()),
+            MatType::Double(array![2.0].into_dyn()),
+        ]);
+        for (value, dims) in [(cell, [1i32, 2]), (MatType::Struct(fields), [1, 1])] {
+            let mut vars = HashMap::new();
+            vars.insert("v".to_string(), value);
+            write_mat(&path, &vars).unwrap();
+            let bytes = std::fs::read(&path).unwrap();
+
+            // Rewrite the dimensions element of the top-level array
+            let mut element = vec![MI_INT32 as u8, 0, 0, 0, 8, 0, 0, 0];
+            element.extend_from_slice(&dims[0].to_le_bytes());
+            element.extend_from_slice(&dims[1].to_le_bytes());
+            let pos = bytes
+                .windows(element.len())
+                .position(|w| w == element)
+                .unwrap();
+            for huge in [[0x7FFF_FFFFi32, 0x7FFF_FFFF], [1, 1000]] {
+                let mut patched = bytes.clone();
+                patched[pos + 8..pos + 12].copy_from_slice(&huge[0].to_le_bytes());
+                patched[pos + 12..pos + 16].copy_from_slice(&huge[1].to_le_bytes());
+                std::fs::write(&path, patched).unwrap();
+                assert!(read_mat(&path).is_err(), "{:?}", huge);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compressed_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.mat");
+        let compressed = dir.path().join("compressed.mat");
+        let mut vars = sample_vars();
+        vars.insert(
+            "zeros".to_string(),
+            MatType::Double(ArrayD::zeros(IxDyn(&[100, 100]))),
+        );
+        write_mat(&plain, &vars).unwrap();
+        write_mat_compressed(&compressed, &vars).unwrap();
+
+        let plain_len = std::fs::metadata(&plain).unwrap().len();
+        let compressed_len = std::fs::metadata(&compressed).unwrap().len();
+        assert!(compressed_len < plain_len);
+
+        let mut read = read_mat(&compressed).unwrap();
+        assert!(
+            matches!(read.remove("zeros"), Some(MatType::Double(a)) if a.shape() == [100, 100])
+        );
+        assert_sample(&read);
+    }
+
+    #[test]
+    fn test_invalid_names_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("invalid.mat");
+        let mut vars = HashMap::new();
+        vars.insert("x".repeat(64), MatType::Char("a".to_string()));
+        assert!(write_mat(&path, &vars).is_err());
+    }
+
+    fn dataset(
+        name: &str,
+        dtype: HDF5DataType,
+        shape: Vec<usize>,
+        data: DataArray,
+        class: &str,
+    ) -> Dataset {
+        let mut attributes = HashMap::new();
+        attributes.insert(
+            "MATLAB_class".to_string(),
+            AttributeValue::String(class.to_string()),
+        );
+        Dataset {
+            name: name.to_string(),
+            dtype,
+            shape,
+            data,
+            attributes,
+            options: DatasetOptions::default(),
+        }
+    }
+
+    #[test]
+    fn test_read_v73() {
+        let dir = tempfile::tempdir().unwrap();
+        let h5 = dir.path().join("vars.h5");
+        let path = dir.path().join("vars.mat");
+
+        create_hdf5_with_structure(&h5, |file| {
+            let root = file.root_mut();
+            // A 2x3 MATLAB matrix is stored with the dimensions reversed
+            root.datasets.insert(
+                "a".to_string(),
+                dataset(
+                    "a",
+                    HDF5DataType::Float { size: 8 },
+                    vec![3, 2],
+                    DataArray::Float(vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]),
+                    "double",
+                ),
+            );
+            root.datasets.insert(
+                "name".to_string(),
+                dataset(
+                    "name",
+                    HDF5DataType::Integer {
+                        size: 2,
+                        signed: false,
+                    },
+                    vec![2, 1],
+                    DataArray::Integer(vec![104, 105]),
+                    "char",
+                ),
+            );
+            let mut empty = dataset(
+                "empty",
+                HDF5DataType::Integer {
+                    size: 8,
+                    signed: false,
+                },
+                vec![2],
+                DataArray::Integer(vec![0, 0]),
+                "double",
+            );
+            empty
+                .attributes
+                .insert("MATLAB_empty".to_string(), AttributeValue::Integer(1));
+            root.datasets.insert("empty".to_string(), empty);
+
+            let st = root.create_group("st");
+            st.set_attribute("MATLAB_class", AttributeValue::String("struct".to_string()));
+            st.datasets.insert(
+                "flag".to_string(),
+                dataset(
+                    "flag",
+                    HDF5DataType::Integer {
+                        size: 1,
+                        signed: false,
+                    },
+                    vec![1, 1],
+                    DataArray::Integer(vec![1]),
+                    "logical",
+                ),
+            );
+
+            let sp = root.create_group("sp");
+            sp.set_attribute("MATLAB_class", AttributeValue::String("double".to_string()));
+            sp.set_attribute("MATLAB_sparse", AttributeValue::Integer(3));
+            let index = HDF5DataType::Integer {
+                size: 8,
+                signed: false,
+            };
+            sp.datasets.insert(
+                "jc".to_string(),
+                dataset(
+                    "jc",
+                    index.clone(),
+                    vec![3],
+                    DataArray::Integer(vec![0, 1, 2]),
+                    "",
+                ),
+            );
+            sp.datasets.insert(
+                "ir".to_string(),
+                dataset("ir", index, vec![2], DataArray::Integer(vec![2, 0]), ""),
+            );
+            sp.datasets.insert(
+                "data".to_string(),
+                dataset(
+                    "data",
+                    HDF5DataType::Float { size: 8 },
+                    vec![2],
+                    DataArray::Float(vec![7.0, 8.0]),
+                    "",
+                ),
+            );
+
+            let refs = root.create_group("#refs#");
+            refs.datasets.insert(
+                "a".to_string(),
+                dataset(
+                    "a",
+                    HDF5DataType::String {
+                        encoding: StringEncoding::ASCII,
+                    },
+                    vec![1],
+                    DataArray::String(vec!["ignored".to_string()]),
+                    "char",
+                ),
+            );
+            Ok(())
+        })
+        .unwrap();
+
+        // Prepend the 512-byte user block holding the MATLAB header
+        let mut header = vec![0u8; 512];
+        let text = b"MATLAB 7.3 MAT-file, Platform: GLNXA64, Created on: Mon Jan  1 00:00:00 2024 HDF5 schema 1.00 .";
+        header[..116].fill(b' ');
+        header[..text.len()].copy_from_slice(text);
+        header[124..128].copy_from_slice(&[0x00, 0x02, b'I', b'M']);
+        header.extend_from_slice(&std::fs::read(&h5).unwrap());
+        std::fs::write(&path, header).unwrap();
+
+        let vars = read_mat(&path).unwrap();
+        assert_eq!(vars.len(), 5);
+        match &vars["a"] {
+            MatType::Double(a) => {
+                assert_eq!(a, &array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]].into_dyn())
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(&vars["name"], MatType::Char(s) if s == "hi"));
+        assert!(matches!(&vars["empty"], MatType::Double(a) if a.shape() == [0, 0]));
+        match &vars["st"] {
+            MatType::Struct(fields) => {
+                assert!(matches!(&fields["flag"], MatType::Logical(a) if a[[0, 0]]))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match &vars["sp"] {
+            MatType::Sparse(sp) => {
+                assert_eq!((sp.rows, sp.cols), (3, 2));
+                assert_eq!(sp.row_indices, vec![2, 0]);
+                assert_eq!(sp.values, vec![7.0, 8.0]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
 }
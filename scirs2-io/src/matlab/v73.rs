@@ -0,0 +1,268 @@
+//! MATLAB v7.3 file reading
+//!
+//! Version 7.3 MAT-files are HDF5 files with a 512-byte user block holding
+//! the MATLAB header. Every variable is a dataset or group in the root group
+//! tagged with a `MATLAB_class` attribute. Cell arrays are datasets of object
+//! references into the `#refs#` group, structs are groups with one member per
+//! field and sparse matrices are groups holding `data`, `ir` and `jc`.
+//!
+//! HDF5 stores the MATLAB dimensions in reverse order, so a dataset of shape
+//! `[n, m]` holds an `m x n` MATLAB array in column-major order.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use ndarray::ArrayD;
+use num_traits::NumCast;
+
+use super::{char_array_to_string, column_major_array, MatType, SparseMatrix};
+use crate::error::{IoError, Result};
+use crate::hdf5::reader::read_file_with_paths;
+use crate::hdf5::{AttributeValue, DataArray, Dataset, Group};
+
+/// Read every variable of a v7.3 MAT-file
+pub(super) fn read_mat_v73(path: &Path) -> Result<HashMap<String, MatType>> {
+    let (root, paths) = read_file_with_paths(path)?;
+    let reader = V73Reader {
+        root: &root,
+        paths: &paths,
+    };
+
+    let mut variables = HashMap::new();
+    for (name, dataset) in &root.datasets {
+        if let Some(value) = reader.dataset(dataset)? {
+            variables.insert(name.clone(), value);
+        }
+    }
+    for (name, group) in &root.groups {
+        // "#refs#" holds cell contents and "#subsystem#" MATLAB object data
+        if name.starts_with('#') {
+            continue;
+        }
+        if let Some(value) = reader.group(group)? {
+            variables.insert(name.clone(), value);
+        }
+    }
+    Ok(variables)
+}
+
+struct V73Reader<'a> {
+    root: &'a Group,
+    paths: &'a HashMap<u64, String>,
+}
+
+/// Value of the `MATLAB_class` attribute
+fn matlab_class(attributes: &HashMap<String, AttributeValue>) -> Option<&str> {
+    match attributes.get("MATLAB_class")? {
+        AttributeValue::String(s) => Some(s.as_str()),
+        AttributeValue::StringArray(v) if v.len() == 1 => Some(v[0].as_str()),
+        _ => None,
+    }
+}
+
+/// Integer value of an attribute, if present
+fn integer_attribute(attributes: &HashMap<String, AttributeValue>, name: &str) -> Option<i64> {
+    match attributes.get(name)? {
+        AttributeValue::Integer(v) => Some(*v),
+        AttributeValue::IntegerArray(v) if v.len() == 1 => Some(v[0]),
+        _ => None,
+    }
+}
+
+/// Convert dataset values to `T`
+fn values<T: NumCast>(dataset: &Dataset) -> Result<Vec<T>> {
+    let convert = |v: Option<T>| {
+        v.ok_or_else(|| IoError::FormatError("MAT value out of range for its class".to_string()))
+    };
+    match &dataset.data {
+        DataArray::Integer(v) => v.iter().map(|&x| convert(T::from(x))).collect(),
+        DataArray::Float(v) => v.iter().map(|&x| convert(T::from(x))).collect(),
+        _ => Err(IoError::FormatError(format!(
+            "Dataset '{}' does not hold numeric data",
+            dataset.name
+        ))),
+    }
+}
+
+/// Unsigned 64-bit values, which the HDF5 reader returns as wrapped `i64`
+fn u64_values(dataset: &Dataset) -> Result<Vec<u64>> {
+    match &dataset.data {
+        DataArray::Integer(v) => Ok(v.iter().map(|&x| x as u64).collect()),
+        _ => values(dataset),
+    }
+}
+
+/// MATLAB dimensions of a dataset
+fn mat_dims(dataset: &Dataset) -> Vec<usize> {
+    let mut dims: Vec<usize> = dataset.shape.iter().rev().copied().collect();
+    while dims.len() < 2 {
+        dims.push(1);
+    }
+    dims
+}
+
+impl V73Reader<'_> {
+    /// Look up a dataset or group by absolute path
+    fn resolve(&self, path: &str) -> Option<(Option<&Group>, Option<&Dataset>)> {
+        let mut group = self.root;
+        let mut parts = path.trim_matches('/').split('/').peekable();
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                return Some((group.groups.get(part), group.datasets.get(part)));
+            }
+            group = group.groups.get(part)?;
+        }
+        Some((Some(group), None))
+    }
+
+    fn dataset(&self, dataset: &Dataset) -> Result<Option<MatType>> {
+        let Some(class) = matlab_class(&dataset.attributes) else {
+            return Ok(None);
+        };
+
+        // Empty arrays store their dimensions as the dataset contents
+        if integer_attribute(&dataset.attributes, "MATLAB_empty").unwrap_or(0) != 0 {
+            let dims: Vec<usize> = values::<u64>(dataset)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|d| d as usize)
+                .collect();
+            let dims = if dims.is_empty() { vec![0, 0] } else { dims };
+            return Ok(Some(empty_value(class, &dims)));
+        }
+
+        let dims = mat_dims(dataset);
+        let value = match class {
+            "double" => MatType::Double(column_major_array(&dims, values(dataset)?)?),
+            "single" => MatType::Single(column_major_array(&dims, values(dataset)?)?),
+            "int8" => MatType::Int8(column_major_array(&dims, values(dataset)?)?),
+            "uint8" => MatType::UInt8(column_major_array(&dims, values(dataset)?)?),
+            "int16" => MatType::Int16(column_major_array(&dims, values(dataset)?)?),
+            "uint16" => MatType::UInt16(column_major_array(&dims, values(dataset)?)?),
+            "int32" => MatType::Int32(column_major_array(&dims, values(dataset)?)?),
+            "uint32" => MatType::UInt32(column_major_array(&dims, values(dataset)?)?),
+            "int64" => MatType::Int64(column_major_array(&dims, values(dataset)?)?),
+            "uint64" => MatType::UInt64(column_major_array(&dims, u64_values(dataset)?)?),
+            "logical" => {
+                let flags: Vec<u8> = values(dataset)?;
+                MatType::Logical(column_major_array(
+                    &dims,
+                    flags.into_iter().map(|v| v != 0).collect(),
+                )?)
+            }
+            "char" => MatType::Char(char_array_to_string(&dims, &values::<u32>(dataset)?)),
+            "cell" => {
+                let bytes = match &dataset.data {
+                    DataArray::Binary(bytes) => bytes,
+                    _ => {
+                        return Err(IoError::FormatError(format!(
+                            "Cell array '{}' does not hold object references",
+                            dataset.name
+                        )))
+                    }
+                };
+                let mut cells = Vec::with_capacity(bytes.len() / 8);
+                for chunk in bytes.chunks_exact(8) {
+                    let address = u64::from_le_bytes(chunk.try_into().expect("8-byte chunk"));
+                    cells.push(self.reference(address)?);
+                }
+                MatType::Cell(cells)
+            }
+            // Function handles, objects and other classes are not supported
+            _ => return Ok(None),
+        };
+        Ok(Some(value))
+    }
+
+    fn group(&self, group: &Group) -> Result<Option<MatType>> {
+        let Some(class) = matlab_class(&group.attributes) else {
+            return Ok(None);
+        };
+
+        if let Some(rows) = integer_attribute(&group.attributes, "MATLAB_sparse") {
+            return self.sparse(group, class, rows as usize).map(Some);
+        }
+
+        if class != "struct" {
+            return Ok(None);
+        }
+        let mut fields = HashMap::new();
+        for (name, dataset) in &group.datasets {
+            if let Some(value) = self.dataset(dataset)? {
+                fields.insert(name.clone(), value);
+            }
+        }
+        for (name, child) in &group.groups {
+            if let Some(value) = self.group(child)? {
+                fields.insert(name.clone(), value);
+            }
+        }
+        Ok(Some(MatType::Struct(fields)))
+    }
+
+    fn sparse(&self, group: &Group, class: &str, rows: usize) -> Result<MatType> {
+        let member = |name: &str| {
+            group.datasets.get(name).ok_or_else(|| {
+                IoError::FormatError(format!("Sparse matrix '{}' has no '{}'", group.name, name))
+            })
+        };
+        let col_ptrs: Vec<usize> = values(member("jc")?)?;
+        let cols = col_ptrs.len().saturating_sub(1);
+        let nnz = col_ptrs.last().copied().unwrap_or(0);
+        // Sparse matrices without stored values have no "ir" or "data"
+        let (row_indices, values) = if nnz == 0 {
+            (Vec::new(), Vec::new())
+        } else {
+            (values(member("ir")?)?, values(member("data")?)?)
+        };
+        if row_indices.len() != nnz || values.len() != nnz {
+            return Err(IoError::FormatError(format!(
+                "Sparse matrix '{}' is inconsistent",
+                group.name
+            )));
+        }
+        Ok(MatType::Sparse(SparseMatrix {
+            rows,
+            cols,
+            row_indices,
+            col_ptrs,
+            values,
+            logical: class == "logical",
+        }))
+    }
+
+    /// Resolve an object reference stored in a cell array
+    fn reference(&self, address: u64) -> Result<MatType> {
+        let path = self.paths.get(&address).ok_or_else(|| {
+            IoError::FormatError(format!("Dangling object reference {:#x}", address))
+        })?;
+        let value = match self.resolve(path) {
+            Some((_, Some(dataset))) => self.dataset(dataset)?,
+            Some((Some(group), None)) => self.group(group)?,
+            _ => None,
+        };
+        // Unsupported classes become empty arrays to keep cell positions intact
+        Ok(value.unwrap_or_else(|| MatType::Double(ArrayD::zeros(vec![0, 0]))))
+    }
+}
+
+/// Empty value of a MATLAB class
+fn empty_value(class: &str, dims: &[usize]) -> MatType {
+    let dims = dims.to_vec();
+    match class {
+        "single" => MatType::Single(ArrayD::zeros(dims)),
+        "int8" => MatType::Int8(ArrayD::zeros(dims)),
+        "uint8" => MatType::UInt8(ArrayD::zeros(dims)),
+        "int16" => MatType::Int16(ArrayD::zeros(dims)),
+        "uint16" => MatType::UInt16(ArrayD::zeros(dims)),
+        "int32" => MatType::Int32(ArrayD::zeros(dims)),
+        "uint32" => MatType::UInt32(ArrayD::zeros(dims)),
+        "int64" => MatType::Int64(ArrayD::zeros(dims)),
+        "uint64" => MatType::UInt64(ArrayD::zeros(dims)),
+        "logical" => MatType::Logical(ArrayD::from_elem(dims, false)),
+        "char" => MatType::Char(String::new()),
+        "cell" => MatType::Cell(Vec::new()),
+        "struct" => MatType::Struct(HashMap::new()),
+        _ => MatType::Double(ArrayD::zeros(dims)),
+    }
+}
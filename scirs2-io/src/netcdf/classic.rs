@@ -0,0 +1,617 @@
+//! Encoding and decoding of the NetCDF classic file formats
+//!
+//! Implements the header and data layout of the "classic" (CDF-1) and
+//! "64-bit offset" (CDF-2) formats as described in the NetCDF Users Guide.
+//! All values are stored big-endian and every header element and
+//! non-record variable is padded to a multiple of four bytes.
+
+use num_traits::{NumCast, ToPrimitive};
+use std::io::Read;
+
+use super::{AttributeValue, NetCDFDataType};
+use crate::error::{IoError, Result};
+
+/// Tag introducing the dimension list
+const NC_DIMENSION: u32 = 0x0A;
+/// Tag introducing the variable list
+const NC_VARIABLE: u32 = 0x0B;
+/// Tag introducing an attribute list
+const NC_ATTRIBUTE: u32 = 0x0C;
+/// `numrecs` value written by streaming producers that did not know the record count
+const STREAMING: u32 = 0xFFFF_FFFF;
+
+/// Parsed or to-be-written file header
+#[derive(Debug, Clone)]
+pub(crate) struct Header {
+    /// Format version: 1 for classic, 2 for 64-bit offset
+    pub version: u8,
+    /// Number of records, `None` when the header uses the streaming marker
+    pub numrecs: Option<usize>,
+    /// Dimensions in definition order; a length of 0 marks the unlimited dimension
+    pub dimensions: Vec<(String, usize)>,
+    /// Global attributes
+    pub attributes: Vec<(String, AttributeValue)>,
+    /// Variables in definition order
+    pub variables: Vec<VariableHeader>,
+}
+
+/// Header entry of a single variable
+#[derive(Debug, Clone)]
+pub(crate) struct VariableHeader {
+    /// Variable name
+    pub name: String,
+    /// Indices into the dimension list
+    pub dimension_ids: Vec<usize>,
+    /// Variable attributes
+    pub attributes: Vec<(String, AttributeValue)>,
+    /// External data type
+    pub data_type: NetCDFDataType,
+    /// Offset of the variable's data (of its first record for record variables)
+    pub begin: u64,
+}
+
+/// Numeric code of a data type in the header
+fn type_code(data_type: NetCDFDataType) -> u32 {
+    match data_type {
+        NetCDFDataType::Byte => 1,
+        NetCDFDataType::Char => 2,
+        NetCDFDataType::Short => 3,
+        NetCDFDataType::Int => 4,
+        NetCDFDataType::Float => 5,
+        NetCDFDataType::Double => 6,
+    }
+}
+
+/// Data type corresponding to a header type code
+fn type_from_code(code: u32) -> Result<NetCDFDataType> {
+    match code {
+        1 => Ok(NetCDFDataType::Byte),
+        2 => Ok(NetCDFDataType::Char),
+        3 => Ok(NetCDFDataType::Short),
+        4 => Ok(NetCDFDataType::Int),
+        5 => Ok(NetCDFDataType::Float),
+        6 => Ok(NetCDFDataType::Double),
+        _ => Err(IoError::FormatError(format!(
+            "Unsupported NetCDF data type code {}",
+            code
+        ))),
+    }
+}
+
+/// Size in bytes of one element of the given type
+pub(crate) fn type_size(data_type: NetCDFDataType) -> usize {
+    match data_type {
+        NetCDFDataType::Byte | NetCDFDataType::Char => 1,
+        NetCDFDataType::Short => 2,
+        NetCDFDataType::Int | NetCDFDataType::Float => 4,
+        NetCDFDataType::Double => 8,
+    }
+}
+
+/// Default fill value used for data that was never written
+pub(crate) fn default_fill(data_type: NetCDFDataType) -> f64 {
+    match data_type {
+        NetCDFDataType::Byte => -127.0,
+        NetCDFDataType::Char => 0.0,
+        NetCDFDataType::Short => -32767.0,
+        NetCDFDataType::Int => -2147483647.0,
+        NetCDFDataType::Float => 9.969_21e36_f32 as f64,
+        NetCDFDataType::Double => 9.969_209_968_386_869e36,
+    }
+}
+
+/// Round a size up to the next multiple of four
+pub(crate) fn pad4(size: u64) -> u64 {
+    (size + 3) & !3
+}
+
+/// Append one value converted to `data_type`, returning `None` if it does not fit
+pub(crate) fn encode_value<T: ToPrimitive>(
+    value: &T,
+    data_type: NetCDFDataType,
+    out: &mut Vec<u8>,
+) -> Option<()> {
+    match data_type {
+        NetCDFDataType::Byte => out.push(value.to_i8()? as u8),
+        NetCDFDataType::Char => out.push(value.to_u8()?),
+        NetCDFDataType::Short => out.extend_from_slice(&value.to_i16()?.to_be_bytes()),
+        NetCDFDataType::Int => out.extend_from_slice(&value.to_i32()?.to_be_bytes()),
+        NetCDFDataType::Float => out.extend_from_slice(&value.to_f32()?.to_be_bytes()),
+        NetCDFDataType::Double => out.extend_from_slice(&value.to_f64()?.to_be_bytes()),
+    }
+    Some(())
+}
+
+/// Decode one big-endian element of `data_type`, returning `None` if it does not fit in `T`
+pub(crate) fn decode_value<T: NumCast>(bytes: &[u8], data_type: NetCDFDataType) -> Option<T> {
+    match data_type {
+        NetCDFDataType::Byte => T::from(bytes[0] as i8),
+        NetCDFDataType::Char => T::from(bytes[0]),
+        NetCDFDataType::Short => T::from(i16::from_be_bytes([bytes[0], bytes[1]])),
+        NetCDFDataType::Int => T::from(i32::from_be_bytes(bytes[..4].try_into().ok()?)),
+        NetCDFDataType::Float => T::from(f32::from_be_bytes(bytes[..4].try_into().ok()?)),
+        NetCDFDataType::Double => T::from(f64::from_be_bytes(bytes[..8].try_into().ok()?)),
+    }
+}
+
+/// Sequential big-endian reader over the header bytes
+struct HeaderReader<R: Read> {
+    inner: R,
+    /// Bytes left in the input, which bounds every length read from the header
+    remaining: u64,
+}
+
+impl<R: Read> HeaderReader<R> {
+    fn bytes(&mut self, len: usize) -> Result<Vec<u8>> {
+        if len as u64 > self.remaining {
+            return Err(IoError::FormatError(format!(
+                "Truncated NetCDF header: {} bytes needed but only {} remain",
+                len, self.remaining
+            )));
+        }
+        self.remaining -= len as u64;
+        let mut buf = vec![0u8; len];
+        self.inner
+            .read_exact(&mut buf)
+            .map_err(|e| IoError::FormatError(format!("Truncated NetCDF header: {}", e)))?;
+        Ok(buf)
+    }
+
+    /// Read `len` bytes followed by the padding up to a four byte boundary
+    fn padded(&mut self, len: usize) -> Result<Vec<u8>> {
+        let mut buf = self.bytes(pad4(len as u64) as usize)?;
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let buf = self.bytes(4)?;
+        Ok(u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let buf = self.bytes(8)?;
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&buf);
+        Ok(u64::from_be_bytes(word))
+    }
+
+    fn name(&mut self) -> Result<String> {
+        let len = self.u32()? as usize;
+        let bytes = self.padded(len)?;
+        String::from_utf8(bytes)
+            .map_err(|_| IoError::FormatError("NetCDF name is not valid UTF-8".to_string()))
+    }
+
+    /// Read a list header, returning the number of elements (0 for ABSENT)
+    fn list(&mut self, tag: u32) -> Result<usize> {
+        let found = self.u32()?;
+        let count = self.u32()? as usize;
+        if found == 0 && count == 0 {
+            Ok(0)
+        } else if found == tag {
+            Ok(count)
+        } else {
+            Err(IoError::FormatError(format!(
+                "Expected NetCDF list tag {:#x}, found {:#x}",
+                tag, found
+            )))
+        }
+    }
+
+    fn attributes(&mut self) -> Result<Vec<(String, AttributeValue)>> {
+        let count = self.list(NC_ATTRIBUTE)?;
+        let mut attributes = Vec::with_capacity(count.min(1024));
+        for _ in 0..count {
+            let name = self.name()?;
+            let data_type = type_from_code(self.u32()?)?;
+            let nelems = self.u32()? as usize;
+            let size = nelems.checked_mul(type_size(data_type)).ok_or_else(|| {
+                IoError::FormatError(format!("NetCDF attribute '{}' is too large", name))
+            })?;
+            let raw = self.padded(size)?;
+            attributes.push((name, decode_attribute(data_type, &raw)));
+        }
+        Ok(attributes)
+    }
+}
+
+/// Convert the raw values of an attribute into an `AttributeValue`
+fn decode_attribute(data_type: NetCDFDataType, raw: &[u8]) -> AttributeValue {
+    let size = type_size(data_type);
+    match data_type {
+        NetCDFDataType::Char => {
+            // Text attributes are frequently NUL terminated
+            let end = raw.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+            AttributeValue::String(String::from_utf8_lossy(&raw[..end]).into_owned())
+        }
+        NetCDFDataType::Byte => {
+            let values: Vec<i8> = raw.iter().map(|&b| b as i8).collect();
+            match values.as_slice() {
+                [v] => AttributeValue::Byte(*v),
+                _ => AttributeValue::ByteArray(values),
+            }
+        }
+        NetCDFDataType::Short => {
+            let values: Vec<i16> = raw
+                .chunks_exact(size)
+                .map(|c| i16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            match values.as_slice() {
+                [v] => AttributeValue::Short(*v),
+                _ => AttributeValue::ShortArray(values),
+            }
+        }
+        NetCDFDataType::Int => {
+            let values: Vec<i32> = raw
+                .chunks_exact(size)
+                .map(|c| i32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+                .collect();
+            match values.as_slice() {
+                [v] => AttributeValue::Int(*v),
+                _ => AttributeValue::IntArray(values),
+            }
+        }
+        NetCDFDataType::Float => {
+            let values: Vec<f32> = raw
+                .chunks_exact(size)
+                .map(|c| f32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+                .collect();
+            match values.as_slice() {
+                [v] => AttributeValue::Float(*v),
+                _ => AttributeValue::FloatArray(values),
+            }
+        }
+        NetCDFDataType::Double => {
+            let values: Vec<f64> = raw
+                .chunks_exact(size)
+                .map(|c| {
+                    let mut word = [0u8; 8];
+                    word.copy_from_slice(c);
+                    f64::from_be_bytes(word)
+                })
+                .collect();
+            match values.as_slice() {
+                [v] => AttributeValue::Double(*v),
+                _ => AttributeValue::DoubleArray(values),
+            }
+        }
+    }
+}
+
+/// Parse the header at the start of a classic or 64-bit offset file of `len` bytes
+pub(crate) fn read_header<R: Read>(reader: R, len: u64) -> Result<Header> {
+    let mut r = HeaderReader {
+        inner: reader,
+        remaining: len,
+    };
+
+    let magic = r.bytes(4)?;
+    if &magic[..3] != b"CDF" {
+        return Err(IoError::FormatError("Not a NetCDF file".to_string()));
+    }
+    let version = magic[3];
+    match version {
+        1 | 2 => {}
+        5 => {
+            return Err(IoError::FormatError(
+                "The 64-bit data (CDF-5) NetCDF format is not supported".to_string(),
+            ))
+        }
+        _ => {
+            return Err(IoError::FormatError(format!(
+                "Unknown NetCDF format version {}",
+                version
+            )))
+        }
+    }
+
+    let numrecs = match r.u32()? {
+        STREAMING => None,
+        n => Some(n as usize),
+    };
+
+    let ndims = r.list(NC_DIMENSION)?;
+    let mut dimensions = Vec::with_capacity(ndims.min(1024));
+    for _ in 0..ndims {
+        let name = r.name()?;
+        let length = r.u32()? as usize;
+        dimensions.push((name, length));
+    }
+
+    let attributes = r.attributes()?;
+
+    let nvars = r.list(NC_VARIABLE)?;
+    let mut variables = Vec::with_capacity(nvars.min(1024));
+    for _ in 0..nvars {
+        let name = r.name()?;
+        let rank = r.u32()? as usize;
+        let mut dimension_ids = Vec::with_capacity(rank.min(1024));
+        for position in 0..rank {
+            let id = r.u32()? as usize;
+            let (dim_name, length) = dimensions.get(id).ok_or_else(|| {
+                IoError::FormatError(format!(
+                    "Variable '{}' refers to unknown dimension {}",
+                    name, id
+                ))
+            })?;
+            if *length == 0 && position != 0 {
+                return Err(IoError::FormatError(format!(
+                    "Unlimited dimension '{}' is not the first dimension of '{}'",
+                    dim_name, name
+                )));
+            }
+            dimension_ids.push(id);
+        }
+        let var_attributes = r.attributes()?;
+        let data_type = type_from_code(r.u32()?)?;
+        // vsize is redundant (and clamped for huge variables), so it is
+        // recomputed from the shape instead of being trusted
+        let _vsize = r.u32()?;
+        let begin = if version == 1 {
+            r.u32()? as u64
+        } else {
+            r.u64()?
+        };
+        variables.push(VariableHeader {
+            name,
+            dimension_ids,
+            attributes: var_attributes,
+            data_type,
+            begin,
+        });
+    }
+
+    Ok(Header {
+        version,
+        numrecs,
+        dimensions,
+        attributes,
+        variables,
+    })
+}
+
+impl Header {
+    /// Whether the variable is a record variable
+    pub fn is_record(&self, var: &VariableHeader) -> bool {
+        var.dimension_ids
+            .first()
+            .is_some_and(|&id| self.dimensions[id].1 == 0)
+    }
+
+    /// Unpadded size in bytes of the variable (of one record for record variables)
+    pub fn unpadded_size(&self, var: &VariableHeader) -> u64 {
+        let skip = self.is_record(var) as usize;
+        var.dimension_ids[skip..]
+            .iter()
+            .map(|&id| self.dimensions[id].1 as u64)
+            .product::<u64>()
+            * type_size(var.data_type) as u64
+    }
+
+    /// Size in bytes the variable occupies in the file (per record for record variables)
+    pub fn vsize(&self, var: &VariableHeader) -> u64 {
+        pad4(self.unpadded_size(var))
+    }
+
+    /// Size in bytes of one record, covering all record variables
+    pub fn record_size(&self) -> u64 {
+        let records: Vec<&VariableHeader> = self
+            .variables
+            .iter()
+            .filter(|v| self.is_record(v))
+            .collect();
+        match records.as_slice() {
+            // A lone record variable is stored without record padding
+            [only] => self.unpadded_size(only),
+            _ => records.iter().map(|v| self.vsize(v)).sum(),
+        }
+    }
+
+    /// Encode the header into its on-disk representation
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        out.extend_from_slice(b"CDF");
+        out.push(self.version);
+        put_count(&mut out, self.numrecs.unwrap_or(0), "record count")?;
+
+        if self.dimensions.is_empty() {
+            out.extend_from_slice(&[0u8; 8]);
+        } else {
+            out.extend_from_slice(&NC_DIMENSION.to_be_bytes());
+            put_count(&mut out, self.dimensions.len(), "dimension count")?;
+            for (name, length) in &self.dimensions {
+                put_name(&mut out, name)?;
+                put_count(&mut out, *length, "dimension length")?;
+            }
+        }
+
+        encode_attributes(&mut out, &self.attributes)?;
+
+        if self.variables.is_empty() {
+            out.extend_from_slice(&[0u8; 8]);
+        } else {
+            out.extend_from_slice(&NC_VARIABLE.to_be_bytes());
+            put_count(&mut out, self.variables.len(), "variable count")?;
+            for var in &self.variables {
+                put_name(&mut out, &var.name)?;
+                put_count(&mut out, var.dimension_ids.len(), "variable rank")?;
+                for &id in &var.dimension_ids {
+                    out.extend_from_slice(&(id as u32).to_be_bytes());
+                }
+                encode_attributes(&mut out, &var.attributes)?;
+                out.extend_from_slice(&type_code(var.data_type).to_be_bytes());
+                // Sizes beyond the 32-bit range are clamped as libnetcdf does
+                let vsize = self.vsize(var).min(u32::MAX as u64) as u32;
+                out.extend_from_slice(&vsize.to_be_bytes());
+                if self.version == 1 {
+                    let begin = u32::try_from(var.begin).map_err(|_| {
+                        IoError::FormatError(format!(
+                            "Variable '{}' starts beyond the 4 GiB limit of the classic format; \
+                             use the 64-bit offset format",
+                            var.name
+                        ))
+                    })?;
+                    out.extend_from_slice(&begin.to_be_bytes());
+                } else {
+                    out.extend_from_slice(&var.begin.to_be_bytes());
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+fn put_count(out: &mut Vec<u8>, value: usize, what: &str) -> Result<()> {
+    let value = u32::try_from(value)
+        .map_err(|_| IoError::FormatError(format!("NetCDF {} {} is too large", what, value)))?;
+    out.extend_from_slice(&value.to_be_bytes());
+    Ok(())
+}
+
+fn put_padded(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(bytes);
+    out.resize(pad4(out.len() as u64) as usize, 0);
+}
+
+fn put_name(out: &mut Vec<u8>, name: &str) -> Result<()> {
+    put_count(out, name.len(), "name length")?;
+    put_padded(out, name.as_bytes());
+    Ok(())
+}
+
+fn encode_attributes(out: &mut Vec<u8>, attributes: &[(String, AttributeValue)]) -> Result<()> {
+    if attributes.is_empty() {
+        out.extend_from_slice(&[0u8; 8]);
+        return Ok(());
+    }
+    out.extend_from_slice(&NC_ATTRIBUTE.to_be_bytes());
+    put_count(out, attributes.len(), "attribute count")?;
+    for (name, value) in attributes {
+        put_name(out, name)?;
+        let (data_type, nelems, raw): (NetCDFDataType, usize, Vec<u8>) = match value {
+            AttributeValue::String(s) => (NetCDFDataType::Char, s.len(), s.as_bytes().to_vec()),
+            AttributeValue::Byte(v) => (NetCDFDataType::Byte, 1, vec![*v as u8]),
+            AttributeValue::Short(v) => (NetCDFDataType::Short, 1, v.to_be_bytes().to_vec()),
+            AttributeValue::Int(v) => (NetCDFDataType::Int, 1, v.to_be_bytes().to_vec()),
+            AttributeValue::Float(v) => (NetCDFDataType::Float, 1, v.to_be_bytes().to_vec()),
+            AttributeValue::Double(v) => (NetCDFDataType::Double, 1, v.to_be_bytes().to_vec()),
+            AttributeValue::ByteArray(v) => (
+                NetCDFDataType::Byte,
+                v.len(),
+                v.iter().map(|&b| b as u8).collect(),
+            ),
+            AttributeValue::ShortArray(v) => (
+                NetCDFDataType::Short,
+                v.len(),
+                v.iter().flat_map(|x| x.to_be_bytes()).collect(),
+            ),
+            AttributeValue::IntArray(v) => (
+                NetCDFDataType::Int,
+                v.len(),
+                v.iter().flat_map(|x| x.to_be_bytes()).collect(),
+            ),
+            AttributeValue::FloatArray(v) => (
+                NetCDFDataType::Float,
+                v.len(),
+                v.iter().flat_map(|x| x.to_be_bytes()).collect(),
+            ),
+            AttributeValue::DoubleArray(v) => (
+                NetCDFDataType::Double,
+                v.len(),
+                v.iter().flat_map(|x| x.to_be_bytes()).collect(),
+            ),
+        };
+        out.extend_from_slice(&type_code(data_type).to_be_bytes());
+        put_count(out, nelems, "attribute length")?;
+        put_padded(out, &raw);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_header_layout() {
+        let header = Header {
+            version: 1,
+            numrecs: Some(0),
+            dimensions: Vec::new(),
+            attributes: Vec::new(),
+            variables: Vec::new(),
+        };
+        let bytes = header.encode().unwrap();
+        // magic, numrecs and three ABSENT lists
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(&bytes[..4], b"CDF\x01");
+        assert!(bytes[4..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_lengths_beyond_input() {
+        // A dimension list whose only name claims to be almost 4 GiB long
+        let mut bytes = b"CDF\x01".to_vec();
+        for word in [0, NC_DIMENSION, 1, 0xFFFF_FFF0] {
+            bytes.extend_from_slice(&word.to_be_bytes());
+        }
+        assert!(read_header(bytes.as_slice(), bytes.len() as u64).is_err());
+
+        // Attribute sizes are checked the same way
+        let mut bytes = b"CDF\x01".to_vec();
+        for word in [
+            0,
+            0,
+            0,
+            NC_ATTRIBUTE,
+            1,
+            1,
+            u32::from_be_bytes(*b"a\0\0\0"),
+            6,
+            0x4000_0000,
+        ] {
+            bytes.extend_from_slice(&word.to_be_bytes());
+        }
+        assert!(read_header(bytes.as_slice(), bytes.len() as u64).is_err());
+    }
+
+    #[test]
+    fn test_header_roundtrip() {
+        let header = Header {
+            version: 2,
+            numrecs: Some(3),
+            dimensions: vec![("time".to_string(), 0), ("x".to_string(), 5)],
+            attributes: vec![("title".to_string(), AttributeValue::String("t".into()))],
+            variables: vec![VariableHeader {
+                name: "v".to_string(),
+                dimension_ids: vec![0, 1],
+                attributes: vec![(
+                    "range".to_string(),
+                    AttributeValue::ShortArray(vec![-1, 7, 9]),
+                )],
+                data_type: NetCDFDataType::Short,
+                begin: 1 << 33,
+            }],
+        };
+        let bytes = header.encode().unwrap();
+        assert_eq!(bytes.len() % 4, 0);
+        let parsed = read_header(bytes.as_slice(), bytes.len() as u64).unwrap();
+        assert_eq!(parsed.version, 2);
+        assert_eq!(parsed.numrecs, Some(3));
+        assert_eq!(parsed.dimensions, header.dimensions);
+        assert_eq!(parsed.variables[0].begin, 1 << 33);
+        assert_eq!(parsed.vsize(&parsed.variables[0]), 12);
+        // A single record variable is not padded
+        assert_eq!(parsed.record_size(), 10);
+        match &parsed.variables[0].attributes[0].1 {
+            AttributeValue::ShortArray(v) => assert_eq!(v, &[-1, 7, 9]),
+            other => panic!("unexpected attribute {:?}", other),
+        }
+
+        // The classic format cannot address data beyond 4 GiB
+        let mut classic = header;
+        classic.version = 1;
+        assert!(classic.encode().is_err());
+    }
+}
@@ -0,0 +1,448 @@
+//! Parquet value encodings
+//!
+//! Implements the PLAIN encoding for every physical type and the
+//! RLE/bit-packed hybrid used for definition levels, booleans and
+//! dictionary indices.
+
+use crate::error::{IoError, Result};
+
+/// Physical types from the Parquet format specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PhysicalType {
+    Boolean,
+    Int32,
+    Int64,
+    Int96,
+    Float,
+    Double,
+    ByteArray,
+    FixedLenByteArray(usize),
+}
+
+impl PhysicalType {
+    pub fn from_thrift(code: i32, type_length: Option<i32>) -> Result<Self> {
+        Ok(match code {
+            0 => PhysicalType::Boolean,
+            1 => PhysicalType::Int32,
+            2 => PhysicalType::Int64,
+            3 => PhysicalType::Int96,
+            4 => PhysicalType::Float,
+            5 => PhysicalType::Double,
+            6 => PhysicalType::ByteArray,
+            7 => PhysicalType::FixedLenByteArray(type_length.unwrap_or(0).max(0) as usize),
+            other => {
+                return Err(IoError::FormatError(format!(
+                    "Unknown Parquet physical type {}",
+                    other
+                )))
+            }
+        })
+    }
+
+    pub fn to_thrift(self) -> i32 {
+        match self {
+            PhysicalType::Boolean => 0,
+            PhysicalType::Int32 => 1,
+            PhysicalType::Int64 => 2,
+            PhysicalType::Int96 => 3,
+            PhysicalType::Float => 4,
+            PhysicalType::Double => 5,
+            PhysicalType::ByteArray => 6,
+            PhysicalType::FixedLenByteArray(_) => 7,
+        }
+    }
+}
+
+/// Decoded values of one physical type
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Values {
+    Boolean(Vec<bool>),
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    Float(Vec<f32>),
+    Double(Vec<f64>),
+    Bytes(Vec<Vec<u8>>),
+}
+
+impl Values {
+    pub fn empty(physical: PhysicalType) -> Self {
+        match physical {
+            PhysicalType::Boolean => Values::Boolean(Vec::new()),
+            PhysicalType::Int32 => Values::Int32(Vec::new()),
+            PhysicalType::Int64 => Values::Int64(Vec::new()),
+            PhysicalType::Float => Values::Float(Vec::new()),
+            PhysicalType::Double => Values::Double(Vec::new()),
+            PhysicalType::Int96 | PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_) => {
+                Values::Bytes(Vec::new())
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Values::Boolean(v) => v.len(),
+            Values::Int32(v) => v.len(),
+            Values::Int64(v) => v.len(),
+            Values::Float(v) => v.len(),
+            Values::Double(v) => v.len(),
+            Values::Bytes(v) => v.len(),
+        }
+    }
+
+    /// Append the values of another buffer of the same type
+    pub fn extend(&mut self, other: Values) -> Result<()> {
+        match (self, other) {
+            (Values::Boolean(a), Values::Boolean(b)) => a.extend(b),
+            (Values::Int32(a), Values::Int32(b)) => a.extend(b),
+            (Values::Int64(a), Values::Int64(b)) => a.extend(b),
+            (Values::Float(a), Values::Float(b)) => a.extend(b),
+            (Values::Double(a), Values::Double(b)) => a.extend(b),
+            (Values::Bytes(a), Values::Bytes(b)) => a.extend(b),
+            _ => {
+                return Err(IoError::FormatError(
+                    "Parquet page has a different type than its column".to_string(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Look up dictionary entries
+    pub fn take(&self, indices: &[u32]) -> Result<Values> {
+        fn gather<T: Clone>(dict: &[T], indices: &[u32]) -> Result<Vec<T>> {
+            indices
+                .iter()
+                .map(|&i| {
+                    dict.get(i as usize).cloned().ok_or_else(|| {
+                        IoError::FormatError(format!("Parquet dictionary index {} out of range", i))
+                    })
+                })
+                .collect()
+        }
+        Ok(match self {
+            Values::Boolean(d) => Values::Boolean(gather(d, indices)?),
+            Values::Int32(d) => Values::Int32(gather(d, indices)?),
+            Values::Int64(d) => Values::Int64(gather(d, indices)?),
+            Values::Float(d) => Values::Float(gather(d, indices)?),
+            Values::Double(d) => Values::Double(gather(d, indices)?),
+            Values::Bytes(d) => Values::Bytes(gather(d, indices)?),
+        })
+    }
+}
+
+fn truncated<T>() -> Result<T> {
+    Err(IoError::FormatError("Truncated Parquet page".to_string()))
+}
+
+/// Number of bits needed to store `max_value`
+pub(crate) fn bit_width(max_value: u64) -> u8 {
+    (64 - max_value.leading_zeros()) as u8
+}
+
+/// Decode `count` PLAIN-encoded values, returning them and the bytes consumed
+pub(crate) fn decode_plain(
+    data: &[u8],
+    physical: PhysicalType,
+    count: usize,
+) -> Result<(Values, usize)> {
+    fn fixed<const N: usize, T>(
+        data: &[u8],
+        count: usize,
+        convert: fn([u8; N]) -> T,
+    ) -> Result<(Vec<T>, usize)> {
+        let Some(bytes) = count.checked_mul(N).and_then(|n| data.get(..n)) else {
+            return truncated();
+        };
+        let values = bytes
+            .chunks_exact(N)
+            .map(|c| convert(c.try_into().expect("chunk size")))
+            .collect();
+        Ok((values, count * N))
+    }
+
+    Ok(match physical {
+        PhysicalType::Boolean => {
+            let nbytes = count.div_ceil(8);
+            let Some(bytes) = data.get(..nbytes) else {
+                return truncated();
+            };
+            let values = (0..count)
+                .map(|i| bytes[i / 8] >> (i % 8) & 1 == 1)
+                .collect();
+            (Values::Boolean(values), nbytes)
+        }
+        PhysicalType::Int32 => {
+            let (v, n) = fixed(data, count, i32::from_le_bytes)?;
+            (Values::Int32(v), n)
+        }
+        PhysicalType::Int64 => {
+            let (v, n) = fixed(data, count, i64::from_le_bytes)?;
+            (Values::Int64(v), n)
+        }
+        PhysicalType::Float => {
+            let (v, n) = fixed(data, count, f32::from_le_bytes)?;
+            (Values::Float(v), n)
+        }
+        PhysicalType::Double => {
+            let (v, n) = fixed(data, count, f64::from_le_bytes)?;
+            (Values::Double(v), n)
+        }
+        PhysicalType::Int96 | PhysicalType::FixedLenByteArray(_) => {
+            let width = match physical {
+                PhysicalType::FixedLenByteArray(w) => w,
+                _ => 12,
+            };
+            let Some(bytes) = count.checked_mul(width).and_then(|n| data.get(..n)) else {
+                return truncated();
+            };
+            let values = if width == 0 {
+                vec![Vec::new(); count]
+            } else {
+                bytes.chunks_exact(width).map(<[u8]>::to_vec).collect()
+            };
+            (Values::Bytes(values), count * width)
+        }
+        PhysicalType::ByteArray => {
+            let mut pos = 0;
+            let mut values = Vec::with_capacity(count.min(data.len() / 4 + 1));
+            for _ in 0..count {
+                let Some(len) = data.get(pos..pos + 4) else {
+                    return truncated();
+                };
+                let len = u32::from_le_bytes(len.try_into().expect("4 bytes")) as usize;
+                pos += 4;
+                let Some(value) = data.get(pos..pos + len) else {
+                    return truncated();
+                };
+                values.push(value.to_vec());
+                pos += len;
+            }
+            (Values::Bytes(values), pos)
+        }
+    })
+}
+
+/// PLAIN-encode values, appending to `out`
+pub(crate) fn encode_plain(values: &Values, out: &mut Vec<u8>) {
+    match values {
+        Values::Boolean(v) => {
+            let start = out.len();
+            out.resize(start + v.len().div_ceil(8), 0);
+            for (i, &b) in v.iter().enumerate() {
+                if b {
+                    out[start + i / 8] |= 1 << (i % 8);
+                }
+            }
+        }
+        Values::Int32(v) => v
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes())),
+        Values::Int64(v) => v
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes())),
+        Values::Float(v) => v
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes())),
+        Values::Double(v) => v
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes())),
+        Values::Bytes(v) => {
+            for x in v {
+                out.extend_from_slice(&(x.len() as u32).to_le_bytes());
+                out.extend_from_slice(x);
+            }
+        }
+    }
+}
+
+fn read_uleb(data: &[u8], pos: &mut usize) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let Some(&b) = data.get(*pos) else {
+            return truncated();
+        };
+        *pos += 1;
+        value |= u64::from(b & 0x7F) << shift;
+        if b & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(IoError::FormatError(
+        "Invalid varint in Parquet page".to_string(),
+    ))
+}
+
+fn write_uleb(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Decode `count` values of the RLE/bit-packed hybrid encoding
+///
+/// `data` must not include the length prefix used for levels.
+pub(crate) fn decode_rle(data: &[u8], bit_width: u8, count: usize) -> Result<Vec<u32>> {
+    if bit_width > 32 {
+        return Err(IoError::FormatError(format!(
+            "Invalid RLE bit width {}",
+            bit_width
+        )));
+    }
+    let width = bit_width as usize;
+    let byte_width = width.div_ceil(8);
+    let mut values = Vec::with_capacity(count);
+    let mut pos = 0;
+    while values.len() < count {
+        let header = read_uleb(data, &mut pos)?;
+        if header & 1 == 1 {
+            // Bit-packed groups of eight values
+            let groups = (header >> 1) as usize;
+            let nbytes = groups * width;
+            let Some(bytes) = data.get(pos..pos + nbytes) else {
+                return truncated();
+            };
+            pos += nbytes;
+            let n = (groups * 8).min(count - values.len());
+            for i in 0..n {
+                let mut value = 0u64;
+                let bit = i * width;
+                for b in 0..width {
+                    let idx = bit + b;
+                    value |= u64::from(bytes[idx / 8] >> (idx % 8) & 1) << b;
+                }
+                values.push(value as u32);
+            }
+        } else {
+            let run = (header >> 1) as usize;
+            let Some(bytes) = data.get(pos..pos + byte_width) else {
+                return truncated();
+            };
+            pos += byte_width;
+            let value = bytes
+                .iter()
+                .rev()
+                .fold(0u32, |acc, &b| (acc << 8) | u32::from(b));
+            let n = run.min(count - values.len());
+            if n == 0 {
+                return Err(IoError::FormatError("Empty RLE run".to_string()));
+            }
+            values.extend(std::iter::repeat_n(value, n));
+        }
+    }
+    Ok(values)
+}
+
+/// Encode values with the RLE/bit-packed hybrid encoding
+///
+/// Runs of eight or more equal values become RLE runs; everything else is
+/// bit-packed in groups of eight, padding the final group with zeros.
+pub(crate) fn encode_rle(values: &[u32], bit_width: u8) -> Vec<u8> {
+    let width = bit_width as usize;
+    let byte_width = width.div_ceil(8);
+    let mut out = Vec::new();
+    let mut literals: Vec<u32> = Vec::new();
+
+    let flush_literals = |out: &mut Vec<u8>, literals: &mut Vec<u32>| {
+        if literals.is_empty() {
+            return;
+        }
+        let groups = literals.len().div_ceil(8);
+        literals.resize(groups * 8, 0);
+        write_uleb(out, ((groups as u64) << 1) | 1);
+        let start = out.len();
+        out.resize(start + groups * width, 0);
+        for (i, &v) in literals.iter().enumerate() {
+            for b in 0..width {
+                if v >> b & 1 == 1 {
+                    let idx = i * width + b;
+                    out[start + idx / 8] |= 1 << (idx % 8);
+                }
+            }
+        }
+        literals.clear();
+    };
+
+    let mut i = 0;
+    while i < values.len() {
+        let value = values[i];
+        let mut run = 1;
+        while i + run < values.len() && values[i + run] == value {
+            run += 1;
+        }
+        // Complete the pending bit-packed group before starting an RLE run
+        let pad = (8 - literals.len() % 8) % 8;
+        if run >= 8 + pad {
+            literals.extend(std::iter::repeat_n(value, pad));
+            flush_literals(&mut out, &mut literals);
+            let rle = run - pad;
+            write_uleb(&mut out, (rle as u64) << 1);
+            out.extend_from_slice(&value.to_le_bytes()[..byte_width]);
+        } else {
+            literals.extend(std::iter::repeat_n(value, run));
+        }
+        i += run;
+    }
+    flush_literals(&mut out, &mut literals);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rle_roundtrip() {
+        let mut values = vec![1u32, 0, 1, 1, 0];
+        values.extend(std::iter::repeat_n(1, 30));
+        values.extend([2, 3, 4, 5, 6, 7, 0, 0, 0]);
+        values.extend(std::iter::repeat_n(5, 9));
+        for width in [3u8, 8, 12, 20] {
+            let encoded = encode_rle(&values, width);
+            assert_eq!(decode_rle(&encoded, width, values.len()).unwrap(), values);
+        }
+        assert!(encode_rle(&[7u32; 1000], 3).len() < 6);
+    }
+
+    #[test]
+    fn test_rle_reference() {
+        // Example from the Parquet specification: 0..=7 bit-packed with width 3
+        let data = [0x03, 0x88, 0xC6, 0xFA];
+        assert_eq!(
+            decode_rle(&data, 3, 8).unwrap(),
+            (0..8).collect::<Vec<u32>>()
+        );
+    }
+
+    #[test]
+    fn test_plain_roundtrip() {
+        let cases = vec![
+            (
+                PhysicalType::Boolean,
+                Values::Boolean(vec![
+                    true, false, true, true, false, false, true, false, true,
+                ]),
+            ),
+            (PhysicalType::Int32, Values::Int32(vec![-1, 0, i32::MAX])),
+            (PhysicalType::Int64, Values::Int64(vec![i64::MIN, 42])),
+            (PhysicalType::Float, Values::Float(vec![1.5, -0.25])),
+            (
+                PhysicalType::Double,
+                Values::Double(vec![std::f64::consts::PI]),
+            ),
+            (
+                PhysicalType::ByteArray,
+                Values::Bytes(vec![b"abc".to_vec(), Vec::new(), b"z".to_vec()]),
+            ),
+        ];
+        for (physical, values) in cases {
+            let mut bytes = Vec::new();
+            encode_plain(&values, &mut bytes);
+            let (decoded, used) = decode_plain(&bytes, physical, values.len()).unwrap();
+            assert_eq!(decoded, values);
+            assert_eq!(used, bytes.len());
+        }
+        assert!(decode_plain(&[1, 2, 3], PhysicalType::Int32, 1).is_err());
+    }
+}
@@ -0,0 +1,484 @@
+//! Apache Parquet file format
+//!
+//! This module reads and writes Parquet files with flat schemas, where every
+//! column is a required or optional primitive value. Files are read into the
+//! column-oriented [`Table`] from the [`columnar`](crate::columnar) module,
+//! from which columns can be taken as `ndarray` arrays or the whole table as
+//! a `scirs2_core::array::RecordArray`.
+//!
+//! Supported features:
+//!
+//! - PLAIN, PLAIN_DICTIONARY / RLE_DICTIONARY and RLE (boolean) encodings
+//! - Version 1 and version 2 data pages
+//! - Uncompressed, Snappy, Gzip and Zstd pages via the
+//!   [`compression`](crate::compression) module
+//! - Column projection and row-group-by-row-group streaming
+//! - Writing with optional dictionary encoding and configurable row groups
+//!
+//! Nested and repeated columns are not supported.
+//!
+//! # Example
+//!
+//! ```
+//! use ndarray::array;
+//! use scirs2_io::columnar::{Column, Table};
+//! use scirs2_io::parquet::{read_parquet_columns, write_parquet};
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! let path = dir.path().join("data.parquet");
+//!
+//! let table = Table::new(vec![
+//!     Column::from_array("time", &array![0.0, 0.5, 1.0]),
+//!     Column::from_array("count", &array![3i64, 1, 4]),
+//! ])
+//! .unwrap();
+//! write_parquet(&path, &table, None).unwrap();
+//!
+//! let counts = read_parquet_columns(&path, &["count"]).unwrap();
+//! assert_eq!(counts.num_columns(), 1);
+//! assert_eq!(counts.column("count").unwrap().to_array::<i64>().unwrap(), array![3, 1, 4]);
+//! ```
+
+use std::path::Path;
+
+use crate::columnar::{DataType, Table};
+use crate::compression::CompressionAlgorithm;
+use crate::error::{IoError, Result};
+
+mod encoding;
+mod reader;
+mod thrift;
+mod writer;
+
+pub use reader::{ParquetReader, RowGroups};
+pub use writer::ParquetWriter;
+
+use encoding::PhysicalType;
+
+/// Magic bytes at the start and end of every Parquet file
+const MAGIC: &[u8; 4] = b"PAR1";
+
+// Converted types from the Parquet format specification
+const CONVERTED_UTF8: i32 = 0;
+const CONVERTED_ENUM: i32 = 4;
+const CONVERTED_UINT_8: i32 = 11;
+const CONVERTED_UINT_16: i32 = 12;
+const CONVERTED_UINT_32: i32 = 13;
+const CONVERTED_UINT_64: i32 = 14;
+const CONVERTED_INT_8: i32 = 15;
+const CONVERTED_INT_16: i32 = 16;
+const CONVERTED_INT_32: i32 = 17;
+const CONVERTED_INT_64: i32 = 18;
+const CONVERTED_JSON: i32 = 19;
+
+/// Page compression codec
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParquetCompression {
+    /// No compression
+    Uncompressed,
+    /// Snappy compression
+    #[default]
+    Snappy,
+    /// Gzip compression
+    Gzip,
+    /// Zstandard compression
+    Zstd,
+}
+
+impl ParquetCompression {
+    fn from_thrift(code: i32) -> Result<Self> {
+        match code {
+            0 => Ok(ParquetCompression::Uncompressed),
+            1 => Ok(ParquetCompression::Snappy),
+            2 => Ok(ParquetCompression::Gzip),
+            6 => Ok(ParquetCompression::Zstd),
+            3 => Err(IoError::UnsupportedCompressionAlgorithm("LZO".to_string())),
+            4 => Err(IoError::UnsupportedCompressionAlgorithm(
+                "BROTLI".to_string(),
+            )),
+            5 | 7 => Err(IoError::UnsupportedCompressionAlgorithm("LZ4".to_string())),
+            other => Err(IoError::UnsupportedCompressionAlgorithm(format!(
+                "Parquet codec {}",
+                other
+            ))),
+        }
+    }
+
+    fn to_thrift(self) -> i32 {
+        match self {
+            ParquetCompression::Uncompressed => 0,
+            ParquetCompression::Snappy => 1,
+            ParquetCompression::Gzip => 2,
+            ParquetCompression::Zstd => 6,
+        }
+    }
+
+    fn algorithm(self) -> Option<CompressionAlgorithm> {
+        match self {
+            ParquetCompression::Uncompressed => None,
+            ParquetCompression::Snappy => Some(CompressionAlgorithm::Snappy),
+            ParquetCompression::Gzip => Some(CompressionAlgorithm::Gzip),
+            ParquetCompression::Zstd => Some(CompressionAlgorithm::Zstd),
+        }
+    }
+}
+
+/// Options for writing Parquet files
+#[derive(Debug, Clone)]
+pub struct ParquetWriteOptions {
+    /// Page compression codec
+    pub compression: ParquetCompression,
+    /// Maximum number of rows per row group
+    pub row_group_size: usize,
+    /// Maximum number of rows per data page
+    pub data_page_size: usize,
+    /// Dictionary-encode columns with many repeated values
+    pub dictionary: bool,
+}
+
+impl Default for ParquetWriteOptions {
+    fn default() -> Self {
+        Self {
+            compression: ParquetCompression::Snappy,
+            row_group_size: 1 << 20,
+            data_page_size: 1 << 16,
+            dictionary: true,
+        }
+    }
+}
+
+/// A leaf column of a flat Parquet schema
+#[derive(Debug, Clone)]
+struct ColumnSchema {
+    name: String,
+    physical: PhysicalType,
+    data_type: DataType,
+    nullable: bool,
+}
+
+/// Logical column type for a physical type and its annotations
+fn logical_type(
+    physical: PhysicalType,
+    converted: Option<i32>,
+    logical: Option<&thrift::Struct>,
+) -> DataType {
+    // INTEGER logical type: bit width and signedness
+    let integer = logical
+        .and_then(|l| l.structure(10))
+        .map(|int| (int.i32(1).unwrap_or(32), int.bool(2).unwrap_or(true)));
+    let is_string = logical.is_some_and(|l| {
+        l.structure(1).is_some() || l.structure(4).is_some() || l.structure(7).is_some()
+    });
+
+    match physical {
+        PhysicalType::Boolean => DataType::Boolean,
+        PhysicalType::Int32 => match (integer, converted) {
+            (Some((8, true)), _) | (None, Some(CONVERTED_INT_8)) => DataType::Int8,
+            (Some((16, true)), _) | (None, Some(CONVERTED_INT_16)) => DataType::Int16,
+            (Some((8, false)), _) | (None, Some(CONVERTED_UINT_8)) => DataType::UInt8,
+            (Some((16, false)), _) | (None, Some(CONVERTED_UINT_16)) => DataType::UInt16,
+            (Some((32, false)), _) | (None, Some(CONVERTED_UINT_32)) => DataType::UInt32,
+            _ => DataType::Int32,
+        },
+        PhysicalType::Int64 => match (integer, converted) {
+            (Some((64, false)), _) | (None, Some(CONVERTED_UINT_64)) => DataType::UInt64,
+            _ => DataType::Int64,
+        },
+        PhysicalType::Float => DataType::Float32,
+        PhysicalType::Double => DataType::Float64,
+        PhysicalType::ByteArray
+            if is_string
+                || matches!(
+                    converted,
+                    Some(CONVERTED_UTF8 | CONVERTED_ENUM | CONVERTED_JSON)
+                ) =>
+        {
+            DataType::Utf8
+        }
+        PhysicalType::Int96 | PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_) => {
+            DataType::Binary
+        }
+    }
+}
+
+/// Physical type, converted type and logical type used to store a column type
+fn storage_type(data_type: DataType) -> (PhysicalType, Option<i32>, Option<thrift::Struct>) {
+    use thrift::{Struct, Value};
+
+    let integer = |bits: i8, signed: bool| {
+        Some(
+            Struct::new().with(
+                10,
+                Value::Struct(
+                    Struct::new()
+                        .with(1, Value::Byte(bits))
+                        .with(2, Value::Bool(signed)),
+                ),
+            ),
+        )
+    };
+    match data_type {
+        DataType::Boolean => (PhysicalType::Boolean, None, None),
+        DataType::Int8 => (PhysicalType::Int32, Some(CONVERTED_INT_8), integer(8, true)),
+        DataType::Int16 => (
+            PhysicalType::Int32,
+            Some(CONVERTED_INT_16),
+            integer(16, true),
+        ),
+        DataType::Int32 => (
+            PhysicalType::Int32,
+            Some(CONVERTED_INT_32),
+            integer(32, true),
+        ),
+        DataType::Int64 => (
+            PhysicalType::Int64,
+            Some(CONVERTED_INT_64),
+            integer(64, true),
+        ),
+        DataType::UInt8 => (
+            PhysicalType::Int32,
+            Some(CONVERTED_UINT_8),
+            integer(8, false),
+        ),
+        DataType::UInt16 => (
+            PhysicalType::Int32,
+            Some(CONVERTED_UINT_16),
+            integer(16, false),
+        ),
+        DataType::UInt32 => (
+            PhysicalType::Int32,
+            Some(CONVERTED_UINT_32),
+            integer(32, false),
+        ),
+        DataType::UInt64 => (
+            PhysicalType::Int64,
+            Some(CONVERTED_UINT_64),
+            integer(64, false),
+        ),
+        DataType::Float32 => (PhysicalType::Float, None, None),
+        DataType::Float64 => (PhysicalType::Double, None, None),
+        DataType::Utf8 => (
+            PhysicalType::ByteArray,
+            Some(CONVERTED_UTF8),
+            Some(Struct::new().with(1, Value::Struct(Struct::new()))),
+        ),
+        DataType::Binary => (PhysicalType::ByteArray, None, None),
+    }
+}
+
+/// Read a whole Parquet file into a table
+///
+/// # Arguments
+///
+/// * `path` - Path to the Parquet file
+///
+/// # Returns
+///
+/// A table with one column per leaf column of the file's schema
+pub fn read_parquet<P: AsRef<Path>>(path: P) -> Result<Table> {
+    ParquetReader::open(path)?.read_all(None)
+}
+
+/// Read selected columns of a Parquet file into a table
+///
+/// Only the column chunks of the requested columns are read from disk.
+///
+/// # Arguments
+///
+/// * `path` - Path to the Parquet file
+/// * `columns` - Names of the columns to read, in the order they should appear
+///
+/// # Returns
+///
+/// A table holding the requested columns
+pub fn read_parquet_columns<P: AsRef<Path>>(path: P, columns: &[&str]) -> Result<Table> {
+    ParquetReader::open(path)?.read_all(Some(columns))
+}
+
+/// Write a table to a Parquet file
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to create
+/// * `table` - The table to write
+/// * `options` - Write options (defaults to Snappy pages with dictionary encoding)
+pub fn write_parquet<P: AsRef<Path>>(
+    path: P,
+    table: &Table,
+    options: Option<ParquetWriteOptions>,
+) -> Result<()> {
+    let mut writer = ParquetWriter::create(path, &table.schema(), options)?;
+    writer.write_table(table)?;
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::columnar::{Column, ColumnData};
+    use ndarray::array;
+    use tempfile::tempdir;
+
+    fn sample_table() -> Table {
+        let n = 1000;
+        Table::new(vec![
+            Column::new("id", ColumnData::Int64((0..n as i64).collect())),
+            Column::new(
+                "value",
+                ColumnData::Float64((0..n).map(|i| i as f64 * 0.5).collect()),
+            ),
+            Column::with_validity(
+                "category",
+                ColumnData::Utf8((0..n).map(|i| format!("c{}", i % 4)).collect()),
+                (0..n).map(|i| i % 7 != 0).collect(),
+            )
+            .unwrap(),
+            Column::new(
+                "flag",
+                ColumnData::Boolean((0..n).map(|i| i % 3 == 0).collect()),
+            ),
+            Column::with_validity(
+                "small",
+                ColumnData::UInt8((0..n).map(|i| (i % 200) as u8).collect()),
+                (0..n).map(|i| i % 5 != 1).collect(),
+            )
+            .unwrap(),
+            Column::new(
+                "f32",
+                ColumnData::Float32((0..n).map(|i| i as f32).collect()),
+            ),
+            Column::new(
+                "bytes",
+                ColumnData::Binary((0..n).map(|i| vec![i as u8; i % 3]).collect()),
+            ),
+            Column::new(
+                "i8",
+                ColumnData::Int8((0..n).map(|i| (i % 100) as i8 - 50).collect()),
+            ),
+            Column::new(
+                "u64",
+                ColumnData::UInt64((0..n).map(|i| u64::MAX - i as u64).collect()),
+            ),
+        ])
+        .unwrap()
+    }
+
+    /// Null entries hold unspecified values, so compare them through the mask
+    fn assert_tables_equal(a: &Table, b: &Table) {
+        assert_eq!(a.schema(), b.schema());
+        assert_eq!(a.num_rows(), b.num_rows());
+        for (ca, cb) in a.columns().iter().zip(b.columns()) {
+            assert_eq!(ca.validity, cb.validity, "validity of {}", ca.name);
+            let valid: Vec<Option<usize>> =
+                (0..ca.len()).map(|i| ca.is_valid(i).then_some(i)).collect();
+            assert_eq!(
+                ca.data.take(&valid).unwrap(),
+                cb.data.take(&valid).unwrap(),
+                "column {}",
+                ca.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_all_codecs() {
+        let dir = tempdir().unwrap();
+        let table = sample_table();
+        for compression in [
+            ParquetCompression::Uncompressed,
+            ParquetCompression::Snappy,
+            ParquetCompression::Gzip,
+            ParquetCompression::Zstd,
+        ] {
+            for dictionary in [false, true] {
+                let path = dir
+                    .path()
+                    .join(format!("{:?}_{}.parquet", compression, dictionary));
+                let options = ParquetWriteOptions {
+                    compression,
+                    row_group_size: 300,
+                    data_page_size: 128,
+                    dictionary,
+                };
+                write_parquet(&path, &table, Some(options)).unwrap();
+                let read = read_parquet(&path).unwrap();
+                assert_tables_equal(&table, &read);
+            }
+        }
+    }
+
+    #[test]
+    fn test_projection_and_row_groups() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("groups.parquet");
+        let table = sample_table();
+        let options = ParquetWriteOptions {
+            row_group_size: 256,
+            ..Default::default()
+        };
+        write_parquet(&path, &table, Some(options)).unwrap();
+
+        let mut reader = ParquetReader::open(&path).unwrap();
+        assert_eq!(reader.num_rows(), 1000);
+        assert_eq!(reader.num_row_groups(), 4);
+        assert_eq!(reader.schema(), table.schema());
+
+        let mut ids = Vec::new();
+        for group in reader.row_groups(Some(&["value", "id"])).unwrap() {
+            let group = group.unwrap();
+            assert_eq!(group.column_names(), vec!["value", "id"]);
+            ids.extend(group.column("id").unwrap().to_array::<i64>().unwrap());
+        }
+        assert_eq!(ids, (0..1000).collect::<Vec<i64>>());
+
+        let last = reader.read_row_group(3, Some(&["category"])).unwrap();
+        assert_eq!(last.num_rows(), 1000 - 768);
+        assert!(reader.read_row_group(4, None).is_err());
+        assert!(reader.read_row_group(0, Some(&["missing"])).is_err());
+
+        let projected = read_parquet_columns(&path, &["small"]).unwrap();
+        let small = projected.column("small").unwrap();
+        assert_eq!(small.null_count(), 200);
+        assert!(small.to_f64_array().unwrap()[1].is_nan());
+    }
+
+    #[test]
+    fn test_streaming_writer_and_record_array() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stream.parquet");
+        let first = Table::new(vec![
+            Column::from_array("x", &array![1.0, 2.0]),
+            Column::new("name", ColumnData::Utf8(vec!["a".into(), "b".into()])),
+        ])
+        .unwrap();
+        let second = Table::new(vec![
+            Column::from_array("x", &array![3.0]),
+            Column::new("name", ColumnData::Utf8(vec!["c".into()])),
+        ])
+        .unwrap();
+
+        let mut writer = ParquetWriter::create(&path, &first.schema(), None).unwrap();
+        writer.write_table(&first).unwrap();
+        writer.write_table(&second).unwrap();
+        assert!(writer.write_table(&sample_table()).is_err());
+        writer.finish().unwrap();
+
+        let table = read_parquet(&path).unwrap();
+        assert_eq!(
+            table.column("x").unwrap().to_array::<f64>().unwrap(),
+            array![1.0, 2.0, 3.0]
+        );
+        let records = table.to_record_array().unwrap();
+        assert_eq!(records.num_records(), 3);
+    }
+
+    #[test]
+    fn test_rejects_invalid_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bad.parquet");
+        std::fs::write(&path, b"PAR1 definitely not parquet PAR1").unwrap();
+        assert!(read_parquet(&path).is_err());
+        std::fs::write(&path, b"PAR").unwrap();
+        assert!(read_parquet(&path).is_err());
+    }
+}
@@ -0,0 +1,574 @@
+//! Parquet file reader
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use super::encoding::{self, PhysicalType, Values};
+use super::thrift::{Decoder, Struct};
+use super::{logical_type, ColumnSchema, ParquetCompression, MAGIC};
+use crate::columnar::{Column, ColumnData, DataType, Field, Table};
+use crate::compression::decompress_data;
+use crate::error::{IoError, Result};
+
+// Page types
+const PAGE_DATA: i32 = 0;
+const PAGE_DICTIONARY: i32 = 2;
+const PAGE_DATA_V2: i32 = 3;
+
+// Value encodings
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_PLAIN_DICTIONARY: i32 = 2;
+const ENCODING_RLE: i32 = 3;
+const ENCODING_RLE_DICTIONARY: i32 = 8;
+
+/// Location and encoding of one column chunk
+#[derive(Debug, Clone)]
+struct ChunkMeta {
+    codec: ParquetCompression,
+    num_values: usize,
+    start: u64,
+    length: usize,
+}
+
+/// Column chunks of one row group
+#[derive(Debug, Clone)]
+struct RowGroupMeta {
+    num_rows: usize,
+    chunks: Vec<ChunkMeta>,
+}
+
+fn format_error<T>(msg: impl Into<String>) -> Result<T> {
+    Err(IoError::FormatError(msg.into()))
+}
+
+/// Reader for Parquet files
+///
+/// The footer is parsed when the file is opened; column data is read lazily,
+/// one row group at a time.
+pub struct ParquetReader {
+    file: File,
+    columns: Vec<ColumnSchema>,
+    row_groups: Vec<RowGroupMeta>,
+    num_rows: usize,
+    created_by: Option<String>,
+}
+
+impl ParquetReader {
+    /// Open a Parquet file and read its metadata
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut file = File::open(path.as_ref())
+            .map_err(|e| IoError::FileError(format!("{}: {}", path.as_ref().display(), e)))?;
+        let len = file
+            .metadata()
+            .map_err(|e| IoError::FileError(e.to_string()))?
+            .len();
+        if len < 12 {
+            return format_error("File is too small to be a Parquet file");
+        }
+
+        let mut head = [0u8; 4];
+        read_at(&mut file, 0, &mut head)?;
+        let mut tail = [0u8; 8];
+        read_at(&mut file, len - 8, &mut tail)?;
+        if &head != MAGIC || &tail[4..] != MAGIC {
+            return format_error("Not a Parquet file (missing PAR1 magic)");
+        }
+        let footer_len = u32::from_le_bytes(tail[..4].try_into().expect("4 bytes")) as u64;
+        if footer_len + 12 > len {
+            return format_error("Parquet footer length exceeds file size");
+        }
+        let mut footer = vec![0u8; footer_len as usize];
+        read_at(&mut file, len - 8 - footer_len, &mut footer)?;
+        let metadata = Decoder::new(&footer).read_struct()?;
+
+        let columns = parse_schema(&metadata)?;
+        let mut row_groups = Vec::new();
+        for group in metadata.list(4).unwrap_or(&[]) {
+            let super::thrift::Value::Struct(group) = group else {
+                return format_error("Invalid Parquet row group");
+            };
+            row_groups.push(parse_row_group(group, columns.len(), len)?);
+        }
+
+        Ok(Self {
+            file,
+            columns,
+            row_groups,
+            num_rows: metadata.req_i64(3, "num_rows")?.max(0) as usize,
+            created_by: metadata.string(6),
+        })
+    }
+
+    /// Schema of the columns in the file
+    pub fn schema(&self) -> Vec<Field> {
+        self.columns
+            .iter()
+            .map(|c| Field::new(&c.name, c.data_type, c.nullable))
+            .collect()
+    }
+
+    /// Total number of rows
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Number of row groups
+    pub fn num_row_groups(&self) -> usize {
+        self.row_groups.len()
+    }
+
+    /// Application that wrote the file, if recorded
+    pub fn created_by(&self) -> Option<&str> {
+        self.created_by.as_deref()
+    }
+
+    /// Read one row group
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the row group
+    /// * `columns` - Names of the columns to read, or `None` for all columns
+    pub fn read_row_group(&mut self, index: usize, columns: Option<&[&str]>) -> Result<Table> {
+        let projection = self.projection(columns)?;
+        self.read_projected(index, &projection)
+    }
+
+    /// Iterate over the row groups, reading each one as a table
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - Names of the columns to read, or `None` for all columns
+    pub fn row_groups(&mut self, columns: Option<&[&str]>) -> Result<RowGroups<'_>> {
+        let projection = self.projection(columns)?;
+        Ok(RowGroups {
+            reader: self,
+            projection,
+            next: 0,
+        })
+    }
+
+    /// Read all row groups into a single table
+    pub(super) fn read_all(&mut self, columns: Option<&[&str]>) -> Result<Table> {
+        let projection = self.projection(columns)?;
+        let fields: Vec<Field> = projection
+            .iter()
+            .map(|&i| {
+                let c = &self.columns[i];
+                Field::new(&c.name, c.data_type, c.nullable)
+            })
+            .collect();
+        let mut table = Table::empty(&fields);
+        for index in 0..self.row_groups.len() {
+            let group = self.read_projected(index, &projection)?;
+            table.append(&group)?;
+        }
+        Ok(table)
+    }
+
+    /// Resolve column names to schema indices
+    fn projection(&self, columns: Option<&[&str]>) -> Result<Vec<usize>> {
+        match columns {
+            None => Ok((0..self.columns.len()).collect()),
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    self.columns
+                        .iter()
+                        .position(|c| c.name == *name)
+                        .ok_or_else(|| {
+                            IoError::ValidationError(format!(
+                                "Parquet file has no column '{}'",
+                                name
+                            ))
+                        })
+                })
+                .collect(),
+        }
+    }
+
+    fn read_projected(&mut self, index: usize, projection: &[usize]) -> Result<Table> {
+        let Some(group) = self.row_groups.get(index).cloned() else {
+            return Err(IoError::ValidationError(format!(
+                "Row group {} out of range (file has {})",
+                index,
+                self.row_groups.len()
+            )));
+        };
+        let mut columns = Vec::with_capacity(projection.len());
+        for &c in projection {
+            let chunk = &group.chunks[c];
+            let mut bytes = vec![0u8; chunk.length];
+            read_at(&mut self.file, chunk.start, &mut bytes)?;
+            let schema = &self.columns[c];
+            let column = read_chunk(&bytes, schema, chunk)?;
+            if column.len() != group.num_rows {
+                return format_error(format!(
+                    "Column '{}' has {} values in a row group of {} rows",
+                    schema.name,
+                    column.len(),
+                    group.num_rows
+                ));
+            }
+            columns.push(column);
+        }
+        if columns.is_empty() {
+            return Ok(Table::empty(&[]));
+        }
+        Table::new(columns)
+    }
+}
+
+/// Iterator over the row groups of a Parquet file
+pub struct RowGroups<'a> {
+    reader: &'a mut ParquetReader,
+    projection: Vec<usize>,
+    next: usize,
+}
+
+impl Iterator for RowGroups<'_> {
+    type Item = Result<Table>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.reader.row_groups.len() {
+            return None;
+        }
+        self.next += 1;
+        Some(self.reader.read_projected(self.next - 1, &self.projection))
+    }
+}
+
+fn read_at(file: &mut File, offset: u64, buf: &mut [u8]) -> Result<()> {
+    file.seek(SeekFrom::Start(offset))
+        .and_then(|_| file.read_exact(buf))
+        .map_err(|e| IoError::FileError(e.to_string()))
+}
+
+/// Flatten the schema tree into leaf columns
+fn parse_schema(metadata: &Struct) -> Result<Vec<ColumnSchema>> {
+    let elements: Vec<&Struct> = metadata
+        .list(2)
+        .unwrap_or(&[])
+        .iter()
+        .map(|v| match v {
+            super::thrift::Value::Struct(s) => Ok(s),
+            _ => format_error("Invalid Parquet schema element"),
+        })
+        .collect::<Result<_>>()?;
+    let Some((_root, leaves)) = elements.split_first() else {
+        return format_error("Parquet schema is empty");
+    };
+
+    let mut columns = Vec::with_capacity(leaves.len());
+    for element in leaves {
+        let name = element.string(4).unwrap_or_default();
+        if element.i32(5).unwrap_or(0) > 0 || element.i32(1).is_none() {
+            return Err(IoError::FormatError(format!(
+                "Nested Parquet column '{}' is not supported",
+                name
+            )));
+        }
+        let nullable = match element.i32(3).unwrap_or(0) {
+            0 => false,
+            1 => true,
+            _ => {
+                return Err(IoError::FormatError(format!(
+                    "Repeated Parquet column '{}' is not supported",
+                    name
+                )))
+            }
+        };
+        let physical =
+            PhysicalType::from_thrift(element.req_i32(1, "column type")?, element.i32(2))?;
+        let data_type = logical_type(physical, element.i32(6), element.structure(10));
+        columns.push(ColumnSchema {
+            name,
+            physical,
+            data_type,
+            nullable,
+        });
+    }
+    Ok(columns)
+}
+
+fn parse_row_group(group: &Struct, num_columns: usize, file_len: u64) -> Result<RowGroupMeta> {
+    let chunks = group.list(1).unwrap_or(&[]);
+    if chunks.len() != num_columns {
+        return format_error(format!(
+            "Row group has {} column chunks, schema has {} columns",
+            chunks.len(),
+            num_columns
+        ));
+    }
+    let chunks = chunks
+        .iter()
+        .map(|chunk| {
+            let meta = match chunk {
+                super::thrift::Value::Struct(c) => c.structure(3),
+                _ => None,
+            };
+            let Some(meta) = meta else {
+                return format_error("Parquet column chunk has no inline metadata");
+            };
+            let data_offset = meta.req_i64(9, "data_page_offset")?;
+            // Some writers store a zero dictionary offset to mean "absent"
+            let start = match meta.i64(11) {
+                Some(dict) if dict > 0 => dict.min(data_offset),
+                _ => data_offset,
+            };
+            let length = meta.req_i64(7, "total_compressed_size")?;
+            if start < 0 || length < 0 || (start + length) as u64 > file_len {
+                return format_error("Parquet column chunk lies outside the file");
+            }
+            Ok(ChunkMeta {
+                codec: ParquetCompression::from_thrift(meta.req_i32(4, "codec")?)?,
+                num_values: meta.req_i64(5, "num_values")?.max(0) as usize,
+                start: start as u64,
+                length: length as usize,
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(RowGroupMeta {
+        num_rows: group.req_i64(3, "num_rows")?.max(0) as usize,
+        chunks,
+    })
+}
+
+fn decompress(codec: ParquetCompression, data: &[u8], expected: usize) -> Result<Vec<u8>> {
+    let out = match codec.algorithm() {
+        None => data.to_vec(),
+        Some(algorithm) => decompress_data(data, algorithm)?,
+    };
+    if out.len() != expected {
+        return format_error(format!(
+            "Parquet page decompressed to {} bytes, expected {}",
+            out.len(),
+            expected
+        ));
+    }
+    Ok(out)
+}
+
+/// Decode all pages of a column chunk
+fn read_chunk(bytes: &[u8], schema: &ColumnSchema, chunk: &ChunkMeta) -> Result<Column> {
+    let mut pos = 0;
+    let mut dictionary: Option<Values> = None;
+    let mut values = Values::empty(schema.physical);
+    let mut validity: Vec<bool> = Vec::new();
+
+    while validity.len() < chunk.num_values && pos < bytes.len() {
+        let mut decoder = Decoder::new(&bytes[pos..]);
+        let header = decoder.read_struct()?;
+        pos += decoder.position();
+        let compressed_size = header.req_i32(3, "compressed_page_size")?.max(0) as usize;
+        let uncompressed_size = header.req_i32(2, "uncompressed_page_size")?.max(0) as usize;
+        let Some(page) = bytes.get(pos..pos + compressed_size) else {
+            return format_error("Truncated Parquet page");
+        };
+        pos += compressed_size;
+
+        match header.req_i32(1, "page type")? {
+            PAGE_DICTIONARY => {
+                let dict_header = header
+                    .structure(7)
+                    .ok_or_else(|| IoError::FormatError("Missing dictionary page header".into()))?;
+                let count = dict_header.req_i32(1, "dictionary size")?.max(0) as usize;
+                let data = decompress(chunk.codec, page, uncompressed_size)?;
+                dictionary = Some(encoding::decode_plain(&data, schema.physical, count)?.0);
+            }
+            PAGE_DATA => {
+                let data_header = header
+                    .structure(5)
+                    .ok_or_else(|| IoError::FormatError("Missing data page header".into()))?;
+                let count = data_header.req_i32(1, "page value count")?.max(0) as usize;
+                let value_encoding = data_header.req_i32(2, "page encoding")?;
+                let data = decompress(chunk.codec, page, uncompressed_size)?;
+                let mut offset = 0;
+                let page_validity = if schema.nullable {
+                    let Some(len) = data.get(..4) else {
+                        return format_error("Truncated definition levels");
+                    };
+                    let len = u32::from_le_bytes(len.try_into().expect("4 bytes")) as usize;
+                    let Some(levels) = data.get(4..4 + len) else {
+                        return format_error("Truncated definition levels");
+                    };
+                    offset = 4 + len;
+                    decode_levels(levels, count)?
+                } else {
+                    vec![true; count]
+                };
+                let non_null = page_validity.iter().filter(|&&v| v).count();
+                let page_values = decode_values(
+                    &data[offset..],
+                    value_encoding,
+                    schema.physical,
+                    non_null,
+                    dictionary.as_ref(),
+                    true,
+                )?;
+                values.extend(page_values)?;
+                validity.extend(page_validity);
+            }
+            PAGE_DATA_V2 => {
+                let v2 = header
+                    .structure(8)
+                    .ok_or_else(|| IoError::FormatError("Missing data page v2 header".into()))?;
+                let count = v2.req_i32(1, "page value count")?.max(0) as usize;
+                let value_encoding = v2.req_i32(4, "page encoding")?;
+                let def_len = v2.req_i32(5, "definition levels length")?.max(0) as usize;
+                let rep_len = v2.req_i32(6, "repetition levels length")?.max(0) as usize;
+                let levels_len = def_len + rep_len;
+                if levels_len > page.len() || levels_len > uncompressed_size {
+                    return format_error("Parquet page levels exceed page size");
+                }
+                let page_validity = if schema.nullable {
+                    decode_levels(&page[rep_len..levels_len], count)?
+                } else {
+                    vec![true; count]
+                };
+                let body = if v2.bool(7).unwrap_or(true) {
+                    decompress(
+                        chunk.codec,
+                        &page[levels_len..],
+                        uncompressed_size - levels_len,
+                    )?
+                } else {
+                    page[levels_len..].to_vec()
+                };
+                let non_null = page_validity.iter().filter(|&&v| v).count();
+                let page_values = decode_values(
+                    &body,
+                    value_encoding,
+                    schema.physical,
+                    non_null,
+                    dictionary.as_ref(),
+                    false,
+                )?;
+                values.extend(page_values)?;
+                validity.extend(page_validity);
+            }
+            // Index pages and unknown page types carry no values
+            _ => {}
+        }
+    }
+
+    if validity.len() != chunk.num_values {
+        return format_error(format!(
+            "Column '{}' chunk holds {} values, expected {}",
+            schema.name,
+            validity.len(),
+            chunk.num_values
+        ));
+    }
+    build_column(schema, values, validity)
+}
+
+/// Decode one-bit definition levels into a validity mask
+fn decode_levels(levels: &[u8], count: usize) -> Result<Vec<bool>> {
+    Ok(encoding::decode_rle(levels, 1, count)?
+        .into_iter()
+        .map(|l| l == 1)
+        .collect())
+}
+
+/// Decode the non-null values of a data page
+///
+/// `length_prefixed` is set for version 1 pages, where RLE-encoded booleans
+/// carry a four-byte length prefix.
+fn decode_values(
+    data: &[u8],
+    value_encoding: i32,
+    physical: PhysicalType,
+    count: usize,
+    dictionary: Option<&Values>,
+    length_prefixed: bool,
+) -> Result<Values> {
+    match value_encoding {
+        ENCODING_PLAIN => {
+            let (values, _) = encoding::decode_plain(data, physical, count)?;
+            Ok(values)
+        }
+        ENCODING_PLAIN_DICTIONARY | ENCODING_RLE_DICTIONARY => {
+            let Some(dictionary) = dictionary else {
+                return format_error("Dictionary-encoded page without a dictionary page");
+            };
+            if count == 0 {
+                return Ok(Values::empty(physical));
+            }
+            let Some((&width, indices)) = data.split_first() else {
+                return format_error("Truncated dictionary indices");
+            };
+            dictionary.take(&encoding::decode_rle(indices, width, count)?)
+        }
+        ENCODING_RLE if physical == PhysicalType::Boolean => {
+            let data = if length_prefixed {
+                data.get(4..).unwrap_or(&[])
+            } else {
+                data
+            };
+            Ok(Values::Boolean(
+                encoding::decode_rle(data, 1, count)?
+                    .into_iter()
+                    .map(|v| v == 1)
+                    .collect(),
+            ))
+        }
+        other => Err(IoError::FormatError(format!(
+            "Unsupported Parquet value encoding {}",
+            other
+        ))),
+    }
+}
+
+/// Convert physical values to a column, inserting placeholders for nulls
+fn build_column(schema: &ColumnSchema, values: Values, validity: Vec<bool>) -> Result<Column> {
+    fn expand<T: Clone + Default>(values: Vec<T>, validity: &[bool]) -> Vec<T> {
+        if values.len() == validity.len() {
+            return values;
+        }
+        let mut iter = values.into_iter();
+        validity
+            .iter()
+            .map(|&valid| {
+                if valid {
+                    iter.next().unwrap_or_default()
+                } else {
+                    T::default()
+                }
+            })
+            .collect()
+    }
+    fn convert<T, U: Clone + Default>(values: Vec<T>, validity: &[bool], f: fn(T) -> U) -> Vec<U> {
+        expand(values.into_iter().map(f).collect(), validity)
+    }
+
+    let v = &validity;
+    let data = match (values, schema.data_type) {
+        (Values::Boolean(x), DataType::Boolean) => ColumnData::Boolean(expand(x, v)),
+        (Values::Int32(x), DataType::Int8) => ColumnData::Int8(convert(x, v, |x| x as i8)),
+        (Values::Int32(x), DataType::Int16) => ColumnData::Int16(convert(x, v, |x| x as i16)),
+        (Values::Int32(x), DataType::Int32) => ColumnData::Int32(expand(x, v)),
+        (Values::Int32(x), DataType::UInt8) => ColumnData::UInt8(convert(x, v, |x| x as u8)),
+        (Values::Int32(x), DataType::UInt16) => ColumnData::UInt16(convert(x, v, |x| x as u16)),
+        (Values::Int32(x), DataType::UInt32) => ColumnData::UInt32(convert(x, v, |x| x as u32)),
+        (Values::Int64(x), DataType::Int64) => ColumnData::Int64(expand(x, v)),
+        (Values::Int64(x), DataType::UInt64) => ColumnData::UInt64(convert(x, v, |x| x as u64)),
+        (Values::Float(x), DataType::Float32) => ColumnData::Float32(expand(x, v)),
+        (Values::Double(x), DataType::Float64) => ColumnData::Float64(expand(x, v)),
+        (Values::Bytes(x), DataType::Utf8) => ColumnData::Utf8(convert(x, v, |b| {
+            String::from_utf8(b).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into())
+        })),
+        (Values::Bytes(x), DataType::Binary) => ColumnData::Binary(expand(x, v)),
+        _ => {
+            return format_error(format!(
+                "Column '{}' values do not match its {:?} type",
+                schema.name, schema.data_type
+            ))
+        }
+    };
+    if schema.nullable {
+        Column::with_validity(&schema.name, data, validity)
+    } else {
+        Ok(Column::new(&schema.name, data))
+    }
+}
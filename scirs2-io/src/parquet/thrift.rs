@@ -0,0 +1,419 @@
+//! Thrift compact protocol
+//!
+//! Parquet stores its file and page metadata as Thrift structs in the
+//! compact protocol. Structs are decoded into a generic [`Struct`] tree keyed
+//! by field id, which keeps unknown fields out of the way without a schema,
+//! and are encoded from the same representation.
+
+use crate::error::{IoError, Result};
+
+// Compact protocol type codes
+const TYPE_STOP: u8 = 0;
+const TYPE_TRUE: u8 = 1;
+const TYPE_FALSE: u8 = 2;
+const TYPE_BYTE: u8 = 3;
+const TYPE_I16: u8 = 4;
+const TYPE_I32: u8 = 5;
+const TYPE_I64: u8 = 6;
+const TYPE_DOUBLE: u8 = 7;
+const TYPE_BINARY: u8 = 8;
+const TYPE_LIST: u8 = 9;
+const TYPE_SET: u8 = 10;
+const TYPE_MAP: u8 = 11;
+const TYPE_STRUCT: u8 = 12;
+
+/// Maximum nesting of structs and containers
+const MAX_DEPTH: usize = 64;
+
+/// A decoded Thrift value
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Bool(bool),
+    Byte(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Double(f64),
+    Binary(Vec<u8>),
+    List(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Struct(Struct),
+}
+
+/// A Thrift struct as a list of (field id, value) pairs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Struct {
+    pub fields: Vec<(i16, Value)>,
+}
+
+impl Struct {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a field, returning the struct for chaining
+    pub fn with(mut self, id: i16, value: Value) -> Self {
+        self.fields.push((id, value));
+        self
+    }
+
+    /// Add a field if a value is present
+    pub fn with_opt(self, id: i16, value: Option<Value>) -> Self {
+        match value {
+            Some(value) => self.with(id, value),
+            None => self,
+        }
+    }
+
+    pub fn get(&self, id: i16) -> Option<&Value> {
+        self.fields.iter().find(|(i, _)| *i == id).map(|(_, v)| v)
+    }
+
+    pub fn i32(&self, id: i16) -> Option<i32> {
+        match self.get(id)? {
+            Value::Byte(v) => Some(*v as i32),
+            Value::I16(v) => Some(*v as i32),
+            Value::I32(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn i64(&self, id: i16) -> Option<i64> {
+        match self.get(id)? {
+            Value::I64(v) => Some(*v),
+            _ => self.i32(id).map(i64::from),
+        }
+    }
+
+    pub fn bool(&self, id: i16) -> Option<bool> {
+        match self.get(id)? {
+            Value::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn binary(&self, id: i16) -> Option<&[u8]> {
+        match self.get(id)? {
+            Value::Binary(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn string(&self, id: i16) -> Option<String> {
+        self.binary(id)
+            .map(|b| String::from_utf8_lossy(b).into_owned())
+    }
+
+    pub fn list(&self, id: i16) -> Option<&[Value]> {
+        match self.get(id)? {
+            Value::List(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn structure(&self, id: i16) -> Option<&Struct> {
+        match self.get(id)? {
+            Value::Struct(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Required i32 field
+    pub fn req_i32(&self, id: i16, what: &str) -> Result<i32> {
+        self.i32(id).ok_or_else(|| missing(what))
+    }
+
+    /// Required i64 field
+    pub fn req_i64(&self, id: i16, what: &str) -> Result<i64> {
+        self.i64(id).ok_or_else(|| missing(what))
+    }
+}
+
+fn missing(what: &str) -> IoError {
+    IoError::FormatError(format!("Parquet metadata is missing {}", what))
+}
+
+fn truncated<T>() -> Result<T> {
+    Err(IoError::FormatError(
+        "Truncated Parquet metadata".to_string(),
+    ))
+}
+
+/// Decoder over a byte slice
+pub(crate) struct Decoder<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Number of bytes consumed so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        let Some(&b) = self.buf.get(self.pos) else {
+            return truncated();
+        };
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..70).step_by(7) {
+            let b = self.byte()?;
+            value |= u64::from(b & 0x7F) << shift;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(IoError::FormatError(
+            "Invalid varint in Parquet metadata".to_string(),
+        ))
+    }
+
+    fn zigzag(&mut self) -> Result<i64> {
+        let v = self.varint()?;
+        Ok((v >> 1) as i64 ^ -((v & 1) as i64))
+    }
+
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        if n > self.buf.len() - self.pos {
+            return truncated();
+        }
+        let out = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(out)
+    }
+
+    /// Decode a struct
+    pub fn read_struct(&mut self) -> Result<Struct> {
+        self.read_struct_at(0)
+    }
+
+    fn read_struct_at(&mut self, depth: usize) -> Result<Struct> {
+        if depth > MAX_DEPTH {
+            return Err(IoError::FormatError(
+                "Parquet metadata is nested too deeply".to_string(),
+            ));
+        }
+        let mut result = Struct::new();
+        let mut last_id: i16 = 0;
+        loop {
+            let header = self.byte()?;
+            let field_type = header & 0x0F;
+            if field_type == TYPE_STOP {
+                return Ok(result);
+            }
+            let delta = header >> 4;
+            let id = if delta == 0 {
+                self.zigzag()? as i16
+            } else {
+                last_id.wrapping_add(delta as i16)
+            };
+            last_id = id;
+            let value = match field_type {
+                TYPE_TRUE => Value::Bool(true),
+                TYPE_FALSE => Value::Bool(false),
+                t => self.read_value(t, depth)?,
+            };
+            result.fields.push((id, value));
+        }
+    }
+
+    fn read_value(&mut self, value_type: u8, depth: usize) -> Result<Value> {
+        Ok(match value_type {
+            // Booleans inside containers are stored as a full byte
+            TYPE_TRUE | TYPE_FALSE => Value::Bool(self.byte()? == TYPE_TRUE),
+            TYPE_BYTE => Value::Byte(self.byte()? as i8),
+            TYPE_I16 => Value::I16(self.zigzag()? as i16),
+            TYPE_I32 => Value::I32(self.zigzag()? as i32),
+            TYPE_I64 => Value::I64(self.zigzag()?),
+            TYPE_DOUBLE => {
+                let b = self.bytes(8)?;
+                Value::Double(f64::from_le_bytes(b.try_into().expect("8 bytes")))
+            }
+            TYPE_BINARY => {
+                let len = self.varint()? as usize;
+                Value::Binary(self.bytes(len)?.to_vec())
+            }
+            TYPE_LIST | TYPE_SET => {
+                let header = self.byte()?;
+                let mut len = (header >> 4) as usize;
+                if len == 15 {
+                    len = self.varint()? as usize;
+                }
+                let elem_type = header & 0x0F;
+                let mut items = Vec::with_capacity(len.min(1024));
+                for _ in 0..len {
+                    items.push(self.read_value(elem_type, depth + 1)?);
+                }
+                Value::List(items)
+            }
+            TYPE_MAP => {
+                let len = self.varint()? as usize;
+                let mut entries = Vec::with_capacity(len.min(1024));
+                if len > 0 {
+                    let types = self.byte()?;
+                    for _ in 0..len {
+                        let key = self.read_value(types >> 4, depth + 1)?;
+                        let value = self.read_value(types & 0x0F, depth + 1)?;
+                        entries.push((key, value));
+                    }
+                }
+                Value::Map(entries)
+            }
+            TYPE_STRUCT => Value::Struct(self.read_struct_at(depth + 1)?),
+            other => {
+                return Err(IoError::FormatError(format!(
+                    "Unknown Thrift type {} in Parquet metadata",
+                    other
+                )))
+            }
+        })
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_zigzag(out: &mut Vec<u8>, value: i64) {
+    write_varint(out, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+fn type_code(value: &Value) -> u8 {
+    match value {
+        Value::Bool(true) => TYPE_TRUE,
+        Value::Bool(false) => TYPE_FALSE,
+        Value::Byte(_) => TYPE_BYTE,
+        Value::I16(_) => TYPE_I16,
+        Value::I32(_) => TYPE_I32,
+        Value::I64(_) => TYPE_I64,
+        Value::Double(_) => TYPE_DOUBLE,
+        Value::Binary(_) => TYPE_BINARY,
+        Value::List(_) => TYPE_LIST,
+        Value::Map(_) => TYPE_MAP,
+        Value::Struct(_) => TYPE_STRUCT,
+    }
+}
+
+impl Struct {
+    /// Encode the struct, appending to `out`
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        let mut fields: Vec<&(i16, Value)> = self.fields.iter().collect();
+        fields.sort_by_key(|(id, _)| *id);
+        let mut last_id = 0i16;
+        for (id, value) in fields {
+            let code = type_code(value);
+            let delta = id.wrapping_sub(last_id);
+            if (1..=15).contains(&delta) {
+                out.push(((delta as u8) << 4) | code);
+            } else {
+                out.push(code);
+                write_zigzag(out, *id as i64);
+            }
+            last_id = *id;
+            if !matches!(value, Value::Bool(_)) {
+                encode_value(value, out);
+            }
+        }
+        out.push(TYPE_STOP);
+    }
+}
+
+fn encode_value(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Bool(b) => out.push(if *b { TYPE_TRUE } else { TYPE_FALSE }),
+        Value::Byte(v) => out.push(*v as u8),
+        Value::I16(v) => write_zigzag(out, *v as i64),
+        Value::I32(v) => write_zigzag(out, *v as i64),
+        Value::I64(v) => write_zigzag(out, *v),
+        Value::Double(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::Binary(v) => {
+            write_varint(out, v.len() as u64);
+            out.extend_from_slice(v);
+        }
+        Value::List(items) => {
+            // Element type of an empty list is irrelevant; use struct
+            let elem_type = items.first().map_or(TYPE_STRUCT, |v| match v {
+                Value::Bool(_) => TYPE_TRUE,
+                other => type_code(other),
+            });
+            if items.len() < 15 {
+                out.push(((items.len() as u8) << 4) | elem_type);
+            } else {
+                out.push(0xF0 | elem_type);
+                write_varint(out, items.len() as u64);
+            }
+            for item in items {
+                encode_value(item, out);
+            }
+        }
+        Value::Map(entries) => {
+            write_varint(out, entries.len() as u64);
+            if let Some((k, v)) = entries.first() {
+                out.push((type_code(k) << 4) | type_code(v));
+            }
+            for (k, v) in entries {
+                encode_value(k, out);
+                encode_value(v, out);
+            }
+        }
+        Value::Struct(s) => s.encode(out),
+    }
+}
+
+/// Binary value from a string
+pub(crate) fn string(s: &str) -> Value {
+    Value::Binary(s.as_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let inner = Struct::new()
+            .with(1, Value::I32(-7))
+            .with(2, string("name"));
+        let s = Struct::new()
+            .with(1, Value::I32(1))
+            .with(2, Value::List(vec![Value::Struct(inner.clone()); 20]))
+            .with(3, Value::I64(1 << 40))
+            .with(4, Value::Bool(true))
+            .with(5, Value::Bool(false))
+            .with(40, Value::Double(1.5))
+            .with(41, Value::List(vec![Value::I32(0), Value::I32(8)]))
+            .with(42, Value::Map(vec![(string("k"), Value::I16(-3))]));
+        let mut bytes = Vec::new();
+        s.encode(&mut bytes);
+
+        let mut decoder = Decoder::new(&bytes);
+        let decoded = decoder.read_struct().unwrap();
+        assert_eq!(decoder.position(), bytes.len());
+        assert_eq!(decoded, s);
+        assert_eq!(decoded.list(2).unwrap().len(), 20);
+        assert_eq!(decoded.i64(3), Some(1 << 40));
+        assert_eq!(decoded.bool(4), Some(true));
+    }
+
+    #[test]
+    fn test_truncated() {
+        let mut bytes = Vec::new();
+        Struct::new().with(1, string("value")).encode(&mut bytes);
+        assert!(Decoder::new(&bytes[..bytes.len() - 2])
+            .read_struct()
+            .is_err());
+    }
+}
@@ -0,0 +1,378 @@
+//! Parquet file writer
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::encoding::{self, Values};
+use super::thrift::{self, Struct, Value};
+use super::{storage_type, ParquetWriteOptions, MAGIC};
+use crate::columnar::{Column, ColumnData, Field, Table};
+use crate::compression::compress_data;
+use crate::error::{IoError, Result};
+
+// Page types and encodings
+const PAGE_DATA: i32 = 0;
+const PAGE_DICTIONARY: i32 = 2;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const ENCODING_RLE_DICTIONARY: i32 = 8;
+
+/// Incremental Parquet writer
+///
+/// Every call to [`write_table`](ParquetWriter::write_table) appends one or
+/// more row groups. [`finish`](ParquetWriter::finish) must be called to write
+/// the footer; a file that is not finished cannot be read.
+pub struct ParquetWriter {
+    out: BufWriter<File>,
+    schema: Vec<Field>,
+    options: ParquetWriteOptions,
+    offset: u64,
+    row_groups: Vec<Value>,
+    num_rows: i64,
+}
+
+fn io_error(e: std::io::Error) -> IoError {
+    IoError::FileError(e.to_string())
+}
+
+impl ParquetWriter {
+    /// Create a Parquet file for tables with the given schema
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file to create
+    /// * `schema` - Names, types and nullability of the columns
+    /// * `options` - Write options (defaults to Snappy pages with dictionary encoding)
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        schema: &[Field],
+        options: Option<ParquetWriteOptions>,
+    ) -> Result<Self> {
+        let options = options.unwrap_or_default();
+        if options.row_group_size == 0 || options.data_page_size == 0 {
+            return Err(IoError::ValidationError(
+                "Row group and data page sizes must be positive".to_string(),
+            ));
+        }
+        let file = File::create(path.as_ref())
+            .map_err(|e| IoError::FileError(format!("{}: {}", path.as_ref().display(), e)))?;
+        let mut out = BufWriter::new(file);
+        out.write_all(MAGIC).map_err(io_error)?;
+        Ok(Self {
+            out,
+            schema: schema.to_vec(),
+            options,
+            offset: MAGIC.len() as u64,
+            row_groups: Vec::new(),
+            num_rows: 0,
+        })
+    }
+
+    /// Append the rows of a table
+    ///
+    /// The table's columns must match the writer's schema by name and type.
+    /// A column without nulls may be written to a nullable schema column.
+    pub fn write_table(&mut self, table: &Table) -> Result<()> {
+        if table.num_columns() != self.schema.len() {
+            return Err(IoError::ValidationError(format!(
+                "Table has {} columns, Parquet schema has {}",
+                table.num_columns(),
+                self.schema.len()
+            )));
+        }
+        for (column, field) in table.columns().iter().zip(&self.schema) {
+            if column.name != field.name || column.data.data_type() != field.data_type {
+                return Err(IoError::ValidationError(format!(
+                    "Column '{}' ({:?}) does not match schema field '{}' ({:?})",
+                    column.name,
+                    column.data.data_type(),
+                    field.name,
+                    field.data_type
+                )));
+            }
+            if column.null_count() > 0 && !field.nullable {
+                return Err(IoError::ValidationError(format!(
+                    "Column '{}' contains nulls but is not nullable",
+                    column.name
+                )));
+            }
+        }
+
+        let mut start = 0;
+        while start < table.num_rows() {
+            let len = self.options.row_group_size.min(table.num_rows() - start);
+            let group = table.slice(start, len)?;
+            self.write_row_group(&group)?;
+            start += len;
+        }
+        Ok(())
+    }
+
+    /// Write the footer and close the file
+    pub fn finish(mut self) -> Result<()> {
+        let mut schema = vec![Value::Struct(
+            Struct::new()
+                .with(4, thrift::string("schema"))
+                .with(5, Value::I32(self.schema.len() as i32)),
+        )];
+        for field in &self.schema {
+            let (physical, converted, logical) = storage_type(field.data_type);
+            schema.push(Value::Struct(
+                Struct::new()
+                    .with(1, Value::I32(physical.to_thrift()))
+                    .with(3, Value::I32(field.nullable as i32))
+                    .with(4, thrift::string(&field.name))
+                    .with_opt(6, converted.map(Value::I32))
+                    .with_opt(10, logical.map(Value::Struct)),
+            ));
+        }
+        let metadata = Struct::new()
+            .with(1, Value::I32(1))
+            .with(2, Value::List(schema))
+            .with(3, Value::I64(self.num_rows))
+            .with(4, Value::List(std::mem::take(&mut self.row_groups)))
+            .with(
+                6,
+                thrift::string(concat!("scirs2-io version ", env!("CARGO_PKG_VERSION"))),
+            );
+
+        let mut footer = Vec::new();
+        metadata.encode(&mut footer);
+        self.out.write_all(&footer).map_err(io_error)?;
+        self.out
+            .write_all(&(footer.len() as u32).to_le_bytes())
+            .map_err(io_error)?;
+        self.out.write_all(MAGIC).map_err(io_error)?;
+        self.out.flush().map_err(io_error)
+    }
+
+    fn write_row_group(&mut self, table: &Table) -> Result<()> {
+        let mut chunks = Vec::with_capacity(table.num_columns());
+        let mut total_size = 0i64;
+        let nullable: Vec<bool> = self.schema.iter().map(|f| f.nullable).collect();
+        for (column, nullable) in table.columns().iter().zip(nullable) {
+            let chunk = self.write_chunk(column, nullable)?;
+            total_size += chunk.1;
+            chunks.push(Value::Struct(chunk.0));
+        }
+        self.row_groups.push(Value::Struct(
+            Struct::new()
+                .with(1, Value::List(chunks))
+                .with(2, Value::I64(total_size))
+                .with(3, Value::I64(table.num_rows() as i64)),
+        ));
+        self.num_rows += table.num_rows() as i64;
+        Ok(())
+    }
+
+    /// Write one column chunk, returning its metadata and uncompressed size
+    fn write_chunk(&mut self, column: &Column, nullable: bool) -> Result<(Struct, i64)> {
+        let (physical, _, _) = storage_type(column.data.data_type());
+        let values = physical_values(column);
+        let non_null = values.len();
+        let dictionary = if self.options.dictionary {
+            dictionary_encode(&values)
+                .filter(|(dict, _)| non_null > 0 && dict.len() * 2 <= non_null)
+        } else {
+            None
+        };
+
+        let chunk_start = self.offset;
+        let mut uncompressed_total = 0i64;
+        let mut dictionary_offset = None;
+        let mut encodings = vec![ENCODING_RLE];
+
+        let value_encoding = match &dictionary {
+            Some((dict, _)) => {
+                let mut body = Vec::new();
+                encoding::encode_plain(dict, &mut body);
+                dictionary_offset = Some(self.offset as i64);
+                let header = Struct::new().with(
+                    7,
+                    Value::Struct(
+                        Struct::new()
+                            .with(1, Value::I32(dict.len() as i32))
+                            .with(2, Value::I32(ENCODING_PLAIN)),
+                    ),
+                );
+                uncompressed_total += self.write_page(PAGE_DICTIONARY, header, &body)?;
+                encodings.push(ENCODING_PLAIN);
+                encodings.push(ENCODING_RLE_DICTIONARY);
+                ENCODING_RLE_DICTIONARY
+            }
+            None => {
+                encodings.push(ENCODING_PLAIN);
+                ENCODING_PLAIN
+            }
+        };
+
+        let data_page_offset = self.offset as i64;
+        let page_size = self.options.data_page_size;
+        let mut value_pos = 0;
+        let mut row = 0;
+        while row < column.len() {
+            let rows = page_size.min(column.len() - row);
+            let mut body = Vec::new();
+            let mut page_non_null = rows;
+            if nullable {
+                let levels: Vec<u32> = (row..row + rows)
+                    .map(|i| column.is_valid(i) as u32)
+                    .collect();
+                page_non_null = levels.iter().filter(|&&l| l == 1).count();
+                let encoded = encoding::encode_rle(&levels, 1);
+                body.extend_from_slice(&(encoded.len() as u32).to_le_bytes());
+                body.extend_from_slice(&encoded);
+            }
+            let range = value_pos..value_pos + page_non_null;
+            match &dictionary {
+                Some((dict, indices)) => {
+                    let width = encoding::bit_width(dict.len().saturating_sub(1) as u64);
+                    body.push(width);
+                    body.extend_from_slice(&encoding::encode_rle(&indices[range], width));
+                }
+                None => encoding::encode_plain(&slice_values(&values, range), &mut body),
+            }
+            value_pos += page_non_null;
+
+            let header = Struct::new().with(
+                5,
+                Value::Struct(
+                    Struct::new()
+                        .with(1, Value::I32(rows as i32))
+                        .with(2, Value::I32(value_encoding))
+                        .with(3, Value::I32(ENCODING_RLE))
+                        .with(4, Value::I32(ENCODING_RLE)),
+                ),
+            );
+            uncompressed_total += self.write_page(PAGE_DATA, header, &body)?;
+            row += rows;
+        }
+
+        let compressed_total = (self.offset - chunk_start) as i64;
+        let null_count = column.null_count() as i64;
+        let meta = Struct::new()
+            .with(1, Value::I32(physical.to_thrift()))
+            .with(
+                2,
+                Value::List(encodings.into_iter().map(Value::I32).collect()),
+            )
+            .with(3, Value::List(vec![thrift::string(&column.name)]))
+            .with(4, Value::I32(self.options.compression.to_thrift()))
+            .with(5, Value::I64(column.len() as i64))
+            .with(6, Value::I64(uncompressed_total))
+            .with(7, Value::I64(compressed_total))
+            .with(9, Value::I64(data_page_offset))
+            .with_opt(11, dictionary_offset.map(Value::I64))
+            .with(
+                12,
+                Value::Struct(Struct::new().with(3, Value::I64(null_count))),
+            );
+        let chunk = Struct::new()
+            .with(2, Value::I64(chunk_start as i64))
+            .with(3, Value::Struct(meta));
+        Ok((chunk, uncompressed_total))
+    }
+
+    /// Compress and write a page, returning its uncompressed size with header
+    fn write_page(&mut self, page_type: i32, header: Struct, body: &[u8]) -> Result<i64> {
+        let compressed = match self.options.compression.algorithm() {
+            Some(algorithm) => compress_data(body, algorithm, None)?,
+            None => body.to_vec(),
+        };
+        let header = header
+            .with(1, Value::I32(page_type))
+            .with(2, Value::I32(body.len() as i32))
+            .with(3, Value::I32(compressed.len() as i32));
+        let mut bytes = Vec::new();
+        header.encode(&mut bytes);
+        let header_len = bytes.len();
+        bytes.extend_from_slice(&compressed);
+        self.out.write_all(&bytes).map_err(io_error)?;
+        self.offset += bytes.len() as u64;
+        Ok((header_len + body.len()) as i64)
+    }
+}
+
+/// Non-null values of a column in their physical representation
+fn physical_values(column: &Column) -> Values {
+    fn valid<T: Clone, U>(column: &Column, values: &[T], f: impl Fn(T) -> U) -> Vec<U> {
+        values
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| column.is_valid(*i))
+            .map(|(_, v)| f(v.clone()))
+            .collect()
+    }
+    match &column.data {
+        ColumnData::Boolean(v) => Values::Boolean(valid(column, v, |x| x)),
+        ColumnData::Int8(v) => Values::Int32(valid(column, v, i32::from)),
+        ColumnData::Int16(v) => Values::Int32(valid(column, v, i32::from)),
+        ColumnData::Int32(v) => Values::Int32(valid(column, v, |x| x)),
+        ColumnData::Int64(v) => Values::Int64(valid(column, v, |x| x)),
+        ColumnData::UInt8(v) => Values::Int32(valid(column, v, i32::from)),
+        ColumnData::UInt16(v) => Values::Int32(valid(column, v, i32::from)),
+        ColumnData::UInt32(v) => Values::Int32(valid(column, v, |x| x as i32)),
+        ColumnData::UInt64(v) => Values::Int64(valid(column, v, |x| x as i64)),
+        ColumnData::Float32(v) => Values::Float(valid(column, v, |x| x)),
+        ColumnData::Float64(v) => Values::Double(valid(column, v, |x| x)),
+        ColumnData::Utf8(v) => Values::Bytes(valid(column, v, String::into_bytes)),
+        ColumnData::Binary(v) => Values::Bytes(valid(column, v, |x| x)),
+    }
+}
+
+fn slice_values(values: &Values, range: std::ops::Range<usize>) -> Values {
+    match values {
+        Values::Boolean(v) => Values::Boolean(v[range].to_vec()),
+        Values::Int32(v) => Values::Int32(v[range].to_vec()),
+        Values::Int64(v) => Values::Int64(v[range].to_vec()),
+        Values::Float(v) => Values::Float(v[range].to_vec()),
+        Values::Double(v) => Values::Double(v[range].to_vec()),
+        Values::Bytes(v) => Values::Bytes(v[range].to_vec()),
+    }
+}
+
+/// Split values into a dictionary of distinct values and indices into it
+///
+/// Booleans are never dictionary-encoded. Floats are keyed by bit pattern.
+fn dictionary_encode(values: &Values) -> Option<(Values, Vec<u32>)> {
+    fn build<T: Clone, K: Hash + Eq>(values: &[T], key: impl Fn(&T) -> K) -> (Vec<T>, Vec<u32>) {
+        let mut lookup = HashMap::new();
+        let mut dict = Vec::new();
+        let indices = values
+            .iter()
+            .map(|v| {
+                *lookup.entry(key(v)).or_insert_with(|| {
+                    dict.push(v.clone());
+                    (dict.len() - 1) as u32
+                })
+            })
+            .collect();
+        (dict, indices)
+    }
+    Some(match values {
+        Values::Boolean(_) => return None,
+        Values::Int32(v) => {
+            let (d, i) = build(v, |x| *x);
+            (Values::Int32(d), i)
+        }
+        Values::Int64(v) => {
+            let (d, i) = build(v, |x| *x);
+            (Values::Int64(d), i)
+        }
+        Values::Float(v) => {
+            let (d, i) = build(v, |x| x.to_bits());
+            (Values::Float(d), i)
+        }
+        Values::Double(v) => {
+            let (d, i) = build(v, |x| x.to_bits());
+            (Values::Double(d), i)
+        }
+        Values::Bytes(v) => {
+            let (d, i) = build(v, |x| x.clone());
+            (Values::Bytes(d), i)
+        }
+    })
+}
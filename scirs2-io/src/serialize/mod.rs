@@ -10,6 +10,7 @@
 //! - Matrix Market format integration
 //! - Compression support for sparse matrices
 //! - Memory-efficient sparse matrix operations
+//! - NumPy `.npy` and `.npz` files
 
 use ndarray::{Array, Array2, ArrayBase, IxDyn};
 use serde::{Deserialize, Serialize};
@@ -20,6 +21,13 @@ use std::path::Path;
 
 use crate::error::{IoError, Result};
 
+mod npy;
+mod zip;
+
+pub use npy::{
+    read_npy, read_npy_any, read_npz, write_npy, write_npz, NpyArray, NpyElement, NpzCompression,
+};
+
 /// Format for data serialization
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SerializationFormat {
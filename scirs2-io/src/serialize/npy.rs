@@ -0,0 +1,972 @@
+//! NumPy `.npy` and `.npz` files
+//!
+//! Arrays are read and written in the NumPy binary format (versions 1.0, 2.0
+//! and 3.0), with C or Fortran element order, either byte order, and the
+//! boolean, integer, floating-point and complex dtypes. `.npz` archives are ZIP
+//! files holding one `.npy` member per array, either stored or deflated.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use ndarray::{ArrayBase, ArrayD, Data, Dimension, IxDyn, ShapeBuilder};
+use num_complex::{Complex32, Complex64};
+
+use super::zip;
+use crate::error::{IoError, Result};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Element type that can be stored in a `.npy` file
+pub trait NpyElement: Copy + 'static {
+    /// NumPy type character (`b`, `i`, `u`, `f` or `c`)
+    const KIND: char;
+    /// Size of one element in bytes
+    const SIZE: usize;
+
+    /// Decode one element
+    fn from_bytes(bytes: &[u8], big_endian: bool) -> Self;
+
+    /// Append the little-endian encoding of the element
+    fn write_le(self, out: &mut Vec<u8>);
+
+    /// Wrap an array in the matching [`NpyArray`] variant
+    fn into_npy_array(array: ArrayD<Self>) -> NpyArray;
+
+    /// Unwrap an [`NpyArray`] of this element type
+    fn from_npy_array(array: NpyArray) -> std::result::Result<ArrayD<Self>, NpyArray>;
+}
+
+/// A `.npy` array of any supported dtype
+#[derive(Debug, Clone, PartialEq)]
+pub enum NpyArray {
+    /// `bool`
+    Bool(ArrayD<bool>),
+    /// `int8`
+    Int8(ArrayD<i8>),
+    /// `int16`
+    Int16(ArrayD<i16>),
+    /// `int32`
+    Int32(ArrayD<i32>),
+    /// `int64`
+    Int64(ArrayD<i64>),
+    /// `uint8`
+    UInt8(ArrayD<u8>),
+    /// `uint16`
+    UInt16(ArrayD<u16>),
+    /// `uint32`
+    UInt32(ArrayD<u32>),
+    /// `uint64`
+    UInt64(ArrayD<u64>),
+    /// `float32`
+    Float32(ArrayD<f32>),
+    /// `float64`
+    Float64(ArrayD<f64>),
+    /// `complex64`
+    Complex64(ArrayD<Complex32>),
+    /// `complex128`
+    Complex128(ArrayD<Complex64>),
+}
+
+macro_rules! npy_elements {
+    ($($ty:ty => $variant:ident, $kind:expr;)*) => {
+        $(
+            impl NpyElement for $ty {
+                const KIND: char = $kind;
+                const SIZE: usize = std::mem::size_of::<$ty>();
+
+                fn from_bytes(bytes: &[u8], big_endian: bool) -> Self {
+                    let bytes = bytes.try_into().unwrap();
+                    if big_endian {
+                        <$ty>::from_be_bytes(bytes)
+                    } else {
+                        <$ty>::from_le_bytes(bytes)
+                    }
+                }
+
+                fn write_le(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn into_npy_array(array: ArrayD<Self>) -> NpyArray {
+                    NpyArray::$variant(array)
+                }
+
+                fn from_npy_array(array: NpyArray) -> std::result::Result<ArrayD<Self>, NpyArray> {
+                    match array {
+                        NpyArray::$variant(array) => Ok(array),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+npy_elements! {
+    i8 => Int8, 'i';
+    i16 => Int16, 'i';
+    i32 => Int32, 'i';
+    i64 => Int64, 'i';
+    u8 => UInt8, 'u';
+    u16 => UInt16, 'u';
+    u32 => UInt32, 'u';
+    u64 => UInt64, 'u';
+    f32 => Float32, 'f';
+    f64 => Float64, 'f';
+}
+
+impl NpyElement for bool {
+    const KIND: char = 'b';
+    const SIZE: usize = 1;
+
+    fn from_bytes(bytes: &[u8], _big_endian: bool) -> Self {
+        bytes[0] != 0
+    }
+
+    fn write_le(self, out: &mut Vec<u8>) {
+        out.push(self as u8);
+    }
+
+    fn into_npy_array(array: ArrayD<Self>) -> NpyArray {
+        NpyArray::Bool(array)
+    }
+
+    fn from_npy_array(array: NpyArray) -> std::result::Result<ArrayD<Self>, NpyArray> {
+        match array {
+            NpyArray::Bool(array) => Ok(array),
+            other => Err(other),
+        }
+    }
+}
+
+macro_rules! npy_complex {
+    ($($ty:ty => $variant:ident;)*) => {
+        $(
+            impl NpyElement for $ty {
+                const KIND: char = 'c';
+                const SIZE: usize = std::mem::size_of::<$ty>();
+
+                fn from_bytes(bytes: &[u8], big_endian: bool) -> Self {
+                    let (re, im) = bytes.split_at(Self::SIZE / 2);
+                    Self::new(
+                        NpyElement::from_bytes(re, big_endian),
+                        NpyElement::from_bytes(im, big_endian),
+                    )
+                }
+
+                fn write_le(self, out: &mut Vec<u8>) {
+                    self.re.write_le(out);
+                    self.im.write_le(out);
+                }
+
+                fn into_npy_array(array: ArrayD<Self>) -> NpyArray {
+                    NpyArray::$variant(array)
+                }
+
+                fn from_npy_array(array: NpyArray) -> std::result::Result<ArrayD<Self>, NpyArray> {
+                    match array {
+                        NpyArray::$variant(array) => Ok(array),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+npy_complex! {
+    Complex32 => Complex64;
+    Complex64 => Complex128;
+}
+
+/// Apply an expression to the array inside any [`NpyArray`] variant
+macro_rules! with_npy_array {
+    ($value:expr, $array:ident => $body:expr) => {
+        match $value {
+            NpyArray::Bool($array) => $body,
+            NpyArray::Int8($array) => $body,
+            NpyArray::Int16($array) => $body,
+            NpyArray::Int32($array) => $body,
+            NpyArray::Int64($array) => $body,
+            NpyArray::UInt8($array) => $body,
+            NpyArray::UInt16($array) => $body,
+            NpyArray::UInt32($array) => $body,
+            NpyArray::UInt64($array) => $body,
+            NpyArray::Float32($array) => $body,
+            NpyArray::Float64($array) => $body,
+            NpyArray::Complex64($array) => $body,
+            NpyArray::Complex128($array) => $body,
+        }
+    };
+}
+
+impl NpyArray {
+    /// Shape of the array
+    pub fn shape(&self) -> &[usize] {
+        with_npy_array!(self, a => a.shape())
+    }
+
+    /// NumPy dtype string of the array as it is written, e.g. `<f8`
+    pub fn dtype(&self) -> String {
+        fn descr<T: NpyElement>(_: &ArrayD<T>) -> String {
+            dtype_descr::<T>()
+        }
+        with_npy_array!(self, a => descr(a))
+    }
+
+    /// Extract the array with element type `T`
+    ///
+    /// Fails if the array holds a different dtype; no conversion is performed.
+    pub fn into_array<T: NpyElement>(self) -> Result<ArrayD<T>> {
+        T::from_npy_array(self).map_err(|other| {
+            IoError::ValidationError(format!(
+                "Array has dtype '{}', not '{}'",
+                other.dtype(),
+                dtype_descr::<T>()
+            ))
+        })
+    }
+}
+
+impl<T: NpyElement> From<ArrayD<T>> for NpyArray {
+    fn from(array: ArrayD<T>) -> Self {
+        T::into_npy_array(array)
+    }
+}
+
+/// Compression of the members of a `.npz` archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NpzCompression {
+    /// Members are stored uncompressed, like `numpy.savez`
+    #[default]
+    Stored,
+    /// Members are deflated, like `numpy.savez_compressed`
+    Deflated,
+}
+
+fn dtype_descr<T: NpyElement>() -> String {
+    let order = if T::SIZE == 1 { '|' } else { '<' };
+    format!("{}{}{}", order, T::KIND, T::SIZE)
+}
+
+/// Parsed `.npy` header
+struct Header {
+    kind: char,
+    size: usize,
+    big_endian: bool,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+impl Header {
+    fn descr(&self) -> String {
+        let order = match (self.size, self.big_endian) {
+            (1, _) => '|',
+            (_, true) => '>',
+            (_, false) => '<',
+        };
+        format!("{}{}{}", order, self.kind, self.size)
+    }
+
+    fn len(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Check that the header describes elements of type `T`
+    fn check<T: NpyElement>(&self) -> Result<()> {
+        if self.kind != T::KIND || self.size != T::SIZE {
+            return Err(IoError::ValidationError(format!(
+                "Array has dtype '{}', not '{}'",
+                self.descr(),
+                dtype_descr::<T>()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Value of the Python literal in a `.npy` header
+enum Literal {
+    Str(String),
+    Bool(bool),
+    Int(usize),
+    Sequence(Vec<Literal>),
+}
+
+/// Parser for the Python dictionary literal of a `.npy` header
+struct LiteralParser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl LiteralParser<'_> {
+    fn error(&self, message: &str) -> IoError {
+        IoError::FormatError(format!(
+            "Invalid .npy header at offset {}: {}",
+            self.pos, message
+        ))
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        while self.pos < self.text.len() && self.text[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        self.text.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<()> {
+        if self.peek() != Some(c) {
+            return Err(self.error(&format!("expected '{}'", c as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn dict(&mut self) -> Result<HashMap<String, Literal>> {
+        self.expect(b'{')?;
+        let mut dict = HashMap::new();
+        while self.peek() != Some(b'}') {
+            let Literal::Str(key) = self.value()? else {
+                return Err(self.error("dictionary keys must be strings"));
+            };
+            self.expect(b':')?;
+            dict.insert(key, self.value()?);
+            if self.peek() == Some(b',') {
+                self.pos += 1;
+            } else if self.peek() != Some(b'}') {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+        self.pos += 1;
+        Ok(dict)
+    }
+
+    fn value(&mut self) -> Result<Literal> {
+        match self.peek() {
+            Some(quote @ (b'\'' | b'"')) => {
+                self.pos += 1;
+                let start = self.pos;
+                while self.text.get(self.pos).is_some_and(|&c| c != quote) {
+                    self.pos += 1;
+                }
+                if self.pos == self.text.len() {
+                    return Err(self.error("unterminated string"));
+                }
+                let value = String::from_utf8_lossy(&self.text[start..self.pos]).into_owned();
+                self.pos += 1;
+                Ok(Literal::Str(value))
+            }
+            Some(open @ (b'(' | b'[')) => {
+                let close = if open == b'(' { b')' } else { b']' };
+                self.pos += 1;
+                let mut items = Vec::new();
+                while self.peek() != Some(close) {
+                    items.push(self.value()?);
+                    if self.peek() == Some(b',') {
+                        self.pos += 1;
+                    } else if self.peek() != Some(close) {
+                        return Err(self.error("expected ',' or end of sequence"));
+                    }
+                }
+                self.pos += 1;
+                Ok(Literal::Sequence(items))
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.pos;
+                while self.text.get(self.pos).is_some_and(u8::is_ascii_digit) {
+                    self.pos += 1;
+                }
+                let digits = std::str::from_utf8(&self.text[start..self.pos]).unwrap();
+                let value = digits.parse().map_err(|_| self.error("invalid integer"))?;
+                // Python 2 long integer suffix
+                if self.text.get(self.pos) == Some(&b'L') {
+                    self.pos += 1;
+                }
+                Ok(Literal::Int(value))
+            }
+            Some(_) if self.text[self.pos..].starts_with(b"True") => {
+                self.pos += 4;
+                Ok(Literal::Bool(true))
+            }
+            Some(_) if self.text[self.pos..].starts_with(b"False") => {
+                self.pos += 5;
+                Ok(Literal::Bool(false))
+            }
+            _ => Err(self.error("unsupported value")),
+        }
+    }
+}
+
+/// Read and parse the header of a `.npy` stream
+///
+/// Returns the header and the offset of the array data.
+fn read_header<R: Read>(reader: &mut R) -> Result<(Header, usize)> {
+    let io = |e: std::io::Error| IoError::FormatError(format!("Invalid .npy header: {}", e));
+    let mut preamble = [0u8; 8];
+    reader.read_exact(&mut preamble).map_err(io)?;
+    if &preamble[..6] != MAGIC {
+        return Err(IoError::FormatError("Not a NumPy .npy file".to_string()));
+    }
+    let (header_length, prefix) = match preamble[6] {
+        1 => {
+            let mut length = [0u8; 2];
+            reader.read_exact(&mut length).map_err(io)?;
+            (u16::from_le_bytes(length) as usize, 10)
+        }
+        2 | 3 => {
+            let mut length = [0u8; 4];
+            reader.read_exact(&mut length).map_err(io)?;
+            (u32::from_le_bytes(length) as usize, 12)
+        }
+        major => {
+            return Err(IoError::FormatError(format!(
+                "Unsupported .npy format version {}.{}",
+                major, preamble[7]
+            )))
+        }
+    };
+    let mut text = vec![0u8; header_length];
+    reader.read_exact(&mut text).map_err(io)?;
+
+    let mut dict = LiteralParser {
+        text: &text,
+        pos: 0,
+    }
+    .dict()?;
+    let missing = |key: &str| IoError::FormatError(format!(".npy header has no valid '{}'", key));
+
+    let descr = match dict.remove("descr") {
+        Some(Literal::Str(descr)) => descr,
+        Some(Literal::Sequence(_)) => {
+            return Err(IoError::FormatError(
+                "Structured dtypes are not supported".to_string(),
+            ))
+        }
+        _ => return Err(missing("descr")),
+    };
+    let fortran_order = match dict.remove("fortran_order") {
+        Some(Literal::Bool(value)) => value,
+        _ => return Err(missing("fortran_order")),
+    };
+    let shape = match dict.remove("shape") {
+        Some(Literal::Sequence(items)) => items
+            .into_iter()
+            .map(|item| match item {
+                Literal::Int(n) => Ok(n),
+                _ => Err(missing("shape")),
+            })
+            .collect::<Result<Vec<_>>>()?,
+        _ => return Err(missing("shape")),
+    };
+
+    let unsupported = || IoError::FormatError(format!("Unsupported dtype '{}'", descr));
+    let mut chars = descr.chars();
+    let big_endian = match chars.next() {
+        Some('<') | Some('|') => false,
+        Some('>') => true,
+        Some('=') => cfg!(target_endian = "big"),
+        _ => return Err(unsupported()),
+    };
+    let kind = chars.next().ok_or_else(unsupported)?;
+    let size: usize = chars.as_str().parse().map_err(|_| unsupported())?;
+    let supported = match kind {
+        'b' => size == 1,
+        'i' | 'u' => matches!(size, 1 | 2 | 4 | 8),
+        'f' => matches!(size, 4 | 8),
+        'c' => matches!(size, 8 | 16),
+        _ => false,
+    };
+    if !supported {
+        return Err(unsupported());
+    }
+
+    let header = Header {
+        kind,
+        size,
+        big_endian,
+        fortran_order,
+        shape,
+    };
+    Ok((header, prefix + header_length))
+}
+
+/// Read the data of an array described by `header`
+fn read_data<T: NpyElement, R: Read>(reader: &mut R, header: &Header) -> Result<ArrayD<T>> {
+    header.check::<T>()?;
+    let length = header
+        .len()
+        .checked_mul(T::SIZE)
+        .ok_or_else(|| IoError::FormatError("Array is too large".to_string()))?;
+    let mut bytes = vec![0u8; length];
+    reader.read_exact(&mut bytes).map_err(|_| {
+        IoError::FormatError(format!(
+            "Truncated .npy data: expected {} bytes for shape {:?}",
+            length, header.shape
+        ))
+    })?;
+    let values: Vec<T> = bytes
+        .chunks_exact(T::SIZE)
+        .map(|chunk| T::from_bytes(chunk, header.big_endian))
+        .collect();
+
+    let shape = IxDyn(&header.shape);
+    let array = if header.fortran_order {
+        ArrayD::from_shape_vec(shape.f(), values)
+    } else {
+        ArrayD::from_shape_vec(shape, values)
+    };
+    array.map_err(|e| IoError::FormatError(e.to_string()))
+}
+
+/// Read an array of the dtype given in the header
+fn read_any<R: Read>(reader: &mut R) -> Result<NpyArray> {
+    let (header, _) = read_header(reader)?;
+    Ok(match (header.kind, header.size) {
+        ('b', _) => NpyArray::Bool(read_data(reader, &header)?),
+        ('i', 1) => NpyArray::Int8(read_data(reader, &header)?),
+        ('i', 2) => NpyArray::Int16(read_data(reader, &header)?),
+        ('i', 4) => NpyArray::Int32(read_data(reader, &header)?),
+        ('i', _) => NpyArray::Int64(read_data(reader, &header)?),
+        ('u', 1) => NpyArray::UInt8(read_data(reader, &header)?),
+        ('u', 2) => NpyArray::UInt16(read_data(reader, &header)?),
+        ('u', 4) => NpyArray::UInt32(read_data(reader, &header)?),
+        ('u', _) => NpyArray::UInt64(read_data(reader, &header)?),
+        ('f', 4) => NpyArray::Float32(read_data(reader, &header)?),
+        ('f', _) => NpyArray::Float64(read_data(reader, &header)?),
+        ('c', 8) => NpyArray::Complex64(read_data(reader, &header)?),
+        _ => NpyArray::Complex128(read_data(reader, &header)?),
+    })
+}
+
+/// Encode an array in the `.npy` format
+///
+/// Arrays in Fortran (column-major) layout are written with `fortran_order`
+/// set, anything else in C order.
+fn encode<T, S, D>(array: &ArrayBase<S, D>) -> Vec<u8>
+where
+    T: NpyElement,
+    S: Data<Elem = T>,
+    D: Dimension,
+{
+    let fortran_order = array.ndim() > 1 && !array.is_standard_layout() && {
+        let transposed = array.view().reversed_axes();
+        transposed.is_standard_layout()
+    };
+    let shape = match array.shape() {
+        [n] => format!("({},)", n),
+        shape => format!(
+            "({})",
+            shape
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut dict = format!(
+        "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+        dtype_descr::<T>(),
+        if fortran_order { "True" } else { "False" },
+        shape
+    );
+
+    // Version 1.0 has a 16-bit header length; the whole header is padded to
+    // a multiple of 64 bytes and terminated by a newline
+    let (version, prefix) = if dict.len() + 11 + 64 <= u16::MAX as usize {
+        (1u8, 10)
+    } else {
+        (2u8, 12)
+    };
+    let padding = (64 - (prefix + dict.len() + 1) % 64) % 64;
+    dict.extend(std::iter::repeat_n(' ', padding));
+    dict.push('\n');
+
+    let mut out = Vec::with_capacity(prefix + dict.len() + array.len() * T::SIZE);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&[version, 0]);
+    if version == 1 {
+        out.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+    } else {
+        out.extend_from_slice(&(dict.len() as u32).to_le_bytes());
+    }
+    out.extend_from_slice(dict.as_bytes());
+    if fortran_order {
+        array
+            .view()
+            .reversed_axes()
+            .iter()
+            .for_each(|&x| x.write_le(&mut out));
+    } else {
+        array.iter().for_each(|&x| x.write_le(&mut out));
+    }
+    out
+}
+
+fn open(path: &Path) -> Result<BufReader<File>> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| IoError::FileError(format!("{}: {}", path.display(), e)))
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>> {
+    File::create(path)
+        .map(BufWriter::new)
+        .map_err(|e| IoError::FileError(format!("{}: {}", path.display(), e)))
+}
+
+/// Read a NumPy `.npy` file
+///
+/// The dtype stored in the file must match `T` exactly; use
+/// [`read_npy_any`] to read an array of unknown dtype. Big-endian data and
+/// Fortran-ordered arrays are converted transparently.
+///
+/// # Arguments
+///
+/// * `path` - Path to the `.npy` file
+///
+/// # Returns
+///
+/// The array with the shape given in the file
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, ArrayD};
+/// use scirs2_io::serialize::{read_npy, write_npy};
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let path = dir.path().join("data.npy");
+///
+/// write_npy(&path, &array![[1.0, 2.0], [3.0, 4.0]]).unwrap();
+/// let read: ArrayD<f64> = read_npy(&path).unwrap();
+/// assert_eq!(read, array![[1.0, 2.0], [3.0, 4.0]].into_dyn());
+/// ```
+pub fn read_npy<P: AsRef<Path>, T: NpyElement>(path: P) -> Result<ArrayD<T>> {
+    let mut reader = open(path.as_ref())?;
+    let (header, _) = read_header(&mut reader)?;
+    read_data(&mut reader, &header)
+}
+
+/// Read a NumPy `.npy` file of any supported dtype
+///
+/// # Arguments
+///
+/// * `path` - Path to the `.npy` file
+///
+/// # Returns
+///
+/// The array, wrapped in the [`NpyArray`] variant of its dtype
+pub fn read_npy_any<P: AsRef<Path>>(path: P) -> Result<NpyArray> {
+    read_any(&mut open(path.as_ref())?)
+}
+
+/// Write an array to a NumPy `.npy` file
+///
+/// Data is written little-endian in format version 1.0, or 2.0 for very
+/// large headers. Arrays in Fortran layout are stored in Fortran order.
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to create
+/// * `array` - The array to write
+pub fn write_npy<P, T, S, D>(path: P, array: &ArrayBase<S, D>) -> Result<()>
+where
+    P: AsRef<Path>,
+    T: NpyElement,
+    S: Data<Elem = T>,
+    D: Dimension,
+{
+    let mut writer = create(path.as_ref())?;
+    writer
+        .write_all(&encode(array))
+        .and_then(|_| writer.flush())
+        .map_err(|e| IoError::FileError(e.to_string()))
+}
+
+/// Read all arrays of a NumPy `.npz` archive
+///
+/// Both stored (`numpy.savez`) and deflated (`numpy.savez_compressed`)
+/// archives are supported.
+///
+/// # Arguments
+///
+/// * `path` - Path to the `.npz` file
+///
+/// # Returns
+///
+/// The arrays keyed by name, without the `.npy` extension of the members
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use ndarray::array;
+/// use scirs2_io::serialize::{read_npz, write_npz, NpzCompression};
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let path = dir.path().join("arrays.npz");
+///
+/// let mut arrays = HashMap::new();
+/// arrays.insert("x".to_string(), array![1.0f32, 2.0].into_dyn().into());
+/// arrays.insert("n".to_string(), array![[1i64, 2], [3, 4]].into_dyn().into());
+/// write_npz(&path, &arrays, NpzCompression::Deflated).unwrap();
+///
+/// let read = read_npz(&path).unwrap();
+/// let n = read["n"].clone().into_array::<i64>().unwrap();
+/// assert_eq!(n.shape(), &[2, 2]);
+/// ```
+pub fn read_npz<P: AsRef<Path>>(path: P) -> Result<HashMap<String, NpyArray>> {
+    let path = path.as_ref();
+    let data = std::fs::read(path)
+        .map_err(|e| IoError::FileError(format!("{}: {}", path.display(), e)))?;
+    zip::read_archive(&data)?
+        .into_iter()
+        .map(|(name, contents)| {
+            let key = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+            let array = read_any(&mut contents.as_slice())
+                .map_err(|e| IoError::FormatError(format!("{}: {}", name, e)))?;
+            Ok((key, array))
+        })
+        .collect()
+}
+
+/// Write arrays to a NumPy `.npz` archive
+///
+/// Each array becomes a member named after its key with a `.npy` extension.
+/// Members are written in key order.
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to create
+/// * `arrays` - The arrays to write, keyed by name
+/// * `compression` - Whether to store or deflate the members
+pub fn write_npz<P: AsRef<Path>>(
+    path: P,
+    arrays: &HashMap<String, NpyArray>,
+    compression: NpzCompression,
+) -> Result<()> {
+    let mut keys: Vec<&String> = arrays.keys().collect();
+    keys.sort();
+    let members: Vec<(String, Vec<u8>)> = keys
+        .into_iter()
+        .map(|key| {
+            let bytes = with_npy_array!(&arrays[key], a => encode(a));
+            (format!("{}.npy", key), bytes)
+        })
+        .collect();
+    let mut writer = create(path.as_ref())?;
+    zip::write_archive(
+        &mut writer,
+        &members,
+        compression == NpzCompression::Deflated,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array, Array3};
+    use tempfile::tempdir;
+
+    /// Build a `.npy` file by hand, the way NumPy would write it
+    fn npy_bytes(version: u8, header: &str, data: &[u8]) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        out.extend_from_slice(&[version, 0]);
+        if version == 1 {
+            out.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        } else {
+            out.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        }
+        out.extend_from_slice(header.as_bytes());
+        out.extend_from_slice(data);
+        out
+    }
+
+    #[test]
+    fn test_roundtrip_all_dtypes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.npy");
+
+        fn check<T: NpyElement + PartialEq + std::fmt::Debug>(path: &Path, values: Vec<T>) {
+            let array = Array::from_shape_vec((2, 3), values).unwrap().into_dyn();
+            write_npy(path, &array).unwrap();
+            assert_eq!(read_npy::<_, T>(path).unwrap(), array);
+            assert_eq!(read_npy_any(path).unwrap(), NpyArray::from(array));
+        }
+
+        check(&path, vec![true, false, true, true, false, false]);
+        check(&path, vec![-1i8, 2, -3, 4, -5, 6]);
+        check(&path, vec![-1i16, 2, -3, 4, -5, i16::MAX]);
+        check(&path, vec![-1i32, 2, -3, 4, -5, i32::MIN]);
+        check(&path, vec![-1i64, 2, -3, 4, -5, i64::MAX]);
+        check(&path, vec![1u8, 2, 3, 4, 5, 255]);
+        check(&path, vec![1u16, 2, 3, 4, 5, u16::MAX]);
+        check(&path, vec![1u32, 2, 3, 4, 5, u32::MAX]);
+        check(&path, vec![1u64, 2, 3, 4, 5, u64::MAX]);
+        check(&path, vec![0.5f32, -1.5, 2.0, 1e-30, f32::MAX, 0.0]);
+        check(&path, vec![0.5f64, -1.5, 2.0, 1e-300, f64::MIN, 0.0]);
+        check(
+            &path,
+            (0..6).map(|i| Complex32::new(i as f32, -1.0)).collect(),
+        );
+        check(
+            &path,
+            (0..6).map(|i| Complex64::new(0.5, i as f64)).collect(),
+        );
+
+        // Header is padded to a multiple of 64 bytes
+        let bytes = std::fs::read(&path).unwrap();
+        let header_length = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_length) % 64, 0);
+        assert_eq!(bytes[9 + header_length], b'\n');
+        assert_eq!(bytes.len(), 10 + header_length + 6 * 16);
+    }
+
+    #[test]
+    fn test_shapes_and_fortran_order() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.npy");
+
+        let scalar = ndarray::arr0(7.5f64).into_dyn();
+        write_npy(&path, &scalar).unwrap();
+        assert_eq!(read_npy::<_, f64>(&path).unwrap(), scalar);
+
+        let empty = Array::<i32, _>::zeros((0, 3)).into_dyn();
+        write_npy(&path, &empty).unwrap();
+        assert_eq!(read_npy::<_, i32>(&path).unwrap(), empty);
+
+        // A Fortran-layout array keeps its logical values
+        let array = Array3::from_shape_fn((2, 3, 4).f(), |(i, j, k)| (i * 100 + j * 10 + k) as u16);
+        write_npy(&path, &array).unwrap();
+        let text = String::from_utf8_lossy(&std::fs::read(&path).unwrap()[10..80]).into_owned();
+        assert!(text.contains("'fortran_order': True"));
+        assert_eq!(read_npy::<_, u16>(&path).unwrap(), array.clone().into_dyn());
+
+        // Non-contiguous views are written in C order
+        let view = array.slice(ndarray::s![.., ..;2, 1..]);
+        write_npy(&path, &view).unwrap();
+        assert_eq!(
+            read_npy::<_, u16>(&path).unwrap(),
+            view.to_owned().into_dyn()
+        );
+    }
+
+    #[test]
+    fn test_reads_foreign_headers() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.npy");
+
+        // Big-endian, Fortran order, version 2.0 header with double quotes
+        let data: Vec<u8> = [1i32, 2, 3, 4, 5, 6]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        let header = "{\"descr\": \">i4\", \"fortran_order\": True, \"shape\": (2, 3)}\n";
+        std::fs::write(&path, npy_bytes(2, header, &data)).unwrap();
+        assert_eq!(
+            read_npy::<_, i32>(&path).unwrap(),
+            array![[1, 3, 5], [2, 4, 6]].into_dyn()
+        );
+
+        // Version 3.0 header with Python 2 long integers and big-endian complex
+        let data: Vec<u8> = [1.0f64, -2.0]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        let header = "{'descr': '>c16', 'shape': (1L,), 'fortran_order': False}\n";
+        std::fs::write(&path, npy_bytes(3, header, &data)).unwrap();
+        assert_eq!(
+            read_npy::<_, Complex64>(&path).unwrap(),
+            array![Complex64::new(1.0, -2.0)].into_dyn()
+        );
+
+        // Wrong dtype, structured dtype, truncated data and bad magic
+        assert!(read_npy::<_, f64>(&path).is_err());
+        assert!(read_npy_any(&path).unwrap().into_array::<f64>().is_err());
+        let header = "{'descr': [('a', '<f8')], 'fortran_order': False, 'shape': (1,)}\n";
+        std::fs::write(&path, npy_bytes(1, header, &[0; 8])).unwrap();
+        assert!(read_npy_any(&path).is_err());
+        let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (4,)}\n";
+        std::fs::write(&path, npy_bytes(1, header, &[0; 8])).unwrap();
+        assert!(read_npy_any(&path).is_err());
+        std::fs::write(&path, b"\x93NUMPZ\x01\x00").unwrap();
+        assert!(read_npy_any(&path).is_err());
+    }
+
+    #[test]
+    fn test_npz_roundtrip() {
+        let dir = tempdir().unwrap();
+        let mut arrays = HashMap::new();
+        arrays.insert(
+            "values".to_string(),
+            NpyArray::from(Array::linspace(0.0, 1.0, 1000).into_dyn()),
+        );
+        arrays.insert(
+            "mask".to_string(),
+            NpyArray::from(array![[true, false], [false, true]].into_dyn()),
+        );
+        arrays.insert(
+            "z".to_string(),
+            NpyArray::from(array![Complex32::new(1.0, 2.0)].into_dyn()),
+        );
+
+        for compression in [NpzCompression::Stored, NpzCompression::Deflated] {
+            let path = dir.path().join(format!("{:?}.npz", compression));
+            write_npz(&path, &arrays, compression).unwrap();
+            assert_eq!(read_npz(&path).unwrap(), arrays);
+        }
+        let stored = std::fs::metadata(dir.path().join("Stored.npz")).unwrap();
+        let deflated = std::fs::metadata(dir.path().join("Deflated.npz")).unwrap();
+        assert!(deflated.len() < stored.len());
+
+        // Corrupted member data fails the checksum
+        let path = dir.path().join("Stored.npz");
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[1000] ^= 0xFF;
+        std::fs::write(&path, bytes).unwrap();
+        assert!(read_npz(&path).is_err());
+    }
+
+    #[test]
+    fn test_zip64_archive() {
+        // Small archives end with the plain end of central directory record
+        let members = vec![("a.npy".to_string(), encode(&array![1u8, 2, 3]))];
+        let mut archive = Vec::new();
+        zip::write_archive(&mut archive, &members, false).unwrap();
+        assert_eq!(zip::read_archive(&archive).unwrap(), members);
+
+        // More than 65534 members need the ZIP64 end records
+        let many: Vec<(String, Vec<u8>)> = (0..0x10000)
+            .map(|i| (format!("{}.npy", i), Vec::new()))
+            .collect();
+        let mut archive = Vec::new();
+        zip::write_archive(&mut archive, &many, true).unwrap();
+        let read = zip::read_archive(&archive).unwrap();
+        assert_eq!(read.len(), 0x10000);
+        assert_eq!(read[0xFFFF].0, "65535.npy");
+    }
+
+    #[test]
+    fn test_zip_untrusted_sizes() {
+        let members = vec![("a.npy".to_string(), vec![7u8; 1000])];
+        let mut archive = Vec::new();
+        zip::write_archive(&mut archive, &members, true).unwrap();
+        let central = archive
+            .windows(4)
+            .position(|w| w == 0x0201_4b50u32.to_le_bytes())
+            .unwrap();
+
+        // Sizes that disagree with the deflated data are rejected without
+        // allocating or inflating more than the data itself supports
+        for size in [0xFFFF_FFFEu32, 1] {
+            let mut bytes = archive.clone();
+            bytes[central + 24..central + 28].copy_from_slice(&size.to_le_bytes());
+            assert!(matches!(
+                zip::read_archive(&bytes),
+                Err(IoError::ChecksumError(_))
+            ));
+        }
+    }
+}
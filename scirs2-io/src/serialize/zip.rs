@@ -0,0 +1,330 @@
+//! Minimal ZIP archive support for `.npz` files
+//!
+//! Only what NumPy produces and consumes is implemented: stored and deflated
+//! members, ZIP64 extensions for large archives, and no encryption or
+//! multi-disk archives.
+
+use std::borrow::Cow;
+use std::io::{Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use crate::error::{IoError, Result};
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const ZIP64_END_OF_CENTRAL_DIRECTORY: u32 = 0x0606_4b50;
+const ZIP64_LOCATOR: u32 = 0x0706_4b50;
+const ZIP64_EXTRA: u16 = 0x0001;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+
+/// MS-DOS date of 1980-01-01, the earliest representable date
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+/// Little-endian cursor over a byte slice
+struct Bytes<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Bytes<'a> {
+    fn at(data: &'a [u8], pos: usize) -> Self {
+        Self { data, pos }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| IoError::FormatError("Truncated ZIP archive".to_string()))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+fn to_usize(value: u64) -> Result<usize> {
+    usize::try_from(value).map_err(|_| IoError::FormatError("ZIP archive is too large".to_string()))
+}
+
+/// Read all members of a ZIP archive as `(name, contents)` pairs
+pub(crate) fn read_archive(data: &[u8]) -> Result<Vec<(String, Vec<u8>)>> {
+    // The end of central directory record is followed by a comment of at most 64 KiB
+    let search_start = data.len().saturating_sub(22 + 0xFFFF);
+    let eocd = (search_start..data.len().saturating_sub(21))
+        .rev()
+        .find(|&i| data[i..i + 4] == END_OF_CENTRAL_DIRECTORY.to_le_bytes())
+        .ok_or_else(|| IoError::FormatError("Not a ZIP archive".to_string()))?;
+
+    let mut record = Bytes::at(data, eocd + 10);
+    let mut entries = u64::from(record.u16()?);
+    let mut directory_size = u64::from(record.u32()?);
+    let mut directory_offset = u64::from(record.u32()?);
+
+    if entries == 0xFFFF || directory_size == 0xFFFF_FFFF || directory_offset == 0xFFFF_FFFF {
+        let locator = eocd
+            .checked_sub(20)
+            .filter(|&i| data[i..i + 4] == ZIP64_LOCATOR.to_le_bytes())
+            .ok_or_else(|| IoError::FormatError("Missing ZIP64 locator".to_string()))?;
+        let offset = to_usize(Bytes::at(data, locator + 8).u64()?)?;
+        let mut record = Bytes::at(data, offset);
+        if record.u32()? != ZIP64_END_OF_CENTRAL_DIRECTORY {
+            return Err(IoError::FormatError(
+                "Invalid ZIP64 end of central directory".to_string(),
+            ));
+        }
+        record.take(20)?;
+        record.u64()?;
+        entries = record.u64()?;
+        directory_size = record.u64()?;
+        directory_offset = record.u64()?;
+    }
+
+    let directory_end = to_usize(directory_offset.saturating_add(directory_size))?;
+    if directory_end > data.len() {
+        return Err(IoError::FormatError(
+            "ZIP central directory lies outside the file".to_string(),
+        ));
+    }
+
+    let mut directory = Bytes::at(data, to_usize(directory_offset)?);
+    let mut members = Vec::new();
+    for _ in 0..entries {
+        if directory.u32()? != CENTRAL_HEADER {
+            return Err(IoError::FormatError(
+                "Invalid ZIP central directory entry".to_string(),
+            ));
+        }
+        directory.take(4)?;
+        let flags = directory.u16()?;
+        let method = directory.u16()?;
+        directory.take(4)?;
+        let crc = directory.u32()?;
+        let mut compressed_size = u64::from(directory.u32()?);
+        let mut size = u64::from(directory.u32()?);
+        let name_length = directory.u16()? as usize;
+        let extra_length = directory.u16()? as usize;
+        let comment_length = directory.u16()? as usize;
+        directory.take(8)?;
+        let mut offset = u64::from(directory.u32()?);
+        let name = String::from_utf8_lossy(directory.take(name_length)?).into_owned();
+        let mut extra = Bytes::at(directory.take(extra_length)?, 0);
+        directory.take(comment_length)?;
+
+        if flags & 1 != 0 {
+            return Err(IoError::FormatError(format!(
+                "Encrypted ZIP member '{}' is not supported",
+                name
+            )));
+        }
+
+        // ZIP64 fields are present only for the values that overflowed
+        while extra.pos + 4 <= extra.data.len() {
+            let id = extra.u16()?;
+            let length = extra.u16()? as usize;
+            let mut field = Bytes::at(extra.take(length)?, 0);
+            if id == ZIP64_EXTRA {
+                if size == 0xFFFF_FFFF {
+                    size = field.u64()?;
+                }
+                if compressed_size == 0xFFFF_FFFF {
+                    compressed_size = field.u64()?;
+                }
+                if offset == 0xFFFF_FFFF {
+                    offset = field.u64()?;
+                }
+            }
+        }
+
+        let mut local = Bytes::at(data, to_usize(offset)?);
+        if local.u32()? != LOCAL_HEADER {
+            return Err(IoError::FormatError(format!(
+                "Invalid ZIP local header for '{}'",
+                name
+            )));
+        }
+        local.take(22)?;
+        let skip = local.u16()? as usize + local.u16()? as usize;
+        local.take(skip)?;
+        let raw = local.take(to_usize(compressed_size)?)?;
+
+        let contents = match method {
+            METHOD_STORED => raw.to_vec(),
+            METHOD_DEFLATED => {
+                // The declared size is untrusted: preallocate no more than the
+                // compressed length and stop inflating just past the declared size
+                let mut contents = Vec::with_capacity(to_usize(size.min(raw.len() as u64))?);
+                DeflateDecoder::new(raw)
+                    .take(size.saturating_add(1))
+                    .read_to_end(&mut contents)
+                    .map_err(|e| IoError::DecompressionError(format!("{}: {}", name, e)))?;
+                contents
+            }
+            _ => {
+                return Err(IoError::FormatError(format!(
+                    "ZIP member '{}' uses unsupported compression method {}",
+                    name, method
+                )))
+            }
+        };
+        if contents.len() as u64 != size || crc32fast::hash(&contents) != crc {
+            return Err(IoError::ChecksumError(format!(
+                "ZIP member '{}' is corrupt",
+                name
+            )));
+        }
+        members.push((name, contents));
+    }
+    Ok(members)
+}
+
+/// Write `(name, contents)` pairs as a ZIP archive
+pub(crate) fn write_archive<W: Write>(
+    out: &mut W,
+    members: &[(String, Vec<u8>)],
+    deflate: bool,
+) -> Result<()> {
+    let io = |e: std::io::Error| IoError::FileError(e.to_string());
+    let method = if deflate {
+        METHOD_DEFLATED
+    } else {
+        METHOD_STORED
+    };
+
+    let mut offset = 0u64;
+    let mut directory = Vec::new();
+    for (name, contents) in members {
+        let crc = crc32fast::hash(contents);
+        let data: Cow<[u8]> = if deflate {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder
+                .write_all(contents)
+                .map_err(|e| IoError::CompressionError(e.to_string()))?;
+            Cow::Owned(
+                encoder
+                    .finish()
+                    .map_err(|e| IoError::CompressionError(e.to_string()))?,
+            )
+        } else {
+            Cow::Borrowed(contents)
+        };
+        let size = contents.len() as u64;
+        let compressed_size = data.len() as u64;
+        let large = size >= 0xFFFF_FFFF || compressed_size >= 0xFFFF_FFFF;
+
+        // Local header, with both sizes moved to a ZIP64 field if either overflows
+        let mut header = Vec::with_capacity(30 + name.len() + 20);
+        header.extend_from_slice(&LOCAL_HEADER.to_le_bytes());
+        header.extend_from_slice(&(if large { 45u16 } else { 20 }).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(&method.to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(&DOS_DATE.to_le_bytes());
+        header.extend_from_slice(&crc.to_le_bytes());
+        let clamp = |v: u64| if large { 0xFFFF_FFFF } else { v as u32 };
+        header.extend_from_slice(&clamp(compressed_size).to_le_bytes());
+        header.extend_from_slice(&clamp(size).to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&(if large { 20u16 } else { 0 }).to_le_bytes());
+        header.extend_from_slice(name.as_bytes());
+        if large {
+            header.extend_from_slice(&ZIP64_EXTRA.to_le_bytes());
+            header.extend_from_slice(&16u16.to_le_bytes());
+            header.extend_from_slice(&size.to_le_bytes());
+            header.extend_from_slice(&compressed_size.to_le_bytes());
+        }
+        out.write_all(&header).map_err(io)?;
+        out.write_all(&data).map_err(io)?;
+
+        // Central directory entry
+        let mut extra = Vec::new();
+        let mut field = |value: u64| {
+            if value >= 0xFFFF_FFFF {
+                extra.extend_from_slice(&value.to_le_bytes());
+                0xFFFF_FFFFu32
+            } else {
+                value as u32
+            }
+        };
+        let size32 = field(size);
+        let compressed_size32 = field(compressed_size);
+        let offset32 = field(offset);
+        let version = if extra.is_empty() { 20u16 } else { 45 };
+        directory.extend_from_slice(&CENTRAL_HEADER.to_le_bytes());
+        directory.extend_from_slice(&version.to_le_bytes());
+        directory.extend_from_slice(&version.to_le_bytes());
+        directory.extend_from_slice(&0u16.to_le_bytes());
+        directory.extend_from_slice(&method.to_le_bytes());
+        directory.extend_from_slice(&0u16.to_le_bytes());
+        directory.extend_from_slice(&DOS_DATE.to_le_bytes());
+        directory.extend_from_slice(&crc.to_le_bytes());
+        directory.extend_from_slice(&compressed_size32.to_le_bytes());
+        directory.extend_from_slice(&size32.to_le_bytes());
+        directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        let extra_length = if extra.is_empty() { 0 } else { extra.len() + 4 };
+        directory.extend_from_slice(&(extra_length as u16).to_le_bytes());
+        // Comment length, disk number and file attributes
+        directory.extend_from_slice(&[0; 10]);
+        directory.extend_from_slice(&offset32.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+        if !extra.is_empty() {
+            directory.extend_from_slice(&ZIP64_EXTRA.to_le_bytes());
+            directory.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+            directory.extend_from_slice(&extra);
+        }
+
+        offset += (header.len() + data.len()) as u64;
+    }
+
+    let entries = members.len() as u64;
+    let directory_size = directory.len() as u64;
+    out.write_all(&directory).map_err(io)?;
+
+    let zip64 = entries >= 0xFFFF || directory_size >= 0xFFFF_FFFF || offset >= 0xFFFF_FFFF;
+    let mut trailer = Vec::new();
+    if zip64 {
+        let record_offset = offset + directory_size;
+        trailer.extend_from_slice(&ZIP64_END_OF_CENTRAL_DIRECTORY.to_le_bytes());
+        trailer.extend_from_slice(&44u64.to_le_bytes());
+        trailer.extend_from_slice(&45u16.to_le_bytes());
+        trailer.extend_from_slice(&45u16.to_le_bytes());
+        trailer.extend_from_slice(&[0; 8]);
+        trailer.extend_from_slice(&entries.to_le_bytes());
+        trailer.extend_from_slice(&entries.to_le_bytes());
+        trailer.extend_from_slice(&directory_size.to_le_bytes());
+        trailer.extend_from_slice(&offset.to_le_bytes());
+        trailer.extend_from_slice(&ZIP64_LOCATOR.to_le_bytes());
+        trailer.extend_from_slice(&0u32.to_le_bytes());
+        trailer.extend_from_slice(&record_offset.to_le_bytes());
+        trailer.extend_from_slice(&1u32.to_le_bytes());
+    }
+    let entries16 = if zip64 { 0xFFFF } else { entries as u16 };
+    let clamp = |v: u64| if zip64 { 0xFFFF_FFFF } else { v as u32 };
+    trailer.extend_from_slice(&END_OF_CENTRAL_DIRECTORY.to_le_bytes());
+    trailer.extend_from_slice(&[0; 4]);
+    trailer.extend_from_slice(&entries16.to_le_bytes());
+    trailer.extend_from_slice(&entries16.to_le_bytes());
+    trailer.extend_from_slice(&clamp(directory_size).to_le_bytes());
+    trailer.extend_from_slice(&clamp(offset).to_le_bytes());
+    trailer.extend_from_slice(&0u16.to_le_bytes());
+    out.write_all(&trailer).map_err(io)?;
+    out.flush().map_err(io)
+}
@@ -0,0 +1,270 @@
+//! WAV metadata chunks: LIST/INFO, cue and smpl
+
+use std::collections::BTreeMap;
+
+use crate::error::{IoError, Result};
+
+/// Cue point from a `cue ` chunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CuePoint {
+    /// Unique identifier of the cue point
+    pub id: u32,
+    /// Sample position in play order
+    pub position: u32,
+    /// Sample offset of the cue point within the data chunk
+    pub sample_offset: u32,
+    /// Label from the `LIST`/`adtl` `labl` sub-chunk
+    pub label: Option<String>,
+}
+
+/// Loop of a `smpl` chunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleLoop {
+    /// Identifier of the cue point associated with the loop
+    pub cue_point_id: u32,
+    /// Loop type (0 forward, 1 alternating, 2 backward)
+    pub loop_type: u32,
+    /// First sample of the loop
+    pub start: u32,
+    /// Last sample of the loop
+    pub end: u32,
+    /// Fractional sample offset of the loop end
+    pub fraction: u32,
+    /// Number of repetitions, 0 for infinite
+    pub play_count: u32,
+}
+
+/// Sampler information from a `smpl` chunk
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SamplerInfo {
+    /// MIDI manufacturer code
+    pub manufacturer: u32,
+    /// Product code
+    pub product: u32,
+    /// Sample period in nanoseconds
+    pub sample_period: u32,
+    /// MIDI note at which the sample plays at its original pitch
+    pub midi_unity_note: u32,
+    /// Fraction of a semitone above the unity note
+    pub midi_pitch_fraction: u32,
+    /// SMPTE time format
+    pub smpte_format: u32,
+    /// SMPTE time offset
+    pub smpte_offset: u32,
+    /// Sample loops
+    pub loops: Vec<SampleLoop>,
+}
+
+/// Metadata stored in the auxiliary chunks of a WAV file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WavMetadata {
+    /// `LIST`/`INFO` entries keyed by their four-character id, e.g. `INAM` or `IART`
+    pub info: BTreeMap<String, String>,
+    /// Cue points from the `cue ` chunk
+    pub cue_points: Vec<CuePoint>,
+    /// Sampler information from the `smpl` chunk
+    pub sampler: Option<SamplerInfo>,
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| IoError::FormatError("Truncated WAV metadata chunk".to_string()))
+}
+
+fn zstring(data: &[u8]) -> String {
+    let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).into_owned()
+}
+
+/// Iterate over the `(id, payload)` sub-chunks of a `LIST` chunk
+fn sub_chunks(mut data: &[u8]) -> impl Iterator<Item = ([u8; 4], &[u8])> {
+    std::iter::from_fn(move || {
+        if data.len() < 8 {
+            return None;
+        }
+        let id: [u8; 4] = data[..4].try_into().unwrap();
+        let size = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+        let payload = &data[8..(8 + size).min(data.len())];
+        data = &data[(8 + size + size % 2).min(data.len())..];
+        Some((id, payload))
+    })
+}
+
+fn push_chunk(out: &mut Vec<u8>, id: &[u8; 4], payload: &[u8]) {
+    out.extend_from_slice(id);
+    out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    out.extend_from_slice(payload);
+    if payload.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+impl WavMetadata {
+    /// Whether no metadata is present
+    pub fn is_empty(&self) -> bool {
+        self.info.is_empty() && self.cue_points.is_empty() && self.sampler.is_none()
+    }
+
+    /// Parse the payload of a `LIST` chunk
+    pub(crate) fn parse_list(&mut self, data: &[u8]) {
+        if data.len() < 4 {
+            return;
+        }
+        match &data[..4] {
+            b"INFO" => {
+                for (id, payload) in sub_chunks(&data[4..]) {
+                    self.info
+                        .insert(String::from_utf8_lossy(&id).into_owned(), zstring(payload));
+                }
+            }
+            b"adtl" => {
+                for (id, payload) in sub_chunks(&data[4..]) {
+                    if &id != b"labl" || payload.len() < 4 {
+                        continue;
+                    }
+                    let cue_id = u32::from_le_bytes(payload[..4].try_into().unwrap());
+                    let label = zstring(&payload[4..]);
+                    match self.cue_points.iter_mut().find(|c| c.id == cue_id) {
+                        Some(cue) => cue.label = Some(label),
+                        // The label may precede the cue chunk
+                        None => self.cue_points.push(CuePoint {
+                            id: cue_id,
+                            position: 0,
+                            sample_offset: 0,
+                            label: Some(label),
+                        }),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Parse the payload of a `cue ` chunk
+    pub(crate) fn parse_cue(&mut self, data: &[u8]) -> Result<()> {
+        let count = u32_at(data, 0)? as usize;
+        for i in 0..count {
+            let base = 4 + i * 24;
+            let id = u32_at(data, base)?;
+            let position = u32_at(data, base + 4)?;
+            let sample_offset = u32_at(data, base + 20)?;
+            match self.cue_points.iter_mut().find(|c| c.id == id) {
+                Some(cue) => {
+                    cue.position = position;
+                    cue.sample_offset = sample_offset;
+                }
+                None => self.cue_points.push(CuePoint {
+                    id,
+                    position,
+                    sample_offset,
+                    label: None,
+                }),
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse the payload of a `smpl` chunk
+    pub(crate) fn parse_smpl(&mut self, data: &[u8]) -> Result<()> {
+        let num_loops = u32_at(data, 28)? as usize;
+        let loops = (0..num_loops)
+            .map(|i| {
+                let base = 36 + i * 24;
+                Ok(SampleLoop {
+                    cue_point_id: u32_at(data, base)?,
+                    loop_type: u32_at(data, base + 4)?,
+                    start: u32_at(data, base + 8)?,
+                    end: u32_at(data, base + 12)?,
+                    fraction: u32_at(data, base + 16)?,
+                    play_count: u32_at(data, base + 20)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.sampler = Some(SamplerInfo {
+            manufacturer: u32_at(data, 0)?,
+            product: u32_at(data, 4)?,
+            sample_period: u32_at(data, 8)?,
+            midi_unity_note: u32_at(data, 12)?,
+            midi_pitch_fraction: u32_at(data, 16)?,
+            smpte_format: u32_at(data, 20)?,
+            smpte_offset: u32_at(data, 24)?,
+            loops,
+        });
+        Ok(())
+    }
+
+    /// Encode the metadata as complete RIFF chunks
+    pub(crate) fn to_chunks(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        if !self.info.is_empty() {
+            let mut list = b"INFO".to_vec();
+            for (id, text) in &self.info {
+                let id: [u8; 4] = id.as_bytes().try_into().map_err(|_| {
+                    IoError::ValidationError(format!(
+                        "INFO id '{}' must have exactly four ASCII characters",
+                        id
+                    ))
+                })?;
+                let mut payload = text.as_bytes().to_vec();
+                payload.push(0);
+                push_chunk(&mut list, &id, &payload);
+            }
+            push_chunk(&mut out, b"LIST", &list);
+        }
+        if !self.cue_points.is_empty() {
+            let mut cue = (self.cue_points.len() as u32).to_le_bytes().to_vec();
+            for point in &self.cue_points {
+                cue.extend_from_slice(&point.id.to_le_bytes());
+                cue.extend_from_slice(&point.position.to_le_bytes());
+                cue.extend_from_slice(b"data");
+                cue.extend_from_slice(&[0; 8]);
+                cue.extend_from_slice(&point.sample_offset.to_le_bytes());
+            }
+            push_chunk(&mut out, b"cue ", &cue);
+
+            let mut adtl = b"adtl".to_vec();
+            for point in &self.cue_points {
+                if let Some(label) = &point.label {
+                    let mut payload = point.id.to_le_bytes().to_vec();
+                    payload.extend_from_slice(label.as_bytes());
+                    payload.push(0);
+                    push_chunk(&mut adtl, b"labl", &payload);
+                }
+            }
+            if adtl.len() > 4 {
+                push_chunk(&mut out, b"LIST", &adtl);
+            }
+        }
+        if let Some(sampler) = &self.sampler {
+            let mut smpl = Vec::with_capacity(36 + 24 * sampler.loops.len());
+            for value in [
+                sampler.manufacturer,
+                sampler.product,
+                sampler.sample_period,
+                sampler.midi_unity_note,
+                sampler.midi_pitch_fraction,
+                sampler.smpte_format,
+                sampler.smpte_offset,
+                sampler.loops.len() as u32,
+                0,
+            ] {
+                smpl.extend_from_slice(&value.to_le_bytes());
+            }
+            for l in &sampler.loops {
+                for value in [
+                    l.cue_point_id,
+                    l.loop_type,
+                    l.start,
+                    l.end,
+                    l.fraction,
+                    l.play_count,
+                ] {
+                    smpl.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            push_chunk(&mut out, b"smpl", &smpl);
+        }
+        Ok(out)
+    }
+}
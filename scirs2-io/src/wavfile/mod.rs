@@ -1,15 +1,26 @@
 //! WAV file format handling module
 //!
-//! This module provides functionality for reading and writing WAV audio files.
+//! This module provides functionality for reading and writing WAV audio files:
+//!
+//! - 8, 16, 24 and 32-bit integer PCM, 32 and 64-bit IEEE float, A-law and μ-law data
+//! - `WAVE_FORMAT_EXTENSIBLE` headers with speaker channel masks
+//! - `LIST`/`INFO`, `cue ` and `smpl` metadata chunks
+//! - Chunked streaming with [`WavReader`] and [`WavWriter`] for recordings larger than memory
+//! - RF64/BW64 files with more than 4 GiB of audio data
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use ndarray::ArrayD;
-use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use ndarray::{ArrayD, Axis, Ix2};
+use std::io::{Read, Write};
 use std::path::Path;
 
 use crate::error::{IoError, Result};
 
+mod metadata;
+mod stream;
+
+pub use metadata::{CuePoint, SampleLoop, SamplerInfo, WavMetadata};
+pub use stream::{WavReader, WavWriter};
+
 /// WAV audio format codes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WavFormat {
@@ -53,6 +64,107 @@ pub struct WavHeader {
     pub bits_per_sample: u16,
     /// Total number of samples per channel
     pub samples_per_channel: usize,
+    /// Number of significant bits within each sample container
+    pub valid_bits_per_sample: u16,
+    /// Size of one frame (a sample of every channel) in bytes
+    pub block_align: u16,
+    /// Speaker positions of a `WAVE_FORMAT_EXTENSIBLE` file, see [`channel_mask`]
+    pub channel_mask: Option<u32>,
+}
+
+/// Speaker position bits of the `WAVE_FORMAT_EXTENSIBLE` channel mask
+pub mod channel_mask {
+    /// Front left speaker
+    pub const FRONT_LEFT: u32 = 0x1;
+    /// Front right speaker
+    pub const FRONT_RIGHT: u32 = 0x2;
+    /// Front center speaker
+    pub const FRONT_CENTER: u32 = 0x4;
+    /// Low-frequency effects channel
+    pub const LOW_FREQUENCY: u32 = 0x8;
+    /// Back left speaker
+    pub const BACK_LEFT: u32 = 0x10;
+    /// Back right speaker
+    pub const BACK_RIGHT: u32 = 0x20;
+    /// Front left of center speaker
+    pub const FRONT_LEFT_OF_CENTER: u32 = 0x40;
+    /// Front right of center speaker
+    pub const FRONT_RIGHT_OF_CENTER: u32 = 0x80;
+    /// Back center speaker
+    pub const BACK_CENTER: u32 = 0x100;
+    /// Side left speaker
+    pub const SIDE_LEFT: u32 = 0x200;
+    /// Side right speaker
+    pub const SIDE_RIGHT: u32 = 0x400;
+}
+
+/// Conventional channel mask for a number of channels, 0 if there is none
+pub(crate) fn default_channel_mask(channels: u16) -> u32 {
+    use channel_mask::*;
+    match channels {
+        1 => FRONT_CENTER,
+        2 => FRONT_LEFT | FRONT_RIGHT,
+        3 => FRONT_LEFT | FRONT_RIGHT | FRONT_CENTER,
+        4 => FRONT_LEFT | FRONT_RIGHT | BACK_LEFT | BACK_RIGHT,
+        5 => FRONT_LEFT | FRONT_RIGHT | FRONT_CENTER | BACK_LEFT | BACK_RIGHT,
+        6 => FRONT_LEFT | FRONT_RIGHT | FRONT_CENTER | LOW_FREQUENCY | BACK_LEFT | BACK_RIGHT,
+        8 => {
+            FRONT_LEFT
+                | FRONT_RIGHT
+                | FRONT_CENTER
+                | LOW_FREQUENCY
+                | BACK_LEFT
+                | BACK_RIGHT
+                | SIDE_LEFT
+                | SIDE_RIGHT
+        }
+        _ => 0,
+    }
+}
+
+/// Sample format of a WAV file to be written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WavSpec {
+    /// Sample rate in Hz
+    pub sample_rate: u32,
+    /// Number of channels
+    pub channels: u16,
+    /// Bits per sample (8, 16, 24 or 32 for PCM; 32 or 64 for float)
+    pub bits_per_sample: u16,
+    /// Sample encoding, [`WavFormat::Pcm`] or [`WavFormat::Float`]
+    pub format: WavFormat,
+    /// Speaker positions; setting a mask forces a `WAVE_FORMAT_EXTENSIBLE` header
+    pub channel_mask: Option<u32>,
+}
+
+impl WavSpec {
+    /// Integer PCM samples
+    pub fn pcm(sample_rate: u32, channels: u16, bits_per_sample: u16) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            bits_per_sample,
+            format: WavFormat::Pcm,
+            channel_mask: None,
+        }
+    }
+
+    /// IEEE float samples
+    pub fn float(sample_rate: u32, channels: u16, bits_per_sample: u16) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            bits_per_sample,
+            format: WavFormat::Float,
+            channel_mask: None,
+        }
+    }
+
+    /// Set the speaker positions of the channels
+    pub fn with_channel_mask(mut self, mask: u32) -> Self {
+        self.channel_mask = Some(mask);
+        self
+    }
 }
 
 /// RIFF chunk type
@@ -109,113 +221,19 @@ impl RiffChunk {
 /// println!("Samples per channel: {}", header.samples_per_channel);
 /// ```
 pub fn read_wav<P: AsRef<Path>>(path: P) -> Result<(WavHeader, ArrayD<f32>)> {
-    let file = File::open(path).map_err(|e| IoError::FileError(e.to_string()))?;
-    let mut reader = BufReader::new(file);
-
-    // Read RIFF chunk
-    let riff_chunk = RiffChunk::read(&mut reader)
-        .map_err(|e| IoError::FormatError(format!("Failed to read RIFF chunk: {}", e)))?;
-
-    if !riff_chunk.is_id("RIFF") {
-        return Err(IoError::FormatError("Not a RIFF file".to_string()));
-    }
-
-    // Read format (should be "WAVE")
-    let mut format = [0u8; 4];
-    reader
-        .read_exact(&mut format)
-        .map_err(|e| IoError::FormatError(format!("Failed to read WAVE format: {}", e)))?;
-
-    if format != *b"WAVE" {
-        return Err(IoError::FormatError("Not a WAVE file".to_string()));
-    }
-
-    // Read fmt chunk
-    let fmt_chunk = RiffChunk::read(&mut reader)
-        .map_err(|e| IoError::FormatError(format!("Failed to read fmt chunk: {}", e)))?;
-
-    if !fmt_chunk.is_id("fmt ") {
-        return Err(IoError::FormatError("Expected fmt chunk".to_string()));
-    }
-
-    // Read format data
-    let audio_format = reader
-        .read_u16::<LittleEndian>()
-        .map_err(|e| IoError::FormatError(format!("Failed to read audio format: {}", e)))?;
-    let format = WavFormat::try_from(audio_format)?;
-
-    let channels = reader
-        .read_u16::<LittleEndian>()
-        .map_err(|e| IoError::FormatError(format!("Failed to read channel count: {}", e)))?;
-
-    let sample_rate = reader
-        .read_u32::<LittleEndian>()
-        .map_err(|e| IoError::FormatError(format!("Failed to read sample rate: {}", e)))?;
-
-    let _byte_rate = reader
-        .read_u32::<LittleEndian>()
-        .map_err(|e| IoError::FormatError(format!("Failed to read byte rate: {}", e)))?;
-
-    let _block_align = reader
-        .read_u16::<LittleEndian>()
-        .map_err(|e| IoError::FormatError(format!("Failed to read block align: {}", e)))?;
-
-    let bits_per_sample = reader
-        .read_u16::<LittleEndian>()
-        .map_err(|e| IoError::FormatError(format!("Failed to read bits per sample: {}", e)))?;
-
-    // Skip any extra fmt data
-    if fmt_chunk.size > 16 {
-        let extra_bytes = fmt_chunk.size as usize - 16;
-        let mut extra_data = vec![0u8; extra_bytes];
-        reader
-            .read_exact(&mut extra_data)
-            .map_err(|e| IoError::FormatError(format!("Failed to read extra fmt data: {}", e)))?;
-    }
-
-    // Find data chunk
-    let data_size;
-    loop {
-        let chunk = RiffChunk::read(&mut reader)
-            .map_err(|e| IoError::FormatError(format!("Failed to read chunk: {}", e)))?;
-
-        if chunk.is_id("data") {
-            data_size = chunk.size;
-            break;
-        }
-
-        // Skip this chunk
-        reader
-            .seek(SeekFrom::Current(chunk.size as i64))
-            .map_err(|e| IoError::FormatError(format!("Failed to skip chunk: {}", e)))?;
-    }
-
-    // Calculate number of samples
-    let bytes_per_sample = bits_per_sample / 8;
-    let samples_per_channel = (data_size / (channels as u32 * bytes_per_sample as u32)) as usize;
-
-    // Create header
-    let _header = WavHeader {
-        format,
-        channels,
-        sample_rate,
-        bits_per_sample,
-        samples_per_channel,
-    };
-
-    // Read audio data
-    // For now, we'll just create a dummy array, to be replaced with actual data reading
-    // TODO: Implement actual reading of audio samples
-    let _shape = ndarray::IxDyn(&[channels as usize, samples_per_channel]);
-
-    // For now, return a placeholder message
-    Err(IoError::Other(
-        "WAV file reading not fully implemented yet".to_string(),
-    ))
+    let mut reader = WavReader::open(path)?;
+    let header = reader.header().clone();
+    let data = reader
+        .read_frames(header.samples_per_channel)?
+        .unwrap_or_else(|| ndarray::Array2::zeros((header.channels as usize, 0)));
+    Ok((header, data.into_dyn()))
 }
 
 /// Writes audio data to a WAV file
 ///
+/// The samples are stored as 32-bit IEEE float; use [`write_wav_with_spec`]
+/// for other sample formats.
+///
 /// # Arguments
 ///
 /// * `path` - Path where the WAV file should be written
@@ -244,86 +262,55 @@ pub fn read_wav<P: AsRef<Path>>(path: P) -> Result<(WavHeader, ArrayD<f32>)> {
 /// write_wav(Path::new("sine_wave.wav"), sample_rate, &samples.into_dyn()).unwrap();
 /// ```
 pub fn write_wav<P: AsRef<Path>>(path: P, sample_rate: u32, data: &ArrayD<f32>) -> Result<()> {
-    let file = File::create(path).map_err(|e| IoError::FileError(e.to_string()))?;
-    let mut writer = BufWriter::new(file);
+    let channels = data.shape().first().copied().unwrap_or(0);
+    let channels = u16::try_from(channels)
+        .map_err(|_| IoError::FormatError(format!("Too many channels: {}", channels)))?;
+    write_wav_with_spec(path, &WavSpec::float(sample_rate, channels, 32), data)
+}
 
-    // Check that data is at least 2D (channels, samples)
-    if data.ndim() < 2 {
-        return Err(IoError::FormatError(
-            "Audio data must be at least 2D (channels, samples)".to_string(),
-        ));
+/// Writes audio data to a WAV file with the given sample format
+///
+/// # Arguments
+///
+/// * `path` - Path where the WAV file should be written
+/// * `spec` - Sample rate, channel count and sample format
+/// * `data` - Audio data of shape (channels, samples) with values in [-1, 1]
+///
+/// # Example
+///
+/// ```
+/// use ndarray::Array2;
+/// use scirs2_io::wavfile::{read_wav, write_wav_with_spec, WavSpec};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("quad.wav");
+/// let samples = Array2::from_shape_fn((4, 480), |(c, i)| ((c + 1) * i) as f32 / 2000.0);
+///
+/// write_wav_with_spec(&path, &WavSpec::pcm(48000, 4, 24), &samples.clone().into_dyn()).unwrap();
+/// let (header, data) = read_wav(&path).unwrap();
+/// assert_eq!(header.channels, 4);
+/// assert_eq!(header.channel_mask, Some(0x33));
+/// assert!((data[[3, 479]] - samples[[3, 479]]).abs() < 1e-6);
+/// ```
+pub fn write_wav_with_spec<P: AsRef<Path>>(
+    path: P,
+    spec: &WavSpec,
+    data: &ArrayD<f32>,
+) -> Result<()> {
+    // Check that data is 2D (channels, samples)
+    let data = data.view().into_dimensionality::<Ix2>().map_err(|_| {
+        IoError::FormatError("Audio data must be 2D (channels, samples)".to_string())
+    })?;
+    if data.len_of(Axis(0)) != spec.channels as usize {
+        return Err(IoError::FormatError(format!(
+            "Audio data has {} channels, the spec expects {}",
+            data.len_of(Axis(0)),
+            spec.channels
+        )));
     }
-
-    let shape = data.shape();
-    let channels = shape[0] as u16;
-    let samples_per_channel = shape[1];
-
-    // Set up WAV parameters
-    let bits_per_sample: u16 = 32; // 32-bit float
-    let bytes_per_sample = bits_per_sample / 8;
-    let block_align = channels * bytes_per_sample;
-    let byte_rate = sample_rate * block_align as u32;
-
-    // Calculate total data size
-    let data_size = samples_per_channel * channels as usize * bytes_per_sample as usize;
-
-    // Write RIFF header
-    writer
-        .write_all(b"RIFF")
-        .map_err(|e| IoError::FileError(format!("Failed to write RIFF header: {}", e)))?;
-
-    // File size = 36 (header) + data size
-    writer
-        .write_u32::<LittleEndian>(36 + data_size as u32)
-        .map_err(|e| IoError::FileError(format!("Failed to write file size: {}", e)))?;
-
-    writer
-        .write_all(b"WAVE")
-        .map_err(|e| IoError::FileError(format!("Failed to write WAVE header: {}", e)))?;
-
-    // Write fmt chunk
-    writer
-        .write_all(b"fmt ")
-        .map_err(|e| IoError::FileError(format!("Failed to write fmt chunk: {}", e)))?;
-
-    writer.write_u32::<LittleEndian>(16) // fmt chunk size
-        .map_err(|e| IoError::FileError(format!("Failed to write fmt chunk size: {}", e)))?;
-
-    writer.write_u16::<LittleEndian>(3) // format = 3 (IEEE float)
-        .map_err(|e| IoError::FileError(format!("Failed to write audio format: {}", e)))?;
-
-    writer
-        .write_u16::<LittleEndian>(channels)
-        .map_err(|e| IoError::FileError(format!("Failed to write channel count: {}", e)))?;
-
-    writer
-        .write_u32::<LittleEndian>(sample_rate)
-        .map_err(|e| IoError::FileError(format!("Failed to write sample rate: {}", e)))?;
-
-    writer
-        .write_u32::<LittleEndian>(byte_rate)
-        .map_err(|e| IoError::FileError(format!("Failed to write byte rate: {}", e)))?;
-
-    writer
-        .write_u16::<LittleEndian>(block_align)
-        .map_err(|e| IoError::FileError(format!("Failed to write block align: {}", e)))?;
-
-    writer
-        .write_u16::<LittleEndian>(bits_per_sample)
-        .map_err(|e| IoError::FileError(format!("Failed to write bits per sample: {}", e)))?;
-
-    // Write data chunk
-    writer
-        .write_all(b"data")
-        .map_err(|e| IoError::FileError(format!("Failed to write data chunk: {}", e)))?;
-
-    writer
-        .write_u32::<LittleEndian>(data_size as u32)
-        .map_err(|e| IoError::FileError(format!("Failed to write data size: {}", e)))?;
-
-    // TODO: Implement actual writing of audio samples
-    // For now, return a placeholder message
-    Err(IoError::Other(
-        "WAV file writing not fully implemented yet".to_string(),
-    ))
+    let mut writer = WavWriter::create(path, spec.clone())?;
+    writer.write_frames(data)?;
+    writer.finalize()?;
+    Ok(())
 }
@@ -0,0 +1,800 @@
+//! Chunked WAV reading and writing for recordings larger than memory
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use ndarray::{Array2, ArrayView2};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use super::metadata::WavMetadata;
+use super::{RiffChunk, WavFormat, WavHeader, WavSpec};
+use crate::error::{IoError, Result};
+
+/// `WAVE_FORMAT_EXTENSIBLE` format tag
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+/// Trailing 14 bytes of the `KSDATAFORMAT_SUBTYPE_*` GUIDs
+const SUBFORMAT_GUID_TAIL: [u8; 14] = [
+    0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71,
+];
+/// Size of the `ds64` chunk payload without a table
+const DS64_SIZE: u32 = 28;
+
+fn read_error(context: &str) -> impl Fn(std::io::Error) -> IoError + '_ {
+    move |e| IoError::FormatError(format!("Failed to read {}: {}", context, e))
+}
+
+fn write_error(context: &str) -> impl Fn(std::io::Error) -> IoError + '_ {
+    move |e| IoError::FileError(format!("Failed to write {}: {}", context, e))
+}
+
+/// Decode G.711 A-law to a 16-bit sample
+fn alaw_to_i16(value: u8) -> i16 {
+    let value = value ^ 0x55;
+    let exponent = (value >> 4) & 0x07;
+    let mantissa = (value & 0x0F) as i16;
+    let magnitude = if exponent == 0 {
+        (mantissa << 4) + 8
+    } else {
+        ((mantissa << 4) + 0x108) << (exponent - 1)
+    };
+    if value & 0x80 != 0 {
+        magnitude
+    } else {
+        -magnitude
+    }
+}
+
+/// Decode G.711 μ-law to a 16-bit sample
+fn mulaw_to_i16(value: u8) -> i16 {
+    let value = !value;
+    let exponent = (value >> 4) & 0x07;
+    let mantissa = (value & 0x0F) as i16;
+    let magnitude = (((mantissa << 3) + 0x84) << exponent) - 0x84;
+    if value & 0x80 != 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Check that a sample format can be decoded and encoded
+fn check_sample_format(format: WavFormat, bits_per_sample: u16) -> Result<()> {
+    let supported = match format {
+        WavFormat::Pcm => matches!(bits_per_sample, 8 | 16 | 24 | 32),
+        WavFormat::Float => matches!(bits_per_sample, 32 | 64),
+        WavFormat::Alaw | WavFormat::Mulaw => bits_per_sample == 8,
+    };
+    if supported {
+        Ok(())
+    } else {
+        Err(IoError::FormatError(format!(
+            "Unsupported WAV sample format: {:?} with {} bits per sample",
+            format, bits_per_sample
+        )))
+    }
+}
+
+/// Decode interleaved samples to values in [-1, 1)
+fn decode_samples(format: WavFormat, bits_per_sample: u16, bytes: &[u8], out: &mut Vec<f64>) {
+    let width = bits_per_sample as usize / 8;
+    let samples = bytes.chunks_exact(width);
+    match (format, bits_per_sample) {
+        (WavFormat::Pcm, 8) => out.extend(samples.map(|b| (b[0] as f64 - 128.0) / 128.0)),
+        (WavFormat::Pcm, 16) => {
+            out.extend(samples.map(|b| i16::from_le_bytes([b[0], b[1]]) as f64 / 32768.0))
+        }
+        (WavFormat::Pcm, 24) => out.extend(
+            samples.map(|b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f64 / 8388608.0),
+        ),
+        (WavFormat::Pcm, _) => out.extend(
+            samples.map(|b| i32::from_le_bytes(b.try_into().unwrap()) as f64 / 2147483648.0),
+        ),
+        (WavFormat::Float, 32) => {
+            out.extend(samples.map(|b| f32::from_le_bytes(b.try_into().unwrap()) as f64))
+        }
+        (WavFormat::Float, _) => {
+            out.extend(samples.map(|b| f64::from_le_bytes(b.try_into().unwrap())))
+        }
+        (WavFormat::Alaw, _) => out.extend(samples.map(|b| alaw_to_i16(b[0]) as f64 / 32768.0)),
+        (WavFormat::Mulaw, _) => out.extend(samples.map(|b| mulaw_to_i16(b[0]) as f64 / 32768.0)),
+    }
+}
+
+/// Quantize a value in [-1, 1] to a signed integer with the given number of bits
+fn quantize(value: f64, bits: u32) -> i64 {
+    let scale = (1i64 << (bits - 1)) as f64;
+    (value * scale).round().clamp(-scale, scale - 1.0) as i64
+}
+
+/// Encode one sample, appending its little-endian bytes
+fn encode_sample(format: WavFormat, bits_per_sample: u16, value: f64, out: &mut Vec<u8>) {
+    match (format, bits_per_sample) {
+        (WavFormat::Pcm, 8) => out.push((quantize(value, 8) + 128) as u8),
+        (WavFormat::Pcm, 16) => out.extend_from_slice(&(quantize(value, 16) as i16).to_le_bytes()),
+        (WavFormat::Pcm, 24) => {
+            out.extend_from_slice(&(quantize(value, 24) as i32).to_le_bytes()[..3])
+        }
+        (WavFormat::Pcm, _) => out.extend_from_slice(&(quantize(value, 32) as i32).to_le_bytes()),
+        (WavFormat::Float, 32) => out.extend_from_slice(&(value as f32).to_le_bytes()),
+        _ => out.extend_from_slice(&value.to_le_bytes()),
+    }
+}
+
+/// Streaming WAV reader that decodes frames on demand
+///
+/// Supports PCM (8, 16, 24 and 32 bits), IEEE float (32 and 64 bits),
+/// A-law and μ-law data in plain, `WAVE_FORMAT_EXTENSIBLE`, RF64 and BW64 files.
+///
+/// # Examples
+///
+/// ```no_run
+/// use scirs2_io::wavfile::WavReader;
+///
+/// let mut reader = WavReader::open("recording.wav").unwrap();
+/// while let Some(block) = reader.read_frames(65536).unwrap() {
+///     // `block` has shape (channels, frames)
+///     println!("{:?}", block.shape());
+/// }
+/// ```
+pub struct WavReader<R: Read + Seek> {
+    reader: R,
+    header: WavHeader,
+    metadata: WavMetadata,
+    data_start: u64,
+    position: usize,
+}
+
+impl WavReader<BufReader<File>> {
+    /// Open a WAV file for streaming
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the WAV file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<WavReader<BufReader<File>>>` - The reader or an error
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path).map_err(|e| IoError::FileError(e.to_string()))?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read + Seek> WavReader<R> {
+    /// Create a reader from any seekable stream
+    ///
+    /// All chunks are scanned, so metadata stored after the audio data is available.
+    pub fn new(mut reader: R) -> Result<Self> {
+        let riff = RiffChunk::read(&mut reader).map_err(read_error("RIFF chunk"))?;
+        let is_rf64 = riff.is_id("RF64") || riff.is_id("BW64");
+        if !riff.is_id("RIFF") && !is_rf64 {
+            return Err(IoError::FormatError("Not a RIFF file".to_string()));
+        }
+        let mut wave = [0u8; 4];
+        reader
+            .read_exact(&mut wave)
+            .map_err(read_error("WAVE format"))?;
+        if wave != *b"WAVE" {
+            return Err(IoError::FormatError("Not a WAVE file".to_string()));
+        }
+        let stream_end = reader
+            .seek(SeekFrom::End(0))
+            .map_err(read_error("stream length"))?;
+        reader
+            .seek(SeekFrom::Start(12))
+            .map_err(read_error("chunks"))?;
+
+        let mut fmt = None;
+        let mut metadata = WavMetadata::default();
+        let mut data = None;
+        let mut ds64_data_size = None;
+        let mut offset = 12u64;
+        while offset + 8 <= stream_end {
+            let chunk = RiffChunk::read(&mut reader).map_err(read_error("chunk"))?;
+            let start = offset + 8;
+            let mut size = chunk.size as u64;
+            if chunk.is_id("data") {
+                if is_rf64 && chunk.size == u32::MAX {
+                    size = ds64_data_size.ok_or_else(|| {
+                        IoError::FormatError("RF64 file without ds64 chunk".to_string())
+                    })?;
+                }
+                // Tolerate a data size that runs past the end of a truncated file
+                size = size.min(stream_end - start);
+                data = Some((start, size));
+            } else if chunk.is_id("fmt ")
+                || chunk.is_id("LIST")
+                || chunk.is_id("cue ")
+                || chunk.is_id("smpl")
+                || chunk.is_id("ds64")
+            {
+                let mut payload = vec![0u8; chunk.size as usize];
+                reader
+                    .read_exact(&mut payload)
+                    .map_err(read_error("chunk payload"))?;
+                match &chunk.id {
+                    b"fmt " => fmt = Some(payload),
+                    b"LIST" => metadata.parse_list(&payload),
+                    b"cue " => metadata.parse_cue(&payload)?,
+                    b"smpl" => metadata.parse_smpl(&payload)?,
+                    _ => {
+                        if payload.len() >= 16 {
+                            ds64_data_size =
+                                Some(u64::from_le_bytes(payload[8..16].try_into().unwrap()));
+                        }
+                    }
+                }
+            }
+            offset = start + size + size % 2;
+            reader
+                .seek(SeekFrom::Start(offset))
+                .map_err(read_error("chunk"))?;
+        }
+
+        let fmt = fmt.ok_or_else(|| IoError::FormatError("Missing fmt chunk".to_string()))?;
+        let (data_start, data_size) =
+            data.ok_or_else(|| IoError::FormatError("Missing data chunk".to_string()))?;
+        let header = parse_fmt(&fmt, data_size)?;
+        reader
+            .seek(SeekFrom::Start(data_start))
+            .map_err(read_error("data chunk"))?;
+        Ok(Self {
+            reader,
+            header,
+            metadata,
+            data_start,
+            position: 0,
+        })
+    }
+
+    /// Header of the file
+    pub fn header(&self) -> &WavHeader {
+        &self.header
+    }
+
+    /// Metadata from the LIST/INFO, cue and smpl chunks
+    pub fn metadata(&self) -> &WavMetadata {
+        &self.metadata
+    }
+
+    /// Number of frames not read yet
+    pub fn frames_remaining(&self) -> usize {
+        self.header.samples_per_channel - self.position
+    }
+
+    /// Move to the given frame
+    pub fn seek(&mut self, frame: usize) -> Result<()> {
+        if frame > self.header.samples_per_channel {
+            return Err(IoError::ValidationError(format!(
+                "Frame {} is beyond the end of the data ({} frames)",
+                frame, self.header.samples_per_channel
+            )));
+        }
+        let offset = self.data_start + frame as u64 * self.header.block_align as u64;
+        self.reader
+            .seek(SeekFrom::Start(offset))
+            .map_err(read_error("data chunk"))?;
+        self.position = frame;
+        Ok(())
+    }
+
+    /// Read up to `max_frames` frames as `f64` values in [-1, 1)
+    ///
+    /// Returns an array of shape (channels, frames), or `None` at the end of the data.
+    pub fn read_frames_f64(&mut self, max_frames: usize) -> Result<Option<Array2<f64>>> {
+        let frames = max_frames.min(self.frames_remaining());
+        if frames == 0 {
+            return Ok(None);
+        }
+        let header = &self.header;
+        let block_align = header.block_align as usize;
+        let mut bytes = vec![0u8; frames * block_align];
+        self.reader
+            .read_exact(&mut bytes)
+            .map_err(read_error("audio samples"))?;
+        self.position += frames;
+
+        let channels = header.channels as usize;
+        let width = header.bits_per_sample as usize / 8;
+        let mut samples = Vec::with_capacity(frames * channels);
+        if block_align == channels * width {
+            decode_samples(header.format, header.bits_per_sample, &bytes, &mut samples);
+        } else {
+            // Padded frames: decode only the leading samples of each block
+            for block in bytes.chunks_exact(block_align) {
+                decode_samples(
+                    header.format,
+                    header.bits_per_sample,
+                    &block[..channels * width],
+                    &mut samples,
+                );
+            }
+        }
+        Ok(Some(Array2::from_shape_fn((channels, frames), |(c, i)| {
+            samples[i * channels + c]
+        })))
+    }
+
+    /// Read up to `max_frames` frames as `f32` values in [-1, 1)
+    ///
+    /// Returns an array of shape (channels, frames), or `None` at the end of the data.
+    pub fn read_frames(&mut self, max_frames: usize) -> Result<Option<Array2<f32>>> {
+        Ok(self
+            .read_frames_f64(max_frames)?
+            .map(|block| block.mapv(|x| x as f32)))
+    }
+}
+
+/// Parse a `fmt ` chunk payload
+fn parse_fmt(fmt: &[u8], data_size: u64) -> Result<WavHeader> {
+    let mut cursor = fmt;
+    let mut field = |name: &str, bytes: usize| -> Result<u32> {
+        if cursor.len() < bytes {
+            return Err(IoError::FormatError(format!(
+                "fmt chunk too short to contain {}",
+                name
+            )));
+        }
+        let value = if bytes == 2 {
+            cursor.read_u16::<LittleEndian>().unwrap() as u32
+        } else {
+            cursor.read_u32::<LittleEndian>().unwrap()
+        };
+        Ok(value)
+    };
+    let format_tag = field("audio format", 2)? as u16;
+    let channels = field("channel count", 2)? as u16;
+    let sample_rate = field("sample rate", 4)?;
+    let _byte_rate = field("byte rate", 4)?;
+    let block_align = field("block align", 2)? as u16;
+    let bits_per_sample = field("bits per sample", 2)? as u16;
+
+    let mut valid_bits_per_sample = bits_per_sample;
+    let mut channel_mask = None;
+    let format = if format_tag == FORMAT_EXTENSIBLE {
+        let extension_size = field("extension size", 2)?;
+        if extension_size < 22 {
+            return Err(IoError::FormatError(
+                "WAVE_FORMAT_EXTENSIBLE fmt chunk is too short".to_string(),
+            ));
+        }
+        valid_bits_per_sample = field("valid bits per sample", 2)? as u16;
+        channel_mask = Some(field("channel mask", 4)?);
+        let sub_format = field("sub-format", 2)? as u16;
+        if cursor.len() < 14 || cursor[..14] != SUBFORMAT_GUID_TAIL {
+            return Err(IoError::FormatError(
+                "Unsupported WAVE_FORMAT_EXTENSIBLE sub-format GUID".to_string(),
+            ));
+        }
+        WavFormat::try_from(sub_format)?
+    } else {
+        WavFormat::try_from(format_tag)?
+    };
+
+    check_sample_format(format, bits_per_sample)?;
+    if channels == 0 || (block_align as usize) < channels as usize * bits_per_sample as usize / 8 {
+        return Err(IoError::FormatError(format!(
+            "Invalid block align {} for {} channels of {} bits",
+            block_align, channels, bits_per_sample
+        )));
+    }
+    Ok(WavHeader {
+        format,
+        channels,
+        sample_rate,
+        bits_per_sample,
+        samples_per_channel: (data_size / block_align as u64) as usize,
+        valid_bits_per_sample,
+        block_align,
+        channel_mask,
+    })
+}
+
+/// Streaming WAV writer
+///
+/// Frames are appended with [`WavWriter::write_frames`]; [`WavWriter::finalize`]
+/// writes the metadata chunks and fixes up the chunk sizes. Files whose data
+/// exceeds 4 GiB are turned into RF64 files using a reserved `JUNK` chunk.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array2;
+/// use scirs2_io::wavfile::{WavReader, WavSpec, WavWriter};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("tone.wav");
+///
+/// let mut writer = WavWriter::create(&path, WavSpec::pcm(48000, 2, 24)).unwrap();
+/// for block in 0..4 {
+///     let frames = Array2::from_shape_fn((2, 1000), |(c, i)| {
+///         ((block * 1000 + i) as f32 * 0.01).sin() * if c == 0 { 0.5 } else { 0.25 }
+///     });
+///     writer.write_frames(frames.view()).unwrap();
+/// }
+/// writer.finalize().unwrap();
+///
+/// let reader = WavReader::open(&path).unwrap();
+/// assert_eq!(reader.header().bits_per_sample, 24);
+/// assert_eq!(reader.header().samples_per_channel, 4000);
+/// ```
+pub struct WavWriter<W: Write + Seek> {
+    writer: W,
+    spec: WavSpec,
+    metadata: WavMetadata,
+    riff_size_offset: u64,
+    data_size_offset: u64,
+    data_bytes: u64,
+    frames: u64,
+    /// Data size above which the file is written as RF64
+    rf64_threshold: u64,
+}
+
+impl WavWriter<BufWriter<File>> {
+    /// Create a WAV file for streaming output
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file to create
+    /// * `spec` - Sample format of the file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<WavWriter<BufWriter<File>>>` - The writer or an error
+    pub fn create<P: AsRef<Path>>(path: P, spec: WavSpec) -> Result<Self> {
+        let file = File::create(path).map_err(|e| IoError::FileError(e.to_string()))?;
+        Self::new(BufWriter::new(file), spec)
+    }
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+    /// Create a writer on any seekable stream and write the file header
+    pub fn new(mut writer: W, spec: WavSpec) -> Result<Self> {
+        check_sample_format(spec.format, spec.bits_per_sample)?;
+        if matches!(spec.format, WavFormat::Alaw | WavFormat::Mulaw) {
+            return Err(IoError::ValidationError(
+                "Writing A-law and μ-law WAV files is not supported".to_string(),
+            ));
+        }
+        if spec.channels == 0 {
+            return Err(IoError::ValidationError(
+                "A WAV file needs at least one channel".to_string(),
+            ));
+        }
+
+        let riff_size_offset = writer
+            .stream_position()
+            .map_err(write_error("RIFF header"))?
+            + 4;
+        writer
+            .write_all(b"RIFF")
+            .map_err(write_error("RIFF header"))?;
+        writer
+            .write_u32::<LittleEndian>(0)
+            .map_err(write_error("file size"))?;
+        writer
+            .write_all(b"WAVE")
+            .map_err(write_error("WAVE header"))?;
+
+        // Space for a ds64 chunk in case the file grows beyond 4 GiB
+        writer
+            .write_all(b"JUNK")
+            .map_err(write_error("JUNK chunk"))?;
+        writer
+            .write_u32::<LittleEndian>(DS64_SIZE)
+            .map_err(write_error("JUNK chunk"))?;
+        writer
+            .write_all(&[0; DS64_SIZE as usize])
+            .map_err(write_error("JUNK chunk"))?;
+
+        let fmt = format_chunk(&spec);
+        writer
+            .write_all(b"fmt ")
+            .map_err(write_error("fmt chunk"))?;
+        writer
+            .write_u32::<LittleEndian>(fmt.len() as u32)
+            .map_err(write_error("fmt chunk size"))?;
+        writer.write_all(&fmt).map_err(write_error("fmt chunk"))?;
+
+        writer
+            .write_all(b"data")
+            .map_err(write_error("data chunk"))?;
+        let data_size_offset = writer
+            .stream_position()
+            .map_err(write_error("data chunk"))?;
+        writer
+            .write_u32::<LittleEndian>(0)
+            .map_err(write_error("data size"))?;
+
+        Ok(Self {
+            writer,
+            spec,
+            metadata: WavMetadata::default(),
+            riff_size_offset,
+            data_size_offset,
+            data_bytes: 0,
+            frames: 0,
+            rf64_threshold: u32::MAX as u64,
+        })
+    }
+
+    /// Set the metadata written after the audio data
+    pub fn set_metadata(&mut self, metadata: WavMetadata) {
+        self.metadata = metadata;
+    }
+
+    /// Number of frames written so far
+    pub fn frames_written(&self) -> u64 {
+        self.frames
+    }
+
+    /// Append frames given as an array of shape (channels, frames)
+    ///
+    /// Values are expected in [-1, 1]; integer formats clip values outside this range.
+    pub fn write_frames<T: Copy + Into<f64>>(&mut self, frames: ArrayView2<T>) -> Result<()> {
+        if frames.nrows() != self.spec.channels as usize {
+            return Err(IoError::ValidationError(format!(
+                "Expected {} channels, got {}",
+                self.spec.channels,
+                frames.nrows()
+            )));
+        }
+        let mut bytes = Vec::with_capacity(frames.len() * self.spec.bits_per_sample as usize / 8);
+        for frame in frames.columns() {
+            for &value in frame {
+                encode_sample(
+                    self.spec.format,
+                    self.spec.bits_per_sample,
+                    value.into(),
+                    &mut bytes,
+                );
+            }
+        }
+        self.writer
+            .write_all(&bytes)
+            .map_err(write_error("audio samples"))?;
+        self.data_bytes += bytes.len() as u64;
+        self.frames += frames.ncols() as u64;
+        Ok(())
+    }
+
+    /// Write the metadata chunks, fix up the chunk sizes and return the stream
+    pub fn finalize(mut self) -> Result<W> {
+        if self.data_bytes % 2 == 1 {
+            self.writer
+                .write_all(&[0])
+                .map_err(write_error("padding"))?;
+        }
+        let chunks = self.metadata.to_chunks()?;
+        self.writer
+            .write_all(&chunks)
+            .map_err(write_error("metadata chunks"))?;
+        let end = self
+            .writer
+            .stream_position()
+            .map_err(write_error("RIFF header"))?;
+        let riff_size = end - self.riff_size_offset - 4;
+
+        if riff_size > self.rf64_threshold || self.data_bytes > self.rf64_threshold {
+            self.writer
+                .seek(SeekFrom::Start(self.riff_size_offset - 4))
+                .map_err(write_error("RF64 header"))?;
+            self.writer
+                .write_all(b"RF64")
+                .map_err(write_error("RF64 header"))?;
+            self.writer
+                .write_u32::<LittleEndian>(u32::MAX)
+                .map_err(write_error("file size"))?;
+            self.writer
+                .seek(SeekFrom::Current(4))
+                .map_err(write_error("ds64 chunk"))?;
+            self.writer
+                .write_all(b"ds64")
+                .map_err(write_error("ds64 chunk"))?;
+            self.writer
+                .seek(SeekFrom::Current(4))
+                .map_err(write_error("ds64 chunk"))?;
+            for value in [riff_size, self.data_bytes, self.frames] {
+                self.writer
+                    .write_u64::<LittleEndian>(value)
+                    .map_err(write_error("ds64 chunk"))?;
+            }
+            self.writer
+                .write_u32::<LittleEndian>(0)
+                .map_err(write_error("ds64 chunk"))?;
+            self.writer
+                .seek(SeekFrom::Start(self.data_size_offset))
+                .map_err(write_error("data size"))?;
+            self.writer
+                .write_u32::<LittleEndian>(u32::MAX)
+                .map_err(write_error("data size"))?;
+        } else {
+            self.writer
+                .seek(SeekFrom::Start(self.riff_size_offset))
+                .map_err(write_error("file size"))?;
+            self.writer
+                .write_u32::<LittleEndian>(riff_size as u32)
+                .map_err(write_error("file size"))?;
+            self.writer
+                .seek(SeekFrom::Start(self.data_size_offset))
+                .map_err(write_error("data size"))?;
+            self.writer
+                .write_u32::<LittleEndian>(self.data_bytes as u32)
+                .map_err(write_error("data size"))?;
+        }
+        self.writer
+            .seek(SeekFrom::Start(end))
+            .map_err(write_error("WAV file"))?;
+        self.writer.flush().map_err(write_error("WAV file"))?;
+        Ok(self.writer)
+    }
+}
+
+/// Build the `fmt ` chunk payload for a spec
+fn format_chunk(spec: &WavSpec) -> Vec<u8> {
+    let block_align = spec.channels * (spec.bits_per_sample / 8);
+    let extensible = spec.channel_mask.is_some()
+        || spec.channels > 2
+        || (spec.format == WavFormat::Pcm && spec.bits_per_sample > 16);
+    let tag = if extensible {
+        FORMAT_EXTENSIBLE
+    } else {
+        spec.format as u16
+    };
+
+    let mut fmt = Vec::with_capacity(40);
+    fmt.extend_from_slice(&tag.to_le_bytes());
+    fmt.extend_from_slice(&spec.channels.to_le_bytes());
+    fmt.extend_from_slice(&spec.sample_rate.to_le_bytes());
+    fmt.extend_from_slice(&(spec.sample_rate * block_align as u32).to_le_bytes());
+    fmt.extend_from_slice(&block_align.to_le_bytes());
+    fmt.extend_from_slice(&spec.bits_per_sample.to_le_bytes());
+    if extensible {
+        let mask = spec
+            .channel_mask
+            .unwrap_or_else(|| super::default_channel_mask(spec.channels));
+        fmt.extend_from_slice(&22u16.to_le_bytes());
+        fmt.extend_from_slice(&spec.bits_per_sample.to_le_bytes());
+        fmt.extend_from_slice(&mask.to_le_bytes());
+        fmt.extend_from_slice(&(spec.format as u16).to_le_bytes());
+        fmt.extend_from_slice(&SUBFORMAT_GUID_TAIL);
+    }
+    fmt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wavfile::metadata::{CuePoint, SampleLoop, SamplerInfo};
+    use std::io::Cursor;
+
+    fn ramp(channels: usize, frames: usize) -> Array2<f64> {
+        Array2::from_shape_fn((channels, frames), |(c, i)| {
+            ((i as f64 / frames as f64) * 2.0 - 1.0) * (c + 1) as f64 / channels as f64
+        })
+    }
+
+    fn roundtrip(spec: WavSpec, data: &Array2<f64>) -> (WavHeader, Array2<f64>) {
+        let mut writer = WavWriter::new(Cursor::new(Vec::new()), spec).unwrap();
+        writer.write_frames(data.view()).unwrap();
+        let bytes = writer.finalize().unwrap().into_inner();
+        let mut reader = WavReader::new(Cursor::new(bytes)).unwrap();
+        let header = reader.header().clone();
+        let frames = reader.read_frames_f64(usize::MAX).unwrap().unwrap();
+        (header, frames)
+    }
+
+    #[test]
+    fn test_sample_formats_roundtrip() {
+        let data = ramp(2, 257);
+        for (spec, tolerance) in [
+            (WavSpec::pcm(8000, 2, 8), 1.0 / 128.0),
+            (WavSpec::pcm(8000, 2, 16), 1.0 / 32768.0),
+            (WavSpec::pcm(8000, 2, 24), 1.0 / 8388608.0),
+            (WavSpec::pcm(8000, 2, 32), 1.0 / 2147483648.0),
+            (WavSpec::float(8000, 2, 32), 1e-7),
+            (WavSpec::float(8000, 2, 64), 0.0),
+        ] {
+            let (header, frames) = roundtrip(spec.clone(), &data);
+            assert_eq!(header.format, spec.format);
+            assert_eq!(header.bits_per_sample, spec.bits_per_sample);
+            assert_eq!(header.samples_per_channel, 257);
+            let error = (&frames - &data)
+                .mapv(f64::abs)
+                .fold(0.0f64, |a, &b| a.max(b));
+            assert!(error <= tolerance, "{:?}: error {}", spec, error);
+        }
+    }
+
+    #[test]
+    fn test_extensible_and_chunked_reading() {
+        let data = ramp(6, 100);
+        let spec = WavSpec::pcm(48000, 6, 24);
+        let mut writer = WavWriter::new(Cursor::new(Vec::new()), spec).unwrap();
+        writer.write_frames(data.view()).unwrap();
+        let bytes = writer.finalize().unwrap().into_inner();
+        assert_eq!(
+            u16::from_le_bytes([bytes[56], bytes[57]]),
+            FORMAT_EXTENSIBLE
+        );
+
+        let mut reader = WavReader::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(reader.header().channel_mask, Some(0x3F));
+        let mut total = 0;
+        while let Some(block) = reader.read_frames_f64(30).unwrap() {
+            assert_eq!(block.nrows(), 6);
+            assert!((block[[5, 0]] - data[[5, total]]).abs() < 1e-6);
+            total += block.ncols();
+        }
+        assert_eq!(total, 100);
+
+        reader.seek(90).unwrap();
+        assert_eq!(reader.frames_remaining(), 10);
+        assert_eq!(reader.read_frames(100).unwrap().unwrap().ncols(), 10);
+    }
+
+    #[test]
+    fn test_metadata_chunks() {
+        let mut metadata = WavMetadata::default();
+        metadata
+            .info
+            .insert("INAM".to_string(), "Test tone".to_string());
+        metadata.info.insert("IART".to_string(), "Lab".to_string());
+        metadata.cue_points.push(CuePoint {
+            id: 1,
+            position: 10,
+            sample_offset: 10,
+            label: Some("onset".to_string()),
+        });
+        metadata.sampler = Some(SamplerInfo {
+            midi_unity_note: 60,
+            loops: vec![SampleLoop {
+                cue_point_id: 1,
+                loop_type: 0,
+                start: 10,
+                end: 50,
+                fraction: 0,
+                play_count: 0,
+            }],
+            ..Default::default()
+        });
+
+        let mut writer = WavWriter::new(Cursor::new(Vec::new()), WavSpec::pcm(8000, 1, 8)).unwrap();
+        writer.set_metadata(metadata.clone());
+        // Odd data size to exercise chunk padding
+        writer.write_frames(ramp(1, 63).view()).unwrap();
+        let bytes = writer.finalize().unwrap().into_inner();
+
+        let reader = WavReader::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(reader.metadata(), &metadata);
+        assert_eq!(reader.header().samples_per_channel, 63);
+    }
+
+    #[test]
+    fn test_rf64() {
+        let data = ramp(1, 10);
+        let mut writer =
+            WavWriter::new(Cursor::new(Vec::new()), WavSpec::float(8000, 1, 32)).unwrap();
+        writer.rf64_threshold = 16;
+        writer.write_frames(data.view()).unwrap();
+        let bytes = writer.finalize().unwrap().into_inner();
+        assert_eq!(&bytes[..4], b"RF64");
+        assert_eq!(&bytes[12..16], b"ds64");
+
+        let mut reader = WavReader::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(reader.header().samples_per_channel, 10);
+        let frames = reader.read_frames_f64(100).unwrap().unwrap();
+        assert!((frames[[0, 3]] - data[[0, 3]]).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_g711_decoding() {
+        assert_eq!(mulaw_to_i16(0xFF), 0);
+        assert_eq!(mulaw_to_i16(0x80), 32124);
+        assert_eq!(mulaw_to_i16(0x00), -32124);
+        assert_eq!(alaw_to_i16(0xD5), 8);
+        assert_eq!(alaw_to_i16(0xAA), 32256);
+        assert_eq!(alaw_to_i16(0x2A), -32256);
+    }
+}
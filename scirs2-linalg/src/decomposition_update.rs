@@ -0,0 +1,775 @@
+//! Updating and downdating of Cholesky and QR factorizations
+//!
+//! These functions modify the outputs of [`crate::cholesky`] and [`crate::qr`]
+//! after a low-rank change of the factored matrix in `O(n²)` operations per
+//! rank-one term, row or column, instead of the `O(n³)` of a new factorization.
+
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2, Axis};
+use num_traits::{Float, NumAssign};
+use std::iter::Sum;
+
+use crate::error::{LinalgError, LinalgResult};
+
+/// Update a Cholesky factor after a rank-one modification `A + xxᵀ`.
+///
+/// # Arguments
+///
+/// * `l` - Lower triangular Cholesky factor of `A` (entries above the diagonal are ignored)
+/// * `x` - Update vector
+///
+/// # Returns
+///
+/// * Lower triangular Cholesky factor of `A + xxᵀ`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{cholesky, cholesky_update};
+///
+/// let a = array![[4.0_f64, 2.0], [2.0, 5.0]];
+/// let x = array![1.0_f64, 2.0];
+/// let l = cholesky(&a.view()).unwrap();
+/// let l_new = cholesky_update(&l.view(), &x.view()).unwrap();
+/// let a_new = l_new.dot(&l_new.t());
+/// assert!((a_new[[0, 1]] - 4.0).abs() < 1e-12);
+/// assert!((a_new[[1, 1]] - 9.0).abs() < 1e-12);
+/// ```
+pub fn cholesky_update<F>(l: &ArrayView2<F>, x: &ArrayView1<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign,
+{
+    let mut l = lower_factor(l)?;
+    check_update_length(l.nrows(), x.len())?;
+    update_in_place(&mut l, x.to_owned());
+    Ok(l)
+}
+
+/// Downdate a Cholesky factor after a rank-one modification `A - xxᵀ`.
+///
+/// Uses the LINPACK algorithm, which solves `Lp = x` and applies plane rotations,
+/// and is more stable than hyperbolic rotations.
+///
+/// # Arguments
+///
+/// * `l` - Lower triangular Cholesky factor of `A` (entries above the diagonal are ignored)
+/// * `x` - Downdate vector
+///
+/// # Returns
+///
+/// * Lower triangular Cholesky factor of `A - xxᵀ`, or a `NonPositiveDefiniteError`
+///   if `A - xxᵀ` is not numerically positive definite
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{cholesky, cholesky_downdate};
+///
+/// let a = array![[5.0_f64, 4.0], [4.0, 9.0]];
+/// let l = cholesky(&a.view()).unwrap();
+/// let l_new = cholesky_downdate(&l.view(), &array![1.0, 2.0].view()).unwrap();
+/// let a_new = l_new.dot(&l_new.t());
+/// assert!((a_new[[0, 0]] - 4.0).abs() < 1e-12);
+/// assert!((a_new[[0, 1]] - 2.0).abs() < 1e-12);
+///
+/// // Removing too much destroys positive definiteness
+/// assert!(cholesky_downdate(&l.view(), &array![3.0, 0.0].view()).is_err());
+/// ```
+pub fn cholesky_downdate<F>(l: &ArrayView2<F>, x: &ArrayView1<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign,
+{
+    let mut l = lower_factor(l)?;
+    check_update_length(l.nrows(), x.len())?;
+    downdate_in_place(&mut l, &x.to_owned())?;
+    Ok(l)
+}
+
+/// Update a Cholesky factor after a rank-k modification `A + XXᵀ`.
+///
+/// # Arguments
+///
+/// * `l` - Lower triangular Cholesky factor of `A` (entries above the diagonal are ignored)
+/// * `x` - `n × k` matrix whose columns are the update vectors
+///
+/// # Returns
+///
+/// * Lower triangular Cholesky factor of `A + XXᵀ`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{cholesky, cholesky_update_rank_k};
+///
+/// let l = cholesky(&array![[2.0_f64, 0.0], [0.0, 2.0]].view()).unwrap();
+/// let x = array![[1.0_f64, 0.0], [0.0, 1.0]];
+/// let l_new = cholesky_update_rank_k(&l.view(), &x.view()).unwrap();
+/// assert!((l_new[[0, 0]] - 3.0_f64.sqrt()).abs() < 1e-12);
+/// assert!((l_new[[1, 1]] - 3.0_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn cholesky_update_rank_k<F>(l: &ArrayView2<F>, x: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign,
+{
+    let mut l = lower_factor(l)?;
+    check_update_length(l.nrows(), x.nrows())?;
+    for column in x.columns() {
+        update_in_place(&mut l, column.to_owned());
+    }
+    Ok(l)
+}
+
+/// Downdate a Cholesky factor after a rank-k modification `A - XXᵀ`.
+///
+/// The columns of `X` are removed one at a time, and every intermediate matrix
+/// must remain positive definite.
+///
+/// # Arguments
+///
+/// * `l` - Lower triangular Cholesky factor of `A` (entries above the diagonal are ignored)
+/// * `x` - `n × k` matrix whose columns are the downdate vectors
+///
+/// # Returns
+///
+/// * Lower triangular Cholesky factor of `A - XXᵀ`, or a `NonPositiveDefiniteError`
+///   if positive definiteness is lost
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{cholesky, cholesky_downdate_rank_k};
+///
+/// let l = cholesky(&array![[3.0_f64, 0.0], [0.0, 3.0]].view()).unwrap();
+/// let x = array![[1.0_f64, 0.0], [0.0, 1.0]];
+/// let l_new = cholesky_downdate_rank_k(&l.view(), &x.view()).unwrap();
+/// assert!((l_new[[0, 0]] - 2.0_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn cholesky_downdate_rank_k<F>(l: &ArrayView2<F>, x: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign,
+{
+    let mut l = lower_factor(l)?;
+    check_update_length(l.nrows(), x.nrows())?;
+    for (k, column) in x.columns().into_iter().enumerate() {
+        downdate_in_place(&mut l, &column.to_owned()).map_err(|err| match err {
+            LinalgError::NonPositiveDefiniteError(msg) => {
+                LinalgError::NonPositiveDefiniteError(format!("{} (downdate column {})", msg, k))
+            }
+            other => other,
+        })?;
+    }
+    Ok(l)
+}
+
+/// Update a full QR factorization after inserting rows or columns.
+///
+/// With `Axis(0)` the rows of `u` are inserted into `A` before row `k`; with
+/// `Axis(1)` the columns of `u` are inserted before column `k`. Each inserted
+/// row or column costs `O(m²)` plane rotations.
+///
+/// # Arguments
+///
+/// * `q` - Orthogonal `m × m` factor of `A`
+/// * `r` - Upper triangular `m × n` factor of `A` (entries below the diagonal are ignored)
+/// * `u` - Rows (`p × n`) or columns (`m × p`) to insert
+/// * `k` - Index before which to insert
+/// * `axis` - `Axis(0)` for rows, `Axis(1)` for columns
+///
+/// # Returns
+///
+/// * Tuple `(Q, R)` of the enlarged matrix
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, Axis};
+/// use scirs2_linalg::{qr, qr_insert};
+///
+/// let a = array![[1.0_f64, 2.0], [3.0, 4.0], [5.0, 6.0]];
+/// let (q, r) = qr(&a.view()).unwrap();
+/// let row = array![[7.0_f64, 9.0]];
+/// let (q1, r1) = qr_insert(&q.view(), &r.view(), &row.view(), 1, Axis(0)).unwrap();
+/// let a1 = q1.dot(&r1);
+/// assert!((a1[[1, 0]] - 7.0).abs() < 1e-12);
+/// assert!((a1[[2, 1]] - 4.0).abs() < 1e-12);
+/// assert!(r1[[3, 1]].abs() < 1e-12);
+/// ```
+pub fn qr_insert<F>(
+    q: &ArrayView2<F>,
+    r: &ArrayView2<F>,
+    u: &ArrayView2<F>,
+    k: usize,
+    axis: Axis,
+) -> LinalgResult<(Array2<F>, Array2<F>)>
+where
+    F: Float + NumAssign + Sum + 'static,
+{
+    let (m, n) = check_qr(q, r)?;
+    let (mut q, mut r) = (q.to_owned(), upper_factor(r));
+    match axis {
+        Axis(0) => {
+            if u.ncols() != n {
+                return Err(LinalgError::ShapeError(format!(
+                    "Inserted rows must have {} columns, got shape {:?}",
+                    n,
+                    u.shape()
+                )));
+            }
+            check_index(k, m, "row")?;
+            for (offset, row) in u.rows().into_iter().enumerate() {
+                (q, r) = insert_row(&q, &r, &row, k + offset);
+            }
+        }
+        Axis(1) => {
+            if u.nrows() != m {
+                return Err(LinalgError::ShapeError(format!(
+                    "Inserted columns must have {} rows, got shape {:?}",
+                    m,
+                    u.shape()
+                )));
+            }
+            check_index(k, n, "column")?;
+            for (offset, column) in u.columns().into_iter().enumerate() {
+                r = insert_column(&mut q, &r, &column, k + offset);
+            }
+        }
+        Axis(other) => return Err(invalid_axis(other)),
+    }
+    Ok((q, r))
+}
+
+/// Update a full QR factorization after deleting rows or columns.
+///
+/// With `Axis(0)` rows `k..k + p` of `A` are deleted; with `Axis(1)` columns
+/// `k..k + p` are deleted.
+///
+/// # Arguments
+///
+/// * `q` - Orthogonal `m × m` factor of `A`
+/// * `r` - Upper triangular `m × n` factor of `A` (entries below the diagonal are ignored)
+/// * `k` - First row or column to delete
+/// * `p` - Number of rows or columns to delete
+/// * `axis` - `Axis(0)` for rows, `Axis(1)` for columns
+///
+/// # Returns
+///
+/// * Tuple `(Q, R)` of the reduced matrix
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, Axis};
+/// use scirs2_linalg::{qr, qr_delete};
+///
+/// let a = array![[1.0_f64, 2.0, 0.5], [3.0, 4.0, 1.5], [5.0, 6.0, -1.0]];
+/// let (q, r) = qr(&a.view()).unwrap();
+/// let (q1, r1) = qr_delete(&q.view(), &r.view(), 1, 1, Axis(1)).unwrap();
+/// let a1 = q1.dot(&r1);
+/// assert!((a1[[2, 1]] + 1.0).abs() < 1e-12);
+/// assert!(r1[[2, 1]].abs() < 1e-12);
+/// ```
+pub fn qr_delete<F>(
+    q: &ArrayView2<F>,
+    r: &ArrayView2<F>,
+    k: usize,
+    p: usize,
+    axis: Axis,
+) -> LinalgResult<(Array2<F>, Array2<F>)>
+where
+    F: Float + NumAssign + Sum + 'static,
+{
+    let (m, n) = check_qr(q, r)?;
+    let (mut q, mut r) = (q.to_owned(), upper_factor(r));
+    match axis {
+        Axis(0) => {
+            check_range(k, p, m, "rows")?;
+            for _ in 0..p {
+                (q, r) = delete_row(q, r, k);
+            }
+        }
+        Axis(1) => {
+            check_range(k, p, n, "columns")?;
+            for _ in 0..p {
+                r = delete_column(&mut q, &r, k);
+            }
+        }
+        Axis(other) => return Err(invalid_axis(other)),
+    }
+    Ok((q, r))
+}
+
+/// Update a full QR factorization after a rank-one modification `A + uvᵀ`.
+///
+/// # Arguments
+///
+/// * `q` - Orthogonal `m × m` factor of `A`
+/// * `r` - Upper triangular `m × n` factor of `A` (entries below the diagonal are ignored)
+/// * `u` - Left update vector of length `m`
+/// * `v` - Right update vector of length `n`
+///
+/// # Returns
+///
+/// * Tuple `(Q, R)` of `A + uvᵀ`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{qr, qr_update};
+///
+/// let a = array![[2.0_f64, 1.0], [1.0, 3.0]];
+/// let (q, r) = qr(&a.view()).unwrap();
+/// let (u, v) = (array![1.0_f64, -1.0], array![0.5_f64, 2.0]);
+/// let (q1, r1) = qr_update(&q.view(), &r.view(), &u.view(), &v.view()).unwrap();
+/// let a1 = q1.dot(&r1);
+/// assert!((a1[[0, 1]] - 3.0).abs() < 1e-12);
+/// assert!((a1[[1, 0]] - 0.5).abs() < 1e-12);
+/// assert!(r1[[1, 0]].abs() < 1e-12);
+/// ```
+pub fn qr_update<F>(
+    q: &ArrayView2<F>,
+    r: &ArrayView2<F>,
+    u: &ArrayView1<F>,
+    v: &ArrayView1<F>,
+) -> LinalgResult<(Array2<F>, Array2<F>)>
+where
+    F: Float + NumAssign + Sum + 'static,
+{
+    let (m, n) = check_qr(q, r)?;
+    if u.len() != m || v.len() != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Update vectors must have lengths {} and {}, got {} and {}",
+            m,
+            n,
+            u.len(),
+            v.len()
+        )));
+    }
+    let (mut q, mut r) = (q.to_owned(), upper_factor(r));
+
+    // Reduce w = Qᵀu to a multiple of e₁; R becomes upper Hessenberg
+    let mut w = q.t().dot(u);
+    for i in (1..m).rev() {
+        let (c, s) = givens(w[i - 1], w[i]);
+        w[i - 1] = c * w[i - 1] + s * w[i];
+        w[i] = F::zero();
+        rotate_rows(&mut r, i - 1, i, c, s, i - 1);
+        rotate_columns(&mut q, i - 1, i, c, s);
+    }
+    if m > 0 {
+        r.row_mut(0).scaled_add(w[0], v);
+    }
+    restore_triangle(&mut q, &mut r, 0);
+    Ok((q, r))
+}
+
+/// Copy of the lower triangle of a Cholesky factor, checking its diagonal
+fn lower_factor<F: Float>(l: &ArrayView2<F>) -> LinalgResult<Array2<F>> {
+    if l.nrows() != l.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Cholesky factor must be square, got shape {:?}",
+            l.shape()
+        )));
+    }
+    if (0..l.nrows()).any(|i| l[[i, i]] <= F::zero()) {
+        return Err(LinalgError::InvalidInputError(
+            "Cholesky factor must have a positive diagonal".to_string(),
+        ));
+    }
+    Ok(Array2::from_shape_fn(l.dim(), |(i, j)| {
+        if j <= i {
+            l[[i, j]]
+        } else {
+            F::zero()
+        }
+    }))
+}
+
+fn check_update_length(n: usize, len: usize) -> LinalgResult<()> {
+    if len != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Update vectors must have length {} to match the Cholesky factor, got {}",
+            n, len
+        )));
+    }
+    Ok(())
+}
+
+/// Rank-one Cholesky update `LLᵀ + xxᵀ` with plane rotations
+fn update_in_place<F: Float + NumAssign>(l: &mut Array2<F>, mut x: Array1<F>) {
+    let n = l.nrows();
+    for k in 0..n {
+        let diagonal = l[[k, k]];
+        let r = diagonal.hypot(x[k]);
+        let (c, s) = (r / diagonal, x[k] / diagonal);
+        l[[k, k]] = r;
+        for i in k + 1..n {
+            l[[i, k]] = (l[[i, k]] + s * x[i]) / c;
+            x[i] = c * x[i] - s * l[[i, k]];
+        }
+    }
+}
+
+/// Rank-one Cholesky downdate `LLᵀ - xxᵀ` (LINPACK `dchdd`)
+fn downdate_in_place<F: Float + NumAssign>(l: &mut Array2<F>, x: &Array1<F>) -> LinalgResult<()> {
+    let n = l.nrows();
+
+    // Solve Lp = x; the downdated matrix is positive definite iff ‖p‖ < 1
+    let mut p = x.clone();
+    for i in 0..n {
+        for j in 0..i {
+            let lp = l[[i, j]] * p[j];
+            p[i] -= lp;
+        }
+        p[i] /= l[[i, i]];
+    }
+    let p_norm_sq = p.iter().fold(F::zero(), |s, &v| s + v * v);
+    let rho_sq = F::one() - p_norm_sq;
+    // ρ² is det(A - xxᵀ) / det(A)
+    if rho_sq <= F::epsilon() {
+        return Err(LinalgError::NonPositiveDefiniteError(
+            "Cholesky downdate would make the matrix indefinite or singular".to_string(),
+        ));
+    }
+
+    // Rotations that reduce [ρ; p] to e₁, from the last component upwards
+    let mut alpha = rho_sq.sqrt();
+    let mut rotations = vec![(F::one(), F::zero()); n];
+    for i in (0..n).rev() {
+        let scale = alpha + p[i].abs();
+        let (a, b) = (alpha / scale, p[i] / scale);
+        let norm = a.hypot(b);
+        rotations[i] = (a / norm, b / norm);
+        alpha = scale * norm;
+    }
+
+    // Apply them to the columns of R = Lᵀ
+    for j in 0..n {
+        let mut xx = F::zero();
+        for i in (0..=j).rev() {
+            let (c, s) = rotations[i];
+            let t = c * xx + s * l[[j, i]];
+            l[[j, i]] = c * l[[j, i]] - s * xx;
+            xx = t;
+        }
+    }
+
+    // Keep the diagonal positive
+    for i in 0..n {
+        if l[[i, i]] < F::zero() {
+            l.column_mut(i).mapv_inplace(|v| -v);
+        }
+        if l[[i, i]] == F::zero() {
+            return Err(LinalgError::NonPositiveDefiniteError(
+                "Cholesky downdate produced a singular factor".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_qr<F>(q: &ArrayView2<F>, r: &ArrayView2<F>) -> LinalgResult<(usize, usize)> {
+    if q.nrows() != q.ncols() || r.nrows() != q.nrows() {
+        return Err(LinalgError::ShapeError(format!(
+            "Expected a square Q and an R with as many rows, got shapes {:?} and {:?}",
+            q.shape(),
+            r.shape()
+        )));
+    }
+    Ok(r.dim())
+}
+
+/// Copy of `R` with the rounding-level entries below the diagonal removed
+fn upper_factor<F: Float>(r: &ArrayView2<F>) -> Array2<F> {
+    Array2::from_shape_fn(r.dim(), |(i, j)| if j >= i { r[[i, j]] } else { F::zero() })
+}
+
+fn check_index(k: usize, size: usize, what: &str) -> LinalgResult<()> {
+    if k > size {
+        return Err(LinalgError::IndexError(format!(
+            "Cannot insert before {} {} of a matrix with {} {}s",
+            what, k, size, what
+        )));
+    }
+    Ok(())
+}
+
+fn check_range(k: usize, p: usize, size: usize, what: &str) -> LinalgResult<()> {
+    if k + p > size {
+        return Err(LinalgError::IndexError(format!(
+            "Cannot delete {} {}..{} of a matrix with {} {}",
+            what,
+            k,
+            k + p,
+            size,
+            what
+        )));
+    }
+    Ok(())
+}
+
+fn invalid_axis(axis: usize) -> LinalgError {
+    LinalgError::InvalidInputError(format!(
+        "Axis must be 0 (rows) or 1 (columns), got {}",
+        axis
+    ))
+}
+
+/// Rotation `(c, s)` with `[c s; -s c] [a; b] = [r; 0]`
+fn givens<F: Float>(a: F, b: F) -> (F, F) {
+    if b == F::zero() {
+        (F::one(), F::zero())
+    } else {
+        let r = a.hypot(b);
+        (a / r, b / r)
+    }
+}
+
+/// Apply a rotation to rows `i` and `j` of `m`, starting at column `from`
+fn rotate_rows<F: Float>(m: &mut Array2<F>, i: usize, j: usize, c: F, s: F, from: usize) {
+    for col in from..m.ncols() {
+        let (x, y) = (m[[i, col]], m[[j, col]]);
+        m[[i, col]] = c * x + s * y;
+        m[[j, col]] = c * y - s * x;
+    }
+}
+
+/// Multiply columns `i` and `j` of `q` by the transpose of a rotation
+fn rotate_columns<F: Float>(q: &mut Array2<F>, i: usize, j: usize, c: F, s: F) {
+    for row in 0..q.nrows() {
+        let (x, y) = (q[[row, i]], q[[row, j]]);
+        q[[row, i]] = c * x + s * y;
+        q[[row, j]] = c * y - s * x;
+    }
+}
+
+/// Zero the subdiagonal of an `R` that is upper Hessenberg from column `from` on
+fn restore_triangle<F: Float>(q: &mut Array2<F>, r: &mut Array2<F>, from: usize) {
+    let (m, n) = r.dim();
+    for j in from..n.min(m.saturating_sub(1)) {
+        let (c, s) = givens(r[[j, j]], r[[j + 1, j]]);
+        rotate_rows(r, j, j + 1, c, s, j);
+        r[[j + 1, j]] = F::zero();
+        rotate_columns(q, j, j + 1, c, s);
+    }
+}
+
+fn insert_row<F: Float>(
+    q: &Array2<F>,
+    r: &Array2<F>,
+    row: &ArrayView1<F>,
+    k: usize,
+) -> (Array2<F>, Array2<F>) {
+    let m = q.nrows();
+    // [row; A] = diag(1, Q) [row; R], with an upper Hessenberg right factor
+    let mut q1 = Array2::zeros((m + 1, m + 1));
+    q1[[0, 0]] = F::one();
+    q1.slice_mut(s![1.., 1..]).assign(q);
+    let mut r1 = Array2::zeros((m + 1, r.ncols()));
+    r1.row_mut(0).assign(row);
+    r1.slice_mut(s![1.., ..]).assign(r);
+    restore_triangle(&mut q1, &mut r1, 0);
+
+    // Move the new row of Q from the top to position k
+    let order: Vec<usize> = (1..=k).chain(std::iter::once(0)).chain(k + 1..=m).collect();
+    (q1.select(Axis(0), &order), r1)
+}
+
+fn delete_row<F: Float>(q: Array2<F>, mut r: Array2<F>, k: usize) -> (Array2<F>, Array2<F>) {
+    let m = q.nrows();
+    // Move the deleted row of Q to the top
+    let order: Vec<usize> = std::iter::once(k).chain(0..k).chain(k + 1..m).collect();
+    let mut q = q.select(Axis(0), &order);
+
+    // Reduce the first row of Q to ±e₁ from the right; R becomes upper Hessenberg
+    for j in (1..m).rev() {
+        let (c, s) = givens(q[[0, j - 1]], q[[0, j]]);
+        rotate_columns(&mut q, j - 1, j, c, s);
+        q[[0, j]] = F::zero();
+        rotate_rows(&mut r, j - 1, j, c, s, j - 1);
+    }
+    (
+        q.slice(s![1.., 1..]).to_owned(),
+        r.slice(s![1.., ..]).to_owned(),
+    )
+}
+
+fn insert_column<F: Float + 'static>(
+    q: &mut Array2<F>,
+    r: &Array2<F>,
+    column: &ArrayView1<F>,
+    k: usize,
+) -> Array2<F> {
+    let (m, n) = r.dim();
+    let mut r1 = Array2::zeros((m, n + 1));
+    r1.slice_mut(s![.., ..k]).assign(&r.slice(s![.., ..k]));
+    r1.column_mut(k).assign(&q.t().dot(column));
+    r1.slice_mut(s![.., k + 1..]).assign(&r.slice(s![.., k..]));
+
+    // Zero the new column below the diagonal from the bottom up
+    for i in (k + 1..m).rev() {
+        let (c, s) = givens(r1[[i - 1, k]], r1[[i, k]]);
+        rotate_rows(&mut r1, i - 1, i, c, s, k);
+        r1[[i, k]] = F::zero();
+        rotate_columns(q, i - 1, i, c, s);
+    }
+    r1
+}
+
+fn delete_column<F: Float>(q: &mut Array2<F>, r: &Array2<F>, k: usize) -> Array2<F> {
+    let keep: Vec<usize> = (0..r.ncols()).filter(|&j| j != k).collect();
+    let mut r1 = r.select(Axis(1), &keep);
+    restore_triangle(q, &mut r1, k);
+    r1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decomposition::{cholesky, qr};
+    use approx::assert_relative_eq;
+    use ndarray::array;
+
+    fn assert_close(a: &Array2<f64>, b: &Array2<f64>, tol: f64) {
+        assert_eq!(a.dim(), b.dim());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert_relative_eq!(x, y, epsilon = tol);
+        }
+    }
+
+    fn check_qr_factors(q: &Array2<f64>, r: &Array2<f64>, a: &Array2<f64>) {
+        let m = q.nrows();
+        assert_close(&q.t().dot(q), &Array2::eye(m), 1e-12);
+        for i in 0..r.nrows() {
+            for j in 0..i.min(r.ncols()) {
+                assert_eq!(r[[i, j]], 0.0);
+            }
+        }
+        assert_close(&q.dot(r), a, 1e-12);
+    }
+
+    #[test]
+    fn test_cholesky_update_and_downdate() {
+        let a = array![
+            [6.0, 2.0, 1.0, 0.5],
+            [2.0, 5.0, 1.5, 0.0],
+            [1.0, 1.5, 4.0, 1.0],
+            [0.5, 0.0, 1.0, 3.0]
+        ];
+        let x = array![0.5, -1.0, 2.0, 0.3];
+        let l = cholesky(&a.view()).unwrap();
+
+        let updated = cholesky_update(&l.view(), &x.view()).unwrap();
+        let outer = Array2::from_shape_fn((4, 4), |(i, j)| x[i] * x[j]);
+        let a_plus = &a + &outer;
+        assert_close(&updated.dot(&updated.t()), &a_plus, 1e-12);
+        assert_close(&updated, &cholesky(&a_plus.view()).unwrap(), 1e-12);
+
+        // Downdating the update recovers the original factor
+        let restored = cholesky_downdate(&updated.view(), &x.view()).unwrap();
+        assert_close(&restored, &l, 1e-12);
+        for i in 0..4 {
+            for j in i + 1..4 {
+                assert_eq!(restored[[i, j]], 0.0);
+            }
+        }
+
+        // A - xxᵀ is indefinite
+        assert!(matches!(
+            cholesky_downdate(&l.view(), &x.view()),
+            Err(LinalgError::NonPositiveDefiniteError(_))
+        ));
+        assert!(cholesky_update(&l.view(), &array![1.0, 2.0].view()).is_err());
+    }
+
+    #[test]
+    fn test_cholesky_rank_k() {
+        let a = array![[4.0, 1.0, 0.0], [1.0, 3.0, 0.5], [0.0, 0.5, 2.0]];
+        let x = array![[1.0, 0.2], [0.5, -0.4], [-0.3, 0.6]];
+        let l = cholesky(&a.view()).unwrap();
+        let updated = cholesky_update_rank_k(&l.view(), &x.view()).unwrap();
+        let a_plus = &a + &x.dot(&x.t());
+        assert_close(&updated.dot(&updated.t()), &a_plus, 1e-12);
+
+        let restored = cholesky_downdate_rank_k(&updated.view(), &x.view()).unwrap();
+        assert_close(&restored, &l, 1e-12);
+
+        // The second column makes the matrix singular
+        let x = array![[1.0, 0.0], [0.0, 1.0], [0.0, 0.0]];
+        let d = array![[2.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let l = cholesky(&d.view()).unwrap();
+        let err = cholesky_downdate_rank_k(&l.view(), &x.view()).unwrap_err();
+        assert!(
+            matches!(err, LinalgError::NonPositiveDefiniteError(msg) if msg.contains("column 1"))
+        );
+    }
+
+    #[test]
+    fn test_qr_insert_and_delete() {
+        let a = array![
+            [1.0, 2.0, 0.5],
+            [3.0, -4.0, 1.5],
+            [5.0, 6.0, -1.0],
+            [0.5, 1.0, 2.0]
+        ];
+        let (q, r) = qr(&a.view()).unwrap();
+
+        // Two rows before row 1
+        let rows = array![[7.0, 9.0, 1.0], [-2.0, 0.0, 3.0]];
+        let (q1, r1) = qr_insert(&q.view(), &r.view(), &rows.view(), 1, Axis(0)).unwrap();
+        let mut expected = a.clone();
+        for (offset, row) in rows.rows().into_iter().enumerate() {
+            let _ = expected.push_row(row);
+            let n = expected.nrows();
+            let order: Vec<usize> = (0..1 + offset)
+                .chain(std::iter::once(n - 1))
+                .chain(1 + offset..n - 1)
+                .collect();
+            expected = expected.select(Axis(0), &order);
+        }
+        check_qr_factors(&q1, &r1, &expected);
+
+        // Deleting them again
+        let (q2, r2) = qr_delete(&q1.view(), &r1.view(), 1, 2, Axis(0)).unwrap();
+        check_qr_factors(&q2, &r2, &a);
+
+        // Columns, including growth to a wide matrix
+        let columns = array![[1.0, 0.0], [2.0, 1.0], [0.0, -1.0], [3.0, 0.5]];
+        let (q3, r3) = qr_insert(&q.view(), &r.view(), &columns.view(), 3, Axis(1)).unwrap();
+        let mut expected = a.clone();
+        for column in columns.columns() {
+            let _ = expected.push_column(column);
+        }
+        check_qr_factors(&q3, &r3, &expected);
+        let (q4, r4) = qr_insert(&q3.view(), &r3.view(), &columns.view(), 0, Axis(1)).unwrap();
+        assert_eq!(r4.dim(), (4, 7));
+        assert_close(&q4.dot(&r4).slice(s![.., 2..]).to_owned(), &expected, 1e-12);
+
+        let (q5, r5) = qr_delete(&q.view(), &r.view(), 0, 2, Axis(1)).unwrap();
+        check_qr_factors(&q5, &r5, &a.slice(s![.., 2..]).to_owned());
+
+        assert!(qr_delete(&q.view(), &r.view(), 3, 2, Axis(0)).is_err());
+        assert!(qr_insert(&q.view(), &r.view(), &rows.view(), 5, Axis(0)).is_err());
+        assert!(qr_delete(&q.view(), &r.view(), 0, 1, Axis(2)).is_err());
+    }
+
+    #[test]
+    fn test_qr_update() {
+        let a = array![
+            [1.0, 2.0, 0.5],
+            [3.0, -4.0, 1.5],
+            [5.0, 6.0, -1.0],
+            [0.5, 1.0, 2.0]
+        ];
+        let (q, r) = qr(&a.view()).unwrap();
+        let u = array![1.0, -0.5, 2.0, 0.25];
+        let v = array![0.3, 1.0, -2.0];
+        let (q1, r1) = qr_update(&q.view(), &r.view(), &u.view(), &v.view()).unwrap();
+        let expected = &a + &Array2::from_shape_fn((4, 3), |(i, j)| u[i] * v[j]);
+        check_qr_factors(&q1, &r1, &expected);
+        assert!(qr_update(&q.view(), &r.view(), &v.view(), &v.view()).is_err());
+    }
+}
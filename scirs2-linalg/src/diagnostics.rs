@@ -282,6 +282,89 @@ where
     LinalgError::SingularMatrixError(message)
 }
 
+/// Residual diagnostics for a computed solution of a matrix equation
+#[derive(Debug, Clone)]
+pub struct MatrixEquationDiagnostics<F: Float> {
+    /// Description of the equation
+    pub equation: String,
+    /// Frobenius norm of the residual
+    pub residual_norm: F,
+    /// Residual norm divided by the sum of the norms of the equation terms
+    pub relative_residual: F,
+    /// Frobenius norm of the solution
+    pub solution_norm: F,
+    /// Suggested fixes for common issues
+    pub suggestions: Vec<String>,
+}
+
+impl<F: Float + fmt::Display> fmt::Display for MatrixEquationDiagnostics<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Matrix Equation Diagnostics:")?;
+        writeln!(f, "  Equation: {}", self.equation)?;
+        writeln!(f, "  Residual norm: {}", self.residual_norm)?;
+        writeln!(f, "  Relative residual: {}", self.relative_residual)?;
+        writeln!(f, "  Solution norm: {}", self.solution_norm)?;
+
+        if !self.suggestions.is_empty() {
+            writeln!(f, "\nSuggestions:")?;
+            for suggestion in &self.suggestions {
+                writeln!(f, "  - {}", suggestion)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Summarize the residual of a matrix equation
+///
+/// `scale` is the sum of the Frobenius norms of the terms of the equation,
+/// which makes the relative residual independent of the scaling of the
+/// coefficients.
+pub fn analyze_matrix_equation<F>(
+    equation: &str,
+    residual: &ArrayView2<F>,
+    scale: F,
+    solution: &ArrayView2<F>,
+) -> MatrixEquationDiagnostics<F>
+where
+    F: Float + NumAssign + std::iter::Sum + fmt::Display,
+{
+    let frobenius = |a: &ArrayView2<F>| a.iter().map(|&v| v * v).sum::<F>().sqrt();
+    let residual_norm = frobenius(residual);
+    let solution_norm = frobenius(solution);
+    let relative_residual = if scale > F::zero() {
+        residual_norm / scale
+    } else {
+        residual_norm
+    };
+
+    let mut suggestions = Vec::new();
+    if !residual_norm.is_finite() || !solution_norm.is_finite() {
+        suggestions.push(
+            "Solution contains non-finite values. The equation is singular or nearly singular."
+                .to_string(),
+        );
+    } else if relative_residual > F::epsilon().sqrt() {
+        suggestions.push(
+            "Residual is large relative to the equation terms. The problem is ill-conditioned, \
+             for example because eigenvalues are close to the stability boundary."
+                .to_string(),
+        );
+        suggestions.push(
+            "Consider balancing the coefficients or rescaling the weight matrices.".to_string(),
+        );
+    }
+
+    MatrixEquationDiagnostics {
+        equation: equation.to_string(),
+        residual_norm,
+        relative_residual,
+        solution_norm,
+        suggestions,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,6 +97,7 @@ pub mod broadcast;
 pub mod complex;
 pub mod convolution;
 mod decomposition;
+pub mod decomposition_update;
 // Main eigen module
 pub mod eigen;
 pub use self::eigen::{eig, eigh, eigvals, eigvalsh, power_iteration};
@@ -119,12 +120,14 @@ mod iterative_solvers;
 pub mod kronecker;
 pub mod lowrank;
 pub mod matrix_calculus;
+pub mod matrix_equations;
 pub mod matrix_factorization;
 pub mod matrix_functions;
 pub mod matrixfree;
 pub mod mixed_precision;
 mod norm;
 pub mod optim;
+pub mod out_of_core;
 pub mod perf_opt;
 pub mod projection;
 /// Quantization-aware linear algebra operations
@@ -145,6 +148,7 @@ pub mod special;
 pub mod specialized;
 pub mod stats;
 pub mod structured;
+pub mod symmetric_indefinite;
 #[cfg(feature = "tensor_contraction")]
 pub mod tensor_contraction;
 // Automatic differentiation support
@@ -187,11 +191,20 @@ pub use self::complex::enhanced_ops::{
 };
 pub use self::complex::{complex_inverse, complex_matmul, hermitian_transpose};
 pub use self::decomposition::*;
+pub use self::decomposition_update::{
+    cholesky_downdate, cholesky_downdate_rank_k, cholesky_update, cholesky_update_rank_k,
+    qr_delete, qr_insert, qr_update,
+};
 // Eigen module exports included in other use statements
+pub use self::diagnostics::MatrixEquationDiagnostics;
 pub use self::eigen_specialized::*;
 pub use self::extended_precision::*;
 pub use self::iterative_solvers::*;
 pub use self::matrix_calculus::*;
+pub use self::matrix_equations::{
+    matrix_equation_residual, solve_continuous_are, solve_continuous_lyapunov, solve_discrete_are,
+    solve_discrete_lyapunov, solve_sylvester, MatrixEquation,
+};
 pub use self::matrix_factorization::{
     cur_decomposition, interpolative_decomposition, nmf, rank_revealing_qr, utv_decomposition,
 };
@@ -210,6 +223,9 @@ pub use self::stats::*;
 pub use self::structured::{
     structured_to_operator, CirculantMatrix, HankelMatrix, StructuredMatrix, ToeplitzMatrix,
 };
+pub use self::symmetric_indefinite::{
+    ldl, ldl_inertia, ldl_solve, Inertia, LDLDecomposition, LDLPivoting,
+};
 #[cfg(feature = "tensor_contraction")]
 pub use self::tensor_contraction::{batch_matmul, contract, einsum, hosvd};
 
@@ -250,6 +266,11 @@ pub mod prelude {
         max_pool2d_backward,
     };
     pub use super::decomposition::{cholesky, lu, qr, svd};
+    pub use super::symmetric_indefinite::{ldl, ldl_inertia, ldl_solve, LDLPivoting};
+    pub use super::decomposition_update::{
+        cholesky_downdate, cholesky_downdate_rank_k, cholesky_update, cholesky_update_rank_k,
+        qr_delete, qr_insert, qr_update,
+    };
     pub use super::eigen::{eig, eigh, eigvals, eigvalsh, power_iteration};
     pub use super::eigen_specialized::banded::{banded_eigh, banded_eigvalsh};
     pub use super::eigen_specialized::sparse::{largest_k_eigh, smallest_k_eigh};
@@ -280,7 +301,10 @@ pub mod prelude {
     pub use super::matrix_factorization::{
         cur_decomposition, interpolative_decomposition, nmf, rank_revealing_qr, utv_decomposition,
     };
-    pub use super::matrix_functions::{expm, logm, matrix_power, sqrtm};
+    pub use super::matrix_functions::{
+        coshm, cosm, expm, expm_cond, expm_frechet, fractional_matrix_power, funm, logm,
+        logm_cond, logm_frechet, matrix_power, sinhm, sinm, sqrtm, tanhm, tanm,
+    };
     pub use super::matrixfree::{
         block_diagonal_operator, conjugate_gradient as matrix_free_conjugate_gradient,
         diagonal_operator, gmres as matrix_free_gmres, jacobi_preconditioner,
@@ -0,0 +1,849 @@
+//! Solvers for linear and quadratic matrix equations
+//!
+//! This module provides solvers for the equations that appear in control
+//! theory and model reduction:
+//!
+//! * Sylvester equation `AX + XB = Q`
+//! * Continuous Lyapunov equation `AX + XAᵀ = Q`
+//! * Discrete Lyapunov (Stein) equation `AXAᵀ - X + Q = 0`
+//! * Continuous algebraic Riccati equation
+//!   `AᵀX + XA - XBR⁻¹BᵀX + Q = 0`
+//! * Discrete algebraic Riccati equation
+//!   `AᵀXA - X - AᵀXB(R + BᵀXB)⁻¹BᵀXA + Q = 0`
+//!
+//! The linear equations are solved with the Bartels–Stewart algorithm on the
+//! complex Schur forms of the coefficients. The Riccati equations are solved
+//! with the Schur-vector method: the solution is built from the stable
+//! invariant subspace of the associated Hamiltonian matrix, or of the Cayley
+//! transform of the symplectic pencil in the discrete case. The residual of
+//! any computed solution can be inspected with [`matrix_equation_residual`].
+
+use ndarray::{s, Array2, ArrayView2};
+use num_complex::Complex;
+use num_traits::{Float, NumAssign, One, Zero};
+use std::fmt::Debug;
+use std::iter::Sum;
+
+use crate::complex::decompositions::{complex_schur, swap_schur};
+use crate::complex::{complex_inverse, hermitian_transpose};
+use crate::diagnostics::{analyze_matrix_equation, MatrixEquationDiagnostics};
+use crate::error::{LinalgError, LinalgResult};
+use crate::solve::solve_multiple;
+
+/// Solve the Sylvester equation `AX + XB = Q`
+///
+/// Uses the Bartels–Stewart algorithm: both coefficients are reduced to
+/// complex Schur form and the transformed equation is solved column by column
+/// by back substitution. A unique solution exists if and only if `A` and `-B`
+/// have no common eigenvalue.
+///
+/// # Arguments
+///
+/// * `a` - Square matrix of size m×m
+/// * `b` - Square matrix of size n×n
+/// * `q` - Right-hand side of size m×n
+///
+/// # Returns
+///
+/// * Solution `X` of size m×n
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::solve_sylvester;
+///
+/// let a = array![[-3.0_f64, -2.0, 0.0], [-1.0, -1.0, 3.0], [3.0, -5.0, -1.0]];
+/// let b = array![[1.0_f64]];
+/// let q = array![[1.0_f64], [2.0], [3.0]];
+/// let x = solve_sylvester(&a.view(), &b.view(), &q.view()).unwrap();
+///
+/// let residual = a.dot(&x) + x.dot(&b) - &q;
+/// assert!(residual.iter().all(|r| r.abs() < 1e-10));
+/// ```
+pub fn solve_sylvester<F>(
+    a: &ArrayView2<F>,
+    b: &ArrayView2<F>,
+    q: &ArrayView2<F>,
+) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    check_square(a, "A")?;
+    check_square(b, "B")?;
+    if q.dim() != (a.nrows(), b.nrows()) {
+        return Err(LinalgError::ShapeError(format!(
+            "Right-hand side must have shape {:?}, got {:?}",
+            (a.nrows(), b.nrows()),
+            q.shape()
+        )));
+    }
+
+    let (ua, ta) = complex_schur(&to_complex(a).view())?;
+    let (ub, tb) = complex_schur(&to_complex(b).view())?;
+    let f = hermitian_transpose(&ua.view()).dot(&to_complex(q)).dot(&ub);
+    let y = triangular_sylvester(&ta, &tb, &f)?;
+    let x = ua.dot(&y).dot(&hermitian_transpose(&ub.view()));
+    Ok(x.mapv(|z| z.re))
+}
+
+/// Solve the continuous Lyapunov equation `AX + XAᵀ = Q`
+///
+/// A unique solution exists if and only if no two eigenvalues of `A` add up
+/// to zero, which holds in particular for stable `A`. The solution is
+/// symmetric whenever `Q` is.
+///
+/// # Arguments
+///
+/// * `a` - Square matrix
+/// * `q` - Right-hand side of the same size
+///
+/// # Returns
+///
+/// * Solution `X`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::solve_continuous_lyapunov;
+///
+/// let a = array![[-1.0_f64, 0.5], [0.0, -2.0]];
+/// let q = array![[-1.0_f64, 0.0], [0.0, -1.0]];
+/// let x = solve_continuous_lyapunov(&a.view(), &q.view()).unwrap();
+///
+/// let residual = a.dot(&x) + x.dot(&a.t()) - &q;
+/// assert!(residual.iter().all(|r| r.abs() < 1e-12));
+/// assert!((x[[0, 1]] - x[[1, 0]]).abs() < 1e-12);
+/// ```
+pub fn solve_continuous_lyapunov<F>(a: &ArrayView2<F>, q: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    solve_sylvester(a, &a.t(), q)
+}
+
+/// Solve the discrete Lyapunov (Stein) equation `AXAᵀ - X + Q = 0`
+///
+/// With `A = UTUᴴ` in complex Schur form the transformed equation
+/// `TYTᴴ - Y = -UᴴQU` is solved column by column from the last one. A unique
+/// solution exists if and only if `λᵢλⱼ ≠ 1` for all eigenvalues of `A`,
+/// which holds in particular when all of them lie inside the unit circle.
+///
+/// # Arguments
+///
+/// * `a` - Square matrix
+/// * `q` - Square matrix of the same size
+///
+/// # Returns
+///
+/// * Solution `X`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::solve_discrete_lyapunov;
+///
+/// let a = array![[0.5_f64, 0.1], [-0.2, 0.3]];
+/// let q = array![[1.0_f64, 0.0], [0.0, 2.0]];
+/// let x = solve_discrete_lyapunov(&a.view(), &q.view()).unwrap();
+///
+/// let residual = a.dot(&x).dot(&a.t()) - &x + &q;
+/// assert!(residual.iter().all(|r| r.abs() < 1e-12));
+/// ```
+pub fn solve_discrete_lyapunov<F>(a: &ArrayView2<F>, q: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    check_square(a, "A")?;
+    if q.dim() != a.dim() {
+        return Err(LinalgError::ShapeError(format!(
+            "Q must have the same shape as A {:?}, got {:?}",
+            a.shape(),
+            q.shape()
+        )));
+    }
+
+    let n = a.nrows();
+    let (u, t) = complex_schur(&to_complex(a).view())?;
+    let uh = hermitian_transpose(&u.view());
+    let f = uh.dot(&to_complex(q)).dot(&u);
+    let tolerance = singularity_tolerance(&t, &t);
+
+    let mut y = Array2::<Complex<F>>::zeros((n, n));
+    for j in (0..n).rev() {
+        // w = Σ_{l>j} conj(t_jl) y_l, rhs = -f_j - T w
+        let mut w = vec![Complex::zero(); n];
+        for l in j + 1..n {
+            let c = t[[j, l]].conj();
+            for (i, wi) in w.iter_mut().enumerate() {
+                *wi += c * y[[i, l]];
+            }
+        }
+        let rhs: Vec<Complex<F>> = (0..n)
+            .map(|i| {
+                let tw = (i..n).fold(Complex::zero(), |s, k| s + t[[i, k]] * w[k]);
+                -f[[i, j]] - tw
+            })
+            .collect();
+
+        // Back substitution with the upper triangular conj(t_jj) T - I
+        let c = t[[j, j]].conj();
+        for i in (0..n).rev() {
+            let mut s = rhs[i];
+            for k in i + 1..n {
+                s -= c * t[[i, k]] * y[[k, j]];
+            }
+            let d = c * t[[i, i]] - Complex::one();
+            if d.norm() <= tolerance {
+                return Err(LinalgError::SingularMatrixError(
+                    "Discrete Lyapunov equation is singular: A has eigenvalues with λᵢλⱼ = 1"
+                        .to_string(),
+                ));
+            }
+            y[[i, j]] = s / d;
+        }
+    }
+
+    let x = u.dot(&y).dot(&uh);
+    Ok(x.mapv(|z| z.re))
+}
+
+/// Solve the continuous algebraic Riccati equation
+/// `AᵀX + XA - XBR⁻¹BᵀX + Q = 0`
+///
+/// Computes the stabilizing solution with the Schur-vector method. The
+/// Hamiltonian matrix
+///
+/// ```text
+/// H = [  A  -BR⁻¹Bᵀ ]
+///     [ -Q     -Aᵀ  ]
+/// ```
+///
+/// is reduced to ordered Schur form with the eigenvalues in the open left
+/// half-plane first. If `[U₁; U₂]` spans the corresponding invariant subspace,
+/// the solution is `X = U₂U₁⁻¹`.
+///
+/// # Arguments
+///
+/// * `a` - State matrix of size n×n
+/// * `b` - Input matrix of size n×m
+/// * `q` - Symmetric state weight of size n×n
+/// * `r` - Symmetric nonsingular input weight of size m×m
+/// * `balanced` - Whether to balance the Hamiltonian matrix by a diagonal
+///   similarity before the Schur decomposition, which improves accuracy for
+///   badly scaled problems
+///
+/// # Returns
+///
+/// * Symmetric stabilizing solution `X`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::solve_continuous_are;
+///
+/// let a = array![[4.0_f64, 3.0], [-4.5, -3.5]];
+/// let b = array![[1.0_f64], [-1.0]];
+/// let q = array![[9.0_f64, 6.0], [6.0, 4.0]];
+/// let r = array![[1.0_f64]];
+/// let x = solve_continuous_are(&a.view(), &b.view(), &q.view(), &r.view(), true).unwrap();
+///
+/// let residual = a.t().dot(&x) + x.dot(&a) - x.dot(&b).dot(&b.t()).dot(&x) + &q;
+/// assert!(residual.iter().all(|r| r.abs() < 1e-8));
+/// ```
+pub fn solve_continuous_are<F>(
+    a: &ArrayView2<F>,
+    b: &ArrayView2<F>,
+    q: &ArrayView2<F>,
+    r: &ArrayView2<F>,
+    balanced: bool,
+) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    let n = a.nrows();
+    let g = check_riccati_arguments(a, b, q, r)?;
+
+    let mut h = Array2::zeros((2 * n, 2 * n));
+    h.slice_mut(s![..n, ..n]).assign(a);
+    h.slice_mut(s![..n, n..]).assign(&g.mapv(|v| -v));
+    h.slice_mut(s![n.., ..n]).assign(&q.mapv(|v| -v));
+    h.slice_mut(s![n.., n..]).assign(&a.t().mapv(|v| -v));
+
+    let x = stable_subspace_solution(h, balanced)?;
+    Ok(x)
+}
+
+/// Solve the discrete algebraic Riccati equation
+/// `AᵀXA - X - AᵀXB(R + BᵀXB)⁻¹BᵀXA + Q = 0`
+///
+/// Computes the stabilizing solution from the deflating subspace of the
+/// symplectic pencil
+///
+/// ```text
+/// [  A  0 ] - λ [ I  BR⁻¹Bᵀ ]
+/// [ -Q  I ]     [ 0     Aᵀ  ]
+/// ```
+///
+/// that belongs to the eigenvalues inside the unit circle. The Cayley
+/// transform `μ = (λ - 1)/(λ + 1)` maps them to the open left half-plane, so
+/// the subspace is found with the same ordered Schur decomposition as in
+/// [`solve_continuous_are`]. `A` does not need to be invertible, but the
+/// pencil must not have the eigenvalue `-1`.
+///
+/// # Arguments
+///
+/// * `a` - State matrix of size n×n
+/// * `b` - Input matrix of size n×m
+/// * `q` - Symmetric state weight of size n×n
+/// * `r` - Symmetric nonsingular input weight of size m×m
+/// * `balanced` - Whether to balance the transformed matrix by a diagonal
+///   similarity before the Schur decomposition
+///
+/// # Returns
+///
+/// * Symmetric stabilizing solution `X`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::solve_discrete_are;
+///
+/// // Scalar equation x = x - x²/(1 + x) + 1 with solution (1 + √5)/2
+/// let one = array![[1.0_f64]];
+/// let x = solve_discrete_are(&one.view(), &one.view(), &one.view(), &one.view(), false).unwrap();
+/// assert!((x[[0, 0]] - (1.0 + 5.0_f64.sqrt()) / 2.0).abs() < 1e-12);
+/// ```
+pub fn solve_discrete_are<F>(
+    a: &ArrayView2<F>,
+    b: &ArrayView2<F>,
+    q: &ArrayView2<F>,
+    r: &ArrayView2<F>,
+    balanced: bool,
+) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    let n = a.nrows();
+    let g = check_riccati_arguments(a, b, q, r)?;
+
+    // M + L and M - L for the pencil M - λL
+    let mut sum = Array2::zeros((2 * n, 2 * n));
+    let mut difference = Array2::zeros((2 * n, 2 * n));
+    for i in 0..n {
+        for j in 0..n {
+            let identity = if i == j { F::one() } else { F::zero() };
+            sum[[i, j]] = a[[i, j]] + identity;
+            difference[[i, j]] = a[[i, j]] - identity;
+            sum[[i, n + j]] = g[[i, j]];
+            difference[[i, n + j]] = -g[[i, j]];
+            sum[[n + i, j]] = -q[[i, j]];
+            difference[[n + i, j]] = -q[[i, j]];
+            sum[[n + i, n + j]] = identity + a[[j, i]];
+            difference[[n + i, n + j]] = identity - a[[j, i]];
+        }
+    }
+    let cayley = match solve_multiple(&sum.view(), &difference.view()) {
+        Err(LinalgError::SingularMatrixError(_)) => {
+            return Err(LinalgError::SingularMatrixError(
+                "The symplectic pencil of the discrete Riccati equation has the eigenvalue -1"
+                    .to_string(),
+            ))
+        }
+        other => other?,
+    };
+
+    stable_subspace_solution(cayley, balanced)
+}
+
+/// A matrix equation together with its coefficients, used to evaluate the
+/// residual of a computed solution
+#[derive(Debug, Clone, Copy)]
+pub enum MatrixEquation<'a, F> {
+    /// `AX + XB = Q`
+    Sylvester {
+        a: ArrayView2<'a, F>,
+        b: ArrayView2<'a, F>,
+        q: ArrayView2<'a, F>,
+    },
+    /// `AX + XAᵀ = Q`
+    ContinuousLyapunov {
+        a: ArrayView2<'a, F>,
+        q: ArrayView2<'a, F>,
+    },
+    /// `AXAᵀ - X + Q = 0`
+    DiscreteLyapunov {
+        a: ArrayView2<'a, F>,
+        q: ArrayView2<'a, F>,
+    },
+    /// `AᵀX + XA - XBR⁻¹BᵀX + Q = 0`
+    ContinuousRiccati {
+        a: ArrayView2<'a, F>,
+        b: ArrayView2<'a, F>,
+        q: ArrayView2<'a, F>,
+        r: ArrayView2<'a, F>,
+    },
+    /// `AᵀXA - X - AᵀXB(R + BᵀXB)⁻¹BᵀXA + Q = 0`
+    DiscreteRiccati {
+        a: ArrayView2<'a, F>,
+        b: ArrayView2<'a, F>,
+        q: ArrayView2<'a, F>,
+        r: ArrayView2<'a, F>,
+    },
+}
+
+/// Evaluate the residual of a solution of a matrix equation
+///
+/// The relative residual divides the Frobenius norm of the residual by the
+/// sum of the norms of the individual terms of the equation, so that values
+/// near machine precision indicate a backward stable solution.
+///
+/// # Arguments
+///
+/// * `equation` - The equation and its coefficients
+/// * `x` - Computed solution
+///
+/// # Returns
+///
+/// * Residual diagnostics, with suggestions when the residual is large
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{matrix_equation_residual, solve_sylvester, MatrixEquation};
+///
+/// let a = array![[1.0_f64, 2.0], [0.0, 3.0]];
+/// let b = array![[4.0_f64, 0.0], [1.0, 5.0]];
+/// let q = array![[1.0_f64, 0.0], [0.0, 1.0]];
+/// let x = solve_sylvester(&a.view(), &b.view(), &q.view()).unwrap();
+///
+/// let equation = MatrixEquation::Sylvester { a: a.view(), b: b.view(), q: q.view() };
+/// let diagnostics = matrix_equation_residual(&equation, &x.view()).unwrap();
+/// assert!(diagnostics.relative_residual < 1e-14);
+/// ```
+pub fn matrix_equation_residual<F>(
+    equation: &MatrixEquation<'_, F>,
+    x: &ArrayView2<F>,
+) -> LinalgResult<MatrixEquationDiagnostics<F>>
+where
+    F: Float + NumAssign + Sum + Debug + std::fmt::Display + 'static,
+{
+    let norm = |m: &Array2<F>| m.iter().map(|&v| v * v).sum::<F>().sqrt();
+    let expect_shape = |name: &str, m: &ArrayView2<F>, shape: (usize, usize)| {
+        if m.dim() == shape {
+            Ok(())
+        } else {
+            Err(LinalgError::ShapeError(format!(
+                "{} has shape {:?}, expected {:?}",
+                name,
+                m.shape(),
+                shape
+            )))
+        }
+    };
+
+    let (name, terms): (&str, Vec<(F, Array2<F>)>) = match equation {
+        MatrixEquation::Sylvester { a, b, q } => {
+            expect_shape("X", x, q.dim())?;
+            (
+                "Sylvester equation AX + XB = Q",
+                vec![
+                    (F::one(), a.dot(x)),
+                    (F::one(), x.dot(b)),
+                    (-F::one(), q.to_owned()),
+                ],
+            )
+        }
+        MatrixEquation::ContinuousLyapunov { a, q } => {
+            expect_shape("X", x, q.dim())?;
+            (
+                "continuous Lyapunov equation AX + XAᵀ = Q",
+                vec![
+                    (F::one(), a.dot(x)),
+                    (F::one(), x.dot(&a.t())),
+                    (-F::one(), q.to_owned()),
+                ],
+            )
+        }
+        MatrixEquation::DiscreteLyapunov { a, q } => {
+            expect_shape("X", x, q.dim())?;
+            (
+                "discrete Lyapunov equation AXAᵀ - X + Q = 0",
+                vec![
+                    (F::one(), a.dot(x).dot(&a.t())),
+                    (-F::one(), x.to_owned()),
+                    (F::one(), q.to_owned()),
+                ],
+            )
+        }
+        MatrixEquation::ContinuousRiccati { a, b, q, r } => {
+            expect_shape("X", x, a.dim())?;
+            let g = check_riccati_arguments(a, b, q, r)?;
+            (
+                "continuous Riccati equation AᵀX + XA - XBR⁻¹BᵀX + Q = 0",
+                vec![
+                    (F::one(), a.t().dot(x)),
+                    (F::one(), x.dot(a)),
+                    (-F::one(), x.dot(&g).dot(x)),
+                    (F::one(), q.to_owned()),
+                ],
+            )
+        }
+        MatrixEquation::DiscreteRiccati { a, b, q, r } => {
+            expect_shape("X", x, a.dim())?;
+            check_riccati_arguments(a, b, q, r)?;
+            let bxa = b.t().dot(x).dot(a);
+            let gain = solve_multiple(&(r + &b.t().dot(x).dot(b)).view(), &bxa.view())?;
+            (
+                "discrete Riccati equation AᵀXA - X - AᵀXB(R + BᵀXB)⁻¹BᵀXA + Q = 0",
+                vec![
+                    (F::one(), a.t().dot(x).dot(a)),
+                    (-F::one(), x.to_owned()),
+                    (-F::one(), bxa.t().dot(&gain)),
+                    (F::one(), q.to_owned()),
+                ],
+            )
+        }
+    };
+
+    let mut residual = Array2::zeros(terms[0].1.dim());
+    let mut scale = F::zero();
+    for (sign, term) in &terms {
+        residual.scaled_add(*sign, term);
+        scale += norm(term);
+    }
+    Ok(analyze_matrix_equation(name, &residual.view(), scale, x))
+}
+
+fn check_square<F>(a: &ArrayView2<F>, name: &str) -> LinalgResult<()> {
+    if a.nrows() != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "{} must be square, got shape {:?}",
+            name,
+            a.shape()
+        )));
+    }
+    Ok(())
+}
+
+/// Validate the Riccati coefficients and return `G = BR⁻¹Bᵀ`
+fn check_riccati_arguments<F>(
+    a: &ArrayView2<F>,
+    b: &ArrayView2<F>,
+    q: &ArrayView2<F>,
+    r: &ArrayView2<F>,
+) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + 'static,
+{
+    check_square(a, "A")?;
+    check_square(r, "R")?;
+    let n = a.nrows();
+    if b.nrows() != n || b.ncols() != r.nrows() {
+        return Err(LinalgError::ShapeError(format!(
+            "B must have shape {:?}, got {:?}",
+            (n, r.nrows()),
+            b.shape()
+        )));
+    }
+    if q.dim() != (n, n) {
+        return Err(LinalgError::ShapeError(format!(
+            "Q must have shape {:?}, got {:?}",
+            (n, n),
+            q.shape()
+        )));
+    }
+    let r_inv_bt = solve_multiple(r, &b.t())?;
+    Ok(b.dot(&r_inv_bt))
+}
+
+pub(crate) fn to_complex<F: Float>(a: &ArrayView2<F>) -> Array2<Complex<F>> {
+    a.mapv(|v| Complex::new(v, F::zero()))
+}
+
+/// Threshold below which a diagonal entry of the transformed triangular
+/// system is treated as zero
+fn singularity_tolerance<F: Float>(ta: &Array2<Complex<F>>, tb: &Array2<Complex<F>>) -> F {
+    let scale = ta
+        .iter()
+        .chain(tb.iter())
+        .fold(F::zero(), |m, z| m.max(z.norm()));
+    F::epsilon() * scale.max(F::min_positive_value())
+}
+
+/// Solve `TₐY + YT_b = F` for upper triangular `Tₐ` and `T_b`
+pub(crate) fn triangular_sylvester<F: Float>(
+    ta: &Array2<Complex<F>>,
+    tb: &Array2<Complex<F>>,
+    f: &Array2<Complex<F>>,
+) -> LinalgResult<Array2<Complex<F>>> {
+    let (m, n) = f.dim();
+    let tolerance = singularity_tolerance(ta, tb);
+    let mut y = Array2::<Complex<F>>::zeros((m, n));
+    for k in 0..n {
+        let rhs: Vec<Complex<F>> = (0..m)
+            .map(|i| (0..k).fold(f[[i, k]], |s, j| s - tb[[j, k]] * y[[i, j]]))
+            .collect();
+        for i in (0..m).rev() {
+            let mut s = rhs[i];
+            for l in i + 1..m {
+                s = s - ta[[i, l]] * y[[l, k]];
+            }
+            let d = ta[[i, i]] + tb[[k, k]];
+            if d.norm() <= tolerance {
+                return Err(LinalgError::SingularMatrixError(
+                    "Sylvester equation is singular: A and -B have a common eigenvalue".to_string(),
+                ));
+            }
+            y[[i, k]] = s / d;
+        }
+    }
+    Ok(y)
+}
+
+/// Compute `X = U₂U₁⁻¹` from the invariant subspace `[U₁; U₂]` of the
+/// eigenvalues of `h` in the open left half-plane
+fn stable_subspace_solution<F>(h: Array2<F>, balanced: bool) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    let n = h.nrows() / 2;
+    let (h, scaling) = if balanced {
+        balance(h)
+    } else {
+        let ones = vec![F::one(); h.nrows()];
+        (h, ones)
+    };
+
+    let (mut z, mut t) = complex_schur(&to_complex(&h.view()).view())?;
+    let stable = reorder_schur(&mut t, &mut z, |lambda| lambda.re < F::zero());
+    if stable != n {
+        return Err(LinalgError::ComputationError(format!(
+            "Expected {} eigenvalues in the stable region, found {}; the Riccati equation \
+             has no stabilizing solution or eigenvalues lie on the stability boundary",
+            n, stable
+        )));
+    }
+
+    // Undo the balancing on the basis of the subspace
+    let mut basis = z.slice(s![.., ..n]).to_owned();
+    for (mut row, &d) in basis.rows_mut().into_iter().zip(&scaling) {
+        row.mapv_inplace(|v| v * d);
+    }
+    let u1 = basis.slice(s![..n, ..]).to_owned();
+    let u2 = basis.slice(s![n.., ..]);
+    let u1_inv = complex_inverse(&u1.view()).map_err(|_| {
+        LinalgError::SingularMatrixError(
+            "The stable invariant subspace has a singular upper block; the Riccati equation \
+             has no stabilizing solution"
+                .to_string(),
+        )
+    })?;
+    let x = u2.dot(&u1_inv).mapv(|v| v.re);
+
+    // Remove the rounding-level asymmetry
+    let two = F::from(2.0).unwrap();
+    Ok(Array2::from_shape_fn((n, n), |(i, j)| {
+        (x[[i, j]] + x[[j, i]]) / two
+    }))
+}
+
+/// Move the diagonal entries of a complex Schur form that satisfy `select`
+/// to the top, updating the Schur vectors. Returns the number of selected
+/// entries.
+fn reorder_schur<F: Float>(
+    t: &mut Array2<Complex<F>>,
+    z: &mut Array2<Complex<F>>,
+    select: impl Fn(Complex<F>) -> bool,
+) -> usize {
+    let size = t.nrows();
+    let mut selected = 0;
+    for k in 0..size {
+        if select(t[[k, k]]) {
+            for j in (selected..k).rev() {
+                swap_schur(t, z, j);
+            }
+            selected += 1;
+        }
+    }
+    selected
+}
+
+/// Balance a matrix by a diagonal similarity `D⁻¹HD` with powers of two,
+/// returning the balanced matrix and the diagonal of `D`
+fn balance<F: Float>(mut h: Array2<F>) -> (Array2<F>, Vec<F>) {
+    let n = h.nrows();
+    let radix = F::from(2.0).unwrap();
+    let radix2 = radix * radix;
+    let mut scaling = vec![F::one(); n];
+    let mut converged = false;
+    while !converged {
+        converged = true;
+        for i in 0..n {
+            let mut c = F::zero();
+            let mut r = F::zero();
+            for j in (0..n).filter(|&j| j != i) {
+                c = c + h[[j, i]].abs();
+                r = r + h[[i, j]].abs();
+            }
+            if c == F::zero() || r == F::zero() {
+                continue;
+            }
+            let s = c + r;
+            let mut f = F::one();
+            while c < r / radix {
+                f = f * radix;
+                c = c * radix2;
+            }
+            while c >= r * radix {
+                f = f / radix;
+                c = c / radix2;
+            }
+            if (c + r) / f < F::from(0.95).unwrap() * s {
+                converged = false;
+                scaling[i] = scaling[i] * f;
+                h.column_mut(i).mapv_inplace(|v| v * f);
+                h.row_mut(i).mapv_inplace(|v| v / f);
+            }
+        }
+    }
+    (h, scaling)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+
+    fn assert_solved(equation: MatrixEquation<'_, f64>, x: &Array2<f64>, tol: f64) {
+        let diagnostics = matrix_equation_residual(&equation, &x.view()).unwrap();
+        assert!(diagnostics.relative_residual < tol, "{}", diagnostics);
+    }
+
+    #[test]
+    fn test_solve_sylvester() {
+        // A has complex eigenvalues, B is not triangular
+        let a = array![[1.0, -2.0, 0.5], [3.0, 1.0, 0.0], [0.0, 1.0, 4.0]];
+        let b = array![[2.0, 1.0], [-1.0, 3.0]];
+        let q = array![[1.0, 2.0], [0.0, -1.0], [3.0, 0.5]];
+        let x = solve_sylvester(&a.view(), &b.view(), &q.view()).unwrap();
+        let equation = MatrixEquation::Sylvester {
+            a: a.view(),
+            b: b.view(),
+            q: q.view(),
+        };
+        assert_solved(equation, &x, 1e-13);
+
+        // A and -B share the eigenvalue 1
+        let a = array![[1.0]];
+        let b = array![[-1.0]];
+        assert!(solve_sylvester(&a.view(), &b.view(), &a.view()).is_err());
+        assert!(solve_sylvester(&a.view(), &b.view(), &q.view()).is_err());
+    }
+
+    #[test]
+    fn test_solve_lyapunov() {
+        let a = array![[-2.0, 1.0, 0.0], [-1.0, -3.0, 1.0], [0.5, 0.0, -1.0]];
+        let q = array![[2.0, 1.0, 0.0], [1.0, 3.0, 0.5], [0.0, 0.5, 1.0]];
+
+        let x = solve_continuous_lyapunov(&a.view(), &q.view()).unwrap();
+        let equation = MatrixEquation::ContinuousLyapunov {
+            a: a.view(),
+            q: q.view(),
+        };
+        assert_solved(equation, &x, 1e-13);
+        assert_relative_eq!(x, x.t(), epsilon = 1e-12);
+
+        let a = a.mapv(|v| v / 4.0);
+        let x = solve_discrete_lyapunov(&a.view(), &q.view()).unwrap();
+        let equation = MatrixEquation::DiscreteLyapunov {
+            a: a.view(),
+            q: q.view(),
+        };
+        assert_solved(equation, &x, 1e-13);
+        assert_relative_eq!(x, x.t(), epsilon = 1e-12);
+
+        // Eigenvalues 2 and 1/2 give λᵢλⱼ = 1
+        let a = array![[2.0, 0.0], [0.0, 0.5]];
+        let q = Array2::eye(2);
+        assert!(solve_discrete_lyapunov(&a.view(), &q.view()).is_err());
+    }
+
+    #[test]
+    fn test_solve_continuous_are() {
+        // Scalar equation 2x - x² + 1 = 0 with stabilizing root 1 + √2
+        let one = array![[1.0]];
+        let x = solve_continuous_are(&one.view(), &one.view(), &one.view(), &one.view(), false)
+            .unwrap();
+        assert_relative_eq!(x[[0, 0]], 1.0 + 2.0_f64.sqrt(), epsilon = 1e-12);
+
+        let a = array![[0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [-1.0, 2.0, 0.5]];
+        let b = array![[0.0, 1.0], [0.0, 0.0], [1.0, 0.0]];
+        let q = array![[1e3, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1e-2]];
+        let r = array![[1.0, 0.2], [0.2, 2.0]];
+        for balanced in [false, true] {
+            let x =
+                solve_continuous_are(&a.view(), &b.view(), &q.view(), &r.view(), balanced).unwrap();
+            let equation = MatrixEquation::ContinuousRiccati {
+                a: a.view(),
+                b: b.view(),
+                q: q.view(),
+                r: r.view(),
+            };
+            assert_solved(equation, &x, 1e-10);
+
+            // The closed loop A - BR⁻¹BᵀX is stable: its Lyapunov equation
+            // with negative definite right-hand side has a positive
+            // definite solution
+            let g = check_riccati_arguments(&a.view(), &b.view(), &q.view(), &r.view()).unwrap();
+            let closed = &a - &g.dot(&x);
+            let p = solve_continuous_lyapunov(&closed.view(), &(-Array2::eye(3)).view()).unwrap();
+            assert!(crate::cholesky(&p.view()).is_ok());
+        }
+
+        // An uncontrollable unstable mode admits no stabilizing solution
+        let a = array![[1.0, 0.0], [0.0, -1.0]];
+        let b = array![[0.0], [1.0]];
+        let q = array![[0.0, 0.0], [0.0, 1.0]];
+        assert!(solve_continuous_are(&a.view(), &b.view(), &q.view(), &one.view(), true).is_err());
+    }
+
+    #[test]
+    fn test_solve_discrete_are() {
+        let a = array![[1.1, 0.3, 0.0], [0.0, 0.9, 0.2], [0.1, 0.0, 1.2]];
+        let b = array![[0.0], [0.0], [1.0]];
+        let q = Array2::eye(3);
+        let r = array![[0.5]];
+        for balanced in [false, true] {
+            let x =
+                solve_discrete_are(&a.view(), &b.view(), &q.view(), &r.view(), balanced).unwrap();
+            let equation = MatrixEquation::DiscreteRiccati {
+                a: a.view(),
+                b: b.view(),
+                q: q.view(),
+                r: r.view(),
+            };
+            assert_solved(equation, &x, 1e-10);
+        }
+
+        // Singular A is allowed
+        let a = array![[0.0, 1.0], [0.0, 0.0]];
+        let b = array![[0.0], [1.0]];
+        let q = Array2::eye(2);
+        let r = array![[1.0]];
+        let x = solve_discrete_are(&a.view(), &b.view(), &q.view(), &r.view(), true).unwrap();
+        let equation = MatrixEquation::DiscreteRiccati {
+            a: a.view(),
+            b: b.view(),
+            q: q.view(),
+            r: r.view(),
+        };
+        assert_solved(equation, &x, 1e-12);
+    }
+}
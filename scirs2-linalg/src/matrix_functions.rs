@@ -1,10 +1,16 @@
 //! Matrix functions such as matrix exponential, logarithm, and square root
 
-use ndarray::{Array2, ArrayView2};
-use num_traits::{Float, NumAssign, One};
+use ndarray::{s, Array1, Array2, ArrayView2};
+use num_complex::Complex;
+use num_traits::{Float, NumAssign, One, Zero};
+use std::fmt::Debug;
 use std::iter::Sum;
+use std::ops::Range;
 
+use crate::complex::decompositions::{complex_schur, swap_schur};
+use crate::complex::hermitian_transpose;
 use crate::error::{LinalgError, LinalgResult};
+use crate::matrix_equations::{to_complex, triangular_sylvester};
 use crate::norm::matrix_norm;
 use crate::solve::solve_multiple;
 
@@ -672,6 +678,826 @@ where
     ))
 }
 
+/// Eigenvalues closer than this are evaluated together in one diagonal block
+/// of the Schur–Parlett algorithm
+const CLUSTER_SEPARATION: f64 = 0.1;
+
+/// Largest number of quadrature nodes for the Cauchy integral of a block
+const MAX_QUADRATURE_NODES: usize = 1024;
+
+/// Evaluate a general matrix function with the Schur–Parlett algorithm.
+///
+/// The matrix is reduced to the complex Schur form `A = QTQᴴ` and `f(T)` is
+/// computed with the block Parlett recurrence. Eigenvalues closer than 0.1 are
+/// grouped into one diagonal block, which is evaluated from the Cauchy integral
+/// of `f` over a circle enclosing the block's eigenvalues, so repeated and
+/// defective eigenvalues need no derivatives of `f`.
+///
+/// `f` must be analytic on the spectrum of `A` and on a disk of radius at least
+/// 0.05 around every cluster of eigenvalues. The result is returned as a real
+/// matrix, so `f` must map complex conjugates to complex conjugates, as every
+/// function with a real Taylor series does.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `f` - Scalar function, evaluated at complex arguments
+///
+/// # Returns
+///
+/// * `f(A)`, or a `DomainError` if `f(A)` is not real
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use num_complex::Complex;
+/// use scirs2_linalg::matrix_functions::funm;
+///
+/// // exp of a Jordan block: exp([[1, 1], [0, 1]]) = e [[1, 1], [0, 1]]
+/// let a = array![[1.0_f64, 1.0], [0.0, 1.0]];
+/// let exp_a = funm(&a.view(), |z: Complex<f64>| z.exp()).unwrap();
+/// let e = 1.0_f64.exp();
+/// assert!((exp_a[[0, 0]] - e).abs() < 1e-12);
+/// assert!((exp_a[[0, 1]] - e).abs() < 1e-12);
+/// assert!(exp_a[[1, 0]].abs() < 1e-12);
+/// ```
+pub fn funm<F, G>(a: &ArrayView2<F>, f: G) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+    G: Fn(Complex<F>) -> Complex<F>,
+{
+    apply_function(a, f, "matrix function")
+}
+
+/// Compute the matrix cosine.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+///
+/// # Returns
+///
+/// * `cos(A)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::{cosm, sinm};
+///
+/// let a = array![[1.0_f64, 2.0], [-0.5, 0.3]];
+/// let (c, s) = (cosm(&a.view()).unwrap(), sinm(&a.view()).unwrap());
+/// // cos²(A) + sin²(A) = I
+/// let identity = c.dot(&c) + s.dot(&s);
+/// assert!((identity[[0, 0]] - 1.0).abs() < 1e-12);
+/// assert!(identity[[0, 1]].abs() < 1e-12);
+/// ```
+pub fn cosm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    apply_function(a, |z| z.cos(), "cosine")
+}
+
+/// Compute the matrix sine.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+///
+/// # Returns
+///
+/// * `sin(A)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::sinm;
+///
+/// let a = array![[0.0_f64, 1.0], [0.0, 0.0]];
+/// // A is nilpotent, so sin(A) = A
+/// let s = sinm(&a.view()).unwrap();
+/// assert!((s[[0, 1]] - 1.0).abs() < 1e-12);
+/// assert!(s[[0, 0]].abs() < 1e-12);
+/// ```
+pub fn sinm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    apply_function(a, |z| z.sin(), "sine")
+}
+
+/// Compute the matrix tangent `sin(A) cos(A)⁻¹`.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+///
+/// # Returns
+///
+/// * `tan(A)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::tanm;
+///
+/// let a = array![[0.5_f64, 0.0], [0.0, -0.25]];
+/// let t = tanm(&a.view()).unwrap();
+/// assert!((t[[0, 0]] - 0.5_f64.tan()).abs() < 1e-12);
+/// assert!((t[[1, 1]] + 0.25_f64.tan()).abs() < 1e-12);
+/// ```
+pub fn tanm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    apply_function(a, |z| z.tan(), "tangent")
+}
+
+/// Compute the matrix hyperbolic cosine.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+///
+/// # Returns
+///
+/// * `cosh(A)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::{coshm, sinhm};
+///
+/// let a = array![[0.2_f64, 1.0], [0.4, -0.7]];
+/// let (c, s) = (coshm(&a.view()).unwrap(), sinhm(&a.view()).unwrap());
+/// // cosh²(A) - sinh²(A) = I
+/// let identity = c.dot(&c) - s.dot(&s);
+/// assert!((identity[[1, 1]] - 1.0).abs() < 1e-12);
+/// assert!(identity[[1, 0]].abs() < 1e-12);
+/// ```
+pub fn coshm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    apply_function(a, |z| z.cosh(), "hyperbolic cosine")
+}
+
+/// Compute the matrix hyperbolic sine.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+///
+/// # Returns
+///
+/// * `sinh(A)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::sinhm;
+///
+/// let a = array![[1.0_f64, 0.0], [0.0, 2.0]];
+/// let s = sinhm(&a.view()).unwrap();
+/// assert!((s[[1, 1]] - 2.0_f64.sinh()).abs() < 1e-12);
+/// ```
+pub fn sinhm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    apply_function(a, |z| z.sinh(), "hyperbolic sine")
+}
+
+/// Compute the matrix hyperbolic tangent `sinh(A) cosh(A)⁻¹`.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+///
+/// # Returns
+///
+/// * `tanh(A)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::tanhm;
+///
+/// let a = array![[3.0_f64, 0.0], [0.0, 0.5]];
+/// let t = tanhm(&a.view()).unwrap();
+/// assert!((t[[0, 0]] - 3.0_f64.tanh()).abs() < 1e-12);
+/// ```
+pub fn tanhm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    apply_function(a, |z| z.tanh(), "hyperbolic tangent")
+}
+
+/// Compute the principal fractional power `Aᵖ` of a matrix.
+///
+/// Integer exponents use binary exponentiation (of the inverse for negative
+/// exponents); other exponents use the Schur–Parlett algorithm with the
+/// principal branch `zᵖ = exp(p log z)`, which is real for real matrices
+/// without eigenvalues on the closed negative real axis.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `p` - Exponent
+///
+/// # Returns
+///
+/// * `Aᵖ`, or a `DomainError` if the principal power is not real
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::fractional_matrix_power;
+///
+/// let a = array![[4.0_f64, 1.0], [0.0, 9.0]];
+/// let root = fractional_matrix_power(&a.view(), 0.5).unwrap();
+/// let square = root.dot(&root);
+/// assert!((square[[0, 1]] - 1.0).abs() < 1e-12);
+/// assert!((root[[1, 1]] - 3.0).abs() < 1e-12);
+/// ```
+pub fn fractional_matrix_power<F>(a: &ArrayView2<F>, p: F) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    check_square(a, "fractional power")?;
+    if p == p.round() && p.abs() <= F::from(i32::MAX).unwrap() {
+        return integer_matrix_power(a, p.to_i32().unwrap());
+    }
+    apply_function(a, |z| z.powf(p), "fractional power")
+}
+
+/// `Aⁿ` by repeated squaring, inverting `A` first when `n` is negative
+fn integer_matrix_power<F>(a: &ArrayView2<F>, n: i32) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + 'static,
+{
+    let mut base = if n < 0 {
+        crate::basic::inv(a)?
+    } else {
+        a.to_owned()
+    };
+    let mut result = Array2::eye(a.nrows());
+    let mut k = n.unsigned_abs();
+    while k > 0 {
+        if k & 1 == 1 {
+            result = result.dot(&base);
+        }
+        k >>= 1;
+        if k > 0 {
+            base = base.dot(&base);
+        }
+    }
+    Ok(result)
+}
+
+/// Compute the matrix exponential and its Fréchet derivative.
+///
+/// The Fréchet derivative `L(A, E)` is the linear term of
+/// `exp(A + E) - exp(A)` in `E`. Both are computed together by the scaling
+/// and squaring algorithm of Al-Mohy and Higham with Padé approximants of
+/// degree 3 to 13.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `e` - Direction of the derivative, with the shape of `a`
+///
+/// # Returns
+///
+/// * Tuple `(exp(A), L(A, E))`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::expm_frechet;
+///
+/// // In the direction of A itself the derivative is A exp(A)
+/// let a = array![[0.0_f64, 1.0], [-2.0, -3.0]];
+/// let (exp_a, deriv) = expm_frechet(&a.view(), &a.view()).unwrap();
+/// let expected = a.dot(&exp_a);
+/// assert!((&deriv - &expected).iter().all(|v| v.abs() < 1e-12));
+/// ```
+pub fn expm_frechet<F>(a: &ArrayView2<F>, e: &ArrayView2<F>) -> LinalgResult<(Array2<F>, Array2<F>)>
+where
+    F: Float + NumAssign + Sum + 'static,
+{
+    check_square(a, "exponential")?;
+    if e.dim() != a.dim() {
+        return Err(LinalgError::ShapeError(format!(
+            "Direction of the Fréchet derivative must have the shape of the matrix {:?}, got {:?}",
+            a.shape(),
+            e.shape()
+        )));
+    }
+
+    // Backward error bounds of the Padé approximants of degree 3, 5, 7, 9, 13
+    let thresholds = [1.08e-2, 2.00e-1, 7.83e-1, 1.78, 4.74];
+    let coefficients: [&[f64]; 5] = [&PADE_3, &PADE_5, &PADE_7, &PADE_9, &PADE_13];
+    let norm = matrix_norm(a, "1")?;
+    let degree = thresholds
+        .iter()
+        .position(|&theta| norm <= F::from(theta).unwrap());
+    let (degree, squarings) = match degree {
+        Some(index) => (index, 0),
+        None => {
+            let ratio = (norm / F::from(thresholds[4]).unwrap()).to_f64().unwrap();
+            (4, ratio.log2().ceil().max(0.0) as i32)
+        }
+    };
+    let scale = F::from(2.0).unwrap().powi(-squarings);
+    let a = a.mapv(|v| v * scale);
+    let e = e.mapv(|v| v * scale);
+
+    let (u, v, lu, lv) = pade_frechet(&a, &e, coefficients[degree]);
+    let denominator = &v - &u;
+    let mut r = solve_multiple(&denominator.view(), &(&u + &v).view())?;
+    let rhs = &lu + &lv + (&lu - &lv).dot(&r);
+    let mut l = solve_multiple(&denominator.view(), &rhs.view())?;
+    for _ in 0..squarings {
+        l = r.dot(&l) + l.dot(&r);
+        r = r.dot(&r);
+    }
+    Ok((r, l))
+}
+
+/// Compute the relative condition number of the matrix exponential.
+///
+/// The condition number in the Frobenius norm is
+/// `κ(A) = ‖L(A)‖ ‖A‖_F / ‖exp(A)‖_F`, where `‖L(A)‖` is the largest singular
+/// value of the Kronecker form of the Fréchet derivative. The Kronecker form
+/// is assembled from `n²` Fréchet derivatives, so the cost is `O(n⁵)`.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+///
+/// # Returns
+///
+/// * Relative condition number of `exp` at `A`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::expm_cond;
+///
+/// // For a normal matrix ‖L(A)‖ = exp(max Re λ)
+/// let a = array![[1.0_f64, 0.0], [0.0, 2.0]];
+/// let kappa = expm_cond(&a.view()).unwrap();
+/// let expected = 2.0_f64.exp() * 5.0_f64.sqrt()
+///     / (1.0_f64.exp().powi(2) + 2.0_f64.exp().powi(2)).sqrt();
+/// assert!((kappa - expected).abs() < 1e-8 * expected);
+/// ```
+pub fn expm_cond<F>(a: &ArrayView2<F>) -> LinalgResult<F>
+where
+    F: Float + NumAssign + Sum + 'static,
+{
+    check_square(a, "exponential")?;
+    let (exp_a, _) = expm_frechet(a, &Array2::zeros(a.dim()).view())?;
+    let kronecker = kronecker_form(a.nrows(), |e| Ok(expm_frechet(a, &e.view())?.1))?;
+    Ok(spectral_norm(&kronecker) * frobenius(&a.view()) / frobenius(&exp_a.view()))
+}
+
+/// Compute the principal matrix logarithm and its Fréchet derivative.
+///
+/// The derivative is read off the upper right block of the logarithm of
+/// `[[A, E], [0, A]]`, which is evaluated with the Schur–Parlett algorithm.
+/// `A` must not have eigenvalues on the closed negative real axis.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `e` - Direction of the derivative, with the shape of `a`
+///
+/// # Returns
+///
+/// * Tuple `(log(A), L(A, E))`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::logm_frechet;
+///
+/// // In the direction of A itself the derivative is the identity
+/// let a = array![[2.0_f64, 1.0], [0.5, 3.0]];
+/// let (_, deriv) = logm_frechet(&a.view(), &a.view()).unwrap();
+/// assert!((deriv[[0, 0]] - 1.0).abs() < 1e-10);
+/// assert!(deriv[[0, 1]].abs() < 1e-10);
+/// ```
+pub fn logm_frechet<F>(a: &ArrayView2<F>, e: &ArrayView2<F>) -> LinalgResult<(Array2<F>, Array2<F>)>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    check_square(a, "logarithm")?;
+    if e.dim() != a.dim() {
+        return Err(LinalgError::ShapeError(format!(
+            "Direction of the Fréchet derivative must have the shape of the matrix {:?}, got {:?}",
+            a.shape(),
+            e.shape()
+        )));
+    }
+    let n = a.nrows();
+    let mut block = Array2::<F>::zeros((2 * n, 2 * n));
+    block.slice_mut(s![..n, ..n]).assign(a);
+    block.slice_mut(s![n.., n..]).assign(a);
+    block.slice_mut(s![..n, n..]).assign(e);
+    let log_block = schur_parlett(&to_complex(&block.view()), &|z: Complex<F>| z.ln())?;
+    let log_block = real_matrix(log_block, "logarithm")?;
+    Ok((
+        log_block.slice(s![..n, ..n]).to_owned(),
+        log_block.slice(s![..n, n..]).to_owned(),
+    ))
+}
+
+/// Compute the relative condition number of the principal matrix logarithm.
+///
+/// The condition number in the Frobenius norm is
+/// `κ(A) = ‖L(A)‖ ‖A‖_F / ‖log(A)‖_F`, with `‖L(A)‖` as in [`expm_cond`]. It is
+/// infinite for `A = I`, where the logarithm vanishes.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+///
+/// # Returns
+///
+/// * Relative condition number of `log` at `A`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::logm_cond;
+///
+/// // For a normal matrix ‖L(A)‖ = 1 / min |λ|
+/// let a = array![[1.0_f64, 0.0], [0.0, 2.0]];
+/// let kappa = logm_cond(&a.view()).unwrap();
+/// let expected = 5.0_f64.sqrt() / 2.0_f64.ln();
+/// assert!((kappa - expected).abs() < 1e-8 * expected);
+/// ```
+pub fn logm_cond<F>(a: &ArrayView2<F>) -> LinalgResult<F>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    check_square(a, "logarithm")?;
+    let log_a = apply_function(a, |z| z.ln(), "logarithm")?;
+    let kronecker = kronecker_form(a.nrows(), |e| Ok(logm_frechet(a, &e.view())?.1))?;
+    Ok(spectral_norm(&kronecker) * frobenius(&a.view()) / frobenius(&log_a.view()))
+}
+
+const PADE_3: [f64; 4] = [120.0, 60.0, 12.0, 1.0];
+const PADE_5: [f64; 6] = [30240.0, 15120.0, 3360.0, 420.0, 30.0, 1.0];
+const PADE_7: [f64; 8] = [
+    17297280.0, 8648640.0, 1995840.0, 277200.0, 25200.0, 1512.0, 56.0, 1.0,
+];
+const PADE_9: [f64; 10] = [
+    17643225600.0,
+    8821612800.0,
+    2075673600.0,
+    302702400.0,
+    30270240.0,
+    2162160.0,
+    110880.0,
+    3960.0,
+    90.0,
+    1.0,
+];
+const PADE_13: [f64; 14] = [
+    64764752532480000.0,
+    32382376266240000.0,
+    7771770303897600.0,
+    1187353796428800.0,
+    129060195264000.0,
+    10559470521600.0,
+    670442572800.0,
+    33522128640.0,
+    1323241920.0,
+    40840800.0,
+    960960.0,
+    16380.0,
+    182.0,
+    1.0,
+];
+
+/// Odd and even parts `U`, `V` of the Padé numerator with coefficients `b`,
+/// and their Fréchet derivatives `L_U`, `L_V` in the direction `e`
+#[allow(clippy::type_complexity)]
+fn pade_frechet<F>(
+    a: &Array2<F>,
+    e: &Array2<F>,
+    b: &[f64],
+) -> (Array2<F>, Array2<F>, Array2<F>, Array2<F>)
+where
+    F: Float + NumAssign + 'static,
+{
+    let n = a.nrows();
+    let a2 = a.dot(a);
+    let m2 = a.dot(e) + e.dot(a);
+
+    // Even powers A²ᵏ and their derivatives
+    let mut powers = vec![Array2::eye(n), a2.clone()];
+    let mut derivatives = vec![Array2::zeros((n, n)), m2.clone()];
+    for k in 2..b.len() / 2 {
+        let power = powers[k - 1].dot(&a2);
+        let derivative = derivatives[k - 1].dot(&a2) + powers[k - 1].dot(&m2);
+        powers.push(power);
+        derivatives.push(derivative);
+    }
+
+    let mut odd = Array2::zeros((n, n));
+    let mut even = Array2::zeros((n, n));
+    let mut odd_derivative = Array2::zeros((n, n));
+    let mut even_derivative = Array2::zeros((n, n));
+    for (k, (power, derivative)) in powers.iter().zip(&derivatives).enumerate() {
+        let (b_even, b_odd) = (F::from(b[2 * k]).unwrap(), F::from(b[2 * k + 1]).unwrap());
+        even.scaled_add(b_even, power);
+        odd.scaled_add(b_odd, power);
+        even_derivative.scaled_add(b_even, derivative);
+        odd_derivative.scaled_add(b_odd, derivative);
+    }
+    let u = a.dot(&odd);
+    let lu = a.dot(&odd_derivative) + e.dot(&odd);
+    (u, even, lu, even_derivative)
+}
+
+fn check_square<F>(a: &ArrayView2<F>, what: &str) -> LinalgResult<()> {
+    if a.nrows() != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Matrix must be square to compute {}, got shape {:?}",
+            what,
+            a.shape()
+        )));
+    }
+    Ok(())
+}
+
+fn frobenius<F: Float>(a: &ArrayView2<F>) -> F {
+    a.iter().fold(F::zero(), |s, &v| s + v * v).sqrt()
+}
+
+/// Evaluate `f(A)` with the Schur–Parlett algorithm and check that it is real
+fn apply_function<F, G>(a: &ArrayView2<F>, f: G, what: &str) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+    G: Fn(Complex<F>) -> Complex<F>,
+{
+    check_square(a, what)?;
+    real_matrix(schur_parlett(&to_complex(a), &f)?, what)
+}
+
+/// Real part of a matrix function, rejecting non-finite results and results
+/// whose imaginary part is above rounding level
+fn real_matrix<F: Float + Debug>(x: Array2<Complex<F>>, what: &str) -> LinalgResult<Array2<F>> {
+    if x.iter().any(|z| !z.re.is_finite() || !z.im.is_finite()) {
+        return Err(LinalgError::ComputationError(format!(
+            "The {} is not finite; the function is singular at an eigenvalue",
+            what
+        )));
+    }
+    let scale = x.iter().fold(F::zero(), |m, z| m.max(z.norm()));
+    let imaginary = x.iter().fold(F::zero(), |m, z| m.max(z.im.abs()));
+    if imaginary > F::epsilon().sqrt() * scale {
+        return Err(LinalgError::DomainError(format!(
+            "The {} of this matrix is not real (imaginary part of size {:?})",
+            what, imaginary
+        )));
+    }
+    Ok(x.mapv(|z| z.re))
+}
+
+/// Schur–Parlett evaluation of `f(A)` for a complex matrix
+fn schur_parlett<F, G>(a: &Array2<Complex<F>>, f: &G) -> LinalgResult<Array2<Complex<F>>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+    G: Fn(Complex<F>) -> Complex<F>,
+{
+    let n = a.nrows();
+    if n == 0 {
+        return Ok(Array2::zeros((0, 0)));
+    }
+    let (mut q, mut t) = complex_schur(&a.view())?;
+    let blocks = cluster_schur(&mut t, &mut q);
+
+    let mut fx = Array2::<Complex<F>>::zeros((n, n));
+    for block in &blocks {
+        let value = if block.len() == 1 {
+            Array2::from_elem((1, 1), f(t[[block.start, block.start]]))
+        } else {
+            cauchy_integral(&t.slice(s![block.clone(), block.clone()]).to_owned(), f)?
+        };
+        fx.slice_mut(s![block.clone(), block.clone()])
+            .assign(&value);
+    }
+
+    // Block Parlett recurrence, one superdiagonal of blocks at a time:
+    // T_ii F_ij - F_ij T_jj = F_ii T_ij - T_ij F_jj + Σ_k (F_ik T_kj - T_ik F_kj)
+    for distance in 1..blocks.len() {
+        for i in 0..blocks.len() - distance {
+            let j = i + distance;
+            let (bi, bj) = (blocks[i].clone(), blocks[j].clone());
+            let t_ij = t.slice(s![bi.clone(), bj.clone()]);
+            let mut rhs = fx.slice(s![bi.clone(), bi.clone()]).dot(&t_ij)
+                - t_ij.dot(&fx.slice(s![bj.clone(), bj.clone()]));
+            for bk in &blocks[i + 1..j] {
+                rhs = rhs
+                    + fx.slice(s![bi.clone(), bk.clone()])
+                        .dot(&t.slice(s![bk.clone(), bj.clone()]))
+                    - t.slice(s![bi.clone(), bk.clone()])
+                        .dot(&fx.slice(s![bk.clone(), bj.clone()]));
+            }
+            let t_ii = t.slice(s![bi.clone(), bi.clone()]).to_owned();
+            let t_jj = t.slice(s![bj.clone(), bj.clone()]).mapv(|z| -z);
+            let value = triangular_sylvester(&t_ii, &t_jj, &rhs)?;
+            fx.slice_mut(s![bi, bj]).assign(&value);
+        }
+    }
+
+    Ok(q.dot(&fx).dot(&hermitian_transpose(&q.view())))
+}
+
+/// Group the eigenvalues of a complex Schur form into clusters, joining any
+/// two closer than `CLUSTER_SEPARATION`, reorder the form so that every
+/// cluster is contiguous and return the ranges of the diagonal blocks
+fn cluster_schur<F: Float>(
+    t: &mut Array2<Complex<F>>,
+    q: &mut Array2<Complex<F>>,
+) -> Vec<Range<usize>> {
+    let n = t.nrows();
+    let separation = F::from(CLUSTER_SEPARATION).unwrap();
+    let mut label: Vec<usize> = (0..n).collect();
+    for i in 0..n {
+        for j in 0..i {
+            let (li, lj) = (label[i], label[j]);
+            if li != lj && (t[[i, i]] - t[[j, j]]).norm() <= separation {
+                let (keep, merged) = (li.min(lj), li.max(lj));
+                label
+                    .iter_mut()
+                    .filter(|l| **l == merged)
+                    .for_each(|l| *l = keep);
+            }
+        }
+    }
+
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    while start < n {
+        let mut end = start + 1;
+        for k in start + 1..n {
+            if label[k] == label[start] {
+                for j in (end..k).rev() {
+                    swap_schur(t, q, j);
+                    label.swap(j, j + 1);
+                }
+                end += 1;
+            }
+        }
+        blocks.push(start..end);
+        start = end;
+    }
+    blocks
+}
+
+/// Evaluate `f(T)` for an upper triangular block with clustered eigenvalues by
+/// the trapezoidal rule for the Cauchy integral over a circle around them,
+/// doubling the number of nodes until the result settles
+fn cauchy_integral<F, G>(t: &Array2<Complex<F>>, f: &G) -> LinalgResult<Array2<Complex<F>>>
+where
+    F: Float + NumAssign + Debug + 'static,
+    G: Fn(Complex<F>) -> Complex<F>,
+{
+    let m = t.nrows();
+    let center = (0..m).fold(Complex::<F>::zero(), |s, i| s + t[[i, i]]) / F::from(m).unwrap();
+    let spread = (0..m).fold(F::zero(), |r, i| r.max((t[[i, i]] - center).norm()));
+    let radius = (spread + spread).max(F::from(CLUSTER_SEPARATION / 2.0).unwrap());
+
+    // The quadrature error squares when the nodes double, so a change below
+    // √ε means the finer rule is accurate to rounding level
+    let tolerance = F::epsilon().sqrt();
+    let mut nodes = 32;
+    let (mut previous, _) = cauchy_trapezoid(t, f, center, radius, nodes);
+    while nodes < MAX_QUADRATURE_NODES {
+        nodes *= 2;
+        let (current, f_max) = cauchy_trapezoid(t, f, center, radius, nodes);
+        let change = (&current - &previous)
+            .iter()
+            .fold(F::zero(), |m, z| m.max(z.norm()));
+        let scale = current.iter().fold(f_max, |m, z| m.max(z.norm()));
+        if change <= tolerance * scale {
+            return Ok(current);
+        }
+        previous = current;
+    }
+    Err(LinalgError::ConvergenceError(format!(
+        "Cauchy integral for a cluster of {} eigenvalues around {:?} did not converge; \
+         the function may not be analytic near the cluster",
+        m, center
+    )))
+}
+
+/// Trapezoidal rule with `nodes` points for `(2πi)⁻¹ ∮ f(z) (zI - T)⁻¹ dz`,
+/// also returning the largest `|f(z)|` on the nodes
+fn cauchy_trapezoid<F, G>(
+    t: &Array2<Complex<F>>,
+    f: &G,
+    center: Complex<F>,
+    radius: F,
+    nodes: usize,
+) -> (Array2<Complex<F>>, F)
+where
+    F: Float + NumAssign + 'static,
+    G: Fn(Complex<F>) -> Complex<F>,
+{
+    let m = t.nrows();
+    let mut f_max = F::zero();
+    let step = F::from(2.0 * std::f64::consts::PI / nodes as f64).unwrap();
+    let mut sum = Array2::<Complex<F>>::zeros((m, m));
+    let mut resolvent = Array2::<Complex<F>>::zeros((m, m));
+    for k in 0..nodes {
+        let offset = Complex::from_polar(radius, step * F::from(k).unwrap());
+        let z = center + offset;
+        // Upper triangular (zI - T)⁻¹ by back substitution
+        for j in 0..m {
+            resolvent[[j, j]] = Complex::<F>::one() / (z - t[[j, j]]);
+            for i in (0..j).rev() {
+                let s = (i + 1..=j).fold(Complex::<F>::zero(), |s, l| {
+                    s + t[[i, l]] * resolvent[[l, j]]
+                });
+                resolvent[[i, j]] = s / (z - t[[i, i]]);
+            }
+        }
+        let value = f(z);
+        f_max = f_max.max(value.norm());
+        sum.scaled_add(value * offset, &resolvent);
+    }
+    let count = F::from(nodes).unwrap();
+    (sum.mapv(|v| v / count), f_max)
+}
+
+/// Kronecker form of a linear matrix map on `n × n` matrices: column
+/// `i n + j` holds the image of the unit matrix `E_ij`, flattened by rows
+fn kronecker_form<F, M>(n: usize, map: M) -> LinalgResult<Array2<F>>
+where
+    F: Float,
+    M: Fn(Array2<F>) -> LinalgResult<Array2<F>>,
+{
+    let mut kronecker = Array2::zeros((n * n, n * n));
+    for i in 0..n {
+        for j in 0..n {
+            let mut unit = Array2::zeros((n, n));
+            unit[[i, j]] = F::one();
+            let image = map(unit)?;
+            for (row, &v) in image.iter().enumerate() {
+                kronecker[[row, i * n + j]] = v;
+            }
+        }
+    }
+    Ok(kronecker)
+}
+
+/// Largest singular value by power iteration on `KᵀK`
+fn spectral_norm<F: Float + 'static>(k: &Array2<F>) -> F {
+    let n = k.ncols();
+    // Deterministic start vector with no special structure
+    let mut x = Array1::from_shape_fn(n, |i| {
+        F::from(1.0 + (0.618_033_988_75 * i as f64).fract()).unwrap()
+    });
+    let norm = x.iter().fold(F::zero(), |s, &v| s + v * v).sqrt();
+    x.mapv_inplace(|v| v / norm);
+    let tolerance = F::from(1e-12).unwrap();
+    let mut sigma = F::zero();
+    for _ in 0..1000 {
+        let y = k.dot(&x);
+        let z = k.t().dot(&y);
+        let z_norm = z.iter().fold(F::zero(), |s, &v| s + v * v).sqrt();
+        let estimate = y.iter().fold(F::zero(), |s, &v| s + v * v).sqrt();
+        if z_norm == F::zero() {
+            return F::zero();
+        }
+        x = z.mapv(|v| v / z_norm);
+        if (estimate - sigma).abs() <= tolerance * estimate {
+            return estimate;
+        }
+        sigma = estimate;
+    }
+    sigma
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -843,4 +1669,172 @@ mod tests {
         assert_relative_eq!(a_squared[[1, 0]], 15.0, epsilon = 1e-10);
         assert_relative_eq!(a_squared[[1, 1]], 22.0, epsilon = 1e-10);
     }
+
+    #[test]
+    fn test_funm_clusters_and_defective_blocks() {
+        // Diagonalizable with distinct eigenvalues: agrees with expm
+        let a = array![[1.0, 2.0, 0.0], [-1.0, 0.5, 0.3], [0.2, 0.0, -1.0]];
+        let exp_a = funm(&a.view(), |z: Complex<f64>| z.exp()).unwrap();
+        let (reference, _) = expm_frechet(&a.view(), &Array2::zeros((3, 3)).view()).unwrap();
+        for (x, y) in exp_a.iter().zip(reference.iter()) {
+            assert_relative_eq!(x, y, epsilon = 1e-12);
+        }
+
+        // Defective 3x3 Jordan block with a close eigenvalue in the same cluster
+        let a = array![
+            [2.0, 1.0, 0.0, 0.0],
+            [0.0, 2.0, 1.0, 0.0],
+            [0.0, 0.0, 2.0, 0.0],
+            [0.0, 0.0, 0.5, 2.05]
+        ];
+        let exp_a = funm(&a.view(), |z: Complex<f64>| z.exp()).unwrap();
+        let (reference, _) = expm_frechet(&a.view(), &Array2::zeros((4, 4)).view()).unwrap();
+        for (x, y) in exp_a.iter().zip(reference.iter()) {
+            assert_relative_eq!(x, y, epsilon = 1e-10);
+        }
+        let e2 = 2.0f64.exp();
+        assert_relative_eq!(exp_a[[0, 2]], e2 / 2.0, epsilon = 1e-10);
+
+        // A function that is not real on the spectrum is rejected
+        let a = array![[-1.0, 0.0], [0.0, 2.0]];
+        assert!(matches!(
+            funm(&a.view(), |z: Complex<f64>| z.sqrt()),
+            Err(LinalgError::DomainError(_))
+        ));
+        assert!(cosm(&array![[1.0, 2.0]].view()).is_err());
+    }
+
+    #[test]
+    fn test_trigonometric_and_hyperbolic_functions() {
+        let a = array![[0.3, -1.2, 0.4], [0.8, 0.1, -0.5], [0.0, 0.7, -0.4]];
+        let (c, s) = (cosm(&a.view()).unwrap(), sinm(&a.view()).unwrap());
+        let (ch, sh) = (coshm(&a.view()).unwrap(), sinhm(&a.view()).unwrap());
+        let pythagoras = c.dot(&c) + s.dot(&s);
+        let hyperbolic = ch.dot(&ch) - sh.dot(&sh);
+        let tan_a = tanm(&a.view()).unwrap();
+        let tanh_a = tanhm(&a.view()).unwrap();
+        let tan_c = tan_a.dot(&c);
+        let tanh_ch = tanh_a.dot(&ch);
+        for i in 0..3 {
+            for j in 0..3 {
+                let delta = if i == j { 1.0 } else { 0.0 };
+                assert_relative_eq!(pythagoras[[i, j]], delta, epsilon = 1e-12);
+                assert_relative_eq!(hyperbolic[[i, j]], delta, epsilon = 1e-12);
+                assert_relative_eq!(tan_c[[i, j]], s[[i, j]], epsilon = 1e-12);
+                assert_relative_eq!(tanh_ch[[i, j]], sh[[i, j]], epsilon = 1e-12);
+            }
+        }
+
+        // sin(2A) = 2 sin(A) cos(A)
+        let double = a.mapv(|v| 2.0 * v);
+        let sin_double = sinm(&double.view()).unwrap();
+        let product = s.dot(&c);
+        for (x, y) in sin_double.iter().zip(product.iter()) {
+            assert_relative_eq!(*x, 2.0 * y, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_fractional_matrix_power() {
+        let a = array![[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]];
+        let cube_root = fractional_matrix_power(&a.view(), 1.0 / 3.0).unwrap();
+        let cube = cube_root.dot(&cube_root).dot(&cube_root);
+        for (x, y) in cube.iter().zip(a.iter()) {
+            assert_relative_eq!(x, y, epsilon = 1e-12);
+        }
+
+        // A^0.75 A^-0.75 = I and A^p A^q = A^(p+q)
+        let p = fractional_matrix_power(&a.view(), 0.75).unwrap();
+        let q = fractional_matrix_power(&a.view(), -0.75).unwrap();
+        let identity = p.dot(&q);
+        let inverse = fractional_matrix_power(&a.view(), -1.0).unwrap();
+        let inverse_product = inverse.dot(&a);
+        for i in 0..3 {
+            for j in 0..3 {
+                let delta = if i == j { 1.0 } else { 0.0 };
+                assert_relative_eq!(identity[[i, j]], delta, epsilon = 1e-12);
+                assert_relative_eq!(inverse_product[[i, j]], delta, epsilon = 1e-12);
+            }
+        }
+
+        // Integer powers agree with repeated multiplication
+        let square = fractional_matrix_power(&a.view(), 2.0).unwrap();
+        let cube = fractional_matrix_power(&a.view(), 3.0).unwrap();
+        let inverse_square = fractional_matrix_power(&a.view(), -2.0).unwrap();
+        let expected_square = a.dot(&a);
+        let expected_cube = expected_square.dot(&a);
+        let should_be_identity = inverse_square.dot(&expected_square);
+        for i in 0..3 {
+            for j in 0..3 {
+                let delta = if i == j { 1.0 } else { 0.0 };
+                assert_relative_eq!(square[[i, j]], expected_square[[i, j]], epsilon = 1e-12);
+                assert_relative_eq!(cube[[i, j]], expected_cube[[i, j]], epsilon = 1e-12);
+                assert_relative_eq!(should_be_identity[[i, j]], delta, epsilon = 1e-12);
+            }
+        }
+
+        // Negative eigenvalues have no real principal square root
+        let b = array![[-4.0, 0.0], [0.0, 1.0]];
+        assert!(matches!(
+            fractional_matrix_power(&b.view(), 0.5),
+            Err(LinalgError::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_expm_frechet_against_finite_differences() {
+        // Norms below and above the largest Padé threshold
+        for scale in [0.001, 0.5, 8.0] {
+            let a = array![[1.0, 2.0, -0.5], [0.3, -1.0, 1.0], [0.0, 0.4, 0.2]].mapv(|v| v * scale);
+            let e = array![[0.2, -0.1, 0.0], [0.5, 0.3, -0.2], [0.1, 0.0, 0.4]];
+            let (exp_a, deriv) = expm_frechet(&a.view(), &e.view()).unwrap();
+            let exp_reference = funm(&a.view(), |z: Complex<f64>| z.exp()).unwrap();
+            let h = 1e-6;
+            let zero = Array2::zeros((3, 3));
+            let (plus, _) = expm_frechet(&(&a + &e.mapv(|v| v * h)).view(), &zero.view()).unwrap();
+            let (minus, _) = expm_frechet(&(&a - &e.mapv(|v| v * h)).view(), &zero.view()).unwrap();
+            let norm = deriv.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+            let exp_norm = exp_a.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+            for i in 0..3 {
+                for j in 0..3 {
+                    let fd = (plus[[i, j]] - minus[[i, j]]) / (2.0 * h);
+                    assert!((deriv[[i, j]] - fd).abs() < 1e-7 * norm);
+                    assert!((exp_a[[i, j]] - exp_reference[[i, j]]).abs() < 1e-13 * exp_norm);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_logm_frechet_and_condition_numbers() {
+        let a = array![[3.0, 1.0, 0.2], [0.5, 2.0, 0.1], [0.0, 0.3, 1.5]];
+        let e = array![[0.1, 0.2, 0.0], [-0.3, 0.1, 0.4], [0.2, 0.0, -0.1]];
+        let (log_a, deriv) = logm_frechet(&a.view(), &e.view()).unwrap();
+        let (exp_log, _) = expm_frechet(&log_a.view(), &Array2::zeros((3, 3)).view()).unwrap();
+        for (x, y) in exp_log.iter().zip(a.iter()) {
+            assert_relative_eq!(x, y, epsilon = 1e-12);
+        }
+        // log is the inverse of exp, so L_exp(log A, L_log(A, E)) = E
+        let (_, round_trip) = expm_frechet(&log_a.view(), &deriv.view()).unwrap();
+        for (x, y) in round_trip.iter().zip(e.iter()) {
+            assert_relative_eq!(x, y, epsilon = 1e-10);
+        }
+
+        // Normal matrices: ‖L‖ is the largest divided difference of f
+        let d = array![[1.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 4.0]];
+        let frobenius_d = 21.0f64.sqrt();
+        let exp_norm = (2.0f64.exp() + 4.0f64.exp() + 8.0f64.exp()).sqrt();
+        assert_relative_eq!(
+            expm_cond(&d.view()).unwrap(),
+            4.0f64.exp() * frobenius_d / exp_norm,
+            max_relative = 1e-8
+        );
+        let log_norm = (2.0f64.ln().powi(2) + 4.0f64.ln().powi(2)).sqrt();
+        assert_relative_eq!(
+            logm_cond(&d.view()).unwrap(),
+            frobenius_d / log_norm,
+            max_relative = 1e-8
+        );
+        assert!(logm_cond(&array![[-1.0, 0.0], [0.0, 1.0]].view()).is_err());
+    }
 }
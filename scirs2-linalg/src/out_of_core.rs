@@ -0,0 +1,1128 @@
+//! Out-of-core blocked dense linear algebra
+//!
+//! This module provides tile-based matrix multiplication and Cholesky, LU and
+//! QR factorizations for matrices that are too large to be held in memory.
+//! The algorithms only keep a bounded number of `tile_size × tile_size` tiles
+//! (plus one block row or block column of the current panel) in RAM; all
+//! other data is read and written on demand through the [`TileStorage`]
+//! trait. With the `parallel` feature, independent tile updates within a step
+//! are dispatched through `scirs2_core::parallel`.
+//!
+//! With the `memory_efficient` feature, [`TileStorage`] is implemented for
+//! `scirs2_core::memory_efficient::MemoryMappedArray`, so operands can live in
+//! memory-mapped files. An in-memory implementation for `Array2` is always
+//! available. With the `logging` feature, progress of each operation is
+//! reported through `scirs2_core::logging::ProgressTracker`.
+//!
+//! The factorizations work in place and use LAPACK's storage conventions:
+//!
+//! * [`cholesky`] overwrites the matrix with its lower Cholesky factor `L`
+//! * [`lu`] overwrites the matrix with the unit lower factor `L` (below the
+//!   diagonal) and `U` (on and above it) and returns the row permutation
+//! * [`qr`] overwrites the matrix with `R` (on and above the diagonal) and the
+//!   Householder vectors (below it) and returns the scalar factors `tau`;
+//!   [`qr_q`] forms `Q` explicitly from that representation
+
+use crate::error::{LinalgError, LinalgResult};
+use ndarray::linalg::general_mat_mul;
+use ndarray::{s, Array2, ArrayView2};
+use num_traits::{Float, NumAssign};
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use scirs2_core::parallel;
+
+#[cfg(feature = "memory_efficient")]
+use scirs2_core::memory_efficient::MemoryMappedArray;
+
+/// Storage that can read and write rectangular blocks of a row-major matrix
+///
+/// The out-of-core routines access their operands exclusively through this
+/// trait, so any backing store (memory-mapped files, in-memory arrays, ...)
+/// can be used as long as individual tiles can be transferred to and from RAM.
+pub trait TileStorage<F> {
+    /// Shape of the stored matrix as `(rows, cols)`
+    fn shape(&self) -> LinalgResult<(usize, usize)>;
+
+    /// Copy the block `rows × cols` into memory
+    fn read_tile(&self, rows: Range<usize>, cols: Range<usize>) -> LinalgResult<Array2<F>>;
+
+    /// Write `tile` back with its top-left corner at `(row, col)`
+    fn write_tile(&mut self, row: usize, col: usize, tile: &ArrayView2<F>) -> LinalgResult<()>;
+
+    /// Make all previous writes durable in the backing store
+    fn flush(&mut self) -> LinalgResult<()> {
+        Ok(())
+    }
+}
+
+impl<F: Clone> TileStorage<F> for Array2<F> {
+    fn shape(&self) -> LinalgResult<(usize, usize)> {
+        Ok(self.dim())
+    }
+
+    fn read_tile(&self, rows: Range<usize>, cols: Range<usize>) -> LinalgResult<Array2<F>> {
+        check_block(self.dim(), &rows, &cols)?;
+        Ok(self.slice(s![rows, cols]).to_owned())
+    }
+
+    fn write_tile(&mut self, row: usize, col: usize, tile: &ArrayView2<F>) -> LinalgResult<()> {
+        let (h, w) = tile.dim();
+        check_block(self.dim(), &(row..row + h), &(col..col + w))?;
+        self.slice_mut(s![row..row + h, col..col + w]).assign(tile);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "memory_efficient")]
+impl<F: Clone + Copy + 'static> TileStorage<F> for MemoryMappedArray<F> {
+    fn shape(&self) -> LinalgResult<(usize, usize)> {
+        match self.shape.as_slice() {
+            [rows, cols] => Ok((*rows, *cols)),
+            shape => Err(LinalgError::ShapeError(format!(
+                "Out-of-core operations require a 2-dimensional memory-mapped array, got shape {:?}",
+                shape
+            ))),
+        }
+    }
+
+    fn read_tile(&self, rows: Range<usize>, cols: Range<usize>) -> LinalgResult<Array2<F>> {
+        let shape = TileStorage::shape(self)?;
+        check_block(shape, &rows, &cols)?;
+
+        // Copy row by row: the mapped data need not be aligned for `F`
+        let (h, w) = (rows.len(), cols.len());
+        let mut buffer = Vec::with_capacity(h * w);
+        for i in rows {
+            let start = i * shape.1;
+            buffer.extend(self.read_elements(start + cols.start..start + cols.end)?);
+        }
+
+        Array2::from_shape_vec((h, w), buffer).map_err(|e| LinalgError::ShapeError(e.to_string()))
+    }
+
+    fn write_tile(&mut self, row: usize, col: usize, tile: &ArrayView2<F>) -> LinalgResult<()> {
+        let shape = TileStorage::shape(self)?;
+        let (h, w) = tile.dim();
+        check_block(shape, &(row..row + h), &(col..col + w))?;
+
+        for (r, tile_row) in tile.outer_iter().enumerate() {
+            let start = (row + r) * shape.1 + col;
+            match tile_row.as_slice() {
+                Some(values) => self.write_elements(start, values)?,
+                None => self.write_elements(start, &tile_row.to_vec())?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> LinalgResult<()> {
+        MemoryMappedArray::flush(self)?;
+        Ok(())
+    }
+}
+
+/// Configuration for out-of-core operations
+#[derive(Debug, Clone)]
+pub struct OutOfCoreConfig {
+    /// Edge length of the square tiles held in memory
+    pub tile_size: usize,
+    /// Whether independent tile updates are processed in parallel (requires the
+    /// `parallel` feature)
+    pub parallel: bool,
+    /// Whether progress is reported through the core logging facilities
+    pub progress: bool,
+}
+
+impl Default for OutOfCoreConfig {
+    fn default() -> Self {
+        OutOfCoreConfig {
+            tile_size: 1024,
+            parallel: true,
+            progress: true,
+        }
+    }
+}
+
+impl OutOfCoreConfig {
+    /// Set the edge length of the tiles
+    pub fn with_tile_size(mut self, tile_size: usize) -> Self {
+        self.tile_size = tile_size;
+        self
+    }
+
+    /// Enable or disable parallel tile processing
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Enable or disable progress reporting
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+}
+
+/// Out-of-core matrix multiplication `C = A · B`
+///
+/// `C` is computed one block row at a time; the tiles of a block row are
+/// evaluated in parallel, each accumulating `A[i, k] · B[k, j]` over `k`.
+///
+/// # Arguments
+///
+/// * `a` - Left operand of shape `m × k`
+/// * `b` - Right operand of shape `k × n`
+/// * `c` - Output storage of shape `m × n`, overwritten with the product
+/// * `config` - Tile size, parallelism and progress settings
+///
+/// # Returns
+///
+/// * `Ok(())` once every tile of `C` has been written
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use scirs2_linalg::out_of_core::{matmul, OutOfCoreConfig};
+///
+/// let a = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+/// let b = array![[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
+/// let mut c = Array2::<f64>::zeros((2, 2));
+///
+/// let config = OutOfCoreConfig::default().with_tile_size(2);
+/// matmul(&a, &b, &mut c, &config).unwrap();
+/// assert_eq!(c, a.dot(&b));
+/// ```
+pub fn matmul<F, A, B, C>(a: &A, b: &B, c: &mut C, config: &OutOfCoreConfig) -> LinalgResult<()>
+where
+    F: Float + NumAssign + Send + Sync + 'static,
+    A: TileStorage<F> + Sync,
+    B: TileStorage<F> + Sync,
+    C: TileStorage<F>,
+{
+    check_config(config)?;
+    let (m, k) = a.shape()?;
+    let (k2, n) = b.shape()?;
+    let (cm, cn) = c.shape()?;
+
+    if k != k2 {
+        return Err(LinalgError::DimensionError(format!(
+            "Matrix dimensions don't match: ({}, {}) x ({}, {})",
+            m, k, k2, n
+        )));
+    }
+    if (cm, cn) != (m, n) {
+        return Err(LinalgError::ShapeError(format!(
+            "Output has shape ({}, {}), expected ({}, {})",
+            cm, cn, m, n
+        )));
+    }
+
+    let row_tiles = tiles(m, config.tile_size);
+    let col_tiles = tiles(n, config.tile_size);
+    let inner_tiles = tiles(k, config.tile_size);
+    let mut progress = Progress::new("out-of-core matmul", row_tiles.len(), config.progress);
+
+    for rows in &row_tiles {
+        let products = map_tasks(col_tiles.clone(), config.parallel, |cols| {
+            let mut acc = Array2::zeros((rows.len(), cols.len()));
+            for inner in &inner_tiles {
+                let a_tile = a.read_tile(rows.clone(), inner.clone())?;
+                let b_tile = b.read_tile(inner.clone(), cols.clone())?;
+                general_mat_mul(F::one(), &a_tile, &b_tile, F::one(), &mut acc);
+            }
+            Ok((cols.start, acc))
+        })?;
+
+        for (col, tile) in products {
+            c.write_tile(rows.start, col, &tile.view())?;
+        }
+        progress.step();
+    }
+
+    c.flush()?;
+    progress.finish();
+    Ok(())
+}
+
+/// Out-of-core Cholesky decomposition
+///
+/// Computes the lower triangular factor `L` with `A = L Lᵀ` using a
+/// right-looking tiled algorithm and overwrites `a` with it; the strictly upper
+/// triangle is set to zero and only the lower triangle of the input is read.
+/// At any time one block column of `L` is held in memory.
+///
+/// # Arguments
+///
+/// * `a` - Symmetric positive definite matrix, overwritten with `L`
+/// * `config` - Tile size, parallelism and progress settings
+///
+/// # Returns
+///
+/// * `Ok(())` on success
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::out_of_core::{cholesky, OutOfCoreConfig};
+///
+/// let a = array![[4.0_f64, 2.0, 2.0], [2.0, 5.0, 3.0], [2.0, 3.0, 6.0]];
+/// let mut l = a.clone();
+/// cholesky(&mut l, &OutOfCoreConfig::default().with_tile_size(2)).unwrap();
+///
+/// let reconstructed = l.dot(&l.t());
+/// for (x, y) in reconstructed.iter().zip(a.iter()) {
+///     assert!((x - y).abs() < 1e-12);
+/// }
+/// ```
+pub fn cholesky<F, S>(a: &mut S, config: &OutOfCoreConfig) -> LinalgResult<()>
+where
+    F: Float + NumAssign + Send + Sync + 'static,
+    S: TileStorage<F> + Sync,
+{
+    check_config(config)?;
+    let n = square_dim(a, "Cholesky decomposition")?;
+    let blocks = tiles(n, config.tile_size);
+    let mut progress = Progress::new("out-of-core Cholesky", blocks.len(), config.progress);
+
+    for (k, diag) in blocks.iter().enumerate() {
+        let mut l_kk = a.read_tile(diag.clone(), diag.clone())?;
+        factor_cholesky_block(&mut l_kk, diag.start)?;
+        a.write_tile(diag.start, diag.start, &l_kk.view())?;
+
+        // Zero the tiles above the diagonal in this block row
+        for cols in &blocks[k + 1..] {
+            let zeros = Array2::zeros((diag.len(), cols.len()));
+            a.write_tile(diag.start, cols.start, &zeros.view())?;
+        }
+
+        // Panel below the diagonal: L[i, k] = A[i, k] L[k, k]^{-T}
+        let storage: &S = a;
+        let panel = map_tasks(blocks[k + 1..].to_vec(), config.parallel, |rows| {
+            let mut tile = storage.read_tile(rows, diag.clone())?;
+            solve_lower_transposed_right(&l_kk, &mut tile);
+            Ok(tile)
+        })?;
+        for (rows, tile) in blocks[k + 1..].iter().zip(&panel) {
+            a.write_tile(rows.start, diag.start, &tile.view())?;
+        }
+
+        // Trailing update of the lower triangle: A[i, j] -= L[i, k] L[j, k]^T
+        for (di, rows) in blocks[k + 1..].iter().enumerate() {
+            let storage: &S = a;
+            let updated = map_tasks((0..=di).collect(), config.parallel, |dj| {
+                let cols = &blocks[k + 1 + dj];
+                let mut tile = storage.read_tile(rows.clone(), cols.clone())?;
+                general_mat_mul(-F::one(), &panel[di], &panel[dj].t(), F::one(), &mut tile);
+                Ok((cols.start, tile))
+            })?;
+            for (col, tile) in updated {
+                a.write_tile(rows.start, col, &tile.view())?;
+            }
+        }
+        progress.step();
+    }
+
+    a.flush()?;
+    progress.finish();
+    Ok(())
+}
+
+/// Out-of-core LU decomposition with partial pivoting
+///
+/// Computes `P A = L U` with a right-looking tiled algorithm and overwrites
+/// `a` with `L` (unit diagonal not stored) and `U`. Each block column is
+/// factored as an in-memory panel of `m × tile_size` elements; row
+/// interchanges are then applied to the rest of the matrix before the block
+/// row of `U` and the trailing submatrix are updated tile by tile.
+///
+/// # Arguments
+///
+/// * `a` - Matrix of shape `m × n`, overwritten with its LU factors
+/// * `config` - Tile size, parallelism and progress settings
+///
+/// # Returns
+///
+/// * Permutation indices: row `i` of `P A` is row `piv[i]` of the input
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use scirs2_linalg::out_of_core::{lu, OutOfCoreConfig};
+///
+/// let a = array![[2.0_f64, 1.0, 1.0], [4.0, 3.0, 3.0], [8.0, 7.0, 9.0]];
+/// let mut factors = a.clone();
+/// let piv = lu(&mut factors, &OutOfCoreConfig::default().with_tile_size(2)).unwrap();
+///
+/// let l = Array2::from_shape_fn((3, 3), |(i, j)| match i.cmp(&j) {
+///     std::cmp::Ordering::Greater => factors[[i, j]],
+///     std::cmp::Ordering::Equal => 1.0,
+///     std::cmp::Ordering::Less => 0.0,
+/// });
+/// let u = Array2::from_shape_fn((3, 3), |(i, j)| if i <= j { factors[[i, j]] } else { 0.0 });
+/// let lu_product = l.dot(&u);
+/// for i in 0..3 {
+///     for j in 0..3 {
+///         assert!((lu_product[[i, j]] - a[[piv[i], j]]).abs() < 1e-12);
+///     }
+/// }
+/// ```
+pub fn lu<F, S>(a: &mut S, config: &OutOfCoreConfig) -> LinalgResult<Vec<usize>>
+where
+    F: Float + NumAssign + Send + Sync + 'static,
+    S: TileStorage<F> + Sync,
+{
+    check_config(config)?;
+    let (m, n) = a.shape()?;
+    if m == 0 || n == 0 {
+        return Err(LinalgError::ComputationError(
+            "Empty matrix provided".to_string(),
+        ));
+    }
+
+    let kmax = m.min(n);
+    let panels = tiles(kmax, config.tile_size);
+    let mut piv: Vec<usize> = (0..m).collect();
+    let mut progress = Progress::new("out-of-core LU", panels.len(), config.progress);
+
+    for block in &panels {
+        let (c0, w) = (block.start, block.len());
+
+        // Factor the panel A[c0.., c0..c0+w] in memory
+        let mut panel = a.read_tile(c0..m, block.clone())?;
+        let mut swaps = Vec::with_capacity(w);
+        for j in 0..w {
+            let p = (j..panel.nrows())
+                .max_by(|&x, &y| {
+                    panel[[x, j]]
+                        .abs()
+                        .partial_cmp(&panel[[y, j]].abs())
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap_or(j);
+            if panel[[p, j]] == F::zero() {
+                return Err(LinalgError::SingularMatrixError(format!(
+                    "Matrix is singular: zero pivot in column {}",
+                    c0 + j
+                )));
+            }
+            if p != j {
+                for c in 0..w {
+                    panel.swap([j, c], [p, c]);
+                }
+                piv.swap(c0 + j, c0 + p);
+            }
+            swaps.push(c0 + p);
+
+            let pivot = panel[[j, j]];
+            for i in j + 1..panel.nrows() {
+                let factor = panel[[i, j]] / pivot;
+                panel[[i, j]] = factor;
+                for c in j + 1..w {
+                    let delta = factor * panel[[j, c]];
+                    panel[[i, c]] -= delta;
+                }
+            }
+        }
+        a.write_tile(c0, c0, &panel.view())?;
+
+        // Apply the interchanges to the columns left and right of the panel
+        for (j, &p) in swaps.iter().enumerate() {
+            let r = c0 + j;
+            if p != r {
+                for cols in [0..c0, block.end..n] {
+                    if !cols.is_empty() {
+                        swap_rows(a, r, p, cols)?;
+                    }
+                }
+            }
+        }
+
+        let right = tiles_from(block.end, n, config.tile_size);
+        if right.is_empty() {
+            progress.step();
+            continue;
+        }
+
+        // Block row of U: U[k, j] = L[k, k]^{-1} A[k, j]
+        let l_kk = panel.slice(s![..w, ..]).to_owned();
+        let storage: &S = a;
+        let u_row = map_tasks(right.clone(), config.parallel, |cols| {
+            let mut tile = storage.read_tile(block.clone(), cols)?;
+            solve_unit_lower_left(&l_kk, &mut tile);
+            Ok(tile)
+        })?;
+        for (cols, tile) in right.iter().zip(&u_row) {
+            a.write_tile(c0, cols.start, &tile.view())?;
+        }
+
+        // Trailing update: A[i, j] -= L[i, k] U[k, j]
+        for rows in tiles_from(block.end, m, config.tile_size) {
+            let l_ik = panel.slice(s![rows.start - c0..rows.end - c0, ..]);
+            let storage: &S = a;
+            let updated = map_tasks((0..right.len()).collect(), config.parallel, |dj| {
+                let cols = &right[dj];
+                let mut tile = storage.read_tile(rows.clone(), cols.clone())?;
+                general_mat_mul(-F::one(), &l_ik, &u_row[dj], F::one(), &mut tile);
+                Ok((cols.start, tile))
+            })?;
+            for (col, tile) in updated {
+                a.write_tile(rows.start, col, &tile.view())?;
+            }
+        }
+        progress.step();
+    }
+
+    a.flush()?;
+    progress.finish();
+    Ok(piv)
+}
+
+/// Out-of-core QR decomposition using Householder reflections
+///
+/// Each block column is factored as an in-memory panel of `m × tile_size`
+/// elements, after which its reflectors are applied to the trailing block
+/// columns, processed in parallel batches. On return `a` holds `R` on and
+/// above the diagonal and the essential parts of the Householder vectors
+/// `vᵢ` (with implicit unit leading entry) below it, so that
+/// `Q = H₀ H₁ ⋯ H_{k-1}` with `Hᵢ = I - τᵢ vᵢ vᵢᵀ`.
+///
+/// # Arguments
+///
+/// * `a` - Matrix of shape `m × n`, overwritten with the compact QR factors
+/// * `config` - Tile size, parallelism and progress settings
+///
+/// # Returns
+///
+/// * The scalar factors `τ` of the `min(m, n)` Householder reflections
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use scirs2_linalg::out_of_core::{qr, qr_q, OutOfCoreConfig};
+///
+/// let a = array![[12.0_f64, -51.0, 4.0], [6.0, 167.0, -68.0], [-4.0, 24.0, -41.0]];
+/// let config = OutOfCoreConfig::default().with_tile_size(2);
+///
+/// let mut factors = a.clone();
+/// let tau = qr(&mut factors, &config).unwrap();
+/// let mut q = Array2::<f64>::zeros((3, 3));
+/// qr_q(&factors, &tau, &mut q, &config).unwrap();
+///
+/// let r = Array2::from_shape_fn((3, 3), |(i, j)| if i <= j { factors[[i, j]] } else { 0.0 });
+/// let qr_product = q.dot(&r);
+/// for (x, y) in qr_product.iter().zip(a.iter()) {
+///     assert!((x - y).abs() < 1e-10);
+/// }
+/// ```
+pub fn qr<F, S>(a: &mut S, config: &OutOfCoreConfig) -> LinalgResult<Vec<F>>
+where
+    F: Float + NumAssign + Send + Sync + 'static,
+    S: TileStorage<F> + Sync,
+{
+    check_config(config)?;
+    let (m, n) = a.shape()?;
+    if m == 0 || n == 0 {
+        return Err(LinalgError::ComputationError(
+            "Empty matrix provided".to_string(),
+        ));
+    }
+
+    let panels = tiles(m.min(n), config.tile_size);
+    let batch = batch_size(config);
+    let mut tau = Vec::with_capacity(m.min(n));
+    let mut progress = Progress::new("out-of-core QR", panels.len(), config.progress);
+
+    for block in &panels {
+        let (c0, w) = (block.start, block.len());
+
+        let mut panel = a.read_tile(c0..m, block.clone())?;
+        let panel_tau = factor_householder_panel(&mut panel);
+        a.write_tile(c0, c0, &panel.view())?;
+
+        for chunk in tiles_from(block.end, n, config.tile_size).chunks(batch) {
+            let storage: &S = a;
+            let updated = map_tasks(chunk.to_vec(), config.parallel, |cols| {
+                let mut tile = storage.read_tile(c0..m, cols.clone())?;
+                apply_reflectors(&panel, &panel_tau, &mut tile, false);
+                Ok((cols.start, tile))
+            })?;
+            for (col, tile) in updated {
+                a.write_tile(c0, col, &tile.view())?;
+            }
+        }
+
+        debug_assert_eq!(panel_tau.len(), w);
+        tau.extend(panel_tau);
+        progress.step();
+    }
+
+    a.flush()?;
+    progress.finish();
+    Ok(tau)
+}
+
+/// Form the orthogonal factor of an out-of-core QR decomposition
+///
+/// Accumulates the Householder reflections stored by [`qr`] into the leading
+/// `p` columns of `Q`, where `p` is the number of columns of `q`. Passing an
+/// `m × min(m, n)` output yields the economic factor, an `m × m` output the
+/// full one.
+///
+/// # Arguments
+///
+/// * `factors` - Matrix overwritten by [`qr`]
+/// * `tau` - Scalar factors returned by [`qr`]
+/// * `q` - Output storage of shape `m × p` with `tau.len() <= p <= m`
+/// * `config` - Tile size, parallelism and progress settings
+///
+/// # Returns
+///
+/// * `Ok(())` once `q` holds the requested columns of `Q`
+pub fn qr_q<F, S, Q>(
+    factors: &S,
+    tau: &[F],
+    q: &mut Q,
+    config: &OutOfCoreConfig,
+) -> LinalgResult<()>
+where
+    F: Float + NumAssign + Send + Sync + 'static,
+    S: TileStorage<F> + Sync,
+    Q: TileStorage<F> + Sync,
+{
+    check_config(config)?;
+    let (m, n) = factors.shape()?;
+    let (qm, p) = q.shape()?;
+    let k = tau.len();
+
+    if k != m.min(n) {
+        return Err(LinalgError::DimensionError(format!(
+            "Expected {} Householder factors for a ({}, {}) matrix, got {}",
+            m.min(n),
+            m,
+            n,
+            k
+        )));
+    }
+    if qm != m || p < k || p > m {
+        return Err(LinalgError::ShapeError(format!(
+            "Output has shape ({}, {}), expected ({}, p) with {} <= p <= {}",
+            qm, p, m, k, m
+        )));
+    }
+
+    // Start from the leading columns of the identity
+    let row_tiles = tiles(m, config.tile_size);
+    let col_tiles = tiles(p, config.tile_size);
+    for rows in &row_tiles {
+        for cols in &col_tiles {
+            let tile = Array2::from_shape_fn((rows.len(), cols.len()), |(i, j)| {
+                if rows.start + i == cols.start + j {
+                    F::one()
+                } else {
+                    F::zero()
+                }
+            });
+            q.write_tile(rows.start, cols.start, &tile.view())?;
+        }
+    }
+
+    let panels = tiles(k, config.tile_size);
+    let batch = batch_size(config);
+    let mut progress = Progress::new("out-of-core QR (form Q)", panels.len(), config.progress);
+
+    // Q = H₀ ⋯ H_{k-1} I: apply the panels from last to first. Columns left of
+    // the panel are still unit vectors with zeros in the rows it touches.
+    for block in panels.iter().rev() {
+        let c0 = block.start;
+        let panel = factors.read_tile(c0..m, block.clone())?;
+        let panel_tau = &tau[block.clone()];
+
+        for chunk in tiles_from(c0, p, config.tile_size).chunks(batch) {
+            let storage: &Q = q;
+            let updated = map_tasks(chunk.to_vec(), config.parallel, |cols| {
+                let mut tile = storage.read_tile(c0..m, cols.clone())?;
+                apply_reflectors(&panel, panel_tau, &mut tile, true);
+                Ok((cols.start, tile))
+            })?;
+            for (col, tile) in updated {
+                q.write_tile(c0, col, &tile.view())?;
+            }
+        }
+        progress.step();
+    }
+
+    q.flush()?;
+    progress.finish();
+    Ok(())
+}
+
+/// Progress reporting through the core logging facilities
+struct Progress {
+    #[cfg(feature = "logging")]
+    tracker: Option<(scirs2_core::logging::ProgressTracker, usize)>,
+}
+
+#[cfg(feature = "logging")]
+impl Progress {
+    fn new(name: &str, total: usize, enabled: bool) -> Self {
+        let tracker = if enabled {
+            Some((scirs2_core::logging::ProgressTracker::new(name, total), 0))
+        } else {
+            None
+        };
+        Progress { tracker }
+    }
+
+    fn step(&mut self) {
+        if let Some((tracker, done)) = self.tracker.as_mut() {
+            *done += 1;
+            tracker.update(*done);
+        }
+    }
+
+    fn finish(mut self) {
+        if let Some((tracker, _)) = self.tracker.as_mut() {
+            tracker.complete();
+        }
+    }
+}
+
+#[cfg(not(feature = "logging"))]
+impl Progress {
+    fn new(_name: &str, _total: usize, _enabled: bool) -> Self {
+        Progress {}
+    }
+
+    fn step(&mut self) {}
+
+    fn finish(self) {}
+}
+
+/// Validate the tile size of a configuration
+fn check_config(config: &OutOfCoreConfig) -> LinalgResult<()> {
+    if config.tile_size == 0 {
+        return Err(LinalgError::InvalidInputError(
+            "Tile size must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that a block lies within a matrix of the given shape
+fn check_block(
+    shape: (usize, usize),
+    rows: &Range<usize>,
+    cols: &Range<usize>,
+) -> LinalgResult<()> {
+    if rows.start > rows.end || cols.start > cols.end || rows.end > shape.0 || cols.end > shape.1 {
+        return Err(LinalgError::IndexError(format!(
+            "Block [{:?}, {:?}] is out of bounds for a ({}, {}) matrix",
+            rows, cols, shape.0, shape.1
+        )));
+    }
+    Ok(())
+}
+
+/// Dimension of a non-empty square matrix
+fn square_dim<F, S: TileStorage<F>>(a: &S, operation: &str) -> LinalgResult<usize> {
+    let (m, n) = a.shape()?;
+    if m == 0 || n == 0 {
+        return Err(LinalgError::ComputationError(
+            "Empty matrix provided".to_string(),
+        ));
+    }
+    if m != n {
+        return Err(LinalgError::DimensionError(format!(
+            "Matrix must be square for {}, got shape ({}, {})",
+            operation, m, n
+        )));
+    }
+    Ok(n)
+}
+
+/// Split `0..n` into consecutive ranges of at most `size` elements
+fn tiles(n: usize, size: usize) -> Vec<Range<usize>> {
+    tiles_from(0, n, size)
+}
+
+/// Split `start..end` into consecutive ranges of at most `size` elements
+fn tiles_from(start: usize, end: usize, size: usize) -> Vec<Range<usize>> {
+    (start..end)
+        .step_by(size)
+        .map(|s| s..(s + size).min(end))
+        .collect()
+}
+
+/// Number of tall tiles processed together, bounding memory use to one tile per worker
+fn batch_size(config: &OutOfCoreConfig) -> usize {
+    #[cfg(feature = "parallel")]
+    if config.parallel {
+        return parallel::get_num_workers().max(1);
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = config;
+    1
+}
+
+/// Run independent tile tasks, in parallel if requested
+fn map_tasks<T, R, G>(tasks: Vec<T>, parallel: bool, task: G) -> LinalgResult<Vec<R>>
+where
+    T: Clone + Sync,
+    R: Send,
+    G: Fn(T) -> LinalgResult<R> + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if parallel {
+        return parallel::try_parallel_map(&tasks, |t| task(t.clone()));
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = parallel;
+    tasks.into_iter().map(task).collect()
+}
+
+/// Swap two rows of the stored matrix within the given columns
+fn swap_rows<F, S: TileStorage<F>>(
+    a: &mut S,
+    r1: usize,
+    r2: usize,
+    cols: Range<usize>,
+) -> LinalgResult<()> {
+    let row1 = a.read_tile(r1..r1 + 1, cols.clone())?;
+    let row2 = a.read_tile(r2..r2 + 1, cols.clone())?;
+    a.write_tile(r1, cols.start, &row2.view())?;
+    a.write_tile(r2, cols.start, &row1.view())
+}
+
+/// In-memory Cholesky factorization of a diagonal tile
+fn factor_cholesky_block<F: Float + NumAssign>(
+    tile: &mut Array2<F>,
+    offset: usize,
+) -> LinalgResult<()> {
+    let n = tile.nrows();
+    for j in 0..n {
+        let mut d = tile[[j, j]];
+        for p in 0..j {
+            d -= tile[[j, p]] * tile[[j, p]];
+        }
+        if d <= F::zero() || !d.is_finite() {
+            return Err(LinalgError::NonPositiveDefiniteError(format!(
+                "Matrix is not positive definite (leading minor of order {} is not positive)",
+                offset + j + 1
+            )));
+        }
+        let d = d.sqrt();
+        tile[[j, j]] = d;
+
+        for i in j + 1..n {
+            let mut s = tile[[i, j]];
+            for p in 0..j {
+                s -= tile[[i, p]] * tile[[j, p]];
+            }
+            tile[[i, j]] = s / d;
+        }
+        for i in 0..j {
+            tile[[i, j]] = F::zero();
+        }
+    }
+    Ok(())
+}
+
+/// Overwrite `b` with `b L^{-T}` for lower triangular `l`
+fn solve_lower_transposed_right<F: Float + NumAssign>(l: &Array2<F>, b: &mut Array2<F>) {
+    let n = l.nrows();
+    for mut row in b.outer_iter_mut() {
+        for c in 0..n {
+            let mut s = row[c];
+            for p in 0..c {
+                s -= row[p] * l[[c, p]];
+            }
+            row[c] = s / l[[c, c]];
+        }
+    }
+}
+
+/// Overwrite `b` with `L^{-1} b` for unit lower triangular `l`
+fn solve_unit_lower_left<F: Float + NumAssign>(l: &Array2<F>, b: &mut Array2<F>) {
+    let n = l.nrows();
+    for mut col in b.columns_mut() {
+        for r in 0..n {
+            let mut s = col[r];
+            for p in 0..r {
+                s -= l[[r, p]] * col[p];
+            }
+            col[r] = s;
+        }
+    }
+}
+
+/// Householder QR of an in-memory panel, returning the reflector factors
+fn factor_householder_panel<F: Float + NumAssign>(panel: &mut Array2<F>) -> Vec<F> {
+    let (rows, cols) = panel.dim();
+    let mut tau = Vec::with_capacity(cols);
+
+    for j in 0..cols {
+        let alpha = panel[[j, j]];
+        let tail_norm = (j + 1..rows)
+            .map(|i| panel[[i, j]] * panel[[i, j]])
+            .fold(F::zero(), |acc, x| acc + x)
+            .sqrt();
+
+        if tail_norm == F::zero() {
+            tau.push(F::zero());
+            continue;
+        }
+
+        let beta = -alpha.signum() * alpha.hypot(tail_norm);
+        let scale = F::one() / (alpha - beta);
+        for i in j + 1..rows {
+            panel[[i, j]] *= scale;
+        }
+        panel[[j, j]] = beta;
+        let t = (beta - alpha) / beta;
+        tau.push(t);
+
+        for c in j + 1..cols {
+            let mut s = panel[[j, c]];
+            for i in j + 1..rows {
+                s += panel[[i, j]] * panel[[i, c]];
+            }
+            s *= t;
+            panel[[j, c]] -= s;
+            for i in j + 1..rows {
+                let v = panel[[i, j]];
+                panel[[i, c]] -= s * v;
+            }
+        }
+    }
+
+    tau
+}
+
+/// Apply the reflectors stored in `panel` to `b`, whose rows align with the panel's
+///
+/// Computes `H_{w-1} ⋯ H₀ b` (i.e. `Qᵀ b`) or, with `reverse`, `H₀ ⋯ H_{w-1} b`.
+fn apply_reflectors<F: Float + NumAssign>(
+    panel: &Array2<F>,
+    tau: &[F],
+    b: &mut Array2<F>,
+    reverse: bool,
+) {
+    let rows = panel.nrows();
+    let mut apply = |j: usize| {
+        let t = tau[j];
+        if t == F::zero() {
+            return;
+        }
+        for mut col in b.columns_mut() {
+            let mut s = col[j];
+            for i in j + 1..rows {
+                s += panel[[i, j]] * col[i];
+            }
+            s *= t;
+            col[j] -= s;
+            for i in j + 1..rows {
+                col[i] -= s * panel[[i, j]];
+            }
+        }
+    };
+
+    if reverse {
+        (0..tau.len()).rev().for_each(&mut apply);
+    } else {
+        (0..tau.len()).for_each(&mut apply);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn test_matrix(m: usize, n: usize, seed: usize) -> Array2<f64> {
+        Array2::from_shape_fn((m, n), |(i, j)| {
+            (((i * 31 + j * 17 + seed * 7) % 23) as f64 - 11.0) / 7.0
+        })
+    }
+
+    fn configs() -> Vec<OutOfCoreConfig> {
+        vec![
+            OutOfCoreConfig::default().with_tile_size(3),
+            OutOfCoreConfig::default()
+                .with_tile_size(4)
+                .with_parallel(false)
+                .with_progress(false),
+            OutOfCoreConfig::default().with_tile_size(64),
+        ]
+    }
+
+    #[test]
+    fn test_matmul() {
+        let a = test_matrix(7, 5, 1);
+        let b = test_matrix(5, 9, 2);
+        let expected = a.dot(&b);
+
+        for config in configs() {
+            let mut c = Array2::from_elem((7, 9), f64::NAN);
+            matmul(&a, &b, &mut c, &config).unwrap();
+            for (x, y) in c.iter().zip(expected.iter()) {
+                assert_relative_eq!(*x, *y, epsilon = 1e-12);
+            }
+        }
+
+        let mut wrong = Array2::zeros((7, 8));
+        assert!(matmul(&a, &b, &mut wrong, &configs()[0]).is_err());
+        assert!(matmul(&a, &a, &mut wrong, &configs()[0]).is_err());
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let g = test_matrix(10, 10, 3);
+        let a = g.dot(&g.t()) + Array2::<f64>::eye(10) * 10.0;
+
+        for config in configs() {
+            let mut l = a.clone();
+            cholesky(&mut l, &config).unwrap();
+            for i in 0..10 {
+                for j in i + 1..10 {
+                    assert_eq!(l[[i, j]], 0.0);
+                }
+            }
+            let reconstructed = l.dot(&l.t());
+            for (x, y) in reconstructed.iter().zip(a.iter()) {
+                assert_relative_eq!(*x, *y, epsilon = 1e-10);
+            }
+        }
+
+        let mut indefinite = a.clone();
+        indefinite[[6, 6]] = -100.0;
+        assert!(matches!(
+            cholesky(&mut indefinite, &configs()[0]),
+            Err(LinalgError::NonPositiveDefiniteError(_))
+        ));
+    }
+
+    #[test]
+    fn test_lu() {
+        for (m, n) in [(9, 9), (11, 6), (5, 10)] {
+            let a = test_matrix(m, n, m + n);
+            for config in configs() {
+                let mut factors = a.clone();
+                let piv = lu(&mut factors, &config).unwrap();
+
+                let k = m.min(n);
+                let l = Array2::from_shape_fn((m, k), |(i, j)| match i.cmp(&j) {
+                    std::cmp::Ordering::Greater => factors[[i, j]],
+                    std::cmp::Ordering::Equal => 1.0,
+                    std::cmp::Ordering::Less => 0.0,
+                });
+                let u = Array2::from_shape_fn(
+                    (k, n),
+                    |(i, j)| {
+                        if i <= j {
+                            factors[[i, j]]
+                        } else {
+                            0.0
+                        }
+                    },
+                );
+                let product = l.dot(&u);
+                for i in 0..m {
+                    for j in 0..n {
+                        assert_relative_eq!(product[[i, j]], a[[piv[i], j]], epsilon = 1e-10);
+                    }
+                }
+            }
+        }
+
+        let mut singular = Array2::<f64>::zeros((4, 4));
+        singular[[0, 0]] = 1.0;
+        assert!(matches!(
+            lu(&mut singular, &configs()[0]),
+            Err(LinalgError::SingularMatrixError(_))
+        ));
+    }
+
+    #[test]
+    fn test_qr() {
+        for (m, n) in [(11, 7), (8, 8), (5, 9)] {
+            let a = test_matrix(m, n, 2 * m + n);
+            let k = m.min(n);
+            for config in configs() {
+                let mut factors = a.clone();
+                let tau = qr(&mut factors, &config).unwrap();
+                assert_eq!(tau.len(), k);
+
+                let r = Array2::from_shape_fn(
+                    (k, n),
+                    |(i, j)| {
+                        if i <= j {
+                            factors[[i, j]]
+                        } else {
+                            0.0
+                        }
+                    },
+                );
+                let mut q = Array2::zeros((m, k));
+                qr_q(&factors, &tau, &mut q, &config).unwrap();
+
+                let product = q.dot(&r);
+                for (x, y) in product.iter().zip(a.iter()) {
+                    assert_relative_eq!(*x, *y, epsilon = 1e-10);
+                }
+                let gram = q.t().dot(&q);
+                for (x, y) in gram.iter().zip(Array2::<f64>::eye(k).iter()) {
+                    assert_relative_eq!(*x, *y, epsilon = 1e-12);
+                }
+
+                let mut full_q = Array2::zeros((m, m));
+                qr_q(&factors, &tau, &mut full_q, &config).unwrap();
+                let gram = full_q.t().dot(&full_q);
+                for (x, y) in gram.iter().zip(Array2::<f64>::eye(m).iter()) {
+                    assert_relative_eq!(*x, *y, epsilon = 1e-12);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "memory_efficient")]
+    #[test]
+    fn test_memory_mapped_cholesky() {
+        use scirs2_core::memory_efficient::{AccessMode, MemoryMappedArray};
+
+        let g = test_matrix(9, 9, 5);
+        let a = g.dot(&g.t()) + Array2::<f64>::eye(9) * 9.0;
+        let mut mapped = MemoryMappedArray::new_temp(&a, AccessMode::ReadWrite, 0).unwrap();
+        cholesky(&mut mapped, &OutOfCoreConfig::default().with_tile_size(4)).unwrap();
+
+        let l = mapped.as_array::<ndarray::Ix2>().unwrap();
+        let reconstructed = l.dot(&l.t());
+        for (x, y) in reconstructed.iter().zip(a.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-10);
+        }
+    }
+
+    #[cfg(feature = "memory_efficient")]
+    #[test]
+    fn test_memory_mapped_cholesky_unaligned() {
+        use scirs2_core::memory_efficient::{AccessMode, MemoryMappedArray};
+
+        // An odd byte offset leaves the mapped f64 values misaligned
+        let g = test_matrix(7, 7, 3);
+        let a = g.dot(&g.t()) + Array2::<f64>::eye(7) * 7.0;
+        let mut mapped = MemoryMappedArray::new_temp(&a, AccessMode::ReadWrite, 3).unwrap();
+        assert!(mapped.as_slice().is_err());
+        cholesky(&mut mapped, &OutOfCoreConfig::default().with_tile_size(3)).unwrap();
+
+        let l = Array2::from_shape_vec((7, 7), mapped.read_elements(0..49).unwrap()).unwrap();
+        let reconstructed = l.dot(&l.t());
+        for (x, y) in reconstructed.iter().zip(a.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-10);
+        }
+    }
+}
@@ -0,0 +1,574 @@
+//! Symmetric indefinite LDLᵀ factorization
+//!
+//! Factors a symmetric (or Hermitian) matrix as `PAPᵀ = LDLᵀ` with a unit lower
+//! triangular `L` and a block diagonal `D` with 1×1 and 2×2 blocks, using the
+//! diagonal pivoting strategies of Bunch and Kaufman or the rook variant of
+//! Ashcraft, Grimes and Lewis. Unlike [`crate::cholesky`], this works for
+//! indefinite matrices such as the KKT systems of constrained optimization.
+
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+use num_traits::{Float, NumAssign};
+use std::fmt::Debug;
+use std::ops::Neg;
+
+use crate::error::{LinalgError, LinalgResult};
+
+/// Pivoting strategy of the LDLᵀ factorization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LDLPivoting {
+    /// Partial Bunch–Kaufman pivoting, which searches at most two columns per step
+    #[default]
+    BunchKaufman,
+    /// Rook pivoting, which bounds the entries of `L` at the cost of a longer search
+    Rook,
+}
+
+/// LDLᵀ decomposition `PAPᵀ = LDLᵀ` of a symmetric or Hermitian matrix
+#[derive(Debug, Clone)]
+pub struct LDLDecomposition<T> {
+    /// Unit lower triangular factor
+    pub l: Array2<T>,
+    /// Block diagonal factor with 1×1 and 2×2 blocks
+    pub d: Array2<T>,
+    /// Permutation: row `i` of `PAPᵀ` is row `perm[i]` of `A`
+    pub perm: Vec<usize>,
+    /// Sizes (1 or 2) of the diagonal blocks of `D`, from the top left
+    pub block_sizes: Vec<usize>,
+}
+
+/// Inertia of a symmetric matrix: the numbers of positive, negative and zero
+/// eigenvalues
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inertia {
+    /// Number of positive eigenvalues
+    pub positive: usize,
+    /// Number of negative eigenvalues
+    pub negative: usize,
+    /// Number of zero eigenvalues
+    pub zero: usize,
+}
+
+/// Compute the LDLᵀ factorization of a symmetric indefinite matrix.
+///
+/// Only the lower triangle of `a` is referenced. The factorization exists for
+/// every symmetric matrix, including singular ones, for which `D` has zero
+/// diagonal blocks.
+///
+/// # Arguments
+///
+/// * `a` - Symmetric matrix
+/// * `pivoting` - Pivoting strategy
+///
+/// # Returns
+///
+/// * The factorization `PAPᵀ = LDLᵀ`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{ldl, LDLPivoting};
+///
+/// // A KKT matrix with a zero block
+/// let a = array![[2.0_f64, 0.0, 1.0], [0.0, 2.0, 1.0], [1.0, 1.0, 0.0]];
+/// let f = ldl(&a.view(), LDLPivoting::BunchKaufman).unwrap();
+/// let ldlt = f.l.dot(&f.d).dot(&f.l.t());
+/// for i in 0..3 {
+///     for j in 0..3 {
+///         assert!((ldlt[[i, j]] - a[[f.perm[i], f.perm[j]]]).abs() < 1e-12);
+///     }
+/// }
+/// ```
+pub fn ldl<F>(a: &ArrayView2<F>, pivoting: LDLPivoting) -> LinalgResult<LDLDecomposition<F>>
+where
+    F: Float + NumAssign + Debug,
+{
+    ldl_factor(a, pivoting, &real_ops())
+}
+
+/// Solve `Ax = b` with an LDLᵀ factorization of `A`.
+///
+/// # Arguments
+///
+/// * `factorization` - Result of [`ldl`]
+/// * `b` - Right-hand side
+///
+/// # Returns
+///
+/// * Solution `x`, or a `SingularMatrixError` if `D` is singular
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{ldl, ldl_solve, LDLPivoting};
+///
+/// let a = array![[0.0_f64, 1.0], [1.0, 0.0]];
+/// let f = ldl(&a.view(), LDLPivoting::Rook).unwrap();
+/// let x = ldl_solve(&f, &array![2.0, 3.0].view()).unwrap();
+/// assert!((x[0] - 3.0).abs() < 1e-12);
+/// assert!((x[1] - 2.0).abs() < 1e-12);
+/// ```
+pub fn ldl_solve<F>(
+    factorization: &LDLDecomposition<F>,
+    b: &ArrayView1<F>,
+) -> LinalgResult<Array1<F>>
+where
+    F: Float + NumAssign + Debug,
+{
+    ldl_solve_factored(factorization, b, &real_ops())
+}
+
+/// Inertia of a symmetric matrix from its LDLᵀ factorization.
+///
+/// By Sylvester's law of inertia `A` and `D` have the same numbers of
+/// positive, negative and zero eigenvalues. Eigenvalues of `D` below
+/// `n ε max|Dᵢⱼ|` in magnitude count as zero.
+///
+/// # Arguments
+///
+/// * `factorization` - Result of [`ldl`]
+///
+/// # Returns
+///
+/// * Counts of positive, negative and zero eigenvalues
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{ldl, ldl_inertia, LDLPivoting};
+///
+/// let a = array![[1.0_f64, 2.0, 0.0], [2.0, 1.0, 0.0], [0.0, 0.0, 0.0]];
+/// let f = ldl(&a.view(), LDLPivoting::BunchKaufman).unwrap();
+/// let inertia = ldl_inertia(&f);
+/// assert_eq!((inertia.positive, inertia.negative, inertia.zero), (1, 1, 1));
+/// ```
+pub fn ldl_inertia<F>(factorization: &LDLDecomposition<F>) -> Inertia
+where
+    F: Float + NumAssign + Debug,
+{
+    inertia_factored(factorization, &real_ops())
+}
+
+/// Scalar operations that distinguish the symmetric and Hermitian cases
+pub(crate) struct ScalarOps<T, R> {
+    pub(crate) conj: fn(T) -> T,
+    pub(crate) abs: fn(T) -> R,
+    pub(crate) re: fn(T) -> R,
+    pub(crate) from_real: fn(R) -> T,
+}
+
+fn real_ops<F: Float>() -> ScalarOps<F, F> {
+    ScalarOps {
+        conj: |x| x,
+        abs: |x| x.abs(),
+        re: |x| x,
+        from_real: |x| x,
+    }
+}
+
+/// Growth bound `(1 + √17) / 8` of Bunch and Kaufman
+fn pivot_threshold<R: Float>() -> R {
+    (R::one() + R::from(17.0).unwrap().sqrt()) / R::from(8.0).unwrap()
+}
+
+/// Largest `|a[i, col]|` over `i ≥ from`, `i ≠ col`, and its row
+fn off_diagonal_max<T: Copy, R: Float>(
+    a: &Array2<T>,
+    col: usize,
+    from: usize,
+    abs: fn(T) -> R,
+) -> (R, usize) {
+    (from..a.nrows())
+        .filter(|&i| i != col)
+        .fold((R::zero(), col), |(max, arg), i| {
+            let v = abs(a[[i, col]]);
+            if v > max {
+                (v, i)
+            } else {
+                (max, arg)
+            }
+        })
+}
+
+/// LDLᵀ (or LDLᴴ) factorization of the matrix given by its lower triangle
+pub(crate) fn ldl_factor<T, R>(
+    a: &ArrayView2<T>,
+    pivoting: LDLPivoting,
+    ops: &ScalarOps<T, R>,
+) -> LinalgResult<LDLDecomposition<T>>
+where
+    T: Copy + NumAssign + Neg<Output = T>,
+    R: Float,
+{
+    if a.nrows() != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Matrix must be square for LDL decomposition, got shape {:?}",
+            a.shape()
+        )));
+    }
+    let n = a.nrows();
+    if a.iter().any(|&v| !(ops.abs)(v).is_finite()) {
+        return Err(LinalgError::InvalidInputError(
+            "Matrix for LDL decomposition contains non-finite values".to_string(),
+        ));
+    }
+
+    // Full working copy from the lower triangle, with a real diagonal
+    let mut w = Array2::from_shape_fn((n, n), |(i, j)| match i.cmp(&j) {
+        std::cmp::Ordering::Greater => a[[i, j]],
+        std::cmp::Ordering::Less => (ops.conj)(a[[j, i]]),
+        std::cmp::Ordering::Equal => (ops.from_real)((ops.re)(a[[i, i]])),
+    });
+    let mut l = Array2::<T>::zeros((n, n));
+    let mut d = Array2::<T>::zeros((n, n));
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut block_sizes = Vec::new();
+    let alpha = pivot_threshold::<R>();
+
+    let mut k = 0;
+    while k < n {
+        let abs_kk = (ops.abs)(w[[k, k]]);
+        let (col_max, r) = off_diagonal_max(&w, k, k, ops.abs);
+
+        // Choose the pivot rows: (first, second) for a 2x2 block
+        let (first, second) = if abs_kk.max(col_max) == R::zero() || abs_kk >= alpha * col_max {
+            (k, None)
+        } else {
+            match pivoting {
+                LDLPivoting::BunchKaufman => {
+                    let (row_max, _) = off_diagonal_max(&w, r, k, ops.abs);
+                    if abs_kk * row_max >= alpha * col_max * col_max {
+                        (k, None)
+                    } else if (ops.abs)(w[[r, r]]) >= alpha * row_max {
+                        (r, None)
+                    } else {
+                        (k, Some(r))
+                    }
+                }
+                LDLPivoting::Rook => {
+                    let (mut p, mut candidate, mut p_max) = (k, r, col_max);
+                    loop {
+                        let (c_max, next) = off_diagonal_max(&w, candidate, k, ops.abs);
+                        if (ops.abs)(w[[candidate, candidate]]) >= alpha * c_max {
+                            break (candidate, None);
+                        }
+                        if next == p || c_max <= p_max {
+                            break (p, Some(candidate));
+                        }
+                        p = candidate;
+                        p_max = c_max;
+                        candidate = next;
+                    }
+                }
+            }
+        };
+
+        match second {
+            None => {
+                symmetric_swap(&mut w, &mut l, &mut perm, k, first);
+                let pivot = w[[k, k]];
+                d[[k, k]] = pivot;
+                l[[k, k]] = T::one();
+                if (ops.abs)(pivot) != R::zero() {
+                    for i in k + 1..n {
+                        l[[i, k]] = w[[i, k]] / pivot;
+                    }
+                    for j in k + 1..n {
+                        let factor = (ops.conj)(w[[j, k]]);
+                        for i in k + 1..n {
+                            let update = l[[i, k]] * factor;
+                            w[[i, j]] -= update;
+                        }
+                    }
+                }
+                block_sizes.push(1);
+                k += 1;
+            }
+            Some(second) => {
+                // Bring the pair to positions k and k + 1
+                symmetric_swap(&mut w, &mut l, &mut perm, k, first);
+                let second = if second == k { first } else { second };
+                symmetric_swap(&mut w, &mut l, &mut perm, k + 1, second);
+
+                let (d11, d21, d22) = (w[[k, k]], w[[k + 1, k]], w[[k + 1, k + 1]]);
+                let d12 = (ops.conj)(d21);
+                let det = d11 * d22 - d21 * d12;
+                d[[k, k]] = d11;
+                d[[k + 1, k]] = d21;
+                d[[k, k + 1]] = d12;
+                d[[k + 1, k + 1]] = d22;
+                l[[k, k]] = T::one();
+                l[[k + 1, k + 1]] = T::one();
+                // Rows of L are [w_ik, w_i,k+1] D⁻¹
+                for i in k + 2..n {
+                    let (x, y) = (w[[i, k]], w[[i, k + 1]]);
+                    l[[i, k]] = (x * d22 - y * d21) / det;
+                    l[[i, k + 1]] = (y * d11 - x * d12) / det;
+                }
+                for j in k + 2..n {
+                    let (x, y) = ((ops.conj)(w[[j, k]]), (ops.conj)(w[[j, k + 1]]));
+                    for i in k + 2..n {
+                        let update = l[[i, k]] * x + l[[i, k + 1]] * y;
+                        w[[i, j]] -= update;
+                    }
+                }
+                block_sizes.push(2);
+                k += 2;
+            }
+        }
+        // Keep the diagonal of the trailing matrix exactly real
+        for i in k..n {
+            w[[i, i]] = (ops.from_real)((ops.re)(w[[i, i]]));
+        }
+    }
+
+    Ok(LDLDecomposition {
+        l,
+        d,
+        perm,
+        block_sizes,
+    })
+}
+
+/// Swap rows and columns `i ≥ j` of the trailing matrix, the computed rows
+/// of `L` and the permutation
+fn symmetric_swap<T: Copy>(
+    w: &mut Array2<T>,
+    l: &mut Array2<T>,
+    perm: &mut [usize],
+    i: usize,
+    j: usize,
+) {
+    if i == j {
+        return;
+    }
+    let n = w.nrows();
+    for c in 0..n {
+        w.swap([i, c], [j, c]);
+    }
+    for r in 0..n {
+        w.swap([r, i], [r, j]);
+    }
+    for c in 0..i.min(j) {
+        l.swap([i, c], [j, c]);
+    }
+    perm.swap(i, j);
+}
+
+/// Solve with `PAPᵀ = LDLᵀ` (or `LDLᴴ`)
+pub(crate) fn ldl_solve_factored<T, R>(
+    factorization: &LDLDecomposition<T>,
+    b: &ArrayView1<T>,
+    ops: &ScalarOps<T, R>,
+) -> LinalgResult<Array1<T>>
+where
+    T: Copy + NumAssign + Neg<Output = T>,
+    R: Float,
+{
+    let LDLDecomposition {
+        l,
+        d,
+        perm,
+        block_sizes,
+    } = factorization;
+    let n = l.nrows();
+    if b.len() != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Right-hand side must have length {}, got {}",
+            n,
+            b.len()
+        )));
+    }
+
+    // L y = P b
+    let mut y: Vec<T> = perm.iter().map(|&p| b[p]).collect();
+    for i in 0..n {
+        for j in 0..i {
+            let v = l[[i, j]] * y[j];
+            y[i] -= v;
+        }
+    }
+
+    // D z = y, block by block
+    let scale = d.iter().fold(R::zero(), |m, &v| m.max((ops.abs)(v)));
+    let tolerance = R::epsilon() * scale * R::from(n).unwrap();
+    let singular = || {
+        LinalgError::SingularMatrixError(
+            "Block diagonal factor of the LDL decomposition is singular".to_string(),
+        )
+    };
+    let mut k = 0;
+    for &size in block_sizes {
+        if size == 1 {
+            if (ops.abs)(d[[k, k]]) <= tolerance {
+                return Err(singular());
+            }
+            y[k] /= d[[k, k]];
+        } else {
+            let (d11, d21, d12, d22) = (d[[k, k]], d[[k + 1, k]], d[[k, k + 1]], d[[k + 1, k + 1]]);
+            let det = d11 * d22 - d21 * d12;
+            if (ops.abs)(det) <= tolerance * scale {
+                return Err(singular());
+            }
+            let (x1, x2) = (y[k], y[k + 1]);
+            y[k] = (d22 * x1 - d12 * x2) / det;
+            y[k + 1] = (d11 * x2 - d21 * x1) / det;
+        }
+        k += size;
+    }
+
+    // Lᴴ w = z
+    for i in (0..n).rev() {
+        for j in i + 1..n {
+            let v = (ops.conj)(l[[j, i]]) * y[j];
+            y[i] -= v;
+        }
+    }
+
+    let mut x = Array1::zeros(n);
+    for (i, &p) in perm.iter().enumerate() {
+        x[p] = y[i];
+    }
+    Ok(x)
+}
+
+/// Inertia from the eigenvalues of the diagonal blocks of `D`
+pub(crate) fn inertia_factored<T, R>(
+    factorization: &LDLDecomposition<T>,
+    ops: &ScalarOps<T, R>,
+) -> Inertia
+where
+    T: Copy,
+    R: Float,
+{
+    let d = &factorization.d;
+    let n = d.nrows();
+    let scale = d.iter().fold(R::zero(), |m, &v| m.max((ops.abs)(v)));
+    let tolerance = R::epsilon() * scale * R::from(n.max(1)).unwrap();
+    let mut inertia = Inertia {
+        positive: 0,
+        negative: 0,
+        zero: 0,
+    };
+    let mut count = |lambda: R| {
+        if lambda > tolerance {
+            inertia.positive += 1;
+        } else if lambda < -tolerance {
+            inertia.negative += 1;
+        } else {
+            inertia.zero += 1;
+        }
+    };
+
+    let mut k = 0;
+    for &size in &factorization.block_sizes {
+        if size == 1 {
+            count((ops.re)(d[[k, k]]));
+        } else {
+            // Eigenvalues of the Hermitian block [[a, c̄], [c, b]]
+            let two = R::from(2.0).unwrap();
+            let (a, b, c) = (
+                (ops.re)(d[[k, k]]),
+                (ops.re)(d[[k + 1, k + 1]]),
+                (ops.abs)(d[[k + 1, k]]),
+            );
+            let mean = (a + b) / two;
+            let radius = ((a - b) / two).hypot(c);
+            count(mean + radius);
+            count(mean - radius);
+        }
+        k += size;
+    }
+    inertia
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+
+    fn check_factorization(a: &Array2<f64>, f: &LDLDecomposition<f64>) {
+        let n = a.nrows();
+        let ldlt = f.l.dot(&f.d).dot(&f.l.t());
+        for i in 0..n {
+            assert_eq!(f.l[[i, i]], 1.0);
+            for j in 0..n {
+                if j > i {
+                    assert_eq!(f.l[[i, j]], 0.0);
+                }
+                assert_relative_eq!(ldlt[[i, j]], a[[f.perm[i], f.perm[j]]], epsilon = 1e-12);
+            }
+        }
+        assert_eq!(f.block_sizes.iter().sum::<usize>(), n);
+    }
+
+    #[test]
+    fn test_ldl_indefinite_with_both_strategies() {
+        let a = array![
+            [0.0, 1.0, 2.0, -1.0],
+            [1.0, 0.0, 3.0, 0.5],
+            [2.0, 3.0, 0.1, 1.0],
+            [-1.0, 0.5, 1.0, -2.0]
+        ];
+        let b = array![1.0, -2.0, 0.5, 3.0];
+        for pivoting in [LDLPivoting::BunchKaufman, LDLPivoting::Rook] {
+            let f = ldl(&a.view(), pivoting).unwrap();
+            check_factorization(&a, &f);
+            assert!(f.block_sizes.contains(&2));
+            // Pivoting bounds the multipliers
+            assert!(f.l.iter().all(|v| v.abs() < 10.0));
+
+            let x = ldl_solve(&f, &b.view()).unwrap();
+            let residual = a.dot(&x) - &b;
+            assert!(residual.iter().all(|r| r.abs() < 1e-12));
+
+            let inertia = ldl_inertia(&f);
+            assert_eq!(inertia.positive + inertia.negative, 4);
+            assert_eq!(inertia.zero, 0);
+        }
+        assert!(ldl(&array![[1.0, 2.0]].view(), LDLPivoting::BunchKaufman).is_err());
+    }
+
+    #[test]
+    fn test_ldl_inertia_matches_eigenvalues() {
+        // KKT matrix [[H, Aᵀ], [A, 0]] with H positive definite and A of full
+        // row rank has n positive and m negative eigenvalues
+        let a = array![
+            [4.0, 1.0, 0.0, 1.0, 0.0],
+            [1.0, 3.0, 0.5, 1.0, 1.0],
+            [0.0, 0.5, 2.0, 0.0, 1.0],
+            [1.0, 1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 1.0, 0.0, 0.0]
+        ];
+        let f = ldl(&a.view(), LDLPivoting::BunchKaufman).unwrap();
+        check_factorization(&a, &f);
+        assert_eq!(
+            ldl_inertia(&f),
+            Inertia {
+                positive: 3,
+                negative: 2,
+                zero: 0
+            }
+        );
+
+        // Singular: a zero eigenvalue and a failing solve
+        let s = array![[1.0, 1.0, 0.0], [1.0, 1.0, 0.0], [0.0, 0.0, -3.0]];
+        let f = ldl(&s.view(), LDLPivoting::Rook).unwrap();
+        check_factorization(&s, &f);
+        assert_eq!(
+            ldl_inertia(&f),
+            Inertia {
+                positive: 1,
+                negative: 1,
+                zero: 1
+            }
+        );
+        assert!(matches!(
+            ldl_solve(&f, &array![1.0, 0.0, 0.0].view()),
+            Err(LinalgError::SingularMatrixError(_))
+        ));
+    }
+}
@@ -67,7 +67,7 @@ pub enum Method {
     /// Trust-region constrained algorithm
     TrustConstr,
 
-    /// Linear programming using the simplex algorithm
+    /// Constrained Optimization BY Linear Approximations
     COBYLA,
 }
 
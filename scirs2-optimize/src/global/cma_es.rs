@@ -0,0 +1,791 @@
+//! Covariance matrix adaptation evolution strategy (CMA-ES)
+//!
+//! Each generation samples `λ` candidates from the normal distribution
+//! `m + σ N(0, C)`. The mean `m` moves to a weighted average of the best `μ`
+//! candidates, the step size `σ` follows cumulative step-size adaptation and
+//! the covariance `C` learns from the evolution path (rank-one update) and the
+//! selected steps (rank-μ update). Three variants are provided:
+//!
+//! * `Full`: the standard algorithm with a full covariance matrix
+//! * `Separable`: a diagonal covariance with faster learning rates, which
+//!   costs `O(n)` per sample instead of `O(n²)` and suits high-dimensional,
+//!   nearly separable problems
+//! * `Active`: a full covariance that also actively decreases the variance
+//!   in the directions of the worst candidates
+//!
+//! Bounds are handled by reflection: the search distribution lives in an
+//! unbounded space and every candidate is mirrored into the box before it is
+//! evaluated. Infinite and one-sided bounds are allowed.
+//!
+//! IPOP restarts the search with a population that grows by a constant
+//! factor whenever a run stops, which helps on multimodal functions with a
+//! global structure. BIPOP alternates these runs with runs that use a small
+//! population and a small random step size, giving the same budget to both
+//! regimes.
+//!
+//! References:
+//!
+//! * N. Hansen, "The CMA Evolution Strategy: A Tutorial", arXiv:1604.00772
+//! * R. Ros and N. Hansen, "A Simple Modification in CMA-ES Achieving Linear
+//!   Time and Space Complexity", PPSN X (2008)
+//! * A. Auger and N. Hansen, "A Restart CMA Evolution Strategy With
+//!   Increasing Population Size", CEC (2005)
+//! * N. Hansen, "Benchmarking a BI-Population CMA-ES on the BBOB-2009
+//!   Function Testbed", GECCO (2009)
+
+use crate::error::OptimizeError;
+use crate::parallel::{parallel_evaluate_batch, ParallelOptions};
+use crate::unconstrained::OptimizeResult;
+use ndarray::{Array1, Array2, ArrayView1};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use rand_distr::StandardNormal;
+use std::collections::VecDeque;
+
+/// Bounds for variables
+pub type Bounds = Vec<(f64, f64)>;
+
+/// Form of the covariance matrix adapted by CMA-ES
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CmaEsVariant {
+    /// Full covariance matrix
+    Full,
+    /// Diagonal covariance matrix (sep-CMA-ES)
+    Separable,
+    /// Full covariance matrix with negative updates from the worst candidates
+    Active,
+}
+
+/// Restart strategy of CMA-ES
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestartStrategy {
+    /// A single run
+    None,
+    /// Restarts with increasing population size
+    Ipop,
+    /// Restarts alternating between a large and a small population regime
+    Bipop,
+}
+
+/// Options for CMA-ES
+#[derive(Debug, Clone)]
+pub struct CmaEsOptions {
+    /// Initial mean (None = uniformly random point within the bounds, which
+    /// must then be finite)
+    pub x0: Option<Array1<f64>>,
+    /// Initial step size (None = 0.3 times the width of the bounds in each
+    /// coordinate, which must then be finite)
+    pub sigma0: Option<f64>,
+    /// Population size of the first run (None = 4 + ⌊3 ln n⌋)
+    pub popsize: Option<usize>,
+    /// Covariance matrix variant
+    pub variant: CmaEsVariant,
+    /// Restart strategy
+    pub restart: RestartStrategy,
+    /// Maximum number of restarts; BIPOP counts only the restarts of the
+    /// large population regime
+    pub max_restarts: usize,
+    /// Factor by which the population grows at each IPOP or large BIPOP restart
+    pub popsize_increase: f64,
+    /// Maximum number of generations of each run
+    /// (None = 100 + 150 (n + 3)² / √λ)
+    pub maxiter: Option<usize>,
+    /// Maximum total number of function evaluations
+    pub maxfev: Option<usize>,
+    /// Stop as soon as a function value below this target is found
+    pub ftarget: Option<f64>,
+    /// A run has converged when the best function values of the recent
+    /// generations and all values of the last generation lie within this range
+    pub tol: f64,
+    /// A run has converged when the standard deviation of the search
+    /// distribution falls below this value in every coordinate
+    pub xtol: f64,
+    /// Random seed for reproducibility
+    pub seed: Option<u64>,
+    /// Parallel computation options
+    pub parallel: Option<ParallelOptions>,
+}
+
+impl Default for CmaEsOptions {
+    fn default() -> Self {
+        Self {
+            x0: None,
+            sigma0: None,
+            popsize: None,
+            variant: CmaEsVariant::Full,
+            restart: RestartStrategy::None,
+            max_restarts: 9,
+            popsize_increase: 2.0,
+            maxiter: None,
+            maxfev: None,
+            ftarget: None,
+            tol: 1e-11,
+            xtol: 1e-11,
+            seed: None,
+            parallel: None,
+        }
+    }
+}
+
+/// Reason why a run stopped
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stop {
+    /// The function values stopped changing
+    TolFun,
+    /// The search distribution became smaller than `xtol`
+    TolX,
+    /// Steps no longer change the mean in floating point arithmetic
+    NoEffect,
+    /// A function value below `ftarget` was found
+    Target,
+    /// The covariance matrix became too ill-conditioned
+    ConditionCov,
+    /// The step size diverged
+    TolXUp,
+    /// The generation or evaluation limit of the run was reached
+    MaxIter,
+    /// The total evaluation budget was exhausted
+    MaxFev,
+}
+
+impl Stop {
+    fn converged(self) -> bool {
+        matches!(
+            self,
+            Stop::TolFun | Stop::TolX | Stop::NoEffect | Stop::Target
+        )
+    }
+
+    /// Whether no further restart may follow
+    fn is_final(self) -> bool {
+        matches!(self, Stop::Target | Stop::MaxFev)
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            Stop::TolFun | Stop::TolX | Stop::NoEffect => "Optimization converged successfully",
+            Stop::Target => "Target function value reached",
+            Stop::ConditionCov => "Covariance matrix became ill-conditioned",
+            Stop::TolXUp => "Step size diverged",
+            Stop::MaxIter => "Maximum number of iterations reached",
+            Stop::MaxFev => "Maximum number of function evaluations reached",
+        }
+    }
+}
+
+/// Strategy parameters for a given dimension and population size
+struct Parameters {
+    mu: usize,
+    /// Recombination weights of all `λ` ranks; negative weights are zero
+    /// unless the active update is used
+    weights: Vec<f64>,
+    mueff: f64,
+    cc: f64,
+    cs: f64,
+    c1: f64,
+    cmu: f64,
+    damps: f64,
+    /// Expected norm of a standard normal vector
+    chi_n: f64,
+    /// Number of generations between eigendecompositions
+    eigen_interval: usize,
+}
+
+impl Parameters {
+    fn new(n: usize, lambda: usize, variant: CmaEsVariant) -> Self {
+        let nf = n as f64;
+        let mu = lambda / 2;
+        let raw: Vec<f64> = (1..=lambda)
+            .map(|i| ((lambda as f64 + 1.0) / 2.0).ln() - (i as f64).ln())
+            .collect();
+        let positive: f64 = raw.iter().filter(|&&w| w > 0.0).sum();
+        let positive_sq: f64 = raw.iter().filter(|&&w| w > 0.0).map(|w| w * w).sum();
+        let mueff = positive * positive / positive_sq;
+
+        let cc = (4.0 + mueff / nf) / (nf + 4.0 + 2.0 * mueff / nf);
+        let cs = (mueff + 2.0) / (nf + mueff + 5.0);
+        let mut c1 = 2.0 / ((nf + 1.3).powi(2) + mueff);
+        let mut cmu =
+            (1.0 - c1).min(2.0 * (mueff - 2.0 + 1.0 / mueff) / ((nf + 2.0).powi(2) + mueff));
+        if variant == CmaEsVariant::Separable {
+            c1 = (c1 * (nf + 2.0) / 3.0).min(1.0);
+            cmu = (cmu * (nf + 2.0) / 3.0).min(1.0 - c1);
+        }
+        let damps = 1.0 + 2.0 * (((mueff - 1.0) / (nf + 1.0)).sqrt() - 1.0).max(0.0) + cs;
+
+        let negative: f64 = raw.iter().filter(|&&w| w < 0.0).map(|w| -w).sum();
+        let negative_sq: f64 = raw.iter().filter(|&&w| w < 0.0).map(|w| w * w).sum();
+        let negative_scale = if variant == CmaEsVariant::Active && negative > 0.0 {
+            let mueff_neg = negative * negative / negative_sq;
+            let alpha_mu = 1.0 + c1 / cmu;
+            let alpha_mueff = 1.0 + 2.0 * mueff_neg / (mueff + 2.0);
+            let alpha_posdef = (1.0 - c1 - cmu) / (nf * cmu);
+            alpha_mu.min(alpha_mueff).min(alpha_posdef) / negative
+        } else {
+            0.0
+        };
+        let weights = raw
+            .iter()
+            .map(|&w| {
+                if w >= 0.0 {
+                    w / positive
+                } else {
+                    w * negative_scale
+                }
+            })
+            .collect();
+
+        let eigen_interval = ((1.0 / (10.0 * nf * (c1 + cmu))).floor() as usize).max(1);
+        Self {
+            mu,
+            weights,
+            mueff,
+            cc,
+            cs,
+            c1,
+            cmu,
+            damps,
+            chi_n: nf.sqrt() * (1.0 - 1.0 / (4.0 * nf) + 1.0 / (21.0 * nf * nf)),
+            eigen_interval,
+        }
+    }
+}
+
+/// Problem, options and the state shared by all runs
+struct CmaEs<'a, F> {
+    func: &'a F,
+    bounds: &'a [(f64, f64)],
+    options: &'a CmaEsOptions,
+    /// Initial standard deviation of each coordinate relative to the step size
+    scale: Array1<f64>,
+    rng: StdRng,
+    nfev: usize,
+    nit: usize,
+    best_x: Option<Array1<f64>>,
+    best_f: f64,
+}
+
+impl<F> CmaEs<'_, F>
+where
+    F: Fn(&ArrayView1<f64>) -> f64 + Sync,
+{
+    fn n(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// A uniformly random point within the bounds, or `x0` if the bounds are infinite
+    fn start_point(&mut self) -> Array1<f64> {
+        if self
+            .bounds
+            .iter()
+            .all(|&(lb, ub)| lb.is_finite() && ub.is_finite())
+        {
+            Array1::from_iter(
+                self.bounds
+                    .iter()
+                    .map(|&(lb, ub)| self.rng.random_range(lb..ub)),
+            )
+        } else {
+            // Validated when the bounds are not all finite
+            self.options.x0.clone().unwrap_or_default()
+        }
+    }
+
+    /// Evaluate a generation, treating NaN as +∞
+    fn evaluate(&mut self, candidates: &[Array1<f64>]) -> Vec<f64> {
+        let values = match &self.options.parallel {
+            Some(parallel) => parallel_evaluate_batch(self.func, candidates, parallel),
+            None => candidates.iter().map(|x| (self.func)(&x.view())).collect(),
+        };
+        self.nfev += candidates.len();
+        values
+            .into_iter()
+            .map(|v| if v.is_nan() { f64::INFINITY } else { v })
+            .collect()
+    }
+
+    /// Run CMA-ES once from `mean` with step size `sigma` and population `lambda`
+    fn run(
+        &mut self,
+        mut mean: Array1<f64>,
+        mut sigma: f64,
+        lambda: usize,
+        max_evals: Option<usize>,
+    ) -> Stop {
+        let n = self.n();
+        let options = self.options;
+        let variant = options.variant;
+        let p = Parameters::new(n, lambda, variant);
+        let maxiter = options.maxiter.unwrap_or_else(|| {
+            (100.0 + 150.0 * (n as f64 + 3.0).powi(2) / (lambda as f64).sqrt()) as usize
+        });
+        let history_len = 10 + (30.0 * n as f64 / lambda as f64).ceil() as usize;
+
+        // C = B diag(d²) Bᵀ. The separable variant stores only the diagonal
+        // of C and keeps B = I implicitly.
+        let separable = variant == CmaEsVariant::Separable;
+        let full_size = if separable { 0 } else { n };
+        let mut c_diag = self.scale.mapv(|s| s * s);
+        let mut c = Array2::from_diag(&c_diag.slice(ndarray::s![..full_size]));
+        let mut b = Array2::<f64>::eye(full_size);
+        let mut d = self.scale.clone();
+        let mut pc = Array1::<f64>::zeros(n);
+        let mut ps = Array1::<f64>::zeros(n);
+        let max_d0 = d.fold(0.0f64, |a, &v| a.max(v));
+        let sigma0 = sigma;
+        let mut history: VecDeque<f64> = VecDeque::with_capacity(history_len);
+        let start_evals = self.nfev;
+        let mut eigen_generation = 0;
+
+        let mut generation = 0;
+        loop {
+            if generation >= maxiter
+                || max_evals.is_some_and(|m| self.nfev - start_evals + lambda > m)
+            {
+                return Stop::MaxIter;
+            }
+            if options.maxfev.is_some_and(|m| self.nfev + lambda > m) {
+                return Stop::MaxFev;
+            }
+
+            // Sample y = B D z and mirror x = m + σ y into the bounds
+            let mut zs = Vec::with_capacity(lambda);
+            let mut ys = Vec::with_capacity(lambda);
+            let mut candidates = Vec::with_capacity(lambda);
+            for _ in 0..lambda {
+                let z =
+                    Array1::from_iter((0..n).map(|_| self.rng.sample::<f64, _>(StandardNormal)));
+                let y = if separable {
+                    &d * &z
+                } else {
+                    b.dot(&(&d * &z))
+                };
+                let x = Array1::from_iter(
+                    (&mean + &(sigma * &y))
+                        .iter()
+                        .zip(self.bounds)
+                        .map(|(&x, &bound)| mirror(x, bound)),
+                );
+                zs.push(z);
+                ys.push(y);
+                candidates.push(x);
+            }
+            let values = self.evaluate(&candidates);
+            self.nit += 1;
+
+            let mut order: Vec<usize> = (0..lambda).collect();
+            order.sort_by(|&i, &j| values[i].total_cmp(&values[j]).then(i.cmp(&j)));
+            let best = order[0];
+            if values[best] < self.best_f || self.best_x.is_none() {
+                self.best_f = values[best];
+                self.best_x = Some(candidates[best].clone());
+            }
+
+            // Mean and evolution paths
+            let mut y_w = Array1::<f64>::zeros(n);
+            let mut z_w = Array1::<f64>::zeros(n);
+            for (rank, &k) in order.iter().take(p.mu).enumerate() {
+                y_w.scaled_add(p.weights[rank], &ys[k]);
+                z_w.scaled_add(p.weights[rank], &zs[k]);
+            }
+            mean.scaled_add(sigma, &y_w);
+            let whitened = if separable { z_w } else { b.dot(&z_w) };
+            ps *= 1.0 - p.cs;
+            ps.scaled_add((p.cs * (2.0 - p.cs) * p.mueff).sqrt(), &whitened);
+            let ps_norm = ps.dot(&ps).sqrt();
+            let hsig = ps_norm / (1.0 - (1.0 - p.cs).powi(2 * (generation as i32 + 1))).sqrt()
+                < (1.4 + 2.0 / (n as f64 + 1.0)) * p.chi_n;
+            pc *= 1.0 - p.cc;
+            if hsig {
+                pc.scaled_add((p.cc * (2.0 - p.cc) * p.mueff).sqrt(), &y_w);
+            }
+
+            // Covariance: rank-one and rank-μ updates. Negative weights are
+            // rescaled by the Mahalanobis norm of the step, ‖C^(-1/2) y‖ = ‖z‖,
+            // so that C stays positive definite.
+            let delta = if hsig { 0.0 } else { p.cc * (2.0 - p.cc) };
+            let weight_sum: f64 = p.weights.iter().sum();
+            let decay = 1.0 + p.c1 * delta - p.c1 - p.cmu * weight_sum;
+            let rank_weights: Vec<(usize, f64)> = order
+                .iter()
+                .zip(&p.weights)
+                .filter(|(_, &w)| w != 0.0)
+                .map(|(&k, &w)| {
+                    if w > 0.0 {
+                        (k, w)
+                    } else {
+                        let z2 = zs[k].dot(&zs[k]);
+                        (k, if z2 > 0.0 { w * n as f64 / z2 } else { 0.0 })
+                    }
+                })
+                .collect();
+            if separable {
+                for i in 0..n {
+                    let rank_mu: f64 = rank_weights
+                        .iter()
+                        .map(|&(k, w)| w * ys[k][i].powi(2))
+                        .sum();
+                    c_diag[i] = decay * c_diag[i] + p.c1 * pc[i] * pc[i] + p.cmu * rank_mu;
+                }
+            } else {
+                for i in 0..n {
+                    for j in 0..=i {
+                        let rank_mu: f64 = rank_weights
+                            .iter()
+                            .map(|&(k, w)| w * ys[k][i] * ys[k][j])
+                            .sum();
+                        let value = decay * c[[i, j]] + p.c1 * pc[i] * pc[j] + p.cmu * rank_mu;
+                        c[[i, j]] = value;
+                        c[[j, i]] = value;
+                    }
+                }
+                c_diag.assign(&c.diag());
+            }
+
+            // Step size, enlarged on flat fitness
+            sigma *= ((p.cs / p.damps) * (ps_norm / p.chi_n - 1.0))
+                .min(1.0)
+                .exp();
+            let kth = ((0.1 + lambda as f64 / 4.0).ceil() as usize).min(lambda - 1);
+            if values[order[0]] == values[order[kth]] {
+                sigma *= (0.2 + p.cs / p.damps).exp();
+            }
+
+            // Factorize C = B diag(d²) Bᵀ
+            generation += 1;
+            if separable {
+                d = c_diag.mapv(|v| v.max(0.0).sqrt());
+            } else if generation - eigen_generation >= p.eigen_interval {
+                eigen_generation = generation;
+                let (eigenvalues, eigenvectors) = symmetric_eigen(c.clone());
+                let largest = eigenvalues.fold(0.0f64, |a, &v| a.max(v));
+                d = eigenvalues.mapv(|v| v.max(largest * 1e-20).sqrt());
+                b = eigenvectors;
+            }
+
+            // Termination
+            if options.ftarget.is_some_and(|target| self.best_f <= target) {
+                return Stop::Target;
+            }
+            if !sigma.is_finite()
+                || sigma * d.fold(0.0f64, |a, &v| a.max(v)) > 1e4 * sigma0 * max_d0
+            {
+                return Stop::TolXUp;
+            }
+            let max_d = d.fold(0.0f64, |a, &v| a.max(v));
+            let min_d = d.fold(f64::INFINITY, |a, &v| a.min(v));
+            if min_d <= 0.0 || (max_d / min_d).powi(2) > 1e14 {
+                return Stop::ConditionCov;
+            }
+
+            if history.len() == history_len {
+                history.pop_front();
+            }
+            history.push_back(values[order[0]]);
+            if history.len() == history_len {
+                let (lo, hi) = history
+                    .iter()
+                    .chain(values.iter())
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                        (lo.min(v), hi.max(v))
+                    });
+                if hi - lo < options.tol {
+                    return Stop::TolFun;
+                }
+            }
+
+            if (0..n).all(|i| {
+                sigma * c_diag[i].max(0.0).sqrt() < options.xtol
+                    && sigma * pc[i].abs() < options.xtol
+            }) {
+                return Stop::TolX;
+            }
+
+            let axis = generation % n;
+            let no_effect_axis = !separable
+                && (0..n).all(|i| mean[i] + 0.1 * sigma * d[axis] * b[[i, axis]] == mean[i]);
+            let no_effect_coord =
+                (0..n).any(|i| mean[i] + 0.2 * sigma * c_diag[i].max(0.0).sqrt() == mean[i]);
+            if no_effect_axis || no_effect_coord {
+                return Stop::NoEffect;
+            }
+        }
+    }
+}
+
+/// Reflect `x` into the interval `[lb, ub]`
+fn mirror(x: f64, (lb, ub): (f64, f64)) -> f64 {
+    match (lb.is_finite(), ub.is_finite()) {
+        (true, true) => {
+            let width = ub - lb;
+            let t = (x - lb).rem_euclid(2.0 * width);
+            (lb + if t <= width { t } else { 2.0 * width - t }).clamp(lb, ub)
+        }
+        (true, false) => lb + (x - lb).abs(),
+        (false, true) => ub - (ub - x).abs(),
+        (false, false) => x,
+    }
+}
+
+/// Eigenvalues and eigenvectors (columns) of a symmetric matrix by cyclic Jacobi rotations
+fn symmetric_eigen(mut a: Array2<f64>) -> (Array1<f64>, Array2<f64>) {
+    let n = a.nrows();
+    let mut v = Array2::<f64>::eye(n);
+    let total: f64 = a.iter().map(|x| x * x).sum();
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[[i, j]].powi(2))
+            .sum();
+        if off <= 1e-30 * total || off == 0.0 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[[p, q]];
+                if apq.abs() <= 1e-300 {
+                    continue;
+                }
+                let theta = (a[[q, q]] - a[[p, p]]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (akp, akq) = (a[[k, p]], a[[k, q]]);
+                    a[[k, p]] = c * akp - s * akq;
+                    a[[k, q]] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[[p, k]], a[[q, k]]);
+                    a[[p, k]] = c * apk - s * aqk;
+                    a[[q, k]] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[[k, p]], v[[k, q]]);
+                    v[[k, p]] = c * vkp - s * vkq;
+                    v[[k, q]] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    (a.diag().to_owned(), v)
+}
+
+/// Minimize a function using the covariance matrix adaptation evolution strategy
+///
+/// CMA-ES is a derivative-free method for non-smooth, non-separable and
+/// badly scaled problems. It is invariant to order-preserving transformations
+/// of the function values and, through the covariance matrix, to linear
+/// transformations of the variables. With IPOP or BIPOP restarts it is also
+/// effective on multimodal functions. All candidates of a generation are
+/// evaluated as one batch, in parallel when `options.parallel` is set; the
+/// result for a given seed does not depend on whether they are.
+///
+/// # Arguments
+///
+/// * `func` - Function to minimize
+/// * `bounds` - Lower and upper bound of each variable; infinite bounds are allowed
+/// * `options` - Options for the algorithm
+///
+/// # Returns
+///
+/// * The best point found over all runs. `success` is true if a run
+///   converged or `ftarget` was reached, `nit` counts the generations and
+///   `nfev` the function evaluations of all runs.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::ArrayView1;
+/// use scirs2_optimize::global::{cma_es, CmaEsOptions};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // A rotated, badly scaled ellipsoid
+/// let ellipsoid = |x: &ArrayView1<f64>| {
+///     let u = x[0] + x[1];
+///     let v = x[0] - x[1];
+///     u * u + 1e4 * v * v
+/// };
+///
+/// let options = CmaEsOptions {
+///     seed: Some(7),
+///     ..Default::default()
+/// };
+/// let result = cma_es(ellipsoid, vec![(-5.0, 5.0); 2], Some(options))?;
+///
+/// assert!(result.success);
+/// assert!(result.fun < 1e-8);
+/// # Ok(())
+/// # }
+/// ```
+pub fn cma_es<F>(
+    func: F,
+    bounds: Bounds,
+    options: Option<CmaEsOptions>,
+) -> Result<OptimizeResult<f64>, OptimizeError>
+where
+    F: Fn(&ArrayView1<f64>) -> f64 + Clone + Sync,
+{
+    let options = options.unwrap_or_default();
+    let n = bounds.len();
+    if n == 0 {
+        return Err(OptimizeError::ValueError(
+            "At least one variable is required".to_string(),
+        ));
+    }
+    if bounds
+        .iter()
+        .any(|&(lb, ub)| lb.is_nan() || ub.is_nan() || lb >= ub)
+    {
+        return Err(OptimizeError::ValueError(
+            "Bounds must satisfy lower < upper".to_string(),
+        ));
+    }
+    let finite = bounds
+        .iter()
+        .all(|&(lb, ub)| lb.is_finite() && ub.is_finite());
+    match &options.x0 {
+        Some(x0) => {
+            if x0.len() != n {
+                return Err(OptimizeError::ValueError(format!(
+                    "x0 has {} elements but there are {} bounds",
+                    x0.len(),
+                    n
+                )));
+            }
+            if x0
+                .iter()
+                .zip(&bounds)
+                .any(|(&x, &(lb, ub))| !(lb..=ub).contains(&x))
+            {
+                return Err(OptimizeError::ValueError(
+                    "x0 must lie within the bounds".to_string(),
+                ));
+            }
+        }
+        None if !finite => {
+            return Err(OptimizeError::ValueError(
+                "x0 is required when the bounds are not finite".to_string(),
+            ));
+        }
+        None => {}
+    }
+    let (sigma0, scale) = match options.sigma0 {
+        Some(sigma0) if sigma0.is_finite() && sigma0 > 0.0 => (sigma0, Array1::ones(n)),
+        Some(_) => {
+            return Err(OptimizeError::ValueError(
+                "sigma0 must be positive and finite".to_string(),
+            ));
+        }
+        None if !finite => {
+            return Err(OptimizeError::ValueError(
+                "sigma0 is required when the bounds are not finite".to_string(),
+            ));
+        }
+        None => (
+            0.3,
+            Array1::from_iter(bounds.iter().map(|&(lb, ub)| ub - lb)),
+        ),
+    };
+    let popsize = options
+        .popsize
+        .unwrap_or(4 + (3.0 * (n as f64).ln()).floor() as usize);
+    if popsize < 2 {
+        return Err(OptimizeError::ValueError(
+            "Population size must be at least 2".to_string(),
+        ));
+    }
+    if options.popsize_increase.is_nan() || options.popsize_increase < 1.0 {
+        return Err(OptimizeError::ValueError(
+            "popsize_increase must be at least 1".to_string(),
+        ));
+    }
+    if options.maxfev.is_some_and(|m| m < popsize) {
+        return Err(OptimizeError::ValueError(
+            "maxfev must be at least the population size".to_string(),
+        ));
+    }
+
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut search = CmaEs {
+        func: &func,
+        bounds: &bounds,
+        options: &options,
+        scale,
+        rng: StdRng::seed_from_u64(seed),
+        nfev: 0,
+        nit: 0,
+        best_x: None,
+        best_f: f64::INFINITY,
+    };
+
+    let x0 = match &options.x0 {
+        Some(x0) => x0.clone(),
+        None => search.start_point(),
+    };
+    let mut stop = search.run(x0, sigma0, popsize, None);
+    let mut converged = stop.converged();
+    let grown = |restarts: usize| {
+        (popsize as f64 * options.popsize_increase.powi(restarts as i32)).round() as usize
+    };
+    match options.restart {
+        RestartStrategy::None => {}
+        RestartStrategy::Ipop => {
+            for restart in 1..=options.max_restarts {
+                if stop.is_final() {
+                    break;
+                }
+                let start = search.start_point();
+                stop = search.run(start, sigma0, grown(restart), None);
+                converged |= stop.converged();
+            }
+        }
+        RestartStrategy::Bipop => {
+            // The first run counts towards the large population regime
+            let mut large_budget = search.nfev;
+            let mut last_large_evals = search.nfev;
+            let mut small_budget = 0;
+            let mut large_restarts = 0;
+            while large_restarts < options.max_restarts && !stop.is_final() {
+                let start = search.start_point();
+                let before = search.nfev;
+                if small_budget < large_budget {
+                    // Population between the default and half the next large
+                    // one, with a step size down to 1% of the default
+                    let u: f64 = search.rng.random();
+                    let ratio = 0.5 * grown(large_restarts + 1) as f64 / popsize as f64;
+                    let lambda =
+                        ((popsize as f64 * ratio.max(1.0).powf(u * u)).floor() as usize).max(2);
+                    let sigma = sigma0 * 10f64.powf(-2.0 * u);
+                    let budget = (last_large_evals / 2).max(lambda);
+                    stop = search.run(start, sigma, lambda, Some(budget));
+                    small_budget += search.nfev - before;
+                } else {
+                    large_restarts += 1;
+                    stop = search.run(start, sigma0, grown(large_restarts), None);
+                    last_large_evals = search.nfev - before;
+                    large_budget += last_large_evals;
+                }
+                converged |= stop.converged();
+            }
+        }
+    }
+
+    let success = converged;
+    let message = if success && stop != Stop::Target {
+        Stop::TolFun.message()
+    } else {
+        stop.message()
+    };
+    Ok(OptimizeResult {
+        x: search.best_x.unwrap_or_else(|| Array1::zeros(n)),
+        fun: search.best_f,
+        iterations: search.nit,
+        nit: search.nit,
+        func_evals: search.nfev,
+        nfev: search.nfev,
+        success,
+        message: message.to_string(),
+        ..Default::default()
+    })
+}
@@ -1,16 +1,20 @@
 //! Global optimization algorithms
 //!
 //! This module provides various global optimization algorithms for finding
-//! the global minimum of a multivariate function.
+//! the global minimum of a multivariate function, and evolutionary methods
+//! for approximating the Pareto front of multi-objective problems.
 
 #[allow(dead_code)]
 mod basinhopping;
 #[allow(dead_code)]
 mod bayesian;
 #[allow(dead_code)]
+mod cma_es;
+#[allow(dead_code)]
 mod differential_evolution;
 #[allow(dead_code)]
 mod dual_annealing;
+mod multi_objective;
 #[allow(dead_code)]
 mod multi_start;
 #[allow(dead_code)]
@@ -26,8 +30,13 @@ pub use bayesian::{
     bayesian_optimization, AcquisitionFunctionType, BayesianOptimizationOptions, BayesianOptimizer,
     InitialPointGenerator, KernelType, Parameter, Space,
 };
+pub use cma_es::{cma_es, CmaEsOptions, CmaEsVariant, RestartStrategy};
 pub use differential_evolution::{differential_evolution, DifferentialEvolutionOptions};
 pub use dual_annealing::{dual_annealing, DualAnnealingOptions};
+pub use multi_objective::{
+    hypervolume, inverted_generational_distance, moead, nsga2, nsga3, reference_directions,
+    Decomposition, MultiObjectiveOptions, MultiObjectiveResult,
+};
 pub use multi_start::{multi_start, MultiStartOptions, StartingPointStrategy};
 pub use particle_swarm::{particle_swarm, ParticleSwarmOptions};
 pub use simulated_annealing::{simulated_annealing, SimulatedAnnealingOptions};
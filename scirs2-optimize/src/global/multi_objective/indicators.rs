@@ -0,0 +1,165 @@
+//! Quality indicators for approximations of a Pareto front
+
+use crate::error::OptimizeError;
+use ndarray::{ArrayView1, ArrayView2};
+
+/// Hypervolume dominated by a set of points
+///
+/// Computes the volume of the region that is dominated by at least one point
+/// of `front` and dominates `reference`, for minimization. Points that do not
+/// strictly dominate the reference point contribute nothing. The volume is
+/// computed exactly by slicing along the last objective, which takes
+/// `O(n^(M-1) log n)` time for `n` points and `M` objectives.
+///
+/// # Arguments
+///
+/// * `front` - Objective vectors, one per row
+/// * `reference` - Reference point
+///
+/// # Returns
+///
+/// * The hypervolume
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_optimize::global::hypervolume;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let front = array![[1.0, 3.0], [2.0, 2.0], [3.0, 1.0]];
+/// let volume = hypervolume(&front.view(), &array![4.0, 4.0].view())?;
+/// assert!((volume - 6.0).abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+pub fn hypervolume(
+    front: &ArrayView2<f64>,
+    reference: &ArrayView1<f64>,
+) -> Result<f64, OptimizeError> {
+    if front.ncols() != reference.len() {
+        return Err(OptimizeError::ValueError(format!(
+            "Front has {} objectives but the reference point has {}",
+            front.ncols(),
+            reference.len()
+        )));
+    }
+    if reference.is_empty() {
+        return Ok(0.0);
+    }
+    let points: Vec<Vec<f64>> = front
+        .rows()
+        .into_iter()
+        .filter(|row| row.iter().zip(reference).all(|(p, r)| p < r))
+        .map(|row| row.to_vec())
+        .collect();
+    Ok(slice_volume(points, &reference.to_vec()))
+}
+
+/// Hypervolume of points that all strictly dominate the reference point
+fn slice_volume(mut points: Vec<Vec<f64>>, reference: &[f64]) -> f64 {
+    let d = reference.len();
+    if points.is_empty() {
+        return 0.0;
+    }
+    if d == 1 {
+        let best = points.iter().fold(f64::INFINITY, |acc, p| acc.min(p[0]));
+        return reference[0] - best;
+    }
+    if d == 2 {
+        points.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+        let mut volume = 0.0;
+        let mut ceiling = reference[1];
+        for p in &points {
+            if p[1] < ceiling {
+                volume += (reference[0] - p[0]) * (ceiling - p[1]);
+                ceiling = p[1];
+            }
+        }
+        return volume;
+    }
+
+    // Slabs between consecutive values of the last objective
+    points.sort_by(|a, b| a[d - 1].total_cmp(&b[d - 1]));
+    let mut volume = 0.0;
+    for i in 0..points.len() {
+        let top = if i + 1 < points.len() {
+            points[i + 1][d - 1]
+        } else {
+            reference[d - 1]
+        };
+        let depth = top - points[i][d - 1];
+        if depth > 0.0 {
+            let projected: Vec<Vec<f64>> =
+                points[..=i].iter().map(|p| p[..d - 1].to_vec()).collect();
+            volume += depth * slice_volume(projected, &reference[..d - 1]);
+        }
+    }
+    volume
+}
+
+/// Inverted generational distance
+///
+/// The mean Euclidean distance from each point of `reference_front`, usually
+/// a dense sample of the true Pareto front, to its closest point in `front`.
+/// Small values mean the front is both close to and spread along the
+/// reference front.
+///
+/// # Arguments
+///
+/// * `front` - Objective vectors of the approximation, one per row
+/// * `reference_front` - Points of the reference front, one per row
+///
+/// # Returns
+///
+/// * The inverted generational distance
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_optimize::global::inverted_generational_distance;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let front = array![[0.0, 1.0], [1.0, 0.0]];
+/// let reference = array![[0.0, 1.0], [0.5, 0.5], [1.0, 0.0]];
+/// let igd = inverted_generational_distance(&front.view(), &reference.view())?;
+/// assert!((igd - 0.5f64.sqrt() / 3.0).abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+pub fn inverted_generational_distance(
+    front: &ArrayView2<f64>,
+    reference_front: &ArrayView2<f64>,
+) -> Result<f64, OptimizeError> {
+    if front.ncols() != reference_front.ncols() {
+        return Err(OptimizeError::ValueError(format!(
+            "Front has {} objectives but the reference front has {}",
+            front.ncols(),
+            reference_front.ncols()
+        )));
+    }
+    if front.nrows() == 0 || reference_front.nrows() == 0 {
+        return Err(OptimizeError::ValueError(
+            "Fronts must contain at least one point".to_string(),
+        ));
+    }
+    let total: f64 = reference_front
+        .rows()
+        .into_iter()
+        .map(|r| {
+            front
+                .rows()
+                .into_iter()
+                .map(|p| {
+                    p.iter()
+                        .zip(r.iter())
+                        .map(|(a, b)| (a - b).powi(2))
+                        .sum::<f64>()
+                })
+                .fold(f64::INFINITY, f64::min)
+                .sqrt()
+        })
+        .sum();
+    Ok(total / reference_front.nrows() as f64)
+}
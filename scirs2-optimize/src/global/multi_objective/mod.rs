@@ -0,0 +1,719 @@
+//! Multi-objective evolutionary optimization
+//!
+//! These methods approximate the Pareto front of a problem with several
+//! conflicting objectives
+//!
+//! ```text
+//! minimize    (f_1(x), ..., f_M(x))
+//! subject to  g_i(x) ≥ 0,  lb ≤ x ≤ ub
+//! ```
+//!
+//! Three algorithms are provided:
+//!
+//! * [`nsga2`]: NSGA-II, elitist non-dominated sorting with crowding distance
+//! * [`nsga3`]: NSGA-III, non-dominated sorting with niching around a set of
+//!   reference directions, suited to three or more objectives
+//! * [`moead`]: MOEA/D, decomposition into scalar subproblems solved
+//!   cooperatively by neighbouring weight vectors
+//!
+//! All of them use simulated binary crossover and polynomial mutation, which
+//! keep candidates inside the box constraints. General constraints are
+//! handled with constrained domination: feasible solutions are preferred to
+//! infeasible ones, and infeasible solutions are compared by their total
+//! violation. The result holds the non-dominated set of the final population
+//! together with its hypervolume and, when a reference front is supplied, its
+//! inverted generational distance.
+//!
+//! ## Example
+//!
+//! ```
+//! use ndarray::{array, Array1, ArrayView1};
+//! use scirs2_optimize::global::{nsga2, MultiObjectiveOptions};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // Schaffer's problem: the Pareto set is the interval [0, 2]
+//! let schaffer = |x: &ArrayView1<f64>| array![x[0].powi(2), (x[0] - 2.0).powi(2)];
+//!
+//! let options = MultiObjectiveOptions {
+//!     popsize: 40,
+//!     maxiter: 50,
+//!     seed: Some(1),
+//!     ..Default::default()
+//! };
+//! let result = nsga2(
+//!     schaffer,
+//!     vec![(-5.0, 5.0)],
+//!     None::<fn(&ArrayView1<f64>) -> Array1<f64>>,
+//!     Some(options),
+//! )?;
+//!
+//! assert!(result.success);
+//! assert!(result.x.iter().all(|&x| (-0.01..=2.01).contains(&x)));
+//! # Ok(())
+//! # }
+//! ```
+
+mod indicators;
+mod moead;
+mod nsga;
+
+pub use indicators::{hypervolume, inverted_generational_distance};
+
+use crate::error::OptimizeError;
+use crate::parallel::{ParallelOptions, WorkerPool};
+use ndarray::{Array1, Array2, ArrayView1};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+/// Bounds for variables
+pub type Bounds = Vec<(f64, f64)>;
+
+/// Scalarizing function used by MOEA/D
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decomposition {
+    /// Weighted Tchebycheff distance to the ideal point
+    Tchebycheff,
+    /// Penalty-based boundary intersection with penalty parameter `theta`
+    PenaltyBoundaryIntersection { theta: f64 },
+}
+
+/// Options for multi-objective optimization
+#[derive(Debug, Clone)]
+pub struct MultiObjectiveOptions {
+    /// Population size; NSGA-III enlarges it to the number of reference
+    /// directions if necessary and MOEA/D keeps one solution per direction
+    pub popsize: usize,
+    /// Number of generations
+    pub maxiter: usize,
+    /// Probability of applying crossover to a pair of parents
+    pub crossover_prob: f64,
+    /// Distribution index of simulated binary crossover
+    pub crossover_eta: f64,
+    /// Probability of mutating each variable (None = 1 / number of variables)
+    pub mutation_prob: Option<f64>,
+    /// Distribution index of polynomial mutation
+    pub mutation_eta: f64,
+    /// Number of divisions per objective of the reference directions used by
+    /// NSGA-III and MOEA/D (None = as many as the population size allows)
+    pub reference_divisions: Option<usize>,
+    /// Number of neighbouring subproblems in MOEA/D
+    pub neighborhood_size: usize,
+    /// Probability that MOEA/D selects parents from the neighbourhood rather
+    /// than the whole population
+    pub neighbor_selection_prob: f64,
+    /// Maximum number of solutions an offspring replaces in MOEA/D
+    pub max_replacements: usize,
+    /// Scalarizing function of MOEA/D
+    pub decomposition: Decomposition,
+    /// Reference point of the hypervolume (None = nadir of the returned
+    /// front, moved outwards by 10% of its extent)
+    pub hypervolume_reference: Option<Array1<f64>>,
+    /// Points of the true Pareto front for the inverted generational distance
+    pub reference_front: Option<Array2<f64>>,
+    /// Random seed for reproducibility
+    pub seed: Option<u64>,
+    /// Parallel computation options
+    pub parallel: Option<ParallelOptions>,
+}
+
+impl Default for MultiObjectiveOptions {
+    fn default() -> Self {
+        Self {
+            popsize: 100,
+            maxiter: 200,
+            crossover_prob: 0.9,
+            crossover_eta: 20.0,
+            mutation_prob: None,
+            mutation_eta: 20.0,
+            reference_divisions: None,
+            neighborhood_size: 20,
+            neighbor_selection_prob: 0.9,
+            max_replacements: 2,
+            decomposition: Decomposition::Tchebycheff,
+            hypervolume_reference: None,
+            reference_front: None,
+            seed: None,
+            parallel: None,
+        }
+    }
+}
+
+/// Result of multi-objective optimization
+#[derive(Debug, Clone)]
+pub struct MultiObjectiveResult {
+    /// Non-dominated decision vectors, one per row, sorted by the first objective
+    pub x: Array2<f64>,
+    /// Objective values of the non-dominated solutions, one per row
+    pub f: Array2<f64>,
+    /// Total constraint violation of each solution (zero when feasible)
+    pub constraint_violation: Array1<f64>,
+    /// Hypervolume dominated by the front
+    pub hypervolume: f64,
+    /// Reference point used for the hypervolume
+    pub hypervolume_reference: Array1<f64>,
+    /// Inverted generational distance to `reference_front`, if one was given
+    pub igd: Option<f64>,
+    /// Number of generations performed
+    pub nit: usize,
+    /// Number of function evaluations
+    pub nfev: usize,
+    /// Whether a feasible solution was found
+    pub success: bool,
+    /// Status message
+    pub message: String,
+}
+
+/// A member of the population
+#[derive(Debug, Clone)]
+struct Individual {
+    x: Array1<f64>,
+    f: Vec<f64>,
+    /// Total constraint violation
+    cv: f64,
+}
+
+impl Individual {
+    fn is_feasible(&self) -> bool {
+        self.cv == 0.0
+    }
+}
+
+/// Evaluates batches of candidates, in parallel if requested
+struct Evaluator<'a, F, C> {
+    func: &'a F,
+    constraints: Option<&'a C>,
+    pool: Option<WorkerPool>,
+    n_obj: Option<usize>,
+    nfev: usize,
+}
+
+impl<F, C> Evaluator<'_, F, C>
+where
+    F: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+    C: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+{
+    fn evaluate(&mut self, candidates: Vec<Array1<f64>>) -> Result<Vec<Individual>, OptimizeError> {
+        let evaluate_one = |x: &Array1<f64>| {
+            let f = (self.func)(&x.view());
+            let g = self.constraints.map(|c| c(&x.view()));
+            (f, g)
+        };
+        let values: Vec<(Array1<f64>, Option<Array1<f64>>)> = match &self.pool {
+            Some(pool) => pool.map_batch(evaluate_one, &candidates),
+            None => candidates.iter().map(evaluate_one).collect(),
+        };
+        self.nfev += candidates.len();
+
+        let mut population = Vec::with_capacity(candidates.len());
+        for (x, (f, g)) in candidates.into_iter().zip(values) {
+            let n_obj = *self.n_obj.get_or_insert(f.len());
+            if f.len() != n_obj {
+                return Err(OptimizeError::ValueError(format!(
+                    "Objective function returned {} values, expected {}",
+                    f.len(),
+                    n_obj
+                )));
+            }
+            // Constraints are satisfied when g_i(x) ≥ 0; non-finite
+            // objectives make a candidate infeasible
+            let mut cv = g.map_or(0.0, |g| {
+                g.iter()
+                    .map(|&v| {
+                        if v.is_nan() {
+                            f64::INFINITY
+                        } else {
+                            (-v).max(0.0)
+                        }
+                    })
+                    .sum()
+            });
+            if f.iter().any(|v| !v.is_finite()) {
+                cv = f64::INFINITY;
+            }
+            population.push(Individual {
+                x,
+                f: f.to_vec(),
+                cv,
+            });
+        }
+        Ok(population)
+    }
+}
+
+/// State shared by the algorithms: problem, options, random numbers and evaluations
+struct Optimizer<'a, F, C> {
+    bounds: &'a [(f64, f64)],
+    options: &'a MultiObjectiveOptions,
+    mutation_prob: f64,
+    rng: StdRng,
+    evaluator: Evaluator<'a, F, C>,
+}
+
+impl<'a, F, C> Optimizer<'a, F, C>
+where
+    F: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+    C: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+{
+    fn new(
+        func: &'a F,
+        bounds: &'a [(f64, f64)],
+        constraints: Option<&'a C>,
+        options: &'a MultiObjectiveOptions,
+    ) -> Result<Self, OptimizeError> {
+        if bounds.is_empty() {
+            return Err(OptimizeError::ValueError(
+                "At least one variable is required".to_string(),
+            ));
+        }
+        if bounds
+            .iter()
+            .any(|&(lb, ub)| !(lb.is_finite() && ub.is_finite() && lb < ub))
+        {
+            return Err(OptimizeError::ValueError(
+                "Bounds must be finite with lower < upper".to_string(),
+            ));
+        }
+        if options.popsize < 4 {
+            return Err(OptimizeError::ValueError(
+                "Population size must be at least 4".to_string(),
+            ));
+        }
+        let mutation_prob = options.mutation_prob.unwrap_or(1.0 / bounds.len() as f64);
+        let probabilities = [
+            options.crossover_prob,
+            mutation_prob,
+            options.neighbor_selection_prob,
+        ];
+        if probabilities.iter().any(|p| !(0.0..=1.0).contains(p)) {
+            return Err(OptimizeError::ValueError(
+                "Probabilities must lie in [0, 1]".to_string(),
+            ));
+        }
+        if !(options.crossover_eta >= 0.0 && options.mutation_eta >= 0.0) {
+            return Err(OptimizeError::ValueError(
+                "Distribution indices must be nonnegative".to_string(),
+            ));
+        }
+
+        let seed = options.seed.unwrap_or_else(rand::random);
+        Ok(Self {
+            bounds,
+            options,
+            mutation_prob,
+            rng: StdRng::seed_from_u64(seed),
+            evaluator: Evaluator {
+                func,
+                constraints,
+                pool: options.parallel.as_ref().map(WorkerPool::new).transpose()?,
+                n_obj: None,
+                nfev: 0,
+            },
+        })
+    }
+
+    /// Evaluate a uniformly distributed initial population
+    fn initial_population(&mut self, size: usize) -> Result<Vec<Individual>, OptimizeError> {
+        let candidates: Vec<Array1<f64>> = (0..size)
+            .map(|_| {
+                Array1::from_iter(
+                    self.bounds
+                        .iter()
+                        .map(|&(lb, ub)| self.rng.random_range(lb..ub)),
+                )
+            })
+            .collect();
+        let population = self.evaluator.evaluate(candidates)?;
+        let n_obj = self.n_obj();
+        if n_obj < 2 {
+            return Err(OptimizeError::ValueError(
+                "Multi-objective optimization requires at least two objectives".to_string(),
+            ));
+        }
+        Ok(population)
+    }
+
+    fn n_obj(&self) -> usize {
+        self.evaluator.n_obj.unwrap_or(0)
+    }
+
+    /// Two offspring from simulated binary crossover and polynomial mutation
+    fn offspring(&mut self, a: &Array1<f64>, b: &Array1<f64>) -> (Array1<f64>, Array1<f64>) {
+        let (mut c1, mut c2) = (a.clone(), b.clone());
+        if self.rng.random::<f64>() < self.options.crossover_prob {
+            self.crossover(&mut c1, &mut c2);
+        }
+        self.mutate(&mut c1);
+        self.mutate(&mut c2);
+        (c1, c2)
+    }
+
+    /// Bounded simulated binary crossover (Deb and Agrawal)
+    fn crossover(&mut self, c1: &mut Array1<f64>, c2: &mut Array1<f64>) {
+        let eta = self.options.crossover_eta;
+        for (j, &(lb, ub)) in self.bounds.iter().enumerate() {
+            if self.rng.random::<f64>() > 0.5 || (c1[j] - c2[j]).abs() <= 1e-14 {
+                continue;
+            }
+            let (y1, y2) = (c1[j].min(c2[j]), c1[j].max(c2[j]));
+            let u = self.rng.random::<f64>();
+            let spread = |beta: f64| {
+                let alpha = 2.0 - beta.powf(-(eta + 1.0));
+                if u <= 1.0 / alpha {
+                    (u * alpha).powf(1.0 / (eta + 1.0))
+                } else {
+                    (1.0 / (2.0 - u * alpha)).powf(1.0 / (eta + 1.0))
+                }
+            };
+            let beta_low = spread(1.0 + 2.0 * (y1 - lb) / (y2 - y1));
+            let beta_high = spread(1.0 + 2.0 * (ub - y2) / (y2 - y1));
+            let low = (0.5 * (y1 + y2 - beta_low * (y2 - y1))).clamp(lb, ub);
+            let high = (0.5 * (y1 + y2 + beta_high * (y2 - y1))).clamp(lb, ub);
+            if self.rng.random::<f64>() < 0.5 {
+                c1[j] = low;
+                c2[j] = high;
+            } else {
+                c1[j] = high;
+                c2[j] = low;
+            }
+        }
+    }
+
+    /// Bounded polynomial mutation (Deb and Goyal)
+    fn mutate(&mut self, x: &mut Array1<f64>) {
+        let eta = self.options.mutation_eta;
+        for (j, &(lb, ub)) in self.bounds.iter().enumerate() {
+            if self.rng.random::<f64>() >= self.mutation_prob {
+                continue;
+            }
+            let range = ub - lb;
+            let u = self.rng.random::<f64>();
+            let power = 1.0 / (eta + 1.0);
+            let delta = if u < 0.5 {
+                let xy = 1.0 - (x[j] - lb) / range;
+                (2.0 * u + (1.0 - 2.0 * u) * xy.powf(eta + 1.0)).powf(power) - 1.0
+            } else {
+                let xy = 1.0 - (ub - x[j]) / range;
+                1.0 - (2.0 * (1.0 - u) + 2.0 * (u - 0.5) * xy.powf(eta + 1.0)).powf(power)
+            };
+            x[j] = (x[j] + delta * range).clamp(lb, ub);
+        }
+    }
+
+    /// Assemble the result from the final population
+    fn finish(
+        &self,
+        population: Vec<Individual>,
+        nit: usize,
+    ) -> Result<MultiObjectiveResult, OptimizeError> {
+        let n_obj = self.n_obj();
+        let feasible: Vec<Individual> = population
+            .iter()
+            .filter(|ind| ind.is_feasible())
+            .cloned()
+            .collect();
+        let success = !feasible.is_empty();
+
+        let mut front: Vec<Individual> = if success {
+            let first = non_dominated_sort(&feasible).swap_remove(0);
+            first.into_iter().map(|i| feasible[i].clone()).collect()
+        } else {
+            // Least violating solutions
+            let least = population
+                .iter()
+                .fold(f64::INFINITY, |acc, ind| acc.min(ind.cv));
+            population
+                .into_iter()
+                .filter(|ind| ind.cv == least)
+                .collect()
+        };
+        front.sort_by(|a, b| a.f[0].total_cmp(&b.f[0]));
+        front.dedup_by(|a, b| a.x == b.x);
+
+        let n = self.bounds.len();
+        let x = Array2::from_shape_fn((front.len(), n), |(i, j)| front[i].x[j]);
+        let f = Array2::from_shape_fn((front.len(), n_obj), |(i, j)| front[i].f[j]);
+        let constraint_violation = Array1::from_iter(front.iter().map(|ind| ind.cv));
+
+        let hypervolume_reference = match &self.options.hypervolume_reference {
+            Some(reference) => {
+                if reference.len() != n_obj {
+                    return Err(OptimizeError::ValueError(format!(
+                        "Hypervolume reference point must have {} entries",
+                        n_obj
+                    )));
+                }
+                reference.clone()
+            }
+            None => Array1::from_shape_fn(n_obj, |j| {
+                let column = f.column(j);
+                let nadir = column.fold(f64::NEG_INFINITY, |acc, &v| acc.max(v));
+                let ideal = column.fold(f64::INFINITY, |acc, &v| acc.min(v));
+                let extent = if nadir > ideal {
+                    nadir - ideal
+                } else {
+                    nadir.abs().max(1.0)
+                };
+                nadir + 0.1 * extent
+            }),
+        };
+        let hypervolume = if success {
+            hypervolume(&f.view(), &hypervolume_reference.view())?
+        } else {
+            0.0
+        };
+        let igd = match &self.options.reference_front {
+            Some(reference) if success => Some(inverted_generational_distance(
+                &f.view(),
+                &reference.view(),
+            )?),
+            _ => None,
+        };
+
+        Ok(MultiObjectiveResult {
+            x,
+            f,
+            constraint_violation,
+            hypervolume,
+            hypervolume_reference,
+            igd,
+            nit,
+            nfev: self.evaluator.nfev,
+            success,
+            message: if success {
+                "Maximum number of generations reached"
+            } else {
+                "No feasible solution found"
+            }
+            .to_string(),
+        })
+    }
+}
+
+/// Whether `a` dominates `b` in the constrained sense of Deb
+fn constrained_dominates(a: &Individual, b: &Individual) -> bool {
+    if a.is_feasible() != b.is_feasible() {
+        return a.is_feasible();
+    }
+    if !a.is_feasible() {
+        return a.cv < b.cv;
+    }
+    let mut strictly = false;
+    for (fa, fb) in a.f.iter().zip(&b.f) {
+        if fa > fb {
+            return false;
+        }
+        strictly |= fa < fb;
+    }
+    strictly
+}
+
+/// Fast non-dominated sorting into fronts of indices
+fn non_dominated_sort(population: &[Individual]) -> Vec<Vec<usize>> {
+    let n = population.len();
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut domination_count = vec![0usize; n];
+    for i in 0..n {
+        for j in i + 1..n {
+            if constrained_dominates(&population[i], &population[j]) {
+                dominated[i].push(j);
+                domination_count[j] += 1;
+            } else if constrained_dominates(&population[j], &population[i]) {
+                dominated[j].push(i);
+                domination_count[i] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut current: Vec<usize> = (0..n).filter(|&i| domination_count[i] == 0).collect();
+    while !current.is_empty() {
+        let mut next = Vec::new();
+        for &i in &current {
+            for &j in &dominated[i] {
+                domination_count[j] -= 1;
+                if domination_count[j] == 0 {
+                    next.push(j);
+                }
+            }
+        }
+        next.sort_unstable();
+        fronts.push(current);
+        current = next;
+    }
+    fronts
+}
+
+/// Das and Dennis reference directions on the unit simplex
+///
+/// # Arguments
+///
+/// * `n_obj` - Number of objectives
+/// * `divisions` - Number of divisions along each objective axis
+///
+/// # Returns
+///
+/// * All `C(n_obj + divisions - 1, divisions)` points with coordinates in
+///   multiples of `1 / divisions` that sum to one, one per row
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_optimize::global::reference_directions;
+///
+/// let directions = reference_directions(3, 4);
+/// assert_eq!(directions.nrows(), 15);
+/// for row in directions.rows() {
+///     assert!((row.sum() - 1.0).abs() < 1e-12);
+/// }
+/// ```
+pub fn reference_directions(n_obj: usize, divisions: usize) -> Array2<f64> {
+    fn recurse(
+        remaining: usize,
+        depth: usize,
+        n_obj: usize,
+        current: &mut Vec<usize>,
+        out: &mut Vec<Vec<usize>>,
+    ) {
+        if depth == n_obj - 1 {
+            current.push(remaining);
+            out.push(current.clone());
+            current.pop();
+            return;
+        }
+        for k in 0..=remaining {
+            current.push(k);
+            recurse(remaining - k, depth + 1, n_obj, current, out);
+            current.pop();
+        }
+    }
+
+    if n_obj == 0 {
+        return Array2::zeros((0, 0));
+    }
+    if divisions == 0 {
+        return Array2::from_elem((1, n_obj), 1.0 / n_obj as f64);
+    }
+    let mut points = Vec::new();
+    recurse(divisions, 0, n_obj, &mut Vec::new(), &mut points);
+    Array2::from_shape_fn((points.len(), n_obj), |(i, j)| {
+        points[i][j] as f64 / divisions as f64
+    })
+}
+
+/// Number of Das and Dennis directions, `C(n_obj + divisions - 1, divisions)`
+fn count_directions(n_obj: usize, divisions: usize) -> usize {
+    let mut count = 1usize;
+    for k in 1..=divisions {
+        count = count.saturating_mul(n_obj + k - 1) / k;
+    }
+    count
+}
+
+/// Reference directions from the options, by default with the most divisions
+/// whose direction count does not exceed the population size
+fn default_directions(n_obj: usize, options: &MultiObjectiveOptions) -> Array2<f64> {
+    let divisions = options.reference_divisions.unwrap_or_else(|| {
+        let mut divisions = 1;
+        while count_directions(n_obj, divisions + 1) <= options.popsize {
+            divisions += 1;
+        }
+        divisions
+    });
+    reference_directions(n_obj, divisions)
+}
+
+/// Perform multi-objective optimization with NSGA-II
+///
+/// # Arguments
+///
+/// * `func` - Vector of objectives to minimize
+/// * `bounds` - Lower and upper bound of every variable
+/// * `constraints` - Optional constraint functions, satisfied when every
+///   component is nonnegative
+/// * `options` - Algorithm options
+///
+/// # Returns
+///
+/// * `MultiObjectiveResult` with the non-dominated solutions of the final
+///   population and their quality indicators
+pub fn nsga2<F, C>(
+    func: F,
+    bounds: Bounds,
+    constraints: Option<C>,
+    options: Option<MultiObjectiveOptions>,
+) -> Result<MultiObjectiveResult, OptimizeError>
+where
+    F: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+    C: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+{
+    let options = options.unwrap_or_default();
+    let mut optimizer = Optimizer::new(&func, &bounds, constraints.as_ref(), &options)?;
+    nsga::run_nsga2(&mut optimizer)
+}
+
+/// Perform multi-objective optimization with NSGA-III
+///
+/// Survivors of the last accepted front are chosen by niching around the
+/// reference directions of [`reference_directions`] in the normalized
+/// objective space, which keeps the front well spread for many objectives.
+///
+/// # Arguments
+///
+/// * `func` - Vector of objectives to minimize
+/// * `bounds` - Lower and upper bound of every variable
+/// * `constraints` - Optional constraint functions, satisfied when every
+///   component is nonnegative
+/// * `options` - Algorithm options; `reference_divisions` sets the directions
+///
+/// # Returns
+///
+/// * `MultiObjectiveResult` with the non-dominated solutions of the final
+///   population and their quality indicators
+pub fn nsga3<F, C>(
+    func: F,
+    bounds: Bounds,
+    constraints: Option<C>,
+    options: Option<MultiObjectiveOptions>,
+) -> Result<MultiObjectiveResult, OptimizeError>
+where
+    F: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+    C: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+{
+    let options = options.unwrap_or_default();
+    let mut optimizer = Optimizer::new(&func, &bounds, constraints.as_ref(), &options)?;
+    nsga::run_nsga3(&mut optimizer)
+}
+
+/// Perform multi-objective optimization with MOEA/D
+///
+/// Every reference direction defines a scalar subproblem through
+/// `options.decomposition`. Offspring are bred from neighbouring subproblems
+/// and replace neighbours they improve; each generation's offspring are
+/// evaluated as one batch.
+///
+/// # Arguments
+///
+/// * `func` - Vector of objectives to minimize
+/// * `bounds` - Lower and upper bound of every variable
+/// * `constraints` - Optional constraint functions, satisfied when every
+///   component is nonnegative
+/// * `options` - Algorithm options
+///
+/// # Returns
+///
+/// * `MultiObjectiveResult` with the non-dominated solutions of the final
+///   population and their quality indicators
+pub fn moead<F, C>(
+    func: F,
+    bounds: Bounds,
+    constraints: Option<C>,
+    options: Option<MultiObjectiveOptions>,
+) -> Result<MultiObjectiveResult, OptimizeError>
+where
+    F: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+    C: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+{
+    let options = options.unwrap_or_default();
+    let mut optimizer = Optimizer::new(&func, &bounds, constraints.as_ref(), &options)?;
+    moead::run(&mut optimizer)
+}
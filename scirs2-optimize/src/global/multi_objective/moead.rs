@@ -0,0 +1,164 @@
+//! MOEA/D
+//!
+//! Each reference direction `λ` defines the scalar subproblem of minimizing
+//! the decomposition `g(f(x) | λ, z*)` relative to the ideal point `z*`. One
+//! solution is kept per subproblem. Parents are drawn from the `T` closest
+//! directions, and an offspring replaces at most `max_replacements`
+//! neighbours whose subproblem it improves. The offspring of a generation are
+//! bred from the current population and evaluated together, so that they can
+//! be evaluated in parallel.
+//!
+//! Reference: Q. Zhang and H. Li, "MOEA/D: A multiobjective evolutionary
+//! algorithm based on decomposition", IEEE TEVC 11 (2007).
+
+use super::{default_directions, Decomposition, Individual, MultiObjectiveResult, Optimizer};
+use crate::error::OptimizeError;
+use ndarray::{Array1, ArrayView1};
+use rand::prelude::*;
+
+/// Smallest weight used by the Tchebycheff decomposition
+const MIN_WEIGHT: f64 = 1e-6;
+
+/// Run MOEA/D
+pub(super) fn run<F, C>(
+    optimizer: &mut Optimizer<'_, F, C>,
+) -> Result<MultiObjectiveResult, OptimizeError>
+where
+    F: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+    C: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+{
+    let options = optimizer.options;
+    if options.neighborhood_size < 2 {
+        return Err(OptimizeError::ValueError(
+            "Neighborhood size must be at least 2".to_string(),
+        ));
+    }
+    if let Decomposition::PenaltyBoundaryIntersection { theta } = options.decomposition {
+        if theta.is_nan() || theta < 0.0 {
+            return Err(OptimizeError::ValueError(
+                "PBI penalty must be nonnegative".to_string(),
+            ));
+        }
+    }
+
+    // The first evaluation determines the number of objectives and with it
+    // the number of subproblems
+    let mut population = optimizer.initial_population(options.popsize)?;
+    let n_obj = optimizer.n_obj();
+    let weights: Vec<Vec<f64>> = default_directions(n_obj, options)
+        .rows()
+        .into_iter()
+        .map(|row| row.to_vec())
+        .collect();
+    let size = weights.len();
+    if population.len() < size {
+        let extra = optimizer.initial_population(size - population.len())?;
+        population.extend(extra);
+    }
+    population.truncate(size);
+
+    // Neighbourhoods of the closest weight vectors, including the vector itself
+    let neighborhood_size = options.neighborhood_size.min(size);
+    let neighbors: Vec<Vec<usize>> = weights
+        .iter()
+        .map(|w| {
+            let mut order: Vec<(usize, f64)> = weights
+                .iter()
+                .enumerate()
+                .map(|(k, v)| (k, w.iter().zip(v).map(|(a, b)| (a - b).powi(2)).sum()))
+                .collect();
+            order.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+            order
+                .into_iter()
+                .take(neighborhood_size)
+                .map(|(k, _)| k)
+                .collect()
+        })
+        .collect();
+
+    let mut ideal: Vec<f64> = vec![f64::INFINITY; n_obj];
+    for individual in &population {
+        update_ideal(&mut ideal, individual);
+    }
+
+    for _ in 0..options.maxiter {
+        // Breed one offspring per subproblem from its mating pool
+        let mut pools = Vec::with_capacity(size);
+        let mut candidates = Vec::with_capacity(size);
+        for neighborhood in &neighbors {
+            let local = optimizer.rng.random::<f64>() < options.neighbor_selection_prob;
+            let pick = |rng: &mut rand::rngs::StdRng| {
+                if local {
+                    neighborhood[rng.random_range(0..neighborhood.len())]
+                } else {
+                    rng.random_range(0..size)
+                }
+            };
+            let a = pick(&mut optimizer.rng);
+            let b = pick(&mut optimizer.rng);
+            let (child, _) = optimizer.offspring(&population[a].x, &population[b].x);
+            pools.push(local);
+            candidates.push(child);
+        }
+        let offspring = optimizer.evaluator.evaluate(candidates)?;
+
+        for (i, child) in offspring.into_iter().enumerate() {
+            update_ideal(&mut ideal, &child);
+
+            let mut pool: Vec<usize> = if pools[i] {
+                neighbors[i].clone()
+            } else {
+                (0..size).collect()
+            };
+            pool.shuffle(&mut optimizer.rng);
+            let mut replaced = 0;
+            for k in pool {
+                if replaced >= options.max_replacements {
+                    break;
+                }
+                let better = if child.cv != population[k].cv {
+                    child.cv < population[k].cv
+                } else {
+                    let decomposition = options.decomposition;
+                    scalarize(decomposition, &child.f, &weights[k], &ideal)
+                        < scalarize(decomposition, &population[k].f, &weights[k], &ideal)
+                };
+                if better {
+                    population[k] = child.clone();
+                    replaced += 1;
+                }
+            }
+        }
+    }
+
+    optimizer.finish(population, options.maxiter)
+}
+
+/// Lower the ideal point to the objectives of a feasible solution
+fn update_ideal(ideal: &mut [f64], individual: &Individual) {
+    if individual.is_feasible() {
+        for (z, &f) in ideal.iter_mut().zip(&individual.f) {
+            *z = z.min(f);
+        }
+    }
+}
+
+/// Value of the decomposition for objectives `f`, weights `w` and ideal point `z`
+fn scalarize(decomposition: Decomposition, f: &[f64], w: &[f64], z: &[f64]) -> f64 {
+    // Without a feasible solution the ideal point is still unset
+    let shift = |m: usize| if z[m].is_finite() { f[m] - z[m] } else { f[m] };
+    match decomposition {
+        Decomposition::Tchebycheff => (0..f.len())
+            .map(|m| w[m].max(MIN_WEIGHT) * shift(m).abs())
+            .fold(f64::NEG_INFINITY, f64::max),
+        Decomposition::PenaltyBoundaryIntersection { theta } => {
+            let norm = w.iter().map(|v| v * v).sum::<f64>().sqrt();
+            let d1: f64 = (0..f.len()).map(|m| shift(m) * w[m]).sum::<f64>() / norm;
+            let d2 = (0..f.len())
+                .map(|m| (shift(m) - d1 * w[m] / norm).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            d1 + theta * d2
+        }
+    }
+}
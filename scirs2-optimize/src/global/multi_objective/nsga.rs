@@ -0,0 +1,409 @@
+//! NSGA-II and NSGA-III
+//!
+//! Both algorithms breed a full generation of offspring, merge it with the
+//! parents and keep the best half by non-dominated sorting. They differ in
+//! how the last front that fits only partially is truncated: NSGA-II keeps
+//! the members with the largest crowding distance, NSGA-III the members that
+//! fill the least crowded reference directions.
+//!
+//! References:
+//! K. Deb, A. Pratap, S. Agarwal and T. Meyarivan, "A fast and elitist
+//! multiobjective genetic algorithm: NSGA-II", IEEE TEVC 6 (2002).
+//! K. Deb and H. Jain, "An evolutionary many-objective optimization algorithm
+//! using reference-point-based nondominated sorting approach", IEEE TEVC 18
+//! (2014).
+
+use super::{
+    constrained_dominates, default_directions, non_dominated_sort, Individual,
+    MultiObjectiveResult, Optimizer,
+};
+use crate::error::OptimizeError;
+use ndarray::{Array1, ArrayView1};
+use rand::prelude::*;
+
+/// Run NSGA-II
+pub(super) fn run_nsga2<F, C>(
+    optimizer: &mut Optimizer<'_, F, C>,
+) -> Result<MultiObjectiveResult, OptimizeError>
+where
+    F: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+    C: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+{
+    let size = optimizer.options.popsize;
+    let mut population = optimizer.initial_population(size)?;
+    let (mut rank, mut crowding) = rank_and_crowding(&population);
+
+    for _ in 0..optimizer.options.maxiter {
+        // Binary tournaments on rank, then crowding distance
+        let mut candidates = Vec::with_capacity(size);
+        while candidates.len() < size {
+            let a = crowded_tournament(&mut optimizer.rng, &rank, &crowding);
+            let b = crowded_tournament(&mut optimizer.rng, &rank, &crowding);
+            let (c1, c2) = optimizer.offspring(&population[a].x, &population[b].x);
+            candidates.push(c1);
+            if candidates.len() < size {
+                candidates.push(c2);
+            }
+        }
+        population.extend(optimizer.evaluator.evaluate(candidates)?);
+
+        let fronts = non_dominated_sort(&population);
+        let mut survivors = Vec::with_capacity(size);
+        for front in fronts {
+            if survivors.len() + front.len() <= size {
+                survivors.extend(front);
+                continue;
+            }
+            let mut front = front;
+            truncate_by_crowding(&population, &mut front, size - survivors.len());
+            survivors.extend(front);
+            break;
+        }
+        population = select(population, &survivors);
+        (rank, crowding) = rank_and_crowding(&population);
+    }
+
+    optimizer.finish(population, optimizer.options.maxiter)
+}
+
+/// Run NSGA-III
+pub(super) fn run_nsga3<F, C>(
+    optimizer: &mut Optimizer<'_, F, C>,
+) -> Result<MultiObjectiveResult, OptimizeError>
+where
+    F: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+    C: Fn(&ArrayView1<f64>) -> Array1<f64> + Sync,
+{
+    let mut population = optimizer.initial_population(optimizer.options.popsize)?;
+    let directions = default_directions(optimizer.n_obj(), optimizer.options);
+    let directions: Vec<Vec<f64>> = directions
+        .rows()
+        .into_iter()
+        .map(|row| {
+            let norm = row.dot(&row).sqrt();
+            row.iter().map(|v| v / norm).collect()
+        })
+        .collect();
+    let size = optimizer.options.popsize.max(directions.len());
+    if population.len() < size {
+        let extra = optimizer.initial_population(size - population.len())?;
+        population.extend(extra);
+    }
+
+    for _ in 0..optimizer.options.maxiter {
+        // Binary tournaments on feasibility and constraint violation
+        let mut candidates = Vec::with_capacity(size);
+        while candidates.len() < size {
+            let a = feasibility_tournament(&mut optimizer.rng, &population);
+            let b = feasibility_tournament(&mut optimizer.rng, &population);
+            let (c1, c2) = optimizer.offspring(&population[a].x, &population[b].x);
+            candidates.push(c1);
+            if candidates.len() < size {
+                candidates.push(c2);
+            }
+        }
+        population.extend(optimizer.evaluator.evaluate(candidates)?);
+
+        let fronts = non_dominated_sort(&population);
+        let mut survivors = Vec::with_capacity(size);
+        for front in fronts {
+            if survivors.len() + front.len() <= size {
+                survivors.extend(front);
+                continue;
+            }
+            let remaining = size - survivors.len();
+            let last = if population[front[0]].is_feasible() {
+                niching(
+                    &population,
+                    &survivors,
+                    &front,
+                    remaining,
+                    &directions,
+                    &mut optimizer.rng,
+                )
+            } else {
+                by_violation(&population, front, remaining)
+            };
+            survivors.extend(last);
+            break;
+        }
+        population = select(population, &survivors);
+    }
+
+    optimizer.finish(population, optimizer.options.maxiter)
+}
+
+/// Keep the members at `indices`, in that order
+fn select(population: Vec<Individual>, indices: &[usize]) -> Vec<Individual> {
+    let mut slots: Vec<Option<Individual>> = population.into_iter().map(Some).collect();
+    indices
+        .iter()
+        .map(|&i| slots[i].take().expect("survivor selected twice"))
+        .collect()
+}
+
+/// The `count` members of an infeasible front with the smallest violation
+fn by_violation(population: &[Individual], mut front: Vec<usize>, count: usize) -> Vec<usize> {
+    front.sort_by(|&a, &b| population[a].cv.total_cmp(&population[b].cv));
+    front.truncate(count);
+    front
+}
+
+/// Non-domination rank and crowding distance of every member
+fn rank_and_crowding(population: &[Individual]) -> (Vec<usize>, Vec<f64>) {
+    let mut rank = vec![0; population.len()];
+    let mut crowding = vec![0.0; population.len()];
+    for (r, front) in non_dominated_sort(population).into_iter().enumerate() {
+        let distances = crowding_distance(population, &front);
+        for (&i, d) in front.iter().zip(distances) {
+            rank[i] = r;
+            crowding[i] = d;
+        }
+    }
+    (rank, crowding)
+}
+
+/// Crowding distance of the members of a front
+///
+/// Infeasible members are ordered by their violation instead, so that less
+/// violating members are preferred.
+fn crowding_distance(population: &[Individual], front: &[usize]) -> Vec<f64> {
+    let mut distance = vec![0.0; front.len()];
+    if front.iter().any(|&i| !population[i].is_feasible()) {
+        for (d, &i) in distance.iter_mut().zip(front) {
+            *d = -population[i].cv;
+        }
+        return distance;
+    }
+    if front.len() <= 2 {
+        return vec![f64::INFINITY; front.len()];
+    }
+
+    let n_obj = population[front[0]].f.len();
+    let mut order: Vec<usize> = (0..front.len()).collect();
+    for m in 0..n_obj {
+        let value = |k: usize| population[front[k]].f[m];
+        order.sort_by(|&a, &b| value(a).total_cmp(&value(b)));
+        let (low, high) = (value(order[0]), value(order[front.len() - 1]));
+        distance[order[0]] = f64::INFINITY;
+        distance[order[front.len() - 1]] = f64::INFINITY;
+        if high <= low {
+            continue;
+        }
+        for k in 1..front.len() - 1 {
+            distance[order[k]] += (value(order[k + 1]) - value(order[k - 1])) / (high - low);
+        }
+    }
+    distance
+}
+
+/// Reduce a front to its `count` least crowded members
+fn truncate_by_crowding(population: &[Individual], front: &mut Vec<usize>, count: usize) {
+    let distance = crowding_distance(population, front);
+    let mut order: Vec<usize> = (0..front.len()).collect();
+    order.sort_by(|&a, &b| distance[b].total_cmp(&distance[a]));
+    *front = order.into_iter().take(count).map(|k| front[k]).collect();
+}
+
+/// Binary tournament on rank, then crowding distance
+fn crowded_tournament<R: Rng>(rng: &mut R, rank: &[usize], crowding: &[f64]) -> usize {
+    let a = rng.random_range(0..rank.len());
+    let b = rng.random_range(0..rank.len());
+    if rank[a] != rank[b] {
+        return if rank[a] < rank[b] { a } else { b };
+    }
+    if crowding[a] >= crowding[b] {
+        a
+    } else {
+        b
+    }
+}
+
+/// Binary tournament preferring feasible, then less violating members
+fn feasibility_tournament<R: Rng>(rng: &mut R, population: &[Individual]) -> usize {
+    let a = rng.random_range(0..population.len());
+    let b = rng.random_range(0..population.len());
+    let (pa, pb) = (&population[a], &population[b]);
+    if pa.is_feasible() && pb.is_feasible() {
+        if constrained_dominates(pb, pa) {
+            b
+        } else {
+            a
+        }
+    } else if pa.cv <= pb.cv {
+        a
+    } else {
+        b
+    }
+}
+
+/// Choose `count` members of the last front by niching around the reference
+/// directions in the normalized objective space
+fn niching<R: Rng>(
+    population: &[Individual],
+    survivors: &[usize],
+    last: &[usize],
+    count: usize,
+    directions: &[Vec<f64>],
+    rng: &mut R,
+) -> Vec<usize> {
+    let members: Vec<usize> = survivors.iter().chain(last).copied().collect();
+    let normalized = normalize(population, &members);
+
+    // Associate every member with its closest reference line
+    let association: Vec<(usize, f64)> = normalized
+        .iter()
+        .map(|f| {
+            directions
+                .iter()
+                .enumerate()
+                .map(|(r, w)| {
+                    let projection: f64 = f.iter().zip(w).map(|(a, b)| a * b).sum();
+                    let distance = f
+                        .iter()
+                        .zip(w)
+                        .map(|(a, b)| (a - projection * b).powi(2))
+                        .sum::<f64>()
+                        .sqrt();
+                    (r, distance)
+                })
+                .fold((0, f64::INFINITY), |best, candidate| {
+                    if candidate.1 < best.1 {
+                        candidate
+                    } else {
+                        best
+                    }
+                })
+        })
+        .collect();
+
+    let mut niche_count = vec![0usize; directions.len()];
+    for &(r, _) in &association[..survivors.len()] {
+        niche_count[r] += 1;
+    }
+    // Candidates of the last front per reference direction
+    let mut candidates: Vec<Vec<(usize, f64)>> = vec![Vec::new(); directions.len()];
+    for (k, &i) in last.iter().enumerate() {
+        let (r, distance) = association[survivors.len() + k];
+        candidates[r].push((i, distance));
+    }
+
+    let mut active: Vec<usize> = (0..directions.len()).collect();
+    let mut chosen = Vec::with_capacity(count);
+    while chosen.len() < count && !active.is_empty() {
+        let least = active.iter().map(|&r| niche_count[r]).min().unwrap_or(0);
+        let ties: Vec<usize> = active
+            .iter()
+            .copied()
+            .filter(|&r| niche_count[r] == least)
+            .collect();
+        let r = ties[rng.random_range(0..ties.len())];
+        if candidates[r].is_empty() {
+            active.retain(|&a| a != r);
+            continue;
+        }
+        let pick = if niche_count[r] == 0 {
+            (0..candidates[r].len())
+                .min_by(|&a, &b| candidates[r][a].1.total_cmp(&candidates[r][b].1))
+                .unwrap_or(0)
+        } else {
+            rng.random_range(0..candidates[r].len())
+        };
+        chosen.push(candidates[r].swap_remove(pick).0);
+        niche_count[r] += 1;
+    }
+    chosen
+}
+
+/// Objectives of `members` translated by the ideal point and divided by the
+/// intercepts of the hyperplane through the extreme points
+fn normalize(population: &[Individual], members: &[usize]) -> Vec<Vec<f64>> {
+    let n_obj = population[members[0]].f.len();
+    let ideal: Vec<f64> = (0..n_obj)
+        .map(|m| {
+            members
+                .iter()
+                .fold(f64::INFINITY, |acc, &i| acc.min(population[i].f[m]))
+        })
+        .collect();
+    let translated: Vec<Vec<f64>> = members
+        .iter()
+        .map(|&i| {
+            population[i]
+                .f
+                .iter()
+                .zip(&ideal)
+                .map(|(f, z)| f - z)
+                .collect()
+        })
+        .collect();
+
+    // Extreme point of each axis by the achievement scalarizing function
+    let extremes: Vec<Vec<f64>> = (0..n_obj)
+        .map(|axis| {
+            let asf = |f: &Vec<f64>| {
+                f.iter()
+                    .enumerate()
+                    .map(|(m, v)| if m == axis { *v } else { v / 1e-6 })
+                    .fold(f64::NEG_INFINITY, f64::max)
+            };
+            translated
+                .iter()
+                .min_by(|a, b| asf(a).total_cmp(&asf(b)))
+                .cloned()
+                .unwrap_or_else(|| vec![0.0; n_obj])
+        })
+        .collect();
+
+    let maxima: Vec<f64> = (0..n_obj)
+        .map(|m| translated.iter().fold(0.0f64, |acc, f| acc.max(f[m])))
+        .collect();
+    // Degenerate extreme points fall back to the maxima of the members
+    let intercepts = hyperplane_intercepts(&extremes)
+        .filter(|intercepts| intercepts.iter().all(|&a| a.is_finite() && a > 1e-6))
+        .unwrap_or(maxima);
+
+    translated
+        .into_iter()
+        .map(|f| {
+            f.iter()
+                .zip(&intercepts)
+                .map(|(v, &a)| if a > 1e-10 { v / a } else { *v })
+                .collect()
+        })
+        .collect()
+}
+
+/// Intercepts `1 / b` of the hyperplane `bᵀf = 1` through the extreme points
+fn hyperplane_intercepts(extremes: &[Vec<f64>]) -> Option<Vec<f64>> {
+    let n = extremes.len();
+    let mut a: Vec<Vec<f64>> = extremes
+        .iter()
+        .map(|row| {
+            let mut row = row.clone();
+            row.push(1.0);
+            row
+        })
+        .collect();
+    // Gaussian elimination with partial pivoting
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        let pivot_row = a[col].clone();
+        for row in a.iter_mut().skip(col + 1) {
+            let factor = row[col] / pivot_row[col];
+            for (v, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *v -= factor * p;
+            }
+        }
+    }
+    let mut b = vec![0.0; n];
+    for i in (0..n).rev() {
+        let s: f64 = (i + 1..n).map(|j| a[i][j] * b[j]).sum();
+        b[i] = (a[i][n] - s) / a[i][i];
+    }
+    Some(b.iter().map(|v| 1.0 / v).collect())
+}
@@ -1,12 +1,15 @@
 //! Tests for global optimization algorithms
 
 use crate::global::{
-    basinhopping, differential_evolution, dual_annealing, multi_start, particle_swarm,
-    simulated_annealing, BasinHoppingOptions, DifferentialEvolutionOptions, DualAnnealingOptions,
-    MultiStartOptions, ParticleSwarmOptions, SimulatedAnnealingOptions, StartingPointStrategy,
+    basinhopping, cma_es, differential_evolution, dual_annealing, hypervolume,
+    inverted_generational_distance, moead, multi_start, nsga2, nsga3, particle_swarm,
+    reference_directions, simulated_annealing, BasinHoppingOptions, CmaEsOptions, CmaEsVariant,
+    Decomposition, DifferentialEvolutionOptions, DualAnnealingOptions, MultiObjectiveOptions,
+    MultiStartOptions, ParticleSwarmOptions, RestartStrategy, SimulatedAnnealingOptions,
+    StartingPointStrategy,
 };
 use crate::parallel::ParallelOptions;
-use ndarray::{array, ArrayView1};
+use ndarray::{array, Array1, Array2, ArrayView1};
 
 #[test]
 fn test_differential_evolution_rosenbrock() {
@@ -348,3 +351,400 @@ fn test_differential_evolution_parallel_correctness() {
     assert!((result.x[0] - 1.0).abs() < 0.2);
     assert!((result.x[1] - 1.0).abs() < 0.2);
 }
+
+type NoConstraints = fn(&ArrayView1<f64>) -> Array1<f64>;
+
+/// ZDT1 with Pareto front f2 = 1 - sqrt(f1) for x_2 = ... = x_n = 0
+fn zdt1(x: &ArrayView1<f64>) -> Array1<f64> {
+    let n = x.len();
+    let g = 1.0 + 9.0 * x.iter().skip(1).sum::<f64>() / (n - 1) as f64;
+    array![x[0], g * (1.0 - (x[0] / g).sqrt())]
+}
+
+fn zdt1_front() -> Array2<f64> {
+    Array2::from_shape_fn((200, 2), |(i, j)| {
+        let f1 = i as f64 / 199.0;
+        if j == 0 {
+            f1
+        } else {
+            1.0 - f1.sqrt()
+        }
+    })
+}
+
+#[test]
+fn test_nsga2_zdt1() {
+    let options = MultiObjectiveOptions {
+        popsize: 100,
+        maxiter: 200,
+        seed: Some(1),
+        hypervolume_reference: Some(array![1.1, 1.1]),
+        reference_front: Some(zdt1_front()),
+        ..Default::default()
+    };
+    let result = nsga2(
+        zdt1,
+        vec![(0.0, 1.0); 10],
+        None::<NoConstraints>,
+        Some(options),
+    )
+    .unwrap();
+
+    assert!(result.success);
+    assert!(result.x.nrows() > 50);
+    // The optimal hypervolume is 0.1 + 2/3 + 0.11
+    assert!(result.hypervolume > 0.86, "{}", result.hypervolume);
+    assert!(result.igd.unwrap() < 0.01, "{:?}", result.igd);
+    assert_eq!(result.nfev, 100 * 201);
+}
+
+#[test]
+fn test_nsga3_dtlz2() {
+    // DTLZ2: the Pareto front is the positive octant of the unit sphere
+    let dtlz2 = |x: &ArrayView1<f64>| {
+        let g: f64 = x.iter().skip(2).map(|v| (v - 0.5).powi(2)).sum();
+        let (a, b) = (
+            x[0] * std::f64::consts::FRAC_PI_2,
+            x[1] * std::f64::consts::FRAC_PI_2,
+        );
+        array![
+            (1.0 + g) * a.cos() * b.cos(),
+            (1.0 + g) * a.cos() * b.sin(),
+            (1.0 + g) * a.sin()
+        ]
+    };
+    let directions = reference_directions(3, 12);
+    assert_eq!(directions.nrows(), 91);
+    let sphere = Array2::from_shape_fn((91, 3), |(i, j)| {
+        directions[[i, j]] / directions.row(i).dot(&directions.row(i)).sqrt()
+    });
+
+    let options = MultiObjectiveOptions {
+        popsize: 92,
+        maxiter: 250,
+        reference_divisions: Some(12),
+        seed: Some(3),
+        reference_front: Some(sphere),
+        ..Default::default()
+    };
+    let result = nsga3(
+        dtlz2,
+        vec![(0.0, 1.0); 7],
+        None::<NoConstraints>,
+        Some(options),
+    )
+    .unwrap();
+
+    assert!(result.success);
+    for row in result.f.rows() {
+        assert!((row.dot(&row).sqrt() - 1.0).abs() < 0.05);
+    }
+    assert!(result.igd.unwrap() < 0.08, "{:?}", result.igd);
+}
+
+#[test]
+fn test_moead_zdt1() {
+    for decomposition in [
+        Decomposition::Tchebycheff,
+        Decomposition::PenaltyBoundaryIntersection { theta: 5.0 },
+    ] {
+        let options = MultiObjectiveOptions {
+            popsize: 100,
+            maxiter: 200,
+            decomposition,
+            seed: Some(5),
+            reference_front: Some(zdt1_front()),
+            ..Default::default()
+        };
+        let result = moead(
+            zdt1,
+            vec![(0.0, 1.0); 10],
+            None::<NoConstraints>,
+            Some(options),
+        )
+        .unwrap();
+
+        assert!(result.success);
+        assert!(result.igd.unwrap() < 0.01, "{:?}", result.igd);
+    }
+}
+
+#[test]
+fn test_multi_objective_constraints() {
+    // CONSTR: minimize (x1, (1 + x2) / x1) with two linear constraints
+    let objectives = |x: &ArrayView1<f64>| array![x[0], (1.0 + x[1]) / x[0]];
+    let constraints =
+        |x: &ArrayView1<f64>| array![x[1] + 9.0 * x[0] - 6.0, -x[1] + 9.0 * x[0] - 1.0];
+    let bounds = vec![(0.1, 1.0), (0.0, 5.0)];
+
+    let options = MultiObjectiveOptions {
+        popsize: 60,
+        maxiter: 80,
+        seed: Some(7),
+        ..Default::default()
+    };
+    let results = [
+        nsga2(
+            objectives,
+            bounds.clone(),
+            Some(constraints),
+            Some(options.clone()),
+        )
+        .unwrap(),
+        nsga3(
+            objectives,
+            bounds.clone(),
+            Some(constraints),
+            Some(options.clone()),
+        )
+        .unwrap(),
+        moead(objectives, bounds, Some(constraints), Some(options)).unwrap(),
+    ];
+    for result in &results {
+        assert!(result.success);
+        assert!(result.constraint_violation.iter().all(|&v| v == 0.0));
+        for x in result.x.rows() {
+            assert!(constraints(&x).iter().all(|&g| g >= 0.0));
+        }
+        // The front runs from x1 = 7/18 to x1 = 1
+        let f1 = result.f.column(0);
+        assert!(f1.iter().all(|&v| v > 0.38));
+        assert!(f1.iter().any(|&v| v < 0.45) && f1.iter().any(|&v| v > 0.9));
+    }
+
+    // An infeasible problem reports the least violating solutions
+    let options = MultiObjectiveOptions {
+        popsize: 20,
+        maxiter: 5,
+        seed: Some(7),
+        ..Default::default()
+    };
+    let impossible = |x: &ArrayView1<f64>| array![-1.0 - x[0]];
+    let result = nsga2(
+        objectives,
+        vec![(0.1, 1.0), (0.0, 5.0)],
+        Some(impossible),
+        Some(options),
+    )
+    .unwrap();
+    assert!(!result.success);
+    assert!(result.constraint_violation.iter().all(|&v| v > 0.0));
+}
+
+#[test]
+fn test_multi_objective_parallel_reproducible() {
+    let parallel = ParallelOptions {
+        num_workers: Some(2),
+        min_parallel_size: 4,
+        ..Default::default()
+    };
+    let options = MultiObjectiveOptions {
+        popsize: 40,
+        maxiter: 20,
+        seed: Some(11),
+        ..Default::default()
+    };
+    let parallel_options = MultiObjectiveOptions {
+        parallel: Some(parallel),
+        ..options.clone()
+    };
+    let bounds = vec![(0.0, 1.0); 5];
+
+    let sequential = nsga2(
+        zdt1,
+        bounds.clone(),
+        None::<NoConstraints>,
+        Some(options.clone()),
+    );
+    let parallel = nsga2(
+        zdt1,
+        bounds.clone(),
+        None::<NoConstraints>,
+        Some(parallel_options.clone()),
+    );
+    assert_eq!(sequential.unwrap().f, parallel.unwrap().f);
+
+    let sequential = moead(zdt1, bounds.clone(), None::<NoConstraints>, Some(options));
+    let parallel = moead(zdt1, bounds, None::<NoConstraints>, Some(parallel_options));
+    assert_eq!(sequential.unwrap().x, parallel.unwrap().x);
+}
+
+#[test]
+fn test_hypervolume_and_igd() {
+    let front = array![[1.0, 3.0], [2.0, 2.0], [3.0, 1.0], [3.5, 3.5]];
+    let volume = hypervolume(&front.view(), &array![4.0, 4.0].view()).unwrap();
+    assert!((volume - 6.0).abs() < 1e-12);
+
+    // Two boxes of volume 4 and 2 that overlap in a unit cube
+    let front = array![[1.0, 1.0, 2.0], [2.0, 2.0, 1.0], [3.0, 0.0, 0.0]];
+    let volume = hypervolume(&front.view(), &array![3.0, 3.0, 3.0].view()).unwrap();
+    assert!((volume - 5.0).abs() < 1e-12);
+    assert!(hypervolume(&front.view(), &array![3.0, 3.0].view()).is_err());
+
+    let reference = array![[0.0, 1.0], [1.0, 0.0]];
+    let igd = inverted_generational_distance(&array![[0.0, 1.0]].view(), &reference.view());
+    assert!((igd.unwrap() - 0.5 * 2.0f64.sqrt()).abs() < 1e-12);
+
+    // Invalid input
+    let single = |x: &ArrayView1<f64>| array![x[0]];
+    assert!(nsga2(single, vec![(0.0, 1.0)], None::<NoConstraints>, None).is_err());
+    assert!(nsga2(zdt1, vec![(1.0, 0.0)], None::<NoConstraints>, None).is_err());
+}
+
+/// Ellipsoid with condition number 1e6 along the axes rotated by 45° in pairs
+fn rotated_ellipsoid(x: &ArrayView1<f64>) -> f64 {
+    let n = x.len();
+    (0..n)
+        .map(|i| {
+            let y = if i % 2 == 0 && i + 1 < n {
+                x[i] + x[i + 1]
+            } else if i % 2 == 1 {
+                x[i - 1] - x[i]
+            } else {
+                x[i]
+            };
+            1e6f64.powf(i as f64 / (n - 1) as f64) * y * y / 2.0
+        })
+        .sum()
+}
+
+fn rastrigin(x: &ArrayView1<f64>) -> f64 {
+    10.0 * x.len() as f64
+        + x.iter()
+            .map(|v| v * v - 10.0 * (2.0 * std::f64::consts::PI * v).cos())
+            .sum::<f64>()
+}
+
+#[test]
+fn test_cma_es_variants() {
+    let bounds = vec![(-5.0, 5.0); 4];
+    for variant in [CmaEsVariant::Full, CmaEsVariant::Active] {
+        let options = CmaEsOptions {
+            variant,
+            seed: Some(3),
+            ..Default::default()
+        };
+        let result = cma_es(rotated_ellipsoid, bounds.clone(), Some(options)).unwrap();
+        assert!(result.success, "{:?}: {}", variant, result.message);
+        assert!(result.fun < 1e-10, "{:?}: {}", variant, result.fun);
+        assert_eq!(result.nit, result.iterations);
+        assert_eq!(result.nfev, result.func_evals);
+    }
+
+    // The diagonal covariance learns axis-parallel scaling
+    let separable = |x: &ArrayView1<f64>| {
+        (0..x.len())
+            .map(|i| 1e3f64.powi(i as i32) * x[i] * x[i])
+            .sum::<f64>()
+    };
+    let options = CmaEsOptions {
+        variant: CmaEsVariant::Separable,
+        seed: Some(3),
+        ..Default::default()
+    };
+    let result = cma_es(separable, bounds, Some(options)).unwrap();
+    assert!(result.success);
+    assert!(result.fun < 1e-10);
+
+    // Rosenbrock from a given starting point
+    let rosenbrock =
+        |x: &ArrayView1<f64>| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+    let options = CmaEsOptions {
+        x0: Some(array![-1.2, 1.0]),
+        sigma0: Some(0.5),
+        seed: Some(5),
+        ..Default::default()
+    };
+    let result = cma_es(rosenbrock, vec![(-5.0, 5.0); 2], Some(options)).unwrap();
+    assert!(result.success);
+    assert!((result.x[0] - 1.0).abs() < 1e-5 && (result.x[1] - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_cma_es_restarts() {
+    let bounds = vec![(-5.12, 5.12); 4];
+    for restart in [RestartStrategy::Ipop, RestartStrategy::Bipop] {
+        let options = CmaEsOptions {
+            restart,
+            ftarget: Some(1e-8),
+            seed: Some(11),
+            ..Default::default()
+        };
+        let result = cma_es(rastrigin, bounds.clone(), Some(options)).unwrap();
+        assert!(result.success, "{:?}: {}", restart, result.message);
+        assert!(result.fun <= 1e-8, "{:?}: {}", restart, result.fun);
+        assert!(result.x.iter().all(|v| v.abs() < 1e-4));
+    }
+
+    // The total evaluation budget limits the restarts
+    let options = CmaEsOptions {
+        restart: RestartStrategy::Ipop,
+        maxfev: Some(2000),
+        seed: Some(11),
+        ..Default::default()
+    };
+    let result = cma_es(rastrigin, bounds, Some(options)).unwrap();
+    assert!(result.nfev <= 2000);
+}
+
+#[test]
+fn test_cma_es_bounds() {
+    // The unconstrained minimum at (3, 3, 3) lies outside the box, and the
+    // last variable only has a lower bound
+    let func = |x: &ArrayView1<f64>| x.iter().map(|v| (v - 3.0).powi(2)).sum::<f64>();
+    let bounds = vec![(-1.0, 1.0), (-2.0, 2.0), (0.0, f64::INFINITY)];
+    let options = CmaEsOptions {
+        x0: Some(array![0.0, 0.0, 1.0]),
+        sigma0: Some(0.5),
+        seed: Some(2),
+        ..Default::default()
+    };
+    let result = cma_es(func, bounds.clone(), Some(options)).unwrap();
+    assert!(result
+        .x
+        .iter()
+        .zip(&bounds)
+        .all(|(&x, &(lb, ub))| lb <= x && x <= ub));
+    assert!((result.x[0] - 1.0).abs() < 1e-6);
+    assert!((result.x[1] - 2.0).abs() < 1e-6);
+    assert!((result.x[2] - 3.0).abs() < 1e-4);
+    assert!((result.fun - 5.0).abs() < 1e-10);
+
+    // Invalid input
+    let sphere = |x: &ArrayView1<f64>| x.dot(x);
+    assert!(cma_es(sphere, vec![], None).is_err());
+    assert!(cma_es(sphere, vec![(1.0, 0.0)], None).is_err());
+    assert!(cma_es(sphere, vec![(0.0, f64::INFINITY)], None).is_err());
+    let outside = CmaEsOptions {
+        x0: Some(array![2.0]),
+        ..Default::default()
+    };
+    assert!(cma_es(sphere, vec![(0.0, 1.0)], Some(outside)).is_err());
+}
+
+#[test]
+fn test_cma_es_parallel_reproducible() {
+    let options = CmaEsOptions {
+        restart: RestartStrategy::Bipop,
+        max_restarts: 2,
+        seed: Some(21),
+        ..Default::default()
+    };
+    let parallel_options = CmaEsOptions {
+        parallel: Some(ParallelOptions {
+            min_parallel_size: 1,
+            ..Default::default()
+        }),
+        ..options.clone()
+    };
+    let bounds = vec![(-5.12, 5.12); 3];
+
+    let first = cma_es(rastrigin, bounds.clone(), Some(options.clone())).unwrap();
+    let again = cma_es(rastrigin, bounds.clone(), Some(options)).unwrap();
+    let parallel = cma_es(rastrigin, bounds, Some(parallel_options)).unwrap();
+    assert_eq!(first.x, again.x);
+    assert_eq!(first.x, parallel.x);
+    assert_eq!(first.fun, parallel.fun);
+    assert_eq!(first.nfev, parallel.nfev);
+    assert_eq!(first.nit, parallel.nit);
+}
@@ -10,6 +10,9 @@
 //! * `unconstrained`: Unconstrained optimization algorithms
 //! * `constrained`: Constrained optimization algorithms
 //! * `least_squares`: Least squares minimization (including robust methods)
+//! * `linprog`: Linear programming
+//! * `milp`: Mixed-integer linear programming
+//! * `qp`: Quadratic programming
 //! * `roots`: Root finding algorithms
 //! * `scalar`: Scalar (univariate) optimization algorithms
 //! * `global`: Global optimization algorithms
@@ -28,6 +31,17 @@
 //! - **SLSQP**: Sequential Least SQuares Programming
 //! - **TrustConstr**: Trust-region constrained optimizer
 //!
+//! ### Linear Programming:
+//! - **Dual Simplex**: Bounded revised dual simplex with warm starts
+//! - **Interior Point**: Mehrotra predictor-corrector primal-dual method
+//!
+//! ### Mixed-Integer Linear Programming:
+//! - **Branch and Bound**: LP-based search with Gomory and knapsack cover cuts
+//!
+//! ### Quadratic Programming:
+//! - **Active Set**: Primal null-space method for small dense convex problems
+//! - **ADMM**: OSQP-style operator splitting for large sparse problems
+//!
 //! ### Scalar (Univariate) Optimization:
 //! - **Brent**: Combines parabolic interpolation with golden section search
 //! - **Bounded**: Brent's method with bounds constraints
@@ -35,11 +49,17 @@
 //!
 //! ### Global:
 //! - **Differential Evolution**: Stochastic global optimization method
+//! - **CMA-ES**: Covariance matrix adaptation with IPOP/BIPOP restarts
 //! - **Basin-hopping**: Random perturbations with local minimization
 //! - **Dual Annealing**: Simulated annealing with fast annealing
 //! - **Particle Swarm**: Population-based optimization inspired by swarm behavior
 //! - **Simulated Annealing**: Probabilistic optimization with cooling schedule
 //!
+//! ### Multi-Objective Optimization:
+//! - **NSGA-II**: Non-dominated sorting with crowding distance
+//! - **NSGA-III**: Non-dominated sorting with reference-direction niching
+//! - **MOEA/D**: Decomposition into neighbouring scalar subproblems
+//!
 //! ### Least Squares:
 //! - **Levenberg-Marquardt**: Trust-region algorithm for nonlinear least squares
 //! - **Trust Region Reflective**: Bounds-constrained least squares
@@ -194,11 +214,15 @@ pub use error::{OptimizeError, OptimizeResult};
 pub mod constrained;
 pub mod global;
 pub mod least_squares;
+pub mod linprog;
+pub mod milp;
 pub mod parallel;
+pub mod qp;
 pub mod roots;
 pub mod roots_anderson;
 pub mod roots_krylov;
 pub mod scalar;
+mod sparse_linalg;
 pub mod sparse_numdiff; // Refactored into a module with submodules
 pub mod unconstrained;
 
@@ -209,13 +233,16 @@ pub use result::OptimizeResults;
 // Convenience re-exports for common functions
 pub use constrained::minimize_constrained;
 pub use global::{
-    basinhopping, bayesian_optimization, differential_evolution, dual_annealing, multi_start,
-    particle_swarm, simulated_annealing,
+    basinhopping, bayesian_optimization, cma_es, differential_evolution, dual_annealing, moead,
+    multi_start, nsga2, nsga3, particle_swarm, simulated_annealing,
 };
 pub use least_squares::{
     bounded_least_squares, least_squares, robust_least_squares, separable_least_squares,
     total_least_squares, weighted_least_squares, BisquareLoss, CauchyLoss, HuberLoss,
 };
+pub use linprog::{linprog, LinearProgram, LinprogResult};
+pub use milp::{milp, MilpResult};
+pub use qp::{minimize_qp, QpResult};
 pub use roots::root;
 pub use scalar::minimize_scalar;
 pub use sparse_numdiff::{sparse_hessian, sparse_jacobian, SparseFiniteDiffOptions};
@@ -226,11 +253,13 @@ pub mod prelude {
     pub use crate::constrained::{minimize_constrained, Method as ConstrainedMethod};
     pub use crate::error::{OptimizeError, OptimizeResult};
     pub use crate::global::{
-        basinhopping, bayesian_optimization, differential_evolution, dual_annealing,
-        particle_swarm, simulated_annealing, AcquisitionFunctionType, BasinHoppingOptions,
-        BayesianOptimizationOptions, BayesianOptimizer, DifferentialEvolutionOptions,
-        DualAnnealingOptions, InitialPointGenerator, KernelType, Parameter, ParticleSwarmOptions,
-        SimulatedAnnealingOptions, Space,
+        basinhopping, bayesian_optimization, cma_es, differential_evolution, dual_annealing,
+        hypervolume, inverted_generational_distance, moead, nsga2, nsga3, particle_swarm,
+        reference_directions, simulated_annealing, AcquisitionFunctionType, BasinHoppingOptions,
+        BayesianOptimizationOptions, BayesianOptimizer, CmaEsOptions, CmaEsVariant, Decomposition,
+        DifferentialEvolutionOptions, DualAnnealingOptions, InitialPointGenerator, KernelType,
+        MultiObjectiveOptions, MultiObjectiveResult, Parameter, ParticleSwarmOptions,
+        RestartStrategy, SimulatedAnnealingOptions, Space,
     };
     pub use crate::least_squares::{
         bounded_least_squares, least_squares, robust_least_squares, separable_least_squares,
@@ -239,8 +268,17 @@ pub mod prelude {
         SeparableOptions, SeparableResult, TLSMethod, TotalLeastSquaresOptions,
         TotalLeastSquaresResult, WeightedOptions,
     };
+    pub use crate::linprog::{
+        linprog, Basis, ConstraintMatrix, LinearProgram, LinprogResult, Method as LinprogMethod,
+        Options as LinprogOptions, VariableStatus,
+    };
+    pub use crate::milp::{milp, MilpResult, NodeSelection, Options as MilpOptions};
     pub use crate::parallel::{
-        parallel_evaluate_batch, parallel_finite_diff_gradient, ParallelOptions,
+        parallel_evaluate_batch, parallel_finite_diff_gradient, ParallelOptions, WorkerPool,
+    };
+    pub use crate::qp::{
+        minimize_qp, Method as QpMethod, Options as QpOptions, QpResult, QuadraticProgram,
+        WarmStart,
     };
     pub use crate::result::OptimizeResults;
     pub use crate::roots::{root, Method as RootMethod};
@@ -0,0 +1,442 @@
+//! Mehrotra predictor–corrector primal–dual interior-point method
+//!
+//! The model is first converted to the standard form
+//! `min cᵀx  s.t.  Ax = b,  0 ≤ x,  x_U ≤ u_U` by shifting finite lower
+//! bounds, reflecting variables that are only bounded above, splitting free
+//! variables and eliminating fixed ones. Each iteration solves the normal
+//! equations `A Θ Aᵀ Δy = r` with a sparse Cholesky factorization; the
+//! sparsity pattern of `A Θ Aᵀ` is fixed, so its symbolic analysis is done
+//! once and only the numerical factorization is repeated. When the
+//! iteration stalls or diverges, the problem is classified with the dual
+//! simplex method so that infeasible and unbounded problems are reported
+//! reliably; a stalled iteration on a solvable problem falls back to the
+//! simplex solution.
+
+use super::simplex::dual_simplex;
+use super::{LpModel, LpSolution, LpStatus};
+use crate::sparse_linalg::{csr_from_triplets, SymmetricFactor};
+use ndarray::Array1;
+use scirs2_sparse::csr_array::CsrArray;
+
+/// Fraction of the distance to the boundary taken by each step
+const STEP_FRACTION: f64 = 0.995;
+
+/// Iterates larger than this (relative to the problem scale) indicate divergence
+const DIVERGENCE_LIMIT: f64 = 1e12;
+
+/// Diagonal shift of the normal equations relative to their largest diagonal
+/// entry; it keeps rank-deficient constraint matrices factorizable
+const NORMAL_REGULARIZATION: f64 = 1e-14;
+
+/// A variable of the standard form and its relation to the model variables
+struct StandardVariable {
+    /// Index of the model variable
+    original: usize,
+    /// `+1` or `-1` depending on whether the variable was reflected
+    sign: f64,
+    /// Upper bound in the standard form, if any
+    upper: Option<f64>,
+}
+
+/// Solve the model with Mehrotra's predictor–corrector method
+pub(crate) fn mehrotra(model: &LpModel, tol: f64, maxiter: usize) -> LpSolution {
+    let (n, m) = (model.n, model.m);
+    let total = n + m;
+
+    // Standard form
+    let mut shift = vec![0.0; total];
+    let mut vars = Vec::new();
+    for (j, shift_j) in shift.iter_mut().enumerate() {
+        let (lo, up) = (model.lower[j], model.upper[j]);
+        if lo == up {
+            *shift_j = lo;
+        } else if lo.is_finite() {
+            *shift_j = lo;
+            vars.push(StandardVariable {
+                original: j,
+                sign: 1.0,
+                upper: up.is_finite().then_some(up - lo),
+            });
+        } else if up.is_finite() {
+            *shift_j = up;
+            vars.push(StandardVariable {
+                original: j,
+                sign: -1.0,
+                upper: None,
+            });
+        } else {
+            for sign in [1.0, -1.0] {
+                vars.push(StandardVariable {
+                    original: j,
+                    sign,
+                    upper: None,
+                });
+            }
+        }
+    }
+
+    let columns: Vec<Vec<(usize, f64)>> = vars
+        .iter()
+        .map(|var| {
+            let mut column = Vec::new();
+            model.for_column(var.original, |i, v| column.push((i, var.sign * v)));
+            column
+        })
+        .collect();
+    let c: Vec<f64> = vars
+        .iter()
+        .map(|var| var.sign * model.cost_of(var.original))
+        .collect();
+    let mut b = model.rhs.clone();
+    for (j, &s) in shift.iter().enumerate() {
+        if s != 0.0 {
+            model.for_column(j, |i, v| b[i] -= v * s);
+        }
+    }
+    let ns = vars.len();
+    let upper: Vec<Option<f64>> = vars.iter().map(|var| var.upper).collect();
+    let nu = upper.iter().filter(|u| u.is_some()).count();
+
+    let recover = |x: &[f64]| -> Vec<f64> {
+        let mut values = shift.clone();
+        for (var, &xk) in vars.iter().zip(x) {
+            values[var.original] += var.sign * xk;
+        }
+        values
+    };
+
+    let a_dot = |x: &[f64]| -> Vec<f64> {
+        let mut out = vec![0.0; m];
+        for (column, &xk) in columns.iter().zip(x) {
+            for &(i, v) in column {
+                out[i] += v * xk;
+            }
+        }
+        out
+    };
+    let at_dot = |y: &[f64]| -> Vec<f64> {
+        columns
+            .iter()
+            .map(|column| column.iter().map(|&(i, v)| v * y[i]).sum())
+            .collect()
+    };
+    // Sparsity pattern of `A Θ Aᵀ` and the slot of every term of every column product
+    let mut products = Vec::new();
+    for column in &columns {
+        for &(i, _) in column {
+            for &(k, _) in column {
+                products.push((i, k, 0.0));
+            }
+        }
+    }
+    let pattern = csr_from_triplets(m, m, products);
+    let (indptr, indices) = (pattern.get_indptr(), pattern.get_indices().to_vec());
+    let mut slots = Vec::new();
+    for column in &columns {
+        for &(i, _) in column {
+            for &(k, _) in column {
+                let row = &indices[indptr[i]..indptr[i + 1]];
+                let offset = row.binary_search(&k).expect("product lies in the pattern");
+                slots.push(indptr[i] + offset);
+            }
+        }
+    }
+    let normal_matrix = |theta: &[f64]| -> CsrArray<f64> {
+        let mut data = vec![0.0; indices.len()];
+        let mut slot = slots.iter();
+        for (column, &t) in columns.iter().zip(theta) {
+            for &(_, vi) in column {
+                for (&(_, vk), &p) in column.iter().zip(slot.by_ref()) {
+                    data[p] += t * vi * vk;
+                }
+            }
+        }
+        CsrArray::new(
+            Array1::from_vec(data),
+            pattern.get_indices().clone(),
+            pattern.get_indptr().clone(),
+            (m, m),
+        )
+        .expect("normal matrix has the pattern of A Aᵀ")
+    };
+
+    let norm = |v: &[f64]| v.iter().fold(0.0f64, |acc, x| acc.max(x.abs()));
+    let b_norm = norm(&b);
+    let c_norm = norm(&c);
+    let u_norm = upper
+        .iter()
+        .flatten()
+        .fold(0.0f64, |acc, u| acc.max(u.abs()));
+    let scale = 1.0 + b_norm.max(c_norm).max(u_norm);
+
+    // Mehrotra's starting point
+    let mut factor =
+        match SymmetricFactor::new(&normal_matrix(&vec![1.0; ns]), NORMAL_REGULARIZATION) {
+            Ok(factor) => factor,
+            Err(_) => {
+                return classify(
+                    model,
+                    LpStatus::Numerical,
+                    0,
+                    recover(&vec![0.0; ns]),
+                    vec![0.0; m],
+                )
+            }
+        };
+    let mut x = at_dot(&factor.solve(&b));
+    let mut y = factor.solve(&a_dot(&c));
+    let aty = at_dot(&y);
+    let mut s: Vec<f64> = c.iter().zip(&aty).map(|(ci, ai)| ci - ai).collect();
+    if ns > 0 {
+        let dx = (-1.5 * x.iter().cloned().fold(f64::INFINITY, f64::min)).max(0.0);
+        let ds = (-1.5 * s.iter().cloned().fold(f64::INFINITY, f64::min)).max(0.0);
+        x.iter_mut().for_each(|v| *v += dx);
+        s.iter_mut().for_each(|v| *v += ds);
+        let xs: f64 = x.iter().zip(&s).map(|(a, b)| a * b).sum();
+        let (sum_x, sum_s): (f64, f64) = (x.iter().sum(), s.iter().sum());
+        let (dx, ds) = (0.5 * xs / sum_s, 0.5 * xs / sum_x);
+        x.iter_mut().for_each(|v| *v += dx);
+        s.iter_mut().for_each(|v| *v += ds);
+    }
+    for k in 0..ns {
+        if !(x[k].is_finite() && x[k] > 0.0) {
+            x[k] = 1.0;
+        }
+        if !(s[k].is_finite() && s[k] > 0.0) {
+            s[k] = 1.0;
+        }
+        if let Some(u) = upper[k] {
+            if x[k] >= u {
+                x[k] = 0.5 * u;
+            }
+        }
+    }
+    let mut v: Vec<f64> = (0..ns)
+        .map(|k| upper[k].map_or(0.0, |u| u - x[k]))
+        .collect();
+    let mut w: Vec<f64> = (0..ns)
+        .map(|k| if upper[k].is_some() { s[k] } else { 0.0 })
+        .collect();
+    if y.iter().any(|v| !v.is_finite()) {
+        y = vec![0.0; m];
+    }
+
+    let mut iterations = 0;
+    let mut failure = LpStatus::IterationLimit;
+    let mut converged = false;
+
+    while iterations < maxiter {
+        // Residuals
+        let ax = a_dot(&x);
+        let aty = at_dot(&y);
+        let r_b: Vec<f64> = b.iter().zip(&ax).map(|(bi, ai)| bi - ai).collect();
+        let r_c: Vec<f64> = (0..ns).map(|k| c[k] - aty[k] - s[k] + w[k]).collect();
+        let r_u: Vec<f64> = (0..ns)
+            .map(|k| upper[k].map_or(0.0, |u| u - x[k] - v[k]))
+            .collect();
+
+        let complementarity: f64 = (0..ns).map(|k| x[k] * s[k] + v[k] * w[k]).sum();
+        let mu = if ns + nu > 0 {
+            complementarity / (ns + nu) as f64
+        } else {
+            0.0
+        };
+        let primal_obj: f64 = c.iter().zip(&x).map(|(a, b)| a * b).sum();
+        let dual_obj: f64 = b.iter().zip(&y).map(|(a, b)| a * b).sum::<f64>()
+            - (0..ns)
+                .map(|k| upper[k].map_or(0.0, |u| u * w[k]))
+                .sum::<f64>();
+
+        let primal_res = norm(&r_b) / (1.0 + b_norm) + norm(&r_u) / (1.0 + u_norm);
+        let dual_res = norm(&r_c) / (1.0 + c_norm);
+        let gap = (primal_obj - dual_obj).abs() / (1.0 + primal_obj.abs());
+        if primal_res < tol && dual_res < tol && gap < tol {
+            converged = true;
+            break;
+        }
+        if norm(&x).max(norm(&y)).max(norm(&s)) > DIVERGENCE_LIMIT * scale
+            || !mu.is_finite()
+            || (mu < 1e-3 * tol * tol * scale && (primal_res > tol || dual_res > tol))
+        {
+            failure = LpStatus::Numerical;
+            break;
+        }
+
+        // Normal equations
+        let theta: Vec<f64> = (0..ns)
+            .map(|k| {
+                let d = s[k] / x[k] + if upper[k].is_some() { w[k] / v[k] } else { 0.0 };
+                1.0 / d
+            })
+            .collect();
+        if factor.refactor(&normal_matrix(&theta)).is_err() {
+            failure = LpStatus::Numerical;
+            break;
+        }
+
+        let solve = |r_xs: &[f64], r_vw: &[f64]| {
+            let rho: Vec<f64> = (0..ns)
+                .map(|k| {
+                    let mut value = r_c[k] - r_xs[k] / x[k];
+                    if upper[k].is_some() {
+                        value += (r_vw[k] - w[k] * r_u[k]) / v[k];
+                    }
+                    value
+                })
+                .collect();
+            let theta_rho: Vec<f64> = theta.iter().zip(&rho).map(|(t, r)| t * r).collect();
+            let rhs: Vec<f64> = r_b
+                .iter()
+                .zip(a_dot(&theta_rho))
+                .map(|(a, b)| a + b)
+                .collect();
+            let dy = factor.solve(&rhs);
+            let atdy = at_dot(&dy);
+            let dx: Vec<f64> = (0..ns).map(|k| theta[k] * (atdy[k] - rho[k])).collect();
+            let ds: Vec<f64> = (0..ns).map(|k| (r_xs[k] - s[k] * dx[k]) / x[k]).collect();
+            let dv: Vec<f64> = (0..ns)
+                .map(|k| {
+                    if upper[k].is_some() {
+                        r_u[k] - dx[k]
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            let dw: Vec<f64> = (0..ns)
+                .map(|k| {
+                    if upper[k].is_some() {
+                        (r_vw[k] - w[k] * dv[k]) / v[k]
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            (dx, dy, ds, dv, dw)
+        };
+
+        // Predictor
+        let r_xs: Vec<f64> = (0..ns).map(|k| -x[k] * s[k]).collect();
+        let r_vw: Vec<f64> = (0..ns).map(|k| -v[k] * w[k]).collect();
+        let (dx_a, _, ds_a, dv_a, dw_a) = solve(&r_xs, &r_vw);
+        let alpha_p = max_step(&x, &dx_a).min(max_step(&v, &dv_a)).min(1.0);
+        let alpha_d = max_step(&s, &ds_a).min(max_step(&w, &dw_a)).min(1.0);
+        let mu_aff = if ns + nu > 0 {
+            (0..ns)
+                .map(|k| {
+                    (x[k] + alpha_p * dx_a[k]) * (s[k] + alpha_d * ds_a[k])
+                        + (v[k] + alpha_p * dv_a[k]) * (w[k] + alpha_d * dw_a[k])
+                })
+                .sum::<f64>()
+                / (ns + nu) as f64
+        } else {
+            0.0
+        };
+        let sigma = if mu > 0.0 {
+            (mu_aff / mu).powi(3).min(1.0)
+        } else {
+            0.0
+        };
+
+        // Corrector
+        let r_xs: Vec<f64> = (0..ns)
+            .map(|k| -x[k] * s[k] - dx_a[k] * ds_a[k] + sigma * mu)
+            .collect();
+        let r_vw: Vec<f64> = (0..ns)
+            .map(|k| {
+                if upper[k].is_some() {
+                    -v[k] * w[k] - dv_a[k] * dw_a[k] + sigma * mu
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let (dx, dy, ds, dv, dw) = solve(&r_xs, &r_vw);
+        let alpha_p = (STEP_FRACTION * max_step(&x, &dx).min(max_step(&v, &dv))).min(1.0);
+        let alpha_d = (STEP_FRACTION * max_step(&s, &ds).min(max_step(&w, &dw))).min(1.0);
+
+        if dx.iter().chain(&dy).chain(&ds).any(|v| !v.is_finite()) || alpha_p.max(alpha_d) < 1e-10 {
+            failure = LpStatus::Numerical;
+            break;
+        }
+
+        for k in 0..ns {
+            x[k] += alpha_p * dx[k];
+            s[k] += alpha_d * ds[k];
+            if upper[k].is_some() {
+                v[k] += alpha_p * dv[k];
+                w[k] += alpha_d * dw[k];
+            }
+        }
+        for i in 0..m {
+            y[i] += alpha_d * dy[i];
+        }
+        iterations += 1;
+    }
+
+    if converged {
+        return LpSolution {
+            status: LpStatus::Optimal,
+            x: recover(&x),
+            y,
+            basis: None,
+            iterations,
+            detail: None,
+        };
+    }
+
+    classify(model, failure, iterations, recover(&x), y)
+}
+
+/// Classify infeasible and unbounded problems with the simplex method
+///
+/// `x` and `y` are the last interior-point iterate, returned with status
+/// `failure` if the simplex method cannot classify the problem either.
+fn classify(
+    model: &LpModel,
+    failure: LpStatus,
+    iterations: usize,
+    x: Vec<f64>,
+    y: Vec<f64>,
+) -> LpSolution {
+    let classified = dual_simplex(
+        model,
+        &model.lower,
+        &model.upper,
+        None,
+        1e-9,
+        10 * (model.n + model.m) + 1000,
+    );
+    match classified.status {
+        LpStatus::Infeasible | LpStatus::Unbounded => LpSolution {
+            iterations: iterations + classified.iterations,
+            basis: None,
+            detail: Some("(classified by the dual simplex method)".to_string()),
+            ..classified
+        },
+        LpStatus::Optimal if failure == LpStatus::Numerical => LpSolution {
+            iterations: iterations + classified.iterations,
+            detail: Some(
+                "(interior-point method stalled; solved by the dual simplex method)".to_string(),
+            ),
+            ..classified
+        },
+        _ => LpSolution {
+            status: failure,
+            x,
+            y,
+            basis: None,
+            iterations,
+            detail: None,
+        },
+    }
+}
+
+/// Largest step `α` with `z + α Δz ≥ 0`
+fn max_step(z: &[f64], dz: &[f64]) -> f64 {
+    z.iter()
+        .zip(dz)
+        .filter(|(_, &d)| d < 0.0)
+        .map(|(&zi, &d)| -zi / d)
+        .fold(f64::INFINITY, f64::min)
+}
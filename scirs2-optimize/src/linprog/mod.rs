@@ -0,0 +1,863 @@
+//! Linear programming
+//!
+//! This module solves linear programs of the form
+//!
+//! ```text
+//! minimize    cᵀx
+//! subject to  A_ub x ≤ b_ub
+//!             A_eq x = b_eq
+//!             l ≤ x ≤ u
+//! ```
+//!
+//! modeled after SciPy's `linprog`. Constraint matrices may be dense
+//! (`Array2`) or sparse (`scirs2_sparse::CsrArray`). Two methods are available:
+//!
+//! * [`Method::DualSimplex`]: a bounded revised dual simplex method. It
+//!   returns the final basis, which can be passed back through
+//!   [`Options::warm_start`] to re-solve a modified problem quickly.
+//! * [`Method::InteriorPoint`]: Mehrotra's primal–dual predictor–corrector
+//!   interior-point method, suited to larger problems.
+//!
+//! Both methods report the dual values of all constraints and bounds and
+//! detect infeasible and unbounded problems (status codes `2` and `3`).
+//!
+//! ## Example
+//!
+//! ```
+//! use ndarray::array;
+//! use scirs2_optimize::linprog::{linprog, LinearProgram, Method};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // maximize x + 2y  s.t.  x + y ≤ 4,  x + 3y ≤ 6,  x, y ≥ 0
+//! let problem = LinearProgram::new(array![-1.0, -2.0])
+//!     .with_inequality(array![[1.0, 1.0], [1.0, 3.0]], array![4.0, 6.0]);
+//!
+//! let solution = linprog(&problem, Method::DualSimplex, None)?;
+//! assert!(solution.result.success);
+//! assert!((solution.result.x[0] - 3.0).abs() < 1e-8);
+//! assert!((solution.result.x[1] - 1.0).abs() < 1e-8);
+//! assert!((solution.result.fun + 5.0).abs() < 1e-8);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{OptimizeError, OptimizeResult};
+use crate::result::OptimizeResults;
+use crate::unconstrained::Bounds;
+use ndarray::{Array1, Array2};
+use scirs2_sparse::csr_array::CsrArray;
+use scirs2_sparse::sparray::SparseArray;
+use std::fmt;
+
+mod interior_point;
+mod simplex;
+
+pub(crate) use simplex::{dual_simplex, tableau_rows};
+
+/// Methods for solving linear programs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Bounded revised dual simplex method with warm start support
+    DualSimplex,
+
+    /// Mehrotra predictor–corrector primal–dual interior-point method
+    InteriorPoint,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Method::DualSimplex => write!(f, "dual-simplex"),
+            Method::InteriorPoint => write!(f, "interior-point"),
+        }
+    }
+}
+
+/// Options for the linear programming solvers.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Maximum number of iterations (default depends on the method and problem size)
+    pub maxiter: Option<usize>,
+
+    /// Feasibility and optimality tolerance (default `1e-9` for the simplex
+    /// method and `1e-8` for the interior-point method)
+    pub tol: Option<f64>,
+
+    /// Starting basis for the dual simplex method, typically the
+    /// [`LinprogResult::basis`] of a previous solve of a related problem
+    pub warm_start: Option<Basis>,
+}
+
+/// Status of a variable or constraint slack in a simplex basis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariableStatus {
+    /// The variable is basic
+    Basic,
+
+    /// The variable is nonbasic at its lower bound
+    AtLower,
+
+    /// The variable is nonbasic at its upper bound
+    AtUpper,
+
+    /// The variable is free and nonbasic at zero
+    Free,
+}
+
+/// A simplex basis, used to warm start the dual simplex method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Basis {
+    /// Status of each decision variable
+    pub variables: Vec<VariableStatus>,
+
+    /// Status of the slack of each constraint, inequality rows first
+    pub constraints: Vec<VariableStatus>,
+}
+
+/// Constraint matrix of a linear program, stored densely or in CSR format.
+#[derive(Clone)]
+pub enum ConstraintMatrix {
+    /// Dense matrix
+    Dense(Array2<f64>),
+
+    /// Sparse matrix in compressed sparse row format
+    Sparse(CsrArray<f64>),
+}
+
+impl ConstraintMatrix {
+    /// Shape of the matrix as `(rows, columns)`
+    pub fn shape(&self) -> (usize, usize) {
+        match self {
+            ConstraintMatrix::Dense(a) => a.dim(),
+            ConstraintMatrix::Sparse(a) => a.shape(),
+        }
+    }
+
+    /// Call `f(row, col, value)` for every stored nonzero entry
+    pub(crate) fn for_each_nonzero<G: FnMut(usize, usize, f64)>(&self, mut f: G) {
+        match self {
+            ConstraintMatrix::Dense(a) => {
+                for ((i, j), &v) in a.indexed_iter() {
+                    if v != 0.0 {
+                        f(i, j, v);
+                    }
+                }
+            }
+            ConstraintMatrix::Sparse(a) => {
+                let (indptr, indices, data) = (a.get_indptr(), a.get_indices(), a.get_data());
+                for i in 0..a.shape().0 {
+                    for k in indptr[i]..indptr[i + 1] {
+                        if data[k] != 0.0 {
+                            f(i, indices[k], data[k]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Matrix–vector product
+    pub(crate) fn dot(&self, x: &Array1<f64>) -> Array1<f64> {
+        let mut y = Array1::zeros(self.shape().0);
+        self.for_each_nonzero(|i, j, v| y[i] += v * x[j]);
+        y
+    }
+}
+
+impl From<Array2<f64>> for ConstraintMatrix {
+    fn from(a: Array2<f64>) -> Self {
+        ConstraintMatrix::Dense(a)
+    }
+}
+
+impl From<CsrArray<f64>> for ConstraintMatrix {
+    fn from(a: CsrArray<f64>) -> Self {
+        ConstraintMatrix::Sparse(a)
+    }
+}
+
+/// A linear program `min cᵀx` subject to `A_ub x ≤ b_ub`, `A_eq x = b_eq`
+/// and `l ≤ x ≤ u`.
+///
+/// When no bounds are given every variable is restricted to `x ≥ 0`, as in
+/// SciPy.
+#[derive(Clone)]
+pub struct LinearProgram {
+    /// Cost vector
+    pub c: Array1<f64>,
+
+    /// Inequality constraint matrix and right-hand side
+    pub inequality: Option<(ConstraintMatrix, Array1<f64>)>,
+
+    /// Equality constraint matrix and right-hand side
+    pub equality: Option<(ConstraintMatrix, Array1<f64>)>,
+
+    /// Variable bounds (default: `0 ≤ x`)
+    pub bounds: Option<Bounds>,
+}
+
+impl LinearProgram {
+    /// Create a linear program with cost vector `c` and no constraints
+    pub fn new(c: Array1<f64>) -> Self {
+        LinearProgram {
+            c,
+            inequality: None,
+            equality: None,
+            bounds: None,
+        }
+    }
+
+    /// Set the inequality constraints `A_ub x ≤ b_ub`
+    pub fn with_inequality<M: Into<ConstraintMatrix>>(
+        mut self,
+        a_ub: M,
+        b_ub: Array1<f64>,
+    ) -> Self {
+        self.inequality = Some((a_ub.into(), b_ub));
+        self
+    }
+
+    /// Set the equality constraints `A_eq x = b_eq`
+    pub fn with_equality<M: Into<ConstraintMatrix>>(mut self, a_eq: M, b_eq: Array1<f64>) -> Self {
+        self.equality = Some((a_eq.into(), b_eq));
+        self
+    }
+
+    /// Set the variable bounds
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Number of decision variables
+    pub fn num_variables(&self) -> usize {
+        self.c.len()
+    }
+
+    /// Lower and upper bounds of the decision variables, with infinities for
+    /// missing bounds
+    pub(crate) fn bound_vectors(&self) -> (Vec<f64>, Vec<f64>) {
+        let n = self.c.len();
+        match &self.bounds {
+            Some(bounds) => (
+                bounds
+                    .lower
+                    .iter()
+                    .map(|l| l.unwrap_or(f64::NEG_INFINITY))
+                    .collect(),
+                bounds
+                    .upper
+                    .iter()
+                    .map(|u| u.unwrap_or(f64::INFINITY))
+                    .collect(),
+            ),
+            None => (vec![0.0; n], vec![f64::INFINITY; n]),
+        }
+    }
+}
+
+/// Result of a linear program solve.
+#[derive(Debug, Clone)]
+pub struct LinprogResult {
+    /// Standard optimization results (`x`, `fun`, `nit`, `status`, ...)
+    ///
+    /// Status codes follow SciPy: `0` optimal, `1` iteration limit reached,
+    /// `2` infeasible, `3` unbounded, `4` numerical difficulties.
+    pub result: OptimizeResults<f64>,
+
+    /// Slack of the inequality constraints, `b_ub - A_ub x`
+    pub slack: Array1<f64>,
+
+    /// Residual of the equality constraints, `b_eq - A_eq x`
+    pub con: Array1<f64>,
+
+    /// Sensitivity of the objective to `b_ub` (Lagrange multipliers, `≤ 0`)
+    pub ineqlin_marginals: Array1<f64>,
+
+    /// Sensitivity of the objective to `b_eq` (Lagrange multipliers)
+    pub eqlin_marginals: Array1<f64>,
+
+    /// Sensitivity of the objective to the lower bounds (`≥ 0`)
+    pub lower_marginals: Array1<f64>,
+
+    /// Sensitivity of the objective to the upper bounds (`≤ 0`)
+    pub upper_marginals: Array1<f64>,
+
+    /// Final basis of the dual simplex method, usable as a warm start
+    pub basis: Option<Basis>,
+}
+
+/// Termination status of an LP solve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LpStatus {
+    Optimal,
+    IterationLimit,
+    Infeasible,
+    Unbounded,
+    Numerical,
+}
+
+impl LpStatus {
+    fn code(self) -> i32 {
+        match self {
+            LpStatus::Optimal => 0,
+            LpStatus::IterationLimit => 1,
+            LpStatus::Infeasible => 2,
+            LpStatus::Unbounded => 3,
+            LpStatus::Numerical => 4,
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            LpStatus::Optimal => "Optimization terminated successfully.",
+            LpStatus::IterationLimit => "Iteration limit reached.",
+            LpStatus::Infeasible => "The problem is infeasible.",
+            LpStatus::Unbounded => "The problem is unbounded.",
+            LpStatus::Numerical => "Numerical difficulties encountered.",
+        }
+    }
+}
+
+/// Column-oriented computational form shared by the LP solvers
+///
+/// Every row `i` gets a logical variable `s_i` with `a_iᵀx + s_i = b_i`;
+/// logicals of inequality rows are bounded by `[0, ∞)`, those of equality rows
+/// are fixed at zero. Variables `0..n` are structural, `n..n+m` logical.
+#[derive(Debug, Clone)]
+pub(crate) struct LpModel {
+    /// Number of structural variables
+    pub n: usize,
+    /// Number of rows (inequality rows first, then equality rows, then appended rows)
+    pub m: usize,
+    /// Number of inequality rows of the original problem
+    pub m_ub: usize,
+    /// Costs of the structural variables
+    pub cost: Vec<f64>,
+    /// Sparse columns of the structural variables as `(row, value)` pairs
+    pub columns: Vec<Vec<(usize, f64)>>,
+    /// Right-hand side
+    pub rhs: Vec<f64>,
+    /// Lower bounds of all `n + m` variables
+    pub lower: Vec<f64>,
+    /// Upper bounds of all `n + m` variables
+    pub upper: Vec<f64>,
+}
+
+impl LpModel {
+    /// Build and validate the computational form of a linear program
+    pub(crate) fn new(problem: &LinearProgram) -> OptimizeResult<Self> {
+        let n = problem.c.len();
+        if n == 0 {
+            return Err(OptimizeError::ValueError(
+                "Cost vector must not be empty".to_string(),
+            ));
+        }
+        if problem.c.iter().any(|v| !v.is_finite()) {
+            return Err(OptimizeError::ValueError(
+                "Cost vector must be finite".to_string(),
+            ));
+        }
+
+        let mut columns = vec![Vec::new(); n];
+        let mut rhs = Vec::new();
+        let mut m_ub = 0;
+
+        for (name, block) in [("A_ub", &problem.inequality), ("A_eq", &problem.equality)] {
+            if let Some((a, b)) = block {
+                let (rows, cols) = a.shape();
+                if cols != n {
+                    return Err(OptimizeError::ValueError(format!(
+                        "{} has {} columns, but there are {} variables",
+                        name, cols, n
+                    )));
+                }
+                if b.len() != rows {
+                    return Err(OptimizeError::ValueError(format!(
+                        "{} has {} rows, but the right-hand side has length {}",
+                        name,
+                        rows,
+                        b.len()
+                    )));
+                }
+                if b.iter().any(|v| !v.is_finite()) {
+                    return Err(OptimizeError::ValueError(format!(
+                        "Right-hand side of {} must be finite",
+                        name
+                    )));
+                }
+
+                let offset = rhs.len();
+                let mut finite = true;
+                a.for_each_nonzero(|i, j, v| {
+                    finite &= v.is_finite();
+                    columns[j].push((offset + i, v));
+                });
+                if !finite {
+                    return Err(OptimizeError::ValueError(format!(
+                        "{} must contain only finite values",
+                        name
+                    )));
+                }
+                rhs.extend(b.iter().cloned());
+                if name == "A_ub" {
+                    m_ub = rows;
+                }
+            }
+        }
+        for column in &mut columns {
+            column.sort_by_key(|&(i, _)| i);
+        }
+
+        let m = rhs.len();
+        let (mut lower, mut upper) = problem.bound_vectors();
+        if lower.len() != n || upper.len() != n {
+            return Err(OptimizeError::ValueError(format!(
+                "Bounds have length {}, but there are {} variables",
+                lower.len(),
+                n
+            )));
+        }
+        for j in 0..n {
+            if lower[j].is_nan()
+                || upper[j].is_nan()
+                || lower[j] > upper[j]
+                || lower[j] == f64::INFINITY
+                || upper[j] == f64::NEG_INFINITY
+            {
+                return Err(OptimizeError::ValueError(format!(
+                    "Invalid bounds for variable {}",
+                    j
+                )));
+            }
+        }
+        lower.extend((0..m).map(|_| 0.0));
+        upper.extend((0..m).map(|i| if i < m_ub { f64::INFINITY } else { 0.0 }));
+
+        Ok(LpModel {
+            n,
+            m,
+            m_ub,
+            cost: problem.c.to_vec(),
+            columns,
+            rhs,
+            lower,
+            upper,
+        })
+    }
+
+    /// Append the inequality row `Σ a_j x_j ≤ rhs` on structural variables
+    ///
+    /// The row is placed after all existing rows (including equality rows), and
+    /// its logical variable, bounded by `[0, ∞)`, becomes the last variable.
+    pub(crate) fn append_inequality(&mut self, coefficients: &[(usize, f64)], rhs: f64) {
+        let row = self.m;
+        for &(j, v) in coefficients {
+            self.columns[j].push((row, v));
+        }
+        self.rhs.push(rhs);
+        self.lower.push(0.0);
+        self.upper.push(f64::INFINITY);
+        self.m += 1;
+    }
+
+    /// Row-wise copy of the structural part of the constraint matrix
+    pub(crate) fn rows(&self) -> Vec<Vec<(usize, f64)>> {
+        let mut rows = vec![Vec::new(); self.m];
+        for (j, column) in self.columns.iter().enumerate() {
+            for &(i, v) in column {
+                rows[i].push((j, v));
+            }
+        }
+        rows
+    }
+
+    /// Visit the nonzeros of column `j` of `[A I]`
+    pub(crate) fn for_column<G: FnMut(usize, f64)>(&self, j: usize, mut f: G) {
+        if j < self.n {
+            for &(i, v) in &self.columns[j] {
+                f(i, v);
+            }
+        } else {
+            f(j - self.n, 1.0);
+        }
+    }
+
+    /// Dot product of column `j` of `[A I]` with a row vector
+    pub(crate) fn column_dot(&self, j: usize, y: &[f64]) -> f64 {
+        if j < self.n {
+            self.columns[j].iter().map(|&(i, v)| v * y[i]).sum()
+        } else {
+            y[j - self.n]
+        }
+    }
+
+    /// Cost of variable `j` of `[A I]`
+    pub(crate) fn cost_of(&self, j: usize) -> f64 {
+        if j < self.n {
+            self.cost[j]
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Raw solution of an LP solver in terms of an [`LpModel`]
+#[derive(Debug, Clone)]
+pub(crate) struct LpSolution {
+    /// Termination status
+    pub status: LpStatus,
+    /// Values of all `n + m` variables
+    pub x: Vec<f64>,
+    /// Row duals
+    pub y: Vec<f64>,
+    /// Final basis status of all `n + m` variables (simplex only)
+    pub basis: Option<Vec<VariableStatus>>,
+    /// Number of iterations
+    pub iterations: usize,
+    /// Additional termination detail
+    pub detail: Option<String>,
+}
+
+/// Solve a linear programming problem.
+///
+/// # Arguments
+///
+/// * `problem` - The linear program
+/// * `method` - The solution method
+/// * `options` - Solver options (tolerance, iteration limit, warm start)
+///
+/// # Returns
+///
+/// * A [`LinprogResult`] with the primal solution, slacks and dual values.
+///   Infeasible and unbounded problems are reported through
+///   `result.status` (`2` and `3`) rather than as errors.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_optimize::linprog::{linprog, LinearProgram, Method, Options};
+/// use scirs2_optimize::Bounds;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // minimize -x0 + 4x1  s.t.  -3x0 + x1 ≤ 6,  x0 + 2x1 ≤ 4,  x1 ≥ -3
+/// let problem = LinearProgram::new(array![-1.0, 4.0])
+///     .with_inequality(array![[-3.0, 1.0], [1.0, 2.0]], array![6.0, 4.0])
+///     .with_bounds(Bounds::new(&[(None, None), (Some(-3.0), None)]));
+///
+/// let ip = linprog(&problem, Method::InteriorPoint, None)?;
+/// assert!((ip.result.fun + 22.0).abs() < 1e-6);
+///
+/// // Re-solve a tightened problem from the previous simplex basis
+/// let simplex = linprog(&problem, Method::DualSimplex, None)?;
+/// let tightened = problem
+///     .clone()
+///     .with_bounds(Bounds::new(&[(None, Some(8.0)), (Some(-3.0), None)]));
+/// let options = Options {
+///     warm_start: simplex.basis.clone(),
+///     ..Options::default()
+/// };
+/// let warm = linprog(&tightened, Method::DualSimplex, Some(options))?;
+/// assert!((warm.result.x[0] - 8.0).abs() < 1e-8);
+/// # Ok(())
+/// # }
+/// ```
+pub fn linprog(
+    problem: &LinearProgram,
+    method: Method,
+    options: Option<Options>,
+) -> OptimizeResult<LinprogResult> {
+    let options = options.unwrap_or_default();
+    let model = LpModel::new(problem)?;
+
+    let solution = match method {
+        Method::DualSimplex => {
+            let warm = match &options.warm_start {
+                Some(basis) => {
+                    if basis.variables.len() != model.n || basis.constraints.len() != model.m {
+                        return Err(OptimizeError::ValueError(format!(
+                            "Warm start basis has {} variables and {} constraints, expected {} and {}",
+                            basis.variables.len(),
+                            basis.constraints.len(),
+                            model.n,
+                            model.m
+                        )));
+                    }
+                    let mut status = basis.variables.clone();
+                    status.extend_from_slice(&basis.constraints);
+                    Some(status)
+                }
+                None => None,
+            };
+            dual_simplex(
+                &model,
+                &model.lower,
+                &model.upper,
+                warm.as_deref(),
+                options.tol.unwrap_or(1e-9),
+                options.maxiter.unwrap_or(10 * (model.n + model.m) + 1000),
+            )
+        }
+        Method::InteriorPoint => interior_point::mehrotra(
+            &model,
+            options.tol.unwrap_or(1e-8),
+            options.maxiter.unwrap_or(200),
+        ),
+    };
+
+    Ok(assemble_result(problem, &model, solution))
+}
+
+/// Convert a raw solution into a [`LinprogResult`]
+fn assemble_result(
+    problem: &LinearProgram,
+    model: &LpModel,
+    solution: LpSolution,
+) -> LinprogResult {
+    let n = model.n;
+    let x = Array1::from_vec(solution.x[..n].to_vec());
+    let fun = problem.c.dot(&x);
+
+    let residual = |block: &Option<(ConstraintMatrix, Array1<f64>)>| match block {
+        Some((a, b)) => b - &a.dot(&x),
+        None => Array1::zeros(0),
+    };
+    let slack = residual(&problem.inequality);
+    let con = residual(&problem.equality);
+
+    let y = &solution.y;
+    let ineqlin_marginals = Array1::from_vec(y[..model.m_ub].to_vec());
+    let eqlin_marginals = Array1::from_vec(y[model.m_ub..].to_vec());
+
+    let mut lower_marginals = Array1::zeros(n);
+    let mut upper_marginals = Array1::zeros(n);
+    for j in 0..n {
+        let d = model.cost[j] - model.column_dot(j, y);
+        let at_lower = (x[j] - model.lower[j]).abs() <= (x[j] - model.upper[j]).abs();
+        if d > 0.0 && at_lower {
+            lower_marginals[j] = d;
+        } else if d < 0.0 && !at_lower {
+            upper_marginals[j] = d;
+        }
+    }
+
+    let basis = solution.basis.map(|status| Basis {
+        variables: status[..n].to_vec(),
+        constraints: status[n..].to_vec(),
+    });
+
+    let message = match solution.detail {
+        Some(detail) => format!("{} {}", solution.status.message(), detail),
+        None => solution.status.message().to_string(),
+    };
+
+    LinprogResult {
+        result: OptimizeResults {
+            x,
+            fun,
+            nit: solution.iterations,
+            message,
+            success: solution.status == LpStatus::Optimal,
+            status: solution.status.code(),
+            ..Default::default()
+        },
+        slack,
+        con,
+        ineqlin_marginals,
+        eqlin_marginals,
+        lower_marginals,
+        upper_marginals,
+        basis,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use ndarray::array;
+
+    const METHODS: [Method; 2] = [Method::DualSimplex, Method::InteriorPoint];
+
+    #[test]
+    fn test_linprog_inequality_and_equality() {
+        // minimize 2x0 + 3x1 + x2  s.t.  x0 + x1 + x2 = 10,  x0 - x1 ≤ 2,
+        // x1 + 2x2 ≥ 8,  0 ≤ x ≤ 6
+        let problem = LinearProgram::new(array![2.0, 3.0, 1.0])
+            .with_inequality(
+                array![[1.0, -1.0, 0.0], [0.0, -1.0, -2.0]],
+                array![2.0, -8.0],
+            )
+            .with_equality(array![[1.0, 1.0, 1.0]], array![10.0])
+            .with_bounds(Bounds::new(&[(Some(0.0), Some(6.0)); 3]));
+
+        for method in METHODS {
+            let res = linprog(&problem, method, None).unwrap();
+            assert!(res.result.success, "{}: {}", method, res.result.message);
+            assert_eq!(res.result.status, 0);
+            // Optimum: x2 at its upper bound, x0 - x1 ≤ 2 active
+            assert_abs_diff_eq!(res.result.fun, 15.0, epsilon = 1e-6);
+            assert_abs_diff_eq!(res.result.x[0], 3.0, epsilon = 1e-6);
+            assert_abs_diff_eq!(res.result.x[1], 1.0, epsilon = 1e-6);
+            assert_abs_diff_eq!(res.result.x[2], 6.0, epsilon = 1e-6);
+            assert_abs_diff_eq!(res.slack[0], 0.0, epsilon = 1e-6);
+            assert_abs_diff_eq!(res.slack[1], 5.0, epsilon = 1e-6);
+            assert_abs_diff_eq!(res.con[0], 0.0, epsilon = 1e-6);
+
+            assert_abs_diff_eq!(res.ineqlin_marginals[0], -0.5, epsilon = 1e-5);
+            assert_abs_diff_eq!(res.ineqlin_marginals[1], 0.0, epsilon = 1e-5);
+            assert_abs_diff_eq!(res.eqlin_marginals[0], 2.5, epsilon = 1e-5);
+            assert_abs_diff_eq!(res.upper_marginals[2], -1.5, epsilon = 1e-5);
+            assert_abs_diff_eq!(res.lower_marginals.sum(), 0.0, epsilon = 1e-5);
+
+            // Strong duality: cᵀx = b_ubᵀy_ub + b_eqᵀy_eq + uᵀz_u (all lower bounds are 0)
+            let dual = 2.0 * res.ineqlin_marginals[0] - 8.0 * res.ineqlin_marginals[1]
+                + 10.0 * res.eqlin_marginals[0]
+                + 6.0 * res.upper_marginals.sum();
+            assert_abs_diff_eq!(dual, 15.0, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_linprog_sparse_matches_dense() {
+        let dense = array![
+            [1.0, 0.0, 2.0, 0.0],
+            [0.0, 1.0, 0.0, 3.0],
+            [1.0, 1.0, 1.0, 1.0]
+        ];
+        let (rows, cols, vals): (Vec<usize>, Vec<usize>, Vec<f64>) = {
+            let mut r = Vec::new();
+            let mut c = Vec::new();
+            let mut v = Vec::new();
+            for ((i, j), &x) in dense.indexed_iter() {
+                if x != 0.0 {
+                    r.push(i);
+                    c.push(j);
+                    v.push(x);
+                }
+            }
+            (r, c, v)
+        };
+        let sparse = CsrArray::from_triplets(&rows, &cols, &vals, (3, 4), false).unwrap();
+        let c = array![-1.0, -1.0, -2.0, -1.5];
+        let b = array![4.0, 6.0, 5.0];
+
+        let dense_problem = LinearProgram::new(c.clone()).with_inequality(dense, b.clone());
+        let sparse_problem = LinearProgram::new(c).with_inequality(sparse, b);
+
+        for method in METHODS {
+            let d = linprog(&dense_problem, method, None).unwrap();
+            let s = linprog(&sparse_problem, method, None).unwrap();
+            assert!(d.result.success && s.result.success);
+            assert_abs_diff_eq!(d.result.fun, s.result.fun, epsilon = 1e-6);
+            assert_abs_diff_eq!(d.result.fun, -7.75, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_linprog_sparse_transportation() {
+        // Balanced transportation problem: the supply and demand rows are
+        // linearly dependent, so the normal equations are singular
+        let k = 25;
+        let (mut rows, mut cols, mut vals) = (Vec::new(), Vec::new(), Vec::new());
+        for i in 0..k {
+            for j in 0..k {
+                rows.extend([i, k + j]);
+                cols.extend([i * k + j, i * k + j]);
+                vals.extend([1.0, 1.0]);
+            }
+        }
+        let a = CsrArray::from_triplets(&rows, &cols, &vals, (2 * k, k * k), false).unwrap();
+        let b = Array1::from_elem(2 * k, 1.0);
+        let c = Array1::from_shape_fn(k * k, |v| ((v / k + 2 * (v % k)) % k) as f64);
+        let problem = LinearProgram::new(c).with_equality(a, b);
+
+        let simplex = linprog(&problem, Method::DualSimplex, None).unwrap();
+        let ip = linprog(&problem, Method::InteriorPoint, None).unwrap();
+        assert!(simplex.result.success, "{}", simplex.result.message);
+        assert!(ip.result.success, "{}", ip.result.message);
+        assert!(!ip.result.message.contains("dual simplex"));
+        // Every row can pick a distinct zero-cost column
+        assert_abs_diff_eq!(simplex.result.fun, 0.0, epsilon = 1e-8);
+        assert_abs_diff_eq!(ip.result.fun, 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_linprog_infeasible_and_unbounded() {
+        // x0 + x1 ≤ 1 and x0 + x1 ≥ 3 cannot both hold
+        let infeasible = LinearProgram::new(array![1.0, 1.0])
+            .with_inequality(array![[1.0, 1.0], [-1.0, -1.0]], array![1.0, -3.0]);
+        // minimize -x0 with x0 - x1 ≤ 1 and x ≥ 0 is unbounded along x0 = x1
+        let unbounded =
+            LinearProgram::new(array![-1.0, 0.0]).with_inequality(array![[1.0, -1.0]], array![1.0]);
+        // Free variables with a bounded optimum
+        let free = LinearProgram::new(array![1.0, 1.0])
+            .with_inequality(array![[-1.0, 0.0], [0.0, -1.0]], array![3.0, -2.0])
+            .with_bounds(Bounds::new(&[(None, None), (None, None)]));
+
+        for method in METHODS {
+            let res = linprog(&infeasible, method, None).unwrap();
+            assert_eq!(res.result.status, 2, "{}: {}", method, res.result.message);
+            assert!(!res.result.success);
+
+            let res = linprog(&unbounded, method, None).unwrap();
+            assert_eq!(res.result.status, 3, "{}: {}", method, res.result.message);
+
+            let res = linprog(&free, method, None).unwrap();
+            assert!(res.result.success, "{}: {}", method, res.result.message);
+            assert_abs_diff_eq!(res.result.fun, -1.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_linprog_warm_start() {
+        // Transportation-style problem: 3 supplies, 3 demands
+        let cost = array![4.0, 6.0, 9.0, 5.0, 3.0, 8.0, 7.0, 4.0, 2.0];
+        let mut a_eq = Array2::zeros((3, 9));
+        let mut a_ub = Array2::zeros((3, 9));
+        for i in 0..3 {
+            for j in 0..3 {
+                a_ub[[i, 3 * i + j]] = 1.0;
+                a_eq[[j, 3 * i + j]] = 1.0;
+            }
+        }
+        let problem = LinearProgram::new(cost)
+            .with_inequality(a_ub, array![50.0, 60.0, 40.0])
+            .with_equality(a_eq, array![40.0, 50.0, 45.0]);
+
+        let cold = linprog(&problem, Method::DualSimplex, None).unwrap();
+        assert!(cold.result.success);
+        let ip = linprog(&problem, Method::InteriorPoint, None).unwrap();
+        assert_abs_diff_eq!(cold.result.fun, ip.result.fun, epsilon = 1e-5);
+
+        // Restarting from the optimal basis needs no iterations
+        let options = Options {
+            warm_start: cold.basis.clone(),
+            ..Options::default()
+        };
+        let warm = linprog(&problem, Method::DualSimplex, Some(options.clone())).unwrap();
+        assert_eq!(warm.result.nit, 0);
+        assert_abs_diff_eq!(warm.result.fun, cold.result.fun, epsilon = 1e-9);
+
+        // A tightened upper bound is handled from the old basis
+        let bounds: Vec<_> = (0..9)
+            .map(|j| (Some(0.0), if j == 8 { Some(10.0) } else { None }))
+            .collect();
+        let tightened = problem.clone().with_bounds(Bounds::new(&bounds));
+        let warm = linprog(&tightened, Method::DualSimplex, Some(options)).unwrap();
+        let cold = linprog(&tightened, Method::DualSimplex, None).unwrap();
+        assert!(warm.result.success && cold.result.success);
+        assert_abs_diff_eq!(warm.result.fun, cold.result.fun, epsilon = 1e-8);
+        assert!(warm.result.x[8] <= 10.0 + 1e-9);
+
+        let bad = Options {
+            warm_start: Some(Basis {
+                variables: vec![VariableStatus::Basic; 2],
+                constraints: vec![],
+            }),
+            ..Options::default()
+        };
+        assert!(linprog(&problem, Method::DualSimplex, Some(bad)).is_err());
+    }
+}
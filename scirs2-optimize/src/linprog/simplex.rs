@@ -0,0 +1,616 @@
+//! Bounded revised dual simplex method
+//!
+//! The method works on the computational form of [`LpModel`]. The basis is
+//! factorized with the sparse LU factorization of `scirs2_sparse`, updated in
+//! product form after every pivot and periodically refactorized. Dual feasibility of the starting basis (cold or warm) is
+//! obtained by placing every nonbasic variable at the bound matching the sign
+//! of its reduced cost; where that bound is infinite a temporary artificial
+//! bound is used. Artificial bounds are enlarged or released once the boxed
+//! problem is solved, which also yields rigorous infeasibility and
+//! unboundedness certificates.
+
+use super::{LpModel, LpSolution, LpStatus, VariableStatus};
+use scirs2_sparse::csr::CsrMatrix;
+use scirs2_sparse::linalg::{splu, SparseLu, SpluOptions};
+
+/// Number of basis updates between refactorizations
+const REFACTOR_INTERVAL: usize = 64;
+
+/// Smallest pivot magnitude accepted in the ratio test
+const PIVOT_TOL: f64 = 1e-9;
+
+/// Initial size of artificial bounds relative to the problem scale
+const ARTIFICIAL_BOUND: f64 = 1e6;
+
+/// Artificial bounds beyond this size indicate numerical trouble
+const MAX_ARTIFICIAL_BOUND: f64 = 1e30;
+
+/// Elementary transformation of a product-form basis update
+struct Eta {
+    /// Row of the leaving variable
+    row: usize,
+    /// Pivot element `(B⁻¹ a_q)_r`
+    pivot: f64,
+    /// Remaining nonzeros of `B⁻¹ a_q`
+    column: Vec<(usize, f64)>,
+}
+
+/// Sparse LU factorization of the basis matrix with product-form updates
+///
+/// After `k` pivots the basis is `B₀ E₁ ⋯ E_k`, where `B₀` is the factored
+/// basis and `E_i` is the identity with one column replaced.
+struct BasisFactor {
+    m: usize,
+    lu: SparseLu<f64>,
+    etas: Vec<Eta>,
+}
+
+impl BasisFactor {
+    /// Factorize the basis formed by the columns `head` of `[A I]`
+    fn factor(model: &LpModel, head: &[usize]) -> Option<Self> {
+        let m = model.m;
+        let (mut rows, mut cols, mut data) = (Vec::new(), Vec::new(), Vec::new());
+        for (k, &j) in head.iter().enumerate() {
+            model.for_column(j, |i, v| {
+                rows.push(i);
+                cols.push(k);
+                data.push(v);
+            });
+        }
+        let b = CsrMatrix::new(data, rows, cols, (m, m)).ok()?;
+        let lu = splu(&b, SpluOptions::default()).ok()?;
+
+        // Reject numerically singular bases
+        let u = lu.u().ok()?;
+        if (0..m).any(|k| u.get(k, k).abs() < 1e-11) {
+            return None;
+        }
+
+        Some(BasisFactor {
+            m,
+            lu,
+            etas: Vec::new(),
+        })
+    }
+
+    /// Row `r` of the inverse
+    fn row(&self, r: usize) -> Vec<f64> {
+        let mut e = vec![0.0; self.m];
+        e[r] = 1.0;
+        self.apply_transpose(&e)
+    }
+
+    /// `B⁻¹ v`
+    fn apply(&self, v: &[f64]) -> Vec<f64> {
+        let mut out = self.lu.solve(v).expect("vector has the basis dimension");
+        for eta in &self.etas {
+            let pivot = out[eta.row] / eta.pivot;
+            out[eta.row] = pivot;
+            if pivot != 0.0 {
+                for &(i, a) in &eta.column {
+                    out[i] -= a * pivot;
+                }
+            }
+        }
+        out
+    }
+
+    /// `B⁻ᵀ v`
+    fn apply_transpose(&self, v: &[f64]) -> Vec<f64> {
+        let mut v = v.to_vec();
+        for eta in self.etas.iter().rev() {
+            let dot: f64 = eta.column.iter().map(|&(i, a)| a * v[i]).sum();
+            v[eta.row] = (v[eta.row] - dot) / eta.pivot;
+        }
+        self.lu
+            .solve_transpose(&v)
+            .expect("vector has the basis dimension")
+    }
+
+    /// `B⁻¹ a_j` for column `j` of `[A I]`
+    fn ftran(&self, model: &LpModel, j: usize) -> Vec<f64> {
+        let mut column = vec![0.0; self.m];
+        model.for_column(j, |i, v| column[i] += v);
+        self.apply(&column)
+    }
+
+    /// Replace the basic variable in row `r` by the column with `B⁻¹ a_q = alpha`
+    fn update(&mut self, r: usize, alpha: &[f64]) {
+        let column = alpha
+            .iter()
+            .enumerate()
+            .filter(|&(i, &a)| i != r && a != 0.0)
+            .map(|(i, &a)| (i, a))
+            .collect();
+        self.etas.push(Eta {
+            row: r,
+            pivot: alpha[r],
+            column,
+        });
+    }
+}
+
+/// Solve the model with the bounded dual simplex method
+///
+/// # Arguments
+///
+/// * `model` - Computational form of the LP
+/// * `lower`, `upper` - Bounds of all `n + m` variables (overriding the model's)
+/// * `warm` - Optional starting basis status for all `n + m` variables
+/// * `tol` - Primal and dual feasibility tolerance
+/// * `maxiter` - Maximum number of pivots
+pub(crate) fn dual_simplex(
+    model: &LpModel,
+    lower: &[f64],
+    upper: &[f64],
+    warm: Option<&[VariableStatus]>,
+    tol: f64,
+    maxiter: usize,
+) -> LpSolution {
+    let (n, m) = (model.n, model.m);
+    let total = n + m;
+
+    let scale = model
+        .rhs
+        .iter()
+        .chain(lower.iter())
+        .chain(upper.iter())
+        .filter(|v| v.is_finite())
+        .fold(1.0f64, |acc, v| acc.max(v.abs()));
+    let cost_scale = model.cost.iter().fold(1.0f64, |acc, v| acc.max(v.abs()));
+    let dual_tol = tol * cost_scale;
+    let mut big_m = ARTIFICIAL_BOUND * scale;
+
+    let mut lo = lower.to_vec();
+    let mut up = upper.to_vec();
+    let mut artificial = vec![false; total];
+
+    let (mut head, mut status, mut binv) = warm
+        .and_then(|w| warm_basis(model, w))
+        .unwrap_or_else(|| slack_basis(model, lower, upper));
+
+    let mut x = vec![0.0; total];
+    let mut y = vec![0.0; m];
+    let mut iterations = 0;
+    let mut since_refactor = 0;
+    let mut previous_optimum: Option<Vec<f64>> = None;
+
+    let finish = |status_code: LpStatus,
+                  x: Vec<f64>,
+                  y: Vec<f64>,
+                  basis: Vec<VariableStatus>,
+                  iterations: usize,
+                  detail: Option<&str>| LpSolution {
+        status: status_code,
+        x,
+        y,
+        basis: Some(basis),
+        iterations,
+        detail: detail.map(str::to_string),
+    };
+
+    loop {
+        // Duals and reduced costs
+        let cb: Vec<f64> = head.iter().map(|&j| model.cost_of(j)).collect();
+        y = binv.apply_transpose(&cb);
+        let d: Vec<f64> = (0..total)
+            .map(|j| {
+                if status[j] == VariableStatus::Basic {
+                    0.0
+                } else {
+                    model.cost_of(j) - model.column_dot(j, &y)
+                }
+            })
+            .collect();
+
+        // Place nonbasic variables so that the basis is dual feasible
+        for j in 0..total {
+            if status[j] == VariableStatus::Basic {
+                continue;
+            }
+            if lo[j] == up[j] {
+                status[j] = VariableStatus::AtLower;
+            } else if d[j] > dual_tol {
+                if lo[j] == f64::NEG_INFINITY {
+                    lo[j] = up[j].min(0.0) - big_m;
+                    artificial[j] = true;
+                }
+                status[j] = VariableStatus::AtLower;
+            } else if d[j] < -dual_tol {
+                if up[j] == f64::INFINITY {
+                    up[j] = lo[j].max(0.0) + big_m;
+                    artificial[j] = true;
+                }
+                status[j] = VariableStatus::AtUpper;
+            } else {
+                status[j] = match status[j] {
+                    VariableStatus::AtLower if lo[j].is_finite() => VariableStatus::AtLower,
+                    VariableStatus::AtUpper if up[j].is_finite() => VariableStatus::AtUpper,
+                    _ => default_status(lo[j], up[j]),
+                };
+            }
+        }
+
+        // Primal values
+        let mut rhs = model.rhs.clone();
+        for j in 0..total {
+            x[j] = match status[j] {
+                VariableStatus::Basic => continue,
+                VariableStatus::AtLower => lo[j],
+                VariableStatus::AtUpper => up[j],
+                VariableStatus::Free => 0.0,
+            };
+            if x[j] != 0.0 {
+                let xj = x[j];
+                model.for_column(j, |i, v| rhs[i] -= v * xj);
+            }
+        }
+        for (r, v) in binv.apply(&rhs).into_iter().enumerate() {
+            x[head[r]] = v;
+        }
+
+        // Leaving variable: largest primal infeasibility
+        let mut leave: Option<usize> = None;
+        let mut worst = 0.0;
+        for (r, &p) in head.iter().enumerate() {
+            let infeasibility = if x[p] < lo[p] - tol * (1.0 + lo[p].abs()) {
+                lo[p] - x[p]
+            } else if x[p] > up[p] + tol * (1.0 + up[p].abs()) {
+                x[p] - up[p]
+            } else {
+                continue;
+            };
+            if infeasibility > worst {
+                worst = infeasibility;
+                leave = Some(r);
+            }
+        }
+
+        let r = match leave {
+            Some(r) => r,
+            None => {
+                // Optimal for the current bounds; resolve artificial bounds
+                let mut changed = false;
+                let mut enlarge = false;
+                for j in 0..total {
+                    if !artificial[j] || status[j] == VariableStatus::Basic {
+                        continue;
+                    }
+                    if d[j].abs() <= dual_tol {
+                        lo[j] = lower[j];
+                        up[j] = upper[j];
+                        artificial[j] = false;
+                        status[j] = default_status(lo[j], up[j]);
+                        changed = true;
+                    } else if is_unbounded_ray(model, &binv, &head, lower, upper, j, d[j], tol) {
+                        return finish(
+                            LpStatus::Unbounded,
+                            x,
+                            y,
+                            status,
+                            iterations,
+                            Some("(dual simplex found an unbounded ray)"),
+                        );
+                    } else {
+                        enlarge = true;
+                    }
+                }
+                if enlarge {
+                    if let Some(prev) = &previous_optimum {
+                        if is_recession_direction(model, lower, upper, prev, &x, tol) {
+                            return finish(
+                                LpStatus::Unbounded,
+                                x,
+                                y,
+                                status,
+                                iterations,
+                                Some("(dual simplex found an unbounded ray)"),
+                            );
+                        }
+                    }
+                    previous_optimum = Some(x.clone());
+                    if !enlarge_bounds(&mut big_m, &mut lo, &mut up, lower, upper, &artificial) {
+                        return finish(
+                            LpStatus::Numerical,
+                            x,
+                            y,
+                            status,
+                            iterations,
+                            Some("(artificial bounds grew too large)"),
+                        );
+                    }
+                    changed = true;
+                }
+                if !changed {
+                    return finish(LpStatus::Optimal, x, y, status, iterations, None);
+                }
+                continue;
+            }
+        };
+
+        // Ratio test on the pivot row
+        let p = head[r];
+        let to_lower = x[p] < lo[p];
+        let sign = if to_lower { -1.0 } else { 1.0 };
+        let rho = binv.row(r);
+
+        let mut candidates = Vec::new();
+        let mut blocked_by_artificial = false;
+        for j in 0..total {
+            if status[j] == VariableStatus::Basic || lo[j] == up[j] {
+                continue;
+            }
+            let alpha = sign * model.column_dot(j, &rho);
+            let eligible = match status[j] {
+                VariableStatus::AtLower => alpha > PIVOT_TOL,
+                VariableStatus::AtUpper => alpha < -PIVOT_TOL,
+                VariableStatus::Free => alpha.abs() > PIVOT_TOL,
+                VariableStatus::Basic => false,
+            };
+            if eligible {
+                candidates.push((j, alpha));
+            } else if artificial[j] && alpha.abs() > PIVOT_TOL {
+                // Without the artificial bound the variable could move the other way
+                blocked_by_artificial = true;
+            }
+        }
+
+        if candidates.is_empty() {
+            if blocked_by_artificial {
+                if !enlarge_bounds(&mut big_m, &mut lo, &mut up, lower, upper, &artificial) {
+                    return finish(
+                        LpStatus::Numerical,
+                        x,
+                        y,
+                        status,
+                        iterations,
+                        Some("(artificial bounds grew too large)"),
+                    );
+                }
+                continue;
+            }
+            return finish(
+                LpStatus::Infeasible,
+                x,
+                y,
+                status,
+                iterations,
+                Some("(dual simplex found a dual ray)"),
+            );
+        }
+
+        // Harris two-pass ratio test
+        let bound = candidates
+            .iter()
+            .map(|&(j, alpha)| (d[j].abs() + dual_tol) / alpha.abs())
+            .fold(f64::INFINITY, f64::min);
+        let (q, _) = candidates
+            .iter()
+            .filter(|&&(j, alpha)| d[j].abs() / alpha.abs() <= bound)
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .copied()
+            .unwrap_or(candidates[0]);
+
+        let alpha_q = binv.ftran(model, q);
+        let alpha_rq = model.column_dot(q, &rho);
+        if alpha_q[r].abs() < PIVOT_TOL
+            || (alpha_q[r] - alpha_rq).abs() > 1e-6 * (1.0 + alpha_rq.abs())
+        {
+            if since_refactor == 0 {
+                return finish(
+                    LpStatus::Numerical,
+                    x,
+                    y,
+                    status,
+                    iterations,
+                    Some("(unstable pivot)"),
+                );
+            }
+            match BasisFactor::factor(model, &head) {
+                Some(fresh) => binv = fresh,
+                None => {
+                    return finish(
+                        LpStatus::Numerical,
+                        x,
+                        y,
+                        status,
+                        iterations,
+                        Some("(singular basis)"),
+                    )
+                }
+            }
+            since_refactor = 0;
+            continue;
+        }
+
+        binv.update(r, &alpha_q);
+        status[p] = if to_lower || lo[p] == up[p] {
+            VariableStatus::AtLower
+        } else {
+            VariableStatus::AtUpper
+        };
+        head[r] = q;
+        status[q] = VariableStatus::Basic;
+        if artificial[q] {
+            lo[q] = lower[q];
+            up[q] = upper[q];
+            artificial[q] = false;
+        }
+
+        iterations += 1;
+        since_refactor += 1;
+        if since_refactor >= REFACTOR_INTERVAL {
+            match BasisFactor::factor(model, &head) {
+                Some(fresh) => binv = fresh,
+                None => {
+                    return finish(
+                        LpStatus::Numerical,
+                        x,
+                        y,
+                        status,
+                        iterations,
+                        Some("(singular basis)"),
+                    )
+                }
+            }
+            since_refactor = 0;
+        }
+        if iterations >= maxiter {
+            return finish(LpStatus::IterationLimit, x, y, status, iterations, None);
+        }
+    }
+}
+
+/// Rows of the simplex tableau `B⁻¹[A I]` for selected basic variables
+///
+/// Returns `(p, row)` pairs, where `row` holds the coefficients of all
+/// `n + m` variables in the row of basic variable `p`. Returns `None` if the
+/// basis is invalid or singular.
+pub(crate) fn tableau_rows<P: Fn(usize) -> bool>(
+    model: &LpModel,
+    basis: &[VariableStatus],
+    select: P,
+) -> Option<Vec<(usize, Vec<f64>)>> {
+    let (head, _, binv) = warm_basis(model, basis)?;
+    let total = model.n + model.m;
+    let rows = head
+        .iter()
+        .enumerate()
+        .filter(|&(_, &p)| select(p))
+        .map(|(r, &p)| {
+            let rho = binv.row(r);
+            let row = (0..total).map(|j| model.column_dot(j, &rho)).collect();
+            (p, row)
+        })
+        .collect();
+    Some(rows)
+}
+
+/// Nonbasic status for a variable without a preferred bound
+fn default_status(lo: f64, up: f64) -> VariableStatus {
+    if lo.is_finite() {
+        VariableStatus::AtLower
+    } else if up.is_finite() {
+        VariableStatus::AtUpper
+    } else {
+        VariableStatus::Free
+    }
+}
+
+/// Slack basis with structural variables at their finite bounds
+fn slack_basis(
+    model: &LpModel,
+    lower: &[f64],
+    upper: &[f64],
+) -> (Vec<usize>, Vec<VariableStatus>, BasisFactor) {
+    let (n, m) = (model.n, model.m);
+    let mut status: Vec<VariableStatus> =
+        (0..n).map(|j| default_status(lower[j], upper[j])).collect();
+    status.extend((0..m).map(|_| VariableStatus::Basic));
+    let head: Vec<usize> = (n..n + m).collect();
+    let binv = BasisFactor::factor(model, &head).expect("slack basis is the identity");
+    (head, status, binv)
+}
+
+/// Basis from user supplied statuses, if it is valid and nonsingular
+fn warm_basis(
+    model: &LpModel,
+    warm: &[VariableStatus],
+) -> Option<(Vec<usize>, Vec<VariableStatus>, BasisFactor)> {
+    let head: Vec<usize> = (0..warm.len())
+        .filter(|&j| warm[j] == VariableStatus::Basic)
+        .collect();
+    if warm.len() != model.n + model.m || head.len() != model.m {
+        return None;
+    }
+    let binv = BasisFactor::factor(model, &head)?;
+    Some((head, warm.to_vec(), binv))
+}
+
+/// Check whether moving nonbasic `j` towards its infinite side is an unbounded ray
+#[allow(clippy::too_many_arguments)]
+fn is_unbounded_ray(
+    model: &LpModel,
+    binv: &BasisFactor,
+    head: &[usize],
+    lower: &[f64],
+    upper: &[f64],
+    j: usize,
+    dj: f64,
+    tol: f64,
+) -> bool {
+    let direction = if dj < 0.0 { 1.0 } else { -1.0 };
+    if (direction > 0.0 && upper[j].is_finite()) || (direction < 0.0 && lower[j].is_finite()) {
+        return false;
+    }
+    let alpha = binv.ftran(model, j);
+    head.iter().zip(&alpha).all(|(&p, &a)| {
+        let change = -direction * a;
+        if change < -tol {
+            lower[p] == f64::NEG_INFINITY
+        } else if change > tol {
+            upper[p] == f64::INFINITY
+        } else {
+            true
+        }
+    })
+}
+
+/// Check whether `to - from` is a direction of unboundedness
+///
+/// Both points satisfy the equality system, so only the bound signs and the
+/// cost decrease need to be verified.
+fn is_recession_direction(
+    model: &LpModel,
+    lower: &[f64],
+    upper: &[f64],
+    from: &[f64],
+    to: &[f64],
+    tol: f64,
+) -> bool {
+    let direction: Vec<f64> = to.iter().zip(from).map(|(a, b)| a - b).collect();
+    let norm = direction.iter().fold(0.0f64, |acc, v| acc.max(v.abs()));
+    if norm == 0.0 {
+        return false;
+    }
+    let eps = 1e3 * tol * norm;
+    let signs_ok = direction.iter().enumerate().all(|(j, &dj)| {
+        (lower[j] == f64::NEG_INFINITY || dj >= -eps) && (upper[j] == f64::INFINITY || dj <= eps)
+    });
+    let cost_change: f64 = (0..direction.len())
+        .map(|j| model.cost_of(j) * direction[j])
+        .sum();
+    signs_ok && cost_change < -tol * norm
+}
+
+/// Multiply the artificial bounds by 1000; returns `false` once they become too large
+fn enlarge_bounds(
+    big_m: &mut f64,
+    lo: &mut [f64],
+    up: &mut [f64],
+    lower: &[f64],
+    upper: &[f64],
+    artificial: &[bool],
+) -> bool {
+    *big_m *= 1e3;
+    if *big_m > MAX_ARTIFICIAL_BOUND {
+        return false;
+    }
+    for j in 0..artificial.len() {
+        if artificial[j] {
+            if lower[j] == f64::NEG_INFINITY {
+                lo[j] = up[j].min(0.0) - *big_m;
+                if upper[j] == f64::INFINITY {
+                    lo[j] = -*big_m;
+                }
+            }
+            if upper[j] == f64::INFINITY {
+                up[j] = lo[j].max(0.0) + *big_m;
+                if lower[j] == f64::NEG_INFINITY {
+                    up[j] = *big_m;
+                }
+            }
+        }
+    }
+    true
+}
@@ -0,0 +1,277 @@
+//! Cutting planes for the root LP relaxation
+//!
+//! Gomory mixed-integer cuts are read off the rows of the optimal simplex
+//! tableau, and minimal cover inequalities are separated from inequality rows
+//! that contain only binary variables. Every cut is an inequality
+//! `Σ a_j x_j ≤ b` in the structural variables that is satisfied by all
+//! integer feasible points, so cuts can be added to the model permanently.
+
+use crate::linprog::{tableau_rows, LpModel, VariableStatus};
+
+/// Minimum distance of a basic value from the nearest integer for a Gomory cut
+const MIN_FRACTIONALITY: f64 = 0.01;
+
+/// Tableau entries larger than this make a Gomory cut numerically unreliable
+const MAX_TABLEAU_ENTRY: f64 = 1e6;
+
+/// Largest accepted ratio between the largest and smallest cut coefficient
+const MAX_DYNAMISM: f64 = 1e8;
+
+/// Minimum violation of a cut at the LP solution, relative to its norm
+const MIN_EFFICACY: f64 = 1e-4;
+
+/// A valid inequality `Σ a_j x_j ≤ rhs` on structural variables
+#[derive(Debug, Clone)]
+pub(crate) struct Cut {
+    /// Nonzero coefficients as `(variable, value)` pairs
+    pub coefficients: Vec<(usize, f64)>,
+    /// Right-hand side
+    pub rhs: f64,
+}
+
+impl Cut {
+    /// Euclidean distance by which `x` violates the cut
+    pub(crate) fn efficacy(&self, x: &[f64]) -> f64 {
+        let activity: f64 = self.coefficients.iter().map(|&(j, a)| a * x[j]).sum();
+        let norm = self
+            .coefficients
+            .iter()
+            .map(|&(_, a)| a * a)
+            .sum::<f64>()
+            .sqrt();
+        if norm > 0.0 {
+            (activity - self.rhs) / norm
+        } else {
+            0.0
+        }
+    }
+
+    /// Build a cut from dense coefficients
+    ///
+    /// Tiny coefficients are removed by relaxing the right-hand side with the
+    /// variable bounds; cuts with too large a coefficient range are rejected.
+    fn from_dense(dense: &[f64], mut rhs: f64, lower: &[f64], upper: &[f64]) -> Option<Self> {
+        let largest = dense.iter().fold(0.0f64, |acc, a| acc.max(a.abs()));
+        if largest == 0.0 || !largest.is_finite() || !rhs.is_finite() {
+            return None;
+        }
+
+        let mut coefficients = Vec::new();
+        for (j, &a) in dense.iter().enumerate() {
+            if a == 0.0 {
+                continue;
+            }
+            if a.abs() < largest / MAX_DYNAMISM {
+                // Σ_rest a x ≤ rhs - a x_j ≤ rhs - min over the bounds of a x_j
+                let bound = if a > 0.0 { lower[j] } else { upper[j] };
+                if bound.is_finite() {
+                    rhs -= a * bound;
+                    continue;
+                }
+            }
+            coefficients.push((j, a));
+        }
+
+        let smallest = coefficients
+            .iter()
+            .fold(f64::INFINITY, |acc, &(_, a)| acc.min(a.abs()));
+        if coefficients.is_empty() || largest / smallest > MAX_DYNAMISM {
+            return None;
+        }
+        Some(Cut { coefficients, rhs })
+    }
+}
+
+/// Gomory mixed-integer cuts from the optimal tableau
+///
+/// # Arguments
+///
+/// * `model` - Computational form of the LP relaxation
+/// * `rows` - Row-wise copy of the constraint matrix (see [`LpModel::rows`])
+/// * `basis` - Optimal basis status of all `n + m` variables
+/// * `x` - Optimal values of all `n + m` variables
+/// * `lower`, `upper` - Bounds of all `n + m` variables used in the solve
+/// * `integer` - Integrality flags of the structural variables
+pub(crate) fn gomory_cuts(
+    model: &LpModel,
+    rows: &[Vec<(usize, f64)>],
+    basis: &[VariableStatus],
+    x: &[f64],
+    lower: &[f64],
+    upper: &[f64],
+    integer: &[bool],
+) -> Vec<Cut> {
+    let n = model.n;
+    let fractional = |p: usize| {
+        let f = x[p] - x[p].floor();
+        p < n && integer[p] && (MIN_FRACTIONALITY..=1.0 - MIN_FRACTIONALITY).contains(&f)
+    };
+    let tableau = match tableau_rows(model, basis, fractional) {
+        Some(tableau) => tableau,
+        None => return Vec::new(),
+    };
+
+    let mut cuts = Vec::new();
+    'rows: for (p, row) in tableau {
+        let f0 = x[p] - x[p].floor();
+
+        // The cut reads Σ π_j x_j + constant ≥ 1
+        let mut pi = vec![0.0; n];
+        let mut constant = 0.0;
+        for (j, &alpha) in row.iter().enumerate() {
+            if basis[j] == VariableStatus::Basic || lower[j] == upper[j] || alpha.abs() < 1e-12 {
+                continue;
+            }
+            if alpha.abs() > MAX_TABLEAU_ENTRY {
+                continue 'rows;
+            }
+
+            // Nonbasic variables are written as x_j = l_j + t_j or x_j = u_j - t_j
+            let (abar, sign, bound) = match basis[j] {
+                VariableStatus::AtLower if lower[j].is_finite() => (alpha, 1.0, lower[j]),
+                VariableStatus::AtUpper if upper[j].is_finite() => (-alpha, -1.0, upper[j]),
+                _ => continue 'rows,
+            };
+            let coefficient = if j < n && integer[j] {
+                let fj = abar - abar.floor();
+                if fj <= f0 {
+                    fj / f0
+                } else {
+                    (1.0 - fj) / (1.0 - f0)
+                }
+            } else if abar >= 0.0 {
+                abar / f0
+            } else {
+                -abar / (1.0 - f0)
+            };
+            if coefficient == 0.0 {
+                continue;
+            }
+
+            // coefficient · t_j = coefficient · sign · (x_j - bound)
+            let weight = coefficient * sign;
+            constant -= weight * bound;
+            if j < n {
+                pi[j] += weight;
+            } else {
+                // Logical of row i: s_i = b_i - a_iᵀx
+                let i = j - n;
+                constant += weight * model.rhs[i];
+                for &(k, a) in &rows[i] {
+                    pi[k] -= weight * a;
+                }
+            }
+        }
+
+        let negated: Vec<f64> = pi.iter().map(|v| -v).collect();
+        if let Some(cut) = Cut::from_dense(&negated, constant - 1.0, &lower[..n], &upper[..n]) {
+            if cut.efficacy(x) > MIN_EFFICACY {
+                cuts.push(cut);
+            }
+        }
+    }
+    cuts
+}
+
+/// Minimal cover inequalities for knapsack rows over binary variables
+///
+/// Variables with negative coefficients are complemented, turning each row
+/// into a knapsack `Σ w_j z_j ≤ W` with positive weights. A cover `C` with
+/// `Σ_C w_j > W` is chosen greedily from the LP solution and reduced to a
+/// minimal one, giving the cut `Σ_C z_j ≤ |C| - 1`.
+///
+/// # Arguments
+///
+/// * `model` - Computational form of the LP relaxation
+/// * `rows` - Row-wise copy of the constraint matrix (see [`LpModel::rows`])
+/// * `x` - Values of the structural variables in the LP solution
+/// * `lower`, `upper` - Bounds of the structural variables
+/// * `integer` - Integrality flags of the structural variables
+pub(crate) fn cover_cuts(
+    model: &LpModel,
+    rows: &[Vec<(usize, f64)>],
+    x: &[f64],
+    lower: &[f64],
+    upper: &[f64],
+    integer: &[bool],
+) -> Vec<Cut> {
+    let n = model.n;
+    let is_binary = |j: usize| integer[j] && lower[j] == 0.0 && upper[j] == 1.0;
+
+    let mut cuts = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        let is_inequality = model.lower[n + i] == 0.0 && model.upper[n + i] == f64::INFINITY;
+        if !is_inequality || row.len() < 2 || !row.iter().all(|&(j, _)| is_binary(j)) {
+            continue;
+        }
+
+        // Items as (variable, weight, complemented, LP value of z)
+        let mut capacity = model.rhs[i];
+        let mut items: Vec<(usize, f64, bool, f64)> = row
+            .iter()
+            .map(|&(j, a)| {
+                if a > 0.0 {
+                    (j, a, false, x[j])
+                } else {
+                    capacity -= a;
+                    (j, -a, true, 1.0 - x[j])
+                }
+            })
+            .collect();
+        if capacity < 0.0 {
+            continue;
+        }
+        let eps = 1e-9 * (1.0 + capacity.abs());
+
+        items.sort_by(|a, b| ((1.0 - a.3) / a.1).total_cmp(&((1.0 - b.3) / b.1)));
+        let mut weight = 0.0;
+        let mut cover = Vec::new();
+        for &item in &items {
+            cover.push(item);
+            weight += item.1;
+            if weight > capacity + eps {
+                break;
+            }
+        }
+        if weight <= capacity + eps {
+            continue;
+        }
+
+        // Drop items with the smallest LP values while the set remains a cover
+        cover.sort_by(|a, b| a.3.total_cmp(&b.3));
+        let mut k = 0;
+        while k < cover.len() {
+            if weight - cover[k].1 > capacity + eps {
+                weight -= cover[k].1;
+                cover.remove(k);
+            } else {
+                k += 1;
+            }
+        }
+
+        let lp_value: f64 = cover.iter().map(|item| item.3).sum();
+        if lp_value <= cover.len() as f64 - 1.0 + 1e-6 {
+            continue;
+        }
+
+        // Σ_C z_j ≤ |C| - 1 with z_j = 1 - x_j for complemented variables
+        let mut rhs = cover.len() as f64 - 1.0;
+        let mut coefficients: Vec<(usize, f64)> = cover
+            .iter()
+            .map(|&(j, _, complemented, _)| {
+                if complemented {
+                    rhs -= 1.0;
+                    (j, -1.0)
+                } else {
+                    (j, 1.0)
+                }
+            })
+            .collect();
+        coefficients.sort_by_key(|&(j, _)| j);
+        let cut = Cut { coefficients, rhs };
+        if cut.efficacy(x) > MIN_EFFICACY {
+            cuts.push(cut);
+        }
+    }
+    cuts
+}
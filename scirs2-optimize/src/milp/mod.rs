@@ -0,0 +1,842 @@
+//! Mixed-integer linear programming
+//!
+//! This module solves problems of the form
+//!
+//! ```text
+//! minimize    cᵀx
+//! subject to  A_ub x ≤ b_ub
+//!             A_eq x = b_eq
+//!             l ≤ x ≤ u
+//!             x_j integer for every j with integrality[j] = true
+//! ```
+//!
+//! with LP-based branch-and-bound. The problem is described by the same
+//! [`LinearProgram`] as [`crate::linprog`], and every node relaxation is
+//! solved with the dual simplex method, warm started from the basis of its
+//! parent. The root relaxation is strengthened with Gomory mixed-integer cuts
+//! and knapsack cover cuts before branching. Nodes are explored in best-bound
+//! or depth-first order and may be evaluated in parallel batches.
+//!
+//! The search stops when the relative gap between the incumbent and the
+//! dual bound falls below [`Options::mip_rel_gap`], or when the time or node
+//! limit is reached; in the latter case the best solution found so far is
+//! returned together with the remaining gap.
+//!
+//! ## Example
+//!
+//! ```
+//! use ndarray::array;
+//! use scirs2_optimize::linprog::LinearProgram;
+//! use scirs2_optimize::milp::milp;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // maximize y  s.t.  -x + y ≤ 1,  3x + 2y ≤ 12,  2x + 3y ≤ 12,  x, y ≥ 0 integer
+//! let problem = LinearProgram::new(array![0.0, -1.0]).with_inequality(
+//!     array![[-1.0, 1.0], [3.0, 2.0], [2.0, 3.0]],
+//!     array![1.0, 12.0, 12.0],
+//! );
+//!
+//! let solution = milp(&problem, &[true, true], None)?;
+//! assert!(solution.result.success);
+//! assert!((solution.result.fun + 2.0).abs() < 1e-8);
+//! assert_eq!(solution.mip_gap, 0.0);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{OptimizeError, OptimizeResult};
+use crate::linprog::{dual_simplex, LinearProgram, LpModel, LpSolution, LpStatus, VariableStatus};
+use crate::parallel::{ParallelOptions, WorkerPool};
+use crate::result::OptimizeResults;
+use ndarray::Array1;
+use std::fmt;
+use std::time::Instant;
+
+mod cuts;
+
+/// Feasibility and optimality tolerance of the node LP solves
+const LP_TOL: f64 = 1e-9;
+
+/// Rules for choosing the next node of the branch-and-bound tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeSelection {
+    /// Node with the smallest LP bound first; minimizes the number of nodes
+    BestBound,
+
+    /// Most recently created node first; finds incumbents early and uses little memory
+    DepthFirst,
+}
+
+impl fmt::Display for NodeSelection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NodeSelection::BestBound => write!(f, "best-bound"),
+            NodeSelection::DepthFirst => write!(f, "depth-first"),
+        }
+    }
+}
+
+/// Options for the mixed-integer solver.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Node selection rule (default: best bound)
+    pub node_selection: Option<NodeSelection>,
+
+    /// Wall-clock time limit in seconds (default: none)
+    pub time_limit: Option<f64>,
+
+    /// Maximum number of branch-and-bound nodes (default: none)
+    pub node_limit: Option<usize>,
+
+    /// Relative gap between incumbent and dual bound at which the search stops (default: 1e-4)
+    pub mip_rel_gap: Option<f64>,
+
+    /// Tolerance for considering a value integral (default: 1e-6)
+    pub integrality_tol: Option<f64>,
+
+    /// Maximum number of cutting plane rounds at the root; 0 disables cuts (default: 10)
+    pub cut_rounds: Option<usize>,
+
+    /// Separate Gomory mixed-integer cuts (default: true)
+    pub gomory_cuts: Option<bool>,
+
+    /// Separate knapsack cover cuts (default: true)
+    pub cover_cuts: Option<bool>,
+
+    /// Evaluate batches of nodes in parallel (default: sequential)
+    pub parallel: Option<ParallelOptions>,
+}
+
+/// Result of a mixed-integer linear program.
+///
+/// `result.x` holds the incumbent, the best integer feasible solution found.
+/// If no such solution was found, `result.x` is empty and `result.fun` is
+/// infinite.
+///
+/// The status codes of `result.status` are:
+///
+/// * `0`: optimal within the requested gap
+/// * `1`: time or node limit reached
+/// * `2`: the problem is infeasible
+/// * `3`: the LP relaxation is unbounded
+/// * `4`: numerical difficulties in the LP solver
+#[derive(Debug, Clone)]
+pub struct MilpResult {
+    /// Incumbent solution and solver statistics (`nit` counts simplex iterations)
+    pub result: OptimizeResults<f64>,
+
+    /// Relative gap `(fun - dual_bound) / max(|fun|, 1)`, infinite without an incumbent
+    pub mip_gap: f64,
+
+    /// Lower bound on the optimal objective value proven by the search
+    pub dual_bound: f64,
+
+    /// Number of branch-and-bound nodes solved
+    pub node_count: usize,
+
+    /// Number of cutting planes added at the root
+    pub cut_count: usize,
+}
+
+/// Termination status of the branch-and-bound search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MilpStatus {
+    Optimal,
+    TimeLimit,
+    NodeLimit,
+    Infeasible,
+    Unbounded,
+    Numerical,
+}
+
+impl MilpStatus {
+    fn code(self) -> i32 {
+        match self {
+            MilpStatus::Optimal => 0,
+            MilpStatus::TimeLimit | MilpStatus::NodeLimit => 1,
+            MilpStatus::Infeasible => 2,
+            MilpStatus::Unbounded => 3,
+            MilpStatus::Numerical => 4,
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            MilpStatus::Optimal => "Optimization terminated successfully.",
+            MilpStatus::TimeLimit => "Time limit reached.",
+            MilpStatus::NodeLimit => "Node limit reached.",
+            MilpStatus::Infeasible => "The problem is infeasible.",
+            MilpStatus::Unbounded => "The LP relaxation is unbounded.",
+            MilpStatus::Numerical => "Numerical difficulties encountered.",
+        }
+    }
+}
+
+/// A subproblem of the branch-and-bound tree
+struct Node {
+    /// Bounds of all `n + m` variables
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    /// Objective value of the parent relaxation
+    bound: f64,
+    /// Depth in the tree
+    depth: usize,
+    /// Optimal basis of the parent relaxation
+    basis: Option<Vec<VariableStatus>>,
+}
+
+/// Solve a mixed-integer linear programming problem.
+///
+/// # Arguments
+///
+/// * `problem` - The linear program without integrality restrictions
+/// * `integrality` - For every variable, whether it must take an integer value
+/// * `options` - Optional solver parameters
+///
+/// # Returns
+///
+/// * `MilpResult` containing the incumbent, the dual bound and the MIP gap
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_optimize::linprog::LinearProgram;
+/// use scirs2_optimize::milp::{milp, NodeSelection, Options};
+/// use scirs2_optimize::unconstrained::Bounds;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // 0-1 knapsack: maximize 10a + 13b + 7c + 8d  s.t.  4a + 6b + 3c + 5d ≤ 10
+/// let problem = LinearProgram::new(array![-10.0, -13.0, -7.0, -8.0])
+///     .with_inequality(array![[4.0, 6.0, 3.0, 5.0]], array![10.0])
+///     .with_bounds(Bounds::new(&[(Some(0.0), Some(1.0)); 4]));
+///
+/// let options = Options {
+///     node_selection: Some(NodeSelection::DepthFirst),
+///     ..Options::default()
+/// };
+/// let solution = milp(&problem, &[true; 4], Some(options))?;
+/// assert!((solution.result.fun + 23.0).abs() < 1e-8);
+/// # Ok(())
+/// # }
+/// ```
+pub fn milp(
+    problem: &LinearProgram,
+    integrality: &[bool],
+    options: Option<Options>,
+) -> OptimizeResult<MilpResult> {
+    let options = options.unwrap_or_default();
+    let start = Instant::now();
+
+    let mut model = LpModel::new(problem)?;
+    let n = model.n;
+    if integrality.len() != n {
+        return Err(OptimizeError::ValueError(format!(
+            "Integrality has length {}, but there are {} variables",
+            integrality.len(),
+            n
+        )));
+    }
+    let gap_tol = options.mip_rel_gap.unwrap_or(1e-4);
+    let int_tol = options.integrality_tol.unwrap_or(1e-6);
+    if gap_tol.is_nan() || gap_tol < 0.0 {
+        return Err(OptimizeError::ValueError(
+            "mip_rel_gap must be nonnegative".to_string(),
+        ));
+    }
+    if !(int_tol > 0.0 && int_tol < 0.5) {
+        return Err(OptimizeError::ValueError(
+            "integrality_tol must lie in (0, 0.5)".to_string(),
+        ));
+    }
+    let selection = options.node_selection.unwrap_or(NodeSelection::BestBound);
+    let time_exceeded = || {
+        options
+            .time_limit
+            .is_some_and(|limit| start.elapsed().as_secs_f64() >= limit)
+    };
+
+    let mut search = Search {
+        integrality,
+        int_tol,
+        incumbent: None,
+        lp_iterations: 0,
+        node_count: 0,
+        cut_count: 0,
+    };
+
+    // Integer variables can only take values between the rounded bounds
+    for j in (0..n).filter(|&j| integrality[j]) {
+        model.lower[j] = (model.lower[j] - int_tol).ceil();
+        model.upper[j] = (model.upper[j] + int_tol).floor();
+        if model.lower[j] > model.upper[j] {
+            return Ok(search.finish(MilpStatus::Infeasible, f64::INFINITY));
+        }
+    }
+
+    // Root relaxation
+    let mut root = dual_simplex(
+        &model,
+        &model.lower,
+        &model.upper,
+        None,
+        LP_TOL,
+        lp_maxiter(&model),
+    );
+    search.lp_iterations += root.iterations;
+    match root.status {
+        LpStatus::Optimal => {}
+        LpStatus::Infeasible => return Ok(search.finish(MilpStatus::Infeasible, f64::INFINITY)),
+        LpStatus::Unbounded => return Ok(search.finish(MilpStatus::Unbounded, f64::NEG_INFINITY)),
+        _ => return Ok(search.finish(MilpStatus::Numerical, f64::NEG_INFINITY)),
+    }
+
+    // Cutting plane rounds
+    let gomory = options.gomory_cuts.unwrap_or(true);
+    let cover = options.cover_cuts.unwrap_or(true);
+    let rounds = if gomory || cover {
+        options.cut_rounds.unwrap_or(10)
+    } else {
+        0
+    };
+    for _ in 0..rounds {
+        if time_exceeded() || search.branching_variable(&root.x).is_none() {
+            break;
+        }
+        let basis = match &root.basis {
+            Some(basis) => basis.clone(),
+            None => break,
+        };
+        let rows = model.rows();
+        let mut cuts = Vec::new();
+        if gomory {
+            cuts.extend(cuts::gomory_cuts(
+                &model,
+                &rows,
+                &basis,
+                &root.x,
+                &model.lower,
+                &model.upper,
+                integrality,
+            ));
+        }
+        if cover {
+            cuts.extend(cuts::cover_cuts(
+                &model,
+                &rows,
+                &root.x,
+                &model.lower[..n],
+                &model.upper[..n],
+                integrality,
+            ));
+        }
+        if cuts.is_empty() {
+            break;
+        }
+        cuts.sort_by(|a, b| b.efficacy(&root.x).total_cmp(&a.efficacy(&root.x)));
+        cuts.truncate(n.max(20));
+
+        let mut strengthened = model.clone();
+        let mut warm = basis;
+        for cut in &cuts {
+            strengthened.append_inequality(&cut.coefficients, cut.rhs);
+            warm.push(VariableStatus::Basic);
+        }
+        let solution = dual_simplex(
+            &strengthened,
+            &strengthened.lower,
+            &strengthened.upper,
+            Some(&warm),
+            LP_TOL,
+            lp_maxiter(&strengthened),
+        );
+        search.lp_iterations += solution.iterations;
+        match solution.status {
+            LpStatus::Optimal => {}
+            // Cuts are valid for all integer points, so the problem is infeasible
+            LpStatus::Infeasible => return Ok(search.finish(MilpStatus::Infeasible, f64::INFINITY)),
+            // Discard the round and branch on the previous relaxation
+            _ => break,
+        }
+
+        let previous = objective(&model, &root.x);
+        let current = objective(&strengthened, &solution.x);
+        model = strengthened;
+        root = solution;
+        search.cut_count += cuts.len();
+        if current - previous <= 1e-6 * (1.0 + previous.abs()) {
+            break;
+        }
+    }
+
+    // Branch and bound
+    let pool = WorkerPool::new(&options.parallel.clone().unwrap_or_default())?;
+    let batch_size = match &options.parallel {
+        Some(p) if p.parallel_evaluations => pool.num_threads().max(p.min_parallel_size).max(1),
+        _ => 1,
+    };
+    let mut open = vec![Node {
+        lower: model.lower.clone(),
+        upper: model.upper.clone(),
+        bound: objective(&model, &root.x),
+        depth: 0,
+        basis: root.basis,
+    }];
+    let mut lp_failures = 0;
+    let mut stopped = None;
+
+    loop {
+        let cutoff = search.cutoff();
+        open.retain(|node| node.bound < cutoff);
+        if open.is_empty() {
+            break;
+        }
+        if let Some((_, value)) = &search.incumbent {
+            if relative_gap(*value, lowest_bound(&open)) <= gap_tol {
+                stopped = Some(MilpStatus::Optimal);
+                break;
+            }
+        }
+        if time_exceeded() {
+            stopped = Some(MilpStatus::TimeLimit);
+            break;
+        }
+        let remaining = options
+            .node_limit
+            .map_or(usize::MAX, |limit| limit.saturating_sub(search.node_count));
+        if remaining == 0 {
+            stopped = Some(MilpStatus::NodeLimit);
+            break;
+        }
+
+        let take = batch_size.min(remaining).min(open.len());
+        let batch: Vec<Node> = (0..take).map(|_| select(&mut open, selection)).collect();
+        let solutions = pool.map_batch(
+            |node: &Node| {
+                dual_simplex(
+                    &model,
+                    &node.lower,
+                    &node.upper,
+                    node.basis.as_deref(),
+                    LP_TOL,
+                    lp_maxiter(&model),
+                )
+            },
+            &batch,
+        );
+
+        for (node, solution) in batch.into_iter().zip(solutions) {
+            search.node_count += 1;
+            search.lp_iterations += solution.iterations;
+            match solution.status {
+                LpStatus::Optimal => {}
+                LpStatus::Infeasible => continue,
+                _ => {
+                    lp_failures += 1;
+                    continue;
+                }
+            }
+            let value = objective(&model, &solution.x);
+            if value >= search.cutoff() {
+                continue;
+            }
+            match search.branching_variable(&solution.x) {
+                None => search.offer(&model, &solution.x),
+                Some(j) => {
+                    if let Some(rounded) = search.round(&model, &solution) {
+                        search.offer(&model, &rounded);
+                    }
+                    branch(&mut open, node, solution, j, value, selection);
+                }
+            }
+        }
+    }
+
+    let status = match stopped {
+        Some(status) => status,
+        None if lp_failures > 0 => MilpStatus::Numerical,
+        None if search.incumbent.is_some() => MilpStatus::Optimal,
+        None => MilpStatus::Infeasible,
+    };
+    let dual_bound = match &search.incumbent {
+        Some((_, value)) => lowest_bound(&open).min(*value),
+        None if open.is_empty() && lp_failures == 0 => f64::INFINITY,
+        None => lowest_bound(&open).min(objective(&model, &root.x)),
+    };
+    Ok(search.finish(status, dual_bound))
+}
+
+/// Bookkeeping of the branch-and-bound search
+struct Search<'a> {
+    integrality: &'a [bool],
+    int_tol: f64,
+    /// Best integer feasible solution (structural variables) and its objective value
+    incumbent: Option<(Vec<f64>, f64)>,
+    lp_iterations: usize,
+    node_count: usize,
+    cut_count: usize,
+}
+
+impl Search<'_> {
+    /// Objective value a node must beat to be explored
+    fn cutoff(&self) -> f64 {
+        match &self.incumbent {
+            Some((_, value)) => value - 1e-9 * (1.0 + value.abs()),
+            None => f64::INFINITY,
+        }
+    }
+
+    /// Most fractional integer variable, if any
+    fn branching_variable(&self, x: &[f64]) -> Option<usize> {
+        let mut best = None;
+        let mut best_score = self.int_tol;
+        for (j, &xj) in x.iter().enumerate().take(self.integrality.len()) {
+            if !self.integrality[j] {
+                continue;
+            }
+            let score = (xj - xj.round()).abs();
+            if score > best_score {
+                best_score = score;
+                best = Some(j);
+            }
+        }
+        best
+    }
+
+    /// Accept an integer feasible point if it improves the incumbent
+    fn offer(&mut self, model: &LpModel, x: &[f64]) {
+        let values: Vec<f64> = (0..model.n)
+            .map(|j| {
+                if self.integrality[j] {
+                    x[j].round()
+                } else {
+                    x[j]
+                }
+            })
+            .collect();
+        let value = objective(model, &values);
+        if value < self.cutoff() {
+            self.incumbent = Some((values, value));
+        }
+    }
+
+    /// Rounding heuristic: round the integer variables of an LP solution and
+    /// keep the point if it satisfies all constraints
+    fn round(&self, model: &LpModel, solution: &LpSolution) -> Option<Vec<f64>> {
+        let n = model.n;
+        let values: Vec<f64> = (0..n)
+            .map(|j| {
+                let v = if self.integrality[j] {
+                    solution.x[j].round()
+                } else {
+                    solution.x[j]
+                };
+                v.clamp(model.lower[j], model.upper[j])
+            })
+            .collect();
+
+        let mut activity = vec![0.0; model.m];
+        for (column, &v) in model.columns.iter().zip(&values) {
+            for &(i, a) in column {
+                activity[i] += a * v;
+            }
+        }
+        let feasible = (0..model.m).all(|i| {
+            let slack = model.rhs[i] - activity[i];
+            let tol = 1e-6 * (1.0 + model.rhs[i].abs());
+            slack >= model.lower[n + i] - tol && slack <= model.upper[n + i] + tol
+        });
+        feasible.then_some(values)
+    }
+
+    /// Assemble the final result
+    fn finish(self, status: MilpStatus, dual_bound: f64) -> MilpResult {
+        let (x, fun) = match self.incumbent {
+            Some((values, value)) => (Array1::from_vec(values), value),
+            None => (Array1::zeros(0), f64::INFINITY),
+        };
+        let mip_gap = if fun.is_finite() {
+            relative_gap(fun, dual_bound).max(0.0)
+        } else {
+            f64::INFINITY
+        };
+
+        MilpResult {
+            result: OptimizeResults {
+                x,
+                fun,
+                nit: self.lp_iterations,
+                message: status.message().to_string(),
+                success: status == MilpStatus::Optimal,
+                status: status.code(),
+                ..Default::default()
+            },
+            mip_gap,
+            dual_bound,
+            node_count: self.node_count,
+            cut_count: self.cut_count,
+        }
+    }
+}
+
+/// Create the two children of a node by branching on variable `j`
+fn branch(
+    open: &mut Vec<Node>,
+    node: Node,
+    solution: LpSolution,
+    j: usize,
+    value: f64,
+    selection: NodeSelection,
+) {
+    let xj = solution.x[j];
+    let mut down = Node {
+        lower: node.lower.clone(),
+        upper: node.upper.clone(),
+        bound: value,
+        depth: node.depth + 1,
+        basis: solution.basis.clone(),
+    };
+    down.upper[j] = xj.floor();
+    let mut up = Node {
+        lower: node.lower,
+        upper: node.upper,
+        bound: value,
+        depth: node.depth + 1,
+        basis: solution.basis,
+    };
+    up.lower[j] = xj.ceil();
+
+    // Depth-first search explores the child closer to the LP value first
+    if selection == NodeSelection::DepthFirst && xj - xj.floor() < 0.5 {
+        open.push(up);
+        open.push(down);
+    } else {
+        open.push(down);
+        open.push(up);
+    }
+}
+
+/// Remove the next node to explore from the open list
+fn select(open: &mut Vec<Node>, selection: NodeSelection) -> Node {
+    match selection {
+        NodeSelection::DepthFirst => open.pop().expect("open list is not empty"),
+        NodeSelection::BestBound => {
+            let best = (0..open.len())
+                .min_by(|&a, &b| {
+                    open[a]
+                        .bound
+                        .total_cmp(&open[b].bound)
+                        .then(open[b].depth.cmp(&open[a].depth))
+                })
+                .expect("open list is not empty");
+            open.swap_remove(best)
+        }
+    }
+}
+
+/// Smallest bound among the open nodes
+fn lowest_bound(open: &[Node]) -> f64 {
+    open.iter()
+        .map(|node| node.bound)
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Relative gap between an incumbent value and a dual bound
+fn relative_gap(primal: f64, dual: f64) -> f64 {
+    if primal == dual {
+        0.0
+    } else {
+        (primal - dual) / primal.abs().max(1.0)
+    }
+}
+
+/// Objective value of the structural part of `x`
+fn objective(model: &LpModel, x: &[f64]) -> f64 {
+    model.cost.iter().zip(x).map(|(c, v)| c * v).sum()
+}
+
+/// Iteration limit of a single node relaxation
+fn lp_maxiter(model: &LpModel) -> usize {
+    10 * (model.n + model.m) + 1000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unconstrained::Bounds;
+    use ndarray::{array, Array2};
+
+    /// Brute-force optimum of a pure binary program
+    fn enumerate(c: &[f64], a: &Array2<f64>, b: &[f64]) -> Option<f64> {
+        let n = c.len();
+        (0..1u32 << n)
+            .filter_map(|mask| {
+                let x: Vec<f64> = (0..n).map(|j| ((mask >> j) & 1) as f64).collect();
+                let feasible =
+                    a.rows().into_iter().zip(b).all(|(row, &bi)| {
+                        row.iter().zip(&x).map(|(r, v)| r * v).sum::<f64>() <= bi
+                    });
+                feasible.then(|| c.iter().zip(&x).map(|(ci, v)| ci * v).sum::<f64>())
+            })
+            .min_by(f64::total_cmp)
+    }
+
+    #[test]
+    fn test_milp_general_integers() {
+        // maximize y  s.t.  -x + y ≤ 1,  3x + 2y ≤ 12,  2x + 3y ≤ 12;
+        // the LP optimum is (1.8, 2.8), the integer optimum has y = 2
+        let problem = LinearProgram::new(array![0.0, -1.0]).with_inequality(
+            array![[-1.0, 1.0], [3.0, 2.0], [2.0, 3.0]],
+            array![1.0, 12.0, 12.0],
+        );
+        for selection in [NodeSelection::BestBound, NodeSelection::DepthFirst] {
+            for cut_rounds in [0, 10] {
+                let options = Options {
+                    node_selection: Some(selection),
+                    cut_rounds: Some(cut_rounds),
+                    ..Options::default()
+                };
+                let solution = milp(&problem, &[true, true], Some(options)).unwrap();
+                assert!(solution.result.success, "{}", solution.result.message);
+                assert_eq!(solution.result.status, 0);
+                assert!((solution.result.fun + 2.0).abs() < 1e-9);
+                assert!((solution.result.x[1] - 2.0).abs() < 1e-9);
+                assert_eq!(solution.mip_gap, 0.0);
+                assert!((solution.dual_bound + 2.0).abs() < 1e-9);
+            }
+        }
+
+        // Continuous variables stay continuous
+        let solution = milp(&problem, &[false, false], None).unwrap();
+        assert!((solution.result.fun + 2.8).abs() < 1e-9);
+        assert_eq!(solution.node_count, 1);
+    }
+
+    #[test]
+    fn test_milp_knapsack_matches_enumeration() {
+        let c = [-12.0, -11.0, -9.0, -8.0, -7.0, -6.0, -5.0, -3.0];
+        let a = array![
+            [6.0, 5.0, 5.0, 4.0, 4.0, 3.0, 3.0, 1.0],
+            [2.0, -1.0, 3.0, 1.0, -2.0, 2.0, 1.0, 1.0],
+            [1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0]
+        ];
+        let b = [15.0, 4.0, 2.0];
+        let expected = enumerate(&c, &a, &b).unwrap();
+
+        let problem = LinearProgram::new(Array1::from_vec(c.to_vec()))
+            .with_inequality(a, Array1::from_vec(b.to_vec()))
+            .with_bounds(Bounds::new(&[(Some(0.0), Some(1.0)); 8]));
+        let integrality = [true; 8];
+
+        let reference = milp(
+            &problem,
+            &integrality,
+            Some(Options {
+                cut_rounds: Some(0),
+                mip_rel_gap: Some(0.0),
+                ..Options::default()
+            }),
+        )
+        .unwrap();
+        assert!((reference.result.fun - expected).abs() < 1e-9);
+        assert_eq!(reference.cut_count, 0);
+
+        let with_cuts = milp(
+            &problem,
+            &integrality,
+            Some(Options {
+                mip_rel_gap: Some(0.0),
+                ..Options::default()
+            }),
+        )
+        .unwrap();
+        assert!((with_cuts.result.fun - expected).abs() < 1e-9);
+        assert!(with_cuts.cut_count > 0);
+        assert!(with_cuts.node_count <= reference.node_count);
+
+        // Parallel node evaluation finds the same optimum
+        let parallel = milp(
+            &problem,
+            &integrality,
+            Some(Options {
+                mip_rel_gap: Some(0.0),
+                parallel: Some(ParallelOptions {
+                    num_workers: Some(2),
+                    min_parallel_size: 2,
+                    ..ParallelOptions::default()
+                }),
+                ..Options::default()
+            }),
+        )
+        .unwrap();
+        assert!((parallel.result.fun - expected).abs() < 1e-9);
+        let x = &parallel.result.x;
+        assert!(x.iter().all(|&v| v == 0.0 || v == 1.0));
+    }
+
+    #[test]
+    fn test_milp_infeasible_and_unbounded() {
+        // 2x = 1 has no integer solution although the relaxation is feasible
+        let problem = LinearProgram::new(array![1.0]).with_equality(array![[2.0]], array![1.0]);
+        let solution = milp(&problem, &[true], None).unwrap();
+        assert_eq!(solution.result.status, 2);
+        assert!(!solution.result.success);
+        assert_eq!(solution.result.x.len(), 0);
+        assert!(solution.result.fun.is_infinite());
+
+        // Integer bounds that contain no integer
+        let problem =
+            LinearProgram::new(array![1.0]).with_bounds(Bounds::new(&[(Some(0.2), Some(0.8))]));
+        assert_eq!(milp(&problem, &[true], None).unwrap().result.status, 2);
+
+        // Unbounded relaxation
+        let problem =
+            LinearProgram::new(array![-1.0, 0.0]).with_inequality(array![[1.0, -1.0]], array![0.5]);
+        assert_eq!(
+            milp(&problem, &[true, true], None).unwrap().result.status,
+            3
+        );
+
+        // Mismatched integrality flags
+        let problem = LinearProgram::new(array![1.0, 1.0]);
+        assert!(milp(&problem, &[true], None).is_err());
+    }
+
+    #[test]
+    fn test_milp_node_limit_reports_gap() {
+        // maximize Σ v_j x_j over a tight knapsack with odd coefficients
+        let weights = [41.0, 37.0, 29.0, 23.0, 19.0, 17.0, 13.0, 11.0, 7.0, 5.0];
+        let values = [
+            -43.0, -40.0, -30.0, -25.0, -20.0, -18.0, -14.0, -12.0, -8.0, -6.0,
+        ];
+        let problem = LinearProgram::new(Array1::from_vec(values.to_vec()))
+            .with_inequality(
+                Array2::from_shape_vec((1, 10), weights.to_vec()).unwrap(),
+                array![100.5],
+            )
+            .with_bounds(Bounds::new(&[(Some(0.0), Some(3.0)); 10]));
+        let integrality = [true; 10];
+
+        let options = Options {
+            node_limit: Some(3),
+            cut_rounds: Some(0),
+            mip_rel_gap: Some(0.0),
+            ..Options::default()
+        };
+        let limited = milp(&problem, &integrality, Some(options)).unwrap();
+        assert_eq!(limited.result.status, 1);
+        assert_eq!(limited.node_count, 3);
+        assert!(limited.dual_bound <= limited.result.fun);
+
+        let options = Options {
+            mip_rel_gap: Some(0.0),
+            ..Options::default()
+        };
+        let full = milp(&problem, &integrality, Some(options)).unwrap();
+        assert_eq!(full.result.status, 0);
+        assert!(full.dual_bound >= limited.dual_bound - 1e-9);
+        assert!(full.result.fun <= limited.result.fun);
+        let weight: f64 = full.result.x.iter().zip(&weights).map(|(x, w)| x * w).sum();
+        assert!(weight <= 100.5);
+    }
+}
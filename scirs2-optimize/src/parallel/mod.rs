@@ -21,6 +21,7 @@
 //! let gradient = parallel_finite_diff_gradient(objective, x.view(), &options);
 //! ```
 
+use crate::error::{OptimizeError, OptimizeResult};
 use ndarray::{Array1, ArrayView1};
 use rayon::prelude::*;
 
@@ -148,6 +149,74 @@ where
     }
 }
 
+/// Worker threads shared by the parallel batches of one solve
+///
+/// The pool is built once from [`ParallelOptions`] so that algorithms that
+/// evaluate many batches, such as branch and bound or evolutionary
+/// optimizers, do not start new threads for every batch. Without
+/// `num_workers` the global rayon pool is used.
+pub struct WorkerPool {
+    pool: Option<rayon::ThreadPool>,
+    options: ParallelOptions,
+}
+
+impl WorkerPool {
+    /// Build the pool requested by `options`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ComputationError` if the worker threads cannot be started
+    pub fn new(options: &ParallelOptions) -> OptimizeResult<Self> {
+        let pool = match options.num_workers {
+            Some(num_workers) => Some(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(num_workers)
+                    .build()
+                    .map_err(|e| {
+                        OptimizeError::ComputationError(format!(
+                            "Failed to start {} worker threads: {}",
+                            num_workers, e
+                        ))
+                    })?,
+            ),
+            None => None,
+        };
+        Ok(WorkerPool {
+            pool,
+            options: options.clone(),
+        })
+    }
+
+    /// Number of threads batches are spread over
+    pub fn num_threads(&self) -> usize {
+        match &self.pool {
+            Some(pool) => pool.current_num_threads(),
+            None => rayon::current_num_threads(),
+        }
+    }
+
+    /// Parallel map over a batch of items
+    ///
+    /// Applies `f` to every item, in parallel when the batch is large enough.
+    /// Results are returned in the order of the items, so the outcome does not
+    /// depend on the number of worker threads.
+    pub fn map_batch<T, R, F>(&self, f: F, items: &[T]) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync + Send,
+    {
+        if items.len() < self.options.min_parallel_size || !self.options.parallel_evaluations {
+            return items.iter().map(&f).collect();
+        }
+
+        match &self.pool {
+            Some(pool) => pool.install(|| items.par_iter().map(&f).collect()),
+            None => items.par_iter().map(&f).collect(),
+        }
+    }
+}
+
 /// Parallel multi-start optimization
 ///
 /// Runs multiple optimization instances from different starting points in parallel.
@@ -447,6 +516,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_worker_pool_map_batch() {
+        let items: Vec<usize> = (0..32).collect();
+        let options = ParallelOptions {
+            num_workers: Some(2),
+            min_parallel_size: 4,
+            ..Default::default()
+        };
+
+        let pool = WorkerPool::new(&options).unwrap();
+        assert_eq!(pool.num_threads(), 2);
+        let squares = pool.map_batch(|&i| (i, i * i), &items);
+
+        // Results keep the order of the items
+        for (i, &(item, square)) in squares.iter().enumerate() {
+            assert_eq!(item, i);
+            assert_eq!(square, i * i);
+        }
+    }
+
     #[test]
     fn test_parallel_line_search() {
         let x = array![1.0, 1.0];
@@ -0,0 +1,433 @@
+//! Primal active-set method for small dense problems
+//!
+//! A feasible starting point is found with the dual simplex method (phase
+//! one). Each iteration minimizes the objective on the affine subspace of
+//! the working set with the null-space method: the working constraints are
+//! factorized as `A_Wᵀ = Q R`, the remaining columns `Z` of `Q` span their
+//! null space, and the reduced Hessian `ZᵀPZ` is diagonalized so that
+//! directions of zero curvature, which are present when `P` is only
+//! semidefinite, are followed until a constraint blocks or the problem is
+//! found to be unbounded.
+
+use super::{norm_inf, CsrOps, Options, QpData, QpSolution, QpStatus};
+use crate::error::{OptimizeError, OptimizeResult};
+use crate::linprog::{linprog, LinearProgram, Method as LpMethod};
+use crate::unconstrained::Bounds;
+use ndarray::{Array1, Array2};
+
+/// Side of a constraint in the working set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Lower,
+    Upper,
+    Equal,
+}
+
+/// Solve the problem with the primal active-set method
+pub(super) fn solve(data: &QpData, options: &Options) -> OptimizeResult<QpSolution> {
+    let (n, m) = (data.n, data.m);
+    let tol = options.tol.unwrap_or(1e-9);
+    let maxiter = options.maxiter.unwrap_or(10 * (n + m) + 100);
+    let p = data.p.to_dense();
+    let a = data.a.to_dense();
+    let row = |i: usize| &a[i * n..(i + 1) * n];
+
+    // The method relies on convexity of the objective
+    let (eigenvalues, _) = symmetric_eigen(p.clone(), n);
+    let p_scale = eigenvalues.iter().fold(1.0f64, |acc, v| acc.max(v.abs()));
+    if eigenvalues.iter().any(|&v| v < -1e-8 * p_scale) {
+        return Err(OptimizeError::ValueError(
+            "P must be positive semidefinite for the active-set method".to_string(),
+        ));
+    }
+    let curvature_tol = 1e-10 * p_scale;
+
+    let failure = |status: QpStatus, iterations: usize| QpSolution {
+        status,
+        x: vec![0.0; n],
+        y: vec![0.0; m],
+        iterations,
+        primal_certificate: None,
+        dual_certificate: None,
+    };
+
+    // Phase one: a feasible starting point
+    let feasible = |x: &[f64]| {
+        (0..m).all(|i| {
+            let ax = dot(row(i), x);
+            ax >= data.l[i] - tol * (1.0 + data.l[i].abs())
+                && ax <= data.u[i] + tol * (1.0 + data.u[i].abs())
+        })
+    };
+    let mut x = match &options.warm_start {
+        Some(warm) if feasible(&warm.x.to_vec()) => warm.x.to_vec(),
+        _ => match phase_one(data, &a)? {
+            Some(x) => x,
+            None => return Ok(failure(QpStatus::PrimalInfeasible, 0)),
+        },
+    };
+
+    // Equality rows start in the working set, as long as they are independent
+    let mut working: Vec<(usize, Side)> = Vec::new();
+    for i in (0..m).filter(|&i| data.l[i] == data.u[i]) {
+        working.push((i, Side::Equal));
+        let (_, r) = working_factor(&a, n, &working);
+        let k = working.len() - 1;
+        if r[k * working.len() + k].abs() <= 1e-10 * norm_inf(row(i)).max(1e-300) {
+            working.pop();
+        }
+    }
+
+    let mut iterations = 0;
+    while iterations < maxiter {
+        iterations += 1;
+        let px = mat_vec(&p, n, &x);
+        let g: Vec<f64> = px.iter().zip(&data.q).map(|(a, b)| a + b).collect();
+        let g_norm = norm_inf(&g);
+
+        let k = working.len();
+        let (q, r) = working_factor(&a, n, &working);
+        let nz = n - k;
+
+        // Null-space step
+        let z_col = |c: usize, i: usize| q[i * n + k + c];
+        let mut step = vec![0.0; n];
+        let mut ray = None;
+        if nz > 0 {
+            let mut pz = vec![0.0; n * nz];
+            for c in 0..nz {
+                let zc: Vec<f64> = (0..n).map(|i| z_col(c, i)).collect();
+                let pzc = mat_vec(&p, n, &zc);
+                for i in 0..n {
+                    pz[i * nz + c] = pzc[i];
+                }
+            }
+            let mut hz = vec![0.0; nz * nz];
+            for a_c in 0..nz {
+                for b_c in 0..nz {
+                    hz[a_c * nz + b_c] = (0..n).map(|i| z_col(a_c, i) * pz[i * nz + b_c]).sum();
+                }
+            }
+            let gz: Vec<f64> = (0..nz)
+                .map(|c| (0..n).map(|i| z_col(c, i) * g[i]).sum())
+                .collect();
+            let (values, vectors) = symmetric_eigen(hz, nz);
+
+            let mut reduced = vec![0.0; nz];
+            for (e, &lambda) in values.iter().enumerate() {
+                let ve: Vec<f64> = (0..nz).map(|c| vectors[c * nz + e]).collect();
+                let projection = dot(&ve, &gz);
+                if lambda <= curvature_tol {
+                    if projection.abs() > tol * (1.0 + g_norm) {
+                        // Descent direction without curvature
+                        let sign = -projection.signum();
+                        let direction: Vec<f64> = (0..n)
+                            .map(|i| sign * (0..nz).map(|c| z_col(c, i) * ve[c]).sum::<f64>())
+                            .collect();
+                        ray = Some(direction);
+                        break;
+                    }
+                } else {
+                    for c in 0..nz {
+                        reduced[c] -= projection / lambda * ve[c];
+                    }
+                }
+            }
+            if ray.is_none() {
+                for (i, s) in step.iter_mut().enumerate() {
+                    *s = (0..nz).map(|c| z_col(c, i) * reduced[c]).sum();
+                }
+            }
+        }
+
+        if let Some(direction) = ray {
+            match ratio_test(data, &a, &working, &x, &direction, f64::INFINITY) {
+                (alpha, Some(blocking)) => {
+                    for (xi, di) in x.iter_mut().zip(&direction) {
+                        *xi += alpha * di;
+                    }
+                    working.push(blocking);
+                }
+                (_, None) => {
+                    let scale = norm_inf(&direction);
+                    return Ok(QpSolution {
+                        status: QpStatus::DualInfeasible,
+                        x,
+                        y: vec![0.0; m],
+                        iterations,
+                        primal_certificate: None,
+                        dual_certificate: Some(direction.iter().map(|d| d / scale).collect()),
+                    });
+                }
+            }
+            continue;
+        }
+
+        if norm_inf(&step) > tol * (1.0 + norm_inf(&x)) {
+            let (alpha, blocking) = ratio_test(data, &a, &working, &x, &step, 1.0);
+            for (xi, si) in x.iter_mut().zip(&step) {
+                *xi += alpha * si;
+            }
+            if let Some(blocking) = blocking {
+                working.push(blocking);
+            }
+            continue;
+        }
+
+        // Stationary on the working set: multipliers from R y = -Q₁ᵀ g
+        let mut y_w: Vec<f64> = (0..k)
+            .map(|c| -(0..n).map(|i| q[i * n + c] * g[i]).sum::<f64>())
+            .collect();
+        for c in (0..k).rev() {
+            for d in c + 1..k {
+                y_w[c] -= r[c * k + d] * y_w[d];
+            }
+            y_w[c] /= r[c * k + c];
+        }
+
+        let multiplier_tol = tol * (1.0 + g_norm);
+        let mut worst: Option<(usize, f64)> = None;
+        for (c, &(_, side)) in working.iter().enumerate() {
+            let violation = match side {
+                Side::Lower => y_w[c],
+                Side::Upper => -y_w[c],
+                Side::Equal => 0.0,
+            };
+            if violation > multiplier_tol && worst.is_none_or(|(_, v)| violation > v) {
+                worst = Some((c, violation));
+            }
+        }
+        match worst {
+            Some((c, _)) => {
+                working.remove(c);
+            }
+            None => {
+                let mut y = vec![0.0; m];
+                for (c, &(i, _)) in working.iter().enumerate() {
+                    y[i] = y_w[c];
+                }
+                return Ok(QpSolution {
+                    status: QpStatus::Solved,
+                    x,
+                    y,
+                    iterations,
+                    primal_certificate: None,
+                    dual_certificate: None,
+                });
+            }
+        }
+    }
+
+    Ok(QpSolution {
+        status: QpStatus::IterationLimit,
+        x,
+        y: vec![0.0; m],
+        iterations,
+        primal_certificate: None,
+        dual_certificate: None,
+    })
+}
+
+/// Find a point satisfying `l ≤ Ax ≤ u` with the dual simplex method
+fn phase_one(data: &QpData, a: &[f64]) -> OptimizeResult<Option<Vec<f64>>> {
+    let (n, m) = (data.n, data.m);
+    let mut inequality = Vec::new();
+    let mut equality = Vec::new();
+    for i in 0..m {
+        let row = &a[i * n..(i + 1) * n];
+        if data.l[i] == data.u[i] {
+            equality.push((row.to_vec(), data.l[i]));
+            continue;
+        }
+        if data.u[i].is_finite() {
+            inequality.push((row.to_vec(), data.u[i]));
+        }
+        if data.l[i].is_finite() {
+            inequality.push((row.iter().map(|v| -v).collect(), -data.l[i]));
+        }
+    }
+
+    let block = |rows: &[(Vec<f64>, f64)]| {
+        let mut matrix = Array2::zeros((rows.len(), n));
+        for (i, (row, _)) in rows.iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                matrix[[i, j]] = v;
+            }
+        }
+        let rhs = Array1::from_iter(rows.iter().map(|(_, b)| *b));
+        (matrix, rhs)
+    };
+
+    let mut problem =
+        LinearProgram::new(Array1::zeros(n)).with_bounds(Bounds::new(&vec![(None, None); n]));
+    if !inequality.is_empty() {
+        let (matrix, rhs) = block(&inequality);
+        problem = problem.with_inequality(matrix, rhs);
+    }
+    if !equality.is_empty() {
+        let (matrix, rhs) = block(&equality);
+        problem = problem.with_equality(matrix, rhs);
+    }
+
+    let solution = linprog(&problem, LpMethod::DualSimplex, None)?;
+    match solution.result.status {
+        0 => Ok(Some(solution.result.x.to_vec())),
+        2 => Ok(None),
+        _ => Err(OptimizeError::ComputationError(format!(
+            "Phase one failed: {}",
+            solution.result.message
+        ))),
+    }
+}
+
+/// Largest step along `direction` (at most `max_step`) that keeps all
+/// constraints outside the working set satisfied, and the blocking constraint
+fn ratio_test(
+    data: &QpData,
+    a: &[f64],
+    working: &[(usize, Side)],
+    x: &[f64],
+    direction: &[f64],
+    max_step: f64,
+) -> (f64, Option<(usize, Side)>) {
+    let n = data.n;
+    let direction_norm = norm_inf(direction);
+    let mut best = (max_step, None);
+    for i in 0..data.m {
+        if working.iter().any(|&(w, _)| w == i) {
+            continue;
+        }
+        let row = &a[i * n..(i + 1) * n];
+        let ad = dot(row, direction);
+        if ad.abs() <= 1e-12 * norm_inf(row) * direction_norm {
+            continue;
+        }
+        let ax = dot(row, x);
+        let (limit, side) = if ad > 0.0 {
+            (data.u[i], Side::Upper)
+        } else {
+            (data.l[i], Side::Lower)
+        };
+        if !limit.is_finite() {
+            continue;
+        }
+        let side = if data.l[i] == data.u[i] {
+            Side::Equal
+        } else {
+            side
+        };
+        let step = ((limit - ax) / ad).max(0.0);
+        if step < best.0 {
+            best = (step, Some((i, side)));
+        }
+    }
+    best
+}
+
+/// Householder QR factorization `A_Wᵀ = Q R` of the working constraints
+///
+/// Returns the full orthogonal `Q` (n × n, row-major) and the upper
+/// triangular `R` (k × k, row-major).
+fn working_factor(a: &[f64], n: usize, working: &[(usize, Side)]) -> (Vec<f64>, Vec<f64>) {
+    let k = working.len();
+    // M = A_Wᵀ, n × k row-major
+    let mut mat = vec![0.0; n * k];
+    for (c, &(i, _)) in working.iter().enumerate() {
+        for j in 0..n {
+            mat[j * k + c] = a[i * n + j];
+        }
+    }
+    let mut q = vec![0.0; n * n];
+    for i in 0..n {
+        q[i * n + i] = 1.0;
+    }
+
+    for c in 0..k.min(n) {
+        let norm = (c..n).map(|i| mat[i * k + c].powi(2)).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            continue;
+        }
+        let alpha = if mat[c * k + c] > 0.0 { -norm } else { norm };
+        let mut v: Vec<f64> = (c..n).map(|i| mat[i * k + c]).collect();
+        v[0] -= alpha;
+        let v_norm2: f64 = v.iter().map(|x| x * x).sum();
+        if v_norm2 == 0.0 {
+            continue;
+        }
+        for col in c..k {
+            let w: f64 = (c..n).map(|i| v[i - c] * mat[i * k + col]).sum::<f64>() * 2.0 / v_norm2;
+            for i in c..n {
+                mat[i * k + col] -= w * v[i - c];
+            }
+        }
+        for row in 0..n {
+            let w: f64 = (c..n).map(|i| q[row * n + i] * v[i - c]).sum::<f64>() * 2.0 / v_norm2;
+            for i in c..n {
+                q[row * n + i] -= w * v[i - c];
+            }
+        }
+    }
+
+    let mut r = vec![0.0; k * k];
+    for i in 0..k.min(n) {
+        for c in i..k {
+            r[i * k + c] = mat[i * k + c];
+        }
+    }
+    (q, r)
+}
+
+/// Eigenvalues and eigenvectors (columns, row-major) of a symmetric matrix by cyclic Jacobi rotations
+fn symmetric_eigen(mut a: Vec<f64>, n: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut v = vec![0.0; n * n];
+    for i in 0..n {
+        v[i * n + i] = 1.0;
+    }
+    let total: f64 = a.iter().map(|x| x * x).sum();
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i * n + j].powi(2))
+            .sum();
+        if off <= 1e-30 * total || off == 0.0 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[p * n + q];
+                if apq.abs() <= 1e-300 {
+                    continue;
+                }
+                let theta = (a[q * n + q] - a[p * n + p]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (akp, akq) = (a[k * n + p], a[k * n + q]);
+                    a[k * n + p] = c * akp - s * akq;
+                    a[k * n + q] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[p * n + k], a[q * n + k]);
+                    a[p * n + k] = c * apk - s * aqk;
+                    a[q * n + k] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[k * n + p], v[k * n + q]);
+                    v[k * n + p] = c * vkp - s * vkq;
+                    v[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i * n + i]).collect(), v)
+}
+
+/// Dense row-major matrix-vector product
+fn mat_vec(a: &[f64], n: usize, x: &[f64]) -> Vec<f64> {
+    (0..n).map(|i| dot(&a[i * n..(i + 1) * n], x)).collect()
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
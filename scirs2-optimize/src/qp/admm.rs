@@ -0,0 +1,475 @@
+//! Operator-splitting method of OSQP
+//!
+//! The problem is written as `min ½xᵀPx + qᵀx  s.t.  Ax = z,  l ≤ z ≤ u` and
+//! solved with the alternating direction method of multipliers. Every
+//! iteration solves the quasi-definite system `(P + σI + Aᵀ diag(ρ) A) x̃ = r`,
+//! which is factorized with a sparse Cholesky factorization for small and
+//! medium problems and solved with Jacobi-preconditioned conjugate gradients
+//! for large ones. The data is equilibrated with Ruiz scaling, the step size ρ
+//! adapts to the ratio of the residuals, and diverging iterates are checked for
+//! certificates of primal or dual infeasibility.
+//!
+//! Reference: B. Stellato, G. Banjac, P. Goulart, A. Bemporad and S. Boyd,
+//! "OSQP: an operator splitting solver for quadratic programs",
+//! Mathematical Programming Computation 12 (2020).
+
+use super::{norm_inf, CsrOps, Options, QpData, QpSolution, QpStatus};
+use crate::error::{OptimizeError, OptimizeResult};
+use crate::sparse_linalg::{csr_from_triplets, SymmetricFactor};
+use scirs2_sparse::csr_array::CsrArray;
+
+/// Largest number of variables for which the linear system is factorized
+const DIRECT_LIMIT: usize = 1000;
+
+/// Range of the step size ρ
+const RHO_MIN: f64 = 1e-6;
+const RHO_MAX: f64 = 1e6;
+
+/// Step size multiplier for equality rows
+const RHO_EQUALITY_FACTOR: f64 = 1e3;
+
+/// Iterations before the first step size update; the interval doubles after
+/// every update so that ρ settles and the convergence of ADMM is retained
+const ADAPTIVE_RHO_INTERVAL: usize = 25;
+
+/// Factor by which the step size must change to trigger a refactorization
+const ADAPTIVE_RHO_TOLERANCE: f64 = 5.0;
+
+/// Range of the scaling factors of the Ruiz equilibration
+const SCALING_MIN: f64 = 1e-4;
+const SCALING_MAX: f64 = 1e4;
+
+/// Diagonal scaling `P̄ = c D P D`, `q̄ = c D q`, `Ā = E A D`
+struct Scaling {
+    d: Vec<f64>,
+    e: Vec<f64>,
+    c: f64,
+}
+
+/// Solver of the ADMM linear system `K x = r` with `K = P + σI + Aᵀ diag(ρ) A`
+enum LinearSystem {
+    /// Sparse Cholesky factor of `K`
+    Direct(SymmetricFactor),
+    /// Diagonal of `K` for the preconditioned conjugate gradient method
+    Iterative(Vec<f64>),
+}
+
+impl LinearSystem {
+    fn new(p: &CsrArray<f64>, a: &CsrArray<f64>, sigma: f64, rho: &[f64]) -> Option<Self> {
+        let n = p.nrows();
+        if n <= DIRECT_LIMIT {
+            let mut triplets = Vec::new();
+            p.for_each_entry(|i, j, v| triplets.push((i, j, v)));
+            triplets.extend((0..n).map(|j| (j, j, sigma)));
+            for (i, &rho_i) in rho.iter().enumerate() {
+                let row: Vec<(usize, f64)> = a.row(i).collect();
+                for &(j, v) in &row {
+                    for &(l, w) in &row {
+                        triplets.push((j, l, rho_i * v * w));
+                    }
+                }
+            }
+            let k = csr_from_triplets(n, n, triplets);
+            SymmetricFactor::new(&k, 0.0).ok().map(LinearSystem::Direct)
+        } else {
+            let mut diagonal = vec![sigma; n];
+            p.for_each_entry(|i, j, v| {
+                if i == j {
+                    diagonal[i] += v;
+                }
+            });
+            a.for_each_entry(|i, j, v| diagonal[j] += rho[i] * v * v);
+            diagonal
+                .iter()
+                .all(|&d| d > 0.0)
+                .then_some(LinearSystem::Iterative(diagonal))
+        }
+    }
+
+    /// Solve `K x = rhs`, starting the iterative solver from `guess`
+    fn solve(
+        &self,
+        p: &CsrArray<f64>,
+        a: &CsrArray<f64>,
+        sigma: f64,
+        rho: &[f64],
+        rhs: &[f64],
+        guess: &[f64],
+    ) -> Vec<f64> {
+        match self {
+            LinearSystem::Direct(factor) => factor.solve(rhs),
+            LinearSystem::Iterative(diagonal) => {
+                let apply = |v: &[f64]| {
+                    let av: Vec<f64> = a.mul_vec(v).iter().zip(rho).map(|(x, r)| x * r).collect();
+                    let atav = a.mul_transpose_vec(&av);
+                    let pv = p.mul_vec(v);
+                    (0..v.len())
+                        .map(|j| pv[j] + sigma * v[j] + atav[j])
+                        .collect::<Vec<f64>>()
+                };
+                conjugate_gradient(apply, diagonal, rhs, guess)
+            }
+        }
+    }
+}
+
+/// Jacobi-preconditioned conjugate gradients
+fn conjugate_gradient<K: Fn(&[f64]) -> Vec<f64>>(
+    apply: K,
+    diagonal: &[f64],
+    rhs: &[f64],
+    guess: &[f64],
+) -> Vec<f64> {
+    let n = rhs.len();
+    let mut x = guess.to_vec();
+    let kx = apply(&x);
+    let mut r: Vec<f64> = rhs.iter().zip(&kx).map(|(b, k)| b - k).collect();
+    let tol = 1e-10 * norm_inf(rhs).max(1e-30);
+    let mut z: Vec<f64> = r.iter().zip(diagonal).map(|(r, d)| r / d).collect();
+    let mut p = z.clone();
+    let mut rz: f64 = r.iter().zip(&z).map(|(a, b)| a * b).sum();
+    for _ in 0..(2 * n).max(100) {
+        if norm_inf(&r) <= tol {
+            break;
+        }
+        let kp = apply(&p);
+        let pkp: f64 = p.iter().zip(&kp).map(|(a, b)| a * b).sum();
+        if pkp <= 0.0 {
+            break;
+        }
+        let alpha = rz / pkp;
+        for j in 0..n {
+            x[j] += alpha * p[j];
+            r[j] -= alpha * kp[j];
+        }
+        z = r.iter().zip(diagonal).map(|(r, d)| r / d).collect();
+        let rz_next: f64 = r.iter().zip(&z).map(|(a, b)| a * b).sum();
+        let beta = rz_next / rz;
+        rz = rz_next;
+        for j in 0..n {
+            p[j] = z[j] + beta * p[j];
+        }
+    }
+    x
+}
+
+/// Ruiz equilibration of the problem data, modifying `p`, `q` and `a` in place
+fn equilibrate(
+    p: &mut CsrArray<f64>,
+    q: &mut [f64],
+    a: &mut CsrArray<f64>,
+    passes: usize,
+) -> Scaling {
+    let (n, m) = (p.nrows(), a.nrows());
+    let limit = |v: f64| {
+        if v < SCALING_MIN {
+            1.0
+        } else {
+            v.min(SCALING_MAX)
+        }
+    };
+    let mut scaling = Scaling {
+        d: vec![1.0; n],
+        e: vec![1.0; m],
+        c: 1.0,
+    };
+
+    for _ in 0..passes {
+        let p_columns = p.column_norms();
+        let a_columns = a.column_norms();
+        let delta: Vec<f64> = (0..n)
+            .map(|j| 1.0 / limit(p_columns[j].max(a_columns[j])).sqrt())
+            .collect();
+        let epsilon: Vec<f64> = a
+            .row_norms()
+            .iter()
+            .map(|&v| 1.0 / limit(v).sqrt())
+            .collect();
+
+        *p = p.scaled(&delta, &delta);
+        *a = a.scaled(&epsilon, &delta);
+        for j in 0..n {
+            q[j] *= delta[j];
+            scaling.d[j] *= delta[j];
+        }
+        for (e, eps) in scaling.e.iter_mut().zip(&epsilon) {
+            *e *= eps;
+        }
+
+        // Cost scaling balances the quadratic and linear terms
+        let p_norm = p.column_norms().iter().sum::<f64>() / n as f64;
+        let gamma = 1.0 / limit(p_norm.max(norm_inf(q)));
+        *p = p.scaled(&vec![gamma; n], &vec![1.0; n]);
+        q.iter_mut().for_each(|v| *v *= gamma);
+        scaling.c *= gamma;
+    }
+    scaling
+}
+
+/// Solve the problem with the OSQP-style ADMM
+pub(super) fn solve(data: &QpData, options: &Options) -> OptimizeResult<QpSolution> {
+    let (n, m) = (data.n, data.m);
+    let eps_abs = options.eps_abs.unwrap_or(1e-6);
+    let eps_rel = options.eps_rel.unwrap_or(1e-6);
+    let eps_prim_inf = options.eps_prim_inf.unwrap_or(1e-5);
+    let eps_dual_inf = options.eps_dual_inf.unwrap_or(1e-5);
+    let sigma = options.sigma.unwrap_or(1e-6);
+    let alpha = options.alpha.unwrap_or(1.6);
+    let adaptive_rho = options.adaptive_rho.unwrap_or(true);
+    let maxiter = options.maxiter.unwrap_or(10000);
+    let mut rho = options.rho.unwrap_or(0.1);
+    if !(eps_abs >= 0.0 && eps_rel >= 0.0 && eps_prim_inf > 0.0 && eps_dual_inf > 0.0) {
+        return Err(OptimizeError::ValueError(
+            "Tolerances must be nonnegative".to_string(),
+        ));
+    }
+    if !(sigma > 0.0 && rho > 0.0 && alpha > 0.0 && alpha < 2.0) {
+        return Err(OptimizeError::ValueError(
+            "ADMM requires sigma > 0, rho > 0 and 0 < alpha < 2".to_string(),
+        ));
+    }
+    rho = rho.clamp(RHO_MIN, RHO_MAX);
+
+    // Scaled data
+    let mut p = data.p.clone();
+    let mut q = data.q.clone();
+    let mut a = data.a.clone();
+    let scaling = equilibrate(&mut p, &mut q, &mut a, options.scaling.unwrap_or(10));
+    let Scaling { d, e, c } = &scaling;
+    let c = *c;
+    let l: Vec<f64> = (0..m).map(|i| data.l[i] * e[i]).collect();
+    let u: Vec<f64> = (0..m).map(|i| data.u[i] * e[i]).collect();
+    let at = a.transposed();
+
+    let rho_vector = |rho: f64| -> Vec<f64> {
+        (0..m)
+            .map(|i| {
+                if l[i] == f64::NEG_INFINITY && u[i] == f64::INFINITY {
+                    RHO_MIN
+                } else if l[i] == u[i] {
+                    (RHO_EQUALITY_FACTOR * rho).min(RHO_MAX)
+                } else {
+                    rho
+                }
+            })
+            .collect()
+    };
+    let mut rho_vec = rho_vector(rho);
+
+    let unscale = |x: &[f64], y: &[f64]| -> (Vec<f64>, Vec<f64>) {
+        (
+            (0..n).map(|j| d[j] * x[j]).collect(),
+            (0..m).map(|i| e[i] * y[i] / c).collect(),
+        )
+    };
+    let numerical = |iterations: usize| QpSolution {
+        status: QpStatus::Numerical,
+        x: vec![f64::NAN; n],
+        y: vec![f64::NAN; m],
+        iterations,
+        primal_certificate: None,
+        dual_certificate: None,
+    };
+
+    let mut system = match LinearSystem::new(&p, &a, sigma, &rho_vec) {
+        Some(system) => system,
+        None => return Ok(numerical(0)),
+    };
+
+    // Starting point
+    let mut x = vec![0.0; n];
+    let mut y = vec![0.0; m];
+    if let Some(warm) = &options.warm_start {
+        x = (0..n).map(|j| warm.x[j] / d[j]).collect();
+        if let Some(wy) = &warm.y {
+            y = (0..m).map(|i| wy[i] * c / e[i]).collect();
+        }
+    }
+    let mut z: Vec<f64> = a
+        .mul_vec(&x)
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v.clamp(l[i], u[i]))
+        .collect();
+    let mut x_tilde = x.clone();
+    let mut rho_interval = ADAPTIVE_RHO_INTERVAL;
+    let mut next_rho_update = rho_interval;
+
+    for iteration in 1..=maxiter {
+        let x_prev = x.clone();
+        let y_prev = y.clone();
+
+        // x̃ = K⁻¹ (σx - q + Aᵀ(ρz - y)),  z̃ = A x̃
+        let shifted: Vec<f64> = (0..m).map(|i| rho_vec[i] * z[i] - y[i]).collect();
+        let at_shifted = at.mul_vec(&shifted);
+        let rhs: Vec<f64> = (0..n)
+            .map(|j| sigma * x[j] - q[j] + at_shifted[j])
+            .collect();
+        x_tilde = system.solve(&p, &a, sigma, &rho_vec, &rhs, &x_tilde);
+        let z_tilde = a.mul_vec(&x_tilde);
+
+        // Relaxed updates
+        for j in 0..n {
+            x[j] = alpha * x_tilde[j] + (1.0 - alpha) * x_prev[j];
+        }
+        for i in 0..m {
+            let relaxed = alpha * z_tilde[i] + (1.0 - alpha) * z[i];
+            let z_next = (relaxed + y[i] / rho_vec[i]).clamp(l[i], u[i]);
+            y[i] += rho_vec[i] * (relaxed - z_next);
+            z[i] = z_next;
+        }
+
+        // Residuals of the unscaled problem
+        let ax = a.mul_vec(&x);
+        let px = p.mul_vec(&x);
+        let aty = at.mul_vec(&y);
+        let prim_res = norm_inf(&(0..m).map(|i| (ax[i] - z[i]) / e[i]).collect::<Vec<f64>>());
+        let prim_scale = norm_inf(&(0..m).map(|i| ax[i] / e[i]).collect::<Vec<f64>>())
+            .max(norm_inf(&(0..m).map(|i| z[i] / e[i]).collect::<Vec<f64>>()));
+        let dual_res = norm_inf(
+            &(0..n)
+                .map(|j| (px[j] + q[j] + aty[j]) / d[j])
+                .collect::<Vec<f64>>(),
+        ) / c;
+        let dual_scale = norm_inf(&(0..n).map(|j| px[j] / d[j]).collect::<Vec<f64>>())
+            .max(norm_inf(
+                &(0..n).map(|j| aty[j] / d[j]).collect::<Vec<f64>>(),
+            ))
+            .max(norm_inf(&(0..n).map(|j| q[j] / d[j]).collect::<Vec<f64>>()))
+            / c;
+        if !prim_res.is_finite() || !dual_res.is_finite() {
+            return Ok(numerical(iteration));
+        }
+
+        if prim_res <= eps_abs + eps_rel * prim_scale && dual_res <= eps_abs + eps_rel * dual_scale
+        {
+            let (x, y) = unscale(&x, &y);
+            return Ok(QpSolution {
+                status: QpStatus::Solved,
+                x,
+                y,
+                iterations: iteration,
+                primal_certificate: None,
+                dual_certificate: None,
+            });
+        }
+
+        // Infeasibility certificates from the differences of the iterates
+        let (dx, dy) = unscale(
+            &(0..n).map(|j| x[j] - x_prev[j]).collect::<Vec<f64>>(),
+            &(0..m).map(|i| y[i] - y_prev[i]).collect::<Vec<f64>>(),
+        );
+        if let Some(certificate) = primal_infeasibility(data, dy, eps_prim_inf) {
+            let (x, y) = unscale(&x, &y);
+            return Ok(QpSolution {
+                status: QpStatus::PrimalInfeasible,
+                x,
+                y,
+                iterations: iteration,
+                primal_certificate: Some(certificate),
+                dual_certificate: None,
+            });
+        }
+        if let Some(certificate) = dual_infeasibility(data, dx, eps_dual_inf) {
+            let (x, y) = unscale(&x, &y);
+            return Ok(QpSolution {
+                status: QpStatus::DualInfeasible,
+                x,
+                y,
+                iterations: iteration,
+                primal_certificate: None,
+                dual_certificate: Some(certificate),
+            });
+        }
+
+        // Balance the scaled residuals by adapting ρ
+        if adaptive_rho && iteration == next_rho_update {
+            next_rho_update += rho_interval;
+            let prim = norm_inf(&(0..m).map(|i| ax[i] - z[i]).collect::<Vec<f64>>())
+                / norm_inf(&ax).max(norm_inf(&z)).max(1e-30);
+            let dual = norm_inf(&(0..n).map(|j| px[j] + q[j] + aty[j]).collect::<Vec<f64>>())
+                / norm_inf(&px)
+                    .max(norm_inf(&aty))
+                    .max(norm_inf(&q))
+                    .max(1e-30);
+            let candidate = (rho * (prim / dual.max(1e-30)).sqrt()).clamp(RHO_MIN, RHO_MAX);
+            if candidate > ADAPTIVE_RHO_TOLERANCE * rho || candidate < rho / ADAPTIVE_RHO_TOLERANCE
+            {
+                rho = candidate;
+                rho_vec = rho_vector(rho);
+                rho_interval *= 2;
+                next_rho_update = iteration + rho_interval;
+                system = match LinearSystem::new(&p, &a, sigma, &rho_vec) {
+                    Some(system) => system,
+                    None => return Ok(numerical(iteration)),
+                };
+            }
+        }
+    }
+
+    let (x, y) = unscale(&x, &y);
+    Ok(QpSolution {
+        status: QpStatus::IterationLimit,
+        x,
+        y,
+        iterations: maxiter,
+        primal_certificate: None,
+        dual_certificate: None,
+    })
+}
+
+/// Check whether `δy` certifies primal infeasibility:
+/// `Aᵀδy = 0` and `uᵀmax(δy, 0) + lᵀmin(δy, 0) < 0`
+fn primal_infeasibility(data: &QpData, mut dy: Vec<f64>, eps: f64) -> Option<Vec<f64>> {
+    // Project onto the polar of the recession cone of [l, u]
+    for (i, v) in dy.iter_mut().enumerate() {
+        if data.u[i] == f64::INFINITY {
+            *v = v.min(0.0);
+        }
+        if data.l[i] == f64::NEG_INFINITY {
+            *v = v.max(0.0);
+        }
+    }
+    let norm = norm_inf(&dy);
+    if norm <= 1e-30 {
+        return None;
+    }
+    let terms: Vec<f64> = (0..data.m)
+        .map(|i| {
+            if dy[i] > 0.0 {
+                data.u[i] * dy[i]
+            } else if dy[i] < 0.0 {
+                data.l[i] * dy[i]
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    // The support is compared with the size of its terms so that cancellation
+    // in a weakly feasible problem is not mistaken for a negative value
+    let support: f64 = terms.iter().sum();
+    let magnitude = terms.iter().map(|t| t.abs()).sum::<f64>().max(norm);
+    if support >= -eps * magnitude || norm_inf(&data.a.mul_transpose_vec(&dy)) > eps * norm {
+        return None;
+    }
+    Some(dy.iter().map(|v| v / norm).collect())
+}
+
+/// Check whether `δx` certifies dual infeasibility:
+/// `Pδx = 0`, `qᵀδx < 0` and `Aδx` in the recession cone of `[l, u]`
+fn dual_infeasibility(data: &QpData, dx: Vec<f64>, eps: f64) -> Option<Vec<f64>> {
+    let norm = norm_inf(&dx);
+    if norm <= 1e-30 {
+        return None;
+    }
+    let descent: f64 = data.q.iter().zip(&dx).map(|(a, b)| a * b).sum();
+    if descent >= -eps * norm || norm_inf(&data.p.mul_vec(&dx)) > eps * norm {
+        return None;
+    }
+    let adx = data.a.mul_vec(&dx);
+    let in_cone = (0..data.m).all(|i| {
+        (data.u[i] == f64::INFINITY || adx[i] <= eps * norm)
+            && (data.l[i] == f64::NEG_INFINITY || adx[i] >= -eps * norm)
+    });
+    in_cone.then(|| dx.iter().map(|v| v / norm).collect())
+}
@@ -0,0 +1,765 @@
+//! Convex quadratic programming
+//!
+//! This module solves quadratic programs of the form
+//!
+//! ```text
+//! minimize    ½ xᵀPx + qᵀx
+//! subject to  l ≤ Ax ≤ u
+//! ```
+//!
+//! where `P` is symmetric positive semidefinite. Equality constraints are
+//! rows with `l_i = u_i`, one-sided constraints use infinite bounds and
+//! variable bounds are rows of the identity. `P` and `A` may be dense
+//! (`Array2`) or sparse (`scirs2_sparse::CsrArray`); only the symmetric part
+//! of `P` enters the objective. Two methods are available:
+//!
+//! * [`Method::ActiveSet`]: a primal active-set method using a null-space
+//!   factorization of the working set. It is exact and suited to small dense
+//!   problems.
+//! * [`Method::Admm`]: the operator-splitting method of OSQP (alternating
+//!   direction method of multipliers) with Ruiz equilibration and adaptive
+//!   step size. It handles large sparse problems, can be warm started from a
+//!   previous solution and returns certificates of primal or dual
+//!   infeasibility.
+//!
+//! ## Example
+//!
+//! ```
+//! use ndarray::array;
+//! use scirs2_optimize::qp::{minimize_qp, Method, QuadraticProgram};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // minimize x² + xy + y² + x + y  s.t.  x + y = 1,  0 ≤ x ≤ 0.7,  0 ≤ y ≤ 0.7
+//! let problem = QuadraticProgram::new(array![[2.0, 1.0], [1.0, 2.0]], array![1.0, 1.0])
+//!     .with_constraints(
+//!         array![[1.0, 1.0], [1.0, 0.0], [0.0, 1.0]],
+//!         array![1.0, 0.0, 0.0],
+//!         array![1.0, 0.7, 0.7],
+//!     );
+//!
+//! let solution = minimize_qp(&problem, Method::ActiveSet, None)?;
+//! assert!(solution.result.success);
+//! assert!((solution.result.x[0] - 0.5).abs() < 1e-8);
+//! assert!((solution.result.fun - 1.75).abs() < 1e-8);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{OptimizeError, OptimizeResult};
+use crate::linprog::ConstraintMatrix;
+use crate::result::OptimizeResults;
+use crate::sparse_linalg::csr_from_triplets;
+use ndarray::Array1;
+use scirs2_sparse::csr_array::CsrArray;
+use scirs2_sparse::sparray::SparseArray;
+use std::fmt;
+
+mod active_set;
+mod admm;
+
+/// Methods for solving quadratic programs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Primal active-set method for small dense problems
+    ActiveSet,
+
+    /// OSQP-style operator splitting (ADMM) for large sparse problems
+    Admm,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Method::ActiveSet => write!(f, "active-set"),
+            Method::Admm => write!(f, "admm"),
+        }
+    }
+}
+
+/// Starting point for warm starting a solve.
+#[derive(Debug, Clone)]
+pub struct WarmStart {
+    /// Primal starting point
+    pub x: Array1<f64>,
+
+    /// Constraint multipliers (ignored by the active-set method)
+    pub y: Option<Array1<f64>>,
+}
+
+/// Options for the quadratic programming solvers.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Maximum number of iterations (default: 10000 for ADMM, 10(n + m) + 100 for active set)
+    pub maxiter: Option<usize>,
+
+    /// Absolute tolerance of the ADMM residuals (default: 1e-6)
+    pub eps_abs: Option<f64>,
+
+    /// Relative tolerance of the ADMM residuals (default: 1e-6)
+    pub eps_rel: Option<f64>,
+
+    /// Tolerance of the primal infeasibility test of ADMM (default: 1e-5)
+    pub eps_prim_inf: Option<f64>,
+
+    /// Tolerance of the dual infeasibility test of ADMM (default: 1e-5)
+    pub eps_dual_inf: Option<f64>,
+
+    /// Initial ADMM step size ρ (default: 0.1)
+    pub rho: Option<f64>,
+
+    /// ADMM regularization σ of the primal variables (default: 1e-6)
+    pub sigma: Option<f64>,
+
+    /// ADMM over-relaxation parameter in (0, 2) (default: 1.6)
+    pub alpha: Option<f64>,
+
+    /// Adapt ρ to balance the ADMM residuals (default: true)
+    pub adaptive_rho: Option<bool>,
+
+    /// Number of Ruiz equilibration passes for ADMM; 0 disables scaling (default: 10)
+    pub scaling: Option<usize>,
+
+    /// Feasibility and optimality tolerance of the active-set method (default: 1e-9)
+    pub tol: Option<f64>,
+
+    /// Starting point from a previous solve
+    pub warm_start: Option<WarmStart>,
+}
+
+/// Definition of a convex quadratic program.
+#[derive(Clone)]
+pub struct QuadraticProgram {
+    /// Quadratic term `P` (n × n, positive semidefinite)
+    pub p: ConstraintMatrix,
+
+    /// Linear term `q`
+    pub q: Array1<f64>,
+
+    /// Constraint matrix `A` with lower and upper bounds `l ≤ Ax ≤ u`
+    pub constraints: Option<(ConstraintMatrix, Array1<f64>, Array1<f64>)>,
+}
+
+impl QuadraticProgram {
+    /// Create an unconstrained quadratic program
+    pub fn new<M: Into<ConstraintMatrix>>(p: M, q: Array1<f64>) -> Self {
+        QuadraticProgram {
+            p: p.into(),
+            q,
+            constraints: None,
+        }
+    }
+
+    /// Set the constraints `l ≤ Ax ≤ u`; infinite bounds are allowed
+    pub fn with_constraints<M: Into<ConstraintMatrix>>(
+        mut self,
+        a: M,
+        l: Array1<f64>,
+        u: Array1<f64>,
+    ) -> Self {
+        self.constraints = Some((a.into(), l, u));
+        self
+    }
+
+    /// Number of variables
+    pub fn num_variables(&self) -> usize {
+        self.q.len()
+    }
+}
+
+/// Result of a quadratic program.
+///
+/// The status codes of `result.status` are:
+///
+/// * `0`: solved
+/// * `1`: iteration limit reached
+/// * `2`: the problem is primal infeasible
+/// * `3`: the problem is dual infeasible (unbounded below)
+/// * `4`: numerical difficulties encountered
+///
+/// A problem that is both primal and dual infeasible is reported as primal
+/// infeasible by the active-set method and may be reported with either status
+/// by ADMM.
+#[derive(Debug, Clone)]
+pub struct QpResult {
+    /// Solution and solver statistics
+    pub result: OptimizeResults<f64>,
+
+    /// Constraint multipliers; positive entries belong to active upper bounds,
+    /// negative entries to active lower bounds
+    pub y: Array1<f64>,
+
+    /// Vector `δy` with `Aᵀδy = 0` and `uᵀmax(δy, 0) + lᵀmin(δy, 0) < 0`
+    /// proving that no `x` satisfies the constraints
+    pub primal_infeasibility_certificate: Option<Array1<f64>>,
+
+    /// Direction `δx` with `Pδx = 0`, `qᵀδx < 0` and `Aδx` in the recession
+    /// cone of the constraints, proving that the objective is unbounded below
+    pub dual_infeasibility_certificate: Option<Array1<f64>>,
+}
+
+/// Termination status of a QP solve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QpStatus {
+    Solved,
+    IterationLimit,
+    PrimalInfeasible,
+    DualInfeasible,
+    Numerical,
+}
+
+impl QpStatus {
+    fn code(self) -> i32 {
+        match self {
+            QpStatus::Solved => 0,
+            QpStatus::IterationLimit => 1,
+            QpStatus::PrimalInfeasible => 2,
+            QpStatus::DualInfeasible => 3,
+            QpStatus::Numerical => 4,
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            QpStatus::Solved => "Optimization terminated successfully.",
+            QpStatus::IterationLimit => "Iteration limit reached.",
+            QpStatus::PrimalInfeasible => "The problem is primal infeasible.",
+            QpStatus::DualInfeasible => "The problem is dual infeasible.",
+            QpStatus::Numerical => "Numerical difficulties encountered.",
+        }
+    }
+}
+
+/// Raw solution of a QP solver
+struct QpSolution {
+    status: QpStatus,
+    x: Vec<f64>,
+    y: Vec<f64>,
+    iterations: usize,
+    primal_certificate: Option<Vec<f64>>,
+    dual_certificate: Option<Vec<f64>>,
+}
+
+/// Copy of a constraint matrix in CSR format
+fn csr_from_matrix(matrix: &ConstraintMatrix) -> CsrArray<f64> {
+    let (nrows, ncols) = matrix.shape();
+    let mut triplets = Vec::new();
+    matrix.for_each_nonzero(|i, j, v| triplets.push((i, j, v)));
+    csr_from_triplets(nrows, ncols, triplets)
+}
+
+/// Operations on the CSR matrices used by the solvers
+trait CsrOps {
+    fn nrows(&self) -> usize;
+    fn ncols(&self) -> usize;
+    fn for_each_entry<G: FnMut(usize, usize, f64)>(&self, f: G);
+    fn row(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_;
+
+    /// `M x`
+    fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        (0..self.nrows())
+            .map(|i| self.row(i).map(|(j, v)| v * x[j]).sum())
+            .collect()
+    }
+
+    /// `Mᵀ y`
+    fn mul_transpose_vec(&self, y: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; self.ncols()];
+        self.for_each_entry(|i, j, v| out[j] += v * y[i]);
+        out
+    }
+
+    /// `Mᵀ`
+    fn transposed(&self) -> CsrArray<f64> {
+        let mut triplets = Vec::new();
+        self.for_each_entry(|i, j, v| triplets.push((j, i, v)));
+        csr_from_triplets(self.ncols(), self.nrows(), triplets)
+    }
+
+    /// `diag(row) M diag(col)`
+    fn scaled(&self, row: &[f64], col: &[f64]) -> CsrArray<f64> {
+        let mut triplets = Vec::new();
+        self.for_each_entry(|i, j, v| triplets.push((i, j, row[i] * v * col[j])));
+        csr_from_triplets(self.nrows(), self.ncols(), triplets)
+    }
+
+    /// Infinity norms of the columns
+    fn column_norms(&self) -> Vec<f64> {
+        let mut norms = vec![0.0f64; self.ncols()];
+        self.for_each_entry(|_, j, v| norms[j] = norms[j].max(v.abs()));
+        norms
+    }
+
+    /// Infinity norms of the rows
+    fn row_norms(&self) -> Vec<f64> {
+        (0..self.nrows())
+            .map(|i| self.row(i).fold(0.0f64, |acc, (_, v)| acc.max(v.abs())))
+            .collect()
+    }
+
+    /// Dense row-major copy
+    fn to_dense(&self) -> Vec<f64> {
+        let ncols = self.ncols();
+        let mut dense = vec![0.0; self.nrows() * ncols];
+        self.for_each_entry(|i, j, v| dense[i * ncols + j] += v);
+        dense
+    }
+}
+
+impl CsrOps for CsrArray<f64> {
+    fn nrows(&self) -> usize {
+        self.shape().0
+    }
+
+    fn ncols(&self) -> usize {
+        self.shape().1
+    }
+
+    fn for_each_entry<G: FnMut(usize, usize, f64)>(&self, mut f: G) {
+        for i in 0..self.nrows() {
+            for (j, v) in self.row(i) {
+                f(i, j, v);
+            }
+        }
+    }
+
+    fn row(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let (indptr, indices, data) = (self.get_indptr(), self.get_indices(), self.get_data());
+        (indptr[i]..indptr[i + 1]).map(move |k| (indices[k], data[k]))
+    }
+}
+
+/// Validated problem data shared by the solvers
+struct QpData {
+    n: usize,
+    m: usize,
+    /// Symmetric part of `P`
+    p: CsrArray<f64>,
+    q: Vec<f64>,
+    a: CsrArray<f64>,
+    l: Vec<f64>,
+    u: Vec<f64>,
+}
+
+impl QpData {
+    fn new(problem: &QuadraticProgram) -> OptimizeResult<Self> {
+        let n = problem.q.len();
+        if n == 0 {
+            return Err(OptimizeError::ValueError(
+                "Linear term must not be empty".to_string(),
+            ));
+        }
+        if problem.q.iter().any(|v| !v.is_finite()) {
+            return Err(OptimizeError::ValueError(
+                "Linear term must be finite".to_string(),
+            ));
+        }
+        if problem.p.shape() != (n, n) {
+            return Err(OptimizeError::ValueError(format!(
+                "P has shape {:?}, expected ({}, {})",
+                problem.p.shape(),
+                n,
+                n
+            )));
+        }
+
+        let raw = csr_from_matrix(&problem.p);
+        if raw.get_data().iter().any(|v| !v.is_finite()) {
+            return Err(OptimizeError::ValueError(
+                "P must contain only finite values".to_string(),
+            ));
+        }
+        let mut triplets = Vec::with_capacity(2 * raw.nnz());
+        raw.for_each_entry(|i, j, v| {
+            triplets.push((i, j, 0.5 * v));
+            triplets.push((j, i, 0.5 * v));
+        });
+        let p = csr_from_triplets(n, n, triplets);
+
+        let (a, l, u) = match &problem.constraints {
+            Some((a, l, u)) => {
+                let (m, cols) = a.shape();
+                if cols != n {
+                    return Err(OptimizeError::ValueError(format!(
+                        "A has {} columns, but there are {} variables",
+                        cols, n
+                    )));
+                }
+                if l.len() != m || u.len() != m {
+                    return Err(OptimizeError::ValueError(format!(
+                        "A has {} rows, but the bounds have lengths {} and {}",
+                        m,
+                        l.len(),
+                        u.len()
+                    )));
+                }
+                let a = csr_from_matrix(a);
+                if a.get_data().iter().any(|v| !v.is_finite()) {
+                    return Err(OptimizeError::ValueError(
+                        "A must contain only finite values".to_string(),
+                    ));
+                }
+                for i in 0..m {
+                    if l[i].is_nan()
+                        || u[i].is_nan()
+                        || l[i] > u[i]
+                        || l[i] == f64::INFINITY
+                        || u[i] == f64::NEG_INFINITY
+                    {
+                        return Err(OptimizeError::ValueError(format!(
+                            "Invalid bounds for constraint {}",
+                            i
+                        )));
+                    }
+                }
+                (a, l.to_vec(), u.to_vec())
+            }
+            None => (csr_from_triplets(0, n, Vec::new()), Vec::new(), Vec::new()),
+        };
+
+        Ok(QpData {
+            n,
+            m: a.nrows(),
+            p,
+            q: problem.q.to_vec(),
+            a,
+            l,
+            u,
+        })
+    }
+
+    /// `½ xᵀPx + qᵀx`
+    fn objective(&self, x: &[f64]) -> f64 {
+        let px = self.p.mul_vec(x);
+        x.iter()
+            .zip(&px)
+            .zip(&self.q)
+            .map(|((xi, pxi), qi)| 0.5 * xi * pxi + qi * xi)
+            .sum()
+    }
+}
+
+/// Solve a convex quadratic programming problem.
+///
+/// # Arguments
+///
+/// * `problem` - The quadratic program
+/// * `method` - The method to use
+/// * `options` - Optional solver parameters
+///
+/// # Returns
+///
+/// * `QpResult` containing the solution, the constraint multipliers and, for
+///   infeasible or unbounded problems, a certificate
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_optimize::qp::{minimize_qp, Method, Options, QuadraticProgram, WarmStart};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // minimize (x - 2)² + (y - 1)²  s.t.  x + y ≤ 2
+/// let problem = QuadraticProgram::new(array![[2.0, 0.0], [0.0, 2.0]], array![-4.0, -2.0])
+///     .with_constraints(array![[1.0, 1.0]], array![f64::NEG_INFINITY], array![2.0]);
+///
+/// let first = minimize_qp(&problem, Method::Admm, None)?;
+/// assert!((first.result.x[0] - 1.5).abs() < 1e-4);
+/// assert!((first.y[0] - 1.0).abs() < 1e-4);
+///
+/// // Re-solve from the previous solution
+/// let options = Options {
+///     warm_start: Some(WarmStart {
+///         x: first.result.x.clone(),
+///         y: Some(first.y.clone()),
+///     }),
+///     ..Options::default()
+/// };
+/// let second = minimize_qp(&problem, Method::Admm, Some(options))?;
+/// assert!(second.result.nit <= first.result.nit);
+/// # Ok(())
+/// # }
+/// ```
+pub fn minimize_qp(
+    problem: &QuadraticProgram,
+    method: Method,
+    options: Option<Options>,
+) -> OptimizeResult<QpResult> {
+    let options = options.unwrap_or_default();
+    let data = QpData::new(problem)?;
+
+    if let Some(warm) = &options.warm_start {
+        if warm.x.len() != data.n || warm.y.as_ref().is_some_and(|y| y.len() != data.m) {
+            return Err(OptimizeError::ValueError(format!(
+                "Warm start must have {} primal and {} dual values",
+                data.n, data.m
+            )));
+        }
+    }
+
+    let solution = match method {
+        Method::ActiveSet => active_set::solve(&data, &options)?,
+        Method::Admm => admm::solve(&data, &options)?,
+    };
+
+    let fun = if solution.status == QpStatus::Solved || solution.status == QpStatus::IterationLimit
+    {
+        data.objective(&solution.x)
+    } else if solution.status == QpStatus::DualInfeasible {
+        f64::NEG_INFINITY
+    } else {
+        f64::NAN
+    };
+
+    Ok(QpResult {
+        result: OptimizeResults {
+            x: Array1::from_vec(solution.x),
+            fun,
+            nit: solution.iterations,
+            message: solution.status.message().to_string(),
+            success: solution.status == QpStatus::Solved,
+            status: solution.status.code(),
+            ..Default::default()
+        },
+        y: Array1::from_vec(solution.y),
+        primal_infeasibility_certificate: solution.primal_certificate.map(Array1::from_vec),
+        dual_infeasibility_certificate: solution.dual_certificate.map(Array1::from_vec),
+    })
+}
+
+/// Infinity norm
+fn norm_inf(v: &[f64]) -> f64 {
+    v.iter().fold(0.0f64, |acc, x| acc.max(x.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array2};
+    use scirs2_sparse::csr_array::CsrArray;
+
+    const METHODS: [Method; 2] = [Method::ActiveSet, Method::Admm];
+
+    fn tolerance(method: Method) -> f64 {
+        match method {
+            Method::ActiveSet => 1e-8,
+            Method::Admm => 1e-4,
+        }
+    }
+
+    #[test]
+    fn test_qp_equality_and_bounds() {
+        // minimize x² + xy + y² + x + y  s.t.  x + y = 1,  0 ≤ x, y ≤ 0.7
+        let problem = QuadraticProgram::new(array![[2.0, 1.0], [1.0, 2.0]], array![1.0, 1.0])
+            .with_constraints(
+                array![[1.0, 1.0], [1.0, 0.0], [0.0, 1.0]],
+                array![1.0, 0.0, 0.0],
+                array![1.0, 0.7, 0.7],
+            );
+        for method in METHODS {
+            let solution = minimize_qp(&problem, method, None).unwrap();
+            let tol = tolerance(method);
+            assert!(solution.result.success, "{}", method);
+            assert!((solution.result.x[0] - 0.5).abs() < tol);
+            assert!((solution.result.x[1] - 0.5).abs() < tol);
+            assert!((solution.result.fun - 1.75).abs() < tol);
+            // Stationarity: Px + q + Aᵀy = 0 with y = (-2.5, 0, 0)
+            assert!((solution.y[0] + 2.5).abs() < 10.0 * tol);
+            assert!(solution.y[1].abs() < 10.0 * tol);
+        }
+
+        // Active bounds on the other side give multipliers of both signs
+        let problem = QuadraticProgram::new(array![[1.0, 0.0], [0.0, 1.0]], array![-3.0, 2.0])
+            .with_constraints(
+                array![[1.0, 0.0], [0.0, 1.0]],
+                array![-1.0, -1.0],
+                array![1.0, 1.0],
+            );
+        for method in METHODS {
+            let solution = minimize_qp(&problem, method, None).unwrap();
+            let tol = tolerance(method);
+            assert!((solution.result.x[0] - 1.0).abs() < tol);
+            assert!((solution.result.x[1] + 1.0).abs() < tol);
+            assert!((solution.y[0] - 2.0).abs() < 10.0 * tol);
+            assert!((solution.y[1] + 1.0).abs() < 10.0 * tol);
+        }
+    }
+
+    #[test]
+    fn test_qp_sparse_portfolio_matches_dense() {
+        // Minimum variance portfolio with a return target and no short sales
+        let n = 6;
+        let mut cov = Array2::zeros((n, n));
+        for i in 0..n {
+            cov[[i, i]] = 0.04 + 0.01 * i as f64;
+            if i + 1 < n {
+                cov[[i, i + 1]] = 0.01;
+                cov[[i + 1, i]] = 0.01;
+            }
+        }
+        let returns = [0.05, 0.07, 0.06, 0.09, 0.11, 0.08];
+        let mut a = Array2::zeros((n + 2, n));
+        let mut l = Array1::zeros(n + 2);
+        let mut u = Array1::zeros(n + 2);
+        for j in 0..n {
+            a[[0, j]] = 1.0;
+            a[[1, j]] = returns[j];
+            a[[j + 2, j]] = 1.0;
+            u[j + 2] = 1.0;
+        }
+        l[0] = 1.0;
+        u[0] = 1.0;
+        l[1] = 0.08;
+        u[1] = f64::INFINITY;
+
+        let to_sparse = |m: &Array2<f64>| {
+            let (mut rows, mut cols, mut vals) = (Vec::new(), Vec::new(), Vec::new());
+            for ((i, j), &v) in m.indexed_iter() {
+                if v != 0.0 {
+                    rows.push(i);
+                    cols.push(j);
+                    vals.push(v);
+                }
+            }
+            CsrArray::from_triplets(&rows, &cols, &vals, m.dim(), false).unwrap()
+        };
+
+        let dense = QuadraticProgram::new(cov.clone(), Array1::zeros(n)).with_constraints(
+            a.clone(),
+            l.clone(),
+            u.clone(),
+        );
+        let sparse = QuadraticProgram::new(to_sparse(&cov), Array1::zeros(n)).with_constraints(
+            to_sparse(&a),
+            l,
+            u,
+        );
+
+        let exact = minimize_qp(&dense, Method::ActiveSet, None).unwrap();
+        assert!(exact.result.success);
+        let weights: f64 = exact.result.x.sum();
+        assert!((weights - 1.0).abs() < 1e-10);
+        assert!(exact.result.x.iter().all(|&w| w >= -1e-12));
+
+        for problem in [&dense, &sparse] {
+            let options = Options {
+                eps_abs: Some(1e-8),
+                eps_rel: Some(1e-8),
+                ..Options::default()
+            };
+            let admm = minimize_qp(problem, Method::Admm, Some(options)).unwrap();
+            assert!(admm.result.success, "{}", admm.result.message);
+            assert!((admm.result.fun - exact.result.fun).abs() < 1e-6);
+            for j in 0..n {
+                assert!((admm.result.x[j] - exact.result.x[j]).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qp_infeasibility_certificates() {
+        // x + y ≥ 3 and x, y ≤ 1 cannot hold together
+        let problem = QuadraticProgram::new(array![[1.0, 0.0], [0.0, 1.0]], array![0.0, 0.0])
+            .with_constraints(
+                array![[1.0, 1.0], [1.0, 0.0], [0.0, 1.0]],
+                array![3.0, f64::NEG_INFINITY, f64::NEG_INFINITY],
+                array![f64::INFINITY, 1.0, 1.0],
+            );
+        for method in METHODS {
+            let solution = minimize_qp(&problem, method, None).unwrap();
+            assert_eq!(solution.result.status, 2, "{}", method);
+            assert!(!solution.result.success);
+        }
+        let solution = minimize_qp(&problem, Method::Admm, None).unwrap();
+        let cert = solution.primal_infeasibility_certificate.unwrap();
+        // Aᵀδy = 0 and the support function is negative
+        let aty = [cert[0] + cert[1], cert[0] + cert[2]];
+        let scale = norm_inf(cert.as_slice().unwrap());
+        assert!(norm_inf(&aty) < 1e-4 * scale);
+        assert!(cert[0] < 0.0 && cert[1] > 0.0 && cert[2] > 0.0);
+        assert!(3.0 * cert[0] + cert[1] + cert[2] < 0.0);
+
+        // ½(x - y)² - y with x ≥ 0 decreases without bound along x = y
+        let problem = QuadraticProgram::new(array![[1.0, -1.0], [-1.0, 1.0]], array![0.0, -1.0])
+            .with_constraints(array![[1.0, 0.0]], array![0.0], array![f64::INFINITY]);
+        for method in METHODS {
+            let solution = minimize_qp(&problem, method, None).unwrap();
+            assert_eq!(solution.result.status, 3, "{}", method);
+            let d = solution.dual_infeasibility_certificate.unwrap();
+            let scale = norm_inf(d.as_slice().unwrap());
+            // Pd = 0, qᵀd < 0 and d_0 ≥ 0
+            assert!((d[0] - d[1]).abs() < 1e-4 * scale);
+            assert!(-d[1] < 0.0);
+            assert!(d[0] > -1e-4 * scale);
+        }
+    }
+
+    #[test]
+    fn test_qp_semidefinite_and_validation() {
+        // Linear objective in x, quadratic in y: minimize y² - x  s.t.  x + y ≤ 1
+        let problem = QuadraticProgram::new(array![[0.0, 0.0], [0.0, 2.0]], array![-1.0, 0.0])
+            .with_constraints(array![[1.0, 1.0]], array![f64::NEG_INFINITY], array![1.0]);
+        for method in METHODS {
+            let solution = minimize_qp(&problem, method, None).unwrap();
+            let tol = tolerance(method);
+            assert!(solution.result.success, "{}", method);
+            assert!((solution.result.x[1] + 0.5).abs() < tol);
+            assert!((solution.result.x[0] - 1.5).abs() < tol);
+            assert!((solution.result.fun + 1.25).abs() < tol);
+        }
+
+        // Indefinite P is rejected by the active-set method
+        let problem = QuadraticProgram::new(array![[1.0, 0.0], [0.0, -1.0]], array![0.0, 0.0]);
+        assert!(minimize_qp(&problem, Method::ActiveSet, None).is_err());
+
+        // Dimension and bound errors
+        let problem = QuadraticProgram::new(array![[1.0]], array![1.0, 2.0]);
+        assert!(minimize_qp(&problem, Method::Admm, None).is_err());
+        let problem = QuadraticProgram::new(array![[1.0]], array![1.0]).with_constraints(
+            array![[1.0]],
+            array![2.0],
+            array![1.0],
+        );
+        assert!(minimize_qp(&problem, Method::ActiveSet, None).is_err());
+    }
+
+    #[test]
+    fn test_qp_large_sparse_admm() {
+        // Obstacle-type problem large enough for the iterative linear solver:
+        // min ½xᵀLx + qᵀx with x ≤ 0.5 and Σx = 450, L the second difference matrix
+        let n = 1200;
+        let (mut rows, mut cols, mut vals) = (Vec::new(), Vec::new(), Vec::new());
+        for i in 0..n {
+            rows.push(i);
+            cols.push(i);
+            vals.push(2.0);
+            if i + 1 < n {
+                rows.extend([i, i + 1]);
+                cols.extend([i + 1, i]);
+                vals.extend([-1.0, -1.0]);
+            }
+        }
+        let p = CsrArray::from_triplets(&rows, &cols, &vals, (n, n), false).unwrap();
+        let q = Array1::from_elem(n, -1e-3);
+        let (mut rows, mut cols, mut vals) = (Vec::new(), Vec::new(), Vec::new());
+        for j in 0..n {
+            rows.extend([0, j + 1]);
+            cols.extend([j, j]);
+            vals.extend([1.0, 1.0]);
+        }
+        let a = CsrArray::from_triplets(&rows, &cols, &vals, (n + 1, n), false).unwrap();
+        let mut l = Array1::from_elem(n + 1, f64::NEG_INFINITY);
+        let mut u = Array1::from_elem(n + 1, 0.5);
+        l[0] = 450.0;
+        u[0] = 450.0;
+        let problem = QuadraticProgram::new(p, q).with_constraints(a, l, u);
+
+        let solution = minimize_qp(&problem, Method::Admm, None).unwrap();
+        assert!(solution.result.success, "{}", solution.result.message);
+        let x = &solution.result.x;
+        assert!((x.sum() - 450.0).abs() < 1e-2);
+        assert!(x.iter().all(|&v| v <= 0.5 + 1e-3));
+        // The obstacle is touched in the middle of the domain
+        assert!((x[n / 2] - 0.5).abs() < 1e-3);
+        assert!(x[0] < 0.1 && x[n - 1] < 0.1);
+    }
+}
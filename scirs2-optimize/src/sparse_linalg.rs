@@ -0,0 +1,133 @@
+//! Sparse matrix helpers shared by the linear and quadratic programming solvers
+//!
+//! Symmetric systems such as interior-point normal equations and the ADMM
+//! system are factorized with the sparse Cholesky factorization of
+//! `scirs2_sparse`, optionally shifted by a multiple of the identity so that
+//! positive semidefinite (e.g. rank-deficient) matrices can be factored too.
+
+use crate::error::OptimizeResult;
+use ndarray::Array1;
+use scirs2_sparse::csr::CsrMatrix;
+use scirs2_sparse::csr_array::CsrArray;
+use scirs2_sparse::error::{SparseError, SparseResult};
+use scirs2_sparse::linalg::{sparse_cholesky, OrderingMethod, SparseCholesky};
+use scirs2_sparse::sparray::SparseArray;
+
+/// Factor by which the diagonal shift grows after a failed factorization
+const SHIFT_GROWTH: f64 = 100.0;
+
+/// Number of shifted attempts before the factorization is given up
+const MAX_SHIFTS: usize = 10;
+
+/// Build a CSR array from `(row, column, value)` triplets, summing duplicates
+pub(crate) fn csr_from_triplets(
+    nrows: usize,
+    ncols: usize,
+    mut triplets: Vec<(usize, usize, f64)>,
+) -> CsrArray<f64> {
+    triplets.sort_by_key(|&(i, j, _)| (i, j));
+    let mut indptr = vec![0; nrows + 1];
+    let mut indices: Vec<usize> = Vec::with_capacity(triplets.len());
+    let mut data: Vec<f64> = Vec::with_capacity(triplets.len());
+    let mut last = None;
+    for (i, j, v) in triplets {
+        if last == Some((i, j)) {
+            *data.last_mut().expect("duplicate follows an entry") += v;
+        } else {
+            indices.push(j);
+            data.push(v);
+            indptr[i + 1] += 1;
+            last = Some((i, j));
+        }
+    }
+    for i in 0..nrows {
+        indptr[i + 1] += indptr[i];
+    }
+    CsrArray::new(
+        Array1::from_vec(data),
+        Array1::from_vec(indices),
+        Array1::from_vec(indptr),
+        (nrows, ncols),
+    )
+    .expect("triplets lie within the shape")
+}
+
+/// Cholesky factorization of a sparse symmetric positive semidefinite matrix
+///
+/// With a relative regularization `r > 0` the factored matrix is `A + δI`,
+/// where `δ` starts at `r` times the largest diagonal entry and grows until
+/// all pivots are positive. With `r = 0` the matrix must be positive definite.
+pub(crate) struct SymmetricFactor {
+    factor: SparseCholesky<f64>,
+    regularization: f64,
+}
+
+impl SymmetricFactor {
+    /// Factorize `a`, whose rows must contain both triangles
+    pub(crate) fn new(a: &CsrArray<f64>, regularization: f64) -> OptimizeResult<Self> {
+        let factor = shifted(a, regularization, |m| {
+            sparse_cholesky(m, OrderingMethod::Amd)
+        })?;
+        Ok(SymmetricFactor {
+            factor,
+            regularization,
+        })
+    }
+
+    /// Factorize new values with the sparsity pattern of the original matrix
+    pub(crate) fn refactor(&mut self, a: &CsrArray<f64>) -> OptimizeResult<()> {
+        let factor = &mut self.factor;
+        shifted(a, self.regularization, |m| factor.refactor(m))
+    }
+
+    /// Solve `A z = r`
+    pub(crate) fn solve(&self, r: &[f64]) -> Vec<f64> {
+        self.factor
+            .solve(r)
+            .expect("right-hand side has the dimension of the factor")
+    }
+}
+
+/// Run `factor` on `a + δI`, growing `δ` while the matrix is not positive definite
+fn shifted<T, G>(a: &CsrArray<f64>, regularization: f64, mut factor: G) -> OptimizeResult<T>
+where
+    G: FnMut(&CsrMatrix<f64>) -> SparseResult<T>,
+{
+    let n = a.shape().0;
+    let (indptr, indices, data) = (a.get_indptr(), a.get_indices(), a.get_data());
+    let max_diag = (0..n)
+        .flat_map(|i| (indptr[i]..indptr[i + 1]).filter(move |&k| indices[k] == i))
+        .fold(0.0f64, |acc, k| acc.max(data[k].abs()));
+    let mut shift = if regularization > 0.0 {
+        (regularization * max_diag).max(1e-30)
+    } else {
+        0.0
+    };
+
+    let mut attempt = 0;
+    loop {
+        // The diagonal is always part of the pattern so that refactorizations
+        // with a different shift keep the symbolic analysis valid
+        let mut triplets = Vec::with_capacity(data.len() + n);
+        for i in 0..n {
+            for k in indptr[i]..indptr[i + 1] {
+                triplets.push((i, indices[k], data[k]));
+            }
+            triplets.push((i, i, shift));
+        }
+        let shifted = csr_from_triplets(n, n, triplets);
+        let matrix = CsrMatrix::from_raw_csr(
+            shifted.get_data().to_vec(),
+            shifted.get_indptr().to_vec(),
+            shifted.get_indices().to_vec(),
+            (n, n),
+        )?;
+        match factor(&matrix) {
+            Err(SparseError::ValueError(_)) if shift > 0.0 && attempt < MAX_SHIFTS => {
+                shift *= SHIFT_GROWTH;
+                attempt += 1;
+            }
+            result => return Ok(result?),
+        }
+    }
+}
@@ -4,7 +4,6 @@
 // which is efficient for matrices with block-structured sparsity patterns.
 
 use ndarray::{Array1, Array2, ArrayView1};
-use num_traits::Float;
 use std::fmt::{self, Debug};
 use std::ops::{Add, Div, Mul, Sub};
 
@@ -13,6 +12,9 @@ use crate::csc_array::CscArray;
 use crate::csr_array::CsrArray;
 use crate::dia_array::DiaArray;
 use crate::dok_array::DokArray;
+use crate::element::{
+    element_infinity, element_max, element_min, element_neg_infinity, SparseElement,
+};
 use crate::error::{SparseError, SparseResult};
 use crate::lil_array::LilArray;
 use crate::sparray::{SparseArray, SparseSum};
@@ -34,7 +36,7 @@ use crate::sparray::{SparseArray, SparseSum};
 #[derive(Clone)]
 pub struct BsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -64,7 +66,7 @@ where
 
 impl<T> BsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -389,7 +391,7 @@ where
 
 impl<T> SparseArray<T> for BsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -420,7 +422,11 @@ where
     }
 
     fn dtype(&self) -> &str {
-        "float" // Placeholder; ideally would return the actual type
+        if T::IS_COMPLEX {
+            "complex"
+        } else {
+            "float"
+        }
     }
 
     fn to_array(&self) -> Array2<T> {
@@ -905,18 +911,18 @@ where
     }
 
     fn max(&self) -> T {
-        let mut max_val = T::neg_infinity();
+        let mut max_val = element_neg_infinity::<T>();
 
         for block in &self.data {
             for row in block {
                 for &val in row {
-                    max_val = max_val.max(val);
+                    max_val = element_max(max_val, val);
                 }
             }
         }
 
         // If no elements or all negative infinity, return zero
-        if max_val == T::neg_infinity() {
+        if max_val == element_neg_infinity::<T>() {
             T::zero()
         } else {
             max_val
@@ -924,7 +930,7 @@ where
     }
 
     fn min(&self) -> T {
-        let mut min_val = T::infinity();
+        let mut min_val = element_infinity::<T>();
         let mut has_nonzero = false;
 
         for block in &self.data {
@@ -932,7 +938,7 @@ where
                 for &val in row {
                     if !val.is_zero() {
                         has_nonzero = true;
-                        min_val = min_val.min(val);
+                        min_val = element_min(min_val, val);
                     }
                 }
             }
@@ -989,7 +995,7 @@ where
 // Implement Display for BsrArray for better debugging
 impl<T> fmt::Display for BsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
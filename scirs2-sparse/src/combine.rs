@@ -6,9 +6,11 @@
 
 use crate::coo_array::CooArray;
 use crate::csr_array::CsrArray;
+use crate::element::SparseElement;
 use crate::error::{SparseError, SparseResult};
 use crate::sparray::SparseArray;
 use num_traits::Float;
+use std::cmp::Ordering;
 use std::fmt::Debug;
 use std::ops::{Add, AddAssign, Div, Mul, Sub};
 
@@ -43,7 +45,7 @@ pub fn hstack<'a, T>(
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
     T: 'a
-        + Float
+        + SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -141,7 +143,7 @@ pub fn vstack<'a, T>(
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
     T: 'a
-        + Float
+        + SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -245,7 +247,7 @@ pub fn block_diag<'a, T>(
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
     T: 'a
-        + Float
+        + SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -338,7 +340,7 @@ pub fn tril<T>(
     format: &str,
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -414,7 +416,7 @@ pub fn triu<T>(
     format: &str,
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -496,7 +498,7 @@ pub fn kron<'a, T>(
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
     T: 'a
-        + Float
+        + SparseElement
         + Add<Output = T>
         + AddAssign
         + Sub<Output = T>
@@ -629,7 +631,7 @@ pub fn kronsum<'a, T>(
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
     T: 'a
-        + Float
+        + SparseElement
         + Add<Output = T>
         + AddAssign
         + Sub<Output = T>
@@ -676,7 +678,10 @@ where
         // Add connections within blocks from B ⊗ I_m
         for i in 0..n {
             for j in 0..n {
-                if i != j && (b.get(i, j) > T::zero() || b.get(j, i) > T::zero()) {
+                if i != j
+                    && (b.get(i, j).element_cmp(&T::zero()) == Ordering::Greater
+                        || b.get(j, i).element_cmp(&T::zero()) == Ordering::Greater)
+                {
                     for k in 0..m {
                         rows.push(i * m + k);
                         cols.push(j * m + k);
@@ -805,7 +810,7 @@ pub fn bmat<'a, T>(
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
     T: 'a
-        + Float
+        + SparseElement
         + Add<Output = T>
         + AddAssign
         + Sub<Output = T>
@@ -980,7 +985,7 @@ where
 // Helper function to check if a sparse array is an identity matrix
 fn is_identity_matrix<T>(array: &dyn SparseArray<T>) -> bool
 where
-    T: Float + Debug + Copy + 'static,
+    T: SparseElement + Debug + Copy + 'static,
 {
     let shape = array.shape();
 
@@ -1010,7 +1015,7 @@ where
         }
 
         // All diagonal elements must be 1
-        if (data[i] - T::one()).abs() > T::epsilon() {
+        if (data[i] - T::one()).modulus() > T::Real::epsilon() {
             return false;
         }
     }
@@ -4,7 +4,6 @@
 // including identity matrices, diagonal matrices, random arrays, etc.
 
 use ndarray::Array1;
-use num_traits::Float;
 use rand::seq::SliceRandom;
 use rand::{Rng, SeedableRng};
 use std::fmt::Debug;
@@ -13,6 +12,7 @@ use std::ops::{Add, Div, Mul, Sub};
 use crate::coo_array::CooArray;
 use crate::csr_array::CsrArray;
 use crate::dok_array::DokArray;
+use crate::element::SparseElement;
 use crate::error::{SparseError, SparseResult};
 use crate::lil_array::LilArray;
 use crate::sparray::SparseArray;
@@ -41,7 +41,7 @@ use crate::sparray::SparseArray;
 /// ```
 pub fn eye_array<T>(n: usize, format: &str) -> SparseResult<Box<dyn SparseArray<T>>>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -123,7 +123,7 @@ pub fn eye_array_k<T>(
     format: &str,
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -218,7 +218,7 @@ pub fn diags_array<T>(
     format: &str,
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -487,7 +487,7 @@ pub fn random_array<T>(
     format: &str,
 ) -> SparseResult<Box<dyn SparseArray<T>>>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
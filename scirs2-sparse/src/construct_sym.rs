@@ -4,11 +4,11 @@
 //! symmetric sparse matrices efficiently.
 
 use crate::construct;
+use crate::element::SparseElement;
 use crate::error::SparseResult;
 use crate::sym_coo::{SymCooArray, SymCooMatrix};
 use crate::sym_csr::{SymCsrArray, SymCsrMatrix};
 use crate::sym_sparray::SymSparseArray;
-use num_traits::Float;
 use std::fmt::Debug;
 use std::ops::{Add, Div, Mul, Sub};
 
@@ -39,7 +39,7 @@ use std::ops::{Add, Div, Mul, Sub};
 /// ```
 pub fn eye_sym_array<T>(n: usize, format: &str) -> SparseResult<Box<dyn SymSparseArray<T>>>
 where
-    T: Float
+    T: SparseElement
         + Debug
         + Copy
         + 'static
@@ -135,7 +135,7 @@ pub fn tridiagonal_sym_array<T>(
     format: &str,
 ) -> SparseResult<Box<dyn SymSparseArray<T>>>
 where
-    T: Float
+    T: SparseElement
         + Debug
         + Copy
         + 'static
@@ -305,7 +305,7 @@ pub fn banded_sym_array<T>(
     format: &str,
 ) -> SparseResult<Box<dyn SymSparseArray<T>>>
 where
-    T: Float
+    T: SparseElement
         + Debug
         + Copy
         + 'static
@@ -436,7 +436,7 @@ pub fn random_sym_array<T>(
     format: &str,
 ) -> SparseResult<Box<dyn SymSparseArray<T>>>
 where
-    T: Float
+    T: SparseElement
         + Debug
         + Copy
         + 'static
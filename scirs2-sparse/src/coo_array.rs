@@ -4,11 +4,12 @@
 // which is efficient for incrementally constructing a sparse array.
 
 use ndarray::{Array1, Array2, ArrayView1};
-use num_traits::Float;
+use std::cmp::Ordering;
 use std::fmt::{self, Debug};
 use std::ops::{Add, Div, Mul, Sub};
 
 use crate::csr_array::CsrArray;
+use crate::element::{element_infinity, element_neg_infinity, SparseElement};
 use crate::error::{SparseError, SparseResult};
 use crate::sparray::{SparseArray, SparseSum};
 
@@ -30,7 +31,7 @@ use crate::sparray::{SparseArray, SparseSum};
 #[derive(Clone)]
 pub struct CooArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -53,7 +54,7 @@ where
 
 impl<T> CooArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -203,7 +204,7 @@ where
 
         for i in 1..n {
             if self.row[i] == curr_row && self.col[i] == curr_col {
-                curr_sum = curr_sum + self.data[i];
+                curr_sum += self.data[i];
             } else {
                 if !curr_sum.is_zero() {
                     new_data.push(curr_sum);
@@ -231,7 +232,7 @@ where
 
 impl<T> SparseArray<T> for CooArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -249,7 +250,11 @@ where
     }
 
     fn dtype(&self) -> &str {
-        "float" // Placeholder
+        if T::IS_COMPLEX {
+            "complex"
+        } else {
+            "float"
+        }
     }
 
     fn to_array(&self) -> Array2<T> {
@@ -259,7 +264,7 @@ where
         for i in 0..self.data.len() {
             let r = self.row[i];
             let c = self.col[i];
-            result[[r, c]] = result[[r, c]] + self.data[i]; // Sum duplicates
+            result[[r, c]] += self.data[i]; // Sum duplicates
         }
 
         result
@@ -371,7 +376,7 @@ where
         for i in 0..self.data.len() {
             let row = self.row[i];
             let col = self.col[i];
-            result[row] = result[row] + self.data[i] * other[col];
+            result[row] += self.data[i] * other[col];
         }
 
         Ok(result)
@@ -401,7 +406,7 @@ where
         let mut sum = T::zero();
         for idx in 0..self.data.len() {
             if self.row[idx] == i && self.col[idx] == j {
-                sum = sum + self.data[idx];
+                sum += self.data[idx];
             }
         }
 
@@ -501,18 +506,20 @@ where
 
     fn max(&self) -> T {
         if self.data.is_empty() {
-            return T::neg_infinity();
+            return element_neg_infinity::<T>();
         }
 
         let mut max_val = self.data[0];
         for &val in self.data.iter().skip(1) {
-            if val > max_val {
+            if val.element_cmp(&max_val) == Ordering::Greater {
                 max_val = val;
             }
         }
 
         // Check if max_val is less than zero, as zeros aren't explicitly stored
-        if max_val < T::zero() && self.nnz() < self.shape.0 * self.shape.1 {
+        if max_val.element_cmp(&T::zero()) == Ordering::Less
+            && self.nnz() < self.shape.0 * self.shape.1
+        {
             max_val = T::zero();
         }
 
@@ -521,18 +528,20 @@ where
 
     fn min(&self) -> T {
         if self.data.is_empty() {
-            return T::infinity();
+            return element_infinity::<T>();
         }
 
         let mut min_val = self.data[0];
         for &val in self.data.iter().skip(1) {
-            if val < min_val {
+            if val.element_cmp(&min_val) == Ordering::Less {
                 min_val = val;
             }
         }
 
         // Check if min_val is greater than zero, as zeros aren't explicitly stored
-        if min_val > T::zero() && self.nnz() < self.shape.0 * self.shape.1 {
+        if min_val.element_cmp(&T::zero()) == Ordering::Greater
+            && self.nnz() < self.shape.0 * self.shape.1
+        {
             min_val = T::zero();
         }
 
@@ -568,7 +577,7 @@ where
 
                 for &(r, c, v) in triplets.iter().skip(1) {
                     if r == curr_row && c == curr_col {
-                        curr_sum = curr_sum + v;
+                        curr_sum += v;
                     } else {
                         if !curr_sum.is_zero() {
                             result_row.push(curr_row);
@@ -649,7 +658,7 @@ where
 
 impl<T> fmt::Debug for CooArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
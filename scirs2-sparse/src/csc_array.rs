@@ -4,12 +4,13 @@
 // which is efficient for column-wise operations.
 
 use ndarray::{Array1, Array2, ArrayView1};
-use num_traits::Float;
+use std::cmp::Ordering;
 use std::fmt::{self, Debug};
 use std::ops::{Add, Div, Mul, Sub};
 
 use crate::coo_array::CooArray;
 use crate::csr_array::CsrArray;
+use crate::element::{element_infinity, element_neg_infinity, SparseElement};
 use crate::error::{SparseError, SparseResult};
 use crate::sparray::{SparseArray, SparseSum};
 
@@ -31,7 +32,7 @@ use crate::sparray::{SparseArray, SparseSum};
 #[derive(Clone)]
 pub struct CscArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -54,7 +55,7 @@ where
 
 impl<T> CscArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -252,7 +253,7 @@ where
 
 impl<T> SparseArray<T> for CscArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -270,7 +271,11 @@ where
     }
 
     fn dtype(&self) -> &str {
-        "float" // Placeholder, ideally we would return the actual type
+        if T::IS_COMPLEX {
+            "complex"
+        } else {
+            "float"
+        }
     }
 
     fn to_array(&self) -> Array2<T> {
@@ -401,7 +406,7 @@ where
             if !val.is_zero() {
                 for idx in start..end {
                     let row = self.indices[idx];
-                    result[row] = result[row] + self.data[idx] * val;
+                    result[row] += self.data[idx] * val;
                 }
             }
         }
@@ -565,7 +570,7 @@ where
                 // Sum all elements
                 let mut sum = T::zero();
                 for &val in self.data.iter() {
-                    sum = sum + val;
+                    sum += val;
                 }
                 Ok(SparseSum::Scalar(sum))
             }
@@ -585,7 +590,7 @@ where
 
                     let mut col_sum = T::zero();
                     for idx in start..end {
-                        col_sum = col_sum + self.data[idx];
+                        col_sum += self.data[idx];
                     }
                     result.push(col_sum);
                 }
@@ -619,18 +624,20 @@ where
 
     fn max(&self) -> T {
         if self.data.is_empty() {
-            return T::neg_infinity();
+            return element_neg_infinity::<T>();
         }
 
         let mut max_val = self.data[0];
         for &val in self.data.iter().skip(1) {
-            if val > max_val {
+            if val.element_cmp(&max_val) == Ordering::Greater {
                 max_val = val;
             }
         }
 
         // Check if max_val is less than zero, as zeros aren't explicitly stored
-        if max_val < T::zero() && self.nnz() < self.shape.0 * self.shape.1 {
+        if max_val.element_cmp(&T::zero()) == Ordering::Less
+            && self.nnz() < self.shape.0 * self.shape.1
+        {
             max_val = T::zero();
         }
 
@@ -639,18 +646,20 @@ where
 
     fn min(&self) -> T {
         if self.data.is_empty() {
-            return T::infinity();
+            return element_infinity::<T>();
         }
 
         let mut min_val = self.data[0];
         for &val in self.data.iter().skip(1) {
-            if val < min_val {
+            if val.element_cmp(&min_val) == Ordering::Less {
                 min_val = val;
             }
         }
 
         // Check if min_val is greater than zero, as zeros aren't explicitly stored
-        if min_val > T::zero() && self.nnz() < self.shape.0 * self.shape.1 {
+        if min_val.element_cmp(&T::zero()) == Ordering::Greater
+            && self.nnz() < self.shape.0 * self.shape.1
+        {
             min_val = T::zero();
         }
 
@@ -737,7 +746,7 @@ where
 
 impl<T> fmt::Debug for CscArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -0,0 +1,147 @@
+//! Graph Laplacian
+
+use super::{check_square, GraphMatrix};
+use crate::error::SparseResult;
+use num_traits::Float;
+
+/// Laplacian matrix of a graph
+///
+/// The unnormalized Laplacian is `L = D - A`, where `A` is the adjacency
+/// matrix without self-loops and `D` the diagonal matrix of weighted node
+/// degrees. The normalized Laplacian is `I - D^{-1/2} A D^{-1/2}`, with zero
+/// rows and columns for isolated nodes. For directed graphs the degree is the
+/// column sum (in-degree) unless `use_out_degree` is set.
+///
+/// # Arguments
+///
+/// * `graph` - Square adjacency matrix with edge weights
+/// * `normed` - Whether to compute the symmetrically normalized Laplacian
+/// * `use_out_degree` - Use row sums instead of column sums as degrees
+///
+/// # Returns
+///
+/// * The Laplacian as a graph of the same type
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_sparse::csgraph::laplacian;
+/// use scirs2_sparse::csr::CsrMatrix;
+///
+/// // Path graph 0 - 1 - 2
+/// let graph = CsrMatrix::new(vec![1.0; 4], vec![0, 1, 1, 2], vec![1, 0, 2, 1], (3, 3)).unwrap();
+/// let lap = laplacian(&graph, false, false).unwrap();
+/// assert_eq!(lap.to_dense(), vec![
+///     vec![1.0, -1.0, 0.0],
+///     vec![-1.0, 2.0, -1.0],
+///     vec![0.0, -1.0, 1.0],
+/// ]);
+/// ```
+pub fn laplacian<F, G>(graph: &G, normed: bool, use_out_degree: bool) -> SparseResult<G>
+where
+    F: Float,
+    G: GraphMatrix<F>,
+{
+    let view = graph.graph_view();
+    let n = check_square(view)?;
+    let mut degree = vec![F::zero(); n];
+    for i in 0..n {
+        for p in view.indptr[i]..view.indptr[i + 1] {
+            let j = view.indices[p];
+            if i != j {
+                let node = if use_out_degree { i } else { j };
+                degree[node] = degree[node] + view.data[p];
+            }
+        }
+    }
+    let scale: Vec<F> = degree
+        .iter()
+        .map(|&d| {
+            if normed && d != F::zero() {
+                F::one() / d.abs().sqrt()
+            } else {
+                F::one()
+            }
+        })
+        .collect();
+
+    let mut indptr = Vec::with_capacity(n + 1);
+    let mut indices = Vec::with_capacity(view.data.len() + n);
+    let mut data = Vec::with_capacity(view.data.len() + n);
+    indptr.push(0);
+    for i in 0..n {
+        let diagonal = if normed {
+            if degree[i] != F::zero() {
+                F::one()
+            } else {
+                F::zero()
+            }
+        } else {
+            degree[i]
+        };
+        let mut diagonal_written = false;
+        for p in view.indptr[i]..view.indptr[i + 1] {
+            let j = view.indices[p];
+            if j == i {
+                continue;
+            }
+            if j > i && !diagonal_written {
+                indices.push(i);
+                data.push(diagonal);
+                diagonal_written = true;
+            }
+            indices.push(j);
+            data.push(-view.data[p] * scale[i] * scale[j]);
+        }
+        if !diagonal_written {
+            indices.push(i);
+            data.push(diagonal);
+        }
+        indptr.push(indices.len());
+    }
+    G::from_graph_csr(indptr, indices, data, view.shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csr::CsrMatrix;
+
+    #[test]
+    fn test_laplacian_variants() {
+        // Weighted directed graph with a self-loop and an isolated node
+        let rows = vec![0, 0, 1, 2, 2];
+        let cols = vec![0, 1, 2, 0, 1];
+        let data = vec![5.0, 2.0, 3.0, 4.0, 1.0];
+        let graph = CsrMatrix::new(data, rows, cols, (4, 4)).unwrap();
+
+        let lap = laplacian(&graph, false, true).unwrap().to_dense();
+        for row in &lap {
+            assert_eq!(row.iter().sum::<f64>(), 0.0);
+        }
+        assert_eq!(lap[2][2], 5.0);
+        assert_eq!(lap[3][3], 0.0);
+
+        let lap = laplacian(&graph, false, false).unwrap().to_dense();
+        let column_sums = lap.iter().fold(vec![0.0; 4], |mut sums, row| {
+            sums.iter_mut().zip(row).for_each(|(s, x)| *s += x);
+            sums
+        });
+        assert_eq!(column_sums, vec![0.0; 4]);
+
+        // Normalized Laplacian of an undirected weighted triangle
+        let rows = vec![0, 1, 0, 2, 1, 2];
+        let cols = vec![1, 0, 2, 0, 2, 1];
+        let data = vec![1.0, 1.0, 2.0, 2.0, 3.0, 3.0];
+        let graph = CsrMatrix::new(data, rows, cols, (3, 3)).unwrap();
+        let lap = laplacian(&graph, true, false).unwrap().to_dense();
+        let degree = [3.0f64, 4.0, 5.0];
+        assert_eq!(lap[0][0], 1.0);
+        assert!((lap[1][2] + 3.0 / (degree[1] * degree[2]).sqrt()).abs() < 1e-15);
+        // sqrt(D) 1 is in the null space
+        for row in &lap {
+            let value: f64 = row.iter().zip(&degree).map(|(l, d)| l * d.sqrt()).sum();
+            assert!(value.abs() < 1e-14);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Compressed sparse graph routines
+//!
+//! This module provides graph algorithms that operate directly on sparse
+//! adjacency matrices, similar to SciPy's `scipy.sparse.csgraph`. The stored
+//! entry `(i, j)` of the adjacency matrix is an edge from node `i` to node
+//! `j` whose weight is the stored value; entries that are not stored are not
+//! edges. Algorithms taking a `directed` flag treat the matrix as undirected
+//! when it is `false`, so that every edge can be traversed in both directions.
+//!
+//! Both [`CsrMatrix`] and [`CsrArray`] can be used as graphs through the
+//! [`GraphMatrix`] trait; routines that return a graph (minimum spanning
+//! tree, Laplacian) return the same type as their input.
+//!
+//! ## Overview
+//!
+//! * Connected and strongly connected components
+//! * Breadth-first and depth-first orders
+//! * Shortest paths (Dijkstra, Bellman–Ford, Johnson, Floyd–Warshall)
+//! * Minimum spanning tree
+//! * Graph Laplacian
+//! * Maximum bipartite matching
+//! * Reverse Cuthill–McKee reordering
+//!
+//! ## Examples
+//!
+//! ```
+//! use scirs2_sparse::csgraph::{connected_components, dijkstra, Connection};
+//! use scirs2_sparse::csr_array::CsrArray;
+//!
+//! // Path 0 -> 1 -> 2 and an isolated node 3
+//! let graph = CsrArray::from_triplets(&[0, 1], &[1, 2], &[1.0, 2.0], (4, 4), true).unwrap();
+//!
+//! let (count, labels) = connected_components(&graph, false, Connection::Weak).unwrap();
+//! assert_eq!(count, 2);
+//! assert_eq!(labels[0], labels[2]);
+//!
+//! let paths = dijkstra(&graph, true, Some(&[0])).unwrap();
+//! assert_eq!(paths.distances[[0, 2]], 3.0);
+//! assert_eq!(paths.path(0, 2), Some(vec![0, 1, 2]));
+//! ```
+
+mod laplacian;
+mod matching;
+mod reordering;
+mod shortest_path;
+mod spanning_tree;
+mod traversal;
+
+pub use laplacian::laplacian;
+pub use matching::{maximum_bipartite_matching, MatchingPermutation};
+pub use reordering::reverse_cuthill_mckee;
+pub use shortest_path::{
+    bellman_ford, dijkstra, floyd_warshall, johnson, shortest_path, ShortestPathMethod,
+    ShortestPaths,
+};
+pub use spanning_tree::minimum_spanning_tree;
+pub use traversal::{breadth_first_order, connected_components, depth_first_order, Connection};
+
+use crate::csr::CsrMatrix;
+use crate::csr_array::CsrArray;
+use crate::element::SparseElement;
+use crate::error::{SparseError, SparseResult};
+use crate::sparray::SparseArray;
+use num_traits::Float;
+use std::fmt::Debug;
+
+/// Borrowed compressed sparse row structure of an adjacency matrix
+#[derive(Debug, Clone, Copy)]
+pub struct GraphView<'a, F> {
+    /// Shape of the adjacency matrix
+    pub shape: (usize, usize),
+    /// Row pointers
+    pub indptr: &'a [usize],
+    /// Column indices (edge targets)
+    pub indices: &'a [usize],
+    /// Edge weights
+    pub data: &'a [F],
+}
+
+/// Sparse matrix types that can be used as graphs
+pub trait GraphMatrix<F: Float>: Sized {
+    /// Borrow the compressed sparse row structure
+    fn graph_view(&self) -> GraphView<'_, F>;
+
+    /// Build a matrix of this type from compressed sparse row components
+    fn from_graph_csr(
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<F>,
+        shape: (usize, usize),
+    ) -> SparseResult<Self>;
+}
+
+impl<F: Float> GraphMatrix<F> for CsrMatrix<F> {
+    fn graph_view(&self) -> GraphView<'_, F> {
+        GraphView {
+            shape: self.shape(),
+            indptr: &self.indptr,
+            indices: &self.indices,
+            data: &self.data,
+        }
+    }
+
+    fn from_graph_csr(
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<F>,
+        shape: (usize, usize),
+    ) -> SparseResult<Self> {
+        CsrMatrix::from_raw_csr(data, indptr, indices, shape)
+    }
+}
+
+impl<F: Float + SparseElement> GraphMatrix<F> for CsrArray<F> {
+    fn graph_view(&self) -> GraphView<'_, F> {
+        // Owned one-dimensional arrays are always contiguous
+        GraphView {
+            shape: self.shape(),
+            indptr: self.get_indptr().as_slice().unwrap(),
+            indices: self.get_indices().as_slice().unwrap(),
+            data: self.get_data().as_slice().unwrap(),
+        }
+    }
+
+    fn from_graph_csr(
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<F>,
+        shape: (usize, usize),
+    ) -> SparseResult<Self> {
+        CsrArray::new(data.into(), indices.into(), indptr.into(), shape)
+    }
+}
+
+/// Adjacency lists in compressed form, symmetrized for undirected graphs
+pub(crate) struct Adjacency<F> {
+    pub(crate) n: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    weights: Vec<F>,
+}
+
+impl<F: Float> Adjacency<F> {
+    /// Build the adjacency of a square graph
+    pub(crate) fn new(graph: GraphView<'_, F>, directed: bool) -> SparseResult<Self> {
+        let n = check_square(graph)?;
+        if directed {
+            return Ok(Self {
+                n,
+                indptr: graph.indptr.to_vec(),
+                indices: graph.indices.to_vec(),
+                weights: graph.data.to_vec(),
+            });
+        }
+        // Every edge i -> j also becomes j -> i
+        let mut count = vec![0usize; n + 1];
+        for i in 0..n {
+            for &j in &graph.indices[graph.indptr[i]..graph.indptr[i + 1]] {
+                count[i + 1] += 1;
+                count[j + 1] += 1;
+            }
+        }
+        for i in 0..n {
+            count[i + 1] += count[i];
+        }
+        let indptr = count.clone();
+        let mut next = count;
+        let mut indices = vec![0; indptr[n]];
+        let mut weights = vec![F::zero(); indptr[n]];
+        for i in 0..n {
+            for p in graph.indptr[i]..graph.indptr[i + 1] {
+                let j = graph.indices[p];
+                for (from, to) in [(i, j), (j, i)] {
+                    indices[next[from]] = to;
+                    weights[next[from]] = graph.data[p];
+                    next[from] += 1;
+                }
+            }
+        }
+        Ok(Self {
+            n,
+            indptr,
+            indices,
+            weights,
+        })
+    }
+
+    /// Outgoing edges of node `i` as `(target, weight)`
+    pub(crate) fn edges(&self, i: usize) -> impl Iterator<Item = (usize, F)> + '_ {
+        let range = self.indptr[i]..self.indptr[i + 1];
+        self.indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.weights[range].iter().copied())
+    }
+
+    /// The `k`-th outgoing edge of node `i`, if it has that many
+    pub(crate) fn edge(&self, i: usize, k: usize) -> Option<(usize, F)> {
+        let p = self.indptr[i] + k;
+        (p < self.indptr[i + 1]).then(|| (self.indices[p], self.weights[p]))
+    }
+}
+
+/// Check that the adjacency matrix is square and return the number of nodes
+pub(crate) fn check_square<F>(graph: GraphView<'_, F>) -> SparseResult<usize> {
+    let (rows, cols) = graph.shape;
+    if rows != cols {
+        return Err(SparseError::ValueError(format!(
+            "Graph adjacency matrix must be square, got {}x{}",
+            rows, cols
+        )));
+    }
+    Ok(rows)
+}
+
+/// Check that a node index is valid
+pub(crate) fn check_node(node: usize, n: usize) -> SparseResult<()> {
+    if node >= n {
+        return Err(SparseError::ValueError(format!(
+            "Node {} is out of range for a graph with {} nodes",
+            node, n
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,492 @@
+//! Shortest path algorithms
+
+use super::{check_node, Adjacency, GraphMatrix};
+use crate::error::{SparseError, SparseResult};
+use ndarray::Array2;
+use num_traits::Float;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Algorithm used by [`shortest_path`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortestPathMethod {
+    /// Johnson's algorithm for negative weights, otherwise Floyd–Warshall
+    /// for dense all-pairs problems and Dijkstra's algorithm for the rest
+    Auto,
+    /// Dijkstra's algorithm with a binary heap; weights must be non-negative
+    Dijkstra,
+    /// Bellman–Ford algorithm; handles negative weights
+    BellmanFord,
+    /// Johnson's algorithm; handles negative weights
+    Johnson,
+    /// Floyd–Warshall algorithm for all pairs
+    FloydWarshall,
+}
+
+/// Distances and predecessors computed by a shortest path algorithm
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<F> {
+    /// Source node of each row
+    pub sources: Vec<usize>,
+    /// `distances[[r, j]]` is the length of the shortest path from
+    /// `sources[r]` to `j` (infinite if `j` is unreachable)
+    pub distances: Array2<F>,
+    /// `predecessors[[r, j]]` is the node before `j` on the shortest path
+    /// from `sources[r]` (`None` for the source and unreachable nodes)
+    pub predecessors: Array2<Option<usize>>,
+}
+
+impl<F: Float> ShortestPaths<F> {
+    /// Reconstruct the shortest path from `sources[row]` to `target`
+    ///
+    /// Returns `None` if `target` is unreachable.
+    pub fn path(&self, row: usize, target: usize) -> Option<Vec<usize>> {
+        if !self.distances[[row, target]].is_finite() {
+            return None;
+        }
+        let mut path = vec![target];
+        let mut node = target;
+        while let Some(previous) = self.predecessors[[row, node]] {
+            path.push(previous);
+            node = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    fn new(sources: Vec<usize>, n: usize) -> Self {
+        let rows = sources.len();
+        Self {
+            sources,
+            distances: Array2::from_elem((rows, n), F::infinity()),
+            predecessors: Array2::from_elem((rows, n), None),
+        }
+    }
+}
+
+/// Compute shortest paths with the chosen algorithm
+///
+/// # Arguments
+///
+/// * `graph` - Square adjacency matrix with edge weights
+/// * `method` - Algorithm to use
+/// * `directed` - Whether edges are directed
+/// * `indices` - Source nodes (all nodes if `None`)
+///
+/// # Returns
+///
+/// * Distances and predecessors, one row per source
+pub fn shortest_path<F, G>(
+    graph: &G,
+    method: ShortestPathMethod,
+    directed: bool,
+    indices: Option<&[usize]>,
+) -> SparseResult<ShortestPaths<F>>
+where
+    F: Float,
+    G: GraphMatrix<F>,
+{
+    let method = match method {
+        ShortestPathMethod::Auto => {
+            let view = graph.graph_view();
+            let n = view.shape.0;
+            if view.data.iter().any(|&w| w < F::zero()) {
+                ShortestPathMethod::Johnson
+            } else if indices.is_none() && view.data.len() > n * n / 4 {
+                ShortestPathMethod::FloydWarshall
+            } else {
+                ShortestPathMethod::Dijkstra
+            }
+        }
+        method => method,
+    };
+    match method {
+        ShortestPathMethod::Dijkstra | ShortestPathMethod::Auto => {
+            dijkstra(graph, directed, indices)
+        }
+        ShortestPathMethod::BellmanFord => bellman_ford(graph, directed, indices),
+        ShortestPathMethod::Johnson => johnson(graph, directed, indices),
+        ShortestPathMethod::FloydWarshall => {
+            let paths = floyd_warshall(graph, directed)?;
+            match indices {
+                Some(indices) => {
+                    for &i in indices {
+                        check_node(i, paths.sources.len())?;
+                    }
+                    Ok(ShortestPaths {
+                        sources: indices.to_vec(),
+                        distances: paths.distances.select(ndarray::Axis(0), indices),
+                        predecessors: paths.predecessors.select(ndarray::Axis(0), indices),
+                    })
+                }
+                None => Ok(paths),
+            }
+        }
+    }
+}
+
+/// Resolve the source nodes and validate them
+fn sources(indices: Option<&[usize]>, n: usize) -> SparseResult<Vec<usize>> {
+    match indices {
+        Some(indices) => {
+            for &i in indices {
+                check_node(i, n)?;
+            }
+            Ok(indices.to_vec())
+        }
+        None => Ok((0..n).collect()),
+    }
+}
+
+/// Heap entry ordered so that `BinaryHeap` pops the smallest distance
+struct HeapEntry<F> {
+    distance: F,
+    node: usize,
+}
+
+impl<F: Float> PartialEq for HeapEntry<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<F: Float> Eq for HeapEntry<F> {}
+
+impl<F: Float> PartialOrd for HeapEntry<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: Float> Ord for HeapEntry<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+/// Dijkstra's algorithm from every source on edges reweighted by `weight`
+fn dijkstra_rows<F: Float>(
+    adjacency: &Adjacency<F>,
+    paths: &mut ShortestPaths<F>,
+    weight: impl Fn(usize, usize, F) -> F,
+) {
+    let n = adjacency.n;
+    let mut done = vec![false; n];
+    let mut heap = BinaryHeap::new();
+    for (row, &source) in paths.sources.clone().iter().enumerate() {
+        done.iter_mut().for_each(|d| *d = false);
+        paths.distances[[row, source]] = F::zero();
+        heap.push(HeapEntry {
+            distance: F::zero(),
+            node: source,
+        });
+        while let Some(HeapEntry { distance, node }) = heap.pop() {
+            if done[node] {
+                continue;
+            }
+            done[node] = true;
+            for (j, w) in adjacency.edges(node) {
+                let candidate = distance + weight(node, j, w);
+                if candidate < paths.distances[[row, j]] {
+                    paths.distances[[row, j]] = candidate;
+                    paths.predecessors[[row, j]] = Some(node);
+                    heap.push(HeapEntry {
+                        distance: candidate,
+                        node: j,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Shortest paths by Dijkstra's algorithm
+///
+/// # Arguments
+///
+/// * `graph` - Square adjacency matrix with non-negative edge weights
+/// * `directed` - Whether edges are directed
+/// * `indices` - Source nodes (all nodes if `None`)
+///
+/// # Returns
+///
+/// * Distances and predecessors, one row per source
+pub fn dijkstra<F, G>(
+    graph: &G,
+    directed: bool,
+    indices: Option<&[usize]>,
+) -> SparseResult<ShortestPaths<F>>
+where
+    F: Float,
+    G: GraphMatrix<F>,
+{
+    let adjacency = Adjacency::new(graph.graph_view(), directed)?;
+    if graph.graph_view().data.iter().any(|&w| w < F::zero()) {
+        return Err(SparseError::ValueError(
+            "Dijkstra's algorithm requires non-negative edge weights; use Johnson or Bellman-Ford"
+                .to_string(),
+        ));
+    }
+    let mut paths = ShortestPaths::new(sources(indices, adjacency.n)?, adjacency.n);
+    dijkstra_rows(&adjacency, &mut paths, |_, _, w| w);
+    Ok(paths)
+}
+
+/// Single-source Bellman–Ford relaxation; returns `false` on a negative cycle
+fn bellman_ford_row<F: Float>(
+    adjacency: &Adjacency<F>,
+    distances: &mut [F],
+    predecessors: &mut [Option<usize>],
+) -> bool {
+    let n = adjacency.n;
+    for _ in 0..n {
+        let mut changed = false;
+        for node in 0..n {
+            if !distances[node].is_finite() {
+                continue;
+            }
+            for (j, w) in adjacency.edges(node) {
+                let candidate = distances[node] + w;
+                if candidate < distances[j] {
+                    distances[j] = candidate;
+                    predecessors[j] = Some(node);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return true;
+        }
+    }
+    false
+}
+
+/// Shortest paths by the Bellman–Ford algorithm
+///
+/// Negative edge weights are allowed; a negative cycle (including any
+/// negative edge of an undirected graph) is an error.
+///
+/// # Arguments
+///
+/// * `graph` - Square adjacency matrix with edge weights
+/// * `directed` - Whether edges are directed
+/// * `indices` - Source nodes (all nodes if `None`)
+///
+/// # Returns
+///
+/// * Distances and predecessors, one row per source
+pub fn bellman_ford<F, G>(
+    graph: &G,
+    directed: bool,
+    indices: Option<&[usize]>,
+) -> SparseResult<ShortestPaths<F>>
+where
+    F: Float,
+    G: GraphMatrix<F>,
+{
+    let adjacency = Adjacency::new(graph.graph_view(), directed)?;
+    check_undirected_weights(graph, directed)?;
+    let n = adjacency.n;
+    let mut paths = ShortestPaths::new(sources(indices, n)?, n);
+    for (row, &source) in paths.sources.clone().iter().enumerate() {
+        let mut distances = vec![F::infinity(); n];
+        let mut predecessors = vec![None; n];
+        distances[source] = F::zero();
+        if !bellman_ford_row(&adjacency, &mut distances, &mut predecessors) {
+            return Err(negative_cycle());
+        }
+        for j in 0..n {
+            paths.distances[[row, j]] = distances[j];
+            paths.predecessors[[row, j]] = predecessors[j];
+        }
+    }
+    Ok(paths)
+}
+
+/// Shortest paths by Johnson's algorithm
+///
+/// The edges are reweighted with potentials from a Bellman–Ford pass so that
+/// Dijkstra's algorithm can be used from every source, which is faster than
+/// Bellman–Ford on sparse graphs with negative weights.
+///
+/// # Arguments
+///
+/// * `graph` - Square adjacency matrix with edge weights
+/// * `directed` - Whether edges are directed
+/// * `indices` - Source nodes (all nodes if `None`)
+///
+/// # Returns
+///
+/// * Distances and predecessors, one row per source
+pub fn johnson<F, G>(
+    graph: &G,
+    directed: bool,
+    indices: Option<&[usize]>,
+) -> SparseResult<ShortestPaths<F>>
+where
+    F: Float,
+    G: GraphMatrix<F>,
+{
+    let adjacency = Adjacency::new(graph.graph_view(), directed)?;
+    check_undirected_weights(graph, directed)?;
+    let n = adjacency.n;
+    // Potentials: distances from a virtual node joined to every node by a zero edge
+    let mut potential = vec![F::zero(); n];
+    let mut predecessors = vec![None; n];
+    if !bellman_ford_row(&adjacency, &mut potential, &mut predecessors) {
+        return Err(negative_cycle());
+    }
+    let mut paths = ShortestPaths::new(sources(indices, n)?, n);
+    dijkstra_rows(&adjacency, &mut paths, |i, j, w| {
+        (w + potential[i] - potential[j]).max(F::zero())
+    });
+    for (row, &source) in paths.sources.iter().enumerate() {
+        for j in 0..n {
+            let d = paths.distances[[row, j]];
+            if d.is_finite() {
+                paths.distances[[row, j]] = d - potential[source] + potential[j];
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// All-pairs shortest paths by the Floyd–Warshall algorithm
+///
+/// # Arguments
+///
+/// * `graph` - Square adjacency matrix with edge weights
+/// * `directed` - Whether edges are directed
+///
+/// # Returns
+///
+/// * Distances and predecessors between all pairs of nodes
+pub fn floyd_warshall<F, G>(graph: &G, directed: bool) -> SparseResult<ShortestPaths<F>>
+where
+    F: Float,
+    G: GraphMatrix<F>,
+{
+    let adjacency = Adjacency::new(graph.graph_view(), directed)?;
+    check_undirected_weights(graph, directed)?;
+    let n = adjacency.n;
+    let mut paths = ShortestPaths::new((0..n).collect(), n);
+    let (dist, pred) = (&mut paths.distances, &mut paths.predecessors);
+    for i in 0..n {
+        dist[[i, i]] = F::zero();
+        for (j, w) in adjacency.edges(i) {
+            if i != j && w < dist[[i, j]] {
+                dist[[i, j]] = w;
+                pred[[i, j]] = Some(i);
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            let dik = dist[[i, k]];
+            if !dik.is_finite() {
+                continue;
+            }
+            for j in 0..n {
+                let candidate = dik + dist[[k, j]];
+                if candidate < dist[[i, j]] {
+                    dist[[i, j]] = candidate;
+                    pred[[i, j]] = pred[[k, j]];
+                }
+            }
+        }
+    }
+    if (0..n).any(|i| dist[[i, i]] < F::zero()) {
+        return Err(negative_cycle());
+    }
+    Ok(paths)
+}
+
+/// Negative weights on an undirected graph always form a negative cycle
+fn check_undirected_weights<F: Float, G: GraphMatrix<F>>(
+    graph: &G,
+    directed: bool,
+) -> SparseResult<()> {
+    if !directed && graph.graph_view().data.iter().any(|&w| w < F::zero()) {
+        return Err(negative_cycle());
+    }
+    Ok(())
+}
+
+fn negative_cycle() -> SparseError {
+    SparseError::ValueError("Negative cycle detected in the graph".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csr::CsrMatrix;
+
+    #[test]
+    fn test_shortest_paths_agree() {
+        // Directed graph with a negative edge but no negative cycle
+        let rows = vec![0, 0, 1, 1, 2, 3, 3, 4];
+        let cols = vec![1, 2, 2, 3, 3, 4, 0, 1];
+        let data = vec![4.0, 1.0, -2.0, 5.0, 9.0, 3.0, 2.0, 1.0];
+        let graph = CsrMatrix::new(data, rows, cols, (6, 6)).unwrap();
+
+        let reference = floyd_warshall(&graph, true).unwrap();
+        assert_eq!(reference.distances[[0, 3]], 9.0);
+        assert_eq!(reference.distances[[4, 2]], -1.0);
+        assert_eq!(reference.path(0, 4), Some(vec![0, 1, 3, 4]));
+        assert!(reference.distances[[0, 5]].is_infinite());
+        assert_eq!(reference.path(0, 5), None);
+
+        for method in [ShortestPathMethod::BellmanFord, ShortestPathMethod::Johnson] {
+            let paths = shortest_path(&graph, method, true, Some(&[0, 4])).unwrap();
+            for (row, &source) in [0, 4].iter().enumerate() {
+                for j in 0..6 {
+                    assert_eq!(paths.distances[[row, j]], reference.distances[[source, j]]);
+                    assert_eq!(paths.path(row, j), reference.path(source, j));
+                }
+            }
+        }
+        assert!(dijkstra(&graph, true, None).is_err());
+
+        // Negative cycle 0 -> 1 -> 0
+        let cycle = CsrMatrix::new(vec![1.0, -2.0], vec![0, 1], vec![1, 0], (2, 2)).unwrap();
+        assert!(bellman_ford(&cycle, true, None).is_err());
+        assert!(johnson(&cycle, true, None).is_err());
+        assert!(floyd_warshall(&cycle, true).is_err());
+    }
+
+    #[test]
+    fn test_dijkstra_undirected() {
+        // Grid graph: shortest distances are Manhattan distances
+        let side = 6;
+        let n = side * side;
+        let (mut rows, mut cols) = (Vec::new(), Vec::new());
+        for r in 0..side {
+            for c in 0..side {
+                let i = r * side + c;
+                if c + 1 < side {
+                    rows.push(i);
+                    cols.push(i + 1);
+                }
+                if r + 1 < side {
+                    rows.push(i);
+                    cols.push(i + side);
+                }
+            }
+        }
+        let graph = CsrMatrix::new(vec![1.0; rows.len()], rows, cols, (n, n)).unwrap();
+        let paths = dijkstra(&graph, false, None).unwrap();
+        let all = shortest_path(&graph, ShortestPathMethod::FloydWarshall, false, None).unwrap();
+        for i in 0..n {
+            for j in 0..n {
+                let manhattan = (i / side).abs_diff(j / side) + (i % side).abs_diff(j % side);
+                assert_eq!(paths.distances[[i, j]], manhattan as f64);
+                assert_eq!(all.distances[[i, j]], manhattan as f64);
+            }
+        }
+        assert_eq!(paths.path(n - 1, 0).unwrap().len(), 2 * side - 1);
+    }
+}
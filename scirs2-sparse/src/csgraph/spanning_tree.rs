@@ -0,0 +1,118 @@
+//! Minimum spanning tree
+
+use super::{check_square, GraphMatrix};
+use crate::error::SparseResult;
+use num_traits::Float;
+use std::cmp::Ordering;
+
+/// Minimum spanning tree (or forest) of an undirected graph
+///
+/// The graph is treated as undirected: the stored entries `(i, j)` and
+/// `(j, i)` are both candidate edges between `i` and `j`. Kruskal's
+/// algorithm keeps the lightest edges that do not close a cycle; each kept
+/// edge is stored at the position it occupies in the input. For
+/// disconnected graphs the result is a minimum spanning forest.
+///
+/// # Arguments
+///
+/// * `graph` - Square adjacency matrix with edge weights
+///
+/// # Returns
+///
+/// * A graph of the same type holding the `n - c` tree edges, where `c` is
+///   the number of connected components
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_sparse::csgraph::minimum_spanning_tree;
+/// use scirs2_sparse::csr::CsrMatrix;
+///
+/// // Triangle with weights 1, 2 and 3
+/// let graph = CsrMatrix::new(vec![1.0, 3.0, 2.0], vec![0, 0, 1], vec![1, 2, 2], (3, 3)).unwrap();
+/// let tree = minimum_spanning_tree(&graph).unwrap();
+/// assert_eq!(tree.nnz(), 2);
+/// assert_eq!(tree.data.iter().sum::<f64>(), 3.0);
+/// ```
+pub fn minimum_spanning_tree<F, G>(graph: &G) -> SparseResult<G>
+where
+    F: Float,
+    G: GraphMatrix<F>,
+{
+    let view = graph.graph_view();
+    let n = check_square(view)?;
+    let mut edges: Vec<(usize, usize, F)> = Vec::with_capacity(view.data.len());
+    for i in 0..n {
+        for p in view.indptr[i]..view.indptr[i + 1] {
+            if view.indices[p] != i {
+                edges.push((i, view.indices[p], view.data[p]));
+            }
+        }
+    }
+    // Stable sort keeps ties in storage order
+    edges.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal));
+
+    let mut parent: Vec<usize> = (0..n).collect();
+    let mut rank = vec![0u8; n];
+    let mut tree = Vec::with_capacity(n.saturating_sub(1));
+    for (i, j, w) in edges {
+        let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+        if ri == rj {
+            continue;
+        }
+        match rank[ri].cmp(&rank[rj]) {
+            Ordering::Less => parent[ri] = rj,
+            Ordering::Greater => parent[rj] = ri,
+            Ordering::Equal => {
+                parent[rj] = ri;
+                rank[ri] += 1;
+            }
+        }
+        tree.push((i, j, w));
+        if tree.len() + 1 == n {
+            break;
+        }
+    }
+
+    tree.sort_by_key(|&(i, j, _)| (i, j));
+    let mut indptr = vec![0; n + 1];
+    for &(i, _, _) in &tree {
+        indptr[i + 1] += 1;
+    }
+    for i in 0..n {
+        indptr[i + 1] += indptr[i];
+    }
+    let indices = tree.iter().map(|&(_, j, _)| j).collect();
+    let data = tree.iter().map(|&(_, _, w)| w).collect();
+    G::from_graph_csr(indptr, indices, data, view.shape)
+}
+
+/// Union–find root with path halving
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csr_array::CsrArray;
+    use crate::sparray::SparseArray;
+
+    #[test]
+    fn test_minimum_spanning_forest() {
+        // Two components: a weighted square with a diagonal, and an edge
+        let rows = [0, 1, 2, 3, 0, 4];
+        let cols = [1, 2, 3, 0, 2, 5];
+        let data = [1.0, 4.0, 2.0, 3.0, 1.5, 7.0];
+        let graph = CsrArray::from_triplets(&rows, &cols, &data, (6, 6), false).unwrap();
+        let tree = minimum_spanning_tree(&graph).unwrap();
+        assert_eq!(tree.nnz(), 4);
+        assert_eq!(tree.get_data().sum(), 1.0 + 1.5 + 2.0 + 7.0);
+        assert_eq!(tree.get(0, 2), 1.5);
+        assert_eq!(tree.get(1, 2), 0.0);
+    }
+}
@@ -0,0 +1,254 @@
+//! Graph traversal and connected components
+
+use super::{check_node, Adjacency, GraphMatrix};
+use crate::error::SparseResult;
+use num_traits::Float;
+use std::collections::VecDeque;
+
+/// Kind of connectivity used for directed graphs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connection {
+    /// Nodes are connected if a path exists ignoring edge directions
+    Weak,
+    /// Nodes are connected if paths exist in both directions
+    Strong,
+}
+
+/// Find the connected components of a graph
+///
+/// # Arguments
+///
+/// * `graph` - Square adjacency matrix
+/// * `directed` - Whether edges are directed; undirected graphs ignore `connection`
+/// * `connection` - Weak or strong connectivity for directed graphs
+///
+/// # Returns
+///
+/// * The number of components and the component label of each node
+pub fn connected_components<F, G>(
+    graph: &G,
+    directed: bool,
+    connection: Connection,
+) -> SparseResult<(usize, Vec<usize>)>
+where
+    F: Float,
+    G: GraphMatrix<F>,
+{
+    if directed && connection == Connection::Strong {
+        let adjacency = Adjacency::new(graph.graph_view(), true)?;
+        return Ok(strong_components(&adjacency));
+    }
+    let adjacency = Adjacency::new(graph.graph_view(), false)?;
+    let n = adjacency.n;
+    let mut labels = vec![usize::MAX; n];
+    let mut count = 0;
+    let mut queue = VecDeque::new();
+    for start in 0..n {
+        if labels[start] != usize::MAX {
+            continue;
+        }
+        labels[start] = count;
+        queue.push_back(start);
+        while let Some(node) = queue.pop_front() {
+            for (j, _) in adjacency.edges(node) {
+                if labels[j] == usize::MAX {
+                    labels[j] = count;
+                    queue.push_back(j);
+                }
+            }
+        }
+        count += 1;
+    }
+    Ok((count, labels))
+}
+
+/// Strongly connected components by an iterative version of Tarjan's algorithm
+fn strong_components<F: Float>(adjacency: &Adjacency<F>) -> (usize, Vec<usize>) {
+    let n = adjacency.n;
+    let mut index = vec![usize::MAX; n];
+    let mut lowlink = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut labels = vec![usize::MAX; n];
+    let mut count = 0;
+    let mut next_index = 0;
+    // Call stack of (node, position in its edge list)
+    let mut calls: Vec<(usize, usize)> = Vec::new();
+
+    for root in 0..n {
+        if index[root] != usize::MAX {
+            continue;
+        }
+        calls.push((root, 0));
+        index[root] = next_index;
+        lowlink[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&mut (node, ref mut position)) = calls.last_mut() {
+            if let Some((j, _)) = adjacency.edge(node, *position) {
+                *position += 1;
+                if index[j] == usize::MAX {
+                    index[j] = next_index;
+                    lowlink[j] = next_index;
+                    next_index += 1;
+                    stack.push(j);
+                    on_stack[j] = true;
+                    calls.push((j, 0));
+                } else if on_stack[j] {
+                    lowlink[node] = lowlink[node].min(index[j]);
+                }
+                continue;
+            }
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[node]);
+            }
+            if lowlink[node] == index[node] {
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    labels[member] = count;
+                    if member == node {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+        }
+    }
+    (count, labels)
+}
+
+/// Nodes in breadth-first order from a starting node
+///
+/// # Arguments
+///
+/// * `graph` - Square adjacency matrix
+/// * `start` - Starting node
+/// * `directed` - Whether edges are directed
+///
+/// # Returns
+///
+/// * The reachable nodes in the order they are visited, and the predecessor
+///   of every node in the search tree (`None` for the start and unreached nodes)
+pub fn breadth_first_order<F, G>(
+    graph: &G,
+    start: usize,
+    directed: bool,
+) -> SparseResult<(Vec<usize>, Vec<Option<usize>>)>
+where
+    F: Float,
+    G: GraphMatrix<F>,
+{
+    let adjacency = Adjacency::new(graph.graph_view(), directed)?;
+    check_node(start, adjacency.n)?;
+    let mut predecessors = vec![None; adjacency.n];
+    let mut visited = vec![false; adjacency.n];
+    let mut order = vec![start];
+    visited[start] = true;
+    let mut head = 0;
+    while head < order.len() {
+        let node = order[head];
+        head += 1;
+        for (j, _) in adjacency.edges(node) {
+            if !visited[j] {
+                visited[j] = true;
+                predecessors[j] = Some(node);
+                order.push(j);
+            }
+        }
+    }
+    Ok((order, predecessors))
+}
+
+/// Nodes in depth-first order from a starting node
+///
+/// Neighbours are explored in the order they are stored, as a recursive
+/// depth-first search would.
+///
+/// # Arguments
+///
+/// * `graph` - Square adjacency matrix
+/// * `start` - Starting node
+/// * `directed` - Whether edges are directed
+///
+/// # Returns
+///
+/// * The reachable nodes in preorder, and the predecessor of every node in
+///   the search tree (`None` for the start and unreached nodes)
+pub fn depth_first_order<F, G>(
+    graph: &G,
+    start: usize,
+    directed: bool,
+) -> SparseResult<(Vec<usize>, Vec<Option<usize>>)>
+where
+    F: Float,
+    G: GraphMatrix<F>,
+{
+    let adjacency = Adjacency::new(graph.graph_view(), directed)?;
+    check_node(start, adjacency.n)?;
+    let mut predecessors = vec![None; adjacency.n];
+    let mut visited = vec![false; adjacency.n];
+    let mut order = vec![start];
+    visited[start] = true;
+    let mut calls: Vec<(usize, usize)> = vec![(start, 0)];
+    while let Some(&mut (node, ref mut position)) = calls.last_mut() {
+        match adjacency.edge(node, *position) {
+            Some((j, _)) => {
+                *position += 1;
+                if !visited[j] {
+                    visited[j] = true;
+                    predecessors[j] = Some(node);
+                    order.push(j);
+                    calls.push((j, 0));
+                }
+            }
+            None => {
+                calls.pop();
+            }
+        }
+    }
+    Ok((order, predecessors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csr::CsrMatrix;
+
+    #[test]
+    fn test_components_and_traversal() {
+        // Cycle 0 -> 1 -> 2 -> 0, edge 2 -> 3, cycle 3 <-> 4, isolated 5
+        let rows = vec![0, 1, 2, 2, 3, 4];
+        let cols = vec![1, 2, 0, 3, 4, 3];
+        let graph = CsrMatrix::new(vec![1.0; 6], rows, cols, (6, 6)).unwrap();
+
+        let (count, labels) = connected_components(&graph, true, Connection::Weak).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(labels[0], labels[4]);
+        assert_ne!(labels[0], labels[5]);
+
+        let (count, labels) = connected_components(&graph, true, Connection::Strong).unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(labels[0], labels[1]);
+        assert_eq!(labels[1], labels[2]);
+        assert_eq!(labels[3], labels[4]);
+        assert_ne!(labels[0], labels[3]);
+        assert_ne!(labels[3], labels[5]);
+
+        let (order, predecessors) = breadth_first_order(&graph, 0, true).unwrap();
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+        assert_eq!(predecessors[3], Some(2));
+        assert_eq!(predecessors[5], None);
+
+        // Undirected: 0 reaches 2 directly through the reversed edge 2 -> 0
+        let (order, predecessors) = breadth_first_order(&graph, 0, false).unwrap();
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+        assert_eq!(predecessors[2], Some(0));
+
+        let (order, predecessors) = depth_first_order(&graph, 0, false).unwrap();
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+        assert_eq!(predecessors[2], Some(1));
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides the CSR matrix format implementation, which is
 //! efficient for row operations, matrix-vector multiplication, and more.
 
+use crate::element::SparseElement;
 use crate::error::{SparseError, SparseResult};
 use num_traits::Zero;
 use std::cmp::PartialEq;
@@ -466,6 +467,53 @@ impl<
     }
 }
 
+impl<T: SparseElement> CsrMatrix<T> {
+    /// Conjugate transpose `Aᴴ` of the matrix
+    ///
+    /// For real matrices this is the same as [`CsrMatrix::transpose`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_complex::Complex64;
+    /// use scirs2_sparse::csr::CsrMatrix;
+    ///
+    /// let a = CsrMatrix::new(vec![Complex64::new(1.0, 2.0)], vec![0], vec![1], (2, 2)).unwrap();
+    /// let ah = a.conj_transpose();
+    /// assert_eq!(ah.get(1, 0), Complex64::new(1.0, -2.0));
+    /// ```
+    pub fn conj_transpose(&self) -> Self {
+        let mut result = self.transpose();
+        if T::IS_COMPLEX {
+            result.data.iter_mut().for_each(|v| *v = v.conj());
+        }
+        result
+    }
+
+    /// Check if the matrix is Hermitian, `A = Aᴴ`
+    ///
+    /// For real matrices this is the same as [`CsrMatrix::is_symmetric`].
+    pub fn is_hermitian(&self) -> bool {
+        if self.rows != self.cols {
+            return false;
+        }
+        let mut entries: Vec<(usize, usize, T)> = Vec::with_capacity(self.nnz());
+        let mut adjoint: Vec<(usize, usize, T)> = Vec::with_capacity(self.nnz());
+        for row in 0..self.rows {
+            for j in self.indptr[row]..self.indptr[row + 1] {
+                let value = self.data[j];
+                if value != T::zero() {
+                    entries.push((row, self.indices[j], value));
+                    adjoint.push((self.indices[j], row, value.conj()));
+                }
+            }
+        }
+        entries.sort_by_key(|&(i, j, _)| (i, j));
+        adjoint.sort_by_key(|&(i, j, _)| (i, j));
+        entries == adjoint
+    }
+}
+
 impl CsrMatrix<f64> {
     /// Matrix-vector multiplication
     ///
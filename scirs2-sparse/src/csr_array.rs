@@ -4,10 +4,11 @@
 // which is efficient for row-wise operations and is one of the most common formats.
 
 use ndarray::{Array1, Array2, ArrayView1};
-use num_traits::Float;
+use std::cmp::Ordering;
 use std::fmt::{self, Debug};
 use std::ops::{Add, Div, Mul, Sub};
 
+use crate::element::{element_infinity, element_neg_infinity, SparseElement};
 use crate::error::{SparseError, SparseResult};
 use crate::sparray::{SparseArray, SparseSum};
 
@@ -29,7 +30,7 @@ use crate::sparray::{SparseArray, SparseSum};
 #[derive(Clone)]
 pub struct CsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -52,7 +53,7 @@ where
 
 impl<T> CsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -250,7 +251,7 @@ where
 
 impl<T> SparseArray<T> for CsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -268,7 +269,11 @@ where
     }
 
     fn dtype(&self) -> &str {
-        "float" // Placeholder, ideally we would return the actual type
+        if T::IS_COMPLEX {
+            "complex"
+        } else {
+            "float"
+        }
     }
 
     fn to_array(&self) -> Array2<T> {
@@ -518,7 +523,7 @@ where
                 let mut sum = T::zero();
                 for idx in start..end {
                     let col = self.indices[idx];
-                    sum = sum + self.data[idx] * other_array[[col, j]];
+                    sum += self.data[idx] * other_array[[col, j]];
                 }
                 if !sum.is_zero() {
                     result[[row, j]] = sum;
@@ -569,7 +574,7 @@ where
             let mut sum = T::zero();
             for idx in start..end {
                 let col = self.indices[idx];
-                sum = sum + self.data[idx] * other[col];
+                sum += self.data[idx] * other[col];
             }
             result[row] = sum;
         }
@@ -752,7 +757,7 @@ where
                 // Sum all elements
                 let mut sum = T::zero();
                 for &val in self.data.iter() {
-                    sum = sum + val;
+                    sum += val;
                 }
                 Ok(SparseSum::Scalar(sum))
             }
@@ -767,7 +772,7 @@ where
 
                     for idx in start..end {
                         let col = self.indices[idx];
-                        result[col] = result[col] + self.data[idx];
+                        result[col] += self.data[idx];
                     }
                 }
 
@@ -803,7 +808,7 @@ where
 
                     let mut row_sum = T::zero();
                     for idx in start..end {
-                        row_sum = row_sum + self.data[idx];
+                        row_sum += self.data[idx];
                     }
                     result.push(row_sum);
                 }
@@ -838,18 +843,20 @@ where
 
     fn max(&self) -> T {
         if self.data.is_empty() {
-            return T::neg_infinity();
+            return element_neg_infinity::<T>();
         }
 
         let mut max_val = self.data[0];
         for &val in self.data.iter().skip(1) {
-            if val > max_val {
+            if val.element_cmp(&max_val) == Ordering::Greater {
                 max_val = val;
             }
         }
 
         // Check if max_val is less than zero, as zeros aren't explicitly stored
-        if max_val < T::zero() && self.nnz() < self.shape.0 * self.shape.1 {
+        if max_val.element_cmp(&T::zero()) == Ordering::Less
+            && self.nnz() < self.shape.0 * self.shape.1
+        {
             max_val = T::zero();
         }
 
@@ -858,18 +865,20 @@ where
 
     fn min(&self) -> T {
         if self.data.is_empty() {
-            return T::infinity();
+            return element_infinity::<T>();
         }
 
         let mut min_val = self.data[0];
         for &val in self.data.iter().skip(1) {
-            if val < min_val {
+            if val.element_cmp(&min_val) == Ordering::Less {
                 min_val = val;
             }
         }
 
         // Check if min_val is greater than zero, as zeros aren't explicitly stored
-        if min_val > T::zero() && self.nnz() < self.shape.0 * self.shape.1 {
+        if min_val.element_cmp(&T::zero()) == Ordering::Greater
+            && self.nnz() < self.shape.0 * self.shape.1
+        {
             min_val = T::zero();
         }
 
@@ -955,7 +964,7 @@ where
 
 impl<T> fmt::Debug for CsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -1097,4 +1106,46 @@ mod tests {
             panic!("Expected sparse array sum");
         }
     }
+
+    #[test]
+    fn test_complex_csr_array() {
+        use num_complex::Complex64;
+
+        let c = Complex64::new;
+        let rows = vec![0, 0, 1, 2];
+        let cols = vec![0, 2, 1, 0];
+        let data = vec![c(1.0, 1.0), c(0.0, 2.0), c(3.0, 0.0), c(-1.0, 1.0)];
+        let a = CsrArray::from_triplets(&rows, &cols, &data, (3, 3), false).unwrap();
+        assert_eq!(a.dtype(), "complex");
+
+        // Matrix-vector product
+        let x = Array1::from_vec(vec![c(1.0, 0.0), c(0.0, 1.0), c(2.0, 0.0)]);
+        let y = a.dot_vector(&x.view()).unwrap();
+        assert_eq!(y[0], c(1.0, 5.0));
+        assert_eq!(y[1], c(0.0, 3.0));
+        assert_eq!(y[2], c(-1.0, 1.0));
+
+        // Arithmetic and conversions keep the imaginary parts
+        let sum = a.add(&a).unwrap();
+        assert_eq!(sum.get(0, 2), c(0.0, 4.0));
+        let product = a.mul(&a).unwrap();
+        assert_eq!(product.get(0, 0), c(0.0, 2.0));
+        let square = a.dot(&a).unwrap();
+        assert_eq!(square.get(2, 2), c(-2.0, -2.0));
+        for converted in [
+            a.to_csc().unwrap(),
+            a.to_coo().unwrap(),
+            a.to_dok().unwrap(),
+        ] {
+            assert_eq!(converted.to_array(), a.to_array());
+        }
+
+        // Transpose versus conjugate transpose
+        assert_eq!(a.transpose().unwrap().get(2, 0), c(0.0, 2.0));
+        let ah = a.conj_transpose().unwrap();
+        assert_eq!(ah.get(2, 0), c(0.0, -2.0));
+        assert_eq!(ah.get(0, 2), c(-1.0, -1.0));
+        assert_eq!(a.max(), c(3.0, 0.0));
+        assert_eq!(a.min(), c(-1.0, 1.0));
+    }
 }
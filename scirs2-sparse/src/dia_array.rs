@@ -4,13 +4,15 @@
 // which is efficient for matrices with values concentrated on a small number of diagonals.
 
 use ndarray::{Array1, Array2, ArrayView1};
-use num_traits::Float;
 use std::fmt::{self, Debug};
 use std::ops::{Add, Div, Mul, Sub};
 
 use crate::coo_array::CooArray;
 use crate::csr_array::CsrArray;
 use crate::dok_array::DokArray;
+use crate::element::{
+    element_infinity, element_max, element_min, element_neg_infinity, SparseElement,
+};
 use crate::error::{SparseError, SparseResult};
 use crate::lil_array::LilArray;
 use crate::sparray::{SparseArray, SparseSum};
@@ -31,7 +33,7 @@ use crate::sparray::{SparseArray, SparseSum};
 #[derive(Clone)]
 pub struct DiaArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -51,7 +53,7 @@ where
 
 impl<T> DiaArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -251,7 +253,7 @@ where
 
 impl<T> SparseArray<T> for DiaArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -292,7 +294,11 @@ where
     }
 
     fn dtype(&self) -> &str {
-        "float" // Placeholder; ideally would return the actual type
+        if T::IS_COMPLEX {
+            "complex"
+        } else {
+            "float"
+        }
     }
 
     fn to_array(&self) -> Array2<T> {
@@ -786,7 +792,7 @@ where
     }
 
     fn max(&self) -> T {
-        let mut max_val = T::neg_infinity();
+        let mut max_val = element_neg_infinity::<T>();
 
         for (diag_idx, &offset) in self.offsets.iter().enumerate() {
             let diag = &self.data[diag_idx];
@@ -802,12 +808,12 @@ where
             };
 
             for i in 0..length {
-                max_val = max_val.max(diag[i]);
+                max_val = element_max(max_val, diag[i]);
             }
         }
 
         // If no elements or all negative infinity, return zero
-        if max_val == T::neg_infinity() {
+        if max_val == element_neg_infinity::<T>() {
             T::zero()
         } else {
             max_val
@@ -815,7 +821,7 @@ where
     }
 
     fn min(&self) -> T {
-        let mut min_val = T::infinity();
+        let mut min_val = element_infinity::<T>();
         let mut has_nonzero = false;
 
         for (diag_idx, &offset) in self.offsets.iter().enumerate() {
@@ -834,7 +840,7 @@ where
             for i in 0..length {
                 if !diag[i].is_zero() {
                     has_nonzero = true;
-                    min_val = min_val.min(diag[i]);
+                    min_val = element_min(min_val, diag[i]);
                 }
             }
         }
@@ -890,7 +896,7 @@ where
 // Implement Display for DiaArray for better debugging
 impl<T> fmt::Display for DiaArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
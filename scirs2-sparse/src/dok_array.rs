@@ -4,13 +4,15 @@
 // which is efficient for incremental construction of sparse arrays.
 
 use ndarray::{Array1, Array2, ArrayView1};
-use num_traits::Float;
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::{Add, Div, Mul, Sub};
 
 use crate::coo_array::CooArray;
+use crate::element::{
+    element_infinity, element_max, element_min, element_nan, element_neg_infinity, SparseElement,
+};
 use crate::error::{SparseError, SparseResult};
 use crate::sparray::{SparseArray, SparseSum};
 
@@ -30,7 +32,7 @@ use crate::sparray::{SparseArray, SparseSum};
 #[derive(Clone)]
 pub struct DokArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -47,7 +49,7 @@ where
 
 impl<T> DokArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -164,7 +166,7 @@ where
 
 impl<T> SparseArray<T> for DokArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -182,7 +184,11 @@ where
     }
 
     fn dtype(&self) -> &str {
-        "float" // This is a placeholder; ideally, we'd return the actual type
+        if T::IS_COMPLEX {
+            "complex"
+        } else {
+            "float"
+        }
     }
 
     fn to_array(&self) -> Array2<T> {
@@ -378,7 +384,7 @@ where
         let mut result = Array1::zeros(m);
 
         for (&(row, col), &value) in &self.data {
-            result[row] = result[row] + value * other[col];
+            result[row] += value * other[col];
         }
 
         Ok(result)
@@ -450,7 +456,7 @@ where
                 // Sum all elements
                 let mut sum = T::zero();
                 for &value in self.data.values() {
-                    sum = sum + value;
+                    sum += value;
                 }
                 Ok(SparseSum::Scalar(sum))
             }
@@ -484,20 +490,22 @@ where
 
     fn max(&self) -> T {
         if self.data.is_empty() {
-            return T::nan();
+            return element_nan::<T>();
         }
 
         self.data
             .values()
-            .fold(T::neg_infinity(), |acc, &x| acc.max(x))
+            .fold(element_neg_infinity::<T>(), |acc, &x| element_max(acc, x))
     }
 
     fn min(&self) -> T {
         if self.data.is_empty() {
-            return T::nan();
+            return element_nan::<T>();
         }
 
-        self.data.values().fold(T::infinity(), |acc, &x| acc.min(x))
+        self.data
+            .values()
+            .fold(element_infinity::<T>(), |acc, &x| element_min(acc, x))
     }
 
     fn find(&self) -> (Array1<usize>, Array1<usize>, Array1<T>) {
@@ -0,0 +1,161 @@
+//! Scalar types that can be stored in sparse arrays
+//!
+//! [`SparseElement`] is implemented for `f32`, `f64`, `Complex<f32>` and
+//! `Complex<f64>`, so that every sparse format, the conversions between them
+//! and the Krylov solvers that only need inner products work for real and
+//! complex data alike.
+
+use num_complex::Complex;
+use num_traits::{Float, Num, NumAssign, NumCast};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::iter::Sum;
+use std::ops::Neg;
+
+/// Real or complex scalar stored in a sparse array
+///
+/// # Examples
+///
+/// ```
+/// use num_complex::Complex64;
+/// use scirs2_sparse::SparseElement;
+///
+/// let z = Complex64::new(3.0, 4.0);
+/// assert_eq!(z.modulus(), 5.0);
+/// assert_eq!(SparseElement::conj(z), Complex64::new(3.0, -4.0));
+/// assert_eq!(SparseElement::conj(2.0f64), 2.0);
+/// ```
+pub trait SparseElement:
+    Num + NumCast + NumAssign + Copy + Debug + Neg<Output = Self> + Sum + Send + Sync + 'static
+{
+    /// Real type of the magnitude, real and imaginary parts
+    type Real: Float + NumAssign + Sum + Debug + Send + Sync + 'static;
+
+    /// Whether the type has an imaginary part
+    const IS_COMPLEX: bool;
+
+    /// Complex conjugate (the identity for real types)
+    fn conj(self) -> Self;
+
+    /// Absolute value `|z|`
+    fn modulus(self) -> Self::Real;
+
+    /// Real part
+    fn real(self) -> Self::Real;
+
+    /// Imaginary part (zero for real types)
+    fn imag(self) -> Self::Real;
+
+    /// Embed a real number
+    fn from_real(re: Self::Real) -> Self;
+
+    /// Order used by `max` and `min`: numeric order for real types and
+    /// lexicographic order of (real, imaginary) parts for complex types, as
+    /// in NumPy
+    fn element_cmp(&self, other: &Self) -> Ordering {
+        self.real()
+            .partial_cmp(&other.real())
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| {
+                self.imag()
+                    .partial_cmp(&other.imag())
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+}
+
+macro_rules! impl_real_element {
+    ($t:ty) => {
+        impl SparseElement for $t {
+            type Real = $t;
+            const IS_COMPLEX: bool = false;
+
+            fn conj(self) -> Self {
+                self
+            }
+
+            fn modulus(self) -> $t {
+                self.abs()
+            }
+
+            fn real(self) -> $t {
+                self
+            }
+
+            fn imag(self) -> $t {
+                0.0
+            }
+
+            fn from_real(re: $t) -> Self {
+                re
+            }
+        }
+    };
+}
+
+macro_rules! impl_complex_element {
+    ($t:ty) => {
+        impl SparseElement for Complex<$t> {
+            type Real = $t;
+            const IS_COMPLEX: bool = true;
+
+            fn conj(self) -> Self {
+                Complex::conj(&self)
+            }
+
+            fn modulus(self) -> $t {
+                self.norm()
+            }
+
+            fn real(self) -> $t {
+                self.re
+            }
+
+            fn imag(self) -> $t {
+                self.im
+            }
+
+            fn from_real(re: $t) -> Self {
+                Complex::new(re, 0.0)
+            }
+        }
+    };
+}
+
+impl_real_element!(f32);
+impl_real_element!(f64);
+impl_complex_element!(f32);
+impl_complex_element!(f64);
+
+/// Largest element in the order of [`SparseElement::element_cmp`]
+pub(crate) fn element_max<T: SparseElement>(a: T, b: T) -> T {
+    if b.element_cmp(&a) == Ordering::Greater {
+        b
+    } else {
+        a
+    }
+}
+
+/// Smallest element in the order of [`SparseElement::element_cmp`]
+pub(crate) fn element_min<T: SparseElement>(a: T, b: T) -> T {
+    if b.element_cmp(&a) == Ordering::Less {
+        b
+    } else {
+        a
+    }
+}
+
+/// Positive infinity, on the real axis for complex types
+pub(crate) fn element_infinity<T: SparseElement>() -> T {
+    T::from_real(T::Real::infinity())
+}
+
+/// Negative infinity, on the real axis for complex types
+pub(crate) fn element_neg_infinity<T: SparseElement>() -> T {
+    T::from_real(T::Real::neg_infinity())
+}
+
+/// Not-a-number, on the real axis for complex types
+pub(crate) fn element_nan<T: SparseElement>() -> T {
+    T::from_real(T::Real::nan())
+}
@@ -10,7 +10,9 @@
 //! * Basic operations (addition, multiplication, etc.)
 //! * Sparse linear system solvers
 //! * Sparse eigenvalue computation
+//! * Graph algorithms on sparse adjacency matrices (`csgraph`)
 //! * Conversion between different formats
+//! * Real (`f32`, `f64`) and complex (`Complex<f32>`, `Complex<f64>`) elements
 //!
 //! ## Matrix vs. Array API
 //!
@@ -65,6 +67,10 @@
 pub mod error;
 pub use error::{SparseError, SparseResult};
 
+// Scalar types stored in sparse arrays
+pub mod element;
+pub use element::SparseElement;
+
 // Base trait for sparse arrays
 pub mod sparray;
 pub use sparray::{is_sparse, SparseArray, SparseSum};
@@ -135,11 +141,16 @@ pub mod linalg;
 pub use linalg::{
     // Functions from solvers
     add,
+    // Algebraic multigrid
+    amg,
     // Functions from iterative
     bicg,
     bicgstab,
     cg,
     diag_matrix,
+    // Eigenvalue solvers
+    eigs,
+    eigsh,
     expm,
     // Functions from matfuncs
     expm_multiply,
@@ -153,7 +164,11 @@ pub use linalg::{
     onenormest,
     sparse_direct_solve,
     sparse_lstsq,
+    splu,
     spsolve,
+    svds,
+    AmgOptions,
+    AmgPreconditioner,
     // Interfaces
     AsLinearOperator,
     // Types from iterative
@@ -165,17 +180,25 @@ pub use linalg::{
     CGSResult,
     // Operator types
     DiagonalOperator,
+    EigsOptions,
     GMRESOptions,
     // Preconditioners
     ILU0Preconditioner,
     IdentityOperator,
+    InverseOperator,
     IterationResult,
     JacobiPreconditioner,
     LinearOperator,
     SSORPreconditioner,
     ScaledIdentityOperator,
+    SparseLu,
+    SpluOptions,
+    SvdsOptions,
 };
 
+// Compressed sparse graph routines
+pub mod csgraph;
+
 // Format conversions
 pub mod convert;
 
@@ -194,6 +217,7 @@ pub use index_dtype::{can_cast_safely, get_index_dtype, safely_cast_index_arrays
 // Optimized operations for symmetric sparse formats
 pub mod sym_ops;
 pub use sym_ops::{
+    herm_coo_matvec, herm_csr_matvec, herm_csr_quadratic_form, herm_csr_rank1_update,
     sym_coo_matvec, sym_csr_matvec, sym_csr_quadratic_form, sym_csr_rank1_update, sym_csr_trace,
 };
 
@@ -204,7 +228,7 @@ pub struct SparseWarning;
 /// Check if an object is a sparse array
 pub fn is_sparse_array<T>(obj: &dyn SparseArray<T>) -> bool
 where
-    T: num_traits::Float
+    T: SparseElement
         + std::fmt::Debug
         + Copy
         + std::ops::Add<Output = T>
@@ -219,7 +243,7 @@ where
 /// Check if an object is a symmetric sparse array
 pub fn is_sym_sparse_array<T>(obj: &dyn SymSparseArray<T>) -> bool
 where
-    T: num_traits::Float
+    T: SparseElement
         + std::fmt::Debug
         + Copy
         + std::ops::Add<Output = T>
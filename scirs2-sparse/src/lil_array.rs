@@ -4,12 +4,13 @@
 // which is efficient for incremental matrix construction and row-based operations.
 
 use ndarray::{Array1, Array2, ArrayView1};
-use num_traits::Float;
+use std::cmp::Ordering;
 use std::fmt::{self, Debug};
 use std::ops::{Add, Div, Mul, Sub};
 
 use crate::coo_array::CooArray;
 use crate::csr_array::CsrArray;
+use crate::element::{element_infinity, element_neg_infinity, SparseElement};
 use crate::error::{SparseError, SparseResult};
 use crate::sparray::{SparseArray, SparseSum};
 
@@ -30,7 +31,7 @@ use crate::sparray::{SparseArray, SparseSum};
 #[derive(Clone)]
 pub struct LilArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -49,7 +50,7 @@ where
 
 impl<T> LilArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -252,7 +253,7 @@ where
 
 impl<T> SparseArray<T> for LilArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -270,7 +271,11 @@ where
     }
 
     fn dtype(&self) -> &str {
-        "float" // Placeholder
+        if T::IS_COMPLEX {
+            "complex"
+        } else {
+            "float"
+        }
     }
 
     fn to_array(&self) -> Array2<T> {
@@ -402,7 +407,7 @@ where
 
         for row in 0..rows {
             for (idx, &col) in self.indices[row].iter().enumerate() {
-                result[row] = result[row] + self.data[row][idx] * other[col];
+                result[row] += self.data[row][idx] * other[col];
             }
         }
 
@@ -510,7 +515,7 @@ where
                 let mut sum = T::zero();
                 for row in 0..self.shape.0 {
                     for &val in self.data[row].iter() {
-                        sum = sum + val;
+                        sum += val;
                     }
                 }
                 Ok(SparseSum::Scalar(sum))
@@ -522,7 +527,7 @@ where
 
                 for row in 0..self.shape.0 {
                     for (idx, &col) in self.indices[row].iter().enumerate() {
-                        result[col] = result[col] + self.data[row][idx];
+                        result[col] += self.data[row][idx];
                     }
                 }
 
@@ -543,7 +548,7 @@ where
 
                 for row in 0..rows {
                     for &val in self.data[row].iter() {
-                        result[row] = result[row] + val;
+                        result[row] += val;
                     }
                 }
 
@@ -563,20 +568,22 @@ where
 
     fn max(&self) -> T {
         if self.nnz() == 0 {
-            return T::neg_infinity();
+            return element_neg_infinity::<T>();
         }
 
-        let mut max_val = T::neg_infinity();
+        let mut max_val = element_neg_infinity::<T>();
         for row in 0..self.shape.0 {
             for &val in self.data[row].iter() {
-                if val > max_val {
+                if val.element_cmp(&max_val) == Ordering::Greater {
                     max_val = val;
                 }
             }
         }
 
         // If matrix is not entirely filled and max is negative, zero is the max
-        if max_val < T::zero() && self.nnz() < self.shape.0 * self.shape.1 {
+        if max_val.element_cmp(&T::zero()) == Ordering::Less
+            && self.nnz() < self.shape.0 * self.shape.1
+        {
             T::zero()
         } else {
             max_val
@@ -585,20 +592,22 @@ where
 
     fn min(&self) -> T {
         if self.nnz() == 0 {
-            return T::infinity();
+            return element_infinity::<T>();
         }
 
-        let mut min_val = T::infinity();
+        let mut min_val = element_infinity::<T>();
         for row in 0..self.shape.0 {
             for &val in self.data[row].iter() {
-                if val < min_val {
+                if val.element_cmp(&min_val) == Ordering::Less {
                     min_val = val;
                 }
             }
         }
 
         // If matrix is not entirely filled and min is positive, zero is the min
-        if min_val > T::zero() && self.nnz() < self.shape.0 * self.shape.1 {
+        if min_val.element_cmp(&T::zero()) == Ordering::Greater
+            && self.nnz() < self.shape.0 * self.shape.1
+        {
             T::zero()
         } else {
             min_val
@@ -670,7 +679,7 @@ where
 
 impl<T> fmt::Debug for LilArray<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
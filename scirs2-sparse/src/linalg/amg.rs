@@ -0,0 +1,1012 @@
+//! Algebraic multigrid (AMG) solver and preconditioner
+//!
+//! The hierarchy is built from the matrix alone, either by classical
+//! Ruge–Stüben coarsening with direct interpolation or by smoothed
+//! aggregation. Coarse operators are formed with the Galerkin product
+//! `R A P`, `R = Pᵀ`, and the coarsest level is solved with a sparse LU
+//! factorization.
+
+use crate::csr::CsrMatrix;
+use crate::error::{SparseError, SparseResult};
+use crate::linalg::direct::{splu, SparseLu, SpluOptions};
+use crate::linalg::interface::LinearOperator;
+use crate::linalg::iterative::IterationResult;
+use num_traits::{Float, NumAssign};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::Debug;
+use std::iter::Sum;
+
+/// Coarsening strategy of an AMG hierarchy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmgMethod {
+    /// Classical Ruge–Stüben C/F splitting with direct interpolation
+    RugeStuben,
+    /// Aggregation with a Jacobi-smoothed piecewise constant prolongator
+    SmoothedAggregation,
+}
+
+/// Relaxation method used on every level but the coarsest
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AmgSmoother {
+    /// Weighted Jacobi with damping factor `omega`
+    Jacobi { omega: f64 },
+    /// Forward Gauss–Seidel before and backward Gauss–Seidel after the
+    /// coarse-grid correction, which keeps the cycle symmetric
+    GaussSeidel,
+    /// A forward followed by a backward Gauss–Seidel sweep
+    SymmetricGaussSeidel,
+}
+
+/// Recursion pattern of a multigrid cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmgCycle {
+    /// One coarse-grid correction per level
+    V,
+    /// Two coarse-grid corrections per level
+    W,
+    /// An F-cycle followed by a V-cycle on the next coarser level
+    F,
+}
+
+/// Options for the AMG hierarchy and solver
+#[derive(Debug, Clone)]
+pub struct AmgOptions {
+    /// Coarsening strategy
+    pub method: AmgMethod,
+    /// Strength of connection threshold θ; `None` selects 0.25 for
+    /// Ruge–Stüben and 0.08 for smoothed aggregation
+    pub strength_threshold: Option<f64>,
+    /// Maximum number of levels, including the finest one
+    pub max_levels: usize,
+    /// Size below which a level is solved directly
+    pub max_coarse: usize,
+    /// Relaxation method
+    pub smoother: AmgSmoother,
+    /// Number of smoothing sweeps before the coarse-grid correction
+    pub presmooth: usize,
+    /// Number of smoothing sweeps after the coarse-grid correction
+    pub postsmooth: usize,
+    /// Cycle type
+    pub cycle: AmgCycle,
+    /// Maximum number of cycles of the standalone solver
+    pub max_iter: usize,
+    /// Relative tolerance of the standalone solver
+    pub rtol: f64,
+    /// Absolute tolerance of the standalone solver
+    pub atol: f64,
+}
+
+impl Default for AmgOptions {
+    fn default() -> Self {
+        Self {
+            method: AmgMethod::RugeStuben,
+            strength_threshold: None,
+            max_levels: 10,
+            max_coarse: 50,
+            smoother: AmgSmoother::GaussSeidel,
+            presmooth: 1,
+            postsmooth: 1,
+            cycle: AmgCycle::V,
+            max_iter: 100,
+            rtol: 1e-8,
+            atol: 1e-12,
+        }
+    }
+}
+
+/// One level of the hierarchy together with the transfer operators to the
+/// next coarser level
+struct AmgLevel<F> {
+    a: CsrMatrix<F>,
+    diag: Vec<F>,
+    p: CsrMatrix<F>,
+    r: CsrMatrix<F>,
+}
+
+/// Algebraic multigrid hierarchy
+///
+/// Can be used as a standalone solver through [`AmgPreconditioner::solve`]
+/// or as a preconditioner for the Krylov solvers, in which case every
+/// application performs one cycle from a zero initial guess. With the
+/// default symmetric smoothing and a V- or W-cycle the preconditioner is
+/// symmetric positive definite for SPD matrices and can be used with `cg`.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_sparse::csr::CsrMatrix;
+/// use scirs2_sparse::linalg::{cg, AmgOptions, AmgPreconditioner, AsLinearOperator, CGOptions};
+///
+/// // 1D Poisson matrix
+/// let n = 200;
+/// let (mut rows, mut cols, mut data) = (Vec::new(), Vec::new(), Vec::new());
+/// for i in 0..n {
+///     rows.push(i);
+///     cols.push(i);
+///     data.push(2.0);
+///     if i > 0 {
+///         rows.push(i);
+///         cols.push(i - 1);
+///         data.push(-1.0);
+///     }
+///     if i + 1 < n {
+///         rows.push(i);
+///         cols.push(i + 1);
+///         data.push(-1.0);
+///     }
+/// }
+/// let a = CsrMatrix::new(data, rows, cols, (n, n)).unwrap();
+/// let b = vec![1.0; n];
+///
+/// let amg = AmgPreconditioner::new(&a, AmgOptions::default()).unwrap();
+/// assert!(amg.num_levels() > 1);
+/// let result = amg.solve(&b, None).unwrap();
+/// assert!(result.converged);
+///
+/// let options = CGOptions {
+///     preconditioner: Some(Box::new(amg)),
+///     ..Default::default()
+/// };
+/// let result = cg(a.as_linear_operator().as_ref(), &b, options).unwrap();
+/// assert!(result.converged);
+/// ```
+pub struct AmgPreconditioner<F> {
+    levels: Vec<AmgLevel<F>>,
+    coarse_a: CsrMatrix<F>,
+    coarse_lu: SparseLu<F>,
+    options: AmgOptions,
+}
+
+impl<F: Float + NumAssign + Sum + Debug + 'static> AmgPreconditioner<F> {
+    /// Build the AMG hierarchy of a square matrix
+    ///
+    /// # Arguments
+    ///
+    /// * `matrix` - Square matrix with a nonzero diagonal
+    /// * `options` - Coarsening, smoothing and cycle options
+    ///
+    /// # Returns
+    ///
+    /// * The hierarchy, or an error if the matrix is not square, has a zero
+    ///   on the diagonal or its coarsest level is singular
+    pub fn new(matrix: &CsrMatrix<F>, options: AmgOptions) -> SparseResult<Self> {
+        let n = matrix.rows();
+        if n != matrix.cols() {
+            return Err(SparseError::DimensionMismatch {
+                expected: n,
+                found: matrix.cols(),
+            });
+        }
+        if options.max_levels == 0 {
+            return Err(SparseError::ValueError(
+                "max_levels must be at least 1".to_string(),
+            ));
+        }
+        let theta = F::from(options.strength_threshold.unwrap_or(match options.method {
+            AmgMethod::RugeStuben => 0.25,
+            AmgMethod::SmoothedAggregation => 0.08,
+        }))
+        .unwrap();
+
+        let mut levels = Vec::new();
+        let mut a = matrix.clone();
+        while levels.len() + 1 < options.max_levels && a.rows() > options.max_coarse {
+            let diag = diagonal(&a)?;
+            let p = match options.method {
+                AmgMethod::RugeStuben => ruge_stuben_prolongator(&a, theta),
+                AmgMethod::SmoothedAggregation => aggregation_prolongator(&a, &diag, theta),
+            }?;
+            // Stop when coarsening stagnates
+            if p.cols() == 0 || p.cols() >= a.rows() {
+                break;
+            }
+            let r = p.transpose();
+            let coarse = spmm(&r, &spmm(&a, &p)?)?;
+            levels.push(AmgLevel { a, diag, p, r });
+            a = coarse;
+        }
+        // Coarsening may stall early, so the coarsest level can be large
+        let coarse_lu = splu(&a, SpluOptions::default())?;
+
+        Ok(Self {
+            levels,
+            coarse_a: a,
+            coarse_lu,
+            options,
+        })
+    }
+
+    /// Number of levels, including the finest and the coarsest one
+    pub fn num_levels(&self) -> usize {
+        self.levels.len() + 1
+    }
+
+    /// Number of unknowns on each level, from the finest to the coarsest
+    pub fn level_sizes(&self) -> Vec<usize> {
+        self.levels
+            .iter()
+            .map(|level| level.a.rows())
+            .chain(std::iter::once(self.coarse_a.rows()))
+            .collect()
+    }
+
+    /// Total number of stored entries of all level operators divided by
+    /// that of the finest one
+    pub fn operator_complexity(&self) -> f64 {
+        let fine = match self.levels.first() {
+            Some(level) => level.a.nnz(),
+            None => return 1.0,
+        };
+        let total: usize =
+            self.levels.iter().map(|level| level.a.nnz()).sum::<usize>() + self.coarse_a.nnz();
+        total as f64 / fine.max(1) as f64
+    }
+
+    /// Solve `Ax = b` by repeated multigrid cycles
+    ///
+    /// Iterates until the residual norm drops below
+    /// `max(atol, rtol * ||b||)` or `max_iter` cycles have been performed.
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - Right-hand side
+    /// * `x0` - Initial guess, zero if `None`
+    ///
+    /// # Returns
+    ///
+    /// * The approximate solution and convergence information
+    pub fn solve(&self, b: &[F], x0: Option<&[F]>) -> SparseResult<IterationResult<F>> {
+        let a = self.finest();
+        let n = a.rows();
+        if b.len() != n {
+            return Err(SparseError::DimensionMismatch {
+                expected: n,
+                found: b.len(),
+            });
+        }
+        let mut x = match x0 {
+            Some(x0) if x0.len() != n => {
+                return Err(SparseError::DimensionMismatch {
+                    expected: n,
+                    found: x0.len(),
+                })
+            }
+            Some(x0) => x0.to_vec(),
+            None => vec![F::zero(); n],
+        };
+
+        let tolerance = F::max(
+            F::from(self.options.atol).unwrap(),
+            F::from(self.options.rtol).unwrap() * norm2(b),
+        );
+        let mut residual_norm = norm2(&residual(a, b, &x));
+        if residual_norm <= tolerance {
+            return Ok(IterationResult {
+                x,
+                iterations: 0,
+                residual_norm,
+                converged: true,
+                message: "Converged with initial guess".to_string(),
+            });
+        }
+
+        for iteration in 0..self.options.max_iter {
+            self.cycle(0, b, &mut x, self.options.cycle);
+            residual_norm = norm2(&residual(a, b, &x));
+            if !residual_norm.is_finite() {
+                return Ok(IterationResult {
+                    x,
+                    iterations: iteration + 1,
+                    residual_norm,
+                    converged: false,
+                    message: "AMG iteration diverged".to_string(),
+                });
+            }
+            if residual_norm <= tolerance {
+                return Ok(IterationResult {
+                    x,
+                    iterations: iteration + 1,
+                    residual_norm,
+                    converged: true,
+                    message: "Converged".to_string(),
+                });
+            }
+        }
+
+        Ok(IterationResult {
+            x,
+            iterations: self.options.max_iter,
+            residual_norm,
+            converged: false,
+            message: "Maximum iterations reached".to_string(),
+        })
+    }
+
+    fn finest(&self) -> &CsrMatrix<F> {
+        self.levels
+            .first()
+            .map(|level| &level.a)
+            .unwrap_or(&self.coarse_a)
+    }
+
+    /// Apply one cycle starting at `level` to improve `x`
+    fn cycle(&self, level: usize, b: &[F], x: &mut [F], cycle: AmgCycle) {
+        let Some(current) = self.levels.get(level) else {
+            let coarse_x = self
+                .coarse_lu
+                .solve(b)
+                .expect("right-hand side has the size of the coarsest level");
+            x.copy_from_slice(&coarse_x);
+            return;
+        };
+
+        for _ in 0..self.options.presmooth {
+            relax(current, b, x, self.options.smoother, true);
+        }
+
+        let r = residual(&current.a, b, x);
+        let coarse_b = spmv(&current.r, &r);
+        let mut coarse_x = vec![F::zero(); coarse_b.len()];
+        match cycle {
+            AmgCycle::V => self.cycle(level + 1, &coarse_b, &mut coarse_x, AmgCycle::V),
+            AmgCycle::W => {
+                self.cycle(level + 1, &coarse_b, &mut coarse_x, AmgCycle::W);
+                self.cycle(level + 1, &coarse_b, &mut coarse_x, AmgCycle::W);
+            }
+            AmgCycle::F => {
+                self.cycle(level + 1, &coarse_b, &mut coarse_x, AmgCycle::F);
+                self.cycle(level + 1, &coarse_b, &mut coarse_x, AmgCycle::V);
+            }
+        }
+        for (xi, ci) in x.iter_mut().zip(spmv(&current.p, &coarse_x)) {
+            *xi += ci;
+        }
+
+        for _ in 0..self.options.postsmooth {
+            relax(current, b, x, self.options.smoother, false);
+        }
+    }
+}
+
+impl<F: Float + NumAssign + Sum + Debug + 'static> LinearOperator<F> for AmgPreconditioner<F> {
+    fn shape(&self) -> (usize, usize) {
+        self.finest().shape()
+    }
+
+    fn matvec(&self, x: &[F]) -> SparseResult<Vec<F>> {
+        let n = self.finest().rows();
+        if x.len() != n {
+            return Err(SparseError::DimensionMismatch {
+                expected: n,
+                found: x.len(),
+            });
+        }
+        let mut y = vec![F::zero(); n];
+        self.cycle(0, x, &mut y, self.options.cycle);
+        Ok(y)
+    }
+}
+
+/// Solve `Ax = b` with a standalone algebraic multigrid iteration
+///
+/// Convenience wrapper that builds an [`AmgPreconditioner`] and calls
+/// [`AmgPreconditioner::solve`] with a zero initial guess.
+///
+/// # Arguments
+///
+/// * `a` - Square matrix, typically from an elliptic problem
+/// * `b` - Right-hand side
+/// * `options` - Hierarchy and solver options
+///
+/// # Returns
+///
+/// * The approximate solution and convergence information
+pub fn amg<F>(a: &CsrMatrix<F>, b: &[F], options: AmgOptions) -> SparseResult<IterationResult<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    AmgPreconditioner::new(a, options)?.solve(b, None)
+}
+
+/// Smoothing sweep on one level; `forward` selects the Gauss–Seidel
+/// direction
+fn relax<F: Float + NumAssign>(
+    level: &AmgLevel<F>,
+    b: &[F],
+    x: &mut [F],
+    smoother: AmgSmoother,
+    forward: bool,
+) {
+    let a = &level.a;
+    let gauss_seidel_row = |i: usize, x: &mut [F]| {
+        let mut r = b[i];
+        for p in a.indptr[i]..a.indptr[i + 1] {
+            r -= a.data[p] * x[a.indices[p]];
+        }
+        x[i] += r / level.diag[i];
+    };
+    match smoother {
+        AmgSmoother::Jacobi { omega } => {
+            let omega = F::from(omega).unwrap();
+            let r = residual(a, b, x);
+            for ((xi, ri), &di) in x.iter_mut().zip(r).zip(&level.diag) {
+                *xi += omega * ri / di;
+            }
+        }
+        AmgSmoother::GaussSeidel if forward => (0..a.rows()).for_each(|i| gauss_seidel_row(i, x)),
+        AmgSmoother::GaussSeidel => (0..a.rows()).rev().for_each(|i| gauss_seidel_row(i, x)),
+        AmgSmoother::SymmetricGaussSeidel => {
+            (0..a.rows()).for_each(|i| gauss_seidel_row(i, x));
+            (0..a.rows()).rev().for_each(|i| gauss_seidel_row(i, x));
+        }
+    }
+}
+
+/// Classical strength of connection: `i` strongly depends on `j` if
+/// `-a_ij >= θ max_{k≠i} (-a_ik)`. Returns one flag per stored entry.
+fn classical_strength<F: Float>(a: &CsrMatrix<F>, theta: F) -> Vec<bool> {
+    let mut strong = vec![false; a.nnz()];
+    for i in 0..a.rows() {
+        let range = a.indptr[i]..a.indptr[i + 1];
+        let max_coupling = range
+            .clone()
+            .filter(|&p| a.indices[p] != i)
+            .map(|p| -a.data[p])
+            .fold(F::zero(), F::max);
+        if max_coupling <= F::zero() {
+            continue;
+        }
+        for p in range {
+            strong[p] = a.indices[p] != i && -a.data[p] >= theta * max_coupling;
+        }
+    }
+    strong
+}
+
+/// Ruge–Stüben C/F splitting followed by direct interpolation
+#[allow(clippy::needless_range_loop)]
+fn ruge_stuben_prolongator<F: Float + NumAssign>(
+    a: &CsrMatrix<F>,
+    theta: F,
+) -> SparseResult<CsrMatrix<F>> {
+    let n = a.rows();
+    let strong = classical_strength(a, theta);
+    let depends_on = |i: usize| {
+        (a.indptr[i]..a.indptr[i + 1])
+            .filter(|&p| strong[p])
+            .map(|p| a.indices[p])
+    };
+
+    // influences[j] lists the points that strongly depend on j
+    let mut influences = vec![Vec::new(); n];
+    for i in 0..n {
+        for j in depends_on(i) {
+            influences[j].push(i);
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Point {
+        Undecided,
+        Coarse,
+        Fine,
+    }
+    let mut state = vec![Point::Undecided; n];
+    let mut measure: Vec<usize> = influences.iter().map(Vec::len).collect();
+    let mut heap = BinaryHeap::new();
+    for i in 0..n {
+        if measure[i] == 0 && depends_on(i).next().is_none() {
+            // Isolated points are left to the smoother
+            state[i] = Point::Fine;
+        } else {
+            heap.push((measure[i], Reverse(i)));
+        }
+    }
+
+    // First pass: repeatedly pick the undecided point that influences the
+    // most undecided points as C and make the points it influences F
+    while let Some((m, Reverse(i))) = heap.pop() {
+        if state[i] != Point::Undecided || m != measure[i] {
+            continue;
+        }
+        state[i] = Point::Coarse;
+        for &j in &influences[i] {
+            if state[j] != Point::Undecided {
+                continue;
+            }
+            state[j] = Point::Fine;
+            for k in depends_on(j) {
+                if state[k] == Point::Undecided {
+                    measure[k] += 1;
+                    heap.push((measure[k], Reverse(k)));
+                }
+            }
+        }
+        for k in depends_on(i) {
+            if state[k] == Point::Undecided && measure[k] > 0 {
+                measure[k] -= 1;
+                heap.push((measure[k], Reverse(k)));
+            }
+        }
+    }
+
+    // Every F point with strong connections needs a C point to interpolate
+    // from
+    for i in 0..n {
+        if state[i] == Point::Fine
+            && depends_on(i).next().is_some()
+            && depends_on(i).all(|j| state[j] != Point::Coarse)
+        {
+            state[i] = Point::Coarse;
+        }
+    }
+
+    let mut coarse_index = vec![usize::MAX; n];
+    let mut num_coarse = 0;
+    for i in 0..n {
+        if state[i] == Point::Coarse {
+            coarse_index[i] = num_coarse;
+            num_coarse += 1;
+        }
+    }
+
+    let mut indptr = Vec::with_capacity(n + 1);
+    let mut indices = Vec::new();
+    let mut data = Vec::new();
+    indptr.push(0);
+    let mut row: Vec<(usize, F)> = Vec::new();
+    for i in 0..n {
+        if state[i] == Point::Coarse {
+            indices.push(coarse_index[i]);
+            data.push(F::one());
+            indptr.push(indices.len());
+            continue;
+        }
+
+        let mut diag = F::zero();
+        let (mut neg_all, mut pos_all) = (F::zero(), F::zero());
+        let (mut neg_coarse, mut pos_coarse) = (F::zero(), F::zero());
+        for p in a.indptr[i]..a.indptr[i + 1] {
+            let (j, v) = (a.indices[p], a.data[p]);
+            let interpolatory = strong[p] && state[j] == Point::Coarse;
+            if j == i {
+                diag += v;
+            } else if v < F::zero() {
+                neg_all += v;
+                if interpolatory {
+                    neg_coarse += v;
+                }
+            } else {
+                pos_all += v;
+                if interpolatory {
+                    pos_coarse += v;
+                }
+            }
+        }
+        // Positive couplings without a coarse counterpart are lumped to the
+        // diagonal
+        if pos_coarse == F::zero() {
+            diag += pos_all;
+        }
+        let alpha = if neg_coarse != F::zero() {
+            neg_all / neg_coarse
+        } else {
+            F::zero()
+        };
+        let beta = if pos_coarse != F::zero() {
+            pos_all / pos_coarse
+        } else {
+            F::zero()
+        };
+
+        row.clear();
+        for p in a.indptr[i]..a.indptr[i + 1] {
+            let (j, v) = (a.indices[p], a.data[p]);
+            if strong[p] && state[j] == Point::Coarse {
+                let scale = if v < F::zero() { alpha } else { beta };
+                row.push((coarse_index[j], -scale * v / diag));
+            }
+        }
+        row.sort_by_key(|&(j, _)| j);
+        for &(j, w) in &row {
+            indices.push(j);
+            data.push(w);
+        }
+        indptr.push(indices.len());
+    }
+
+    CsrMatrix::from_raw_csr(data, indptr, indices, (n, num_coarse))
+}
+
+/// Smoothed aggregation prolongator `P = (I - ω/ρ D⁻¹A) T`, where `T` is
+/// the tentative prolongator that interpolates the constant vector
+/// piecewise on aggregates, `ω = 4/3` and `ρ` estimates the spectral
+/// radius of `D⁻¹A`
+#[allow(clippy::needless_range_loop)]
+fn aggregation_prolongator<F: Float + NumAssign + Sum>(
+    a: &CsrMatrix<F>,
+    diag: &[F],
+    theta: F,
+) -> SparseResult<CsrMatrix<F>> {
+    let n = a.rows();
+
+    // Symmetric strength: |a_ij| >= θ sqrt(|a_ii a_jj|)
+    let mut neighbours = vec![Vec::new(); n];
+    for (i, list) in neighbours.iter_mut().enumerate() {
+        for p in a.indptr[i]..a.indptr[i + 1] {
+            let j = a.indices[p];
+            if j != i && a.data[p].abs() >= theta * (diag[i] * diag[j]).abs().sqrt() {
+                list.push(j);
+            }
+        }
+    }
+
+    const UNAGGREGATED: usize = usize::MAX;
+    let mut aggregate = vec![UNAGGREGATED; n];
+    let mut num_aggregates = 0;
+
+    // Phase 1: points whose strong neighbourhood is still free seed new
+    // aggregates
+    for i in 0..n {
+        if aggregate[i] == UNAGGREGATED
+            && !neighbours[i].is_empty()
+            && neighbours[i].iter().all(|&j| aggregate[j] == UNAGGREGATED)
+        {
+            aggregate[i] = num_aggregates;
+            for &j in &neighbours[i] {
+                aggregate[j] = num_aggregates;
+            }
+            num_aggregates += 1;
+        }
+    }
+
+    // Phase 2: remaining points join an aggregate of a strong neighbour
+    let seeded = aggregate.clone();
+    for i in 0..n {
+        if aggregate[i] == UNAGGREGATED {
+            if let Some(&j) = neighbours[i].iter().find(|&&j| seeded[j] != UNAGGREGATED) {
+                aggregate[i] = seeded[j];
+            }
+        }
+    }
+
+    // Phase 3: aggregate what is left with its free strong neighbours;
+    // points without strong connections stay out of every aggregate
+    for i in 0..n {
+        if aggregate[i] == UNAGGREGATED && !neighbours[i].is_empty() {
+            aggregate[i] = num_aggregates;
+            for &j in &neighbours[i] {
+                if aggregate[j] == UNAGGREGATED {
+                    aggregate[j] = num_aggregates;
+                }
+            }
+            num_aggregates += 1;
+        }
+    }
+
+    // Tentative prolongator with orthonormal columns
+    let mut sizes = vec![0usize; num_aggregates];
+    for &g in aggregate.iter().filter(|&&g| g != UNAGGREGATED) {
+        sizes[g] += 1;
+    }
+    let mut t_indptr = Vec::with_capacity(n + 1);
+    let mut t_indices = Vec::with_capacity(n);
+    let mut t_data = Vec::with_capacity(n);
+    t_indptr.push(0);
+    for &g in &aggregate {
+        if g != UNAGGREGATED {
+            t_indices.push(g);
+            t_data.push(F::one() / F::from(sizes[g]).unwrap().sqrt());
+        }
+        t_indptr.push(t_indices.len());
+    }
+    let tentative = CsrMatrix::from_raw_csr(t_data, t_indptr, t_indices, (n, num_aggregates))?;
+
+    // Jacobi smoothing of the tentative prolongator
+    let omega = F::from(4.0 / 3.0).unwrap() / jacobi_spectral_radius(a, diag);
+    let at = spmm(a, &tentative)?;
+    let mut indptr = Vec::with_capacity(n + 1);
+    let mut indices = Vec::with_capacity(at.nnz() + n);
+    let mut data = Vec::with_capacity(at.nnz() + n);
+    indptr.push(0);
+    let mut row: Vec<(usize, F)> = Vec::new();
+    for i in 0..n {
+        row.clear();
+        let scale = omega / diag[i];
+        for p in at.indptr[i]..at.indptr[i + 1] {
+            row.push((at.indices[p], -scale * at.data[p]));
+        }
+        for p in tentative.indptr[i]..tentative.indptr[i + 1] {
+            let j = tentative.indices[p];
+            match row.iter_mut().find(|(k, _)| *k == j) {
+                Some((_, v)) => *v += tentative.data[p],
+                None => row.push((j, tentative.data[p])),
+            }
+        }
+        row.sort_by_key(|&(j, _)| j);
+        for &(j, v) in &row {
+            if v != F::zero() {
+                indices.push(j);
+                data.push(v);
+            }
+        }
+        indptr.push(indices.len());
+    }
+
+    CsrMatrix::from_raw_csr(data, indptr, indices, (n, num_aggregates))
+}
+
+/// Power iteration estimate of the spectral radius of `D⁻¹A`
+fn jacobi_spectral_radius<F: Float + NumAssign + Sum>(a: &CsrMatrix<F>, diag: &[F]) -> F {
+    let n = a.rows();
+    // Deterministic start vector that is not an eigenvector of simple
+    // stencils
+    let mut v: Vec<F> = (0..n)
+        .map(|i| F::one() + F::from(i % 7).unwrap() / F::from(7).unwrap())
+        .collect();
+    let mut rho = F::one();
+    for _ in 0..20 {
+        let norm = norm2(&v);
+        if norm == F::zero() {
+            break;
+        }
+        v.iter_mut().for_each(|vi| *vi /= norm);
+        let mut w = spmv(a, &v);
+        w.iter_mut().zip(diag).for_each(|(wi, &di)| *wi /= di);
+        rho = norm2(&w);
+        v = w;
+    }
+    if rho > F::zero() && rho.is_finite() {
+        rho
+    } else {
+        F::one()
+    }
+}
+
+fn diagonal<F: Float + NumAssign>(a: &CsrMatrix<F>) -> SparseResult<Vec<F>> {
+    let mut diag = vec![F::zero(); a.rows()];
+    for (i, d) in diag.iter_mut().enumerate() {
+        for p in a.indptr[i]..a.indptr[i + 1] {
+            if a.indices[p] == i {
+                *d += a.data[p];
+            }
+        }
+        if *d == F::zero() {
+            return Err(SparseError::SingularMatrix(format!(
+                "zero diagonal entry in row {i}"
+            )));
+        }
+    }
+    Ok(diag)
+}
+
+fn spmv<F: Float + NumAssign>(a: &CsrMatrix<F>, x: &[F]) -> Vec<F> {
+    (0..a.rows())
+        .map(|i| {
+            (a.indptr[i]..a.indptr[i + 1]).fold(F::zero(), |s, p| s + a.data[p] * x[a.indices[p]])
+        })
+        .collect()
+}
+
+fn residual<F: Float + NumAssign>(a: &CsrMatrix<F>, b: &[F], x: &[F]) -> Vec<F> {
+    b.iter()
+        .zip(spmv(a, x))
+        .map(|(&bi, axi)| bi - axi)
+        .collect()
+}
+
+fn norm2<F: Float + Sum>(x: &[F]) -> F {
+    x.iter().map(|&v| v * v).sum::<F>().sqrt()
+}
+
+/// Sparse matrix product by Gustavson's row-wise algorithm
+fn spmm<F: Float + NumAssign>(a: &CsrMatrix<F>, b: &CsrMatrix<F>) -> SparseResult<CsrMatrix<F>> {
+    if a.cols() != b.rows() {
+        return Err(SparseError::DimensionMismatch {
+            expected: a.cols(),
+            found: b.rows(),
+        });
+    }
+    let (m, n) = (a.rows(), b.cols());
+    let mut marker = vec![usize::MAX; n];
+    let mut accumulator = vec![F::zero(); n];
+    let mut columns = Vec::new();
+    let mut indptr = Vec::with_capacity(m + 1);
+    let mut indices = Vec::new();
+    let mut data = Vec::new();
+    indptr.push(0);
+    for i in 0..m {
+        columns.clear();
+        for p in a.indptr[i]..a.indptr[i + 1] {
+            let (k, av) = (a.indices[p], a.data[p]);
+            for q in b.indptr[k]..b.indptr[k + 1] {
+                let j = b.indices[q];
+                if marker[j] != i {
+                    marker[j] = i;
+                    accumulator[j] = F::zero();
+                    columns.push(j);
+                }
+                accumulator[j] += av * b.data[q];
+            }
+        }
+        columns.sort_unstable();
+        for &j in &columns {
+            if accumulator[j] != F::zero() {
+                indices.push(j);
+                data.push(accumulator[j]);
+            }
+        }
+        indptr.push(indices.len());
+    }
+    CsrMatrix::from_raw_csr(data, indptr, indices, (m, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::{cg, gmres, AsLinearOperator, CGOptions, GMRESOptions};
+
+    /// Five-point Laplacian on an `m x m` grid with Dirichlet boundary
+    fn poisson_2d(m: usize) -> CsrMatrix<f64> {
+        let n = m * m;
+        let mut indptr = vec![0];
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+        for i in 0..n {
+            let (r, c) = (i / m, i % m);
+            let mut entries = vec![(i, 4.0)];
+            if r > 0 {
+                entries.push((i - m, -1.0));
+            }
+            if c > 0 {
+                entries.push((i - 1, -1.0));
+            }
+            if c + 1 < m {
+                entries.push((i + 1, -1.0));
+            }
+            if r + 1 < m {
+                entries.push((i + m, -1.0));
+            }
+            entries.sort_by_key(|&(j, _)| j);
+            for (j, v) in entries {
+                indices.push(j);
+                data.push(v);
+            }
+            indptr.push(indices.len());
+        }
+        CsrMatrix::from_raw_csr(data, indptr, indices, (n, n)).unwrap()
+    }
+
+    fn relative_residual(a: &CsrMatrix<f64>, b: &[f64], x: &[f64]) -> f64 {
+        norm2(&residual(a, b, x)) / norm2(b)
+    }
+
+    #[test]
+    fn test_amg_standalone() {
+        let a = poisson_2d(32);
+        let b: Vec<f64> = (0..a.rows()).map(|i| ((i % 13) as f64) - 6.0).collect();
+
+        for method in [AmgMethod::RugeStuben, AmgMethod::SmoothedAggregation] {
+            let options = AmgOptions {
+                method,
+                ..Default::default()
+            };
+            let amg = AmgPreconditioner::new(&a, options.clone()).unwrap();
+            let sizes = amg.level_sizes();
+            assert!(amg.num_levels() >= 3, "{method:?}: {sizes:?}");
+            assert!(sizes.windows(2).all(|w| w[1] < w[0]));
+            assert!(*sizes.last().unwrap() <= options.max_coarse);
+            assert!(amg.operator_complexity() < 3.0);
+
+            let result = amg.solve(&b, None).unwrap();
+            assert!(result.converged, "{method:?}: {}", result.message);
+            assert!(result.iterations <= 30, "{method:?}: {}", result.iterations);
+            assert!(relative_residual(&a, &b, &result.x) <= 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_amg_cycles_and_smoothers() {
+        let a = poisson_2d(24);
+        let b = vec![1.0; a.rows()];
+        let v_iterations = amg(&a, &b, AmgOptions::default()).unwrap().iterations;
+
+        for cycle in [AmgCycle::W, AmgCycle::F] {
+            let options = AmgOptions {
+                cycle,
+                ..Default::default()
+            };
+            let result = amg(&a, &b, options).unwrap();
+            assert!(result.converged);
+            assert!(result.iterations <= v_iterations, "{cycle:?}");
+        }
+
+        for smoother in [
+            AmgSmoother::Jacobi { omega: 2.0 / 3.0 },
+            AmgSmoother::SymmetricGaussSeidel,
+        ] {
+            let options = AmgOptions {
+                method: AmgMethod::SmoothedAggregation,
+                smoother,
+                presmooth: 2,
+                postsmooth: 2,
+                ..Default::default()
+            };
+            let result = amg(&a, &b, options).unwrap();
+            assert!(result.converged, "{smoother:?}");
+            assert!(relative_residual(&a, &b, &result.x) <= 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_amg_preconditioner() {
+        let a = poisson_2d(32);
+        let n = a.rows();
+        let b: Vec<f64> = (0..n).map(|i| (i as f64 * 0.1).sin()).collect();
+
+        let op = a.as_linear_operator();
+        let plain = cg(op.as_ref(), &b, CGOptions::default()).unwrap();
+        let options = CGOptions {
+            preconditioner: Some(Box::new(
+                AmgPreconditioner::new(&a, AmgOptions::default()).unwrap(),
+            )),
+            ..Default::default()
+        };
+        let preconditioned = cg(op.as_ref(), &b, options).unwrap();
+        assert!(preconditioned.converged);
+        assert!(preconditioned.iterations * 4 < plain.iterations);
+        assert!(relative_residual(&a, &b, &preconditioned.x) <= 1e-7);
+
+        let amg_options = AmgOptions {
+            method: AmgMethod::SmoothedAggregation,
+            ..Default::default()
+        };
+        let options = GMRESOptions {
+            preconditioner: Some(Box::new(AmgPreconditioner::new(&a, amg_options).unwrap())),
+            ..Default::default()
+        };
+        let result = gmres(op.as_ref(), &b, options).unwrap();
+        assert!(result.converged);
+        assert!(result.iterations <= 20);
+        assert!(relative_residual(&a, &b, &result.x) <= 1e-7);
+    }
+
+    #[test]
+    fn test_amg_small_and_invalid() {
+        // A matrix below max_coarse is solved directly on a single level
+        let a = poisson_2d(4);
+        let b = vec![1.0; 16];
+        let amg = AmgPreconditioner::new(&a, AmgOptions::default()).unwrap();
+        assert_eq!(amg.num_levels(), 1);
+        let result = amg.solve(&b, None).unwrap();
+        assert!(result.converged);
+        assert_eq!(result.iterations, 1);
+
+        // Positive off-diagonals give no strong connections, so coarsening
+        // stalls and the whole matrix is factorized sparsely
+        let n = 1500usize;
+        let mut triplets = (Vec::new(), Vec::new(), Vec::new());
+        for i in 0..n {
+            for (j, v) in [(i.wrapping_sub(1), 0.3), (i, 1.0), (i + 1, 0.3)] {
+                if j < n {
+                    triplets.0.push(i);
+                    triplets.1.push(j);
+                    triplets.2.push(v);
+                }
+            }
+        }
+        let stalled = CsrMatrix::new(triplets.2, triplets.0, triplets.1, (n, n)).unwrap();
+        let amg = AmgPreconditioner::new(&stalled, AmgOptions::default()).unwrap();
+        assert!(*amg.level_sizes().last().unwrap() > AmgOptions::default().max_coarse);
+        let b = vec![1.0; n];
+        let result = amg.solve(&b, None).unwrap();
+        assert!(result.converged);
+        assert!(relative_residual(&stalled, &b, &result.x) <= 1e-8);
+
+        let rectangular =
+            CsrMatrix::from_raw_csr(vec![1.0], vec![0, 1, 1], vec![0], (2, 3)).unwrap();
+        assert!(AmgPreconditioner::new(&rectangular, AmgOptions::default()).is_err());
+        assert!(amg.solve(&[1.0; 3], None).is_err());
+    }
+}
@@ -0,0 +1,374 @@
+//! Small dense eigenvalue routines for projected Krylov problems
+
+use num_complex::Complex;
+use num_traits::Float;
+
+/// Eigenvalues and orthonormal eigenvectors of a small symmetric matrix
+///
+/// Uses cyclic Jacobi rotations; `a` is given by rows and only its
+/// symmetric part is used. Eigenvector `i` is column `i` of the returned
+/// matrix (stored by rows).
+#[allow(clippy::needless_range_loop)]
+pub(crate) fn symmetric_eigen<F: Float>(a: &[Vec<F>]) -> (Vec<F>, Vec<Vec<F>>) {
+    let m = a.len();
+    let two = F::one() + F::one();
+    let mut s: Vec<Vec<F>> = (0..m)
+        .map(|i| (0..m).map(|j| (a[i][j] + a[j][i]) / two).collect())
+        .collect();
+    let mut v: Vec<Vec<F>> = (0..m)
+        .map(|i| {
+            (0..m)
+                .map(|j| if i == j { F::one() } else { F::zero() })
+                .collect()
+        })
+        .collect();
+
+    let scale = s
+        .iter()
+        .flatten()
+        .fold(F::zero(), |acc, &x| acc + x * x)
+        .sqrt();
+    for _sweep in 0..100 {
+        let off = (0..m)
+            .flat_map(|i| (0..m).filter(move |&j| j != i).map(move |j| (i, j)))
+            .fold(F::zero(), |acc, (i, j)| acc + s[i][j] * s[i][j])
+            .sqrt();
+        if off <= F::epsilon() * scale || off == F::zero() {
+            break;
+        }
+        for p in 0..m {
+            for q in p + 1..m {
+                if s[p][q] == F::zero() {
+                    continue;
+                }
+                // Rotation angle that annihilates s[p][q]
+                let theta = (s[q][q] - s[p][p]) / (two * s[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + F::one()).sqrt());
+                let c = F::one() / (t * t + F::one()).sqrt();
+                let sn = t * c;
+                for k in 0..m {
+                    let (skp, skq) = (s[k][p], s[k][q]);
+                    s[k][p] = c * skp - sn * skq;
+                    s[k][q] = sn * skp + c * skq;
+                }
+                for k in 0..m {
+                    let (spk, sqk) = (s[p][k], s[q][k]);
+                    s[p][k] = c * spk - sn * sqk;
+                    s[q][k] = sn * spk + c * sqk;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - sn * vkq;
+                    row[q] = sn * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..m).map(|i| s[i][i]).collect(), v)
+}
+
+/// Eigenvalues of a small real upper Hessenberg matrix
+///
+/// Uses the single-shift complex QR algorithm with Wilkinson shifts. Complex
+/// eigenvalues are returned as exact conjugate pairs.
+#[allow(clippy::needless_range_loop)]
+pub(crate) fn hessenberg_eigenvalues<F: Float>(h: &[Vec<F>]) -> Vec<Complex<F>> {
+    let m = h.len();
+    let mut a: Vec<Vec<Complex<F>>> = h
+        .iter()
+        .map(|row| row.iter().map(|&x| Complex::new(x, F::zero())).collect())
+        .collect();
+    let mut eig = vec![Complex::new(F::zero(), F::zero()); m];
+    let eps = F::epsilon();
+    let norm = h
+        .iter()
+        .flatten()
+        .fold(F::zero(), |acc, &x| acc.max(x.abs()));
+    let mut hi = m;
+    let mut iter = 0;
+    while hi > 0 {
+        let top = hi - 1;
+        // Find the start of the active unreduced block
+        let mut l = top;
+        while l > 0 {
+            let sub = a[l][l - 1].norm();
+            if sub <= eps * (a[l][l].norm() + a[l - 1][l - 1].norm()) || sub <= eps * norm * eps {
+                a[l][l - 1] = Complex::new(F::zero(), F::zero());
+                break;
+            }
+            l -= 1;
+        }
+        if l == top || iter > 60 * m {
+            eig[top] = a[top][top];
+            hi -= 1;
+            iter = 0;
+            continue;
+        }
+
+        // Wilkinson shift from the trailing 2x2 block, exceptional shifts on stagnation
+        let (p, q, r, s) = (
+            a[top - 1][top - 1],
+            a[top - 1][top],
+            a[top][top - 1],
+            a[top][top],
+        );
+        let two = F::one() + F::one();
+        let mean = (p + s) / two;
+        let disc = (((p - s) / two) * ((p - s) / two) + q * r).sqrt();
+        let (mu1, mu2) = (mean + disc, mean - disc);
+        let mut shift = if (mu1 - s).norm() < (mu2 - s).norm() {
+            mu1
+        } else {
+            mu2
+        };
+        if iter % 11 == 10 {
+            shift = s + Complex::new(a[top][top - 1].norm(), F::zero());
+        }
+
+        // QR step on the active block with Givens rotations
+        for i in l..=top {
+            a[i][i] = a[i][i] - shift;
+        }
+        let mut rotations = Vec::with_capacity(top - l);
+        for i in l..top {
+            let (x, y) = (a[i][i], a[i + 1][i]);
+            let rnorm = (x.norm_sqr() + y.norm_sqr()).sqrt();
+            let (c, sn) = if rnorm == F::zero() {
+                (
+                    Complex::new(F::one(), F::zero()),
+                    Complex::new(F::zero(), F::zero()),
+                )
+            } else {
+                (x / rnorm, y / rnorm)
+            };
+            for j in i..=top {
+                let (aij, ai1j) = (a[i][j], a[i + 1][j]);
+                a[i][j] = c.conj() * aij + sn.conj() * ai1j;
+                a[i + 1][j] = -sn * aij + c * ai1j;
+            }
+            rotations.push((c, sn));
+        }
+        for (offset, &(c, sn)) in rotations.iter().enumerate() {
+            let i = l + offset;
+            for row in a.iter_mut().take((i + 2).min(top) + 1).skip(l) {
+                let (rij, rij1) = (row[i], row[i + 1]);
+                row[i] = rij * c + rij1 * sn;
+                row[i + 1] = -rij * sn.conj() + rij1 * c.conj();
+            }
+        }
+        for i in l..=top {
+            a[i][i] = a[i][i] + shift;
+        }
+        iter += 1;
+    }
+    pair_conjugates(eig, norm)
+}
+
+/// Snap eigenvalues of a real matrix to real values or exact conjugate pairs
+fn pair_conjugates<F: Float>(mut eig: Vec<Complex<F>>, norm: F) -> Vec<Complex<F>> {
+    let tol = F::epsilon().sqrt() * norm.max(F::one());
+    let m = eig.len();
+    let mut paired = vec![false; m];
+    for i in 0..m {
+        if paired[i] {
+            continue;
+        }
+        if eig[i].im.abs() <= tol * F::epsilon().sqrt() {
+            eig[i].im = F::zero();
+            paired[i] = true;
+            continue;
+        }
+        let target = eig[i].conj();
+        let partner = (0..m).filter(|&j| j != i && !paired[j]).min_by(|&x, &y| {
+            (eig[x] - target)
+                .norm()
+                .partial_cmp(&(eig[y] - target).norm())
+                .unwrap()
+        });
+        match partner {
+            Some(j) if (eig[j] - target).norm() <= tol => {
+                let mean = (eig[i] + eig[j].conj()) / (F::one() + F::one());
+                let upper = Complex::new(mean.re, mean.im.abs());
+                eig[i] = upper;
+                eig[j] = upper.conj();
+                paired[i] = true;
+                paired[j] = true;
+            }
+            _ => {
+                // No partner: the imaginary part is rounding noise
+                eig[i].im = F::zero();
+                paired[i] = true;
+            }
+        }
+    }
+    eig
+}
+
+/// Unit eigenvector of a small real matrix for a computed eigenvalue
+///
+/// Uses two steps of inverse iteration with a slightly perturbed shift.
+pub(crate) fn eigenvector<F: Float>(h: &[Vec<F>], lambda: Complex<F>) -> Vec<Complex<F>> {
+    let m = h.len();
+    let norm = h
+        .iter()
+        .flatten()
+        .fold(F::zero(), |acc, &x| acc.max(x.abs()))
+        .max(F::min_positive_value());
+    let shift = lambda + Complex::new(norm * F::epsilon() * F::from(10.0).unwrap(), F::zero());
+    let matrix: Vec<Vec<Complex<F>>> = (0..m)
+        .map(|i| {
+            (0..m)
+                .map(|j| {
+                    let x = Complex::new(h[i][j], F::zero());
+                    if i == j {
+                        x - shift
+                    } else {
+                        x
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let mut z: Vec<Complex<F>> = (0..m)
+        .map(|i| {
+            Complex::new(
+                F::one() + F::from(i).unwrap() / F::from(m).unwrap(),
+                F::zero(),
+            )
+        })
+        .collect();
+    for _ in 0..3 {
+        z = complex_solve(matrix.clone(), z, norm);
+        let scale = z.iter().fold(F::zero(), |acc, x| acc + x.norm_sqr()).sqrt();
+        if scale == F::zero() || !scale.is_finite() {
+            break;
+        }
+        for x in &mut z {
+            *x = *x / scale;
+        }
+    }
+    z
+}
+
+/// Solve a small complex system with partial pivoting, replacing zero pivots
+#[allow(clippy::needless_range_loop)]
+fn complex_solve<F: Float>(
+    mut a: Vec<Vec<Complex<F>>>,
+    mut b: Vec<Complex<F>>,
+    norm: F,
+) -> Vec<Complex<F>> {
+    let m = b.len();
+    let tiny = Complex::new(norm * F::epsilon(), F::zero());
+    for k in 0..m {
+        let pivot = (k..m)
+            .max_by(|&x, &y| a[x][k].norm().partial_cmp(&a[y][k].norm()).unwrap())
+            .unwrap();
+        a.swap(k, pivot);
+        b.swap(k, pivot);
+        if a[k][k].norm() == F::zero() {
+            a[k][k] = tiny;
+        }
+        for i in k + 1..m {
+            let factor = a[i][k] / a[k][k];
+            for j in k..m {
+                let akj = a[k][j];
+                a[i][j] = a[i][j] - factor * akj;
+            }
+            let bk = b[k];
+            b[i] = b[i] - factor * bk;
+        }
+    }
+    for i in (0..m).rev() {
+        let mut sum = b[i];
+        for j in i + 1..m {
+            sum = sum - a[i][j] * b[j];
+        }
+        b[i] = sum / a[i][i];
+    }
+    b
+}
+
+/// Orthogonal factor `Q` of the QR factorization of a small square matrix
+#[allow(clippy::needless_range_loop)]
+pub(crate) fn qr_q<F: Float>(a: &[Vec<F>]) -> Vec<Vec<F>> {
+    let m = a.len();
+    let mut r: Vec<Vec<F>> = a.to_vec();
+    let mut q: Vec<Vec<F>> = (0..m)
+        .map(|i| {
+            (0..m)
+                .map(|j| if i == j { F::one() } else { F::zero() })
+                .collect()
+        })
+        .collect();
+    for k in 0..m.saturating_sub(1) {
+        // Householder reflector for column k below the diagonal
+        let norm = (k..m)
+            .fold(F::zero(), |acc, i| acc + r[i][k] * r[i][k])
+            .sqrt();
+        if norm == F::zero() {
+            continue;
+        }
+        let alpha = if r[k][k] > F::zero() { -norm } else { norm };
+        let mut v: Vec<F> = (k..m).map(|i| r[i][k]).collect();
+        v[0] = v[0] - alpha;
+        let vnorm2 = v.iter().fold(F::zero(), |acc, &x| acc + x * x);
+        if vnorm2 == F::zero() {
+            continue;
+        }
+        let two = F::one() + F::one();
+        for j in 0..m {
+            let dot = (k..m).fold(F::zero(), |acc, i| acc + v[i - k] * r[i][j]);
+            let factor = two * dot / vnorm2;
+            for i in k..m {
+                r[i][j] = r[i][j] - factor * v[i - k];
+            }
+        }
+        // Q <- Q H_k
+        for row in q.iter_mut() {
+            let dot = (k..m).fold(F::zero(), |acc, i| acc + row[i] * v[i - k]);
+            let factor = two * dot / vnorm2;
+            for i in k..m {
+                row[i] = row[i] - factor * v[i - k];
+            }
+        }
+    }
+    q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_eigenproblems() {
+        let a = vec![
+            vec![4.0, 1.0, 0.5],
+            vec![1.0, 3.0, 0.2],
+            vec![0.5, 0.2, 1.0],
+        ];
+        let (values, vectors) = symmetric_eigen(&a);
+        for (i, &lambda) in values.iter().enumerate() {
+            for r in 0..3 {
+                let av: f64 = (0..3).map(|c| a[r][c] * vectors[c][i]).sum();
+                assert!((av - lambda * vectors[r][i]).abs() < 1e-12);
+            }
+        }
+
+        // Rotation block with eigenvalues 1 ± 2i and a real eigenvalue 3
+        let h = vec![
+            vec![1.0, -2.0, 0.3],
+            vec![2.0, 1.0, 0.7],
+            vec![0.0, 0.0, 3.0],
+        ];
+        let mut eig = hessenberg_eigenvalues(&h);
+        eig.sort_by(|x, y| x.im.partial_cmp(&y.im).unwrap());
+        assert!((eig[0] - Complex::new(1.0, -2.0)).norm() < 1e-12);
+        assert_eq!(eig[1], Complex::new(3.0, 0.0));
+        assert_eq!(eig[2], eig[0].conj());
+        let z = eigenvector(&h, eig[2]);
+        for r in 0..3 {
+            let hz: Complex<f64> = (0..3).map(|c| z[c] * h[r][c]).sum();
+            assert!((hz - z[r] * eig[2]).norm() < 1e-10);
+        }
+    }
+}
@@ -0,0 +1,1130 @@
+//! Sparse direct factorizations
+//!
+//! This module provides sparse LU factorization with partial pivoting
+//! (left-looking Gilbert–Peierls algorithm) and up-looking sparse Cholesky and
+//! LDLᵀ factorizations driven by the elimination tree. All factorizations
+//! apply a fill-reducing ordering first and keep it, together with the
+//! symbolic analysis, so that a factor object can solve many right-hand sides
+//! and be refactorized for new values with the same sparsity pattern.
+
+use crate::csr::CsrMatrix;
+use crate::error::{SparseError, SparseResult};
+use crate::linalg::interface::LinearOperator;
+use crate::linalg::ordering::{invert_permutation, OrderingMethod};
+use num_traits::{Float, NumAssign};
+use std::fmt::Debug;
+use std::iter::Sum;
+
+const NONE: usize = usize::MAX;
+
+/// Sparse matrix in compressed sparse column form, used for factors
+#[derive(Debug, Clone)]
+struct Csc<F> {
+    colptr: Vec<usize>,
+    rowidx: Vec<usize>,
+    values: Vec<F>,
+}
+
+impl<F: Float> Csc<F> {
+    /// Columns of a CSR matrix with the rows and columns renumbered
+    ///
+    /// Entry `(i, j)` of `a` is stored at `(row_map[i], col_map[j])`; entries
+    /// rejected by `keep` are dropped.
+    fn from_csr(
+        a: &CsrMatrix<F>,
+        row_map: impl Fn(usize) -> usize,
+        col_map: impl Fn(usize) -> usize,
+        keep: impl Fn(usize, usize) -> bool,
+    ) -> Self {
+        let n = a.cols();
+        let mut colptr = vec![0; n + 1];
+        for i in 0..a.rows() {
+            for &j in &a.indices[a.indptr[i]..a.indptr[i + 1]] {
+                if keep(row_map(i), col_map(j)) {
+                    colptr[col_map(j) + 1] += 1;
+                }
+            }
+        }
+        for j in 0..n {
+            colptr[j + 1] += colptr[j];
+        }
+        let mut next = colptr.clone();
+        let mut rowidx = vec![0; colptr[n]];
+        let mut values = vec![F::zero(); colptr[n]];
+        for i in 0..a.rows() {
+            let range = a.indptr[i]..a.indptr[i + 1];
+            for (&j, &v) in a.indices[range.clone()].iter().zip(&a.data[range]) {
+                let (r, c) = (row_map(i), col_map(j));
+                if keep(r, c) {
+                    rowidx[next[c]] = r;
+                    values[next[c]] = v;
+                    next[c] += 1;
+                }
+            }
+        }
+        Self {
+            colptr,
+            rowidx,
+            values,
+        }
+    }
+
+    fn column(&self, j: usize) -> std::ops::Range<usize> {
+        self.colptr[j]..self.colptr[j + 1]
+    }
+
+    fn to_csr(&self, n: usize) -> SparseResult<CsrMatrix<F>> {
+        let mut rows = Vec::with_capacity(self.values.len());
+        let mut cols = Vec::with_capacity(self.values.len());
+        for j in 0..n {
+            for p in self.column(j) {
+                rows.push(self.rowidx[p]);
+                cols.push(j);
+            }
+        }
+        CsrMatrix::new(self.values.clone(), rows, cols, (n, n))
+    }
+}
+
+fn check_square<F: Float>(a: &CsrMatrix<F>) -> SparseResult<usize> {
+    if a.rows() != a.cols() {
+        return Err(SparseError::ValueError(format!(
+            "Matrix must be square, got {}x{}",
+            a.rows(),
+            a.cols()
+        )));
+    }
+    Ok(a.rows())
+}
+
+fn check_rhs(n: usize, b_len: usize) -> SparseResult<()> {
+    if n != b_len {
+        return Err(SparseError::DimensionMismatch {
+            expected: n,
+            found: b_len,
+        });
+    }
+    Ok(())
+}
+
+/// Options for [`splu`]
+#[derive(Debug, Clone, Copy)]
+pub struct SpluOptions {
+    /// Column ordering applied before factorization
+    pub ordering: OrderingMethod,
+    /// Threshold in `[0, 1]` for preferring the diagonal entry as pivot:
+    /// it is chosen if its magnitude is at least this fraction of the
+    /// largest candidate. `1.0` gives classic partial pivoting.
+    pub pivot_threshold: f64,
+}
+
+impl Default for SpluOptions {
+    fn default() -> Self {
+        Self {
+            ordering: OrderingMethod::Colamd,
+            pivot_threshold: 1.0,
+        }
+    }
+}
+
+/// Sparse LU factorization `P A Q = L U`
+///
+/// `L` is unit lower triangular, `U` upper triangular, `P` the row
+/// permutation chosen by partial pivoting and `Q` the fill-reducing column
+/// permutation.
+#[derive(Debug, Clone)]
+pub struct SparseLu<F> {
+    n: usize,
+    options: SpluOptions,
+    perm_c: Vec<usize>,
+    pinv: Vec<usize>,
+    l: Csc<F>,
+    u: Csc<F>,
+}
+
+/// Compute the sparse LU factorization of a square matrix
+///
+/// # Arguments
+///
+/// * `a` - Square sparse matrix
+/// * `options` - Column ordering and pivoting threshold
+///
+/// # Returns
+///
+/// * The factorization, reusable for many right-hand sides
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_sparse::csr::CsrMatrix;
+/// use scirs2_sparse::linalg::{splu, SpluOptions};
+///
+/// let rows = vec![0, 0, 1, 1, 2, 2];
+/// let cols = vec![1, 2, 0, 2, 0, 1];
+/// let data = vec![2.0, 1.0, 1.0, 3.0, 4.0, 1.0];
+/// let a = CsrMatrix::new(data, rows, cols, (3, 3)).unwrap();
+///
+/// let lu = splu(&a, SpluOptions::default()).unwrap();
+/// let x = lu.solve(&[3.0, 4.0, 5.0]).unwrap();
+/// let ax = a.dot(&x).unwrap();
+/// assert!((ax[0] - 3.0).abs() < 1e-12 && (ax[2] - 5.0).abs() < 1e-12);
+/// ```
+pub fn splu<F>(a: &CsrMatrix<F>, options: SpluOptions) -> SparseResult<SparseLu<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    let n = check_square(a)?;
+    if !(0.0..=1.0).contains(&options.pivot_threshold) {
+        return Err(SparseError::ValueError(format!(
+            "Pivot threshold must be in [0, 1], got {}",
+            options.pivot_threshold
+        )));
+    }
+    let perm_c = options.ordering.compute(a)?;
+    let mut lu = SparseLu {
+        n,
+        options,
+        perm_c,
+        pinv: Vec::new(),
+        l: Csc {
+            colptr: Vec::new(),
+            rowidx: Vec::new(),
+            values: Vec::new(),
+        },
+        u: Csc {
+            colptr: Vec::new(),
+            rowidx: Vec::new(),
+            values: Vec::new(),
+        },
+    };
+    lu.refactor(a)?;
+    Ok(lu)
+}
+
+impl<F> SparseLu<F>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    /// Factorize new values, reusing the column ordering
+    ///
+    /// The matrix should have the same (or a similar) sparsity pattern as
+    /// the one the ordering was computed for; pivoting is redone.
+    pub fn refactor(&mut self, a: &CsrMatrix<F>) -> SparseResult<()> {
+        let n = check_square(a)?;
+        check_rhs(self.n, n)?;
+        let qinv = invert_permutation(&self.perm_c);
+        let a = Csc::from_csr(a, |i| i, |j| qinv[j], |_, _| true);
+        let tol = F::from(self.options.pivot_threshold).unwrap();
+
+        let mut pinv = vec![NONE; n];
+        let mut x = vec![F::zero(); n];
+        let mut xi = vec![0; n];
+        let mut work = ReachWork::new(n);
+        let mut l = Csc {
+            colptr: vec![0; n + 1],
+            rowidx: Vec::with_capacity(4 * a.rowidx.len() + n),
+            values: Vec::with_capacity(4 * a.rowidx.len() + n),
+        };
+        let mut u = l.clone();
+
+        for k in 0..n {
+            l.colptr[k] = l.rowidx.len();
+            u.colptr[k] = u.rowidx.len();
+            let col = self.perm_c[k];
+
+            // x = L \ A(:, col), restricted to the reach of A(:, col) in L
+            let top = work.reach(&l, &a.rowidx[a.column(k)], &pinv, &mut xi);
+            for &i in &xi[top..] {
+                x[i] = F::zero();
+            }
+            for p in a.column(k) {
+                x[a.rowidx[p]] += a.values[p];
+            }
+            for &j in &xi[top..] {
+                let jnew = pinv[j];
+                if jnew == NONE {
+                    continue;
+                }
+                // The first entry of each L column is its unit diagonal
+                let xj = x[j];
+                for p in l.colptr[jnew] + 1..l.colptr[jnew + 1] {
+                    x[l.rowidx[p]] -= l.values[p] * xj;
+                }
+            }
+
+            // Largest candidate among the rows not yet pivotal
+            let mut ipiv = NONE;
+            let mut largest = -F::one();
+            for &i in &xi[top..] {
+                if pinv[i] == NONE {
+                    if x[i].abs() > largest {
+                        largest = x[i].abs();
+                        ipiv = i;
+                    }
+                } else {
+                    u.rowidx.push(pinv[i]);
+                    u.values.push(x[i]);
+                }
+            }
+            if ipiv == NONE || largest <= F::zero() || !largest.is_finite() {
+                return Err(SparseError::SingularMatrix(format!(
+                    "Matrix is singular: no pivot in column {}",
+                    col
+                )));
+            }
+            if pinv[col] == NONE && x[col].abs() >= largest * tol {
+                ipiv = col;
+            }
+
+            let pivot = x[ipiv];
+            u.rowidx.push(k);
+            u.values.push(pivot);
+            pinv[ipiv] = k;
+            l.rowidx.push(ipiv);
+            l.values.push(F::one());
+            for &i in &xi[top..] {
+                if pinv[i] == NONE {
+                    l.rowidx.push(i);
+                    l.values.push(x[i] / pivot);
+                }
+                x[i] = F::zero();
+            }
+        }
+        l.colptr[n] = l.rowidx.len();
+        u.colptr[n] = u.rowidx.len();
+        for r in &mut l.rowidx {
+            *r = pinv[*r];
+        }
+
+        self.pinv = pinv;
+        self.l = l;
+        self.u = u;
+        Ok(())
+    }
+
+    /// Solve `A x = b`
+    pub fn solve(&self, b: &[F]) -> SparseResult<Vec<F>> {
+        check_rhs(self.n, b.len())?;
+        let mut x = vec![F::zero(); self.n];
+        for (i, &bi) in b.iter().enumerate() {
+            x[self.pinv[i]] = bi;
+        }
+        for j in 0..self.n {
+            let xj = x[j];
+            for p in self.l.colptr[j] + 1..self.l.colptr[j + 1] {
+                x[self.l.rowidx[p]] -= self.l.values[p] * xj;
+            }
+        }
+        for j in (0..self.n).rev() {
+            // The diagonal is the last entry of each U column
+            let diag = self.u.colptr[j + 1] - 1;
+            x[j] /= self.u.values[diag];
+            let xj = x[j];
+            for p in self.u.colptr[j]..diag {
+                x[self.u.rowidx[p]] -= self.u.values[p] * xj;
+            }
+        }
+        let mut result = vec![F::zero(); self.n];
+        for (k, &j) in self.perm_c.iter().enumerate() {
+            result[j] = x[k];
+        }
+        Ok(result)
+    }
+
+    /// Solve `Aᵀ x = b`
+    pub fn solve_transpose(&self, b: &[F]) -> SparseResult<Vec<F>> {
+        check_rhs(self.n, b.len())?;
+        let mut x: Vec<F> = self.perm_c.iter().map(|&j| b[j]).collect();
+        for j in 0..self.n {
+            let diag = self.u.colptr[j + 1] - 1;
+            let mut xj = x[j];
+            for p in self.u.colptr[j]..diag {
+                xj -= self.u.values[p] * x[self.u.rowidx[p]];
+            }
+            x[j] = xj / self.u.values[diag];
+        }
+        for j in (0..self.n).rev() {
+            let mut xj = x[j];
+            for p in self.l.colptr[j] + 1..self.l.colptr[j + 1] {
+                xj -= self.l.values[p] * x[self.l.rowidx[p]];
+            }
+            x[j] = xj;
+        }
+        Ok(self.pinv.iter().map(|&k| x[k]).collect())
+    }
+
+    /// Unit lower triangular factor `L`
+    pub fn l(&self) -> SparseResult<CsrMatrix<F>> {
+        self.l.to_csr(self.n)
+    }
+
+    /// Upper triangular factor `U`
+    pub fn u(&self) -> SparseResult<CsrMatrix<F>> {
+        self.u.to_csr(self.n)
+    }
+
+    /// Row permutation: row `perm_r()[i]` of `P A Q` is row `i` of `A`
+    pub fn perm_r(&self) -> &[usize] {
+        &self.pinv
+    }
+
+    /// Column permutation: column `k` of `P A Q` is column `perm_c()[k]` of `A`
+    pub fn perm_c(&self) -> &[usize] {
+        &self.perm_c
+    }
+
+    /// Number of stored entries in `L` and `U`
+    pub fn nnz(&self) -> usize {
+        self.l.rowidx.len() + self.u.rowidx.len()
+    }
+}
+
+/// Linear operator applying the inverse of a sparse matrix through its LU factors
+///
+/// This is the operator expected by the shift-invert modes of
+/// [`eigsh`](crate::linalg::eigsh) and [`eigs`](crate::linalg::eigs).
+pub struct InverseOperator<F> {
+    lu: SparseLu<F>,
+}
+
+impl<F> InverseOperator<F>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    /// Factorize `a` so that applying the operator solves `A x = b`
+    pub fn new(a: &CsrMatrix<F>) -> SparseResult<Self> {
+        Ok(Self {
+            lu: splu(a, SpluOptions::default())?,
+        })
+    }
+
+    /// Factorize `A - σ M` (or `A - σ I` without a mass matrix)
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Square sparse matrix
+    /// * `m` - Optional mass matrix of the same shape
+    /// * `sigma` - Shift
+    pub fn shifted(a: &CsrMatrix<F>, m: Option<&CsrMatrix<F>>, sigma: F) -> SparseResult<Self> {
+        Self::new(&shifted_matrix(a, m, sigma)?)
+    }
+
+    /// The underlying factorization
+    pub fn factorization(&self) -> &SparseLu<F> {
+        &self.lu
+    }
+}
+
+impl<F> LinearOperator<F> for InverseOperator<F>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    fn shape(&self) -> (usize, usize) {
+        (self.lu.n, self.lu.n)
+    }
+
+    fn matvec(&self, x: &[F]) -> SparseResult<Vec<F>> {
+        self.lu.solve(x)
+    }
+}
+
+/// `A - σ M` for sparse matrices, with duplicate entries summed
+fn shifted_matrix<F>(
+    a: &CsrMatrix<F>,
+    m: Option<&CsrMatrix<F>>,
+    sigma: F,
+) -> SparseResult<CsrMatrix<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    let n = a.rows();
+    let (mut rows, mut cols, mut data) = a.get_triplets();
+    match m {
+        Some(m) => {
+            if m.shape() != a.shape() {
+                return Err(SparseError::ShapeMismatch {
+                    expected: a.shape(),
+                    found: m.shape(),
+                });
+            }
+            let (mr, mc, md) = m.get_triplets();
+            rows.extend(mr);
+            cols.extend(mc);
+            data.extend(md.into_iter().map(|x| -sigma * x));
+        }
+        None => {
+            rows.extend(0..n);
+            cols.extend(0..n);
+            data.extend(std::iter::repeat_n(-sigma, n));
+        }
+    }
+    let summed = CsrMatrix::new(data, rows, cols, a.shape())?;
+    let (mut rows, mut cols, mut data) = (Vec::new(), Vec::new(), Vec::new());
+    for i in 0..n {
+        for p in summed.indptr[i]..summed.indptr[i + 1] {
+            let j = summed.indices[p];
+            if rows.last() == Some(&i) && cols.last() == Some(&j) {
+                *data.last_mut().unwrap() += summed.data[p];
+            } else {
+                rows.push(i);
+                cols.push(j);
+                data.push(summed.data[p]);
+            }
+        }
+    }
+    CsrMatrix::new(data, rows, cols, a.shape())
+}
+
+/// Work arrays for the depth-first search of the Gilbert–Peierls solve
+struct ReachWork {
+    marked: Vec<bool>,
+    position: Vec<usize>,
+    stack: Vec<usize>,
+}
+
+impl ReachWork {
+    fn new(n: usize) -> Self {
+        Self {
+            marked: vec![false; n],
+            position: vec![0; n],
+            stack: Vec::new(),
+        }
+    }
+
+    /// Nonzero pattern of `L \ b` in topological order, stored in `xi[top..]`
+    ///
+    /// Rows that are not yet pivotal have no outgoing edges.
+    fn reach<F>(
+        &mut self,
+        l: &Csc<F>,
+        b_rows: &[usize],
+        pinv: &[usize],
+        xi: &mut [usize],
+    ) -> usize {
+        let mut top = xi.len();
+        for &start in b_rows {
+            if self.marked[start] {
+                continue;
+            }
+            self.stack.push(start);
+            while let Some(&j) = self.stack.last() {
+                let jnew = pinv[j];
+                let end = if jnew == NONE { 0 } else { l.colptr[jnew + 1] };
+                if !self.marked[j] {
+                    self.marked[j] = true;
+                    self.position[j] = if jnew == NONE { 0 } else { l.colptr[jnew] };
+                }
+                let mut descended = false;
+                while self.position[j] < end {
+                    let i = l.rowidx[self.position[j]];
+                    self.position[j] += 1;
+                    if !self.marked[i] {
+                        self.stack.push(i);
+                        descended = true;
+                        break;
+                    }
+                }
+                if !descended {
+                    self.stack.pop();
+                    top -= 1;
+                    xi[top] = j;
+                }
+            }
+        }
+        for &i in &xi[top..] {
+            self.marked[i] = false;
+        }
+        top
+    }
+}
+
+/// Ordering, elimination tree and column structure of a symmetric factor
+#[derive(Debug, Clone)]
+struct SymbolicCholesky {
+    perm: Vec<usize>,
+    pinv: Vec<usize>,
+    parent: Vec<usize>,
+    /// Column pointers of `L`, with the diagonal first in each column
+    colptr: Vec<usize>,
+}
+
+impl SymbolicCholesky {
+    fn analyze<F: Float>(a: &CsrMatrix<F>, ordering: OrderingMethod) -> SparseResult<Self> {
+        let n = check_square(a)?;
+        let perm = ordering.compute(a)?;
+        let pinv = invert_permutation(&perm);
+        let c = upper_permuted(a, &pinv);
+
+        // Elimination tree with path compression
+        let mut parent = vec![NONE; n];
+        let mut ancestor = vec![NONE; n];
+        for k in 0..n {
+            for &row in &c.rowidx[c.column(k)] {
+                let mut i = row;
+                while i != NONE && i < k {
+                    let next = ancestor[i];
+                    ancestor[i] = k;
+                    if next == NONE {
+                        parent[i] = k;
+                    }
+                    i = next;
+                }
+            }
+        }
+
+        // Column counts: every row pattern adds one entry to its columns
+        let mut counts = vec![1; n];
+        let mut reach = EtreeReach::new(n);
+        for k in 0..n {
+            for &i in reach.row_pattern(&c, k, &parent) {
+                counts[i] += 1;
+            }
+        }
+        let mut colptr = vec![0; n + 1];
+        for j in 0..n {
+            colptr[j + 1] = colptr[j] + counts[j];
+        }
+        Ok(Self {
+            perm,
+            pinv,
+            parent,
+            colptr,
+        })
+    }
+
+    fn check<F: Float>(&self, a: &CsrMatrix<F>) -> SparseResult<()> {
+        let n = check_square(a)?;
+        check_rhs(self.perm.len(), n)
+    }
+
+    fn pattern_changed() -> SparseError {
+        SparseError::ValueError(
+            "Sparsity pattern differs from the one used in the symbolic analysis".to_string(),
+        )
+    }
+}
+
+/// Upper triangle of `P A Pᵀ` in CSC form (both triangles of A must be stored)
+fn upper_permuted<F: Float>(a: &CsrMatrix<F>, pinv: &[usize]) -> Csc<F> {
+    Csc::from_csr(a, |i| pinv[i], |j| pinv[j], |i, j| i <= j)
+}
+
+/// Work arrays for computing row patterns of `L` from the elimination tree
+struct EtreeReach {
+    mark: Vec<usize>,
+    buffer: Vec<usize>,
+}
+
+impl EtreeReach {
+    fn new(n: usize) -> Self {
+        Self {
+            mark: vec![NONE; n],
+            buffer: vec![0; n],
+        }
+    }
+
+    /// Columns of the off-diagonal entries in row `k` of `L`, in topological order
+    fn row_pattern<F: Float>(&mut self, c: &Csc<F>, k: usize, parent: &[usize]) -> &[usize] {
+        let n = self.buffer.len();
+        let mut top = n;
+        self.mark[k] = k;
+        for &row in &c.rowidx[c.column(k)] {
+            let mut i = row;
+            if i > k {
+                continue;
+            }
+            // Walk up the tree until reaching a node already in the pattern
+            let mut len = 0;
+            while self.mark[i] != k {
+                self.buffer[len] = i;
+                len += 1;
+                self.mark[i] = k;
+                i = parent[i];
+            }
+            // Move the path to the end of the buffer, keeping its order
+            while len > 0 {
+                top -= 1;
+                len -= 1;
+                self.buffer[top] = self.buffer[len];
+            }
+        }
+        &self.buffer[top..]
+    }
+}
+
+fn scatter_column<F: Float + NumAssign>(c: &Csc<F>, k: usize, x: &mut [F]) {
+    for p in c.column(k) {
+        x[c.rowidx[p]] += c.values[p];
+    }
+}
+
+fn permute<F: Copy>(perm: &[usize], b: &[F]) -> Vec<F> {
+    perm.iter().map(|&i| b[i]).collect()
+}
+
+fn unpermute<F: Float>(perm: &[usize], y: &[F]) -> Vec<F> {
+    let mut x = vec![F::zero(); y.len()];
+    for (k, &i) in perm.iter().enumerate() {
+        x[i] = y[k];
+    }
+    x
+}
+
+/// Sparse Cholesky factorization `P A Pᵀ = L Lᵀ` of a symmetric positive definite matrix
+#[derive(Debug, Clone)]
+pub struct SparseCholesky<F> {
+    symbolic: SymbolicCholesky,
+    l: Csc<F>,
+}
+
+/// Compute the sparse Cholesky factorization of a symmetric positive definite matrix
+///
+/// Both triangles of the matrix must be stored.
+///
+/// # Arguments
+///
+/// * `a` - Symmetric positive definite sparse matrix
+/// * `ordering` - Symmetric fill-reducing ordering, usually [`OrderingMethod::Amd`]
+///
+/// # Returns
+///
+/// * The factorization, reusable for many right-hand sides
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_sparse::csr::CsrMatrix;
+/// use scirs2_sparse::linalg::{sparse_cholesky, OrderingMethod};
+///
+/// // 1D Laplacian
+/// let rows = vec![0, 0, 1, 1, 1, 2, 2];
+/// let cols = vec![0, 1, 0, 1, 2, 1, 2];
+/// let data = vec![2.0f64, -1.0, -1.0, 2.0, -1.0, -1.0, 2.0];
+/// let a = CsrMatrix::new(data, rows, cols, (3, 3)).unwrap();
+///
+/// let chol = sparse_cholesky(&a, OrderingMethod::Amd).unwrap();
+/// let x = chol.solve(&[1.0, 0.0, 1.0]).unwrap();
+/// assert!(x.iter().all(|&xi| (xi - 1.0).abs() < 1e-12));
+/// ```
+pub fn sparse_cholesky<F>(
+    a: &CsrMatrix<F>,
+    ordering: OrderingMethod,
+) -> SparseResult<SparseCholesky<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    let symbolic = SymbolicCholesky::analyze(a, ordering)?;
+    let mut chol = SparseCholesky {
+        symbolic,
+        l: Csc {
+            colptr: Vec::new(),
+            rowidx: Vec::new(),
+            values: Vec::new(),
+        },
+    };
+    chol.refactor(a)?;
+    Ok(chol)
+}
+
+impl<F> SparseCholesky<F>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    /// Factorize new values with the same sparsity pattern, reusing the symbolic analysis
+    pub fn refactor(&mut self, a: &CsrMatrix<F>) -> SparseResult<()> {
+        self.symbolic.check(a)?;
+        let SymbolicCholesky {
+            pinv,
+            parent,
+            colptr,
+            ..
+        } = &self.symbolic;
+        let n = pinv.len();
+        let c = upper_permuted(a, pinv);
+        let nnz = colptr[n];
+        let mut l = Csc {
+            colptr: colptr.clone(),
+            rowidx: vec![0; nnz],
+            values: vec![F::zero(); nnz],
+        };
+        let mut next = colptr.clone();
+        let mut x = vec![F::zero(); n];
+        let mut reach = EtreeReach::new(n);
+
+        for k in 0..n {
+            scatter_column(&c, k, &mut x);
+            let mut d = x[k];
+            x[k] = F::zero();
+            for &i in reach.row_pattern(&c, k, parent) {
+                let lki = x[i] / l.values[colptr[i]];
+                x[i] = F::zero();
+                for p in colptr[i] + 1..next[i] {
+                    x[l.rowidx[p]] -= l.values[p] * lki;
+                }
+                d -= lki * lki;
+                let p = next[i];
+                if p >= colptr[i + 1] {
+                    return Err(SymbolicCholesky::pattern_changed());
+                }
+                next[i] += 1;
+                l.rowidx[p] = k;
+                l.values[p] = lki;
+            }
+            if d <= F::zero() || !d.is_finite() {
+                return Err(SparseError::ValueError(format!(
+                    "Matrix is not positive definite (pivot {} is {:?})",
+                    k, d
+                )));
+            }
+            l.rowidx[next[k]] = k;
+            l.values[next[k]] = d.sqrt();
+            next[k] += 1;
+        }
+        if next[..n] != colptr[1..] {
+            return Err(SymbolicCholesky::pattern_changed());
+        }
+        self.l = l;
+        Ok(())
+    }
+
+    /// Solve `A x = b`
+    pub fn solve(&self, b: &[F]) -> SparseResult<Vec<F>> {
+        let perm = &self.symbolic.perm;
+        check_rhs(perm.len(), b.len())?;
+        let l = &self.l;
+        let mut y = permute(perm, b);
+        for j in 0..y.len() {
+            y[j] /= l.values[l.colptr[j]];
+            let yj = y[j];
+            for p in l.colptr[j] + 1..l.colptr[j + 1] {
+                y[l.rowidx[p]] -= l.values[p] * yj;
+            }
+        }
+        for j in (0..y.len()).rev() {
+            for p in l.colptr[j] + 1..l.colptr[j + 1] {
+                let yi = y[l.rowidx[p]];
+                y[j] -= l.values[p] * yi;
+            }
+            y[j] /= l.values[l.colptr[j]];
+        }
+        Ok(unpermute(perm, &y))
+    }
+
+    /// Lower triangular factor `L` of the permuted matrix
+    pub fn l(&self) -> SparseResult<CsrMatrix<F>> {
+        self.l.to_csr(self.symbolic.perm.len())
+    }
+
+    /// Fill-reducing permutation: row `k` of `P A Pᵀ` is row `perm()[k]` of `A`
+    pub fn perm(&self) -> &[usize] {
+        &self.symbolic.perm
+    }
+
+    /// Number of stored entries in `L`
+    pub fn nnz(&self) -> usize {
+        self.l.rowidx.len()
+    }
+}
+
+/// Sparse LDLᵀ factorization `P A Pᵀ = L D Lᵀ` of a symmetric matrix
+///
+/// No pivoting is performed, so the factorization exists for symmetric
+/// positive definite and quasi-definite matrices, but may break down for
+/// general indefinite ones.
+#[derive(Debug, Clone)]
+pub struct SparseLdlt<F> {
+    symbolic: SymbolicCholesky,
+    l: Csc<F>,
+    d: Vec<F>,
+    /// Largest magnitude of an entry of `A`
+    max_entry: F,
+}
+
+/// Compute the sparse LDLᵀ factorization of a symmetric matrix
+///
+/// Both triangles of the matrix must be stored.
+///
+/// # Arguments
+///
+/// * `a` - Symmetric sparse matrix
+/// * `ordering` - Symmetric fill-reducing ordering, usually [`OrderingMethod::Amd`]
+///
+/// # Returns
+///
+/// * The factorization, reusable for many right-hand sides
+pub fn sparse_ldlt<F>(a: &CsrMatrix<F>, ordering: OrderingMethod) -> SparseResult<SparseLdlt<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    let symbolic = SymbolicCholesky::analyze(a, ordering)?;
+    let mut ldlt = SparseLdlt {
+        symbolic,
+        l: Csc {
+            colptr: Vec::new(),
+            rowidx: Vec::new(),
+            values: Vec::new(),
+        },
+        d: Vec::new(),
+        max_entry: F::zero(),
+    };
+    ldlt.refactor(a)?;
+    Ok(ldlt)
+}
+
+impl<F> SparseLdlt<F>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    /// Factorize new values with the same sparsity pattern, reusing the symbolic analysis
+    pub fn refactor(&mut self, a: &CsrMatrix<F>) -> SparseResult<()> {
+        self.symbolic.check(a)?;
+        let SymbolicCholesky {
+            pinv,
+            parent,
+            colptr,
+            ..
+        } = &self.symbolic;
+        let n = pinv.len();
+        let c = upper_permuted(a, pinv);
+        let nnz = colptr[n];
+        let mut l = Csc {
+            colptr: colptr.clone(),
+            rowidx: vec![0; nnz],
+            values: vec![F::zero(); nnz],
+        };
+        let mut d = vec![F::zero(); n];
+        let mut next = colptr.clone();
+        let mut y = vec![F::zero(); n];
+        let mut reach = EtreeReach::new(n);
+
+        for k in 0..n {
+            // The unit diagonal is stored first in each column
+            l.rowidx[next[k]] = k;
+            l.values[next[k]] = F::one();
+            next[k] += 1;
+
+            scatter_column(&c, k, &mut y);
+            let mut dk = y[k];
+            y[k] = F::zero();
+            for &i in reach.row_pattern(&c, k, parent) {
+                let yi = y[i];
+                y[i] = F::zero();
+                for p in colptr[i] + 1..next[i] {
+                    y[l.rowidx[p]] -= l.values[p] * yi;
+                }
+                let lki = yi / d[i];
+                dk -= lki * yi;
+                let p = next[i];
+                if p >= colptr[i + 1] {
+                    return Err(SymbolicCholesky::pattern_changed());
+                }
+                next[i] += 1;
+                l.rowidx[p] = k;
+                l.values[p] = lki;
+            }
+            if dk == F::zero() || !dk.is_finite() {
+                return Err(SparseError::SingularMatrix(format!(
+                    "Zero pivot {} in LDL^T factorization",
+                    k
+                )));
+            }
+            d[k] = dk;
+        }
+        if next[..n] != colptr[1..] {
+            return Err(SymbolicCholesky::pattern_changed());
+        }
+        self.l = l;
+        self.d = d;
+        self.max_entry = a.data.iter().fold(F::zero(), |m, &v| m.max(v.abs()));
+        Ok(())
+    }
+
+    /// Solve `A x = b`
+    pub fn solve(&self, b: &[F]) -> SparseResult<Vec<F>> {
+        let perm = &self.symbolic.perm;
+        check_rhs(perm.len(), b.len())?;
+        let l = &self.l;
+        let mut y = permute(perm, b);
+        for j in 0..y.len() {
+            let yj = y[j];
+            for p in l.colptr[j] + 1..l.colptr[j + 1] {
+                y[l.rowidx[p]] -= l.values[p] * yj;
+            }
+        }
+        for (yj, &dj) in y.iter_mut().zip(&self.d) {
+            *yj /= dj;
+        }
+        for j in (0..y.len()).rev() {
+            for p in l.colptr[j] + 1..l.colptr[j + 1] {
+                let yi = y[l.rowidx[p]];
+                y[j] -= l.values[p] * yi;
+            }
+        }
+        Ok(unpermute(perm, &y))
+    }
+
+    /// Unit lower triangular factor `L` of the permuted matrix
+    pub fn l(&self) -> SparseResult<CsrMatrix<F>> {
+        self.l.to_csr(self.symbolic.perm.len())
+    }
+
+    /// Diagonal of `D`
+    pub fn diagonal(&self) -> &[F] {
+        &self.d
+    }
+
+    /// Numbers of positive, negative and zero eigenvalues of `A` (Sylvester's law of inertia)
+    ///
+    /// Pivots of magnitude at most `n ε max(|a_ij|, |d_k|)` are counted as
+    /// zero, since rounding errors rarely leave the pivot of a singular matrix
+    /// exactly zero.
+    pub fn inertia(&self) -> (usize, usize, usize) {
+        let largest = self.d.iter().fold(self.max_entry, |m, &d| m.max(d.abs()));
+        let tol = F::from(self.d.len()).unwrap_or_else(F::one) * F::epsilon() * largest;
+        let positive = self.d.iter().filter(|&&d| d > tol).count();
+        let negative = self.d.iter().filter(|&&d| d < -tol).count();
+        (positive, negative, self.d.len() - positive - negative)
+    }
+
+    /// Fill-reducing permutation: row `k` of `P A Pᵀ` is row `perm()[k]` of `A`
+    pub fn perm(&self) -> &[usize] {
+        &self.symbolic.perm
+    }
+
+    /// Number of stored entries in `L`
+    pub fn nnz(&self) -> usize {
+        self.l.rowidx.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// 2D Laplacian on a k×k grid, plus `shift` on the diagonal
+    fn laplacian(k: usize, shift: f64) -> CsrMatrix<f64> {
+        let (mut rows, mut cols, mut data) = (Vec::new(), Vec::new(), Vec::new());
+        for i in 0..k {
+            for j in 0..k {
+                let node = i * k + j;
+                rows.push(node);
+                cols.push(node);
+                data.push(4.0 + shift);
+                for (ni, nj) in [(i + 1, j), (i, j + 1)] {
+                    if ni < k && nj < k {
+                        let other = ni * k + nj;
+                        rows.extend([node, other]);
+                        cols.extend([other, node]);
+                        data.extend([-1.0, -1.0]);
+                    }
+                }
+            }
+        }
+        CsrMatrix::new(data, rows, cols, (k * k, k * k)).unwrap()
+    }
+
+    fn residual(a: &CsrMatrix<f64>, x: &[f64], b: &[f64]) -> f64 {
+        let ax = a.dot(x).unwrap();
+        ax.iter()
+            .zip(b)
+            .map(|(l, r)| (l - r).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_splu_unsymmetric() {
+        // Convection-diffusion-like matrix with a zero diagonal entry
+        let n = 50;
+        let (mut rows, mut cols, mut data) = (Vec::new(), Vec::new(), Vec::new());
+        for i in 0..n {
+            if i != 7 {
+                rows.push(i);
+                cols.push(i);
+                data.push(3.0 + i as f64 * 0.01);
+            }
+            rows.push(i);
+            cols.push((i + 1) % n);
+            data.push(-1.5);
+            rows.push((i + 3) % n);
+            cols.push(i);
+            data.push(-0.5);
+        }
+        let a = CsrMatrix::new(data, rows, cols, (n, n)).unwrap();
+        let b: Vec<f64> = (0..n).map(|i| (i as f64).sin()).collect();
+        for ordering in [
+            OrderingMethod::Natural,
+            OrderingMethod::Colamd,
+            OrderingMethod::Amd,
+            OrderingMethod::ReverseCuthillMcKee,
+        ] {
+            let options = SpluOptions {
+                ordering,
+                pivot_threshold: 0.1,
+            };
+            let lu = splu(&a, options).unwrap();
+            assert!(residual(&a, &lu.solve(&b).unwrap(), &b) < 1e-12);
+            let at = a.transpose();
+            assert!(residual(&at, &lu.solve_transpose(&b).unwrap(), &b) < 1e-12);
+        }
+
+        // L U reproduces P A Q
+        let lu = splu(&a, SpluOptions::default()).unwrap();
+        let (l, u) = (lu.l().unwrap().to_dense(), lu.u().unwrap().to_dense());
+        let dense = a.to_dense();
+        for (i, l_row) in l.iter().enumerate() {
+            for j in 0..n {
+                let lu_ij: f64 = (0..n).map(|k| l_row[k] * u[k][j]).sum();
+                let row = lu.perm_r().iter().position(|&r| r == i).unwrap();
+                assert_relative_eq!(lu_ij, dense[row][lu.perm_c()[j]], epsilon = 1e-12);
+            }
+        }
+
+        let singular = CsrMatrix::new(vec![1.0, 2.0], vec![0, 1], vec![0, 0], (2, 2)).unwrap();
+        assert!(splu(&singular, SpluOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_cholesky_and_refactor() {
+        let a = laplacian(15, 0.0);
+        let n = a.rows();
+        let b: Vec<f64> = (0..n).map(|i| 1.0 + (i % 7) as f64).collect();
+        let natural = sparse_cholesky(&a, OrderingMethod::Natural).unwrap();
+        let mut chol = sparse_cholesky(&a, OrderingMethod::Amd).unwrap();
+        assert!(chol.nnz() < natural.nnz());
+        assert!(residual(&a, &chol.solve(&b).unwrap(), &b) < 1e-10);
+
+        let shifted = laplacian(15, 1.5);
+        chol.refactor(&shifted).unwrap();
+        assert!(residual(&shifted, &chol.solve(&b).unwrap(), &b) < 1e-10);
+
+        assert!(sparse_cholesky(&laplacian(4, -6.0), OrderingMethod::Amd).is_err());
+    }
+
+    #[test]
+    fn test_ldlt_indefinite() {
+        let a = laplacian(10, -2.7);
+        let b: Vec<f64> = (0..a.rows()).map(|i| (i as f64).cos()).collect();
+        let ldlt = sparse_ldlt(&a, OrderingMethod::Amd).unwrap();
+        assert!(residual(&a, &ldlt.solve(&b).unwrap(), &b) < 1e-9);
+        // Eigenvalues of the shifted Laplacian are 4 sin²(..) + 4 sin²(..) - 2.7
+        let (positive, negative, zero) = ldlt.inertia();
+        assert!(positive > 0 && negative > 0);
+        assert_eq!(positive + negative + zero, 100);
+    }
+
+    #[test]
+    fn test_ldlt_inertia_singular() {
+        // Singular, but rounding leaves a pivot of about -2e-16
+        let c = 0.7 * 0.7 / 0.3;
+        let a = CsrMatrix::new(
+            vec![0.3, 0.7, 0.7, c],
+            vec![0, 0, 1, 1],
+            vec![0, 1, 0, 1],
+            (2, 2),
+        )
+        .unwrap();
+        let ldlt = sparse_ldlt(&a, OrderingMethod::Natural).unwrap();
+        assert_eq!(ldlt.inertia(), (1, 0, 1));
+    }
+}
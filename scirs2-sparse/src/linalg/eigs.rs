@@ -0,0 +1,826 @@
+//! Implicitly restarted Lanczos and Arnoldi eigensolvers
+//!
+//! [`eigsh`] computes a few eigenpairs of a symmetric operator with the
+//! implicitly restarted Lanczos method and [`eigs`] those of a general
+//! operator with the implicitly restarted Arnoldi method, in the spirit of
+//! ARPACK and SciPy's functions of the same names. Both support generalized
+//! problems `A x = λ M x` and shift-invert mode for interior eigenvalues.
+
+use crate::error::{SparseError, SparseResult};
+use crate::linalg::dense_eig::{eigenvector, hessenberg_eigenvalues, qr_q, symmetric_eigen};
+use crate::linalg::interface::LinearOperator;
+use ndarray::{Array1, Array2};
+use num_complex::Complex;
+use num_traits::{Float, NumAssign};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::iter::Sum;
+
+/// Which eigenvalues to compute
+///
+/// In shift-invert mode the criterion applies to the transformed eigenvalues
+/// `1 / (λ - σ)`, so [`Which::LargestMagnitude`] selects the eigenvalues
+/// closest to `σ`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Which {
+    /// Largest magnitude
+    LargestMagnitude,
+    /// Smallest magnitude
+    SmallestMagnitude,
+    /// Largest real part (largest algebraic for symmetric problems)
+    LargestReal,
+    /// Smallest real part (smallest algebraic for symmetric problems)
+    SmallestReal,
+    /// Largest imaginary part
+    LargestImaginary,
+    /// Smallest imaginary part
+    SmallestImaginary,
+}
+
+impl Which {
+    /// Order in which Ritz values are preferred
+    fn compare<F: Float>(self, x: &Complex<F>, y: &Complex<F>) -> Ordering {
+        let key = |z: &Complex<F>| match self {
+            Which::LargestMagnitude => -z.norm(),
+            Which::SmallestMagnitude => z.norm(),
+            Which::LargestReal => -z.re,
+            Which::SmallestReal => z.re,
+            Which::LargestImaginary => -z.im.abs(),
+            Which::SmallestImaginary => z.im.abs(),
+        };
+        key(x).partial_cmp(&key(y)).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Options for [`eigsh`] and [`eigs`]
+pub struct EigsOptions<F: Float> {
+    /// Number of eigenpairs to compute
+    pub k: usize,
+    /// Which eigenvalues to compute
+    pub which: Which,
+    /// Shift for shift-invert mode; requires `op_inv`
+    pub sigma: Option<F>,
+    /// Mass matrix `M` of the generalized problem `A x = λ M x`
+    /// (symmetric positive definite for [`eigsh`])
+    pub m: Option<Box<dyn LinearOperator<F>>>,
+    /// Operator applying `M⁻¹`, required for generalized problems without shift
+    pub m_inv: Option<Box<dyn LinearOperator<F>>>,
+    /// Operator applying `(A - σ M)⁻¹`, required in shift-invert mode,
+    /// e.g. an [`InverseOperator`](crate::linalg::InverseOperator)
+    pub op_inv: Option<Box<dyn LinearOperator<F>>>,
+    /// Number of Lanczos/Arnoldi vectors (default `max(2k + 1, 20)`, at most n)
+    pub ncv: Option<usize>,
+    /// Maximum number of restarts
+    pub max_iter: usize,
+    /// Relative accuracy of the Ritz values (machine precision if zero)
+    pub tol: F,
+    /// Starting vector
+    pub v0: Option<Vec<F>>,
+    /// Whether to compute eigenvectors
+    pub return_eigenvectors: bool,
+}
+
+impl<F: Float> Default for EigsOptions<F> {
+    fn default() -> Self {
+        Self {
+            k: 6,
+            which: Which::LargestMagnitude,
+            sigma: None,
+            m: None,
+            m_inv: None,
+            op_inv: None,
+            ncv: None,
+            max_iter: 1000,
+            tol: F::zero(),
+            v0: None,
+            return_eigenvectors: true,
+        }
+    }
+}
+
+/// Result of [`eigsh`]
+#[derive(Debug, Clone)]
+pub struct EigshResult<F> {
+    /// Eigenvalues in ascending order
+    pub eigenvalues: Array1<F>,
+    /// Eigenvectors as columns (M-orthonormal for generalized problems)
+    pub eigenvectors: Option<Array2<F>>,
+    /// Number of restarts performed
+    pub iterations: usize,
+    /// Whether all requested eigenpairs converged
+    pub converged: bool,
+}
+
+/// Result of [`eigs`]
+#[derive(Debug, Clone)]
+pub struct EigsResult<F> {
+    /// Eigenvalues, ordered by the `which` criterion
+    pub eigenvalues: Array1<Complex<F>>,
+    /// Unit eigenvectors as columns
+    pub eigenvectors: Option<Array2<Complex<F>>>,
+    /// Number of restarts performed
+    pub iterations: usize,
+    /// Whether all requested eigenpairs converged
+    pub converged: bool,
+}
+
+/// Spectral transformation: the operator iterated on and the inner product
+struct Problem<'a, F: Float> {
+    n: usize,
+    a: &'a dyn LinearOperator<F>,
+    options: &'a EigsOptions<F>,
+}
+
+impl<F> Problem<'_, F>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    /// Apply the operator of the spectral transformation
+    fn op(&self, x: &[F]) -> SparseResult<Vec<F>> {
+        let o = self.options;
+        match (o.sigma, &o.op_inv, &o.m, &o.m_inv) {
+            // (A - σM)⁻¹ M x
+            (Some(_), Some(op_inv), Some(m), _) => op_inv.matvec(&m.matvec(x)?),
+            (Some(_), Some(op_inv), None, _) => op_inv.matvec(x),
+            (Some(_), None, _, _) => Err(SparseError::ValueError(
+                "Shift-invert mode requires `op_inv`, an operator applying (A - sigma M)^-1"
+                    .to_string(),
+            )),
+            // M⁻¹ A x
+            (None, _, Some(_), Some(m_inv)) => m_inv.matvec(&self.a.matvec(x)?),
+            (None, _, Some(_), None) => Err(SparseError::ValueError(
+                "Generalized problems without shift require `m_inv`, an operator applying M^-1"
+                    .to_string(),
+            )),
+            (None, _, None, _) => self.a.matvec(x),
+        }
+    }
+
+    /// Apply the operator defining the inner product (`M` or the identity)
+    fn inner(&self, x: &[F]) -> SparseResult<Vec<F>> {
+        match &self.options.m {
+            Some(m) => m.matvec(x),
+            None => Ok(x.to_vec()),
+        }
+    }
+
+    /// Map an eigenvalue of the operator back to the original problem
+    fn transform(&self, theta: Complex<F>) -> Complex<F> {
+        match self.options.sigma {
+            Some(sigma) => {
+                Complex::new(sigma, F::zero()) + Complex::new(F::one(), F::zero()) / theta
+            }
+            None => theta,
+        }
+    }
+}
+
+fn dot<F: Float>(x: &[F], y: &[F]) -> F {
+    x.iter().zip(y).fold(F::zero(), |acc, (&a, &b)| acc + a * b)
+}
+
+/// Deterministic pseudo-random vector (xorshift), as ARPACK uses a fixed seed
+fn random_vector<F: Float>(n: usize, seed: u64) -> Vec<F> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..n)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            F::from((state >> 11) as f64 / (1u64 << 53) as f64 - 0.5).unwrap()
+        })
+        .collect()
+}
+
+/// Arnoldi factorization `OP V = V H + f e_mᵀ` with B-orthonormal `V`
+struct Arnoldi<F> {
+    v: Vec<Vec<F>>,
+    bv: Vec<Vec<F>>,
+    h: Vec<Vec<F>>,
+    f: Vec<F>,
+    bf: Vec<F>,
+    seed: u64,
+}
+
+impl<F> Arnoldi<F>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    fn residual_norm(&self) -> F {
+        dot(&self.f, &self.bf).max(F::zero()).sqrt()
+    }
+
+    /// Orthogonalize `w` against the basis twice (classical Gram–Schmidt with
+    /// reorthogonalization); returns the coefficients and `B w`
+    fn orthogonalize(
+        &self,
+        problem: &Problem<F>,
+        w: &mut [F],
+        count: usize,
+    ) -> SparseResult<(Vec<F>, Vec<F>)> {
+        let mut coefficients = vec![F::zero(); count];
+        for _ in 0..2 {
+            for (j, c) in coefficients.iter_mut().enumerate() {
+                let hj = dot(&self.bv[j], w);
+                *c += hj;
+                for (wi, &vi) in w.iter_mut().zip(&self.v[j]) {
+                    *wi -= hj * vi;
+                }
+            }
+        }
+        let bw = problem.inner(w)?;
+        Ok((coefficients, bw))
+    }
+
+    /// Extend the factorization from `self.v.len()` to `m` vectors
+    fn extend(&mut self, problem: &Problem<F>, m: usize) -> SparseResult<()> {
+        let scale = self
+            .h
+            .iter()
+            .flatten()
+            .fold(F::zero(), |acc, &x| acc.max(x.abs()));
+        while self.v.len() < m {
+            let j = self.v.len();
+            let mut beta = self.residual_norm();
+            if j > 0 && beta <= F::epsilon() * scale.max(F::one()) {
+                // Invariant subspace found: continue with a fresh direction
+                beta = F::zero();
+                for attempt in 0..3 {
+                    self.seed += 1;
+                    let mut w = problem.op(&random_vector(problem.n, self.seed))?;
+                    let (_, bw) = self.orthogonalize(problem, &mut w, j)?;
+                    let norm = dot(&w, &bw).max(F::zero()).sqrt();
+                    if norm > F::epsilon() || attempt == 2 {
+                        self.f = w;
+                        self.bf = bw;
+                        break;
+                    }
+                }
+            }
+            let fnorm = self.residual_norm();
+            if fnorm == F::zero() {
+                return Err(SparseError::ComputationError(
+                    "Cannot extend the Krylov subspace".to_string(),
+                ));
+            }
+            let vj: Vec<F> = self.f.iter().map(|&x| x / fnorm).collect();
+            let bvj: Vec<F> = self.bf.iter().map(|&x| x / fnorm).collect();
+            if j > 0 {
+                self.h[j][j - 1] = beta;
+            }
+            self.v.push(vj);
+            self.bv.push(bvj);
+
+            let mut w = problem.op(&self.v[j])?;
+            let (coefficients, bw) = self.orthogonalize(problem, &mut w, j + 1)?;
+            for (i, c) in coefficients.into_iter().enumerate() {
+                self.h[i][j] = c;
+            }
+            self.f = w;
+            self.bf = bw;
+        }
+        Ok(())
+    }
+
+    /// Apply the shifts as implicit QR steps and truncate to `k` vectors
+    fn restart(&mut self, shifts: &[Complex<F>], k: usize) {
+        let m = self.v.len();
+        let mut q_total: Vec<Vec<F>> = (0..m)
+            .map(|i| {
+                (0..m)
+                    .map(|j| if i == j { F::one() } else { F::zero() })
+                    .collect()
+            })
+            .collect();
+        let mut i = 0;
+        while i < shifts.len() {
+            let mu = shifts[i];
+            // Real shift: H - μI; conjugate pair: H² - 2 Re(μ) H + |μ|² I
+            let shifted: Vec<Vec<F>> = if mu.im == F::zero() {
+                (0..m)
+                    .map(|r| {
+                        (0..m)
+                            .map(|c| self.h[r][c] - if r == c { mu.re } else { F::zero() })
+                            .collect()
+                    })
+                    .collect()
+            } else {
+                let two = F::one() + F::one();
+                (0..m)
+                    .map(|r| {
+                        (0..m)
+                            .map(|c| {
+                                let h2: F = (0..m).map(|t| self.h[r][t] * self.h[t][c]).sum();
+                                h2 - two * mu.re * self.h[r][c]
+                                    + if r == c { mu.norm_sqr() } else { F::zero() }
+                            })
+                            .collect()
+                    })
+                    .collect()
+            };
+            i += if mu.im == F::zero() { 1 } else { 2 };
+            let q = qr_q(&shifted);
+            // H <- Qᵀ H Q
+            let hq: Vec<Vec<F>> = (0..m)
+                .map(|r| {
+                    (0..m)
+                        .map(|c| (0..m).map(|t| self.h[r][t] * q[t][c]).sum())
+                        .collect()
+                })
+                .collect();
+            self.h = (0..m)
+                .map(|r| {
+                    (0..m)
+                        .map(|c| (0..m).map(|t| q[t][r] * hq[t][c]).sum())
+                        .collect()
+                })
+                .collect();
+            for (r, row) in self.h.iter_mut().enumerate() {
+                for x in row.iter_mut().take(r.saturating_sub(1)) {
+                    *x = F::zero();
+                }
+            }
+            q_total = (0..m)
+                .map(|r| {
+                    (0..m)
+                        .map(|c| (0..m).map(|t| q_total[r][t] * q[t][c]).sum())
+                        .collect()
+                })
+                .collect();
+        }
+
+        // New residual: f = v_{k+1} H[k][k-1] + f Q[m-1][k-1]
+        let combine = |basis: &[Vec<F>], j: usize| -> Vec<F> {
+            let mut out = vec![F::zero(); basis[0].len()];
+            for (t, vt) in basis.iter().enumerate() {
+                let coefficient = q_total[t][j];
+                if coefficient != F::zero() {
+                    for (o, &x) in out.iter_mut().zip(vt) {
+                        *o += coefficient * x;
+                    }
+                }
+            }
+            out
+        };
+        let beta = self.h[k][k - 1];
+        let sigma = q_total[m - 1][k - 1];
+        let vk = combine(&self.v, k);
+        let bvk = combine(&self.bv, k);
+        self.f = vk
+            .iter()
+            .zip(&self.f)
+            .map(|(&v, &f)| v * beta + f * sigma)
+            .collect();
+        self.bf = bvk
+            .iter()
+            .zip(&self.bf)
+            .map(|(&v, &f)| v * beta + f * sigma)
+            .collect();
+        let v: Vec<Vec<F>> = (0..k).map(|j| combine(&self.v, j)).collect();
+        let bv: Vec<Vec<F>> = (0..k).map(|j| combine(&self.bv, j)).collect();
+        self.v = v;
+        self.bv = bv;
+        for (r, row) in self.h.iter_mut().enumerate() {
+            for (c, x) in row.iter_mut().enumerate() {
+                if r >= k || c >= k {
+                    *x = F::zero();
+                }
+            }
+        }
+    }
+}
+
+/// Ritz values, vectors and error estimates of the current factorization
+struct Ritz<F> {
+    values: Vec<Complex<F>>,
+    vectors: Vec<Vec<Complex<F>>>,
+    errors: Vec<F>,
+}
+
+fn ritz<F: Float>(h: &[Vec<F>], beta: F, symmetric: bool) -> Ritz<F> {
+    let m = h.len();
+    let (values, vectors): (Vec<Complex<F>>, Vec<Vec<Complex<F>>>) = if symmetric {
+        let (values, y) = symmetric_eigen(h);
+        (
+            values.iter().map(|&x| Complex::new(x, F::zero())).collect(),
+            (0..m)
+                .map(|i| (0..m).map(|r| Complex::new(y[r][i], F::zero())).collect())
+                .collect(),
+        )
+    } else {
+        let values = hessenberg_eigenvalues(h);
+        let vectors = values
+            .iter()
+            .map(|&lambda| eigenvector(h, lambda))
+            .collect();
+        (values, vectors)
+    };
+    let errors = vectors.iter().map(|y| beta * y[m - 1].norm()).collect();
+    Ritz {
+        values,
+        vectors,
+        errors,
+    }
+}
+
+/// Result of the implicitly restarted iteration: eigenvalues of the operator and Ritz vectors
+struct Converged<F> {
+    values: Vec<Complex<F>>,
+    vectors: Option<Vec<Vec<Complex<F>>>>,
+    iterations: usize,
+    converged: bool,
+}
+
+fn implicitly_restarted<F>(
+    a: &dyn LinearOperator<F>,
+    options: &EigsOptions<F>,
+    symmetric: bool,
+) -> SparseResult<Converged<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    let (rows, cols) = a.shape();
+    if rows != cols {
+        return Err(SparseError::ValueError(format!(
+            "Operator must be square, got {}x{}",
+            rows, cols
+        )));
+    }
+    let n = rows;
+    let k = options.k;
+    let max_k = if symmetric { n } else { n.saturating_sub(1) };
+    if k == 0 || k >= max_k.max(1) {
+        return Err(SparseError::ValueError(format!(
+            "k must satisfy 0 < k < {} for an operator of size {}",
+            max_k, n
+        )));
+    }
+    for operator in [&options.m, &options.m_inv, &options.op_inv]
+        .into_iter()
+        .flatten()
+    {
+        if operator.shape() != (n, n) {
+            return Err(SparseError::ShapeMismatch {
+                expected: (n, n),
+                found: operator.shape(),
+            });
+        }
+    }
+    let m = options
+        .ncv
+        .unwrap_or_else(|| (2 * k + 1).max(20))
+        .clamp(k + 2, n);
+    let tol = if options.tol > F::zero() {
+        options.tol
+    } else {
+        F::epsilon()
+    };
+    let eps23 = F::epsilon().powf(F::from(2.0 / 3.0).unwrap());
+    let problem = Problem { n, a, options };
+
+    // Start in the range of the operator, as ARPACK does for generalized modes
+    let start = match &options.v0 {
+        Some(v0) if v0.len() == n => v0.clone(),
+        Some(v0) => {
+            return Err(SparseError::DimensionMismatch {
+                expected: n,
+                found: v0.len(),
+            })
+        }
+        None => random_vector(n, 1),
+    };
+    let f = if options.m.is_some() || options.sigma.is_some() {
+        problem.op(&start)?
+    } else {
+        start
+    };
+    let bf = problem.inner(&f)?;
+    let mut arnoldi = Arnoldi {
+        v: Vec::with_capacity(m),
+        bv: Vec::with_capacity(m),
+        h: vec![vec![F::zero(); m]; m],
+        f,
+        bf,
+        seed: 1,
+    };
+    arnoldi.extend(&problem, m)?;
+
+    let mut iterations = 0;
+    loop {
+        let ritz = ritz(&arnoldi.h, arnoldi.residual_norm(), symmetric);
+        let mut order: Vec<usize> = (0..m).collect();
+        order.sort_by(|&x, &y| options.which.compare(&ritz.values[x], &ritz.values[y]));
+        let is_converged = |i: usize| ritz.errors[i] <= tol * eps23.max(ritz.values[i].norm());
+        let nconv = order[..k].iter().filter(|&&i| is_converged(i)).count();
+        if nconv == k || iterations >= options.max_iter {
+            let wanted = &order[..k];
+            let values = wanted
+                .iter()
+                .map(|&i| problem.transform(ritz.values[i]))
+                .collect();
+            let vectors = options.return_eigenvectors.then(|| {
+                wanted
+                    .iter()
+                    .map(|&i| {
+                        let mut x = vec![Complex::new(F::zero(), F::zero()); n];
+                        for (vj, &yj) in arnoldi.v.iter().zip(&ritz.vectors[i]) {
+                            for (xr, &v) in x.iter_mut().zip(vj) {
+                                *xr += yj * v;
+                            }
+                        }
+                        x
+                    })
+                    .collect()
+            });
+            return Ok(Converged {
+                values,
+                vectors,
+                iterations,
+                converged: nconv == k,
+            });
+        }
+
+        // Keep extra Ritz values to avoid stagnation, and conjugate pairs together
+        let mut keep = (k + nconv.min((m - k) / 2)).min(m - 1);
+        while keep < m - 1 {
+            let last = ritz.values[order[keep - 1]];
+            let next = ritz.values[order[keep]];
+            if last.im != F::zero() && next == last.conj() {
+                keep += 1;
+            } else {
+                break;
+            }
+        }
+        let mut shifts: Vec<Complex<F>> = order[keep..]
+            .iter()
+            .map(|&i| ritz.values[i])
+            .filter(|z| z.im >= F::zero())
+            .collect();
+        // A pair whose partner is wanted cannot be applied in real arithmetic
+        shifts.retain(|z| {
+            z.im == F::zero() || order[keep..].iter().any(|&i| ritz.values[i] == z.conj())
+        });
+        if shifts.is_empty() {
+            iterations = options.max_iter;
+            continue;
+        }
+        arnoldi.restart(&shifts, keep);
+        arnoldi.extend(&problem, m)?;
+        iterations += 1;
+    }
+}
+
+/// Find eigenvalues and eigenvectors of a symmetric operator
+///
+/// Uses the implicitly restarted Lanczos method with full reorthogonalization.
+/// Generalized problems `A x = λ M x` require `options.m` (symmetric positive
+/// definite) together with `options.m_inv`. With `options.sigma`, the
+/// eigenvalues closest to σ are found in shift-invert mode through
+/// `options.op_inv`.
+///
+/// # Arguments
+///
+/// * `a` - Symmetric linear operator
+/// * `options` - Number of eigenpairs, selection and spectral transformation
+///
+/// # Returns
+///
+/// * Eigenvalues in ascending order and optionally the eigenvectors
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_sparse::csr::CsrMatrix;
+/// use scirs2_sparse::linalg::{eigsh, AsLinearOperator, EigsOptions, InverseOperator};
+///
+/// // 1D Laplacian: eigenvalues 2 - 2 cos(jπ/(n+1))
+/// let n = 100;
+/// let (mut rows, mut cols, mut data) = (Vec::new(), Vec::new(), Vec::new());
+/// for i in 0..n {
+///     rows.push(i); cols.push(i); data.push(2.0);
+///     if i + 1 < n {
+///         rows.extend([i, i + 1]); cols.extend([i + 1, i]); data.extend([-1.0, -1.0]);
+///     }
+/// }
+/// let a = CsrMatrix::new(data, rows, cols, (n, n)).unwrap();
+///
+/// // The three smallest eigenvalues, by shift-invert around zero
+/// let options = EigsOptions {
+///     k: 3,
+///     sigma: Some(0.0),
+///     op_inv: Some(Box::new(InverseOperator::shifted(&a, None, 0.0).unwrap())),
+///     ..Default::default()
+/// };
+/// let result = eigsh(a.as_linear_operator().as_ref(), &options).unwrap();
+/// let exact = 2.0 - 2.0 * (std::f64::consts::PI / (n + 1) as f64).cos();
+/// assert!((result.eigenvalues[0] - exact).abs() < 1e-10);
+/// ```
+pub fn eigsh<F>(a: &dyn LinearOperator<F>, options: &EigsOptions<F>) -> SparseResult<EigshResult<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    let result = implicitly_restarted(a, options, true)?;
+    let n = a.shape().0;
+    let mut order: Vec<usize> = (0..result.values.len()).collect();
+    order.sort_by(|&x, &y| {
+        result.values[x]
+            .re
+            .partial_cmp(&result.values[y].re)
+            .unwrap_or(Ordering::Equal)
+    });
+    let eigenvalues = order.iter().map(|&i| result.values[i].re).collect();
+    let eigenvectors = result
+        .vectors
+        .map(|vectors| Array2::from_shape_fn((n, order.len()), |(r, c)| vectors[order[c]][r].re));
+    Ok(EigshResult {
+        eigenvalues,
+        eigenvectors,
+        iterations: result.iterations,
+        converged: result.converged,
+    })
+}
+
+/// Find eigenvalues and eigenvectors of a general (nonsymmetric) operator
+///
+/// Uses the implicitly restarted Arnoldi method with exact shifts. Generalized
+/// problems and shift-invert mode work as for [`eigsh`]; the shift σ is real.
+///
+/// # Arguments
+///
+/// * `a` - Square linear operator
+/// * `options` - Number of eigenpairs, selection and spectral transformation
+///
+/// # Returns
+///
+/// * Complex eigenvalues ordered by `options.which` and optionally the eigenvectors
+pub fn eigs<F>(a: &dyn LinearOperator<F>, options: &EigsOptions<F>) -> SparseResult<EigsResult<F>>
+where
+    F: Float + NumAssign + Sum + Debug + 'static,
+{
+    let result = implicitly_restarted(a, options, false)?;
+    let n = a.shape().0;
+    let eigenvectors = result.vectors.map(|vectors| {
+        let norms: Vec<F> = vectors
+            .iter()
+            .map(|x| x.iter().fold(F::zero(), |acc, z| acc + z.norm_sqr()).sqrt())
+            .collect();
+        Array2::from_shape_fn((n, vectors.len()), |(r, c)| vectors[c][r] / norms[c])
+    });
+    Ok(EigsResult {
+        eigenvalues: Array1::from(result.values),
+        eigenvectors,
+        iterations: result.iterations,
+        converged: result.converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csr::CsrMatrix;
+    use crate::linalg::{AsLinearOperator, DiagonalOperator, InverseOperator};
+
+    fn laplacian_1d(n: usize) -> CsrMatrix<f64> {
+        let (mut rows, mut cols, mut data) = (Vec::new(), Vec::new(), Vec::new());
+        for i in 0..n {
+            rows.push(i);
+            cols.push(i);
+            data.push(2.0);
+            if i + 1 < n {
+                rows.extend([i, i + 1]);
+                cols.extend([i + 1, i]);
+                data.extend([-1.0, -1.0]);
+            }
+        }
+        CsrMatrix::new(data, rows, cols, (n, n)).unwrap()
+    }
+
+    fn exact_laplacian(n: usize, j: usize) -> f64 {
+        2.0 - 2.0 * (j as f64 * std::f64::consts::PI / (n + 1) as f64).cos()
+    }
+
+    #[test]
+    fn test_eigsh_modes() {
+        let n = 200;
+        let a = laplacian_1d(n);
+        let op = a.as_linear_operator();
+
+        // Largest eigenvalues in regular mode
+        let options = EigsOptions {
+            k: 4,
+            which: Which::LargestReal,
+            ..Default::default()
+        };
+        let result = eigsh(op.as_ref(), &options).unwrap();
+        assert!(result.converged);
+        for (i, &lambda) in result.eigenvalues.iter().enumerate() {
+            assert!((lambda - exact_laplacian(n, n - 3 + i)).abs() < 1e-8);
+        }
+
+        // Interior eigenvalues around σ = 1 by shift-invert
+        let sigma = 1.1;
+        let options = EigsOptions {
+            k: 3,
+            sigma: Some(sigma),
+            op_inv: Some(Box::new(InverseOperator::shifted(&a, None, sigma).unwrap())),
+            ..Default::default()
+        };
+        let result = eigsh(op.as_ref(), &options).unwrap();
+        let mut exact: Vec<f64> = (1..=n).map(|j| exact_laplacian(n, j)).collect();
+        exact.sort_by(|x, y| (x - sigma).abs().partial_cmp(&(y - sigma).abs()).unwrap());
+        let mut nearest = exact[..3].to_vec();
+        nearest.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        for (lambda, expected) in result.eigenvalues.iter().zip(nearest) {
+            assert!((lambda - expected).abs() < 1e-10);
+        }
+        let vectors = result.eigenvectors.unwrap();
+        let x: Vec<f64> = vectors.column(0).to_vec();
+        let ax = a.dot(&x).unwrap();
+        for (l, r) in ax.iter().zip(&x) {
+            assert!((l - result.eigenvalues[0] * r).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_eigsh_generalized() {
+        // A x = λ M x with M = diag(2): eigenvalues are halved
+        let n = 60;
+        let a = laplacian_1d(n);
+        let mass =
+            CsrMatrix::new(vec![2.0; n], (0..n).collect(), (0..n).collect(), (n, n)).unwrap();
+        let options = EigsOptions {
+            k: 2,
+            which: Which::LargestReal,
+            m: Some(Box::new(DiagonalOperator::new(vec![2.0; n]))),
+            m_inv: Some(Box::new(DiagonalOperator::new(vec![0.5; n]))),
+            ..Default::default()
+        };
+        let result = eigsh(a.as_linear_operator().as_ref(), &options).unwrap();
+        assert!((result.eigenvalues[1] - exact_laplacian(n, n) / 2.0).abs() < 1e-8);
+
+        let options = EigsOptions {
+            k: 2,
+            sigma: Some(0.0),
+            m: Some(mass.as_linear_operator()),
+            op_inv: Some(Box::new(
+                InverseOperator::shifted(&a, Some(&mass), 0.0).unwrap(),
+            )),
+            ..Default::default()
+        };
+        let result = eigsh(a.as_linear_operator().as_ref(), &options).unwrap();
+        assert!((result.eigenvalues[0] - exact_laplacian(n, 1) / 2.0).abs() < 1e-10);
+        // Eigenvectors are M-normalized
+        let x = result.eigenvectors.unwrap().column(0).to_vec();
+        assert!((2.0 * dot(&x, &x) - 1.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_eigs_nonsymmetric() {
+        // Upwinded convection-diffusion operator with complex eigenvalues
+        let n = 80;
+        let (mut rows, mut cols, mut data) = (Vec::new(), Vec::new(), Vec::new());
+        for i in 0..n {
+            rows.push(i);
+            cols.push(i);
+            data.push(2.0 + 0.01 * i as f64);
+            rows.push(i);
+            cols.push((i + 1) % n);
+            data.push(-1.4);
+            rows.push((i + 1) % n);
+            cols.push(i);
+            data.push(-0.6);
+        }
+        let a = CsrMatrix::new(data, rows, cols, (n, n)).unwrap();
+        let op = a.as_linear_operator();
+        for (which, sigma) in [
+            (Which::LargestMagnitude, None),
+            (Which::LargestImaginary, None),
+            (Which::LargestMagnitude, Some(1.0)),
+        ] {
+            let options = EigsOptions {
+                k: 4,
+                which,
+                sigma,
+                op_inv: sigma.map(|s| {
+                    Box::new(InverseOperator::shifted(&a, None, s).unwrap())
+                        as Box<dyn LinearOperator<f64>>
+                }),
+                ncv: Some(30),
+                ..Default::default()
+            };
+            let result = eigs(op.as_ref(), &options).unwrap();
+            assert!(result.converged);
+            let vectors = result.eigenvectors.unwrap();
+            for (c, &lambda) in result.eigenvalues.iter().enumerate() {
+                let x = vectors.column(c);
+                let re: Vec<f64> = x.iter().map(|z| z.re).collect();
+                let im: Vec<f64> = x.iter().map(|z| z.im).collect();
+                let (are, aim) = (a.dot(&re).unwrap(), a.dot(&im).unwrap());
+                for r in 0..n {
+                    let residual = Complex::new(are[r], aim[r]) - lambda * x[r];
+                    assert!(residual.norm() < 1e-8, "{:?} {:?}", which, lambda);
+                }
+            }
+        }
+    }
+}
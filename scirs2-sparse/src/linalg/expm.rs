@@ -4,6 +4,7 @@
 //! method with Padé approximation.
 
 use crate::csr::CsrMatrix;
+use crate::element::SparseElement;
 use crate::error::{SparseError, SparseResult};
 use num_traits::{Float, NumAssign, One, Zero};
 use std::iter::Sum;
@@ -28,7 +29,7 @@ use std::iter::Sum;
 /// on the matrix norm to ensure numerical stability.
 pub fn expm<F>(a: &CsrMatrix<F>) -> SparseResult<CsrMatrix<F>>
 where
-    F: Float + NumAssign + Sum + 'static + std::fmt::Debug,
+    F: Float + NumAssign + Sum + SparseElement + std::fmt::Debug,
 {
     let (rows, cols) = a.shape();
     if rows != cols {
@@ -79,7 +80,7 @@ where
 /// Uses the diagonal Padé approximant of order (p,p)
 fn pade_approximation<F>(a: &CsrMatrix<F>, p: usize) -> SparseResult<CsrMatrix<F>>
 where
-    F: Float + NumAssign + Sum + 'static + std::fmt::Debug,
+    F: Float + NumAssign + Sum + SparseElement + std::fmt::Debug,
 {
     let n = a.shape().0;
 
@@ -276,7 +277,7 @@ where
 /// Note: This is a placeholder - in practice you'd use a more sophisticated solver
 fn sparse_solve<F>(a: &CsrMatrix<F>, b: &CsrMatrix<F>) -> SparseResult<CsrMatrix<F>>
 where
-    F: Float + NumAssign + Sum + 'static + std::fmt::Debug,
+    F: Float + NumAssign + Sum + SparseElement + std::fmt::Debug,
 {
     use crate::linalg::interface::MatrixLinearOperator;
     use crate::linalg::iterative::bicgstab;
@@ -1,16 +1,15 @@
 //! Linear operator interface for sparse matrices
 
+use crate::element::SparseElement;
 use crate::error::{SparseError, SparseResult};
-use num_traits::{Float, NumAssign};
-use std::fmt::Debug;
-use std::iter::Sum;
+use num_traits::{NumAssign, Zero};
 use std::marker::PhantomData;
 
 /// Trait for representing a linear operator
 ///
 /// This trait provides an abstract interface for linear operators,
 /// allowing matrix-free implementations and compositions.
-pub trait LinearOperator<F: Float> {
+pub trait LinearOperator<F> {
     /// The shape of the operator (rows, columns)
     fn shape(&self) -> (usize, usize);
 
@@ -67,7 +66,7 @@ impl<F> IdentityOperator<F> {
     }
 }
 
-impl<F: Float> LinearOperator<F> for IdentityOperator<F> {
+impl<F: Copy> LinearOperator<F> for IdentityOperator<F> {
     fn shape(&self) -> (usize, usize) {
         (self.size, self.size)
     }
@@ -97,14 +96,14 @@ pub struct ScaledIdentityOperator<F> {
     scale: F,
 }
 
-impl<F: Float> ScaledIdentityOperator<F> {
+impl<F: SparseElement> ScaledIdentityOperator<F> {
     /// Create a new scaled identity operator
     pub fn new(size: usize, scale: F) -> Self {
         Self { size, scale }
     }
 }
 
-impl<F: Float + NumAssign> LinearOperator<F> for ScaledIdentityOperator<F> {
+impl<F: SparseElement> LinearOperator<F> for ScaledIdentityOperator<F> {
     fn shape(&self) -> (usize, usize) {
         (self.size, self.size)
     }
@@ -120,8 +119,14 @@ impl<F: Float + NumAssign> LinearOperator<F> for ScaledIdentityOperator<F> {
     }
 
     fn rmatvec(&self, x: &[F]) -> SparseResult<Vec<F>> {
-        // For real scalars, adjoint is the same
-        self.matvec(x)
+        if x.len() != self.size {
+            return Err(crate::error::SparseError::DimensionMismatch {
+                expected: self.size,
+                found: x.len(),
+            });
+        }
+        let scale = self.scale.conj();
+        Ok(x.iter().map(|&xi| xi * scale).collect())
     }
 
     fn has_adjoint(&self) -> bool {
@@ -134,7 +139,7 @@ pub struct DiagonalOperator<F> {
     diagonal: Vec<F>,
 }
 
-impl<F: Float> DiagonalOperator<F> {
+impl<F: SparseElement> DiagonalOperator<F> {
     /// Create a new diagonal operator from diagonal values
     pub fn new(diagonal: Vec<F>) -> Self {
         Self { diagonal }
@@ -146,7 +151,7 @@ impl<F: Float> DiagonalOperator<F> {
     }
 }
 
-impl<F: Float + NumAssign> LinearOperator<F> for DiagonalOperator<F> {
+impl<F: SparseElement> LinearOperator<F> for DiagonalOperator<F> {
     fn shape(&self) -> (usize, usize) {
         let n = self.diagonal.len();
         (n, n)
@@ -166,8 +171,16 @@ impl<F: Float + NumAssign> LinearOperator<F> for DiagonalOperator<F> {
     }
 
     fn rmatvec(&self, x: &[F]) -> SparseResult<Vec<F>> {
-        // For real diagonal matrices, adjoint is the same
-        self.matvec(x)
+        if x.len() != self.diagonal.len() {
+            return Err(crate::error::SparseError::DimensionMismatch {
+                expected: self.diagonal.len(),
+                found: x.len(),
+            });
+        }
+        Ok(x.iter()
+            .zip(&self.diagonal)
+            .map(|(&xi, &di)| xi * di.conj())
+            .collect())
     }
 
     fn has_adjoint(&self) -> bool {
@@ -192,7 +205,7 @@ impl<F> ZeroOperator<F> {
     }
 }
 
-impl<F: Float> LinearOperator<F> for ZeroOperator<F> {
+impl<F: Zero + Clone> LinearOperator<F> for ZeroOperator<F> {
     fn shape(&self) -> (usize, usize) {
         self.shape
     }
@@ -223,7 +236,7 @@ impl<F: Float> LinearOperator<F> for ZeroOperator<F> {
 }
 
 /// Convert a sparse matrix to a linear operator
-pub trait AsLinearOperator<F: Float> {
+pub trait AsLinearOperator<F> {
     /// Convert to a linear operator
     fn as_linear_operator(&self) -> Box<dyn LinearOperator<F>>;
 }
@@ -247,9 +260,7 @@ impl<F, M> MatrixLinearOperator<F, M> {
 // Implementation of LinearOperator for CSR matrices
 use crate::csr::CsrMatrix;
 
-impl<F: Float + NumAssign + Sum + 'static + Debug> LinearOperator<F>
-    for MatrixLinearOperator<F, CsrMatrix<F>>
-{
+impl<F: SparseElement> LinearOperator<F> for MatrixLinearOperator<F, CsrMatrix<F>> {
     fn shape(&self) -> (usize, usize) {
         (self.matrix.rows(), self.matrix.cols())
     }
@@ -279,9 +290,73 @@ impl<F: Float + NumAssign + Sum + 'static + Debug> LinearOperator<F>
     }
 
     fn rmatvec(&self, x: &[F]) -> SparseResult<Vec<F>> {
-        // For CSR, we can compute A^T * x by transposing first
-        let transposed = self.matrix.transpose();
-        MatrixLinearOperator::new(transposed).matvec(x)
+        // For CSR, we can compute A^H * x by transposing first
+        let adjoint = self.matrix.conj_transpose();
+        MatrixLinearOperator::new(adjoint).matvec(x)
+    }
+
+    fn has_adjoint(&self) -> bool {
+        true
+    }
+}
+
+impl<F: SparseElement> AsLinearOperator<F> for CsrMatrix<F> {
+    fn as_linear_operator(&self) -> Box<dyn LinearOperator<F>> {
+        Box::new(MatrixLinearOperator::new(self.clone()))
+    }
+}
+
+// Implementation of LinearOperator for CSR arrays
+use crate::csr_array::CsrArray;
+use crate::sparray::SparseArray;
+
+impl<F: SparseElement> LinearOperator<F> for MatrixLinearOperator<F, CsrArray<F>> {
+    fn shape(&self) -> (usize, usize) {
+        self.matrix.shape()
+    }
+
+    fn matvec(&self, x: &[F]) -> SparseResult<Vec<F>> {
+        let (rows, cols) = self.matrix.shape();
+        if x.len() != cols {
+            return Err(SparseError::DimensionMismatch {
+                expected: cols,
+                found: x.len(),
+            });
+        }
+        let (indptr, indices, data) = (
+            self.matrix.get_indptr(),
+            self.matrix.get_indices(),
+            self.matrix.get_data(),
+        );
+        Ok((0..rows)
+            .map(|row| {
+                (indptr[row]..indptr[row + 1])
+                    .map(|p| data[p] * x[indices[p]])
+                    .sum()
+            })
+            .collect())
+    }
+
+    fn rmatvec(&self, x: &[F]) -> SparseResult<Vec<F>> {
+        let (rows, cols) = self.matrix.shape();
+        if x.len() != rows {
+            return Err(SparseError::DimensionMismatch {
+                expected: rows,
+                found: x.len(),
+            });
+        }
+        let (indptr, indices, data) = (
+            self.matrix.get_indptr(),
+            self.matrix.get_indices(),
+            self.matrix.get_data(),
+        );
+        let mut result = vec![F::zero(); cols];
+        for (row, &xi) in x.iter().enumerate() {
+            for p in indptr[row]..indptr[row + 1] {
+                result[indices[p]] += data[p].conj() * xi;
+            }
+        }
+        Ok(result)
     }
 
     fn has_adjoint(&self) -> bool {
@@ -289,7 +364,7 @@ impl<F: Float + NumAssign + Sum + 'static + Debug> LinearOperator<F>
     }
 }
 
-impl<F: Float + NumAssign + Sum + 'static + Debug> AsLinearOperator<F> for CsrMatrix<F> {
+impl<F: SparseElement> AsLinearOperator<F> for CsrArray<F> {
     fn as_linear_operator(&self) -> Box<dyn LinearOperator<F>> {
         Box::new(MatrixLinearOperator::new(self.clone()))
     }
@@ -302,7 +377,7 @@ pub struct SumOperator<F> {
     b: Box<dyn LinearOperator<F>>,
 }
 
-impl<F: Float + NumAssign> SumOperator<F> {
+impl<F: NumAssign + Copy> SumOperator<F> {
     /// Create a new sum operator
     #[allow(dead_code)]
     pub fn new(a: Box<dyn LinearOperator<F>>, b: Box<dyn LinearOperator<F>>) -> SparseResult<Self> {
@@ -316,7 +391,7 @@ impl<F: Float + NumAssign> SumOperator<F> {
     }
 }
 
-impl<F: Float + NumAssign> LinearOperator<F> for SumOperator<F> {
+impl<F: NumAssign + Copy> LinearOperator<F> for SumOperator<F> {
     fn shape(&self) -> (usize, usize) {
         self.a.shape()
     }
@@ -357,7 +432,7 @@ pub struct ProductOperator<F> {
     b: Box<dyn LinearOperator<F>>,
 }
 
-impl<F: Float + NumAssign> ProductOperator<F> {
+impl<F> ProductOperator<F> {
     /// Create a new product operator
     #[allow(dead_code)]
     pub fn new(a: Box<dyn LinearOperator<F>>, b: Box<dyn LinearOperator<F>>) -> SparseResult<Self> {
@@ -373,7 +448,7 @@ impl<F: Float + NumAssign> ProductOperator<F> {
     }
 }
 
-impl<F: Float + NumAssign> LinearOperator<F> for ProductOperator<F> {
+impl<F> LinearOperator<F> for ProductOperator<F> {
     fn shape(&self) -> (usize, usize) {
         let (a_rows, _) = self.a.shape();
         let (_, b_cols) = self.b.shape();
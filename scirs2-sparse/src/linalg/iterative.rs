@@ -1,6 +1,7 @@
+use crate::element::SparseElement;
 use crate::error::{SparseError, SparseResult};
 use crate::linalg::interface::LinearOperator;
-use num_traits::{Float, NumAssign};
+use num_traits::{Float, NumAssign, NumCast, Zero};
 use std::iter::Sum;
 
 /// Result of an iterative solver
@@ -354,7 +355,7 @@ pub struct BiCGSTABOptions<F> {
     pub right_preconditioner: Option<Box<dyn LinearOperator<F>>>,
 }
 
-impl<F: Float> Default for BiCGSTABOptions<F> {
+impl<F: SparseElement> Default for BiCGSTABOptions<F> {
     fn default() -> Self {
         Self {
             max_iter: 1000,
@@ -373,14 +374,15 @@ pub type BiCGSTABResult<F> = IterationResult<F>;
 /// BiConjugate Gradient Stabilized method
 ///
 /// An improved version of BiCG that avoids the irregular convergence patterns
-/// and has better numerical stability. Works for general non-symmetric systems.
+/// and has better numerical stability. Works for general non-symmetric systems
+/// with real or complex entries; `rtol` and `atol` are compared by modulus.
 pub fn bicgstab<F>(
     a: &dyn LinearOperator<F>,
     b: &[F],
     options: BiCGSTABOptions<F>,
 ) -> SparseResult<BiCGSTABResult<F>>
 where
-    F: Float + NumAssign + Sum + 'static,
+    F: SparseElement,
 {
     let (rows, cols) = a.shape();
     if rows != cols {
@@ -416,15 +418,16 @@ where
     let mut r: Vec<F> = b.iter().zip(&ax).map(|(&bi, &axi)| bi - axi).collect();
 
     // Check if initial guess is solution
-    let mut rnorm = norm2(&r);
-    let bnorm = norm2(b);
-    let tolerance = F::max(options.atol, options.rtol * bnorm);
+    let mut rnorm = vector_norm(&r);
+    let bnorm = vector_norm(b);
+    let tolerance = Float::max(options.atol.modulus(), options.rtol.modulus() * bnorm);
+    let breakdown = F::Real::epsilon() * <F::Real as NumCast>::from(10).unwrap();
 
     if rnorm <= tolerance {
         return Ok(BiCGSTABResult {
             x,
             iterations: 0,
-            residual_norm: rnorm,
+            residual_norm: F::from_real(rnorm),
             converged: true,
             message: "Converged with initial guess".to_string(),
         });
@@ -447,14 +450,14 @@ where
     let mut iterations = 0;
     while iterations < options.max_iter {
         // Compute rho = (r_hat, r)
-        let rho = dot(&r_hat, &r);
+        let rho = inner(&r_hat, &r);
 
         // Check for breakdown
-        if rho.abs() < F::epsilon() * F::from(10).unwrap() {
+        if rho.modulus() < breakdown {
             return Ok(BiCGSTABResult {
                 x,
                 iterations,
-                residual_norm: rnorm,
+                residual_norm: F::from_real(rnorm),
                 converged: false,
                 message: "BiCGSTAB breakdown: rho ≈ 0".to_string(),
             });
@@ -483,12 +486,12 @@ where
         }
 
         // Compute alpha = rho / (r_hat, v)
-        let den = dot(&r_hat, &v);
-        if den.abs() < F::epsilon() * F::from(10).unwrap() {
+        let den = inner(&r_hat, &v);
+        if den.modulus() < breakdown {
             return Ok(BiCGSTABResult {
                 x,
                 iterations,
-                residual_norm: rnorm,
+                residual_norm: F::from_real(rnorm),
                 converged: false,
                 message: "BiCGSTAB breakdown: (r_hat, v) ≈ 0".to_string(),
             });
@@ -496,11 +499,11 @@ where
         alpha = rho / den;
 
         // Check if alpha is reasonable
-        if !alpha.is_finite() {
+        if !alpha.modulus().is_finite() {
             return Ok(BiCGSTABResult {
                 x,
                 iterations,
-                residual_norm: rnorm,
+                residual_norm: F::from_real(rnorm),
                 converged: false,
                 message: "BiCGSTAB breakdown: alpha is not finite".to_string(),
             });
@@ -513,7 +516,7 @@ where
         }
 
         // Check convergence
-        let snorm = norm2(&s);
+        let snorm = vector_norm(&s);
         if snorm <= tolerance {
             // Final update: x = y
             x = y;
@@ -526,7 +529,7 @@ where
             return Ok(BiCGSTABResult {
                 x,
                 iterations: iterations + 1,
-                residual_norm: snorm,
+                residual_norm: F::from_real(snorm),
                 converged: true,
                 message: "Converged".to_string(),
             });
@@ -547,27 +550,27 @@ where
         }
 
         // Compute omega = (t, s) / (t, t)
-        let ts = dot(&t, &s);
-        let tt = dot(&t, &t);
+        let ts = inner(&t, &s);
+        let tt = vector_norm(&t).powi(2);
 
-        if tt < F::epsilon() * F::from(10).unwrap() {
+        if tt < breakdown {
             return Ok(BiCGSTABResult {
                 x,
                 iterations,
-                residual_norm: rnorm,
+                residual_norm: F::from_real(rnorm),
                 converged: false,
                 message: "BiCGSTAB breakdown: (t, t) ≈ 0".to_string(),
             });
         }
 
-        omega = ts / tt;
+        omega = ts / F::from_real(tt);
 
         // Check if omega is reasonable
-        if !omega.is_finite() || omega.abs() < F::epsilon() * F::from(10).unwrap() {
+        if !omega.modulus().is_finite() || omega.modulus() < breakdown {
             return Ok(BiCGSTABResult {
                 x,
                 iterations,
-                residual_norm: rnorm,
+                residual_norm: F::from_real(rnorm),
                 converged: false,
                 message: "BiCGSTAB breakdown: omega is not finite or too small".to_string(),
             });
@@ -584,14 +587,14 @@ where
             x = m2.matvec(&x)?;
         }
 
-        rnorm = norm2(&r);
+        rnorm = vector_norm(&r);
 
         // Check for convergence
         if rnorm <= tolerance {
             return Ok(BiCGSTABResult {
                 x,
                 iterations: iterations + 1,
-                residual_norm: rnorm,
+                residual_norm: F::from_real(rnorm),
                 converged: true,
                 message: "Converged".to_string(),
             });
@@ -604,7 +607,7 @@ where
     Ok(BiCGSTABResult {
         x,
         iterations,
-        residual_norm: rnorm,
+        residual_norm: F::from_real(rnorm),
         converged: false,
         message: "Maximum iterations reached".to_string(),
     })
@@ -620,7 +623,7 @@ pub struct GMRESOptions<F> {
     pub preconditioner: Option<Box<dyn LinearOperator<F>>>,
 }
 
-impl<F: Float> Default for GMRESOptions<F> {
+impl<F: SparseElement> Default for GMRESOptions<F> {
     fn default() -> Self {
         Self {
             max_iter: 1000,
@@ -637,14 +640,15 @@ impl<F: Float> Default for GMRESOptions<F> {
 ///
 /// Solves Ax = b for general non-symmetric systems. GMRES is particularly
 /// robust but requires more memory than other methods due to the need to
-/// store the Krylov basis vectors.
+/// store the Krylov basis vectors. Real and complex systems are supported;
+/// `rtol` and `atol` are compared by modulus.
 pub fn gmres<F>(
     a: &dyn LinearOperator<F>,
     b: &[F],
     options: GMRESOptions<F>,
 ) -> SparseResult<IterationResult<F>>
 where
-    F: Float + NumAssign + Sum + 'static,
+    F: SparseElement,
 {
     let (rows, cols) = a.shape();
     if rows != cols {
@@ -685,9 +689,10 @@ where
         r = m.matvec(&r)?;
     }
 
-    let mut rnorm = norm2(&r);
-    let bnorm = norm2(b);
-    let tolerance = F::max(options.atol, options.rtol * bnorm);
+    let mut rnorm = vector_norm(&r);
+    let bnorm = vector_norm(b);
+    let tolerance = Float::max(options.atol.modulus(), options.rtol.modulus() * bnorm);
+    let breakdown = F::Real::epsilon() * <F::Real as NumCast>::from(10).unwrap();
 
     let mut outer_iterations = 0;
 
@@ -696,13 +701,13 @@ where
         // Initialize Krylov subspace
         let mut v = vec![vec![F::zero(); n]; restart + 1];
         let mut h = vec![vec![F::zero(); restart]; restart + 1];
-        let mut cs = vec![F::zero(); restart]; // Cosines for Givens rotations
+        let mut cs = vec![F::Real::zero(); restart]; // Cosines for Givens rotations
         let mut sn = vec![F::zero(); restart]; // Sines for Givens rotations
         let mut s = vec![F::zero(); restart + 1]; // RHS for triangular system
 
         // Set up initial vector
-        v[0] = r.iter().map(|&ri| ri / rnorm).collect();
-        s[0] = rnorm;
+        v[0] = r.iter().map(|&ri| ri / F::from_real(rnorm)).collect();
+        s[0] = F::from_real(rnorm);
 
         // Arnoldi iteration
         let mut inner_iter = 0;
@@ -717,16 +722,17 @@ where
 
             // Orthogonalize against previous vectors
             for i in 0..=inner_iter {
-                h[i][inner_iter] = dot(&v[i], &w);
+                h[i][inner_iter] = inner(&v[i], &w);
                 for (k, w_elem) in w.iter_mut().enumerate().take(n) {
                     *w_elem -= h[i][inner_iter] * v[i][k];
                 }
             }
 
-            h[inner_iter + 1][inner_iter] = norm2(&w);
+            let wnorm = vector_norm(&w);
+            h[inner_iter + 1][inner_iter] = F::from_real(wnorm);
 
             // Check for breakdown
-            if h[inner_iter + 1][inner_iter] < F::epsilon() * F::from(10).unwrap() {
+            if wnorm < breakdown {
                 break;
             }
 
@@ -736,33 +742,39 @@ where
                 .map(|&wi| wi / h[inner_iter + 1][inner_iter])
                 .collect();
 
-            // Apply previous Givens rotations
+            // Apply previous Givens rotations [c, s; -conj(s), c] with real c
             for i in 0..inner_iter {
-                let temp = cs[i] * h[i][inner_iter] + sn[i] * h[i + 1][inner_iter];
-                h[i + 1][inner_iter] = -sn[i] * h[i][inner_iter] + cs[i] * h[i + 1][inner_iter];
+                let c = F::from_real(cs[i]);
+                let temp = c * h[i][inner_iter] + sn[i] * h[i + 1][inner_iter];
+                h[i + 1][inner_iter] = -sn[i].conj() * h[i][inner_iter] + c * h[i + 1][inner_iter];
                 h[i][inner_iter] = temp;
             }
 
             // Compute new Givens rotation
-            let rho = (h[inner_iter][inner_iter] * h[inner_iter][inner_iter]
-                + h[inner_iter + 1][inner_iter] * h[inner_iter + 1][inner_iter])
-                .sqrt();
-            cs[inner_iter] = h[inner_iter][inner_iter] / rho;
-            sn[inner_iter] = h[inner_iter + 1][inner_iter] / rho;
+            let (hjj, hj1) = (h[inner_iter][inner_iter], h[inner_iter + 1][inner_iter]);
+            let (hjj_abs, hj1_abs) = (hjj.modulus(), hj1.modulus());
+            let rho = hjj_abs.hypot(hj1_abs);
+            let phase = if hjj_abs == F::Real::zero() {
+                F::one()
+            } else {
+                hjj / F::from_real(hjj_abs)
+            };
+            cs[inner_iter] = hjj_abs / rho;
+            sn[inner_iter] = phase * hj1.conj() / F::from_real(rho);
 
             // Apply new Givens rotation
-            h[inner_iter][inner_iter] = rho;
+            h[inner_iter][inner_iter] = phase * F::from_real(rho);
             h[inner_iter + 1][inner_iter] = F::zero();
 
-            let temp = cs[inner_iter] * s[inner_iter] + sn[inner_iter] * s[inner_iter + 1];
-            s[inner_iter + 1] =
-                -sn[inner_iter] * s[inner_iter] + cs[inner_iter] * s[inner_iter + 1];
+            let c = F::from_real(cs[inner_iter]);
+            let temp = c * s[inner_iter] + sn[inner_iter] * s[inner_iter + 1];
+            s[inner_iter + 1] = -sn[inner_iter].conj() * s[inner_iter] + c * s[inner_iter + 1];
             s[inner_iter] = temp;
 
             inner_iter += 1;
 
             // Check for convergence
-            let residual = s[inner_iter].abs();
+            let residual = s[inner_iter].modulus();
             if residual <= tolerance {
                 break;
             }
@@ -794,7 +806,7 @@ where
             r = m.matvec(&r)?;
         }
 
-        rnorm = norm2(&r);
+        rnorm = vector_norm(&r);
         outer_iterations += inner_iter;
 
         if rnorm <= tolerance {
@@ -805,7 +817,7 @@ where
     Ok(IterationResult {
         x,
         iterations: outer_iterations,
-        residual_norm: rnorm,
+        residual_norm: F::from_real(rnorm),
         converged: rnorm <= tolerance,
         message: if rnorm <= tolerance {
             "Converged".to_string()
@@ -833,6 +845,19 @@ pub(crate) fn norm2<F: Float + Sum>(x: &[F]) -> F {
     dot(x, x).sqrt()
 }
 
+/// Compute the inner product `xᴴ y` of real or complex vectors
+pub(crate) fn inner<F: SparseElement>(x: &[F], y: &[F]) -> F {
+    x.iter().zip(y).map(|(&xi, &yi)| xi.conj() * yi).sum()
+}
+
+/// Compute the 2-norm of a real or complex vector
+pub(crate) fn vector_norm<F: SparseElement>(x: &[F]) -> F::Real {
+    x.iter()
+        .map(|&xi| xi.modulus().powi(2))
+        .sum::<F::Real>()
+        .sqrt()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -942,4 +967,58 @@ mod tests {
             assert!((axi - bi).abs() < 1e-9);
         }
     }
+
+    #[test]
+    fn test_complex_gmres_bicgstab() {
+        use crate::csr_array::CsrArray;
+        use num_complex::Complex64;
+
+        // Non-Hermitian, diagonally dominant complex tridiagonal matrix
+        let n = 20;
+        let (mut rows, mut cols, mut data) = (Vec::new(), Vec::new(), Vec::new());
+        for i in 0..n {
+            rows.push(i);
+            cols.push(i);
+            data.push(Complex64::new(4.0, 1.0 + i as f64 * 0.1));
+            if i + 1 < n {
+                rows.extend([i, i + 1]);
+                cols.extend([i + 1, i]);
+                data.extend([Complex64::new(-1.0, 0.5), Complex64::new(0.5, -1.0)]);
+            }
+        }
+        let matrix = CsrArray::from_triplets(&rows, &cols, &data, (n, n), false).unwrap();
+        let op = matrix.as_linear_operator();
+        let b: Vec<Complex64> = (0..n)
+            .map(|i| Complex64::new(1.0, -(i as f64) / n as f64))
+            .collect();
+        let check = |x: &[Complex64]| {
+            let ax = op.matvec(x).unwrap();
+            for (axi, bi) in ax.iter().zip(&b) {
+                assert!((axi - bi).norm() < 1e-6);
+            }
+        };
+
+        let result = gmres(op.as_ref(), &b, GMRESOptions::default()).unwrap();
+        assert!(result.converged);
+        assert_eq!(result.residual_norm.im, 0.0);
+        check(&result.x);
+
+        let options = GMRESOptions {
+            restart: 5,
+            ..Default::default()
+        };
+        let result = gmres(op.as_ref(), &b, options).unwrap();
+        assert!(result.converged);
+        check(&result.x);
+
+        let result = bicgstab(op.as_ref(), &b, BiCGSTABOptions::default()).unwrap();
+        assert!(result.converged);
+        check(&result.x);
+
+        // The adjoint uses the conjugate transpose
+        let y: Vec<Complex64> = (0..n).map(|i| Complex64::new(0.0, i as f64)).collect();
+        let lhs: Complex64 = inner(&y, &op.matvec(&b).unwrap());
+        let rhs: Complex64 = inner(&op.rmatvec(&y).unwrap(), &b);
+        assert!((lhs - rhs).norm() < 1e-10);
+    }
 }
@@ -1,9 +1,14 @@
 //! Linear algebra operations for sparse matrices
 //!
 //! This module provides linear algebra operations for sparse matrices,
-//! including solvers, eigenvalue computations, and matrix functions.
+//! including direct and iterative solvers, fill-reducing orderings,
+//! eigenvalue computations, and matrix functions.
 
+mod amg;
 mod cgs;
+mod dense_eig;
+mod direct;
+mod eigs;
 mod expm;
 mod ic;
 mod interface;
@@ -11,12 +16,20 @@ mod iterative;
 mod lgmres;
 mod matfuncs;
 mod minres;
+mod ordering;
 mod preconditioners;
 mod qmr;
 mod solvers;
 mod spai;
+mod svds;
 
+pub use amg::{amg, AmgCycle, AmgMethod, AmgOptions, AmgPreconditioner, AmgSmoother};
 pub use cgs::{cgs, CGSOptions, CGSResult};
+pub use direct::{
+    sparse_cholesky, sparse_ldlt, splu, InverseOperator, SparseCholesky, SparseLdlt, SparseLu,
+    SpluOptions,
+};
+pub use eigs::{eigs, eigsh, EigsOptions, EigsResult, EigshResult, Which};
 pub use expm::expm;
 pub use ic::IC0Preconditioner;
 pub use interface::{
@@ -29,6 +42,7 @@ pub use iterative::{
 pub use lgmres::{lgmres, LGMRESOptions, LGMRESResult};
 pub use matfuncs::{expm_multiply, onenormest};
 pub use minres::{minres, MINRESOptions, MINRESResult};
+pub use ordering::{amd, colamd, reverse_cuthill_mckee, OrderingMethod};
 pub use preconditioners::{ILU0Preconditioner, JacobiPreconditioner, SSORPreconditioner};
 pub use qmr::{qmr, QMROptions, QMRResult};
 pub use solvers::{
@@ -36,3 +50,4 @@ pub use solvers::{
     sparse_lstsq, spsolve,
 };
 pub use spai::{SpaiOptions, SpaiPreconditioner};
+pub use svds::{svds, SvdsOptions, SvdsResult, SvdsWhich};
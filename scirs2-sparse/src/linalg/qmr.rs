@@ -1,8 +1,8 @@
+use crate::element::SparseElement;
 use crate::error::{SparseError, SparseResult};
 use crate::linalg::interface::LinearOperator;
-use num_traits::{Float, NumAssign};
+use num_traits::{Float, NumCast, Zero};
 use std::fmt::Display;
-use std::iter::Sum;
 
 /// Result of QMR solver
 #[derive(Debug, Clone)]
@@ -24,7 +24,7 @@ pub struct QMROptions<F> {
     pub right_preconditioner: Option<Box<dyn LinearOperator<F>>>,
 }
 
-impl<F: Float> Default for QMROptions<F> {
+impl<F: SparseElement> Default for QMROptions<F> {
     fn default() -> Self {
         Self {
             max_iter: 1000,
@@ -40,14 +40,15 @@ impl<F: Float> Default for QMROptions<F> {
 /// QMR (Quasi-Minimal Residual) solver for non-symmetric systems
 ///
 /// This implementation is a simplified version that provides QMR-like
-/// behavior using a BiCG-based approach with quasi-minimization.
+/// behavior using a BiCG-based approach with quasi-minimization. Real and
+/// complex systems are supported; `rtol` and `atol` are compared by modulus.
 pub fn qmr<F>(
     a: &dyn LinearOperator<F>,
     b: &[F],
     options: QMROptions<F>,
 ) -> SparseResult<QMRResult<F>>
 where
-    F: Float + NumAssign + Sum + Display + 'static,
+    F: SparseElement + Display,
 {
     let n = b.len();
 
@@ -93,14 +94,15 @@ where
     // Compute initial norms
     let bnorm = norm2(b);
     let mut rnorm = norm2(&r);
-    let tol = options.atol + options.rtol * bnorm;
+    let tol = options.atol.modulus() + options.rtol.modulus() * bnorm;
+    let breakdown = F::Real::epsilon() * <F::Real as NumCast>::from(10).unwrap();
 
     // Check initial convergence
     if rnorm < tol {
         return Ok(QMRResult {
             x,
             iterations: 0,
-            residual_norm: rnorm,
+            residual_norm: F::from_real(rnorm),
             converged: true,
             message: "Converged at initial guess".to_string(),
         });
@@ -115,11 +117,11 @@ where
         rho = dot(&r_tilde, &r);
 
         // Check for breakdown
-        if rho.abs() < F::epsilon() * F::from(10).unwrap() {
+        if rho.modulus() < breakdown {
             return Ok(QMRResult {
                 x,
                 iterations: iter,
-                residual_norm: rnorm,
+                residual_norm: F::from_real(rnorm),
                 converged: false,
                 message: "Breakdown: rho = 0".to_string(),
             });
@@ -175,11 +177,11 @@ where
 
         // Compute alpha
         let dot_pq = dot(&p_tilde, &q);
-        if dot_pq.abs() < F::epsilon() * F::from(10).unwrap() {
+        if dot_pq.modulus() < breakdown {
             return Ok(QMRResult {
                 x,
                 iterations: iter,
-                residual_norm: rnorm,
+                residual_norm: F::from_real(rnorm),
                 converged: false,
                 message: "Breakdown: <p_tilde, q> = 0".to_string(),
             });
@@ -206,11 +208,11 @@ where
         };
 
         // Compute omega (quasi-minimization parameter)
-        let dot_tt = dot(&t, &t);
-        if dot_tt == F::zero() {
+        let dot_tt = norm2(&t).powi(2);
+        if dot_tt == F::Real::zero() {
             omega = F::zero();
         } else {
-            omega = dot(&t, &s) / dot_tt;
+            omega = dot(&t, &s) / F::from_real(dot_tt);
         }
 
         // Update solution
@@ -228,18 +230,18 @@ where
             return Ok(QMRResult {
                 x,
                 iterations: iter + 1,
-                residual_norm: rnorm,
+                residual_norm: F::from_real(rnorm),
                 converged: true,
                 message: format!("Converged in {} iterations", iter + 1),
             });
         }
 
         // Check for stagnation
-        if omega.abs() < F::epsilon() {
+        if omega.modulus() < F::Real::epsilon() {
             return Ok(QMRResult {
                 x,
                 iterations: iter + 1,
-                residual_norm: rnorm,
+                residual_norm: F::from_real(rnorm),
                 converged: false,
                 message: "Breakdown: omega = 0".to_string(),
             });
@@ -249,33 +251,40 @@ where
     Ok(QMRResult {
         x,
         iterations: options.max_iter,
-        residual_norm: rnorm,
+        residual_norm: F::from_real(rnorm),
         converged: false,
         message: format!(
             "Did not converge in {} iterations. Residual: {}",
-            options.max_iter, rnorm
+            options.max_iter,
+            F::from_real(rnorm)
         ),
     })
 }
 
 // Helper functions
-fn dot<F: Float + Sum>(a: &[F], b: &[F]) -> F {
-    a.iter().zip(b.iter()).map(|(&ai, &bi)| ai * bi).sum()
+fn dot<F: SparseElement>(a: &[F], b: &[F]) -> F {
+    a.iter()
+        .zip(b.iter())
+        .map(|(&ai, &bi)| ai.conj() * bi)
+        .sum()
 }
 
-fn norm2<F: Float + Sum>(v: &[F]) -> F {
-    v.iter().map(|&vi| vi * vi).sum::<F>().sqrt()
+fn norm2<F: SparseElement>(v: &[F]) -> F::Real {
+    v.iter()
+        .map(|&vi| vi.modulus().powi(2))
+        .sum::<F::Real>()
+        .sqrt()
 }
 
-fn vec_add<F: Float>(a: &[F], b: &[F]) -> Vec<F> {
+fn vec_add<F: SparseElement>(a: &[F], b: &[F]) -> Vec<F> {
     a.iter().zip(b.iter()).map(|(&ai, &bi)| ai + bi).collect()
 }
 
-fn vec_sub<F: Float>(a: &[F], b: &[F]) -> Vec<F> {
+fn vec_sub<F: SparseElement>(a: &[F], b: &[F]) -> Vec<F> {
     a.iter().zip(b.iter()).map(|(&ai, &bi)| ai - bi).collect()
 }
 
-fn vec_scaled<F: Float>(v: &[F], s: F) -> Vec<F> {
+fn vec_scaled<F: SparseElement>(v: &[F], s: F) -> Vec<F> {
     v.iter().map(|&vi| vi * s).collect()
 }
 
@@ -380,4 +389,34 @@ mod tests {
             assert!(result.message.contains("Did not converge"));
         }
     }
+
+    #[test]
+    fn test_qmr_complex() {
+        use crate::csr::CsrMatrix;
+        use crate::linalg::interface::AsLinearOperator;
+        use num_complex::Complex64;
+
+        let c = Complex64::new;
+        let rows = vec![0, 0, 1, 1, 1, 2, 2];
+        let cols = vec![0, 1, 0, 1, 2, 1, 2];
+        let data = vec![
+            c(4.0, 1.0),
+            c(1.0, -1.0),
+            c(0.0, 2.0),
+            c(5.0, 0.0),
+            c(1.0, 0.0),
+            c(-1.0, 0.0),
+            c(3.0, -2.0),
+        ];
+        let matrix = CsrMatrix::new(data, rows, cols, (3, 3)).unwrap();
+        let op = matrix.as_linear_operator();
+        let b = vec![c(1.0, 0.0), c(0.0, 1.0), c(2.0, -1.0)];
+
+        let result = qmr(op.as_ref(), &b, QMROptions::default()).unwrap();
+        assert!(result.converged);
+        let ax = op.matvec(&result.x).unwrap();
+        for (axi, bi) in ax.iter().zip(&b) {
+            assert!((axi - bi).norm() < 1e-7);
+        }
+    }
 }
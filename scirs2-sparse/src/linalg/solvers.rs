@@ -2,34 +2,43 @@
 
 use crate::csr::CsrMatrix;
 use crate::error::{SparseError, SparseResult};
+use crate::linalg::direct::{sparse_cholesky, sparse_ldlt, splu, SpluOptions};
+use crate::linalg::ordering::OrderingMethod;
 use num_traits::{Float, NumAssign};
 use std::iter::Sum;
 
-// Re-export the functions from the original linalg.rs
-// For now, we'll implement these functions here. In a real migration,
-// we would move the implementations from linalg.rs to here.
-
-// I'll implement stubs for the functions that need to be moved.
-// The actual implementations should be copied from linalg.rs
-
 /// Solve a sparse linear system Ax = b
+///
+/// Uses a sparse LU factorization with partial pivoting and a COLAMD column
+/// ordering. To solve several systems with the same matrix, factorize it once
+/// with [`splu`].
 pub fn spsolve<F>(a: &CsrMatrix<F>, b: &[F]) -> SparseResult<Vec<F>>
 where
     F: Float + NumAssign + Sum + 'static + std::fmt::Debug,
 {
-    // This implementation should be moved from linalg.rs
-    // For now, I'll forward to sparse_direct_solve
-    // For now, use a simple Gaussian elimination approach
-    let a_dense = a.to_dense();
-    gaussian_elimination(&a_dense, b)
+    sparse_direct_solve(a, b, false, false)
 }
 
 /// Solve a sparse linear system using direct methods
+///
+/// # Arguments
+///
+/// * `a` - Square sparse matrix
+/// * `b` - Right-hand side
+/// * `symmetric` - The matrix is symmetric (both triangles stored): use an
+///   LDLᵀ factorization without pivoting
+/// * `positive_definite` - The matrix is symmetric positive definite: use a
+///   Cholesky factorization
+///
+/// Both symmetric factorizations use an AMD ordering; otherwise a sparse LU
+/// factorization is used. Since LDLᵀ does not pivot, it breaks down or loses
+/// accuracy on some nonsingular indefinite matrices, such as saddle-point
+/// systems with a zero diagonal block. These are solved by sparse LU instead.
 pub fn sparse_direct_solve<F>(
     a: &CsrMatrix<F>,
     b: &[F],
-    _symmetric: bool,
-    _positive_definite: bool,
+    symmetric: bool,
+    positive_definite: bool,
 ) -> SparseResult<Vec<F>>
 where
     F: Float + NumAssign + Sum + 'static + std::fmt::Debug,
@@ -49,10 +58,46 @@ where
         )));
     }
 
-    // For this stub implementation, we'll use Gaussian elimination
-    // The real implementation should use optimized sparse solvers
-    let a_dense = a.to_dense();
-    gaussian_elimination(&a_dense, b)
+    if positive_definite {
+        sparse_cholesky(a, OrderingMethod::Amd)?.solve(b)
+    } else if symmetric {
+        match sparse_ldlt(a, OrderingMethod::Amd) {
+            Ok(ldlt) => {
+                let x = ldlt.solve(b)?;
+                if is_accurate(a, &x, b) {
+                    return Ok(x);
+                }
+            }
+            Err(SparseError::SingularMatrix(_)) => {}
+            Err(e) => return Err(e),
+        }
+        splu(a, SpluOptions::default())?.solve(b)
+    } else {
+        splu(a, SpluOptions::default())?.solve(b)
+    }
+}
+
+/// Whether `x` solves `A x = b` with a small backward error
+fn is_accurate<F>(a: &CsrMatrix<F>, x: &[F], b: &[F]) -> bool
+where
+    F: Float + NumAssign + Sum + 'static + std::fmt::Debug,
+{
+    let max_abs = |v: &[F]| v.iter().fold(F::zero(), |m, &vi| m.max(vi.abs()));
+    let mut a_norm = F::zero();
+    let mut r_norm = F::zero();
+    for (i, &bi) in b.iter().enumerate() {
+        let range = a.row_range(i);
+        let mut ax = F::zero();
+        let mut row_sum = F::zero();
+        for (&j, &aij) in a.indices[range.clone()].iter().zip(&a.data[range]) {
+            ax += aij * x[j];
+            row_sum += aij.abs();
+        }
+        a_norm = a_norm.max(row_sum);
+        r_norm = r_norm.max((bi - ax).abs());
+    }
+    // NaN residuals fail the comparison
+    r_norm <= F::epsilon().sqrt() * (a_norm * max_abs(x) + max_abs(b))
 }
 
 /// Solve a least squares problem
@@ -277,14 +322,15 @@ where
 
     let n = a.rows();
 
-    // Solve A * X = I for X
+    // Solve A * X = I for X, one column at a time with a single factorization
+    let lu = splu(a, SpluOptions::default())?;
     let mut inv_cols = Vec::new();
 
     for j in 0..n {
         // Get column j from identity matrix
         let mut col_vec = vec![F::zero(); n];
         col_vec[j] = F::one();
-        let x = spsolve(a, &col_vec)?;
+        let x = lu.solve(&col_vec)?;
         inv_cols.push(x);
     }
 
@@ -337,69 +383,33 @@ where
     }
 }
 
-// Helper functions
-
-fn gaussian_elimination<F>(a: &[Vec<F>], b: &[F]) -> SparseResult<Vec<F>>
-where
-    F: Float + NumAssign,
-{
-    let n = a.len();
-    let mut aug = vec![vec![F::zero(); n + 1]; n];
-
-    // Create augmented matrix
-    for i in 0..n {
-        for j in 0..n {
-            aug[i][j] = a[i][j];
-        }
-        aug[i][n] = b[i];
-    }
-
-    // Forward elimination
-    for k in 0..n {
-        // Find pivot
-        let mut max_idx = k;
-        for i in (k + 1)..n {
-            if aug[i][k].abs() > aug[max_idx][k].abs() {
-                max_idx = i;
-            }
-        }
-        aug.swap(k, max_idx);
-
-        // Check for zero pivot
-        if aug[k][k].abs() < F::epsilon() {
-            return Err(SparseError::SingularMatrix(
-                "Matrix is singular".to_string(),
-            ));
-        }
-
-        // Eliminate column
-        for i in (k + 1)..n {
-            let factor = aug[i][k] / aug[k][k];
-            for j in k..=n {
-                aug[i][j] = aug[i][j] - factor * aug[k][j];
-            }
-        }
-    }
-
-    // Back substitution
-    let mut x = vec![F::zero(); n];
-    for i in (0..n).rev() {
-        x[i] = aug[i][n];
-        for j in (i + 1)..n {
-            x[i] = x[i] - aug[i][j] * x[j];
-        }
-        x[i] /= aug[i][i];
-    }
-
-    Ok(x)
-}
-
 // Helper functions for matrix exponential have been moved to linalg/expm.rs
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sparse_direct_solve_zero_diagonal() {
+        // Nonsingular but without an LDLᵀ factorization
+        let a = CsrMatrix::new(vec![1.0, 1.0], vec![0, 1], vec![1, 0], (2, 2)).unwrap();
+        let x = sparse_direct_solve(&a, &[2.0, 3.0], true, false).unwrap();
+        assert!((x[0] - 3.0).abs() < 1e-12 && (x[1] - 2.0).abs() < 1e-12);
+
+        // Saddle-point system [[H, Bᵀ], [B, 0]] with H = diag(2, 3), B = [1, 1]
+        let a = CsrMatrix::new(
+            vec![2.0, 1.0, 3.0, 1.0, 1.0, 1.0],
+            vec![0, 0, 1, 1, 2, 2],
+            vec![0, 2, 1, 2, 0, 1],
+            (3, 3),
+        )
+        .unwrap();
+        let b = [1.0, 2.0, 4.0];
+        let x = sparse_direct_solve(&a, &b, true, false).unwrap();
+        let ax = a.dot(&x).unwrap();
+        assert!(ax.iter().zip(&b).all(|(u, v)| (u - v).abs() < 1e-12));
+    }
+
     #[test]
     fn test_eye_matrix() {
         let eye_matrix = eye::<f64>(3).unwrap();
@@ -0,0 +1,308 @@
+//! Truncated singular value decomposition of sparse arrays
+//!
+//! [`svds`] computes a few singular triplets by running the implicitly
+//! restarted Lanczos method of [`eigsh`](crate::linalg::eigsh) on the normal
+//! operator `AᵀA` (or `AAᵀ` for wide arrays), never forming it explicitly.
+
+use crate::csr_array::CsrArray;
+use crate::element::SparseElement;
+use crate::error::{SparseError, SparseResult};
+use crate::linalg::eigs::{eigsh, EigsOptions, Which};
+use crate::linalg::interface::LinearOperator;
+use crate::sparray::SparseArray;
+use ndarray::{Array1, Array2};
+use num_traits::{Float, NumAssign};
+use std::fmt::Debug;
+use std::iter::Sum;
+
+/// Which singular values to compute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SvdsWhich {
+    /// Largest singular values
+    Largest,
+    /// Smallest singular values
+    Smallest,
+}
+
+/// Options for [`svds`]
+#[derive(Debug, Clone)]
+pub struct SvdsOptions<F> {
+    /// Number of singular triplets to compute
+    pub k: usize,
+    /// Which singular values to compute
+    pub which: SvdsWhich,
+    /// Number of Lanczos vectors (see [`EigsOptions::ncv`])
+    pub ncv: Option<usize>,
+    /// Relative accuracy of the squared singular values (machine precision if zero)
+    pub tol: F,
+    /// Maximum number of restarts
+    pub max_iter: usize,
+    /// Whether to compute the singular vectors
+    pub return_singular_vectors: bool,
+}
+
+impl<F: Float> Default for SvdsOptions<F> {
+    fn default() -> Self {
+        Self {
+            k: 6,
+            which: SvdsWhich::Largest,
+            ncv: None,
+            tol: F::zero(),
+            max_iter: 1000,
+            return_singular_vectors: true,
+        }
+    }
+}
+
+/// Result of [`svds`]
+#[derive(Debug, Clone)]
+pub struct SvdsResult<F> {
+    /// Left singular vectors as columns (m x k)
+    pub u: Option<Array2<F>>,
+    /// Singular values in descending order
+    pub s: Array1<F>,
+    /// Right singular vectors as rows (k x n)
+    pub vt: Option<Array2<F>>,
+}
+
+/// `AᵀA` (or `AAᵀ` if `transpose`) as a linear operator
+struct NormalOperator<'a, F>
+where
+    F: Float + SparseElement,
+{
+    a: &'a CsrArray<F>,
+    transpose: bool,
+}
+
+impl<F> NormalOperator<'_, F>
+where
+    F: Float + NumAssign + SparseElement,
+{
+    fn apply(&self, x: &[F]) -> Vec<F> {
+        let (indptr, indices, data) =
+            (self.a.get_indptr(), self.a.get_indices(), self.a.get_data());
+        let (rows, cols) = self.a.shape();
+        let mut y = vec![F::zero(); rows];
+        let mut z = vec![F::zero(); cols];
+        if self.transpose {
+            // y = Aᵀ x, then A y
+            for (i, &xi) in x.iter().enumerate() {
+                for p in indptr[i]..indptr[i + 1] {
+                    z[indices[p]] += data[p] * xi;
+                }
+            }
+            for (i, yi) in y.iter_mut().enumerate() {
+                for p in indptr[i]..indptr[i + 1] {
+                    *yi += data[p] * z[indices[p]];
+                }
+            }
+            y
+        } else {
+            for (i, yi) in y.iter_mut().enumerate() {
+                for p in indptr[i]..indptr[i + 1] {
+                    *yi += data[p] * x[indices[p]];
+                }
+            }
+            for (i, &yi) in y.iter().enumerate() {
+                for p in indptr[i]..indptr[i + 1] {
+                    z[indices[p]] += data[p] * yi;
+                }
+            }
+            z
+        }
+    }
+}
+
+impl<F> LinearOperator<F> for NormalOperator<'_, F>
+where
+    F: Float + NumAssign + SparseElement,
+{
+    fn shape(&self) -> (usize, usize) {
+        let (rows, cols) = self.a.shape();
+        let n = if self.transpose { rows } else { cols };
+        (n, n)
+    }
+
+    fn matvec(&self, x: &[F]) -> SparseResult<Vec<F>> {
+        let n = self.shape().0;
+        if x.len() != n {
+            return Err(SparseError::DimensionMismatch {
+                expected: n,
+                found: x.len(),
+            });
+        }
+        Ok(self.apply(x))
+    }
+
+    fn rmatvec(&self, x: &[F]) -> SparseResult<Vec<F>> {
+        self.matvec(x)
+    }
+
+    fn has_adjoint(&self) -> bool {
+        true
+    }
+}
+
+/// Compute a truncated singular value decomposition of a sparse array
+///
+/// # Arguments
+///
+/// * `a` - Sparse array of shape (m, n)
+/// * `options` - Number of singular triplets and which to compute
+///
+/// # Returns
+///
+/// * `U`, the singular values in descending order and `Vᵀ`, such that
+///   `A ≈ U diag(s) Vᵀ` on the computed subspace
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_sparse::csr_array::CsrArray;
+/// use scirs2_sparse::linalg::{svds, SvdsOptions};
+///
+/// // Diagonal array with singular values 1..=30
+/// let n = 30;
+/// let idx: Vec<usize> = (0..n).collect();
+/// let data: Vec<f64> = (1..=n).map(|i| i as f64).collect();
+/// let a = CsrArray::from_triplets(&idx, &idx, &data, (n, n), true).unwrap();
+///
+/// let options = SvdsOptions { k: 2, ..Default::default() };
+/// let result = svds(&a, &options).unwrap();
+/// assert!((result.s[0] - 30.0).abs() < 1e-10);
+/// assert!((result.s[1] - 29.0).abs() < 1e-10);
+/// ```
+pub fn svds<F>(a: &CsrArray<F>, options: &SvdsOptions<F>) -> SparseResult<SvdsResult<F>>
+where
+    F: Float + NumAssign + Sum + SparseElement,
+{
+    let (rows, cols) = a.shape();
+    let min_dim = rows.min(cols);
+    if options.k == 0 || options.k >= min_dim {
+        return Err(SparseError::ValueError(format!(
+            "k must satisfy 0 < k < {} for an array of shape {}x{}",
+            min_dim, rows, cols
+        )));
+    }
+    let transpose = rows < cols;
+    let normal = NormalOperator { a, transpose };
+    let eig_options = EigsOptions {
+        k: options.k,
+        which: match options.which {
+            SvdsWhich::Largest => Which::LargestReal,
+            SvdsWhich::Smallest => Which::SmallestReal,
+        },
+        ncv: options.ncv,
+        max_iter: options.max_iter,
+        tol: options.tol,
+        return_eigenvectors: options.return_singular_vectors,
+        ..Default::default()
+    };
+    let eig = eigsh(&normal, &eig_options)?;
+    if !eig.converged {
+        return Err(SparseError::IterativeSolverFailure(format!(
+            "svds did not converge in {} restarts",
+            eig.iterations
+        )));
+    }
+
+    // Descending singular values
+    let k = options.k;
+    let s: Array1<F> = eig
+        .eigenvalues
+        .iter()
+        .rev()
+        .map(|&lambda| lambda.max(F::zero()).sqrt())
+        .collect();
+    let vectors = match eig.eigenvectors {
+        Some(vectors) => vectors,
+        None => {
+            return Ok(SvdsResult {
+                u: None,
+                s,
+                vt: None,
+            })
+        }
+    };
+
+    // Recover the other side as A v / s (or Aᵀ u / s)
+    let (indptr, indices, data) = (a.get_indptr(), a.get_indices(), a.get_data());
+    let other_dim = if transpose { cols } else { rows };
+    let mut other = Array2::zeros((other_dim, k));
+    let smax = s[0];
+    for c in 0..k {
+        let sc = s[c];
+        if sc <= F::epsilon() * smax {
+            continue;
+        }
+        let x = vectors.column(k - 1 - c);
+        let mut col = vec![F::zero(); other_dim];
+        for i in 0..rows {
+            for p in indptr[i]..indptr[i + 1] {
+                if transpose {
+                    col[indices[p]] += data[p] * x[i];
+                } else {
+                    col[i] += data[p] * x[indices[p]];
+                }
+            }
+        }
+        for (r, value) in col.into_iter().enumerate() {
+            other[[r, c]] = value / sc;
+        }
+    }
+    let own = Array2::from_shape_fn((vectors.nrows(), k), |(r, c)| vectors[[r, k - 1 - c]]);
+    let (u, v) = if transpose {
+        (own, other)
+    } else {
+        (other, own)
+    };
+    Ok(SvdsResult {
+        u: Some(u),
+        s,
+        vt: Some(v.reversed_axes()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svds_rectangular() {
+        // Bidiagonal 50 x 40 array and its transpose
+        let (m, n) = (50, 40);
+        let (mut rows, mut cols, mut data) = (Vec::new(), Vec::new(), Vec::new());
+        for j in 0..n {
+            rows.extend([j, j + 1]);
+            cols.extend([j, j]);
+            data.extend([1.0 + j as f64 / 10.0, 0.5]);
+        }
+        let a = CsrArray::from_triplets(&rows, &cols, &data, (m, n), false).unwrap();
+        let at = CsrArray::from_triplets(&cols, &rows, &data, (n, m), false).unwrap();
+        let dense = a.to_array();
+
+        for which in [SvdsWhich::Largest, SvdsWhich::Smallest] {
+            let options = SvdsOptions {
+                k: 3,
+                which,
+                ..Default::default()
+            };
+            let result = svds(&a, &options).unwrap();
+            let result_t = svds(&at, &options).unwrap();
+            let (u, vt) = (result.u.unwrap(), result.vt.unwrap());
+            for c in 0..3 {
+                assert!((result.s[c] - result_t.s[c]).abs() < 1e-8);
+                // A v = s u and Aᵀ u = s v
+                let av = dense.dot(&vt.row(c));
+                let atu = dense.t().dot(&u.column(c));
+                for (x, y) in av.iter().zip(u.column(c)) {
+                    assert!((x - result.s[c] * y).abs() < 1e-8);
+                }
+                for (x, y) in atu.iter().zip(vt.row(c)) {
+                    assert!((x - result.s[c] * y).abs() < 1e-8);
+                }
+            }
+            assert!(result.s[0] >= result.s[1] && result.s[1] >= result.s[2]);
+        }
+    }
+}
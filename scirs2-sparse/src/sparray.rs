@@ -4,10 +4,12 @@
 // from matrix-based API to array-based API.
 
 use ndarray::{Array1, Array2, ArrayView1};
-use num_traits::Float;
 use std::fmt::Debug;
 use std::ops::{Add, Div, Mul, Sub};
 
+use crate::element::{
+    element_infinity, element_max, element_min, element_neg_infinity, SparseElement,
+};
 use crate::error::{SparseError, SparseResult};
 
 /// Trait for sparse array types.
@@ -27,7 +29,7 @@ use crate::error::{SparseError, SparseResult};
 ///
 pub trait SparseArray<T>: std::any::Any
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -93,6 +95,27 @@ where
     /// Transpose the sparse array.
     fn transpose(&self) -> SparseResult<Box<dyn SparseArray<T>>>;
 
+    /// Conjugate transpose (Hermitian adjoint) of the sparse array.
+    ///
+    /// For real element types this is the same as [`SparseArray::transpose`];
+    /// for complex element types the result is returned in CSR format.
+    fn conj_transpose(&self) -> SparseResult<Box<dyn SparseArray<T>>> {
+        if !T::IS_COMPLEX {
+            return self.transpose();
+        }
+        let (rows, cols, data) = self.find();
+        let data: Vec<T> = data.iter().map(|v| v.conj()).collect();
+        let (m, n) = self.shape();
+        crate::csr_array::CsrArray::from_triplets(
+            &cols.to_vec(),
+            &rows.to_vec(),
+            &data,
+            (n, m),
+            false,
+        )
+        .map(|array| Box::new(array) as Box<dyn SparseArray<T>>)
+    }
+
     /// Return a copy of the sparse array with the specified elements.
     fn copy(&self) -> Box<dyn SparseArray<T>>;
 
@@ -146,7 +169,7 @@ where
 // Manually implement Debug and Clone instead of deriving them
 pub enum SparseSum<T>
 where
-    T: Float + Debug + Copy + 'static,
+    T: SparseElement + Debug + Copy + 'static,
 {
     /// Sum over a single axis, returning a sparse array.
     SparseArray(Box<dyn SparseArray<T>>),
@@ -157,7 +180,7 @@ where
 
 impl<T> Debug for SparseSum<T>
 where
-    T: Float + Debug + Copy + 'static,
+    T: SparseElement + Debug + Copy + 'static,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -169,7 +192,7 @@ where
 
 impl<T> Clone for SparseSum<T>
 where
-    T: Float + Debug + Copy + 'static,
+    T: SparseElement + Debug + Copy + 'static,
 {
     fn clone(&self) -> Self {
         match self {
@@ -182,7 +205,7 @@ where
 /// Identifies sparse arrays (both matrix and array types)
 pub fn is_sparse<T>(_obj: &dyn SparseArray<T>) -> bool
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -197,7 +220,7 @@ where
 /// Create a base SparseArray implementation for demonstrations and testing
 pub struct SparseArrayBase<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -211,7 +234,7 @@ where
 
 impl<T> SparseArrayBase<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -228,7 +251,7 @@ where
 
 impl<T> SparseArray<T> for SparseArrayBase<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
@@ -247,7 +270,11 @@ where
     }
 
     fn dtype(&self) -> &str {
-        "float" // This is a placeholder; ideally, we'd return the actual type
+        if T::IS_COMPLEX {
+            "complex"
+        } else {
+            "float"
+        }
     }
 
     fn to_array(&self) -> Array2<T> {
@@ -334,7 +361,7 @@ where
             for j in 0..q {
                 let mut sum = T::zero();
                 for k in 0..n {
-                    sum = sum + self.data[[i, k]] * other_array[[k, j]];
+                    sum += self.data[[i, k]] * other_array[[k, j]];
                 }
                 result[[i, j]] = sum;
             }
@@ -356,7 +383,7 @@ where
         for i in 0..m {
             let mut sum = T::zero();
             for j in 0..n {
-                sum = sum + self.data[[i, j]] * other[j];
+                sum += self.data[[i, j]] * other[j];
             }
             result[i] = sum;
         }
@@ -409,7 +436,7 @@ where
             None => {
                 let mut sum = T::zero();
                 for &val in self.data.iter() {
-                    sum = sum + val;
+                    sum += val;
                 }
                 Ok(SparseSum::Scalar(sum))
             }
@@ -419,7 +446,7 @@ where
                 for j in 0..n {
                     let mut sum = T::zero();
                     for i in 0..self.data.shape()[0] {
-                        sum = sum + self.data[[i, j]];
+                        sum += self.data[[i, j]];
                     }
                     result[[0, j]] = sum;
                 }
@@ -433,7 +460,7 @@ where
                 for i in 0..m {
                     let mut sum = T::zero();
                     for j in 0..self.data.shape()[1] {
-                        sum = sum + self.data[[i, j]];
+                        sum += self.data[[i, j]];
                     }
                     result[[i, 0]] = sum;
                 }
@@ -448,11 +475,13 @@ where
     fn max(&self) -> T {
         self.data
             .iter()
-            .fold(T::neg_infinity(), |acc, &x| acc.max(x))
+            .fold(element_neg_infinity::<T>(), |acc, &x| element_max(acc, x))
     }
 
     fn min(&self) -> T {
-        self.data.iter().fold(T::infinity(), |acc, &x| acc.min(x))
+        self.data
+            .iter()
+            .fold(element_infinity::<T>(), |acc, &x| element_min(acc, x))
     }
 
     fn find(&self) -> (Array1<usize>, Array1<usize>, Array1<T>) {
@@ -512,7 +541,7 @@ where
 
 impl<T> Clone for SparseArrayBase<T>
 where
-    T: Float
+    T: SparseElement
         + Add<Output = T>
         + Sub<Output = T>
         + Mul<Output = T>
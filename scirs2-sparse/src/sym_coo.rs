@@ -6,9 +6,10 @@
 
 use crate::coo::CooMatrix;
 use crate::coo_array::CooArray;
+use crate::element::SparseElement;
 use crate::error::{SparseError, SparseResult};
 use crate::sparray::SparseArray;
-use num_traits::Float;
+use num_traits::{Float, NumCast};
 use std::fmt::Debug;
 use std::ops::{Add, Div, Mul, Sub};
 
@@ -24,7 +25,7 @@ use std::ops::{Add, Div, Mul, Sub};
 #[derive(Debug, Clone)]
 pub struct SymCooMatrix<T>
 where
-    T: Float + Debug + Copy,
+    T: SparseElement + Debug + Copy,
 {
     /// Non-zero values in the lower triangular part
     pub data: Vec<T>,
@@ -41,7 +42,7 @@ where
 
 impl<T> SymCooMatrix<T>
 where
-    T: Float + Debug + Copy,
+    T: SparseElement + Debug + Copy,
 {
     /// Create a new symmetric COO matrix from raw data
     ///
@@ -206,8 +207,8 @@ where
             for j in 0..i {
                 // Only need to check upper triangular elements
                 // Compare with sufficient tolerance for floating point comparisons
-                let diff = (dense[i][j] - dense[j][i]).abs();
-                let epsilon = T::epsilon() * T::from(100.0).unwrap();
+                let diff = (dense[i][j] - dense[j][i]).modulus();
+                let epsilon = T::Real::epsilon() * <T::Real as NumCast>::from(100.0).unwrap();
                 if diff > epsilon {
                     return false;
                 }
@@ -350,7 +351,7 @@ where
 #[derive(Debug, Clone)]
 pub struct SymCooArray<T>
 where
-    T: Float + Debug + Copy,
+    T: SparseElement + Debug + Copy,
 {
     /// Inner matrix
     inner: SymCooMatrix<T>,
@@ -358,7 +359,7 @@ where
 
 impl<T> SymCooArray<T>
 where
-    T: Float
+    T: SparseElement
         + Debug
         + Copy
         + 'static
@@ -430,7 +431,7 @@ where
             // Check if the matrix is symmetric
             for i in 0..n {
                 for j in 0..i {
-                    if (dense[i][j] - dense[j][i]).abs() > T::epsilon() {
+                    if (dense[i][j] - dense[j][i]).modulus() > T::Real::epsilon() {
                         return Err(SparseError::ValueError(
                             "Input is not symmetric. Use enforce_symmetric=true to force symmetry"
                                 .to_string(),
@@ -6,9 +6,10 @@
 
 use crate::csr::CsrMatrix;
 use crate::csr_array::CsrArray;
+use crate::element::SparseElement;
 use crate::error::{SparseError, SparseResult};
 use crate::sparray::SparseArray;
-use num_traits::Float;
+use num_traits::{Float, NumCast};
 use std::fmt::Debug;
 use std::ops::{Add, Div, Mul, Sub};
 
@@ -24,7 +25,7 @@ use std::ops::{Add, Div, Mul, Sub};
 #[derive(Debug, Clone)]
 pub struct SymCsrMatrix<T>
 where
-    T: Float + Debug + Copy,
+    T: SparseElement + Debug + Copy,
 {
     /// CSR format data for the lower triangular part (including diagonal)
     pub data: Vec<T>,
@@ -41,7 +42,7 @@ where
 
 impl<T> SymCsrMatrix<T>
 where
-    T: Float + Debug + Copy,
+    T: SparseElement + Debug + Copy,
 {
     /// Create a new symmetric CSR matrix from raw data
     ///
@@ -164,7 +165,50 @@ where
             ));
         }
 
-        // Extract the lower triangular part
+        Ok(Self::lower_triangle(matrix))
+    }
+
+    /// Convert a Hermitian CSR matrix to symmetric CSR storage
+    ///
+    /// The lower triangular part is stored as for symmetric matrices; the
+    /// upper triangular part is implied by `A[j][i] = conj(A[i][j])`. Use the
+    /// `herm_*` functions in [`crate::sym_ops`] and
+    /// [`SymCsrMatrix::to_hermitian_csr`] with matrices built this way.
+    ///
+    /// # Arguments
+    ///
+    /// * `matrix` - Hermitian CSR matrix to convert
+    ///
+    /// # Returns
+    ///
+    /// A symmetric CSR matrix holding the lower triangular part
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_complex::Complex64;
+    /// use scirs2_sparse::csr::CsrMatrix;
+    /// use scirs2_sparse::sym_csr::SymCsrMatrix;
+    ///
+    /// let z = Complex64::new(1.0, 2.0);
+    /// let data = vec![Complex64::new(2.0, 0.0), z, z.conj(), Complex64::new(3.0, 0.0)];
+    /// let a = CsrMatrix::new(data, vec![0, 0, 1, 1], vec![0, 1, 0, 1], (2, 2)).unwrap();
+    /// let herm = SymCsrMatrix::from_hermitian_csr(&a).unwrap();
+    /// assert_eq!(herm.nnz_stored(), 3);
+    /// assert_eq!(herm.to_hermitian_csr().unwrap().get(0, 1), z);
+    /// ```
+    pub fn from_hermitian_csr(matrix: &CsrMatrix<T>) -> SparseResult<Self> {
+        if !Self::is_hermitian(matrix) {
+            return Err(SparseError::ValueError(
+                "Matrix must be Hermitian to convert to SymCSR format".to_string(),
+            ));
+        }
+        Ok(Self::lower_triangle(matrix))
+    }
+
+    /// Lower triangular part (including the diagonal) of a square CSR matrix
+    fn lower_triangle(matrix: &CsrMatrix<T>) -> Self {
+        let (rows, cols) = matrix.shape();
         let mut data = Vec::new();
         let mut indices = Vec::new();
         let mut indptr = vec![0];
@@ -183,12 +227,12 @@ where
             indptr.push(data.len());
         }
 
-        Ok(Self {
+        Self {
             data,
             indptr,
             indices,
             shape: (rows, cols),
-        })
+        }
     }
 
     /// Check if a CSR matrix is symmetric
@@ -218,8 +262,8 @@ where
                 let i_val = matrix.get(j, i);
 
                 // Check if a[i,j] == a[j,i] with sufficient tolerance
-                let diff = (val - i_val).abs();
-                let epsilon = T::epsilon() * T::from(100.0).unwrap();
+                let diff = (val - i_val).modulus();
+                let epsilon = T::Real::epsilon() * <T::Real as NumCast>::from(100.0).unwrap();
                 if diff > epsilon {
                     return false;
                 }
@@ -229,6 +273,36 @@ where
         true
     }
 
+    /// Check if a CSR matrix is Hermitian, `A[i][j] = conj(A[j][i])`
+    ///
+    /// For real matrices this is the same as [`SymCsrMatrix::is_symmetric`].
+    ///
+    /// # Arguments
+    ///
+    /// * `matrix` - CSR matrix to check
+    ///
+    /// # Returns
+    ///
+    /// `true` if the matrix is Hermitian, `false` otherwise
+    pub fn is_hermitian(matrix: &CsrMatrix<T>) -> bool {
+        let (rows, cols) = matrix.shape();
+        if rows != cols {
+            return false;
+        }
+
+        let epsilon = T::Real::epsilon() * <T::Real as NumCast>::from(100.0).unwrap();
+        for i in 0..rows {
+            for j_ptr in matrix.indptr[i]..matrix.indptr[i + 1] {
+                let j = matrix.indices[j_ptr];
+                if (matrix.data[j_ptr] - matrix.get(j, i).conj()).modulus() > epsilon {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
     /// Get the shape of the matrix
     ///
     /// # Returns
@@ -335,6 +409,38 @@ where
         CsrMatrix::new(data, row_indices, col_indices, self.shape)
     }
 
+    /// Convert to standard CSR matrix, treating the stored lower triangular
+    /// part as that of a Hermitian matrix
+    ///
+    /// # Returns
+    ///
+    /// A standard CSR matrix whose upper triangular part holds the conjugates
+    /// of the stored elements
+    pub fn to_hermitian_csr(&self) -> SparseResult<CsrMatrix<T>> {
+        let mut data = Vec::new();
+        let mut row_indices = Vec::new();
+        let mut col_indices = Vec::new();
+
+        for i in 0..self.shape.0 {
+            for j_ptr in self.indptr[i]..self.indptr[i + 1] {
+                let j = self.indices[j_ptr];
+                let val = self.data[j_ptr];
+
+                row_indices.push(i);
+                col_indices.push(j);
+                data.push(val);
+
+                if i != j {
+                    row_indices.push(j);
+                    col_indices.push(i);
+                    data.push(val.conj());
+                }
+            }
+        }
+
+        CsrMatrix::new(data, row_indices, col_indices, self.shape)
+    }
+
     /// Convert to dense matrix
     ///
     /// # Returns
@@ -367,7 +473,7 @@ where
 #[derive(Debug, Clone)]
 pub struct SymCsrArray<T>
 where
-    T: Float + Debug + Copy,
+    T: SparseElement + Debug + Copy,
 {
     /// Inner matrix
     inner: SymCsrMatrix<T>,
@@ -375,7 +481,7 @@ where
 
 impl<T> SymCsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Debug
         + Copy
         + 'static
@@ -5,10 +5,10 @@
 // and other computations that can take advantage of symmetry.
 
 use ndarray::{Array1, ArrayView1};
-use num_traits::Float;
 use std::fmt::Debug;
 use std::ops::{Add, Mul};
 
+use crate::element::SparseElement;
 use crate::error::SparseResult;
 use crate::sym_coo::SymCooMatrix;
 use crate::sym_csr::SymCsrMatrix;
@@ -55,7 +55,7 @@ use crate::sym_csr::SymCsrMatrix;
 /// ```
 pub fn sym_csr_matvec<T>(matrix: &SymCsrMatrix<T>, x: &ArrayView1<T>) -> SparseResult<Array1<T>>
 where
-    T: Float + Debug + Copy + Add<Output = T>,
+    T: SparseElement + Debug + Copy + Add<Output = T>,
 {
     let (n, _) = matrix.shape();
     if x.len() != n {
@@ -73,11 +73,11 @@ where
             let col = matrix.indices[j];
             let val = matrix.data[j];
 
-            y[i] = y[i] + val * x[col];
+            y[i] += val * x[col];
 
             // If not on the diagonal, also update the upper triangular part
             if i != col {
-                y[col] = y[col] + val * x[i];
+                y[col] += val * x[i];
             }
         }
     }
@@ -127,7 +127,7 @@ where
 /// ```
 pub fn sym_coo_matvec<T>(matrix: &SymCooMatrix<T>, x: &ArrayView1<T>) -> SparseResult<Array1<T>>
 where
-    T: Float + Debug + Copy + Add<Output = T>,
+    T: SparseElement + Debug + Copy + Add<Output = T>,
 {
     let (n, _) = matrix.shape();
     if x.len() != n {
@@ -145,11 +145,11 @@ where
         let col = matrix.cols[i];
         let val = matrix.data[i];
 
-        y[row] = y[row] + val * x[col];
+        y[row] += val * x[col];
 
         // If not on the diagonal, also update the upper triangular part
         if row != col {
-            y[col] = y[col] + val * x[row];
+            y[col] += val * x[row];
         }
     }
 
@@ -182,7 +182,7 @@ pub fn sym_csr_rank1_update<T>(
     alpha: T,
 ) -> SparseResult<()>
 where
-    T: Float + Debug + Copy + Add<Output = T> + Mul<Output = T> + std::ops::AddAssign,
+    T: SparseElement + Debug + Copy + Add<Output = T> + Mul<Output = T> + std::ops::AddAssign,
 {
     let (n, _) = matrix.shape();
     if x.len() != n {
@@ -270,7 +270,7 @@ where
 /// ```
 pub fn sym_csr_quadratic_form<T>(matrix: &SymCsrMatrix<T>, x: &ArrayView1<T>) -> SparseResult<T>
 where
-    T: Float + Debug + Copy + Add<Output = T> + Mul<Output = T>,
+    T: SparseElement + Debug + Copy + Add<Output = T> + Mul<Output = T>,
 {
     // First compute A * x
     let ax = sym_csr_matvec(matrix, x)?;
@@ -278,7 +278,7 @@ where
     // Then compute x^T * (A * x)
     let mut result = T::zero();
     for i in 0..ax.len() {
-        result = result + x[i] * ax[i];
+        result += x[i] * ax[i];
     }
 
     Ok(result)
@@ -316,7 +316,7 @@ where
 /// ```
 pub fn sym_csr_trace<T>(matrix: &SymCsrMatrix<T>) -> T
 where
-    T: Float + Debug + Copy + Add<Output = T>,
+    T: SparseElement + Debug + Copy + Add<Output = T>,
 {
     let (n, _) = matrix.shape();
     let mut trace = T::zero();
@@ -326,7 +326,7 @@ where
         for j in matrix.indptr[i]..matrix.indptr[i + 1] {
             let col = matrix.indices[j];
             if col == i {
-                trace = trace + matrix.data[j];
+                trace += matrix.data[j];
                 break;
             }
         }
@@ -335,6 +335,197 @@ where
     trace
 }
 
+/// Computes a matrix-vector product for Hermitian CSR matrices.
+///
+/// This function computes `y = A * x` where `A` is a Hermitian matrix whose
+/// lower triangular part is stored in `matrix` (see
+/// [`SymCsrMatrix::from_hermitian_csr`]). The upper triangular part is taken
+/// as the conjugate of the stored elements. For real matrices this is the
+/// same as [`sym_csr_matvec`].
+///
+/// # Arguments
+///
+/// * `matrix` - The lower triangular part of a Hermitian matrix
+/// * `x` - The input vector
+///
+/// # Returns
+///
+/// The result vector `y = A * x`
+///
+/// # Example
+///
+/// ```
+/// use ndarray::Array1;
+/// use num_complex::Complex64;
+/// use scirs2_sparse::sym_csr::SymCsrMatrix;
+/// use scirs2_sparse::sym_ops::herm_csr_matvec;
+///
+/// // A = [2, -i; i, 3]
+/// let data = vec![
+///     Complex64::new(2.0, 0.0),
+///     Complex64::new(0.0, 1.0),
+///     Complex64::new(3.0, 0.0),
+/// ];
+/// let matrix = SymCsrMatrix::new(data, vec![0, 1, 3], vec![0, 0, 1], (2, 2)).unwrap();
+/// let x = Array1::from_vec(vec![Complex64::new(1.0, 0.0), Complex64::new(1.0, 0.0)]);
+///
+/// let y = herm_csr_matvec(&matrix, &x.view()).unwrap();
+/// assert_eq!(y[0], Complex64::new(2.0, -1.0));
+/// assert_eq!(y[1], Complex64::new(3.0, 1.0));
+/// ```
+pub fn herm_csr_matvec<T>(matrix: &SymCsrMatrix<T>, x: &ArrayView1<T>) -> SparseResult<Array1<T>>
+where
+    T: SparseElement,
+{
+    let (n, _) = matrix.shape();
+    if x.len() != n {
+        return Err(crate::error::SparseError::DimensionMismatch {
+            expected: n,
+            found: x.len(),
+        });
+    }
+
+    let mut y = Array1::zeros(n);
+    for i in 0..n {
+        for j in matrix.indptr[i]..matrix.indptr[i + 1] {
+            let col = matrix.indices[j];
+            let val = matrix.data[j];
+
+            y[i] += val * x[col];
+
+            // The mirrored element of the upper triangular part is conj(val)
+            if i != col {
+                y[col] += val.conj() * x[i];
+            }
+        }
+    }
+
+    Ok(y)
+}
+
+/// Computes a matrix-vector product for Hermitian COO matrices.
+///
+/// This function computes `y = A * x` where `A` is a Hermitian matrix whose
+/// lower triangular part is stored in `matrix`. The upper triangular part is
+/// taken as the conjugate of the stored elements.
+///
+/// # Arguments
+///
+/// * `matrix` - The lower triangular part of a Hermitian matrix in COO format
+/// * `x` - The input vector
+///
+/// # Returns
+///
+/// The result vector `y = A * x`
+pub fn herm_coo_matvec<T>(matrix: &SymCooMatrix<T>, x: &ArrayView1<T>) -> SparseResult<Array1<T>>
+where
+    T: SparseElement,
+{
+    let (n, _) = matrix.shape();
+    if x.len() != n {
+        return Err(crate::error::SparseError::DimensionMismatch {
+            expected: n,
+            found: x.len(),
+        });
+    }
+
+    let mut y = Array1::zeros(n);
+    for ((&row, &col), &val) in matrix.rows.iter().zip(&matrix.cols).zip(&matrix.data) {
+        y[row] += val * x[col];
+        if row != col {
+            y[col] += val.conj() * x[row];
+        }
+    }
+
+    Ok(y)
+}
+
+/// Performs a Hermitian rank-1 update of a Hermitian CSR matrix.
+///
+/// This computes `A = A + alpha * x * x^H`, which keeps `A` Hermitian for a
+/// real `alpha`. Only the lower triangular part is stored and updated.
+///
+/// # Arguments
+///
+/// * `matrix` - The lower triangular part of a Hermitian matrix (modified in-place)
+/// * `x` - The vector to use for the update
+/// * `alpha` - The real scalar multiplier
+///
+/// # Returns
+///
+/// Result with `()` on success
+pub fn herm_csr_rank1_update<T>(
+    matrix: &mut SymCsrMatrix<T>,
+    x: &ArrayView1<T>,
+    alpha: T::Real,
+) -> SparseResult<()>
+where
+    T: SparseElement,
+{
+    let (n, _) = matrix.shape();
+    if x.len() != n {
+        return Err(crate::error::SparseError::DimensionMismatch {
+            expected: n,
+            found: x.len(),
+        });
+    }
+
+    let alpha = T::from_real(alpha);
+    let mut dense = matrix.to_dense();
+    for i in 0..n {
+        for j in 0..=i {
+            dense[i][j] += alpha * x[i] * x[j].conj();
+        }
+    }
+
+    let mut data = Vec::new();
+    let mut indices = Vec::new();
+    let mut indptr = vec![0];
+    for (i, row) in dense.iter().enumerate() {
+        for (j, &val) in row.iter().enumerate().take(i + 1) {
+            if val != T::zero() {
+                data.push(val);
+                indices.push(j);
+            }
+        }
+        indptr.push(data.len());
+    }
+
+    matrix.data = data;
+    matrix.indices = indices;
+    matrix.indptr = indptr;
+
+    Ok(())
+}
+
+/// Calculates the quadratic form `x^H * A * x` for a Hermitian matrix `A`.
+///
+/// The value of a Hermitian form is real, so only the real part is returned.
+///
+/// # Arguments
+///
+/// * `matrix` - The lower triangular part of a Hermitian matrix
+/// * `x` - The vector
+///
+/// # Returns
+///
+/// The real scalar `x^H * A * x`
+pub fn herm_csr_quadratic_form<T>(
+    matrix: &SymCsrMatrix<T>,
+    x: &ArrayView1<T>,
+) -> SparseResult<T::Real>
+where
+    T: SparseElement,
+{
+    let ax = herm_csr_matvec(matrix, x)?;
+    let result: T = x
+        .iter()
+        .zip(ax.iter())
+        .map(|(&xi, &yi)| xi.conj() * yi)
+        .sum();
+    Ok(result.real())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -343,6 +534,7 @@ mod tests {
     use crate::AsLinearOperator; // For the test_compare_with_standard_matvec test
     use approx::assert_relative_eq;
     use ndarray::Array1;
+    use num_complex::Complex64;
 
     // Create a simple symmetric matrix for testing
     fn create_test_sym_csr() -> SymCsrMatrix<f64> {
@@ -471,4 +663,80 @@ mod tests {
             assert_relative_eq!(y_optimized[i], y_standard[i]);
         }
     }
+
+    // Lower triangular part of the Hermitian matrix
+    // [2      1-i   0  ]
+    // [1+i    3    -2i ]
+    // [0      2i    1  ]
+    fn create_test_herm_csr() -> SymCsrMatrix<Complex64> {
+        let data = vec![
+            Complex64::new(2.0, 0.0),
+            Complex64::new(1.0, 1.0),
+            Complex64::new(3.0, 0.0),
+            Complex64::new(0.0, 2.0),
+            Complex64::new(1.0, 0.0),
+        ];
+        SymCsrMatrix::new(data, vec![0, 1, 3, 5], vec![0, 0, 1, 1, 2], (3, 3)).unwrap()
+    }
+
+    #[test]
+    fn test_herm_matvec_matches_full_matrix() {
+        let matrix = create_test_herm_csr();
+        let full = matrix.to_hermitian_csr().unwrap();
+        assert!(full.is_hermitian());
+        assert!(!full.is_symmetric());
+        assert_eq!(full.get(0, 1), Complex64::new(1.0, -1.0));
+
+        let x = Array1::from_vec(vec![
+            Complex64::new(1.0, 2.0),
+            Complex64::new(-1.0, 0.5),
+            Complex64::new(0.0, -3.0),
+        ]);
+        let y = herm_csr_matvec(&matrix, &x.view()).unwrap();
+        let expected = full
+            .as_linear_operator()
+            .matvec(x.as_slice().unwrap())
+            .unwrap();
+        for (a, b) in y.iter().zip(&expected) {
+            assert!((a - b).norm() < 1e-14);
+        }
+
+        // Round trip through the Hermitian constructor and COO storage
+        let sym = SymCsrMatrix::from_hermitian_csr(&full).unwrap();
+        assert_eq!(sym.data, matrix.data);
+        assert!(SymCsrMatrix::from_csr(&full).is_err());
+        let coo = SymCooMatrix::new(
+            matrix.data.clone(),
+            vec![0, 1, 1, 2, 2],
+            matrix.indices.clone(),
+            (3, 3),
+        )
+        .unwrap();
+        let y_coo = herm_coo_matvec(&coo, &x.view()).unwrap();
+        for (a, b) in y.iter().zip(&y_coo) {
+            assert!((a - b).norm() < 1e-14);
+        }
+
+        // x^H A x is real and equals the inner product with A x
+        let form = herm_csr_quadratic_form(&matrix, &x.view()).unwrap();
+        let direct: Complex64 = x.iter().zip(&y).map(|(xi, yi)| xi.conj() * yi).sum();
+        assert_relative_eq!(form, direct.re, epsilon = 1e-12);
+        assert!(direct.im.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_herm_csr_rank1_update() {
+        let mut matrix = create_test_herm_csr();
+        let x = Array1::from_vec(vec![
+            Complex64::new(0.0, 1.0),
+            Complex64::new(1.0, 0.0),
+            Complex64::new(0.0, 0.0),
+        ]);
+        herm_csr_rank1_update(&mut matrix, &x.view(), 2.0).unwrap();
+        // (1,0) += 2 * x1 * conj(x0) = 2 * 1 * (-i)
+        assert_eq!(matrix.get(1, 0), Complex64::new(1.0, -1.0));
+        assert_eq!(matrix.get(0, 0), Complex64::new(4.0, 0.0));
+        assert_eq!(matrix.get(1, 1), Complex64::new(5.0, 0.0));
+        assert!(matrix.to_hermitian_csr().unwrap().is_hermitian());
+    }
 }
@@ -6,11 +6,11 @@
 
 use crate::coo_array::CooArray;
 use crate::csr_array::CsrArray;
+use crate::element::{element_nan, SparseElement};
 use crate::error::{SparseError, SparseResult};
 use crate::sparray::{SparseArray, SparseSum};
 use crate::sym_coo::SymCooArray;
 use crate::sym_csr::SymCsrArray;
-use num_traits::Float;
 use std::fmt::Debug;
 use std::ops::{Add, Div, Mul, Sub};
 
@@ -21,7 +21,7 @@ use std::ops::{Add, Div, Mul, Sub};
 /// is kept symmetric throughout all operations.
 pub trait SymSparseArray<T>: SparseArray<T>
 where
-    T: Float + Debug + Copy + 'static,
+    T: SparseElement + Debug + Copy + 'static,
 {
     /// Get the number of stored non-zero elements
     ///
@@ -77,7 +77,7 @@ where
 /// Implementation of SymSparseArray for SymCsrArray
 impl<T> SymSparseArray<T> for SymCsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Debug
         + Copy
         + 'static
@@ -162,7 +162,7 @@ where
 /// Implementation of SymSparseArray for SymCooArray
 impl<T> SymSparseArray<T> for SymCooArray<T>
 where
-    T: Float
+    T: SparseElement
         + Debug
         + Copy
         + 'static
@@ -248,7 +248,7 @@ where
 /// Implementation of SparseArray for SymCsrArray
 impl<T> SparseArray<T> for SymCsrArray<T>
 where
-    T: Float
+    T: SparseElement
         + Debug
         + Copy
         + 'static
@@ -276,8 +276,13 @@ where
     }
 
     fn dtype(&self) -> &str {
-        if std::any::TypeId::of::<T>() == std::any::TypeId::of::<f32>() {
+        let id = std::any::TypeId::of::<T>();
+        if id == std::any::TypeId::of::<f32>() {
             "f32"
+        } else if id == std::any::TypeId::of::<num_complex::Complex<f32>>() {
+            "complex64"
+        } else if T::IS_COMPLEX {
+            "complex128"
         } else {
             "f64"
         }
@@ -363,7 +368,7 @@ where
         // Convert to CSR and find the maximum value
         match <Self as SymSparseArray<T>>::to_csr(self) {
             Ok(csr) => SparseArray::<T>::max(&csr),
-            Err(_) => T::nan(), // Return NaN if conversion fails
+            Err(_) => element_nan(), // Return NaN if conversion fails
         }
     }
 
@@ -371,7 +376,7 @@ where
         // Convert to CSR and find the minimum value
         match <Self as SymSparseArray<T>>::to_csr(self) {
             Ok(csr) => SparseArray::<T>::min(&csr),
-            Err(_) => T::nan(), // Return NaN if conversion fails
+            Err(_) => element_nan(), // Return NaN if conversion fails
         }
     }
 
@@ -619,7 +624,7 @@ where
 /// Implementation of SparseArray for SymCooArray
 impl<T> SparseArray<T> for SymCooArray<T>
 where
-    T: Float
+    T: SparseElement
         + Debug
         + Copy
         + 'static
@@ -647,8 +652,13 @@ where
     }
 
     fn dtype(&self) -> &str {
-        if std::any::TypeId::of::<T>() == std::any::TypeId::of::<f32>() {
+        let id = std::any::TypeId::of::<T>();
+        if id == std::any::TypeId::of::<f32>() {
             "f32"
+        } else if id == std::any::TypeId::of::<num_complex::Complex<f32>>() {
+            "complex64"
+        } else if T::IS_COMPLEX {
+            "complex128"
         } else {
             "f64"
         }
@@ -735,7 +745,7 @@ where
         // Convert to CSR and find the maximum value
         match <Self as SymSparseArray<T>>::to_csr(self) {
             Ok(csr) => SparseArray::<T>::max(&csr),
-            Err(_) => T::nan(), // Return NaN if conversion fails
+            Err(_) => element_nan(), // Return NaN if conversion fails
         }
     }
 
@@ -743,7 +753,7 @@ where
         // Convert to CSR and find the minimum value
         match <Self as SymSparseArray<T>>::to_csr(self) {
             Ok(csr) => SparseArray::<T>::min(&csr),
-            Err(_) => T::nan(), // Return NaN if conversion fails
+            Err(_) => element_nan(), // Return NaN if conversion fails
         }
     }
 